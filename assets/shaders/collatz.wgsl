@@ -0,0 +1,30 @@
+struct Steps {
+    values: array<u32>;
+};
+
+[[group(0), binding(0)]]
+var<storage, read_write> steps: Steps;
+
+[[stage(compute), workgroup_size(64, 1, 1)]]
+fn main([[builtin(global_invocation_id)]] invocation_id: vec3<u32>) {
+    let index = invocation_id.x;
+    if (index >= arrayLength(&steps.values)) {
+        return;
+    }
+
+    // Count the steps of the Collatz sequence starting at `index + 1` until it reaches 1
+    var n = index + 1u;
+    var count = 0u;
+    loop {
+        if (n <= 1u) {
+            break;
+        }
+        if (n % 2u == 0u) {
+            n = n / 2u;
+        } else {
+            n = 3u * n + 1u;
+        }
+        count = count + 1u;
+    }
+    steps.values[index] = count;
+}
@@ -12,7 +12,9 @@ var<uniform> material: CustomMaterial;
 
 struct Vertex {
     [[location(0)]] position: vec3<f32>;
-    [[location(1)]] blend_color: vec4<f32>;
+#ifdef VERTEX_BLEND_COLOR
+    [[location(7)]] blend_color: vec4<f32>;
+#endif
 };
 
 struct VertexOutput {
@@ -24,7 +26,11 @@ struct VertexOutput {
 fn vertex(vertex: Vertex) -> VertexOutput {
     var out: VertexOutput;
     out.clip_position = mesh_position_local_to_clip(mesh.model, vec4<f32>(vertex.position, 1.0));
+#ifdef VERTEX_BLEND_COLOR
     out.blend_color = vertex.blend_color;
+#else
+    out.blend_color = vec4<f32>(1.0);
+#endif
     return out;
 }
 
@@ -0,0 +1,110 @@
+use bevy_ecs::{
+    entity::Entity,
+    schedule::{Stage, SystemStage},
+    world::World,
+};
+use bevy_hierarchy::BuildWorldChildren;
+use bevy_transform::{components::Transform, transform_propagate_system, TransformBundle};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+criterion_group!(
+    benches,
+    propagate_unchanged,
+    propagate_one_leaf_changed,
+    propagate_all_roots_changed,
+);
+criterion_main!(benches);
+
+/// The depth of the benched hierarchies and the number of children of each of their entities,
+/// from wide hierarchies to deep chains like nested UI nodes
+const HIERARCHIES: [(u32, usize); 3] = [(4, 4), (8, 2), (16, 1)];
+const ROOTS: usize = 64;
+
+/// A world with [`ROOTS`] hierarchies of `depth` levels where each entity has `children`
+/// children, returning the roots and the leaves
+fn setup(depth: u32, children: usize) -> (World, Vec<Entity>, Vec<Entity>) {
+    let mut world = World::default();
+    let mut roots = Vec::new();
+    let mut leaves = Vec::new();
+    for _ in 0..ROOTS {
+        let root = world
+            .spawn()
+            .insert_bundle(TransformBundle::from(Transform::from_xyz(1.0, 0.0, 0.0)))
+            .id();
+        let mut level = vec![root];
+        for _ in 0..depth {
+            let mut next_level = Vec::new();
+            for parent in level {
+                world.entity_mut(parent).with_children(|parent| {
+                    for _ in 0..children {
+                        next_level.push(
+                            parent
+                                .spawn_bundle(TransformBundle::from(Transform::from_xyz(
+                                    0.0, 1.0, 0.0,
+                                )))
+                                .id(),
+                        );
+                    }
+                });
+            }
+            level = next_level;
+        }
+        roots.push(root);
+        leaves.extend(level);
+    }
+    (world, roots, leaves)
+}
+
+fn bench_propagation(
+    criterion: &mut Criterion,
+    name: &str,
+    mut change: impl FnMut(&mut World, &[Entity], &[Entity]),
+) {
+    let mut group = criterion.benchmark_group(name);
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+    for (depth, children) in HIERARCHIES {
+        let (mut world, roots, leaves) = setup(depth, children);
+        let mut stage = SystemStage::parallel();
+        stage.add_system(transform_propagate_system);
+        // run once to propagate the spawned hierarchies
+        stage.run(&mut world);
+
+        group.bench_function(
+            format!("{}_entities_depth_{}", world.entities().len(), depth),
+            |bencher| {
+                bencher.iter(|| {
+                    change(&mut world, &roots, &leaves);
+                    stage.run(&mut world);
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn propagate_unchanged(criterion: &mut Criterion) {
+    bench_propagation(criterion, "propagate_unchanged", |_, _, _| {});
+}
+
+fn propagate_one_leaf_changed(criterion: &mut Criterion) {
+    bench_propagation(
+        criterion,
+        "propagate_one_leaf_changed",
+        |world, _, leaves| {
+            world.get_mut::<Transform>(leaves[0]).unwrap().translation.x += 1.0;
+        },
+    );
+}
+
+fn propagate_all_roots_changed(criterion: &mut Criterion) {
+    bench_propagation(
+        criterion,
+        "propagate_all_roots_changed",
+        |world, roots, _| {
+            for root in roots {
+                world.get_mut::<Transform>(*root).unwrap().translation.x += 1.0;
+            }
+        },
+    );
+}
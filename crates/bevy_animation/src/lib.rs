@@ -2,7 +2,11 @@
 
 #![warn(missing_docs)]
 
-use std::ops::Deref;
+mod property;
+
+pub use property::*;
+
+use std::{ops::Deref, time::Duration};
 
 use bevy_app::{App, CoreStage, Plugin};
 use bevy_asset::{AddAsset, Assets, Handle};
@@ -12,12 +16,12 @@ use bevy_ecs::{
     entity::Entity,
     prelude::Component,
     reflect::ReflectComponent,
-    schedule::ParallelSystemDescriptorCoercion,
+    schedule::{ParallelSystemDescriptorCoercion, SystemLabel},
     system::{Query, Res},
 };
 use bevy_hierarchy::{Children, HierarchySystem};
 use bevy_math::{Quat, Vec3};
-use bevy_reflect::{Reflect, TypeUuid};
+use bevy_reflect::{FromReflect, Reflect, TypeUuid};
 use bevy_time::Time;
 use bevy_transform::{prelude::Transform, TransformSystem};
 use bevy_utils::{tracing::warn, HashMap};
@@ -26,7 +30,8 @@ use bevy_utils::{tracing::warn, HashMap};
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        AnimationClip, AnimationPlayer, AnimationPlugin, EntityPath, Keyframes, VariableCurve,
+        AddAnimatedComponent, AnimationClip, AnimationPlayer, AnimationPlugin, EntityPath,
+        Keyframes, PropertyKeyframes, VariableCurve,
     };
 }
 
@@ -39,6 +44,11 @@ pub enum Keyframes {
     Translation(Vec<Vec3>),
     /// Keyframes for scale.
     Scale(Vec<Vec3>),
+    /// Keyframes for a field of any component, accessed by reflection.
+    ///
+    /// The component must be registered with
+    /// [`add_animated_component`](AddAnimatedComponent::add_animated_component).
+    Property(PropertyKeyframes),
 }
 
 /// Describes how an attribute of a [`Transform`] should be animated.
@@ -52,6 +62,43 @@ pub struct VariableCurve {
     pub keyframes: Keyframes,
 }
 
+/// Where a time falls in the keyframes of a [`VariableCurve`]
+#[derive(Clone, Copy)]
+pub(crate) enum KeyframeStep {
+    /// The curve only has one keyframe, used to set a value
+    Single,
+    /// The time is between the keyframe at index `start` and the next one, `lerp` being the
+    /// fraction of the way to the next one
+    Between { start: usize, lerp: f32 },
+}
+
+impl VariableCurve {
+    /// Find the keyframes to interpolate at the time `elapsed`, `None` if the curve isn't started
+    /// yet or is finished
+    pub(crate) fn step_at(&self, elapsed: f32) -> Option<KeyframeStep> {
+        // Some curves have only one keyframe used to set a transform
+        if self.keyframe_timestamps.len() == 1 {
+            return Some(KeyframeStep::Single);
+        }
+
+        // Find the current keyframe
+        // PERF: finding the current keyframe can be optimised
+        let start = match self
+            .keyframe_timestamps
+            .binary_search_by(|probe| probe.partial_cmp(&elapsed).unwrap())
+        {
+            Ok(i) => i,
+            Err(0) => return None, // this curve isn't started yet
+            Err(n) if n > self.keyframe_timestamps.len() - 1 => return None, // this curve is finished
+            Err(i) => i - 1,
+        };
+        let ts_start = self.keyframe_timestamps[start];
+        let ts_end = self.keyframe_timestamps[start + 1];
+        let lerp = (elapsed - ts_start) / (ts_end - ts_start);
+        Some(KeyframeStep::Between { start, lerp })
+    }
+}
+
 /// Path to an entity, with [`Name`]s. Each entity in a path must have a name.
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Default)]
 pub struct EntityPath {
@@ -90,25 +137,118 @@ impl AnimationClip {
     }
 }
 
+/// An animation playing in an [`AnimationPlayer`], blended with the others by its weight
+#[derive(Clone, Reflect, FromReflect)]
+pub(crate) struct PlayingAnimation {
+    repeat: bool,
+    elapsed: f32,
+    pub(crate) weight: f32,
+    /// The weight the animation is fading to
+    target_weight: f32,
+    /// How much the weight changes per second while fading, `0.0` if its weight was
+    /// set directly
+    fade_speed: f32,
+    animation_clip: Handle<AnimationClip>,
+}
+
+impl PlayingAnimation {
+    fn new(animation_clip: Handle<AnimationClip>, weight: f32) -> Self {
+        Self {
+            repeat: false,
+            elapsed: 0.0,
+            weight,
+            target_weight: weight,
+            fade_speed: 0.0,
+            animation_clip,
+        }
+    }
+
+    fn set_weight(&mut self, weight: f32) {
+        self.weight = weight;
+        self.target_weight = weight;
+        self.fade_speed = 0.0;
+    }
+
+    /// Fade the weight of the animation to `target_weight` over `duration`
+    fn fade_to(&mut self, target_weight: f32, duration: Duration) {
+        let duration = duration.as_secs_f32();
+        let change = (target_weight - self.weight).abs();
+        if duration > 0.0 && change > 0.0 {
+            self.fade_speed = change / duration;
+        } else {
+            self.weight = target_weight;
+            // Still mark the animation as fading, so that it's removed if faded out
+            self.fade_speed = f32::INFINITY;
+        }
+        self.target_weight = target_weight;
+    }
+
+    fn update_weight(&mut self, delta_seconds: f32) {
+        let remaining = self.target_weight - self.weight;
+        if remaining == 0.0 {
+            return;
+        }
+        let step = self.fade_speed * delta_seconds;
+        if remaining.abs() <= step {
+            self.weight = self.target_weight;
+        } else {
+            self.weight += step.copysign(remaining);
+        }
+    }
+
+    /// Time in the clip of the animation, wrapped in its duration if it repeats
+    pub(crate) fn clip_time(&self, animation_clip: &AnimationClip) -> f32 {
+        let mut elapsed = self.elapsed;
+        if self.repeat {
+            elapsed %= animation_clip.duration;
+        }
+        if elapsed < 0.0 {
+            elapsed += animation_clip.duration;
+        }
+        elapsed
+    }
+
+    /// Is the animation done fading out, and can be removed from the player
+    fn is_faded_out(&self) -> bool {
+        self.fade_speed > 0.0 && self.weight <= 0.0 && self.target_weight <= 0.0
+    }
+}
+
 /// Animation controls
+///
+/// The player can play several [`AnimationClip`]s at once, blending the transforms they animate
+/// by the weight of each clip. The last clip started with [`play`](Self::play),
+/// [`play_blended`](Self::play_blended) or [`cross_fade`](Self::cross_fade) is the current
+/// animation, which [`repeat`](Self::repeat) and [`elapsed`](Self::elapsed) apply to.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy_animation::{AnimationClip, AnimationPlayer};
+/// # use bevy_asset::Handle;
+/// # fn blend(player: &mut AnimationPlayer, walk: Handle<AnimationClip>, run: Handle<AnimationClip>, idle: Handle<AnimationClip>, speed: f32) {
+/// // Blend walking and running depending on the speed of the character
+/// let run_weight = (speed - 1.0).clamp(0.0, 1.0);
+/// player.set_weight(&walk, 1.0 - run_weight);
+/// player.set_weight(&run, run_weight);
+///
+/// // Smoothly transition to the idle animation in a quarter of a second
+/// player.cross_fade(idle, Duration::from_secs_f32(0.25)).repeat();
+/// # }
+/// ```
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct AnimationPlayer {
     paused: bool,
-    repeat: bool,
     speed: f32,
-    elapsed: f32,
-    animation_clip: Handle<AnimationClip>,
+    animations: Vec<PlayingAnimation>,
 }
 
 impl Default for AnimationPlayer {
     fn default() -> Self {
         Self {
             paused: false,
-            repeat: false,
             speed: 1.0,
-            elapsed: 0.0,
-            animation_clip: Default::default(),
+            animations: Vec::new(),
         }
     }
 }
@@ -117,21 +257,117 @@ impl AnimationPlayer {
     /// Start playing an animation, resetting state of the player
     pub fn play(&mut self, handle: Handle<AnimationClip>) -> &mut Self {
         *self = Self {
-            animation_clip: handle,
+            animations: vec![PlayingAnimation::new(handle, 1.0)],
             ..Default::default()
         };
         self
     }
 
-    /// Set the animation to repeat
+    /// Start playing an animation alongside the ones already playing, blended with them by its
+    /// `weight`
+    ///
+    /// If the animation is already playing, only its weight is updated.
+    pub fn play_blended(&mut self, handle: Handle<AnimationClip>, weight: f32) -> &mut Self {
+        match self.index_of(&handle) {
+            Some(index) => {
+                let mut animation = self.animations.remove(index);
+                animation.set_weight(weight);
+                self.animations.push(animation);
+            }
+            None => self.animations.push(PlayingAnimation::new(handle, weight)),
+        }
+        self
+    }
+
+    /// Smoothly transition to an animation, fading out all the others over `duration`
+    ///
+    /// If the animation is already playing, it keeps playing from where it is while its weight
+    /// fades in, otherwise it starts from the beginning. The animations that faded out are
+    /// removed from the player.
+    pub fn cross_fade(&mut self, handle: Handle<AnimationClip>, duration: Duration) -> &mut Self {
+        let mut animation = match self.index_of(&handle) {
+            Some(index) => self.animations.remove(index),
+            None => PlayingAnimation::new(handle, 0.0),
+        };
+        for other in &mut self.animations {
+            other.fade_to(0.0, duration);
+        }
+        self.animations
+            .retain(|animation| !animation.is_faded_out());
+        animation.fade_to(1.0, duration);
+        self.animations.push(animation);
+        self
+    }
+
+    /// Weight of an animation, `None` if it isn't playing
+    pub fn weight(&self, handle: &Handle<AnimationClip>) -> Option<f32> {
+        self.index_of(handle)
+            .map(|index| self.animations[index].weight)
+    }
+
+    /// Set the weight of an animation that is playing, stopping it from fading
+    pub fn set_weight(&mut self, handle: &Handle<AnimationClip>, weight: f32) -> &mut Self {
+        if let Some(index) = self.index_of(handle) {
+            self.animations[index].set_weight(weight);
+        }
+        self
+    }
+
+    /// Is the player fading any animation in or out
+    pub fn is_fading(&self) -> bool {
+        self.animations
+            .iter()
+            .any(|animation| animation.weight != animation.target_weight)
+    }
+
+    /// Stop playing an animation
+    pub fn stop(&mut self, handle: &Handle<AnimationClip>) -> &mut Self {
+        if let Some(index) = self.index_of(handle) {
+            self.animations.remove(index);
+        }
+        self
+    }
+
+    /// The animations with a positive weight whose clip is loaded
+    pub(crate) fn playing_clips<'a>(
+        &'a self,
+        animations: &'a Assets<AnimationClip>,
+    ) -> impl Iterator<Item = (&'a PlayingAnimation, &'a AnimationClip)> {
+        self.animations
+            .iter()
+            .filter(|animation| animation.weight > 0.0)
+            .filter_map(|animation| {
+                animations
+                    .get(&animation.animation_clip)
+                    .map(|animation_clip| (animation, animation_clip))
+            })
+    }
+
+    fn index_of(&self, handle: &Handle<AnimationClip>) -> Option<usize> {
+        self.animations
+            .iter()
+            .position(|animation| animation.animation_clip.id == handle.id)
+    }
+
+    /// Set the current animation to repeat
+    ///
+    /// Only the last animation started applies, the other animations that are playing keep
+    /// repeating or not.
     pub fn repeat(&mut self) -> &mut Self {
-        self.repeat = true;
+        if let Some(animation) = self.animations.last_mut() {
+            animation.repeat = true;
+        }
         self
     }
 
-    /// Stop the animation from repeating
+    /// Stop the current animation from repeating
+    ///
+    /// Only the last animation started applies, the other animations that are playing keep
+    /// repeating or not.
     pub fn stop_repeating(&mut self) -> &mut Self {
-        self.repeat = false;
+        if let Some(animation) = self.animations.last_mut() {
+            animation.repeat = false;
+        }
         self
     }
 
@@ -161,20 +397,50 @@ impl AnimationPlayer {
         self
     }
 
-    /// Time elapsed playing the animation
+    /// Time elapsed playing the current animation
+    ///
+    /// Each animation that is playing has its own time, from when it started. This is the time of
+    /// the last animation started.
     pub fn elapsed(&self) -> f32 {
-        self.elapsed
+        self.animations
+            .last()
+            .map_or(0.0, |animation| animation.elapsed)
     }
 
-    /// Seek to a specific time in the animation
+    /// Seek to a specific time in the current animation
+    ///
+    /// Only the last animation started applies, the other animations that are playing keep
+    /// their time.
     pub fn set_elapsed(&mut self, elapsed: f32) -> &mut Self {
-        self.elapsed = elapsed;
+        if let Some(animation) = self.animations.last_mut() {
+            animation.elapsed = elapsed;
+        }
         self
     }
 }
 
+/// The sum of the weights of the animations applied to each part of a [`Transform`]
+#[derive(Default)]
+struct BlendWeights {
+    translation: f32,
+    rotation: f32,
+    scale: f32,
+}
+
+/// Add `weight` to the total weight of a part of a [`Transform`], and return how much it should
+/// be interpolated from its current value to the new one. The first animation applied to a part
+/// returns `1.0`, replacing its value.
+pub(crate) fn blend_factor(total_weight: &mut f32, weight: f32) -> f32 {
+    *total_weight += weight;
+    weight / *total_weight
+}
+
 /// System that will play all animations, using any entity with a [`AnimationPlayer`]
 /// and a [`Handle<AnimationClip>`] as an animation root
+///
+/// When several animations are playing, the transforms are the weighted averages of their
+/// keyframes. The parts of a transform that aren't animated by any playing clip are left as is.
+/// [`Keyframes::Property`] curves are applied by [`animate_component`].
 pub fn animation_player(
     time: Res<Time>,
     animations: Res<Assets<AnimationClip>>,
@@ -183,100 +449,98 @@ pub fn animation_player(
     mut transforms: Query<&mut Transform>,
     children: Query<&Children>,
 ) {
+    let mut blend_weights = HashMap::<Entity, BlendWeights>::default();
     for (entity, mut player) in animation_players.iter_mut() {
-        if let Some(animation_clip) = animations.get(&player.animation_clip) {
-            // Continue if paused unless the `AnimationPlayer` was changed
-            // This allow the animation to still be updated if the player.elapsed field was manually updated in pause
-            if player.paused && !player.is_changed() {
-                continue;
-            }
-            if !player.paused {
-                player.elapsed += time.delta_seconds() * player.speed;
-            }
-            let mut elapsed = player.elapsed;
-            if player.repeat {
-                elapsed %= animation_clip.duration;
-            }
-            if elapsed < 0.0 {
-                elapsed += animation_clip.duration;
+        // Continue if paused unless the `AnimationPlayer` was changed
+        // This allow the animation to still be updated if the player.elapsed field was manually updated in pause
+        if player.paused && !player.is_changed() {
+            continue;
+        }
+        if !player.paused {
+            let player = &mut *player;
+            let delta_seconds = time.delta_seconds();
+            for animation in &mut player.animations {
+                animation.elapsed += delta_seconds * player.speed;
+                animation.update_weight(delta_seconds);
             }
-            'entity: for (path, curves) in &animation_clip.curves {
-                // PERF: finding the target entity can be optimised
-                let mut current_entity = entity;
-                // Ignore the first name, it is the root node which we already have
-                for part in path.parts.iter().skip(1) {
-                    let mut found = false;
-                    if let Ok(children) = children.get(current_entity) {
-                        for child in children.deref() {
-                            if let Ok(name) = names.get(*child) {
-                                if name == part {
-                                    // Found a children with the right name, continue to the next part
-                                    current_entity = *child;
-                                    found = true;
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    if !found {
-                        warn!("Entity not found for path {:?} on part {:?}", path, part);
-                        continue 'entity;
-                    }
-                }
-                if let Ok(mut transform) = transforms.get_mut(current_entity) {
-                    for curve in curves {
-                        // Some curves have only one keyframe used to set a transform
-                        if curve.keyframe_timestamps.len() == 1 {
-                            match &curve.keyframes {
-                                Keyframes::Rotation(keyframes) => transform.rotation = keyframes[0],
-                                Keyframes::Translation(keyframes) => {
-                                    transform.translation = keyframes[0];
-                                }
-                                Keyframes::Scale(keyframes) => transform.scale = keyframes[0],
-                            }
-                            continue;
-                        }
+            player
+                .animations
+                .retain(|animation| !animation.is_faded_out());
+        }
 
-                        // Find the current keyframe
-                        // PERF: finding the current keyframe can be optimised
-                        let step_start = match curve
-                            .keyframe_timestamps
-                            .binary_search_by(|probe| probe.partial_cmp(&elapsed).unwrap())
-                        {
-                            Ok(i) => i,
-                            Err(0) => continue, // this curve isn't started yet
-                            Err(n) if n > curve.keyframe_timestamps.len() - 1 => continue, // this curve is finished
-                            Err(i) => i - 1,
+        blend_weights.clear();
+        for (animation, animation_clip) in player.playing_clips(&animations) {
+            let elapsed = animation.clip_time(animation_clip);
+            for (path, curves) in &animation_clip.curves {
+                let target = match find_entity(entity, path, &children, &names) {
+                    Some(target) => target,
+                    None => continue,
+                };
+                if let Ok(mut transform) = transforms.get_mut(target) {
+                    let weights = blend_weights.entry(target).or_default();
+                    for curve in curves {
+                        let step = match curve.step_at(elapsed) {
+                            Some(step) => step,
+                            None => continue,
                         };
-                        let ts_start = curve.keyframe_timestamps[step_start];
-                        let ts_end = curve.keyframe_timestamps[step_start + 1];
-                        let lerp = (elapsed - ts_start) / (ts_end - ts_start);
 
-                        // Apply the keyframe
+                        // Apply the keyframe, blended with the other animations
                         match &curve.keyframes {
                             Keyframes::Rotation(keyframes) => {
-                                let rot_start = keyframes[step_start];
-                                let mut rot_end = keyframes[step_start + 1];
-                                // Choose the smallest angle for the rotation
-                                if rot_end.dot(rot_start) < 0.0 {
-                                    rot_end = -rot_end;
-                                }
-                                // Rotations are using a spherical linear interpolation
-                                transform.rotation =
-                                    rot_start.normalize().slerp(rot_end.normalize(), lerp);
+                                let rotation = match step {
+                                    KeyframeStep::Single => keyframes[0],
+                                    KeyframeStep::Between { start, lerp } => {
+                                        let rot_start = keyframes[start];
+                                        let mut rot_end = keyframes[start + 1];
+                                        // Choose the smallest angle for the rotation
+                                        if rot_end.dot(rot_start) < 0.0 {
+                                            rot_end = -rot_end;
+                                        }
+                                        // Rotations are using a spherical linear interpolation
+                                        rot_start.normalize().slerp(rot_end.normalize(), lerp)
+                                    }
+                                };
+                                let blend = blend_factor(&mut weights.rotation, animation.weight);
+                                transform.rotation = if blend < 1.0 {
+                                    transform.rotation.slerp(rotation, blend)
+                                } else {
+                                    rotation
+                                };
                             }
                             Keyframes::Translation(keyframes) => {
-                                let translation_start = keyframes[step_start];
-                                let translation_end = keyframes[step_start + 1];
-                                let result = translation_start.lerp(translation_end, lerp);
-                                transform.translation = result;
+                                let translation = match step {
+                                    KeyframeStep::Single => keyframes[0],
+                                    KeyframeStep::Between { start, lerp } => {
+                                        let translation_start = keyframes[start];
+                                        let translation_end = keyframes[start + 1];
+                                        translation_start.lerp(translation_end, lerp)
+                                    }
+                                };
+                                let blend =
+                                    blend_factor(&mut weights.translation, animation.weight);
+                                transform.translation = if blend < 1.0 {
+                                    transform.translation.lerp(translation, blend)
+                                } else {
+                                    translation
+                                };
                             }
                             Keyframes::Scale(keyframes) => {
-                                let scale_start = keyframes[step_start];
-                                let scale_end = keyframes[step_start + 1];
-                                let result = scale_start.lerp(scale_end, lerp);
-                                transform.scale = result;
+                                let scale = match step {
+                                    KeyframeStep::Single => keyframes[0],
+                                    KeyframeStep::Between { start, lerp } => {
+                                        let scale_start = keyframes[start];
+                                        let scale_end = keyframes[start + 1];
+                                        scale_start.lerp(scale_end, lerp)
+                                    }
+                                };
+                                let blend = blend_factor(&mut weights.scale, animation.weight);
+                                transform.scale = if blend < 1.0 {
+                                    transform.scale.lerp(scale, blend)
+                                } else {
+                                    scale
+                                };
                             }
+                            Keyframes::Property(_) => {}
                         }
                     }
                 }
@@ -285,6 +549,45 @@ pub fn animation_player(
     }
 }
 
+/// Find the entity targeted by a path, starting from the root entity of the animation
+pub(crate) fn find_entity(
+    root: Entity,
+    path: &EntityPath,
+    children: &Query<&Children>,
+    names: &Query<&Name>,
+) -> Option<Entity> {
+    // PERF: finding the target entity can be optimised
+    let mut current_entity = root;
+    // Ignore the first name, it is the root node which we already have
+    for part in path.parts.iter().skip(1) {
+        let mut found = false;
+        if let Ok(children) = children.get(current_entity) {
+            for child in children.deref() {
+                if let Ok(name) = names.get(*child) {
+                    if name == part {
+                        // Found a children with the right name, continue to the next part
+                        current_entity = *child;
+                        found = true;
+                        break;
+                    }
+                }
+            }
+        }
+        if !found {
+            warn!("Entity not found for path {:?} on part {:?}", path, part);
+            return None;
+        }
+    }
+    Some(current_entity)
+}
+
+/// Label for the systems of the animation plugin
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub enum AnimationSystem {
+    /// Updates the [`AnimationPlayer`]s and animates the [`Transform`]s
+    AnimationPlayer,
+}
+
 /// Adds animation support to an app
 #[derive(Default)]
 pub struct AnimationPlugin {}
@@ -296,8 +599,130 @@ impl Plugin for AnimationPlugin {
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 animation_player
+                    .label(AnimationSystem::AnimationPlayer)
                     .before(TransformSystem::TransformPropagate)
                     .after(HierarchySystem::ParentUpdate),
             );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::HandleId;
+
+    fn clip() -> Handle<AnimationClip> {
+        Handle::weak(HandleId::random::<AnimationClip>())
+    }
+
+    #[test]
+    fn weight_fades_at_constant_speed() {
+        let mut animation = PlayingAnimation::new(clip(), 1.0);
+        animation.fade_to(0.0, Duration::from_secs(2));
+        assert!(!animation.is_faded_out());
+
+        animation.update_weight(0.5);
+        assert_eq!(animation.weight, 0.75);
+        animation.update_weight(1.0);
+        assert_eq!(animation.weight, 0.25);
+        assert!(!animation.is_faded_out());
+
+        // The weight stops at its target
+        animation.update_weight(1.0);
+        assert_eq!(animation.weight, 0.0);
+        assert!(animation.is_faded_out());
+        animation.update_weight(1.0);
+        assert_eq!(animation.weight, 0.0);
+
+        animation.fade_to(0.5, Duration::from_secs(1));
+        animation.update_weight(0.5);
+        assert_eq!(animation.weight, 0.25);
+        assert!(!animation.is_faded_out());
+    }
+
+    #[test]
+    fn instant_fade() {
+        let mut animation = PlayingAnimation::new(clip(), 0.5);
+        animation.fade_to(1.0, Duration::ZERO);
+        assert_eq!(animation.weight, 1.0);
+
+        animation.fade_to(0.0, Duration::ZERO);
+        assert_eq!(animation.weight, 0.0);
+        assert!(animation.is_faded_out());
+    }
+
+    #[test]
+    fn only_faded_animations_are_faded_out() {
+        // An animation whose weight was set to zero keeps playing
+        let mut animation = PlayingAnimation::new(clip(), 0.0);
+        assert!(!animation.is_faded_out());
+        animation.set_weight(1.0);
+        animation.fade_to(0.0, Duration::from_secs(1));
+        animation.set_weight(0.0);
+        assert!(!animation.is_faded_out());
+    }
+
+    #[test]
+    fn blend_factors_average_the_animations() {
+        let mut total_weight = 0.0;
+        // The first animation replaces the value
+        assert_eq!(blend_factor(&mut total_weight, 0.5), 1.0);
+        // The value is then the weighted average of the animations
+        assert_eq!(blend_factor(&mut total_weight, 1.5), 0.75);
+        assert_eq!(blend_factor(&mut total_weight, 2.0), 0.5);
+        assert_eq!(total_weight, 4.0);
+
+        let value = [1.0_f32, 3.0, 5.0];
+        let mut total_weight = 0.0;
+        let mut blended = 0.0;
+        for (value, weight) in value.into_iter().zip([1.0, 2.0, 1.0]) {
+            let blend = blend_factor(&mut total_weight, weight);
+            blended += (value - blended) * blend;
+        }
+        assert_eq!(blended, 3.0);
+    }
+
+    #[test]
+    fn cross_fade_fades_out_the_other_animations() {
+        let (walk, run) = (clip(), clip());
+        let mut player = AnimationPlayer::default();
+        player.play(walk.clone_weak()).repeat();
+        player.cross_fade(run.clone_weak(), Duration::from_secs(1));
+        assert!(player.is_fading());
+        assert_eq!(player.weight(&walk), Some(1.0));
+        assert_eq!(player.weight(&run), Some(0.0));
+
+        // `repeat` applies to the last animation started
+        player.repeat();
+        assert!(player.animations.iter().all(|animation| animation.repeat));
+        player.stop_repeating();
+        assert!(player.animations[0].repeat);
+
+        for animation in &mut player.animations {
+            animation.update_weight(0.25);
+        }
+        assert_eq!(player.weight(&walk), Some(0.75));
+        assert_eq!(player.weight(&run), Some(0.25));
+
+        // Fading back to the first animation keeps the second one fading out
+        player.cross_fade(walk.clone_weak(), Duration::from_secs(1));
+        assert_eq!(player.animations.len(), 2);
+        assert_eq!(player.animations.last().unwrap().animation_clip, walk);
+        for animation in &mut player.animations {
+            animation.update_weight(0.5);
+        }
+        // Each weight changes at the speed to reach its target over the duration
+        assert_eq!(player.weight(&walk), Some(0.875));
+        assert_eq!(player.weight(&run), Some(0.125));
+        for animation in &mut player.animations {
+            animation.update_weight(0.5);
+        }
+        assert_eq!(player.weight(&walk), Some(1.0));
+        assert_eq!(player.weight(&run), Some(0.0));
+        assert!(!player.is_fading());
+
+        // The faded out animation is removed
+        player.cross_fade(walk.clone_weak(), Duration::from_secs(1));
+        assert_eq!(player.weight(&run), None);
+    }
+}
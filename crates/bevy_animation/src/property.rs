@@ -0,0 +1,375 @@
+use std::{any::TypeId, fmt};
+
+use bevy_app::{App, CoreStage};
+use bevy_asset::Assets;
+use bevy_core::Name;
+use bevy_ecs::{
+    entity::Entity,
+    prelude::Component,
+    query::ChangeTrackers,
+    schedule::ParallelSystemDescriptorCoercion,
+    system::{Query, Res},
+};
+use bevy_hierarchy::Children;
+use bevy_math::Quat;
+use bevy_reflect::{ParsedPath, Reflect, ReflectMut, ReflectPathError, ReflectRef};
+use bevy_transform::TransformSystem;
+use bevy_utils::{tracing::warn, HashMap};
+
+use crate::{
+    blend_factor, find_entity, AnimationClip, AnimationPlayer, AnimationSystem, KeyframeStep,
+    Keyframes,
+};
+
+/// Keyframes for a field of a component, accessed by reflection with a path like
+/// `"sections[0].style.color"`. See [`GetPath`](bevy_reflect::GetPath) for the syntax of paths.
+///
+/// The values are interpolated field by field: floating point numbers are interpolated
+/// linearly, quaternions spherically, and enums like `Color` or `Val` only when both
+/// keyframes are the same variant. Other values, like integers, strings or booleans, keep the
+/// value of the previous keyframe.
+///
+/// ```
+/// # use bevy_animation::{AnimationClip, EntityPath, Keyframes, PropertyKeyframes, VariableCurve};
+/// # use bevy_core::Name;
+/// # use bevy_ecs::{component::Component, reflect::ReflectComponent};
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component)]
+/// struct Light {
+///     intensity: f32,
+/// }
+///
+/// let mut animation = AnimationClip::default();
+/// animation.add_curve_to_path(
+///     EntityPath {
+///         parts: vec![Name::new("lamp")],
+///     },
+///     VariableCurve {
+///         keyframe_timestamps: vec![0.0, 0.5, 1.0],
+///         keyframes: Keyframes::Property(
+///             PropertyKeyframes::new::<Light, f32>("intensity", vec![1.0, 0.2, 1.0]).unwrap(),
+///         ),
+///     },
+/// );
+/// ```
+pub struct PropertyKeyframes {
+    component: TypeId,
+    component_name: &'static str,
+    path: ParsedPath,
+    keyframes: Vec<Box<dyn Reflect>>,
+}
+
+impl PropertyKeyframes {
+    /// Create keyframes for the field at `path` in the component `C`.
+    pub fn new<C: Component + Reflect, T: Reflect>(
+        path: &str,
+        keyframes: Vec<T>,
+    ) -> Result<Self, ReflectPathError<'_>> {
+        Ok(Self {
+            component: TypeId::of::<C>(),
+            component_name: std::any::type_name::<C>(),
+            path: ParsedPath::parse(path)?,
+            keyframes: keyframes
+                .into_iter()
+                .map(|keyframe| Box::new(keyframe) as Box<dyn Reflect>)
+                .collect(),
+        })
+    }
+
+    /// The [`TypeId`] of the animated component
+    #[inline]
+    pub fn component(&self) -> TypeId {
+        self.component
+    }
+
+    /// The path of the animated field in the component
+    #[inline]
+    pub fn path(&self) -> &ParsedPath {
+        &self.path
+    }
+
+    /// The values of the field at each keyframe
+    #[inline]
+    pub fn keyframes(&self) -> &[Box<dyn Reflect>] {
+        &self.keyframes
+    }
+}
+
+impl Clone for PropertyKeyframes {
+    fn clone(&self) -> Self {
+        Self {
+            component: self.component,
+            component_name: self.component_name,
+            path: self.path.clone(),
+            keyframes: self
+                .keyframes
+                .iter()
+                .map(|keyframe| keyframe.clone_value())
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Debug for PropertyKeyframes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PropertyKeyframes")
+            .field("component", &self.component_name)
+            .field("path", &self.path)
+            .field("keyframes", &self.keyframes.len())
+            .finish()
+    }
+}
+
+/// Interpolate `value` towards `target` by `t`, field by field.
+fn interpolate(value: &mut dyn Reflect, target: &dyn Reflect, t: f32) {
+    match (value.reflect_mut(), target.reflect_ref()) {
+        (ReflectMut::Struct(value), ReflectRef::Struct(target)) => {
+            for (index, target_field) in target.iter_fields().enumerate() {
+                if let Some(field) = value.field_at_mut(index) {
+                    interpolate(field, target_field, t);
+                }
+            }
+        }
+        (ReflectMut::TupleStruct(value), ReflectRef::TupleStruct(target)) => {
+            for (index, target_field) in target.iter_fields().enumerate() {
+                if let Some(field) = value.field_mut(index) {
+                    interpolate(field, target_field, t);
+                }
+            }
+        }
+        (ReflectMut::Tuple(value), ReflectRef::Tuple(target)) => {
+            for (index, target_field) in target.iter_fields().enumerate() {
+                if let Some(field) = value.field_mut(index) {
+                    interpolate(field, target_field, t);
+                }
+            }
+        }
+        (ReflectMut::Array(value), ReflectRef::Array(target)) => {
+            for (index, target_item) in target.iter().enumerate() {
+                if let Some(item) = value.get_mut(index) {
+                    interpolate(item, target_item, t);
+                }
+            }
+        }
+        (ReflectMut::List(value), ReflectRef::List(target)) if value.len() == target.len() => {
+            for (index, target_item) in target.iter().enumerate() {
+                if let Some(item) = value.get_mut(index) {
+                    interpolate(item, target_item, t);
+                }
+            }
+        }
+        (ReflectMut::Enum(value), ReflectRef::Enum(target))
+            if value.variant_name() == target.variant_name() =>
+        {
+            for (index, target_field) in target.iter_fields().enumerate() {
+                if let Some(field) = value.field_at_mut(index) {
+                    interpolate(field, target_field.value(), t);
+                }
+            }
+        }
+        (ReflectMut::Value(value), ReflectRef::Value(target)) => {
+            if let (Some(value), Some(target)) =
+                (value.downcast_mut::<f32>(), target.downcast_ref::<f32>())
+            {
+                *value += (target - *value) * t;
+            } else if let (Some(value), Some(target)) =
+                (value.downcast_mut::<f64>(), target.downcast_ref::<f64>())
+            {
+                *value += (target - *value) * t as f64;
+            } else if let (Some(value), Some(target)) =
+                (value.downcast_mut::<Quat>(), target.downcast_ref::<Quat>())
+            {
+                *value = value.slerp(*target, t);
+            }
+        }
+        // Values that can't be interpolated keep their value
+        _ => {}
+    }
+}
+
+/// System that will apply the [`Keyframes::Property`] curves targeting the component `C` of all
+/// animations, after [`animation_player`](crate::animation_player) updated them
+///
+/// When several animations are playing, the fields are blended like the transforms.
+pub fn animate_component<C: Component + Reflect>(
+    animations: Res<Assets<AnimationClip>>,
+    animation_players: Query<(Entity, &AnimationPlayer, ChangeTrackers<AnimationPlayer>)>,
+    names: Query<&Name>,
+    children: Query<&Children>,
+    mut components: Query<&mut C>,
+) {
+    let component_type = TypeId::of::<C>();
+    let mut blend_weights = HashMap::<(Entity, &ParsedPath), f32>::default();
+    for (entity, player, player_tracker) in animation_players.iter() {
+        if player.is_paused() && !player_tracker.is_changed() {
+            continue;
+        }
+
+        blend_weights.clear();
+        for (animation, animation_clip) in player.playing_clips(&animations) {
+            let elapsed = animation.clip_time(animation_clip);
+            for (path, curves) in &animation_clip.curves {
+                let has_property = curves.iter().any(|curve| {
+                    matches!(&curve.keyframes, Keyframes::Property(keyframes) if keyframes.component == component_type)
+                });
+                if !has_property {
+                    continue;
+                }
+                let target = match find_entity(entity, path, &children, &names) {
+                    Some(target) => target,
+                    None => continue,
+                };
+                let mut component = match components.get_mut(target) {
+                    Ok(component) => component,
+                    Err(_) => continue,
+                };
+                for curve in curves {
+                    let keyframes = match &curve.keyframes {
+                        Keyframes::Property(keyframes) if keyframes.component == component_type => {
+                            keyframes
+                        }
+                        _ => continue,
+                    };
+                    let step = match curve.step_at(elapsed) {
+                        Some(step) => step,
+                        None => continue,
+                    };
+                    let field = match keyframes.path.element_mut(&mut *component) {
+                        Ok(field) => field,
+                        Err(err) => {
+                            warn!(
+                                "Can't animate the field {:?} of {}: {}",
+                                keyframes.path, keyframes.component_name, err
+                            );
+                            continue;
+                        }
+                    };
+                    if field.type_name() != keyframes.keyframes[0].type_name() {
+                        warn!(
+                            "Can't animate the field {:?} of {} of type {} with keyframes of type {}",
+                            keyframes.path,
+                            keyframes.component_name,
+                            field.type_name(),
+                            keyframes.keyframes[0].type_name()
+                        );
+                        continue;
+                    }
+
+                    let value = match step {
+                        KeyframeStep::Single => keyframes.keyframes[0].clone_value(),
+                        KeyframeStep::Between { start, lerp } => {
+                            let mut value = keyframes.keyframes[start].clone_value();
+                            interpolate(&mut *value, &*keyframes.keyframes[start + 1], lerp);
+                            value
+                        }
+                    };
+                    let weight = blend_weights.entry((target, &keyframes.path)).or_default();
+                    let blend = blend_factor(weight, animation.weight);
+                    if blend < 1.0 {
+                        interpolate(field, &*value, blend);
+                    } else {
+                        field.apply(&*value);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Adds support for animating the fields of a component with [`Keyframes::Property`] curves
+pub trait AddAnimatedComponent {
+    /// Adds the system animating the component `C`.
+    fn add_animated_component<C: Component + Reflect>(&mut self) -> &mut Self;
+}
+
+impl AddAnimatedComponent for App {
+    fn add_animated_component<C: Component + Reflect>(&mut self) -> &mut Self {
+        self.add_system_to_stage(
+            CoreStage::PostUpdate,
+            animate_component::<C>
+                .after(AnimationSystem::AnimationPlayer)
+                .before(TransformSystem::TransformPropagate),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::{Vec2, Vec3};
+    use bevy_render::color::Color;
+    use bevy_ui::Val;
+
+    fn interpolated<T: Reflect + Clone>(value: T, target: T, t: f32) -> T {
+        let mut value = value;
+        interpolate(&mut value, &target, t);
+        value
+    }
+
+    #[test]
+    fn interpolate_numbers() {
+        assert_eq!(interpolated(1.0_f32, 3.0, 0.25), 1.5);
+        assert_eq!(interpolated(1.0_f64, -1.0, 0.75), -0.5);
+        assert_eq!(
+            interpolated(Vec3::ZERO, Vec3::new(2.0, 4.0, -4.0), 0.5),
+            Vec3::new(1.0, 2.0, -2.0)
+        );
+        assert_eq!(
+            interpolated((0.0_f32, Vec2::ONE), (1.0, Vec2::ZERO), 0.5),
+            (0.5, Vec2::splat(0.5))
+        );
+        assert_eq!(
+            interpolated(vec![0.0_f32, 2.0], vec![2.0, 0.0], 0.5),
+            vec![1.0, 1.0]
+        );
+
+        let rotation = interpolated(
+            Quat::IDENTITY,
+            Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            0.5,
+        );
+        assert!(rotation.abs_diff_eq(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4), 1e-6));
+    }
+
+    #[test]
+    fn values_that_cant_be_interpolated_are_kept() {
+        assert_eq!(interpolated(1_u32, 3, 0.5), 1);
+        assert!(!interpolated(false, true, 0.9));
+        assert_eq!(interpolated(String::from("a"), String::from("b"), 0.5), "a");
+        // Lists of different lengths
+        assert_eq!(interpolated(vec![0.0_f32], vec![1.0, 1.0], 0.5), vec![0.0]);
+    }
+
+    #[test]
+    fn interpolate_colors() {
+        let color = interpolated(
+            Color::rgba(0.0, 0.2, 1.0, 1.0),
+            Color::rgba(1.0, 0.4, 0.0, 0.0),
+            0.5,
+        );
+        assert_eq!(color, Color::rgba(0.5, 0.3, 0.5, 0.5));
+
+        // Colors of different color spaces are not interpolated
+        let color = Color::rgba(0.0, 0.0, 0.0, 1.0);
+        assert_eq!(interpolated(color, Color::hsl(120.0, 0.5, 0.5), 0.5), color);
+    }
+
+    #[test]
+    fn interpolate_vals() {
+        assert_eq!(
+            interpolated(Val::Px(10.0), Val::Px(20.0), 0.25),
+            Val::Px(12.5)
+        );
+        assert_eq!(
+            interpolated(Val::Percent(0.0), Val::Percent(100.0), 0.5),
+            Val::Percent(50.0)
+        );
+        // Vals of different units are not interpolated
+        assert_eq!(
+            interpolated(Val::Px(10.0), Val::Percent(50.0), 0.5),
+            Val::Px(10.0)
+        );
+        assert_eq!(interpolated(Val::Auto, Val::Px(50.0), 0.5), Val::Auto);
+    }
+}
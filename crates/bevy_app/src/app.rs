@@ -4,8 +4,8 @@ use bevy_ecs::{
     event::{Event, Events},
     prelude::{FromWorld, IntoExclusiveSystem},
     schedule::{
-        IntoSystemDescriptor, Schedule, ShouldRun, Stage, StageLabel, State, StateData, SystemSet,
-        SystemStage,
+        apply_state_transition, run_enter_schedule, IntoSystemDescriptor, NextState, Schedule,
+        Schedules, ShouldRun, Stage, StageLabel, State, States, SystemSet, SystemStage,
     },
     system::Resource,
     world::World,
@@ -526,29 +526,78 @@ impl App {
         self
     }
 
-    /// Adds a new [`State`] with the given `initial` value.
-    /// This inserts a new `State<T>` resource and adds a new "driver" to [`CoreStage::Update`].
-    /// Each stage that uses `State<T>` for system run criteria needs a driver. If you need to use
-    /// your state in a different stage, consider using [`Self::add_state_to_stage`] or manually
-    /// adding [`State::get_driver`] to additional stages you need it in.
-    pub fn add_state<T>(&mut self, initial: T) -> &mut Self
-    where
-        T: StateData,
-    {
-        self.add_state_to_stage(CoreStage::Update, initial)
+    /// Adds a new [`State`] of type `S`, starting in its [`Default`] state.
+    ///
+    /// This inserts the [`State<S>`] and [`NextState<S>`] resources, enters the initial state in
+    /// [`StartupStage::PostStartup`], and applies the transitions set in [`NextState<S>`] in
+    /// [`CoreStage::StateTransitions`]. Systems are added to the
+    /// [`OnEnter`](bevy_ecs::schedule::OnEnter) and [`OnExit`](bevy_ecs::schedule::OnExit)
+    /// schedules of the states with [`Self::add_system_to_schedule`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+    /// enum AppState {
+    ///     #[default]
+    ///     Menu,
+    ///     InGame,
+    /// }
+    ///
+    /// # fn setup_menu() {}
+    /// # fn menu() {}
+    /// # fn cleanup_menu() {}
+    /// #
+    /// App::new()
+    ///     .add_state::<AppState>()
+    ///     .add_system_to_schedule(OnEnter(AppState::Menu), setup_menu)
+    ///     .add_system(menu.run_if(in_state(AppState::Menu)))
+    ///     .add_system_to_schedule(OnExit(AppState::Menu), cleanup_menu);
+    /// ```
+    pub fn add_state<S: States>(&mut self) -> &mut Self {
+        self.init_resource::<State<S>>()
+            .init_resource::<NextState<S>>()
+            .init_resource::<Schedules>()
+            .add_startup_system_to_stage(
+                StartupStage::PostStartup,
+                run_enter_schedule::<S>.exclusive_system(),
+            )
+            .add_system_to_stage(
+                CoreStage::StateTransitions,
+                apply_state_transition::<S>.exclusive_system(),
+            )
     }
 
-    /// Adds a new [`State`] with the given `initial` value.
-    /// This inserts a new `State<T>` resource and adds a new "driver" to the given stage.
-    /// Each stage that uses `State<T>` for system run criteria needs a driver. If you need to use
-    /// your state in more than one stage, consider manually adding [`State::get_driver`] to the
-    /// stages you need it in.
-    pub fn add_state_to_stage<T>(&mut self, stage: impl StageLabel, initial: T) -> &mut Self
-    where
-        T: StateData,
-    {
-        self.insert_resource(State::new(initial))
-            .add_system_set_to_stage(stage, State::<T>::get_driver())
+    /// Adds a system to the schedule with the given label, like the
+    /// [`OnEnter`](bevy_ecs::schedule::OnEnter) schedule of a state.
+    ///
+    /// Unlike stages, schedules only run when asked to, with
+    /// [`World::run_schedule`](bevy_ecs::world::World::run_schedule). See [`Schedules`].
+    pub fn add_system_to_schedule<Params>(
+        &mut self,
+        schedule_label: impl StageLabel,
+        system: impl IntoSystemDescriptor<Params>,
+    ) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(Schedules::default)
+            .add_system(schedule_label, system);
+        self
+    }
+
+    /// Adds a [`SystemSet`] to the schedule with the given label, see
+    /// [`Self::add_system_to_schedule`].
+    pub fn add_system_set_to_schedule(
+        &mut self,
+        schedule_label: impl StageLabel,
+        system_set: SystemSet,
+    ) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(Schedules::default)
+            .add_system_set(schedule_label, system_set);
+        self
     }
 
     /// Adds utility stages to the [`Schedule`], giving it a standardized structure.
@@ -572,6 +621,10 @@ impl App {
     ///     - **Post-startup:** Intended for systems that need to run after other startup systems.
     /// - **Pre-update:** Often used by plugins to prepare their internal state before the
     ///   update stage begins.
+    /// - **State transitions:** Applies the transitions of the states added with
+    ///   [`App::add_state`].
+    /// - **Fixed update:** Runs the systems of the `FixedUpdate` schedule of `bevy_time` at a
+    ///   fixed timestep.
     /// - **Update:** Intended for user defined logic. Systems are added here by default.
     /// - **Post-update:** Often used by plugins to finalize their internal state after the
     ///   world changes that happened during the update stage.
@@ -597,6 +650,8 @@ impl App {
                     .with_stage(StartupStage::PostStartup, SystemStage::parallel()),
             )
             .add_stage(CoreStage::PreUpdate, SystemStage::parallel())
+            .add_stage(CoreStage::StateTransitions, SystemStage::parallel())
+            .add_stage(CoreStage::FixedUpdate, SystemStage::parallel())
             .add_stage(CoreStage::Update, SystemStage::parallel())
             .add_stage(CoreStage::PostUpdate, SystemStage::parallel())
             .add_stage(CoreStage::Last, SystemStage::parallel())
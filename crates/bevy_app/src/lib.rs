@@ -35,6 +35,13 @@ pub enum CoreStage {
     First,
     /// The [`Stage`](bevy_ecs::schedule::Stage) that runs before [`CoreStage::Update`].
     PreUpdate,
+    /// The [`Stage`](bevy_ecs::schedule::Stage) that applies the transitions of the states added
+    /// with [`App::add_state`], running their [`OnExit`](bevy_ecs::schedule::OnExit) and
+    /// [`OnEnter`](bevy_ecs::schedule::OnEnter) schedules.
+    StateTransitions,
+    /// The [`Stage`](bevy_ecs::schedule::Stage) that runs the `FixedUpdate` schedule of `bevy_time`
+    /// at a fixed timestep, zero or more times per frame, before [`CoreStage::Update`].
+    FixedUpdate,
     /// The [`Stage`](bevy_ecs::schedule::Stage) responsible for doing most app logic. Systems should be registered here by default.
     Update,
     /// The [`Stage`](bevy_ecs::schedule::Stage) that runs after [`CoreStage::Update`].
@@ -1,8 +1,9 @@
 use crate::{
+    load_group::LoadGroupInfo,
     path::{AssetPath, AssetPathId, SourcePathId},
     Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    AssetLoader, AssetProcessor, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext,
+    LoadGroupId, LoadState, RefChange, RefChangeChannel, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut};
@@ -11,7 +12,10 @@ use bevy_tasks::IoTaskPool;
 use bevy_utils::{Entry, HashMap, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::{Mutex, RwLock};
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::{atomic::AtomicU64, Arc},
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an `AssetServer`
@@ -27,6 +31,8 @@ pub enum AssetServerError {
     AssetLoaderError(anyhow::Error),
     #[error("encountered an error while reading an asset: {0}")]
     AssetIoError(#[from] AssetIoError),
+    #[error("encountered an error while processing an asset: {0}")]
+    AssetProcessorError(anyhow::Error),
 }
 
 fn format_missing_asset_ext(exts: &[String]) -> String {
@@ -55,7 +61,13 @@ pub struct AssetServerInternal {
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
     loaders: RwLock<Vec<Arc<dyn AssetLoader>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
+    pub(crate) processors: RwLock<Vec<Arc<dyn AssetProcessor>>>,
+    pub(crate) extension_to_processor_index: RwLock<HashMap<String, usize>>,
+    pub(crate) processed_asset_folder: RwLock<Option<PathBuf>>,
+    pub(crate) embedded_assets: RwLock<HashMap<PathBuf, &'static [u8]>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
+    pub(crate) load_groups: RwLock<HashMap<LoadGroupId, LoadGroupInfo>>,
+    pub(crate) next_load_group_id: AtomicU64,
 }
 
 /// Loads assets from the filesystem on background threads
@@ -74,10 +86,16 @@ impl AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
                 extension_to_loader_index: Default::default(),
+                processors: Default::default(),
+                extension_to_processor_index: Default::default(),
+                processed_asset_folder: Default::default(),
+                embedded_assets: Default::default(),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
                 asset_lifecycles: Default::default(),
+                load_groups: Default::default(),
+                next_load_group_id: Default::default(),
                 asset_io,
             }),
         }
@@ -289,8 +307,14 @@ impl AssetServer {
             source_info.load_state = LoadState::Failed;
         };
 
-        // get the according asset loader
-        let asset_loader = match self.get_path_asset_loader(asset_path.path()) {
+        // get the according asset loader, which loads the processed asset if there is an
+        // asset processor
+        let asset_processor = self.get_path_asset_processor(asset_path.path());
+        let asset_loader = match &asset_processor {
+            Some(processor) => self.get_asset_loader(processor.processed_extension()),
+            None => self.get_path_asset_loader(asset_path.path()),
+        };
+        let asset_loader = match asset_loader {
             Ok(loader) => loader,
             Err(err) => {
                 set_asset_failed();
@@ -299,11 +323,21 @@ impl AssetServer {
         };
 
         // load the asset bytes
-        let bytes = match self.asset_io().load_path(asset_path.path()).await {
+        let bytes = match asset_processor {
+            Some(processor) => self
+                .process_asset(&*processor, asset_path.path())
+                .await
+                .map(|(bytes, _)| bytes),
+            None => self
+                .load_asset_bytes(asset_path.path())
+                .await
+                .map_err(AssetServerError::AssetIoError),
+        };
+        let bytes = match bytes {
             Ok(bytes) => bytes,
             Err(err) => {
                 set_asset_failed();
-                return Err(AssetServerError::AssetIoError(err));
+                return Err(err);
             }
         };
 
@@ -358,9 +392,11 @@ impl AssetServer {
             }
         }
 
-        self.asset_io()
-            .watch_path_for_changes(asset_path.path())
-            .unwrap();
+        if !self.is_embedded_asset(asset_path.path()) {
+            self.asset_io()
+                .watch_path_for_changes(asset_path.path())
+                .unwrap();
+        }
         self.create_assets_in_load_context(&mut load_context);
         Ok(asset_path_id)
     }
@@ -417,7 +453,9 @@ impl AssetServer {
             if self.asset_io().is_dir(&child_path) {
                 handles.extend(self.load_folder(&child_path)?);
             } else {
-                if self.get_path_asset_loader(&child_path).is_err() {
+                if self.get_path_asset_processor(&child_path).is_none()
+                    && self.get_path_asset_loader(&child_path).is_err()
+                {
                     continue;
                 }
                 let handle =
@@ -566,7 +604,9 @@ pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{loader::LoadedAsset, update_asset_storage_system};
+    use crate::{
+        loader::LoadedAsset, update_asset_storage_system, LoadGroupCompleted, LoadGroupProgress,
+    };
     use bevy_app::App;
     use bevy_ecs::prelude::*;
     use bevy_reflect::TypeUuid;
@@ -607,6 +647,26 @@ mod test {
         }
     }
 
+    struct FakeSceneLoader;
+    impl AssetLoader for FakeSceneLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            ctx.set_default_asset(
+                LoadedAsset::new(PngAsset)
+                    .with_dependency("fake.png".into())
+                    .with_dependency("missing.png".into()),
+            );
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["scene"]
+        }
+    }
+
     struct FakeMultipleDotLoader;
     impl AssetLoader for FakeMultipleDotLoader {
         fn load<'a>(
@@ -840,6 +900,52 @@ mod test {
         assert!(get_asset(&handle, &app.world).is_some());
     }
 
+    #[test]
+    fn test_load_group() {
+        let dir = create_dir_and_file("fake.png");
+        std::fs::write(dir.path().join("fake.scene"), &[]).unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(FakeSceneLoader);
+        let assets = asset_server.register_asset_type::<PngAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_event::<LoadGroupCompleted>();
+        app.add_system(update_asset_storage_system::<PngAsset>);
+        app.add_system(crate::load_group_system.after(update_asset_storage_system::<PngAsset>));
+
+        let group = asset_server.create_load_group();
+        let _handle: Handle<PngAsset> = asset_server.load_in_group(group, "fake.scene");
+
+        let mut progress = asset_server.get_load_group_progress(group).unwrap();
+        for _ in 0..100 {
+            app.update();
+            progress = asset_server.get_load_group_progress(group).unwrap();
+            if progress.total() == 3 && progress.is_complete() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(
+            progress,
+            LoadGroupProgress {
+                loaded: 2,
+                failed: 1,
+                pending: 0,
+            }
+        );
+
+        app.update();
+        let events = app.world.resource::<Events<LoadGroupCompleted>>();
+        let completed: Vec<_> = events.get_reader().iter(events).map(|e| e.id).collect();
+        assert_eq!(completed, vec![group]);
+
+        asset_server.remove_load_group(group);
+        assert!(asset_server.get_load_group_progress(group).is_none());
+    }
+
     #[test]
     fn test_get_handle_path() {
         const PATH: &str = "path/file.png";
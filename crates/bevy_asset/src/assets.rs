@@ -1,6 +1,6 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetLoader, AssetServer, AssetStage, Handle, HandleId,
-    RefChange,
+    update_asset_storage_system, Asset, AssetLoader, AssetProcessor, AssetServer, AssetStage,
+    Handle, HandleId, RefChange,
 };
 use bevy_app::App;
 use bevy_ecs::{
@@ -277,6 +277,9 @@ pub trait AddAsset {
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor;
 }
 
 impl AddAsset for App {
@@ -347,6 +350,16 @@ impl AddAsset for App {
         self.world.resource_mut::<AssetServer>().add_loader(loader);
         self
     }
+
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor,
+    {
+        self.world
+            .resource_mut::<AssetServer>()
+            .add_processor(processor);
+        self
+    }
 }
 
 #[cfg(feature = "debug_asset_server")]
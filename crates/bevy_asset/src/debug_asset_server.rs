@@ -69,6 +69,7 @@ impl Plugin for DebugAssetServerPlugin {
             .insert_resource(AssetServerSettings {
                 asset_folder: "crates".to_string(),
                 watch_for_changes: true,
+                asset_archive: None,
             })
             .add_plugin(AssetPlugin);
         app.insert_non_send_resource(DebugAssetApp(debug_asset_app));
@@ -0,0 +1,161 @@
+use crate::{AssetIoError, AssetServer};
+use std::path::{Component, Path, PathBuf};
+
+/// The folder of the asset paths of embedded assets, see [`embedded_asset!`](crate::embedded_asset).
+pub const EMBEDDED_ASSET_FOLDER: &str = "embedded";
+
+impl AssetServer {
+    /// Adds an asset compiled into the binary, which is loaded from these bytes instead of the
+    /// [`AssetIo`](crate::AssetIo) when loading `path`.
+    ///
+    /// It is usually called by the [`embedded_asset!`](crate::embedded_asset) macro.
+    pub fn add_embedded_asset<P: Into<PathBuf>>(&self, path: P, bytes: &'static [u8]) {
+        self.server
+            .embedded_assets
+            .write()
+            .insert(path.into(), bytes);
+    }
+
+    /// Returns `true` if the asset at `path` has been added with
+    /// [`AssetServer::add_embedded_asset`].
+    pub fn is_embedded_asset<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.server
+            .embedded_assets
+            .read()
+            .contains_key(path.as_ref())
+    }
+
+    /// Reads the bytes of the asset at `path`, from the embedded assets or the
+    /// [`AssetIo`](crate::AssetIo).
+    pub(crate) async fn load_asset_bytes(&self, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+        let embedded = self.server.embedded_assets.read().get(path).copied();
+        match embedded {
+            Some(bytes) => Ok(bytes.to_vec()),
+            None => self.asset_io().load_path(path).await,
+        }
+    }
+}
+
+/// Returns the asset path of an asset embedded with [`embedded_asset!`](crate::embedded_asset):
+/// `embedded/<crate name>/<path relative to the src folder of the crate>`.
+///
+/// `module_path` and `file` are the [`module_path!`] and [`file!`] where the macro was called,
+/// and `path` the path of the asset relative to this file.
+///
+/// ```
+/// # use bevy_asset::embedded_asset_path;
+/// # use std::path::Path;
+/// assert_eq!(
+///     embedded_asset_path("bevy_text::font", "crates/bevy_text/src/font/mod.rs", "FiraMono.ttf"),
+///     Path::new("embedded/bevy_text/font/FiraMono.ttf")
+/// );
+/// ```
+pub fn embedded_asset_path(module_path: &str, file: &str, path: &str) -> PathBuf {
+    let crate_name = module_path.split("::").next().unwrap();
+    let file = Path::new(file);
+    // the path of the file relative to the src folder, or to the crate for examples, tests and
+    // benches
+    let relative_file = file
+        .ancestors()
+        .filter_map(|ancestor| {
+            let name = ancestor.file_name()?;
+            (name == "src" || name == "examples" || name == "tests" || name == "benches")
+                .then(|| file.strip_prefix(ancestor).ok())
+                .flatten()
+        })
+        .next()
+        .unwrap_or(file);
+
+    let mut asset_path = PathBuf::from(EMBEDDED_ASSET_FOLDER);
+    asset_path.push(crate_name);
+    let components = relative_file
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(path);
+    for component in components.components() {
+        match component {
+            Component::Normal(component) => asset_path.push(component),
+            // don't go out of the folder of the crate
+            Component::ParentDir if asset_path.components().count() > 2 => {
+                asset_path.pop();
+            }
+            _ => {}
+        }
+    }
+    asset_path
+}
+
+/// Compiles an asset into the binary, and adds it to the [`AssetServer`] of the app, so that it
+/// can be loaded without being in the assets folder. This is useful for plugins shipping default
+/// assets, like fonts or shaders.
+///
+/// The `path` is relative to the file calling the macro, like for [`include_bytes!`]. The asset
+/// is then loaded with the path returned by [`embedded_asset_path`]:
+/// `embedded/<crate name>/<path relative to the src folder of the crate>`.
+///
+/// ```ignore
+/// # use bevy_app::{App, Plugin};
+/// # use bevy_asset::{embedded_asset, AssetServer};
+/// struct TextPlugin;
+///
+/// impl Plugin for TextPlugin {
+///     fn build(&self, app: &mut App) {
+///         // in `my_text/src/lib.rs`, embeds `my_text/src/fonts/default.ttf`
+///         embedded_asset!(app, "fonts/default.ttf");
+///         // which can then be loaded with
+///         # let asset_server = app.world.resource::<AssetServer>();
+///         let font = asset_server.load_untyped("embedded/my_text/fonts/default.ttf");
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! embedded_asset {
+    ($app: ident, $path: expr) => {{
+        let path = $crate::embedded_asset_path(module_path!(), file!(), $path);
+        $app.world
+            .resource::<$crate::AssetServer>()
+            .add_embedded_asset(path, include_bytes!($path));
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{embedded_asset_path, AssetServer, FileAssetIo};
+    use bevy_app::App;
+    use bevy_tasks::IoTaskPool;
+    use std::path::Path;
+
+    #[test]
+    fn embedded_asset_paths() {
+        assert_eq!(
+            embedded_asset_path("my_crate", "src/lib.rs", "shaders/a.wgsl"),
+            Path::new("embedded/my_crate/shaders/a.wgsl")
+        );
+        assert_eq!(
+            embedded_asset_path(
+                "my_crate::render",
+                "crates/my_crate/src/render/mesh.rs",
+                "../shaders/./b.wgsl"
+            ),
+            Path::new("embedded/my_crate/shaders/b.wgsl")
+        );
+        assert_eq!(
+            embedded_asset_path("my_example", "examples/2d/my_example.rs", "c.png"),
+            Path::new("embedded/my_example/2d/c.png")
+        );
+    }
+
+    #[test]
+    fn load_embedded_asset() {
+        IoTaskPool::init(Default::default);
+        let mut app = App::new();
+        app.insert_resource(AssetServer::new(FileAssetIo::new(".", false)));
+        embedded_asset!(app, "embedded.rs");
+
+        let asset_server = app.world.resource::<AssetServer>();
+        let path = Path::new("embedded/bevy_asset/embedded.rs");
+        assert!(asset_server.is_embedded_asset(path));
+        let bytes = futures_lite::future::block_on(asset_server.load_asset_bytes(path)).unwrap();
+        assert_eq!(bytes, include_bytes!("embedded.rs"));
+    }
+}
@@ -0,0 +1,653 @@
+use crate::{AssetIo, AssetIoError, FileType, Metadata};
+use anyhow::Result;
+use bevy_utils::BoxedFuture;
+use std::{
+    fmt, fs, io,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+/// An error returned by an [`HttpClient`].
+#[derive(Debug)]
+pub enum HttpError {
+    /// The server answered with a status code other than `200 OK`.
+    Status(u16),
+    /// The request could not be sent, or the response could not be read.
+    Io(io::Error),
+}
+
+impl std::error::Error for HttpError {}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HttpError::Status(status) => write!(f, "the server answered with status {}", status),
+            HttpError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<io::Error> for HttpError {
+    fn from(err: io::Error) -> Self {
+        HttpError::Io(err)
+    }
+}
+
+impl HttpError {
+    /// Returns `true` if sending the request again may succeed: for network errors and server
+    /// errors, but not for client errors like `404 Not Found`.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            HttpError::Status(status) => *status >= 500 || *status == 408 || *status == 429,
+            HttpError::Io(_) => true,
+        }
+    }
+}
+
+/// Sends the `GET` requests of an [`HttpAssetIo`].
+///
+/// The default client is [`DefaultHttpClient`]. Implement this trait to send the requests with
+/// the HTTP library of your choice instead.
+pub trait HttpClient: Send + Sync + 'static {
+    /// Sends a `GET` request to the `url`, returning the body of the response.
+    fn get<'a>(&'a self, url: &'a str) -> BoxedFuture<'a, Result<Vec<u8>, HttpError>>;
+}
+
+/// A location assets are fetched from over HTTP by an [`HttpAssetIo`].
+#[derive(Debug, Clone)]
+pub struct HttpSource {
+    /// The URL the asset paths are appended to.
+    pub base_url: String,
+    /// How many times a failed request is sent again, when the error
+    /// [is transient](HttpError::is_transient).
+    pub max_retries: u32,
+    /// The delay before sending a failed request again, doubled after each retry. It is ignored
+    /// on wasm, where requests are retried immediately.
+    pub retry_delay: Duration,
+    /// A folder where fetched assets are saved, to be loaded from the disk afterwards instead of
+    /// being fetched again. Cached assets are never refreshed: clear the folder to fetch new
+    /// versions. Asset paths that aren't made only of normal components, like `..`, are
+    /// rejected so that they can't escape it.
+    pub cache_folder: Option<PathBuf>,
+}
+
+impl HttpSource {
+    /// Creates a source fetching assets from the `base_url`, retrying failed requests three
+    /// times, without caching them on the disk.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            max_retries: 3,
+            retry_delay: Duration::from_millis(500),
+            cache_folder: None,
+        }
+    }
+
+    /// Sets the number of times a failed request is sent again, see [`HttpSource::max_retries`].
+    pub fn with_max_retries(mut self, max_retries: u32, retry_delay: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Saves the fetched assets in the `cache_folder`, see [`HttpSource::cache_folder`].
+    pub fn with_cache_folder(mut self, cache_folder: impl Into<PathBuf>) -> Self {
+        self.cache_folder = Some(cache_folder.into());
+        self
+    }
+
+    fn url(&self, path: &Path) -> String {
+        let mut url = self.base_url.trim_end_matches('/').to_string();
+        for component in path.components() {
+            if let Component::Normal(component) = component {
+                url.push('/');
+                url.push_str(&component.to_string_lossy().replace(' ', "%20"));
+            }
+        }
+        url
+    }
+}
+
+/// Fetches the assets whose path starts with a given prefix over HTTP, delegating the other
+/// ones to another [`AssetIo`], usually the platform default one.
+///
+/// This lets a game ship with its essential assets, and stream large content from a CDN.
+///
+/// ```no_run
+/// # use bevy_app::{App, Plugin};
+/// # use bevy_asset::{create_platform_default_asset_io, AssetServer, HttpAssetIo, HttpSource};
+/// struct StreamedAssetsPlugin;
+///
+/// impl Plugin for StreamedAssetsPlugin {
+///     fn build(&self, app: &mut App) {
+///         let asset_io = HttpAssetIo::new(create_platform_default_asset_io(app)).with_source(
+///             "remote",
+///             HttpSource::new("http://cdn.example.com/game").with_cache_folder("cache"),
+///         );
+///         // `asset_server.load("remote/music/theme.ogg")` will fetch
+///         // `http://cdn.example.com/game/music/theme.ogg`
+///         app.insert_resource(AssetServer::new(asset_io));
+///     }
+/// }
+/// ```
+///
+/// Like in the `custom_asset_io` example, the plugin must be added before the `AssetPlugin`.
+pub struct HttpAssetIo {
+    fallback: Box<dyn AssetIo>,
+    sources: Vec<(PathBuf, HttpSource)>,
+    client: Arc<dyn HttpClient>,
+}
+
+impl HttpAssetIo {
+    /// Creates an [`AssetIo`] delegating to `fallback` until sources are added with
+    /// [`HttpAssetIo::with_source`]. It uses the [`DefaultHttpClient`].
+    pub fn new(fallback: Box<dyn AssetIo>) -> Self {
+        Self {
+            fallback,
+            sources: Vec::new(),
+            client: Arc::new(DefaultHttpClient),
+        }
+    }
+
+    /// Fetches the assets whose path starts with `prefix` from the `source`, without the
+    /// prefix.
+    ///
+    /// When several prefixes match a path, the longest one is used.
+    pub fn with_source(mut self, prefix: impl AsRef<Path>, source: HttpSource) -> Self {
+        self.sources.push((prefix.as_ref().to_owned(), source));
+        self.sources
+            .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.components().count()));
+        self
+    }
+
+    /// Sends the requests with this client instead of the [`DefaultHttpClient`].
+    pub fn with_client(mut self, client: impl HttpClient) -> Self {
+        self.client = Arc::new(client);
+        self
+    }
+
+    fn source(&self, path: &Path) -> Option<(&HttpSource, PathBuf)> {
+        self.sources.iter().find_map(|(prefix, source)| {
+            path.strip_prefix(prefix)
+                .ok()
+                .map(|relative_path| (source, relative_path.to_owned()))
+        })
+    }
+
+    async fn fetch(
+        &self,
+        path: &Path,
+        source: &HttpSource,
+        relative_path: &Path,
+    ) -> Result<Vec<u8>, AssetIoError> {
+        if !relative_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(AssetIoError::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid path for an HTTP asset: {:?}", path),
+            )));
+        }
+        let cache_path = source
+            .cache_folder
+            .as_ref()
+            .map(|cache_folder| cache_folder.join(relative_path));
+        if let Some(bytes) = cache_path.as_ref().and_then(|path| fs::read(path).ok()) {
+            return Ok(bytes);
+        }
+
+        let url = source.url(relative_path);
+        let mut retry_delay = source.retry_delay;
+        let mut retries = 0;
+        let bytes = loop {
+            match self.client.get(&url).await {
+                Ok(bytes) => break bytes,
+                Err(err) if err.is_transient() && retries < source.max_retries => {
+                    bevy_log::debug!("Retrying to fetch {}: {}", url, err);
+                    retries += 1;
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unblock(move || std::thread::sleep(retry_delay)).await;
+                    retry_delay *= 2;
+                }
+                Err(HttpError::Status(404)) => return Err(AssetIoError::NotFound(path.to_owned())),
+                Err(HttpError::Io(err)) => return Err(AssetIoError::Io(err)),
+                Err(err) => {
+                    return Err(AssetIoError::Io(io::Error::new(
+                        io::ErrorKind::Other,
+                        format!("failed to fetch {}: {}", url, err),
+                    )))
+                }
+            }
+        };
+
+        if let Some(cache_path) = cache_path {
+            let cached = cache_path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::write(&cache_path, &bytes));
+            if let Err(err) = cached {
+                bevy_log::warn!("Failed to cache {} in {:?}: {}", url, cache_path, err);
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+impl AssetIo for HttpAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        match self.source(path) {
+            Some((source, relative_path)) => {
+                Box::pin(async move { self.fetch(path, source, &relative_path).await })
+            }
+            None => self.fallback.load_path(path),
+        }
+    }
+
+    /// Directories can't be listed over HTTP: this returns an empty iterator for the paths
+    /// fetched from an [`HttpSource`].
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        match self.source(path) {
+            Some(_) => Ok(Box::new(std::iter::empty::<PathBuf>())),
+            None => self.fallback.read_directory(path),
+        }
+    }
+
+    /// The paths fetched from an [`HttpSource`] are assumed to be files.
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        match self.source(path) {
+            Some(_) => Ok(Metadata::new(FileType::File)),
+            None => self.fallback.get_metadata(path),
+        }
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        match self.source(path) {
+            Some(_) => Ok(()),
+            None => self.fallback.watch_path_for_changes(path),
+        }
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.fallback.watch_for_changes()
+    }
+}
+
+/// The default [`HttpClient`] of an [`HttpAssetIo`].
+///
+/// It uses `fetch` on wasm. On other platforms, it sends HTTP/1.1 requests over a TCP
+/// connection for `http` URLs, and runs the `curl` command line tool for `https` URLs, which
+/// ships with Windows 10, macOS and most Linux distributions. The requests are sent from their
+/// own thread, not to block the [`IoTaskPool`](bevy_tasks::IoTaskPool).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultHttpClient;
+
+#[cfg(target_arch = "wasm32")]
+impl HttpClient for DefaultHttpClient {
+    fn get<'a>(&'a self, url: &'a str) -> BoxedFuture<'a, Result<Vec<u8>, HttpError>> {
+        use js_sys::Uint8Array;
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen_futures::JsFuture;
+        use web_sys::Response;
+
+        let js_error = |value: wasm_bindgen::JsValue| {
+            HttpError::Io(io::Error::new(io::ErrorKind::Other, format!("{:?}", value)))
+        };
+        Box::pin(async move {
+            let window = web_sys::window().unwrap();
+            let resp_value = JsFuture::from(window.fetch_with_str(url))
+                .await
+                .map_err(js_error)?;
+            let resp: Response = resp_value.dyn_into().unwrap();
+            if resp.status() != 200 {
+                return Err(HttpError::Status(resp.status()));
+            }
+            let data = JsFuture::from(resp.array_buffer().map_err(js_error)?)
+                .await
+                .map_err(js_error)?;
+            Ok(Uint8Array::new(&data).to_vec())
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl HttpClient for DefaultHttpClient {
+    fn get<'a>(&'a self, url: &'a str) -> BoxedFuture<'a, Result<Vec<u8>, HttpError>> {
+        let url = url.to_string();
+        Box::pin(async move {
+            unblock(move || {
+                if url.starts_with("https://") {
+                    curl_get(&url)
+                } else {
+                    http_get(&url)
+                }
+            })
+            .await
+        })
+    }
+}
+
+/// Runs the blocking function `f` on its own thread, so that awaiting it doesn't block the
+/// thread of the task pool.
+#[cfg(not(target_arch = "wasm32"))]
+async fn unblock<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    let (sender, receiver) = async_channel::bounded(1);
+    std::thread::spawn(move || {
+        // The receiver is only dropped if the future was dropped
+        let _ = sender.try_send(f());
+    });
+    receiver.recv().await.expect("the blocking thread panicked")
+}
+
+/// Sends a `GET` request to an `https` URL with the `curl` command line tool.
+#[cfg(not(target_arch = "wasm32"))]
+fn curl_get(url: &str) -> Result<Vec<u8>, HttpError> {
+    const STATUS_LEN: usize = 3;
+
+    let output = std::process::Command::new("curl")
+        .args(["--silent", "--show-error", "--location", "--max-time", "30"])
+        // The status code is written after the body
+        .args(["--write-out", "%{http_code}", "--", url])
+        .output()
+        .map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("failed to run curl to fetch {}: {}", url, err),
+            )
+        })?;
+    if !output.status.success() {
+        return Err(HttpError::Io(io::Error::new(
+            io::ErrorKind::Other,
+            format!(
+                "curl failed to fetch {}: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        )));
+    }
+    let mut body = output.stdout;
+    let status = body
+        .len()
+        .checked_sub(STATUS_LEN)
+        .and_then(|status_start| std::str::from_utf8(&body[status_start..]).ok())
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(|| {
+            HttpError::Io(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("curl did not write the status code of {}", url),
+            ))
+        })?;
+    if status != 200 {
+        return Err(HttpError::Status(status));
+    }
+    body.truncate(body.len() - STATUS_LEN);
+    Ok(body)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn http_get(url: &str) -> Result<Vec<u8>, HttpError> {
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+    };
+
+    let invalid =
+        |message: String| HttpError::Io(io::Error::new(io::ErrorKind::InvalidData, message));
+    let rest = url.strip_prefix("http://").ok_or_else(|| {
+        HttpError::Io(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!(
+                "the default HTTP client only supports http and https URLs, use a custom HttpClient for {}",
+                url
+            ),
+        ))
+    })?;
+    let (host, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+
+    let mut stream = TcpStream::connect(address)?;
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nAccept-Encoding: identity\r\nConnection: close\r\n\r\n",
+        path, host
+    );
+    stream.write_all(request.as_bytes())?;
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+
+    let header_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(|| invalid(format!("incomplete HTTP response from {}", url)))?;
+    let head = String::from_utf8_lossy(&response[..header_end]).to_string();
+    let mut body = response.split_off(header_end + 4);
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|status_line| status_line.split(' ').nth(1))
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(|| invalid(format!("invalid HTTP status line from {}", url)))?;
+    if status != 200 {
+        return Err(HttpError::Status(status));
+    }
+
+    let mut chunked = false;
+    for line in lines {
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("transfer-encoding") {
+                chunked = value.eq_ignore_ascii_case("chunked");
+            } else if name.eq_ignore_ascii_case("content-length") {
+                let len = value
+                    .parse::<usize>()
+                    .map_err(|_| invalid(format!("invalid Content-Length from {}", url)))?;
+                if body.len() < len {
+                    return Err(invalid(format!("truncated HTTP response from {}", url)));
+                }
+                body.truncate(len);
+            }
+        }
+    }
+    if chunked {
+        body = decode_chunked(&body)
+            .ok_or_else(|| invalid(format!("invalid chunked response from {}", url)))?;
+    }
+    Ok(body)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn decode_chunked(mut body: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded = Vec::new();
+    loop {
+        let line_end = body.windows(2).position(|window| window == b"\r\n")?;
+        let size = std::str::from_utf8(&body[..line_end]).ok()?;
+        let size = usize::from_str_radix(size.split(';').next()?.trim(), 16).ok()?;
+        body = &body[line_end + 2..];
+        if size == 0 {
+            return Some(decoded);
+        }
+        decoded.extend_from_slice(body.get(..size)?);
+        body = body.get(size + 2..)?;
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    /// Serves the given responses, one per connection, and returns the base URL of the server.
+    fn serve(responses: Vec<&'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/assets", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buffer).unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                }
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        url
+    }
+
+    struct EmptyAssetIo;
+
+    impl AssetIo for EmptyAssetIo {
+        fn load_path<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+            Box::pin(async move { Err(AssetIoError::NotFound(path.to_owned())) })
+        }
+
+        fn read_directory(
+            &self,
+            path: &Path,
+        ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+
+        fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+
+        fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+            Ok(())
+        }
+
+        fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+            Ok(())
+        }
+    }
+
+    fn load(asset_io: &HttpAssetIo, path: &str) -> Result<Vec<u8>, AssetIoError> {
+        futures_lite::future::block_on(asset_io.load_path(Path::new(path)))
+    }
+
+    #[test]
+    fn fetch_with_retries_and_cache() {
+        let url = serve(vec![
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello",
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n2\r\nde\r\n0\r\n\r\n",
+        ]);
+        let cache_folder = tempfile::tempdir().unwrap();
+        let asset_io = HttpAssetIo::new(Box::new(EmptyAssetIo)).with_source(
+            "remote",
+            HttpSource::new(url)
+                .with_max_retries(2, Duration::from_millis(1))
+                .with_cache_folder(cache_folder.path()),
+        );
+
+        assert_eq!(load(&asset_io, "remote/text/hello.txt").unwrap(), b"hello");
+        assert!(matches!(
+            load(&asset_io, "remote/missing.txt"),
+            Err(AssetIoError::NotFound(_))
+        ));
+        assert_eq!(load(&asset_io, "remote/chunked.txt").unwrap(), b"abcde");
+        // The server doesn't answer anymore, the cached asset is used
+        assert_eq!(load(&asset_io, "remote/text/hello.txt").unwrap(), b"hello");
+        assert_eq!(
+            fs::read(cache_folder.path().join("text/hello.txt")).unwrap(),
+            b"hello"
+        );
+
+        assert!(matches!(
+            load(&asset_io, "local.txt"),
+            Err(AssetIoError::NotFound(_))
+        ));
+        assert!(asset_io.is_file(Path::new("remote/text/hello.txt")));
+        assert!(!asset_io.is_file(Path::new("local.txt")));
+    }
+
+    #[test]
+    fn custom_client_and_longest_prefix() {
+        struct EchoClient;
+
+        impl HttpClient for EchoClient {
+            fn get<'a>(&'a self, url: &'a str) -> BoxedFuture<'a, Result<Vec<u8>, HttpError>> {
+                Box::pin(async move { Ok(url.as_bytes().to_vec()) })
+            }
+        }
+
+        let asset_io = HttpAssetIo::new(Box::new(EmptyAssetIo))
+            .with_source("remote", HttpSource::new("https://a.example.com/"))
+            .with_source("remote/music", HttpSource::new("https://b.example.com"))
+            .with_client(EchoClient);
+
+        assert_eq!(
+            load(&asset_io, "remote/my model.gltf").unwrap(),
+            b"https://a.example.com/my%20model.gltf"
+        );
+        assert_eq!(
+            load(&asset_io, "remote/music/theme.ogg").unwrap(),
+            b"https://b.example.com/theme.ogg"
+        );
+    }
+
+    #[test]
+    fn paths_escaping_the_cache_folder_are_rejected() {
+        struct UnreachableClient;
+
+        impl HttpClient for UnreachableClient {
+            fn get<'a>(&'a self, url: &'a str) -> BoxedFuture<'a, Result<Vec<u8>, HttpError>> {
+                panic!("{} should not be fetched", url)
+            }
+        }
+
+        let cache_folder = tempfile::tempdir().unwrap();
+        let asset_io = HttpAssetIo::new(Box::new(EmptyAssetIo))
+            .with_source(
+                "remote",
+                HttpSource::new("http://example.com").with_cache_folder(cache_folder.path()),
+            )
+            .with_client(UnreachableClient);
+
+        for path in ["remote/../secret.txt", "remote/text/../../secret.txt"] {
+            assert!(matches!(
+                load(&asset_io, path),
+                Err(AssetIoError::Io(err)) if err.kind() == io::ErrorKind::InvalidInput
+            ));
+        }
+    }
+
+    #[test]
+    fn curl_status_and_body() {
+        if std::process::Command::new("curl")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            return;
+        }
+        let url = serve(vec![
+            "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello",
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n",
+        ]);
+        assert_eq!(curl_get(&format!("{}/hello.txt", url)).unwrap(), b"hello");
+        assert!(matches!(
+            curl_get(&format!("{}/missing.txt", url)),
+            Err(HttpError::Status(404))
+        ));
+    }
+}
@@ -5,7 +5,9 @@ mod file_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
+mod http_asset_io;
 mod metadata;
+mod pak_asset_io;
 
 #[cfg(target_os = "android")]
 pub use android_asset_io::*;
@@ -14,7 +16,9 @@ pub use file_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
+pub use http_asset_io::*;
 pub use metadata::*;
+pub use pak_asset_io::*;
 
 use anyhow::Result;
 use bevy_utils::BoxedFuture;
@@ -0,0 +1,361 @@
+use crate::{AssetIo, AssetIoError, FileType, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap};
+use std::{
+    collections::BTreeSet,
+    fs::{self, File},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+};
+
+const PAK_MAGIC: &[u8; 4] = b"BPAK";
+const PAK_VERSION: u32 = 1;
+
+/// Loads assets from a single archive file, built from an assets folder with
+/// [`create_pak_archive`] or [`write_pak_archive`].
+///
+/// The archive starts with the `BPAK` magic bytes and a format version, followed by an index of
+/// its files (their path relative to the assets folder, offset and size), then by the content of
+/// the files. Integers are stored in little endian.
+///
+/// Archives are read-only: watching for changes is not supported.
+pub struct PakAssetIo {
+    source: PakSource,
+    files: HashMap<PathBuf, PakEntry>,
+    directories: HashMap<PathBuf, BTreeSet<PathBuf>>,
+}
+
+enum PakSource {
+    File(PathBuf),
+    Bytes(Vec<u8>),
+}
+
+#[derive(Clone, Copy)]
+struct PakEntry {
+    offset: u64,
+    len: u64,
+}
+
+impl PakAssetIo {
+    /// Opens the archive at `path`, reading its index. The files are read from the archive when
+    /// they are loaded.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, AssetIoError> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| {
+            if e.kind() == io::ErrorKind::NotFound {
+                AssetIoError::NotFound(path.to_owned())
+            } else {
+                e.into()
+            }
+        })?;
+        let len = file.metadata()?.len();
+        let files = read_index(&mut io::BufReader::new(file), len)?;
+        Ok(Self::new(PakSource::File(path.to_owned()), files))
+    }
+
+    /// Reads an archive from memory, for example one included in the executable with
+    /// [`include_bytes`].
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, AssetIoError> {
+        let files = read_index(&mut io::Cursor::new(&bytes), bytes.len() as u64)?;
+        Ok(Self::new(PakSource::Bytes(bytes), files))
+    }
+
+    fn new(source: PakSource, files: HashMap<PathBuf, PakEntry>) -> Self {
+        let mut directories: HashMap<PathBuf, BTreeSet<PathBuf>> = HashMap::default();
+        directories.insert(PathBuf::new(), BTreeSet::new());
+        for path in files.keys() {
+            let mut child = path.as_path();
+            while let Some(parent) = child.parent() {
+                directories
+                    .entry(parent.to_owned())
+                    .or_default()
+                    .insert(child.to_owned());
+                child = parent;
+            }
+        }
+        Self {
+            source,
+            files,
+            directories,
+        }
+    }
+
+    fn read_file(&self, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+        let entry = *self
+            .files
+            .get(&normalize_path(path))
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+        match &self.source {
+            PakSource::File(archive_path) => {
+                let mut file = File::open(archive_path)?;
+                file.seek(SeekFrom::Start(entry.offset))?;
+                let mut bytes = vec![0; entry.len as usize];
+                file.read_exact(&mut bytes)?;
+                Ok(bytes)
+            }
+            PakSource::Bytes(archive) => {
+                let start = entry.offset as usize;
+                Ok(archive[start..start + entry.len as usize].to_vec())
+            }
+        }
+    }
+}
+
+impl AssetIo for PakAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move { self.read_file(path) })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let children = self
+            .directories
+            .get(&normalize_path(path))
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+        Ok(Box::new(children.clone().into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let path = normalize_path(path);
+        if self.files.contains_key(&path) {
+            Ok(Metadata::new(FileType::File))
+        } else if self.directories.contains_key(&path) {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path))
+        }
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        bevy_log::warn!(
+            "Watching for changes is not supported when loading assets from an archive"
+        );
+        Ok(())
+    }
+}
+
+/// Writes an archive readable by [`PakAssetIo`] with all the files of the `asset_folder` to
+/// `archive_path`.
+pub fn create_pak_archive<P: AsRef<Path>, Q: AsRef<Path>>(
+    asset_folder: P,
+    archive_path: Q,
+) -> io::Result<()> {
+    let mut writer = io::BufWriter::new(File::create(archive_path)?);
+    write_pak_archive(asset_folder, &mut writer)?;
+    writer.flush()
+}
+
+/// Writes an archive readable by [`PakAssetIo`] with all the files of the `asset_folder` to
+/// `writer`.
+pub fn write_pak_archive<P: AsRef<Path>, W: Write>(
+    asset_folder: P,
+    writer: &mut W,
+) -> io::Result<()> {
+    let asset_folder = asset_folder.as_ref();
+    let mut paths = Vec::new();
+    collect_files(asset_folder, asset_folder, &mut paths)?;
+    paths.sort();
+
+    let names = paths
+        .iter()
+        .map(|path| {
+            path.components()
+                .map(|component| component.as_os_str().to_str())
+                .collect::<Option<Vec<_>>>()
+                .map(|components| components.join("/"))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("asset path is not valid UTF-8: {:?}", path),
+                    )
+                })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    let lens = paths
+        .iter()
+        .map(|path| Ok(fs::metadata(asset_folder.join(path))?.len()))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let index_len = 12 + names.iter().map(|name| 20 + name.len() as u64).sum::<u64>();
+    writer.write_all(PAK_MAGIC)?;
+    writer.write_all(&PAK_VERSION.to_le_bytes())?;
+    writer.write_all(&(names.len() as u32).to_le_bytes())?;
+    let mut offset = index_len;
+    for (name, len) in names.iter().zip(&lens) {
+        writer.write_all(&(name.len() as u32).to_le_bytes())?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(&offset.to_le_bytes())?;
+        writer.write_all(&len.to_le_bytes())?;
+        offset += len;
+    }
+    for (path, &len) in paths.iter().zip(&lens) {
+        let copied = io::copy(&mut File::open(asset_folder.join(path))?, writer)?;
+        if copied != len {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("asset file changed while writing the archive: {:?}", path),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn collect_files(root: &Path, directory: &Path, paths: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, paths)?;
+        } else {
+            paths.push(path.strip_prefix(root).unwrap().to_owned());
+        }
+    }
+    Ok(())
+}
+
+fn read_index<R: Read>(
+    reader: &mut R,
+    archive_len: u64,
+) -> Result<HashMap<PathBuf, PakEntry>, AssetIoError> {
+    let invalid = |message: &str| {
+        AssetIoError::Io(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid asset archive: {}", message),
+        ))
+    };
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != PAK_MAGIC {
+        return Err(invalid("missing BPAK header"));
+    }
+    let version = read_u32(reader)?;
+    if version != PAK_VERSION {
+        return Err(invalid(&format!("unsupported version {}", version)));
+    }
+    let count = read_u32(reader)?;
+    let mut files = HashMap::default();
+    for _ in 0..count {
+        let mut name = vec![0; read_u32(reader)? as usize];
+        reader.read_exact(&mut name)?;
+        let name = String::from_utf8(name).map_err(|_| invalid("file path is not UTF-8"))?;
+        let entry = PakEntry {
+            offset: read_u64(reader)?,
+            len: read_u64(reader)?,
+        };
+        if entry
+            .offset
+            .checked_add(entry.len)
+            .map_or(true, |end| end > archive_len)
+        {
+            return Err(invalid(&format!("{} is out of bounds", name)));
+        }
+        files.insert(normalize_path(Path::new(&name)), entry);
+    }
+    Ok(files)
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Removes the `.` components of the path, so that `./models/a.gltf` and `models/a.gltf` match
+/// the same file.
+fn normalize_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive() -> PakAssetIo {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("models/trees")).unwrap();
+        fs::write(dir.path().join("icon.png"), b"icon").unwrap();
+        fs::write(dir.path().join("models/cube.gltf"), b"cube").unwrap();
+        fs::write(dir.path().join("models/trees/oak.gltf"), b"oak").unwrap();
+        let mut bytes = Vec::new();
+        write_pak_archive(dir.path(), &mut bytes).unwrap();
+        PakAssetIo::from_bytes(bytes).unwrap()
+    }
+
+    fn load(asset_io: &PakAssetIo, path: &str) -> Result<Vec<u8>, AssetIoError> {
+        futures_lite::future::block_on(asset_io.load_path(Path::new(path)))
+    }
+
+    #[test]
+    fn load_from_archive() {
+        let asset_io = archive();
+        assert_eq!(load(&asset_io, "icon.png").unwrap(), b"icon");
+        assert_eq!(load(&asset_io, "models/cube.gltf").unwrap(), b"cube");
+        assert_eq!(load(&asset_io, "./models/trees/oak.gltf").unwrap(), b"oak");
+        assert!(matches!(
+            load(&asset_io, "models/missing.gltf"),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        assert!(asset_io.is_dir(Path::new("models/trees")));
+        assert!(asset_io.is_file(Path::new("models/cube.gltf")));
+        assert!(!asset_io.is_file(Path::new("models")));
+        assert_eq!(
+            asset_io
+                .read_directory(Path::new("models"))
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec![
+                PathBuf::from("models/cube.gltf"),
+                PathBuf::from("models/trees")
+            ]
+        );
+        assert_eq!(
+            asset_io
+                .read_directory(Path::new(""))
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec![PathBuf::from("icon.png"), PathBuf::from("models")]
+        );
+    }
+
+    #[test]
+    fn open_archive_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.txt"), b"a").unwrap();
+        let archive_dir = tempfile::tempdir().unwrap();
+        let archive_path = archive_dir.path().join("assets.pak");
+        create_pak_archive(dir.path(), &archive_path).unwrap();
+
+        let asset_io = PakAssetIo::open(&archive_path).unwrap();
+        assert_eq!(load(&asset_io, "a.txt").unwrap(), b"a");
+    }
+
+    #[test]
+    fn invalid_archive() {
+        assert!(PakAssetIo::from_bytes(b"not an archive".to_vec()).is_err());
+
+        // An archive whose only file is out of bounds
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(PAK_MAGIC);
+        bytes.extend_from_slice(&PAK_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.push(b'a');
+        bytes.extend_from_slice(&100u64.to_le_bytes());
+        bytes.extend_from_slice(&10u64.to_le_bytes());
+        assert!(PakAssetIo::from_bytes(bytes).is_err());
+    }
+}
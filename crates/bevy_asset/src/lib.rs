@@ -3,6 +3,7 @@ mod assets;
 #[cfg(feature = "debug_asset_server")]
 pub mod debug_asset_server;
 pub mod diagnostic;
+mod embedded;
 #[cfg(all(
     feature = "filesystem_watcher",
     all(not(target_arch = "wasm32"), not(target_os = "android"))
@@ -11,8 +12,10 @@ mod filesystem_watcher;
 mod handle;
 mod info;
 mod io;
+mod load_group;
 mod loader;
 mod path;
+mod processor;
 
 pub mod prelude {
     #[doc(hidden)]
@@ -22,11 +25,14 @@ pub mod prelude {
 pub use asset_server::*;
 pub use assets::*;
 pub use bevy_utils::BoxedFuture;
+pub use embedded::*;
 pub use handle::*;
 pub use info::*;
 pub use io::*;
+pub use load_group::*;
 pub use loader::*;
 pub use path::*;
+pub use processor::*;
 
 use bevy_app::{prelude::Plugin, App};
 use bevy_ecs::schedule::{StageLabel, SystemStage};
@@ -48,6 +54,11 @@ pub struct AssetServerSettings {
     /// Whether to watch for changes in asset files. Requires the `filesystem_watcher` feature,
     /// and cannot be supported on the wasm32 arch nor android os.
     pub watch_for_changes: bool,
+    /// The path of an archive built with [`create_pak_archive`] to load the assets from, instead
+    /// of the `asset_folder`. It is relative to the executable, or to the `CARGO_MANIFEST_DIR`
+    /// when run with cargo. Only supported on desktop platforms, without watching for changes. If
+    /// the archive can't be opened, the assets are loaded from the `asset_folder`.
+    pub asset_archive: Option<String>,
 }
 
 impl Default for AssetServerSettings {
@@ -55,6 +66,7 @@ impl Default for AssetServerSettings {
         Self {
             asset_folder: "assets".to_string(),
             watch_for_changes: false,
+            asset_archive: None,
         }
     }
 }
@@ -68,6 +80,27 @@ pub fn create_platform_default_asset_io(app: &mut App) -> Box<dyn AssetIo> {
         .world
         .get_resource_or_insert_with(AssetServerSettings::default);
 
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    if let Some(asset_archive) = &settings.asset_archive {
+        let path = FileAssetIo::get_root_path().join(asset_archive);
+        match PakAssetIo::open(&path) {
+            Ok(source) => {
+                if settings.watch_for_changes {
+                    if let Err(err) = source.watch_for_changes() {
+                        bevy_log::error!("Failed to watch the asset archive {:?}: {}", path, err);
+                    }
+                }
+                return Box::new(source);
+            }
+            Err(err) => bevy_log::error!(
+                "Failed to open the asset archive {:?}, loading the assets from {:?} instead: {}",
+                path,
+                settings.asset_folder,
+                err
+            ),
+        }
+    }
+
     #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
     let source = FileAssetIo::new(&settings.asset_folder, settings.watch_for_changes);
     #[cfg(target_arch = "wasm32")]
@@ -97,10 +130,12 @@ impl Plugin for AssetPlugin {
             SystemStage::parallel(),
         )
         .register_type::<HandleId>()
+        .add_event::<LoadGroupCompleted>()
         .add_system_to_stage(
             bevy_app::CoreStage::PreUpdate,
             asset_server::free_unused_assets_system,
-        );
+        )
+        .add_system_to_stage(bevy_app::CoreStage::PreUpdate, load_group_system);
 
         #[cfg(all(
             feature = "filesystem_watcher",
@@ -0,0 +1,201 @@
+use crate::{
+    path::{AssetPath, SourcePathId},
+    Asset, AssetServer, Handle, HandleId, HandleUntyped, LoadState, SourceInfo,
+};
+use bevy_ecs::{event::EventWriter, system::Res};
+use bevy_utils::{HashMap, HashSet};
+use std::sync::atomic::Ordering;
+
+/// The id of a batch of assets whose loading is tracked together, created with
+/// [`AssetServer::create_load_group`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LoadGroupId(u64);
+
+#[derive(Default)]
+pub(crate) struct LoadGroupInfo {
+    handles: Vec<HandleId>,
+    completed: bool,
+}
+
+/// The number of assets of a load group in each state, including their dependencies.
+///
+/// Assets are counted by source file: a scene file with its meshes and materials counts as one
+/// asset, and each texture it depends on counts as another one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadGroupProgress {
+    /// The number of assets that have been loaded.
+    pub loaded: usize,
+    /// The number of assets that failed to load.
+    pub failed: usize,
+    /// The number of assets still loading, or waiting to be loaded.
+    pub pending: usize,
+}
+
+impl LoadGroupProgress {
+    /// Returns the number of assets of the group, including their known dependencies.
+    pub fn total(&self) -> usize {
+        self.loaded + self.failed + self.pending
+    }
+
+    /// Returns `true` if no asset of the group is still loading.
+    pub fn is_complete(&self) -> bool {
+        self.pending == 0
+    }
+
+    /// Returns the ratio of assets that are loaded or failed to load, between `0.0` and `1.0`.
+    ///
+    /// The dependencies of an asset are only known once it has been loaded, so the ratio may
+    /// decrease when they are discovered.
+    pub fn ratio(&self) -> f32 {
+        if self.total() == 0 {
+            1.0
+        } else {
+            (self.loaded + self.failed) as f32 / self.total() as f32
+        }
+    }
+}
+
+/// An event sent when all the assets of a load group, including their dependencies, have been
+/// loaded or failed to load.
+#[derive(Debug, Clone, Copy)]
+pub struct LoadGroupCompleted {
+    /// The id of the completed load group.
+    pub id: LoadGroupId,
+    /// The final progress of the load group, with the number of assets that failed to load.
+    pub progress: LoadGroupProgress,
+}
+
+impl AssetServer {
+    /// Creates an empty load group, to track the loading of a batch of assets, like the ones
+    /// needed by a level.
+    ///
+    /// Add assets to the group with [`AssetServer::load_in_group`] or
+    /// [`AssetServer::add_to_load_group`], then check its progress with
+    /// [`AssetServer::get_load_group_progress`]. A [`LoadGroupCompleted`] event is sent once all
+    /// of them are loaded or failed to load.
+    pub fn create_load_group(&self) -> LoadGroupId {
+        let id = LoadGroupId(
+            self.server
+                .next_load_group_id
+                .fetch_add(1, Ordering::Relaxed),
+        );
+        self.server
+            .load_groups
+            .write()
+            .insert(id, LoadGroupInfo::default());
+        id
+    }
+
+    /// Queues an [`Asset`] for loading, like [`AssetServer::load`], and adds it to the load group.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_in_group<'a, T: Asset, P: Into<AssetPath<'a>>>(
+        &self,
+        group: LoadGroupId,
+        path: P,
+    ) -> Handle<T> {
+        self.load_untyped_in_group(group, path).typed()
+    }
+
+    /// Queues an asset for loading, like [`AssetServer::load_untyped`], and adds it to the load
+    /// group.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_untyped_in_group<'a, P: Into<AssetPath<'a>>>(
+        &self,
+        group: LoadGroupId,
+        path: P,
+    ) -> HandleUntyped {
+        let handle = self.load_untyped(path);
+        self.add_to_load_group(group, &handle);
+        handle
+    }
+
+    /// Adds an asset loaded by the [`AssetServer`] to the load group.
+    ///
+    /// Assets that were not loaded from a path are ignored.
+    pub fn add_to_load_group<H: Into<HandleId>>(&self, group: LoadGroupId, handle: H) {
+        if let Some(info) = self.server.load_groups.write().get_mut(&group) {
+            info.handles.push(handle.into());
+            info.completed = false;
+        }
+    }
+
+    /// Removes the load group, without unloading its assets.
+    pub fn remove_load_group(&self, group: LoadGroupId) {
+        self.server.load_groups.write().remove(&group);
+    }
+
+    /// Returns the progress of the load group, counting the dependencies of its assets, or `None`
+    /// if it doesn't exist.
+    pub fn get_load_group_progress(&self, group: LoadGroupId) -> Option<LoadGroupProgress> {
+        let load_groups = self.server.load_groups.read();
+        let info = load_groups.get(&group)?;
+        Some(load_group_progress(
+            &self.server.asset_sources.read(),
+            &info.handles,
+        ))
+    }
+}
+
+fn load_group_progress(
+    asset_sources: &HashMap<SourcePathId, SourceInfo>,
+    handles: &[HandleId],
+) -> LoadGroupProgress {
+    let mut progress = LoadGroupProgress::default();
+    let mut visited = HashSet::default();
+    let mut stack = handles
+        .iter()
+        .filter_map(|handle| match handle {
+            HandleId::AssetPathId(id) => Some(id.source_path_id()),
+            HandleId::Id(..) => None,
+        })
+        .collect::<Vec<_>>();
+    while let Some(source_path_id) = stack.pop() {
+        if !visited.insert(source_path_id) {
+            continue;
+        }
+        let source_info = match asset_sources.get(&source_path_id) {
+            Some(source_info) => source_info,
+            None => {
+                progress.pending += 1;
+                continue;
+            }
+        };
+        match source_info.load_state {
+            LoadState::Loaded => progress.loaded += 1,
+            LoadState::Failed => progress.failed += 1,
+            _ => progress.pending += 1,
+        }
+        if let Some(meta) = &source_info.meta {
+            stack.extend(
+                meta.assets
+                    .iter()
+                    .flat_map(|asset| &asset.dependencies)
+                    .map(|dependency| dependency.get_id().source_path_id()),
+            );
+        }
+    }
+    progress
+}
+
+/// Sends a [`LoadGroupCompleted`] event for each non-empty load group whose assets are all loaded
+/// or failed to load. It is sent again if assets are added to the group afterwards.
+pub fn load_group_system(
+    asset_server: Res<AssetServer>,
+    mut events: EventWriter<LoadGroupCompleted>,
+) {
+    let mut load_groups = asset_server.server.load_groups.write();
+    if load_groups.values().all(|info| info.completed) {
+        return;
+    }
+    let asset_sources = asset_server.server.asset_sources.read();
+    for (id, info) in load_groups.iter_mut() {
+        if info.completed {
+            continue;
+        }
+        let progress = load_group_progress(&asset_sources, &info.handles);
+        if !info.handles.is_empty() && progress.is_complete() {
+            info.completed = true;
+            events.send(LoadGroupCompleted { id: *id, progress });
+        }
+    }
+}
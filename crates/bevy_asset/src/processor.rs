@@ -0,0 +1,307 @@
+use crate::{AssetIoError, AssetServer, AssetServerError};
+use anyhow::Result;
+use bevy_utils::BoxedFuture;
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Transforms source assets into a format that is faster to load, like compressing a PNG image
+/// into a GPU texture format, before they are given to an [`AssetLoader`](crate::AssetLoader).
+///
+/// Processors are added with [`AssetServer::add_processor`]. The assets with one of the
+/// [`extensions`](AssetProcessor::extensions) of a processor are processed when they are
+/// loaded, then given to the loader of the
+/// [`processed_extension`](AssetProcessor::processed_extension).
+///
+/// When a processed asset folder is set with [`AssetServer::set_processed_asset_folder`], the
+/// processed assets are saved in it, and only processed again when their source changes. All the
+/// assets of a folder can be processed ahead of time, at build time or at startup, with
+/// [`AssetServer::process_folder`].
+pub trait AssetProcessor: Send + Sync + 'static {
+    /// Transforms the bytes of the source asset at `path` into the processed asset.
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>>;
+
+    /// The extensions of the source assets this processor transforms.
+    fn extensions(&self) -> &[&str];
+
+    /// The extension of the processed assets, used to find their [`AssetLoader`](crate::AssetLoader).
+    fn processed_extension(&self) -> &str;
+
+    /// The version of the processor. Increasing it processes the cached assets again, for
+    /// example when the processor settings change.
+    fn version(&self) -> u32 {
+        0
+    }
+}
+
+impl AssetServer {
+    /// Adds an [`AssetProcessor`], transforming the assets with its extensions when they are
+    /// loaded.
+    pub fn add_processor<T: AssetProcessor>(&self, processor: T) {
+        let mut processors = self.server.processors.write();
+        let processor_index = processors.len();
+        for extension in processor.extensions().iter() {
+            self.server
+                .extension_to_processor_index
+                .write()
+                .insert(extension.to_string(), processor_index);
+        }
+        processors.push(Arc::new(processor));
+    }
+
+    /// Sets the folder the processed assets are saved to, and loaded from when their source
+    /// hasn't changed. Without it, assets are processed every time they are loaded.
+    ///
+    /// The processed assets are also loaded from this folder when their source is missing, so
+    /// that a game can be shipped with only its processed assets.
+    pub fn set_processed_asset_folder<P: Into<PathBuf>>(&self, folder: Option<P>) {
+        *self.server.processed_asset_folder.write() = folder.map(Into::into);
+    }
+
+    /// Processes all the assets in the folder at `path` and its subfolders that have an
+    /// [`AssetProcessor`], saving them in the processed asset folder. Returns the number of
+    /// assets that were processed, skipping the ones whose source didn't change.
+    ///
+    /// ```no_run
+    /// # use bevy_asset::{AssetServer, FileAssetIo};
+    /// # use bevy_tasks::IoTaskPool;
+    /// // A small binary, run at build time
+    /// IoTaskPool::init(Default::default);
+    /// let asset_server = AssetServer::new(FileAssetIo::new("assets", false));
+    /// // asset_server.add_processor(...);
+    /// asset_server.set_processed_asset_folder(Some("imported_assets"));
+    /// let processed = futures_lite::future::block_on(asset_server.process_folder("")).unwrap();
+    /// println!("{} assets processed", processed);
+    /// ```
+    pub async fn process_folder<P: AsRef<Path>>(&self, path: P) -> Result<usize, AssetServerError> {
+        let path = path.as_ref();
+        if !self.asset_io().is_dir(path) {
+            return Err(AssetServerError::AssetFolderNotADirectory(
+                path.to_str().unwrap().to_string(),
+            ));
+        }
+
+        let mut processed = 0;
+        let mut folders = vec![path.to_owned()];
+        while let Some(folder) = folders.pop() {
+            let child_paths: Vec<_> = self.asset_io().read_directory(&folder)?.collect();
+            for child_path in child_paths {
+                if self.asset_io().is_dir(&child_path) {
+                    folders.push(child_path);
+                } else if let Some(processor) = self.get_path_asset_processor(&child_path) {
+                    if self.process_asset(&*processor, &child_path).await?.1 {
+                        processed += 1;
+                    }
+                }
+            }
+        }
+        Ok(processed)
+    }
+
+    pub(crate) fn get_path_asset_processor(&self, path: &Path) -> Option<Arc<dyn AssetProcessor>> {
+        let file_name = path.file_name()?.to_str()?.to_lowercase();
+        let extension_to_processor_index = self.server.extension_to_processor_index.read();
+        let mut ext = file_name.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            if let Some(&index) = extension_to_processor_index.get(ext) {
+                return Some(self.server.processors.read()[index].clone());
+            }
+        }
+        None
+    }
+
+    /// Returns the processed bytes of the asset at `path`, and whether it was processed rather
+    /// than read from the processed asset folder.
+    pub(crate) async fn process_asset(
+        &self,
+        processor: &dyn AssetProcessor,
+        path: &Path,
+    ) -> Result<(Vec<u8>, bool), AssetServerError> {
+        let processed_path = self
+            .server
+            .processed_asset_folder
+            .read()
+            .as_ref()
+            .map(|folder| processed_asset_path(folder, path, processor));
+
+        let source = match self.load_asset_bytes(path).await {
+            Ok(source) => source,
+            Err(AssetIoError::NotFound(_)) if processed_path.is_some() => {
+                let processed_path = processed_path.unwrap();
+                return match fs::read(&processed_path) {
+                    Ok(bytes) => Ok((bytes, false)),
+                    Err(_) => Err(AssetIoError::NotFound(processed_path).into()),
+                };
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let hash = source_hash(&source, processor);
+        if let Some(processed_path) = &processed_path {
+            if fs::read_to_string(hash_path(processed_path))
+                .ok()
+                .as_deref()
+                == Some(hash.as_str())
+            {
+                if let Ok(bytes) = fs::read(processed_path) {
+                    return Ok((bytes, false));
+                }
+            }
+        }
+
+        let bytes = processor
+            .process(&source, path)
+            .await
+            .map_err(AssetServerError::AssetProcessorError)?;
+        if let Some(processed_path) = &processed_path {
+            let saved = processed_path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::write(processed_path, &bytes))
+                .and_then(|_| fs::write(hash_path(processed_path), &hash));
+            if let Err(err) = saved {
+                bevy_log::warn!(
+                    "Failed to save the processed asset {:?}: {}",
+                    processed_path,
+                    err
+                );
+            }
+        }
+        Ok((bytes, true))
+    }
+}
+
+/// The path of the processed asset, keeping the source extension so that assets with the same
+/// name but different extensions don't collide: `textures/grass.png` is saved as
+/// `textures/grass.png.ktx2`.
+fn processed_asset_path(folder: &Path, path: &Path, processor: &dyn AssetProcessor) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_owned();
+    file_name.push(".");
+    file_name.push(processor.processed_extension());
+    folder.join(path).with_file_name(file_name)
+}
+
+fn hash_path(processed_path: &Path) -> PathBuf {
+    let mut file_name = processed_path.file_name().unwrap_or_default().to_owned();
+    file_name.push(".hash");
+    processed_path.with_file_name(file_name)
+}
+
+fn source_hash(source: &[u8], processor: &dyn AssetProcessor) -> String {
+    let mut hasher = DefaultHasher::new();
+    processor.version().hash(&mut hasher);
+    processor.processed_extension().hash(&mut hasher);
+    source.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        update_asset_storage_system, AssetLoader, Assets, FileAssetIo, Handle, LoadContext,
+        LoadedAsset,
+    };
+    use bevy_app::App;
+    use bevy_reflect::TypeUuid;
+    use bevy_tasks::IoTaskPool;
+
+    #[derive(Debug, TypeUuid)]
+    #[uuid = "0d9e9b1e-3c4c-4d1f-9d6f-6f8e6a3c9b21"]
+    struct Text(String);
+
+    struct UppercaseProcessor;
+
+    impl AssetProcessor for UppercaseProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            _path: &'a Path,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>> {
+            Box::pin(async move { Ok(bytes.to_ascii_uppercase()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+
+        fn processed_extension(&self) -> &str {
+            "upper"
+        }
+    }
+
+    struct UppercaseLoader;
+
+    impl AssetLoader for UppercaseLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let text = String::from_utf8(bytes.to_vec())?;
+                load_context.set_default_asset(LoadedAsset::new(Text(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["upper"]
+        }
+    }
+
+    #[test]
+    fn process_and_load_assets() {
+        let source_folder = tempfile::tempdir().unwrap();
+        let processed_folder = tempfile::tempdir().unwrap();
+        fs::create_dir(source_folder.path().join("texts")).unwrap();
+        fs::write(source_folder.path().join("texts/a.txt"), "hello").unwrap();
+        fs::write(source_folder.path().join("texts/b.txt"), "world").unwrap();
+        fs::write(source_folder.path().join("image.png"), "").unwrap();
+
+        IoTaskPool::init(Default::default);
+        let asset_server = AssetServer::new(FileAssetIo::new(source_folder.path(), false));
+        asset_server.add_processor(UppercaseProcessor);
+        asset_server.add_loader(UppercaseLoader);
+        asset_server.set_processed_asset_folder(Some(processed_folder.path()));
+        let process_folder = || futures_lite::future::block_on(asset_server.process_folder(""));
+
+        assert_eq!(process_folder().unwrap(), 2);
+        assert_eq!(
+            fs::read_to_string(processed_folder.path().join("texts/a.txt.upper")).unwrap(),
+            "HELLO"
+        );
+        // Only the changed sources are processed again
+        assert_eq!(process_folder().unwrap(), 0);
+        fs::write(source_folder.path().join("texts/b.txt"), "bevy").unwrap();
+        assert_eq!(process_folder().unwrap(), 1);
+
+        // Loads the processed asset, even when its source is missing
+        fs::remove_file(source_folder.path().join("texts/b.txt")).unwrap();
+        let mut app = App::new();
+        app.insert_resource(asset_server.register_asset_type::<Text>());
+        app.insert_resource(asset_server.clone());
+        app.add_system(update_asset_storage_system::<Text>);
+        let handle: Handle<Text> = asset_server.load("texts/b.txt");
+        for _ in 0..100 {
+            app.update();
+            if app.world.resource::<Assets<Text>>().contains(&handle) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(
+            app.world.resource::<Assets<Text>>().get(&handle).unwrap().0,
+            "BEVY"
+        );
+    }
+}
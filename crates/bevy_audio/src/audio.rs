@@ -1,5 +1,6 @@
 use crate::{AudioSink, AudioSource, Decodable};
-use bevy_asset::{Asset, Handle, HandleId};
+use bevy_asset::{Asset, AssetServer, Handle, HandleId};
+use bevy_ecs::world::{FromWorld, World};
 use parking_lot::RwLock;
 use std::{collections::VecDeque, fmt};
 
@@ -19,6 +20,8 @@ where
 {
     /// Queue for playing audio from asset handles
     pub(crate) queue: RwLock<VecDeque<AudioToPlay<Source>>>,
+    /// Used to return strong handles to the [`AudioSink`]s
+    asset_server: Option<AssetServer>,
 }
 
 impl<Source: Asset> fmt::Debug for Audio<Source>
@@ -30,13 +33,14 @@ where
     }
 }
 
-impl<Source> Default for Audio<Source>
+impl<Source> FromWorld for Audio<Source>
 where
     Source: Asset + Decodable,
 {
-    fn default() -> Self {
+    fn from_world(world: &mut World) -> Self {
         Self {
             queue: Default::default(),
+            asset_server: world.get_resource::<AssetServer>().cloned(),
         }
     }
 }
@@ -56,34 +60,31 @@ where
     /// }
     /// ```
     ///
-    /// Returns a weak [`Handle`] to the [`AudioSink`]. If this handle isn't changed to a
-    /// strong one, the sink will be detached and the sound will continue playing. Changing it
-    /// to a strong handle allows for control on the playback through the [`AudioSink`] asset.
+    /// Returns a strong [`Handle`] to the [`AudioSink`], which allows for control on the
+    /// playback through the [`AudioSink`] asset once the audio source is loaded. If this handle
+    /// is dropped, the sink is detached and the sound continues playing.
     ///
     /// ```
-    /// # use bevy_ecs::system::Res;
-    /// # use bevy_asset::{AssetServer, Assets};
+    /// # use bevy_ecs::system::{Commands, Res};
+    /// # use bevy_asset::{AssetServer, Handle};
     /// # use bevy_audio::{Audio, AudioSink};
+    /// struct Music(Handle<AudioSink>);
+    ///
     /// fn play_audio_system(
+    ///     mut commands: Commands,
     ///     asset_server: Res<AssetServer>,
     ///     audio: Res<Audio>,
-    ///     audio_sinks: Res<Assets<AudioSink>>,
     /// ) {
-    ///     // This is a weak handle, and can't be used to control playback.
-    ///     let weak_handle = audio.play(asset_server.load("my_sound.ogg"));
-    ///     // This is now a strong handle, and can be used to control playback.
-    ///     let strong_handle = audio_sinks.get_handle(weak_handle);
+    ///     let sink = audio.play(asset_server.load("my_sound.ogg"));
+    ///     // Keep the handle to pause, seek or stop the music later
+    ///     commands.insert_resource(Music(sink));
     /// }
     /// ```
+    ///
+    /// The handle is weak if there is no [`AssetServer`] when the [`Audio`] resource is created,
+    /// it can then be changed to a strong one with [`Assets::get_handle`](bevy_asset::Assets::get_handle).
     pub fn play(&self, audio_source: Handle<Source>) -> Handle<AudioSink> {
-        let id = HandleId::random::<AudioSink>();
-        let config = AudioToPlay {
-            settings: PlaybackSettings::ONCE,
-            sink_handle: id,
-            source_handle: audio_source,
-        };
-        self.queue.write().push_back(config);
-        Handle::<AudioSink>::weak(id)
+        self.play_with_settings(audio_source, PlaybackSettings::ONCE)
     }
 
     /// Play audio from a [`Handle`] to the audio source with [`PlaybackSettings`] that
@@ -108,14 +109,21 @@ where
         audio_source: Handle<Source>,
         settings: PlaybackSettings,
     ) -> Handle<AudioSink> {
-        let id = HandleId::random::<AudioSink>();
+        let sink_handle = self.sink_handle(HandleId::random::<AudioSink>());
         let config = AudioToPlay {
             settings,
-            sink_handle: id,
+            sink_handle: sink_handle.clone(),
             source_handle: audio_source,
         };
         self.queue.write().push_back(config);
-        Handle::<AudioSink>::weak(id)
+        sink_handle
+    }
+
+    fn sink_handle(&self, id: HandleId) -> Handle<AudioSink> {
+        match &self.asset_server {
+            Some(asset_server) => asset_server.get_handle(id),
+            None => Handle::weak(id),
+        }
     }
 }
 
@@ -128,6 +136,11 @@ pub struct PlaybackSettings {
     pub volume: f32,
     /// Speed to play at.
     pub speed: f32,
+    /// Mix the sound down to mono and pan it between the left and right channels, so that its
+    /// volume can follow the position of a [`SpatialAudioEmitter`](crate::SpatialAudioEmitter).
+    ///
+    /// Until an emitter updates them, both channels are at full volume.
+    pub spatial: bool,
 }
 
 impl Default for PlaybackSettings {
@@ -142,6 +155,7 @@ impl PlaybackSettings {
         repeat: false,
         volume: 1.0,
         speed: 1.0,
+        spatial: false,
     };
 
     /// Will play the associate audio source in a loop.
@@ -149,6 +163,7 @@ impl PlaybackSettings {
         repeat: true,
         volume: 1.0,
         speed: 1.0,
+        spatial: false,
     };
 
     /// Helper to set the volume from start of playback.
@@ -162,6 +177,12 @@ impl PlaybackSettings {
         self.speed = speed;
         self
     }
+
+    /// Helper to play the sound as a spatial sound, see [`PlaybackSettings::spatial`].
+    pub const fn with_spatial(mut self, spatial: bool) -> Self {
+        self.spatial = spatial;
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -169,7 +190,9 @@ pub(crate) struct AudioToPlay<Source>
 where
     Source: Asset + Decodable,
 {
-    pub(crate) sink_handle: HandleId,
+    /// Kept until the sink is added, so that its id isn't freed while the source is loading if
+    /// the handle returned to the user is dropped
+    pub(crate) sink_handle: Handle<AudioSink>,
     pub(crate) source_handle: Handle<Source>,
     pub(crate) settings: PlaybackSettings,
 }
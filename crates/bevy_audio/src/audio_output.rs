@@ -1,10 +1,21 @@
-use crate::{Audio, AudioSource, Decodable};
+use crate::{Audio, AudioSource, Decodable, PlaybackSettings};
 use bevy_asset::{Asset, Assets};
 use bevy_ecs::system::{NonSend, Res, ResMut};
 use bevy_reflect::TypeUuid;
-use bevy_utils::tracing::warn;
-use rodio::{OutputStream, OutputStreamHandle, Sink, Source};
-use std::marker::PhantomData;
+use bevy_tasks::AsyncComputeTaskPool;
+use bevy_utils::{tracing::warn, Duration};
+use parking_lot::Mutex;
+use rodio::{
+    source::{ChannelVolume, SkipDuration},
+    OutputStream, OutputStreamHandle, Sink, Source,
+};
+use std::{
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 /// Used internally to play audio on the current "audio device"
 pub struct AudioOutput<Source = AudioSource>
@@ -40,17 +51,39 @@ where
 
 impl<Source> AudioOutput<Source>
 where
-    Source: Asset + Decodable,
+    Source: Asset + Decodable + Clone,
 {
-    fn play_source(&self, audio_source: &Source, repeat: bool) -> Option<Sink> {
+    fn play_source(&self, audio_source: &Source, settings: &PlaybackSettings) -> Option<AudioSink> {
         self.stream_handle.as_ref().map(|stream_handle| {
             let sink = Sink::try_new(stream_handle).unwrap();
-            if repeat {
-                sink.append(audio_source.decoder().repeat_infinite());
-            } else {
-                sink.append(audio_source.decoder());
+            let seek = Arc::new(SeekRequest::new(audio_source.clone()));
+            let source = SeekableSource::new(settings.repeat, seek.clone());
+            let spatial =
+                if settings.spatial {
+                    let controls = Arc::new(SpatialControls::default());
+                    let access_controls = controls.clone();
+                    // Mixed down to mono, then played on the left and right channels
+                    let source = ChannelVolume::new(source.speed(1.0), vec![0.0, 0.0])
+                        .periodic_access(Duration::from_millis(5), move |source| {
+                            // The channels of the source are summed when mixed down
+                            let channels = source.inner().channels().max(1) as f32;
+                            source.set_volume(0, access_controls.left() / channels);
+                            source.set_volume(1, access_controls.right() / channels);
+                            source
+                                .inner_mut()
+                                .set_factor(access_controls.doppler_factor());
+                        });
+                    sink.append(source);
+                    Some(controls)
+                } else {
+                    sink.append(source);
+                    None
+                };
+            AudioSink {
+                sink: Some(sink),
+                seek,
+                spatial,
             }
-            sink
         })
     }
 
@@ -66,12 +99,13 @@ where
         while i < len {
             let config = queue.pop_front().unwrap();
             if let Some(audio_source) = audio_sources.get(&config.source_handle) {
-                if let Some(sink) = self.play_source(audio_source, config.settings.repeat) {
+                if let Some(sink) = self.play_source(audio_source, &config.settings) {
                     sink.set_speed(config.settings.speed);
                     sink.set_volume(config.settings.volume);
 
-                    // don't keep the strong handle. there is no way to return it to the user here as it is async
-                    let _ = sinks.set(config.sink_handle, AudioSink { sink: Some(sink) });
+                    // the sink is kept alive by the handle returned to the user if any, the one
+                    // of the queue is dropped with it
+                    sinks.set_untracked(&config.sink_handle, sink);
                 }
             } else {
                 // audio source hasn't loaded yet. add it back to the queue
@@ -83,7 +117,7 @@ where
 }
 
 /// Plays audio currently queued in the [`Audio`] resource through the [`AudioOutput`] resource
-pub fn play_queued_audio_system<Source: Asset + Decodable>(
+pub fn play_queued_audio_system<Source: Asset + Decodable + Clone>(
     audio_output: NonSend<AudioOutput<Source>>,
     audio_sources: Option<Res<Assets<Source>>>,
     mut audio: ResMut<Audio<Source>>,
@@ -94,6 +128,166 @@ pub fn play_queued_audio_system<Source: Asset + Decodable>(
     };
 }
 
+/// Seeks the sound played by an [`AudioSink`]
+trait Seek: Send + Sync {
+    fn seek(self: Arc<Self>, position: Duration);
+}
+
+/// The positions requested by [`AudioSink::seek`], shared with the source it plays.
+///
+/// The audio source is decoded up to the position on the [`AsyncComputeTaskPool`], then the
+/// playing source swaps its decoder with the new one, so that the audio thread never decodes
+/// more than the samples it plays.
+struct SeekRequest<S: Decodable> {
+    source: S,
+    /// Incremented by each request, so that a decoder finished late doesn't replace the one of a
+    /// later request
+    generation: AtomicU64,
+    /// The decoder of the latest request, once it's decoded up to the position
+    decoder: Mutex<Option<SkipDuration<S::Decoder>>>,
+    /// Whether `decoder` is set, checked for every sample without locking
+    ready: AtomicBool,
+}
+
+impl<S: Decodable> SeekRequest<S> {
+    fn new(source: S) -> Self {
+        Self {
+            source,
+            generation: AtomicU64::new(0),
+            decoder: Mutex::new(None),
+            ready: AtomicBool::new(false),
+        }
+    }
+
+    /// Starts a new request, returning its generation
+    fn request(&self) -> u64 {
+        self.generation.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Decodes the source up to `position` for the request of the given generation, unless
+    /// another request was started since
+    fn decode(&self, generation: u64, position: Duration) {
+        let decoder = self.source.decoder().skip_duration(position);
+        let mut slot = self.decoder.lock();
+        if self.generation.load(Ordering::Relaxed) == generation {
+            *slot = Some(decoder);
+            self.ready.store(true, Ordering::Release);
+        }
+    }
+
+    /// Takes the decoder of the latest request if it's ready
+    fn take(&self) -> Option<SkipDuration<S::Decoder>> {
+        if !self.ready.load(Ordering::Acquire) {
+            return None;
+        }
+        // Never block the audio thread, the decoder is taken on a later sample instead
+        let mut slot = self.decoder.try_lock()?;
+        self.ready.store(false, Ordering::Relaxed);
+        slot.take()
+    }
+}
+
+impl<S: Decodable> Seek for SeekRequest<S> {
+    fn seek(self: Arc<Self>, position: Duration) {
+        let generation = self.request();
+        AsyncComputeTaskPool::get()
+            .spawn(async move { self.decode(generation, position) })
+            .detach();
+    }
+}
+
+/// The volumes and pitch of a sound played with [`PlaybackSettings::spatial`], shared with the
+/// source it plays
+#[derive(Debug)]
+struct SpatialControls {
+    left: AtomicU32,
+    right: AtomicU32,
+    doppler_factor: AtomicU32,
+}
+
+impl Default for SpatialControls {
+    fn default() -> Self {
+        // Played at full volume until the volumes are computed from the position of an emitter,
+        // so that the sound can be heard without one
+        Self {
+            left: AtomicU32::new(1.0f32.to_bits()),
+            right: AtomicU32::new(1.0f32.to_bits()),
+            doppler_factor: AtomicU32::new(1.0f32.to_bits()),
+        }
+    }
+}
+
+impl SpatialControls {
+    fn left(&self) -> f32 {
+        f32::from_bits(self.left.load(Ordering::Relaxed))
+    }
+
+    fn right(&self) -> f32 {
+        f32::from_bits(self.right.load(Ordering::Relaxed))
+    }
+
+    fn doppler_factor(&self) -> f32 {
+        f32::from_bits(self.doppler_factor.load(Ordering::Relaxed))
+    }
+}
+
+/// Decodes an audio source from a position, which can be changed while it is playing, and
+/// starts it over when it ends if it repeats.
+struct SeekableSource<S: Decodable> {
+    decoder: SkipDuration<S::Decoder>,
+    repeat: bool,
+    seek: Arc<SeekRequest<S>>,
+}
+
+impl<S: Decodable> SeekableSource<S> {
+    fn new(repeat: bool, seek: Arc<SeekRequest<S>>) -> Self {
+        Self {
+            decoder: seek.source.decoder().skip_duration(Duration::ZERO),
+            repeat,
+            seek,
+        }
+    }
+}
+
+impl<S: Decodable> Iterator for SeekableSource<S> {
+    type Item = S::DecoderItem;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(decoder) = self.seek.take() {
+            self.decoder = decoder;
+        }
+        match self.decoder.next() {
+            None if self.repeat => {
+                self.decoder = self.seek.source.decoder().skip_duration(Duration::ZERO);
+                self.decoder.next()
+            }
+            sample => sample,
+        }
+    }
+}
+
+impl<S: Decodable> Source for SeekableSource<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.decoder.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.decoder.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.decoder.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        if self.repeat {
+            None
+        } else {
+            self.decoder.total_duration()
+        }
+    }
+}
+
 /// Asset controlling the playback of a sound
 ///
 /// ```
@@ -122,6 +316,8 @@ pub struct AudioSink {
     // This field is an Option in order to allow us to have a safe drop that will detach the sink.
     // It will never be None during its life
     sink: Option<Sink>,
+    seek: Arc<dyn Seek>,
+    spatial: Option<Arc<SpatialControls>>,
 }
 
 impl Drop for AudioSink {
@@ -170,21 +366,67 @@ impl AudioSink {
         self.sink.as_ref().unwrap().play();
     }
 
+    /// Resumes playback of a paused sink, same as [`play`](Self::play).
+    ///
+    /// No effect if not paused.
+    pub fn resume(&self) {
+        self.play();
+    }
+
     /// Pauses playback of this sink.
     ///
     /// No effect if already paused.
-    /// A paused sink can be resumed with [`play`](Self::play).
+    /// A paused sink can be resumed with [`resume`](Self::resume).
     pub fn pause(&self) {
         self.sink.as_ref().unwrap().pause();
     }
 
     /// Is this sink paused?
     ///
-    /// Sinks can be paused and resumed using [`pause`](Self::pause) and [`play`](Self::play).
+    /// Sinks can be paused and resumed using [`pause`](Self::pause) and [`resume`](Self::resume).
     pub fn is_paused(&self) -> bool {
         self.sink.as_ref().unwrap().is_paused()
     }
 
+    /// Moves the playback to the given position from the start of the sound.
+    ///
+    /// The sound is decoded again from its start up to the position in the background, on the
+    /// [`AsyncComputeTaskPool`], and keeps playing from where it was until then. Seeking past
+    /// the end ends the sound, or starts it over if it repeats.
+    pub fn seek(&self, position: Duration) {
+        self.seek.clone().seek(position);
+    }
+
+    /// Is this sink played with [`PlaybackSettings::spatial`]?
+    pub fn is_spatial(&self) -> bool {
+        self.spatial.is_some()
+    }
+
+    /// Changes the volumes of the left and right channels of a spatial sound, on top of its
+    /// [`volume`](Self::volume).
+    ///
+    /// They are updated from the position of its [`SpatialAudioEmitter`](crate::SpatialAudioEmitter)
+    /// if it has one. No effect if the sound isn't spatial.
+    pub fn set_spatial_volumes(&self, left: f32, right: f32) {
+        if let Some(spatial) = &self.spatial {
+            spatial.left.store(left.to_bits(), Ordering::Relaxed);
+            spatial.right.store(right.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// Changes the factor applied to the speed of a spatial sound by the doppler effect, on top
+    /// of its [`speed`](Self::speed).
+    ///
+    /// It is updated from the velocity of its [`SpatialAudioEmitter`](crate::SpatialAudioEmitter)
+    /// if it has one. No effect if the sound isn't spatial.
+    pub fn set_doppler_factor(&self, factor: f32) {
+        if let Some(spatial) = &self.spatial {
+            spatial
+                .doppler_factor
+                .store(factor.to_bits(), Ordering::Relaxed);
+        }
+    }
+
     /// Stops the sink.
     ///
     /// It won't be possible to restart it afterwards.
@@ -192,3 +434,126 @@ impl AudioSink {
         self.sink.as_ref().unwrap().stop();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Handle, HandleId};
+    use bevy_tasks::IoTaskPool;
+    use rodio::buffer::SamplesBuffer;
+
+    /// A mono source at 10 samples per second, the samples being their index
+    #[derive(Clone)]
+    struct Samples(Vec<i16>);
+
+    impl Decodable for Samples {
+        type Decoder = SamplesBuffer<i16>;
+        type DecoderItem = i16;
+
+        fn decoder(&self) -> Self::Decoder {
+            SamplesBuffer::new(1, 10, self.0.clone())
+        }
+    }
+
+    fn samples(len: i16) -> Samples {
+        Samples((0..len).collect())
+    }
+
+    #[test]
+    fn repeat_starts_over() {
+        let seek = Arc::new(SeekRequest::new(samples(3)));
+        let source = SeekableSource::new(true, seek);
+        assert_eq!(
+            source.take(7).collect::<Vec<_>>(),
+            vec![0, 1, 2, 0, 1, 2, 0]
+        );
+    }
+
+    #[test]
+    fn seek_once_decoded() {
+        let seek = Arc::new(SeekRequest::new(samples(20)));
+        let mut source = SeekableSource::new(false, seek.clone());
+        assert_eq!(source.next(), Some(0));
+
+        // The sound keeps playing until the decoder of the request is ready
+        let generation = seek.request();
+        assert_eq!(source.next(), Some(1));
+        seek.decode(generation, Duration::from_secs(1));
+        assert_eq!(source.next(), Some(10));
+        assert_eq!(source.next(), Some(11));
+    }
+
+    #[test]
+    fn only_the_latest_seek_is_applied() {
+        let seek = Arc::new(SeekRequest::new(samples(20)));
+        let mut source = SeekableSource::new(false, seek.clone());
+
+        let first = seek.request();
+        let second = seek.request();
+        seek.decode(second, Duration::from_millis(1500));
+        // Finishing after the second request, the first one is ignored
+        seek.decode(first, Duration::from_millis(500));
+        assert_eq!(source.next(), Some(15));
+        assert_eq!(source.next(), Some(16));
+    }
+
+    #[test]
+    fn spatial_sounds_are_heard_without_an_emitter() {
+        let controls = SpatialControls::default();
+        assert_eq!(controls.left(), 1.0);
+        assert_eq!(controls.right(), 1.0);
+        assert_eq!(controls.doppler_factor(), 1.0);
+    }
+
+    #[test]
+    fn queued_sink_is_kept_until_added() {
+        IoTaskPool::init(Default::default);
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin)
+            .add_asset::<AudioSource>()
+            .add_asset::<AudioSink>()
+            .init_resource::<Audio>();
+
+        // The returned handle is dropped while the source is loading
+        let source = Handle::weak(HandleId::random::<AudioSource>());
+        let sink_id = app.world.resource::<Audio>().play(source).id;
+        app.update();
+        app.update();
+
+        // Once loaded, the sink is added with the handle of the queue, as by `try_play_queued`
+        let config = app
+            .world
+            .resource::<Audio>()
+            .queue
+            .write()
+            .pop_front()
+            .unwrap();
+        let (sink, _output) = Sink::new_idle();
+        let sink = AudioSink {
+            sink: Some(sink),
+            seek: Arc::new(SeekRequest::new(samples(1))),
+            spatial: None,
+        };
+        app.world
+            .resource_mut::<Assets<AudioSink>>()
+            .set_untracked(&config.sink_handle, sink);
+        app.update();
+        assert!(app
+            .world
+            .resource::<Assets<AudioSink>>()
+            .get(&Handle::weak(sink_id))
+            .is_some());
+
+        // Without any handle left, the sink is freed instead of being leaked
+        drop(config);
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(app
+            .world
+            .resource::<Assets<AudioSink>>()
+            .get(&Handle::weak(sink_id))
+            .is_none());
+    }
+}
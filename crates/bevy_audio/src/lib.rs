@@ -25,19 +25,26 @@
 mod audio;
 mod audio_output;
 mod audio_source;
+mod spatial;
 
 #[allow(missing_docs)]
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Audio, AudioOutput, AudioSource, Decodable, PlaybackSettings};
+    pub use crate::{
+        Audio, AudioListener, AudioOutput, AudioSource, Decodable, PlaybackSettings,
+        SpatialAudioEmitter,
+    };
 }
 
 pub use audio::*;
 pub use audio_output::*;
 pub use audio_source::*;
+pub use spatial::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::AddAsset;
+use bevy_ecs::schedule::ParallelSystemDescriptorCoercion;
+use bevy_transform::TransformSystem;
 
 /// Adds support for audio playback to a Bevy Application
 ///
@@ -54,6 +61,12 @@ impl Plugin for AudioPlugin {
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 play_queued_audio_system::<AudioSource>,
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_spatial_audio
+                    .after(play_queued_audio_system::<AudioSource>)
+                    .after(TransformSystem::TransformPropagate),
             );
 
         #[cfg(any(feature = "mp3", feature = "flac", feature = "wav", feature = "vorbis"))]
@@ -0,0 +1,362 @@
+use crate::AudioSink;
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    prelude::Component,
+    system::{Local, Query, Res},
+};
+use bevy_math::Vec3;
+use bevy_time::Time;
+use bevy_transform::components::GlobalTransform;
+
+/// How the volume of a [`SpatialAudioEmitter`] decreases with its distance to the
+/// [`AudioListener`].
+///
+/// Below their reference distance, sounds are played at their full volume.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Attenuation {
+    /// The volume doesn't depend on the distance.
+    None,
+    /// The volume decreases linearly from full volume at `min_distance` to silence at
+    /// `max_distance`.
+    Linear {
+        /// The distance below which the sound is played at full volume
+        min_distance: f32,
+        /// The distance above which the sound is silent
+        max_distance: f32,
+    },
+    /// The volume is `reference_distance / (reference_distance + rolloff * (distance -
+    /// reference_distance))`, which sounds natural with a `rolloff` of `1.0`.
+    Inverse {
+        /// The distance below which the sound is played at full volume
+        reference_distance: f32,
+        /// How fast the volume decreases with the distance
+        rolloff: f32,
+    },
+    /// The volume is `(distance / reference_distance) ^ -rolloff`.
+    Exponential {
+        /// The distance below which the sound is played at full volume
+        reference_distance: f32,
+        /// How fast the volume decreases with the distance
+        rolloff: f32,
+    },
+}
+
+impl Default for Attenuation {
+    fn default() -> Self {
+        Attenuation::Inverse {
+            reference_distance: 1.0,
+            rolloff: 1.0,
+        }
+    }
+}
+
+impl Attenuation {
+    /// Returns the volume of a sound at the given distance, between `0.0` and `1.0`.
+    pub fn volume(&self, distance: f32) -> f32 {
+        match *self {
+            Attenuation::None => 1.0,
+            Attenuation::Linear {
+                min_distance,
+                max_distance,
+            } => {
+                if distance <= min_distance {
+                    1.0
+                } else if distance >= max_distance {
+                    0.0
+                } else {
+                    1.0 - (distance - min_distance) / (max_distance - min_distance)
+                }
+            }
+            Attenuation::Inverse {
+                reference_distance,
+                rolloff,
+            } => {
+                let distance = distance.max(reference_distance);
+                reference_distance
+                    / (reference_distance + rolloff * (distance - reference_distance))
+            }
+            Attenuation::Exponential {
+                reference_distance,
+                rolloff,
+            } => {
+                let distance = distance.max(reference_distance);
+                (distance / reference_distance).powf(-rolloff)
+            }
+        }
+    }
+}
+
+/// The entity hearing the [`SpatialAudioEmitter`]s, usually the camera.
+///
+/// Its right ear is towards the X axis of its [`GlobalTransform`]. Only one listener is used.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct AudioListener {
+    /// The speed of sound in world units per second, used for the doppler effect.
+    pub speed_of_sound: f32,
+}
+
+impl Default for AudioListener {
+    fn default() -> Self {
+        // In meters per second, in the air
+        Self {
+            speed_of_sound: 343.0,
+        }
+    }
+}
+
+/// Plays a sound from the position of the entity, relative to the [`AudioListener`].
+///
+/// The sound must be played with [`PlaybackSettings::spatial`](crate::PlaybackSettings::spatial).
+/// Every frame, its volume on the left and right channels is set from the distance and the
+/// direction of the entity to the listener, and its speed changes with their velocities if the
+/// doppler effect is enabled.
+///
+/// ```
+/// # use bevy_ecs::system::{Commands, Res};
+/// # use bevy_asset::AssetServer;
+/// # use bevy_audio::{Audio, PlaybackSettings, SpatialAudioEmitter};
+/// # use bevy_transform::prelude::{GlobalTransform, Transform};
+/// fn play_spatial_audio_system(
+///     mut commands: Commands,
+///     asset_server: Res<AssetServer>,
+///     audio: Res<Audio>,
+/// ) {
+///     let sink = audio.play_with_settings(
+///         asset_server.load("engine.ogg"),
+///         PlaybackSettings::LOOP.with_spatial(true),
+///     );
+///     commands.spawn_bundle((
+///         Transform::from_xyz(10.0, 0.0, 0.0),
+///         GlobalTransform::default(),
+///         SpatialAudioEmitter::new(sink).with_doppler(true),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone)]
+pub struct SpatialAudioEmitter {
+    /// The sound played by the emitter
+    pub sink: Handle<AudioSink>,
+    /// How the volume decreases with the distance to the listener
+    pub attenuation: Attenuation,
+    /// Whether the speed of the sound changes with the velocities of the emitter and the
+    /// listener
+    pub doppler: bool,
+    /// The position of the emitter on the previous update, to compute its velocity
+    previous_position: Option<Vec3>,
+}
+
+impl SpatialAudioEmitter {
+    /// Creates an emitter of the sound, with the default [`Attenuation`] and no doppler effect.
+    pub fn new(sink: Handle<AudioSink>) -> Self {
+        Self {
+            sink,
+            attenuation: Attenuation::default(),
+            doppler: false,
+            previous_position: None,
+        }
+    }
+
+    /// Helper to set the [`Attenuation`] of the emitter.
+    pub fn with_attenuation(mut self, attenuation: Attenuation) -> Self {
+        self.attenuation = attenuation;
+        self
+    }
+
+    /// Helper to enable the doppler effect of the emitter.
+    pub fn with_doppler(mut self, doppler: bool) -> Self {
+        self.doppler = doppler;
+        self
+    }
+}
+
+/// Returns the volumes of the left and right channels of a sound at the given offset from the
+/// listener, in the space of the listener.
+fn spatial_volumes(offset: Vec3, attenuation: &Attenuation) -> (f32, f32) {
+    let distance = offset.length();
+    let volume = attenuation.volume(distance);
+    // From -1.0 on the left to 1.0 on the right, sounds in front and behind are centered
+    let pan = if distance > f32::EPSILON {
+        offset.x / distance
+    } else {
+        0.0
+    };
+    (volume * (1.0 - pan).min(1.0), volume * (1.0 + pan).min(1.0))
+}
+
+/// Returns the factor applied to the speed of a sound by the doppler effect, for an emitter at
+/// the given offset from the listener.
+fn doppler_factor(
+    offset: Vec3,
+    listener_velocity: Vec3,
+    emitter_velocity: Vec3,
+    speed_of_sound: f32,
+) -> f32 {
+    let direction = offset.normalize_or_zero();
+    // The speeds are positive when the listener and the emitter move towards each other, and
+    // kept below the speed of sound
+    let max_speed = speed_of_sound * 0.5;
+    let listener_speed = listener_velocity
+        .dot(direction)
+        .clamp(-max_speed, max_speed);
+    let emitter_speed = (-emitter_velocity.dot(direction)).clamp(-max_speed, max_speed);
+    (speed_of_sound + listener_speed) / (speed_of_sound - emitter_speed)
+}
+
+/// Updates the volumes and the doppler effect of the [`SpatialAudioEmitter`]s from their position
+/// relative to the [`AudioListener`].
+pub fn update_spatial_audio(
+    time: Res<Time>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    mut previous_listener_position: Local<Option<Vec3>>,
+    listener_query: Query<(&GlobalTransform, &AudioListener)>,
+    mut emitter_query: Query<(&GlobalTransform, &mut SpatialAudioEmitter)>,
+) {
+    let (listener_transform, listener) = match listener_query.iter().next() {
+        Some(listener) => listener,
+        None => return,
+    };
+    let delta_seconds = time.delta_seconds();
+    let velocity = |previous_position: Option<Vec3>, position: Vec3| match previous_position {
+        Some(previous_position) if delta_seconds > 0.0 => {
+            (position - previous_position) / delta_seconds
+        }
+        _ => Vec3::ZERO,
+    };
+
+    let listener_position = listener_transform.translation;
+    let listener_velocity = velocity(*previous_listener_position, listener_position);
+    *previous_listener_position = Some(listener_position);
+    let to_listener_space = listener_transform.rotation.inverse();
+
+    for (transform, mut emitter) in emitter_query.iter_mut() {
+        let position = transform.translation;
+        let emitter_velocity = velocity(emitter.previous_position, position);
+        emitter.previous_position = Some(position);
+
+        let sink = match audio_sinks.get(&emitter.sink) {
+            Some(sink) => sink,
+            None => continue,
+        };
+        let offset = position - listener_position;
+        let (left, right) = spatial_volumes(to_listener_space * offset, &emitter.attenuation);
+        sink.set_spatial_volumes(left, right);
+        if emitter.doppler {
+            sink.set_doppler_factor(doppler_factor(
+                offset,
+                listener_velocity,
+                emitter_velocity,
+                listener.speed_of_sound,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_volume(attenuation: Attenuation, distance: f32, volume: f32) {
+        let actual = attenuation.volume(distance);
+        assert!(
+            (actual - volume).abs() < 1e-6,
+            "{:?} at {}: {} instead of {}",
+            attenuation,
+            distance,
+            actual,
+            volume
+        );
+    }
+
+    #[test]
+    fn linear_attenuation() {
+        let attenuation = Attenuation::Linear {
+            min_distance: 2.0,
+            max_distance: 6.0,
+        };
+        assert_volume(attenuation, 0.0, 1.0);
+        assert_volume(attenuation, 2.0, 1.0);
+        assert_volume(attenuation, 3.0, 0.75);
+        assert_volume(attenuation, 5.0, 0.25);
+        assert_volume(attenuation, 6.0, 0.0);
+        assert_volume(attenuation, 100.0, 0.0);
+    }
+
+    #[test]
+    fn inverse_attenuation() {
+        let attenuation = Attenuation::Inverse {
+            reference_distance: 2.0,
+            rolloff: 1.0,
+        };
+        assert_volume(attenuation, 1.0, 1.0);
+        assert_volume(attenuation, 4.0, 0.5);
+        assert_volume(attenuation, 8.0, 0.25);
+        let attenuation = Attenuation::Inverse {
+            reference_distance: 2.0,
+            rolloff: 0.5,
+        };
+        assert_volume(attenuation, 6.0, 0.5);
+        assert_eq!(Attenuation::default().volume(0.5), 1.0);
+    }
+
+    #[test]
+    fn exponential_attenuation() {
+        let attenuation = Attenuation::Exponential {
+            reference_distance: 2.0,
+            rolloff: 2.0,
+        };
+        assert_volume(attenuation, 1.0, 1.0);
+        assert_volume(attenuation, 4.0, 0.25);
+        assert_volume(attenuation, 20.0, 0.01);
+    }
+
+    #[test]
+    fn no_attenuation() {
+        assert_volume(Attenuation::None, 0.0, 1.0);
+        assert_volume(Attenuation::None, 1000.0, 1.0);
+    }
+
+    #[test]
+    fn sounds_are_panned_to_the_nearest_ear() {
+        let attenuation = Attenuation::Linear {
+            min_distance: 1.0,
+            max_distance: 3.0,
+        };
+        assert_eq!(spatial_volumes(Vec3::ZERO, &attenuation), (1.0, 1.0));
+        assert_eq!(
+            spatial_volumes(Vec3::new(0.0, 0.0, -1.0), &attenuation),
+            (1.0, 1.0)
+        );
+        assert_eq!(spatial_volumes(Vec3::X, &attenuation), (0.0, 1.0));
+        assert_eq!(spatial_volumes(-Vec3::X, &attenuation), (1.0, 0.0));
+        // Attenuated, and louder on the right
+        let (left, right) = spatial_volumes(Vec3::new(1.0, 0.0, -1.0), &attenuation);
+        assert!((right - (1.0 - (2.0_f32.sqrt() - 1.0) / 2.0)).abs() < 1e-6);
+        assert!(left < right);
+    }
+
+    #[test]
+    fn doppler_effect() {
+        let offset = Vec3::new(10.0, 0.0, 0.0);
+        assert_eq!(doppler_factor(offset, Vec3::ZERO, Vec3::ZERO, 340.0), 1.0);
+        // The emitter moves towards the listener
+        assert_eq!(
+            doppler_factor(offset, Vec3::ZERO, Vec3::new(-170.0, 0.0, 0.0), 340.0),
+            2.0
+        );
+        // The listener moves away from the emitter
+        assert_eq!(
+            doppler_factor(offset, Vec3::new(-170.0, 0.0, 0.0), Vec3::ZERO, 340.0),
+            0.5
+        );
+        // Moving sideways doesn't change the pitch
+        assert_eq!(
+            doppler_factor(offset, Vec3::Y, Vec3::new(0.0, 0.0, 50.0), 340.0),
+            1.0
+        );
+        // The speeds are kept below the speed of sound
+        assert_eq!(
+            doppler_factor(offset, Vec3::ZERO, Vec3::new(-1000.0, 0.0, 0.0), 340.0),
+            2.0
+        );
+    }
+}
@@ -0,0 +1,82 @@
+struct BloomUniform {
+    threshold: f32;
+    intensity: f32;
+};
+
+[[group(0), binding(0)]]
+var source_texture: texture_2d<f32>;
+[[group(0), binding(1)]]
+var source_sampler: sampler;
+[[group(0), binding(2)]]
+var<uniform> settings: BloomUniform;
+
+// Keeps the part of the color brighter than the threshold
+fn threshold(color: vec3<f32>) -> vec3<f32> {
+    let brightness = max(color.r, max(color.g, color.b));
+    let contribution = max(brightness - settings.threshold, 0.0) / max(brightness, 0.0001);
+    return color * contribution;
+}
+
+// 13 taps downsampling filter, from Call of Duty: Advanced Warfare
+// http://www.iryoku.com/next-generation-post-processing-in-call-of-duty-advanced-warfare
+fn sample_13_tap(uv: vec2<f32>) -> vec3<f32> {
+    let texel = 1.0 / vec2<f32>(textureDimensions(source_texture));
+
+    let a = textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(-2.0, -2.0)).rgb;
+    let b = textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(0.0, -2.0)).rgb;
+    let c = textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(2.0, -2.0)).rgb;
+    let d = textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(-1.0, -1.0)).rgb;
+    let e = textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(1.0, -1.0)).rgb;
+    let f = textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(-2.0, 0.0)).rgb;
+    let g = textureSample(source_texture, source_sampler, uv).rgb;
+    let h = textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(2.0, 0.0)).rgb;
+    let i = textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(-1.0, 1.0)).rgb;
+    let j = textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(1.0, 1.0)).rgb;
+    let k = textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(-2.0, 2.0)).rgb;
+    let l = textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(0.0, 2.0)).rgb;
+    let m = textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(2.0, 2.0)).rgb;
+
+    // The center box weighs 0.5, and the four corner boxes 0.125 each
+    var result = (d + e + i + j) * 0.125;
+    result = result + (a + b + f + g) * 0.03125;
+    result = result + (b + c + g + h) * 0.03125;
+    result = result + (f + g + k + l) * 0.03125;
+    result = result + (g + h + l + m) * 0.03125;
+    return result;
+}
+
+// 3x3 tent upsampling filter
+fn sample_3x3_tent(uv: vec2<f32>) -> vec3<f32> {
+    let texel = 1.0 / vec2<f32>(textureDimensions(source_texture));
+
+    var result = textureSample(source_texture, source_sampler, uv).rgb * 4.0;
+    result = result + textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(-1.0, 0.0)).rgb * 2.0;
+    result = result + textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(1.0, 0.0)).rgb * 2.0;
+    result = result + textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(0.0, -1.0)).rgb * 2.0;
+    result = result + textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(0.0, 1.0)).rgb * 2.0;
+    result = result + textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(-1.0, -1.0)).rgb;
+    result = result + textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(1.0, -1.0)).rgb;
+    result = result + textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(-1.0, 1.0)).rgb;
+    result = result + textureSample(source_texture, source_sampler, uv + texel * vec2<f32>(1.0, 1.0)).rgb;
+    return result / 16.0;
+}
+
+[[stage(fragment)]]
+fn downsample_first([[location(0)]] uv: vec2<f32>) -> [[location(0)]] vec4<f32> {
+    return vec4<f32>(threshold(sample_13_tap(uv)), 1.0);
+}
+
+[[stage(fragment)]]
+fn downsample([[location(0)]] uv: vec2<f32>) -> [[location(0)]] vec4<f32> {
+    return vec4<f32>(sample_13_tap(uv), 1.0);
+}
+
+[[stage(fragment)]]
+fn upsample([[location(0)]] uv: vec2<f32>) -> [[location(0)]] vec4<f32> {
+    return vec4<f32>(sample_3x3_tent(uv), 1.0);
+}
+
+[[stage(fragment)]]
+fn upsample_final([[location(0)]] uv: vec2<f32>) -> [[location(0)]] vec4<f32> {
+    return vec4<f32>(sample_3x3_tent(uv) * settings.intensity, 1.0);
+}
@@ -0,0 +1,312 @@
+mod node;
+
+pub use node::BloomNode;
+
+use crate::{
+    core_3d::{Camera3d, ViewHdrTexture},
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+};
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_math::UVec2;
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    extract_component::UniformComponentPlugin,
+    render_resource::*,
+    renderer::RenderDevice,
+    texture::TextureCache,
+    Extract, RenderApp, RenderStage,
+};
+use std::num::NonZeroU32;
+
+pub const BLOOM_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 5076709351195079681);
+
+/// Makes the brightest parts of the image of a 3d camera glow, like emissive materials.
+///
+/// The parts of the image brighter than the `threshold` are blurred by downsampling them in a
+/// chain of `mip_count` textures, each half the size of the previous one, and upsampling the chain
+/// back. The blur is then added to the image, scaled by the `intensity`.
+///
+/// Bloom needs the camera to render in [`Camera3d::hdr`], it is ignored otherwise.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct BloomSettings {
+    /// The brightness from which the pixels glow. Colors are in linear space, so the brightest
+    /// color of a material without [`hdr`](Camera3d::hdr) has a brightness of 1.0.
+    ///
+    /// Defaults to 1.0.
+    pub threshold: f32,
+    /// How much the blur is added to the image.
+    ///
+    /// Defaults to 0.3.
+    pub intensity: f32,
+    /// The number of times the image is downsampled, a higher count spreads the glow further. It
+    /// is clamped so that the smallest texture of the chain is at least a pixel wide.
+    ///
+    /// Defaults to 6.
+    pub mip_count: u32,
+}
+
+impl Default for BloomSettings {
+    fn default() -> Self {
+        Self {
+            threshold: 1.0,
+            intensity: 0.3,
+            mip_count: 6,
+        }
+    }
+}
+
+/// The parameters of the bloom shader, extracted from the [`BloomSettings`] of a view
+#[derive(Component, ShaderType, Clone)]
+pub struct BloomUniform {
+    threshold: f32,
+    intensity: f32,
+}
+
+pub struct BloomPlugin;
+
+impl Plugin for BloomPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, BLOOM_SHADER_HANDLE, "bloom.wgsl", Shader::from_wgsl);
+
+        app.register_type::<BloomSettings>()
+            .add_plugin(UniformComponentPlugin::<BloomUniform>::default());
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<BloomPipelines>()
+            .add_system_to_stage(RenderStage::Extract, extract_bloom_settings)
+            .add_system_to_stage(RenderStage::Prepare, prepare_bloom_textures);
+    }
+}
+
+pub struct BloomPipelines {
+    pub layout: BindGroupLayout,
+    pub sampler: Sampler,
+    /// Downsamples the [`ViewHdrTexture`] to the first mip, keeping the colors above the threshold
+    pub downsample_first: CachedRenderPipelineId,
+    pub downsample: CachedRenderPipelineId,
+    /// Adds the upsampled mip to the previous one
+    pub upsample: CachedRenderPipelineId,
+    /// Adds the upsampled first mip to the [`ViewHdrTexture`], scaled by the intensity
+    pub upsample_final: CachedRenderPipelineId,
+}
+
+impl FromWorld for BloomPipelines {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("bloom_layout"),
+            entries: &[
+                // Source texture
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // Source texture sampler
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // Settings
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(BloomUniform::min_size()),
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("bloom_sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let additive = BlendState {
+            color: BlendComponent {
+                src_factor: BlendFactor::One,
+                dst_factor: BlendFactor::One,
+                operation: BlendOperation::Add,
+            },
+            alpha: BlendComponent {
+                src_factor: BlendFactor::Zero,
+                dst_factor: BlendFactor::One,
+                operation: BlendOperation::Add,
+            },
+        };
+        let descriptor =
+            |label: &'static str, entry_point: &'static str, blend| RenderPipelineDescriptor {
+                label: Some(label.into()),
+                layout: Some(vec![layout.clone()]),
+                vertex: fullscreen_shader_vertex_state(),
+                fragment: Some(FragmentState {
+                    shader: BLOOM_SHADER_HANDLE.typed(),
+                    shader_defs: Vec::new(),
+                    entry_point: entry_point.into(),
+                    targets: vec![ColorTargetState {
+                        format: ViewHdrTexture::FORMAT,
+                        blend,
+                        write_mask: ColorWrites::ALL,
+                    }],
+                }),
+                primitive: PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: MultisampleState::default(),
+            };
+        let descriptors = [
+            descriptor("bloom_downsample_first_pipeline", "downsample_first", None),
+            descriptor("bloom_downsample_pipeline", "downsample", None),
+            descriptor("bloom_upsample_pipeline", "upsample", Some(additive)),
+            descriptor(
+                "bloom_upsample_final_pipeline",
+                "upsample_final",
+                Some(additive),
+            ),
+        ];
+
+        let mut pipeline_cache = world.resource_mut::<PipelineCache>();
+        let [downsample_first, downsample, upsample, upsample_final] =
+            descriptors.map(|descriptor| pipeline_cache.queue_render_pipeline(descriptor));
+
+        BloomPipelines {
+            layout,
+            sampler,
+            downsample_first,
+            downsample,
+            upsample,
+            upsample_final,
+        }
+    }
+}
+
+pub fn extract_bloom_settings(
+    mut commands: Commands,
+    cameras: Extract<Query<(Entity, &Camera, &Camera3d, &BloomSettings)>>,
+) {
+    for (entity, camera, camera_3d, settings) in cameras.iter() {
+        if camera.is_active && camera_3d.hdr {
+            commands.get_or_spawn(entity).insert_bundle((
+                settings.clone(),
+                BloomUniform {
+                    threshold: settings.threshold,
+                    intensity: settings.intensity,
+                },
+            ));
+        }
+    }
+}
+
+/// The chain of textures the [`ViewHdrTexture`] of a view is blurred with, as the mips of a
+/// texture half its size
+#[derive(Component)]
+pub struct BloomTexture {
+    pub texture: Texture,
+    pub mip_views: Vec<TextureView>,
+}
+
+/// Returns the size of the [`BloomTexture`] of a view of `target_size`, half its size, and its
+/// number of mips, `mip_count` clamped so that the smallest mip is at least a pixel wide
+fn bloom_texture_size(target_size: UVec2, mip_count: u32) -> (UVec2, u32) {
+    let size = (target_size / 2).max(UVec2::ONE);
+    let max_mip_count = u32::BITS - size.min_element().leading_zeros();
+    (size, mip_count.clamp(1, max_mip_count))
+}
+
+pub fn prepare_bloom_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    views: Query<(Entity, &ExtractedCamera, &BloomSettings), With<ViewHdrTexture>>,
+) {
+    for (entity, camera, settings) in views.iter() {
+        if let Some(physical_target_size) = camera.physical_target_size {
+            let (
+                UVec2 {
+                    x: width,
+                    y: height,
+                },
+                mip_count,
+            ) = bloom_texture_size(physical_target_size, settings.mip_count);
+
+            let cached_texture = texture_cache.get(
+                &render_device,
+                TextureDescriptor {
+                    label: Some("bloom_texture"),
+                    size: Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: mip_count,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: ViewHdrTexture::FORMAT,
+                    usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                },
+            );
+            let mip_views = (0..mip_count)
+                .map(|mip| {
+                    cached_texture.texture.create_view(&TextureViewDescriptor {
+                        label: Some("bloom_texture_mip_view"),
+                        base_mip_level: mip,
+                        mip_level_count: NonZeroU32::new(1),
+                        ..Default::default()
+                    })
+                })
+                .collect();
+            commands.entity(entity).insert(BloomTexture {
+                texture: cached_texture.texture,
+                mip_views,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bloom_mips_are_at_least_a_pixel_wide() {
+        assert_eq!(
+            bloom_texture_size(UVec2::new(1280, 720), 6),
+            (UVec2::new(640, 360), 6)
+        );
+        // 64 pixels high, 32 in the first mip, then 16, 8, 4, 2 and 1
+        assert_eq!(
+            bloom_texture_size(UVec2::new(256, 64), 10),
+            (UVec2::new(128, 32), 6)
+        );
+        assert_eq!(bloom_texture_size(UVec2::new(1, 1), 6), (UVec2::ONE, 1));
+        assert_eq!(
+            bloom_texture_size(UVec2::new(1280, 720), 0),
+            (UVec2::new(640, 360), 1)
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{
+    bloom::{BloomPipelines, BloomTexture, BloomUniform},
+    core_3d::ViewHdrTexture,
+};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    extract_component::{ComponentUniforms, DynamicUniformIndex},
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_phase::TrackedRenderPass,
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindingResource, LoadOp, Operations,
+        PipelineCache, RenderPassColorAttachment, RenderPassDescriptor, RenderPipeline,
+        TextureView,
+    },
+    renderer::RenderContext,
+    view::ExtractedView,
+};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+
+/// Adds the bloom of the [`BloomSettings`](crate::bloom::BloomSettings) of a view to its
+/// [`ViewHdrTexture`]
+pub struct BloomNode {
+    query: QueryState<
+        (
+            &'static ViewHdrTexture,
+            &'static BloomTexture,
+            &'static DynamicUniformIndex<BloomUniform>,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl BloomNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for BloomNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(BloomNode::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (hdr_texture, bloom_texture, uniform_index) =
+            match self.query.get_manual(world, view_entity) {
+                Ok(query) => query,
+                // No bloom on this view
+                Err(_) => return Ok(()),
+            };
+        let bloom_pipelines = world.resource::<BloomPipelines>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let (downsample_first, downsample, upsample, upsample_final) = match (
+            pipeline_cache.get_render_pipeline(bloom_pipelines.downsample_first),
+            pipeline_cache.get_render_pipeline(bloom_pipelines.downsample),
+            pipeline_cache.get_render_pipeline(bloom_pipelines.upsample),
+            pipeline_cache.get_render_pipeline(bloom_pipelines.upsample_final),
+        ) {
+            (Some(first), Some(down), Some(up), Some(last)) => (first, down, up, last),
+            _ => return Ok(()),
+        };
+        let uniforms = world.resource::<ComponentUniforms<BloomUniform>>();
+        if uniforms.binding().is_none() {
+            return Ok(());
+        }
+
+        #[cfg(feature = "trace")]
+        let _bloom_span = info_span!("bloom").entered();
+        let bind_group = |source: &TextureView| {
+            render_context
+                .render_device
+                .create_bind_group(&BindGroupDescriptor {
+                    label: Some("bloom_bind_group"),
+                    layout: &bloom_pipelines.layout,
+                    entries: &[
+                        BindGroupEntry {
+                            binding: 0,
+                            resource: BindingResource::TextureView(source),
+                        },
+                        BindGroupEntry {
+                            binding: 1,
+                            resource: BindingResource::Sampler(&bloom_pipelines.sampler),
+                        },
+                        BindGroupEntry {
+                            binding: 2,
+                            resource: uniforms.binding().unwrap(),
+                        },
+                    ],
+                })
+        };
+        let mips = &bloom_texture.mip_views;
+        let mut passes = vec![(
+            "bloom_downsample_first_pass",
+            downsample_first,
+            bind_group(&hdr_texture.view),
+            &mips[0],
+        )];
+        for mip in 1..mips.len() {
+            passes.push((
+                "bloom_downsample_pass",
+                downsample,
+                bind_group(&mips[mip - 1]),
+                &mips[mip],
+            ));
+        }
+        for mip in (0..mips.len() - 1).rev() {
+            passes.push((
+                "bloom_upsample_pass",
+                upsample,
+                bind_group(&mips[mip + 1]),
+                &mips[mip],
+            ));
+        }
+        passes.push((
+            "bloom_upsample_final_pass",
+            upsample_final,
+            bind_group(&mips[0]),
+            &hdr_texture.view,
+        ));
+
+        for (label, pipeline, bind_group, target) in &passes {
+            draw_pass(
+                render_context,
+                label,
+                pipeline,
+                bind_group,
+                uniform_index.index(),
+                target,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Draws a fullscreen triangle with the `pipeline`, blending with the `target`
+fn draw_pass(
+    render_context: &mut RenderContext,
+    label: &str,
+    pipeline: &RenderPipeline,
+    bind_group: &BindGroup,
+    uniform_offset: u32,
+    target: &TextureView,
+) {
+    let pass_descriptor = RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[RenderPassColorAttachment {
+            view: target,
+            resolve_target: None,
+            ops: Operations {
+                load: LoadOp::Load,
+                store: true,
+            },
+        }],
+        depth_stencil_attachment: None,
+    };
+
+    let render_pass = render_context
+        .command_encoder
+        .begin_render_pass(&pass_descriptor);
+    let mut tracked_pass = TrackedRenderPass::new(render_pass);
+    tracked_pass.set_render_pipeline(pipeline);
+    tracked_pass.set_bind_group(0, bind_group, &[uniform_offset]);
+    tracked_pass.draw(0..3, 0..1);
+}
@@ -46,7 +46,10 @@ impl Plugin for Core2dPlugin {
             .init_resource::<DrawFunctions<Transparent2d>>()
             .add_system_to_stage(RenderStage::Extract, extract_core_2d_camera_phases)
             .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<Transparent2d>)
-            .add_system_to_stage(RenderStage::PhaseSort, batch_phase_system::<Transparent2d>);
+            .add_system_to_stage(
+                RenderStage::PhaseSort,
+                batch_phase_system::<Transparent2d>.after(sort_phase_system::<Transparent2d>),
+            );
 
         let pass_node_2d = MainPass2dNode::new(&mut render_app.world);
         let mut graph = render_app.world.resource_mut::<RenderGraph>();
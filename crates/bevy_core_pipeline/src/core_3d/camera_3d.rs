@@ -1,4 +1,4 @@
-use crate::clear_color::ClearColorConfig;
+use crate::{clear_color::ClearColorConfig, tonemapping::Tonemapping};
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 use bevy_render::{
@@ -19,6 +19,17 @@ pub struct Camera3d {
     pub clear_color: ClearColorConfig,
     /// The depth clear operation to perform for the main 3d pass.
     pub depth_load_op: Camera3dDepthLoadOp,
+    /// Renders the main 3d pass to a [`ViewHdrTexture`](super::ViewHdrTexture), which keeps the
+    /// colors brighter than white, before tonemapping it to the render target with the
+    /// `tonemapping` operator. Needed for [`BloomSettings`](crate::bloom::BloomSettings).
+    ///
+    /// The pipelines drawing in the main 3d pass must then target
+    /// [`ViewHdrTexture::FORMAT`](super::ViewHdrTexture::FORMAT), like the mesh pipelines
+    /// specialized with the `HDR` key. The UI is drawn after the tonemapping, over the tonemapped
+    /// image: it isn't affected by the hdr rendering or by bloom.
+    pub hdr: bool,
+    /// The tonemapping operator of the [`hdr`](Self::hdr) rendering.
+    pub tonemapping: Tonemapping,
 }
 
 /// The depth clear operation to perform for the main 3d pass.
@@ -1,13 +1,16 @@
 use crate::{
     clear_color::{ClearColor, ClearColorConfig},
-    core_3d::{AlphaMask3d, Camera3d, Opaque3d, Transparent3d},
+    core_3d::{AlphaMask3d, Camera3d, Opaque3d, Transparent3d, ViewHdrTexture},
 };
 use bevy_ecs::prelude::*;
 use bevy_render::{
     camera::ExtractedCamera,
     render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
     render_phase::{DrawFunctions, RenderPhase, TrackedRenderPass},
-    render_resource::{LoadOp, Operations, RenderPassDepthStencilAttachment, RenderPassDescriptor},
+    render_resource::{
+        LoadOp, Operations, RenderPassColorAttachment, RenderPassDepthStencilAttachment,
+        RenderPassDescriptor,
+    },
     renderer::RenderContext,
     view::{ExtractedView, ViewDepthTexture, ViewTarget},
 };
@@ -24,6 +27,7 @@ pub struct MainPass3dNode {
             &'static Camera3d,
             &'static ViewTarget,
             &'static ViewDepthTexture,
+            Option<&'static ViewHdrTexture>,
         ),
         With<ExtractedView>,
     >,
@@ -55,13 +59,36 @@ impl Node for MainPass3dNode {
         world: &World,
     ) -> Result<(), NodeRunError> {
         let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
-        let (camera, opaque_phase, alpha_mask_phase, transparent_phase, camera_3d, target, depth) =
-            match self.query.get_manual(world, view_entity) {
-                Ok(query) => query,
-                Err(_) => {
-                    return Ok(());
-                } // No window
-            };
+        let (
+            camera,
+            opaque_phase,
+            alpha_mask_phase,
+            transparent_phase,
+            camera_3d,
+            target,
+            depth,
+            hdr_texture,
+        ) = match self.query.get_manual(world, view_entity) {
+            Ok(query) => query,
+            Err(_) => {
+                return Ok(());
+            } // No window
+        };
+        // NOTE: Hdr views are tonemapped to their target after the main pass
+        let get_color_attachment = |ops| match hdr_texture {
+            Some(hdr_texture) => RenderPassColorAttachment {
+                view: hdr_texture
+                    .sampled_view
+                    .as_ref()
+                    .unwrap_or(&hdr_texture.view),
+                resolve_target: hdr_texture
+                    .sampled_view
+                    .is_some()
+                    .then(|| &*hdr_texture.view),
+                ops,
+            },
+            None => target.get_color_attachment(ops),
+        };
 
         // Always run opaque pass to ensure screen is cleared
         {
@@ -73,7 +100,7 @@ impl Node for MainPass3dNode {
                 label: Some("main_opaque_pass_3d"),
                 // NOTE: The opaque pass loads the color
                 // buffer as well as writing to it.
-                color_attachments: &[target.get_color_attachment(Operations {
+                color_attachments: &[get_color_attachment(Operations {
                     load: match camera_3d.clear_color {
                         ClearColorConfig::Default => {
                             LoadOp::Clear(world.resource::<ClearColor>().0.into())
@@ -119,7 +146,7 @@ impl Node for MainPass3dNode {
             let pass_descriptor = RenderPassDescriptor {
                 label: Some("main_alpha_mask_pass_3d"),
                 // NOTE: The alpha_mask pass loads the color buffer as well as overwriting it where appropriate.
-                color_attachments: &[target.get_color_attachment(Operations {
+                color_attachments: &[get_color_attachment(Operations {
                     load: LoadOp::Load,
                     store: true,
                 })],
@@ -158,7 +185,7 @@ impl Node for MainPass3dNode {
             let pass_descriptor = RenderPassDescriptor {
                 label: Some("main_transparent_pass_3d"),
                 // NOTE: The transparent pass loads the color buffer as well as overwriting it where appropriate.
-                color_attachments: &[target.get_color_attachment(Operations {
+                color_attachments: &[get_color_attachment(Operations {
                     load: LoadOp::Load,
                     store: true,
                 })],
@@ -202,7 +229,7 @@ impl Node for MainPass3dNode {
             let _reset_viewport_pass_3d = info_span!("reset_viewport_pass_3d").entered();
             let pass_descriptor = RenderPassDescriptor {
                 label: Some("reset_viewport_pass_3d"),
-                color_attachments: &[target.get_color_attachment(Operations {
+                color_attachments: &[get_color_attachment(Operations {
                     load: LoadOp::Load,
                     store: true,
                 })],
@@ -8,6 +8,8 @@ pub mod graph {
     }
     pub mod node {
         pub const MAIN_PASS: &str = "main_pass";
+        pub const BLOOM: &str = "bloom";
+        pub const TONEMAPPING: &str = "tonemapping";
     }
 }
 
@@ -16,6 +18,7 @@ use std::cmp::Reverse;
 pub use camera_3d::*;
 pub use main_pass_3d_node::*;
 
+use crate::{bloom::BloomNode, tonemapping::TonemappingNode};
 use bevy_app::{App, Plugin};
 use bevy_ecs::prelude::*;
 use bevy_render::{
@@ -28,8 +31,8 @@ use bevy_render::{
         EntityPhaseItem, PhaseItem, RenderPhase,
     },
     render_resource::{
-        CachedRenderPipelineId, Extent3d, TextureDescriptor, TextureDimension, TextureFormat,
-        TextureUsages,
+        CachedRenderPipelineId, Extent3d, Texture, TextureDescriptor, TextureDimension,
+        TextureFormat, TextureUsages, TextureView,
     },
     renderer::RenderDevice,
     texture::TextureCache,
@@ -56,15 +59,20 @@ impl Plugin for Core3dPlugin {
             .init_resource::<DrawFunctions<Transparent3d>>()
             .add_system_to_stage(RenderStage::Extract, extract_core_3d_camera_phases)
             .add_system_to_stage(RenderStage::Prepare, prepare_core_3d_depth_textures)
+            .add_system_to_stage(RenderStage::Prepare, prepare_core_3d_hdr_textures)
             .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<Opaque3d>)
             .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<AlphaMask3d>)
             .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<Transparent3d>);
 
         let pass_node_3d = MainPass3dNode::new(&mut render_app.world);
+        let bloom_node = BloomNode::new(&mut render_app.world);
+        let tonemapping_node = TonemappingNode::new(&mut render_app.world);
         let mut graph = render_app.world.resource_mut::<RenderGraph>();
 
         let mut draw_3d_graph = RenderGraph::default();
         draw_3d_graph.add_node(graph::node::MAIN_PASS, pass_node_3d);
+        draw_3d_graph.add_node(graph::node::BLOOM, bloom_node);
+        draw_3d_graph.add_node(graph::node::TONEMAPPING, tonemapping_node);
         let input_node_id = draw_3d_graph.set_input(vec![SlotInfo::new(
             graph::input::VIEW_ENTITY,
             SlotType::Entity,
@@ -77,6 +85,28 @@ impl Plugin for Core3dPlugin {
                 MainPass3dNode::IN_VIEW,
             )
             .unwrap();
+        draw_3d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::BLOOM,
+                BloomNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::TONEMAPPING,
+                TonemappingNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(graph::node::MAIN_PASS, graph::node::BLOOM)
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(graph::node::BLOOM, graph::node::TONEMAPPING)
+            .unwrap();
         graph.add_sub_graph(graph::NAME, draw_3d_graph);
     }
 }
@@ -267,3 +297,78 @@ pub fn prepare_core_3d_depth_textures(
         }
     }
 }
+
+/// The intermediate texture the main 3d pass of a camera with [`Camera3d::hdr`] renders to, before
+/// being tonemapped to its [`ViewTarget`](bevy_render::view::ViewTarget).
+#[derive(Component)]
+pub struct ViewHdrTexture {
+    pub texture: Texture,
+    /// The view of the texture, which the multisampled view is resolved to
+    pub view: TextureView,
+    /// The view of the multisampled texture, when [`Msaa`] is enabled
+    pub sampled_view: Option<TextureView>,
+}
+
+impl ViewHdrTexture {
+    pub const FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+}
+
+pub fn prepare_core_3d_hdr_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    msaa: Res<Msaa>,
+    render_device: Res<RenderDevice>,
+    views_3d: Query<(Entity, &ExtractedCamera, &Camera3d), With<RenderPhase<Opaque3d>>>,
+) {
+    let mut textures = HashMap::default();
+    for (entity, camera, camera_3d) in views_3d.iter() {
+        if !camera_3d.hdr {
+            continue;
+        }
+        if let Some(physical_target_size) = camera.physical_target_size {
+            let size = Extent3d {
+                depth_or_array_layers: 1,
+                width: physical_target_size.x,
+                height: physical_target_size.y,
+            };
+            let (texture, sampled_texture) = textures
+                .entry(camera.target.clone())
+                .or_insert_with(|| {
+                    let texture = texture_cache.get(
+                        &render_device,
+                        TextureDescriptor {
+                            label: Some("view_hdr_texture"),
+                            size,
+                            mip_level_count: 1,
+                            sample_count: 1,
+                            dimension: TextureDimension::D2,
+                            format: ViewHdrTexture::FORMAT,
+                            usage: TextureUsages::RENDER_ATTACHMENT
+                                | TextureUsages::TEXTURE_BINDING,
+                        },
+                    );
+                    let sampled_texture = (msaa.samples > 1).then(|| {
+                        texture_cache.get(
+                            &render_device,
+                            TextureDescriptor {
+                                label: Some("view_hdr_sampled_texture"),
+                                size,
+                                mip_level_count: 1,
+                                sample_count: msaa.samples,
+                                dimension: TextureDimension::D2,
+                                format: ViewHdrTexture::FORMAT,
+                                usage: TextureUsages::RENDER_ATTACHMENT,
+                            },
+                        )
+                    });
+                    (texture, sampled_texture)
+                })
+                .clone();
+            commands.entity(entity).insert(ViewHdrTexture {
+                texture: texture.texture,
+                view: texture.default_view,
+                sampled_view: sampled_texture.map(|sampled| sampled.default_view),
+            });
+        }
+    }
+}
@@ -0,0 +1,18 @@
+#define_import_path bevy_core_pipeline::fullscreen_vertex_shader
+
+struct FullscreenVertexOutput {
+    [[builtin(position)]]
+    position: vec4<f32>;
+    [[location(0)]]
+    uv: vec2<f32>;
+};
+
+// A single triangle covering the whole screen, with the uvs of the screen going from (0, 0) at the
+// top left to (1, 1) at the bottom right
+[[stage(vertex)]]
+fn fullscreen_vertex_shader([[builtin(vertex_index)]] vertex_index: u32) -> FullscreenVertexOutput {
+    let uv = vec2<f32>(f32(vertex_index >> 1u), f32(vertex_index & 1u)) * 2.0;
+    let clip_position = vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+
+    return FullscreenVertexOutput(clip_position, uv);
+}
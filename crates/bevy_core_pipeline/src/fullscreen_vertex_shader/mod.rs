@@ -0,0 +1,19 @@
+use bevy_asset::HandleUntyped;
+use bevy_reflect::TypeUuid;
+use bevy_render::{prelude::Shader, render_resource::VertexState};
+
+pub const FULLSCREEN_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 7837534426033940724);
+
+/// The [`VertexState`] of a pipeline drawing a single triangle covering the whole screen, without
+/// any vertex buffer. Draw it with `pass.draw(0..3, 0..1)`.
+///
+/// The fragment shader receives the uvs of the screen at `[[location(0)]]`.
+pub fn fullscreen_shader_vertex_state() -> VertexState {
+    VertexState {
+        shader: FULLSCREEN_SHADER_HANDLE.typed(),
+        shader_defs: Vec::new(),
+        entry_point: "fullscreen_vertex_shader".into(),
+        buffers: Vec::new(),
+    }
+}
@@ -0,0 +1,144 @@
+mod node;
+
+pub use node::TonemappingNode;
+
+use crate::{
+    core_3d::{Camera3d, ViewHdrTexture},
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+};
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize, TypeUuid};
+use bevy_render::{
+    render_resource::*, renderer::RenderDevice, texture::BevyDefault, RenderApp, RenderStage,
+};
+use serde::{Deserialize, Serialize};
+
+pub const TONEMAPPING_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 17015368199668024512);
+
+/// The operator mapping the colors of the [`ViewHdrTexture`] of a camera with
+/// [`Camera3d::hdr`] to the displayable range of its render target.
+///
+/// Cameras without `hdr` always use [`Tonemapping::ReinhardLuminance`], in the shaders of the
+/// materials.
+#[derive(Reflect, Serialize, Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+#[reflect_value(Serialize, Deserialize)]
+pub enum Tonemapping {
+    /// Clamp the colors, the brightest parts of the image will be burnt.
+    None,
+    /// Reinhard on each channel, which desaturates the brightest colors.
+    Reinhard,
+    /// Reinhard on the luminance of the colors, preserving their hue.
+    #[default]
+    ReinhardLuminance,
+    /// An approximation of the filmic curve of the Academy Color Encoding System, with more
+    /// contrast than Reinhard.
+    Aces,
+}
+
+pub struct TonemappingPlugin;
+
+impl Plugin for TonemappingPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            TONEMAPPING_SHADER_HANDLE,
+            "tonemapping.wgsl",
+            Shader::from_wgsl
+        );
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<TonemappingPipeline>()
+            .init_resource::<SpecializedRenderPipelines<TonemappingPipeline>>()
+            .add_system_to_stage(RenderStage::Queue, queue_view_tonemapping_pipelines);
+    }
+}
+
+pub struct TonemappingPipeline {
+    pub hdr_texture_layout: BindGroupLayout,
+}
+
+impl FromWorld for TonemappingPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let hdr_texture_layout =
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("tonemapping_hdr_texture_layout"),
+                entries: &[BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                }],
+            });
+
+        TonemappingPipeline { hdr_texture_layout }
+    }
+}
+
+impl SpecializedRenderPipeline for TonemappingPipeline {
+    type Key = Tonemapping;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let shader_defs = match key {
+            Tonemapping::None => Vec::new(),
+            Tonemapping::Reinhard => vec!["TONEMAP_REINHARD".to_string()],
+            Tonemapping::ReinhardLuminance => vec!["TONEMAP_REINHARD_LUMINANCE".to_string()],
+            Tonemapping::Aces => vec!["TONEMAP_ACES".to_string()],
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("tonemapping_pipeline".into()),
+            layout: Some(vec![self.hdr_texture_layout.clone()]),
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: TONEMAPPING_SHADER_HANDLE.typed(),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![ColorTargetState {
+                    format: TextureFormat::bevy_default(),
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                }],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+        }
+    }
+}
+
+/// The pipeline the [`TonemappingNode`] of a view uses
+#[derive(Component)]
+pub struct ViewTonemappingPipeline(pub CachedRenderPipelineId);
+
+pub fn queue_view_tonemapping_pipelines(
+    mut commands: Commands,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<TonemappingPipeline>>,
+    tonemapping_pipeline: Res<TonemappingPipeline>,
+    views: Query<(Entity, &Camera3d), With<ViewHdrTexture>>,
+) {
+    for (entity, camera_3d) in views.iter() {
+        let pipeline_id = pipelines.specialize(
+            &mut pipeline_cache,
+            &tonemapping_pipeline,
+            camera_3d.tonemapping,
+        );
+        commands
+            .entity(entity)
+            .insert(ViewTonemappingPipeline(pipeline_id));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{core_3d::ViewHdrTexture, tonemapping::TonemappingPipeline};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    camera::ExtractedCamera,
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_phase::TrackedRenderPass,
+    render_resource::{
+        BindGroupDescriptor, BindGroupEntry, BindingResource, LoadOp, Operations, PipelineCache,
+        RenderPassColorAttachment, RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::{ExtractedView, ViewTarget},
+};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+
+use super::ViewTonemappingPipeline;
+
+/// Tonemaps the [`ViewHdrTexture`] of a view to its [`ViewTarget`]
+pub struct TonemappingNode {
+    query: QueryState<
+        (
+            &'static ExtractedCamera,
+            &'static ViewTarget,
+            &'static ViewHdrTexture,
+            &'static ViewTonemappingPipeline,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl TonemappingNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for TonemappingNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(TonemappingNode::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (camera, target, hdr_texture, tonemapping) =
+            match self.query.get_manual(world, view_entity) {
+                Ok(query) => query,
+                // Not an hdr view
+                Err(_) => return Ok(()),
+            };
+        let pipeline = match world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(tonemapping.0)
+        {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+
+        #[cfg(feature = "trace")]
+        let _tonemapping_pass_span = info_span!("tonemapping_pass").entered();
+        let bind_group = render_context
+            .render_device
+            .create_bind_group(&BindGroupDescriptor {
+                label: Some("tonemapping_hdr_texture_bind_group"),
+                layout: &world.resource::<TonemappingPipeline>().hdr_texture_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&hdr_texture.view),
+                }],
+            });
+
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("tonemapping_pass"),
+            // NOTE: Other cameras may have rendered to the target outside of the viewport
+            color_attachments: &[RenderPassColorAttachment {
+                view: &target.view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        };
+
+        let render_pass = render_context
+            .command_encoder
+            .begin_render_pass(&pass_descriptor);
+        let mut tracked_pass = TrackedRenderPass::new(render_pass);
+        if let Some(viewport) = camera.viewport.as_ref() {
+            tracked_pass.set_camera_viewport(viewport);
+        }
+        tracked_pass.set_render_pipeline(pipeline);
+        tracked_pass.set_bind_group(0, &bind_group, &[]);
+        tracked_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
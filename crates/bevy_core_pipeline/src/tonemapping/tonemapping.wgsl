@@ -0,0 +1,58 @@
+[[group(0), binding(0)]]
+var hdr_texture: texture_2d<f32>;
+
+// from https://64.github.io/tonemapping/
+// reinhard on RGB oversaturates colors
+fn reinhard(color: vec3<f32>) -> vec3<f32> {
+    return color / (1.0 + color);
+}
+
+// luminance coefficients from Rec. 709.
+// https://en.wikipedia.org/wiki/Rec._709
+fn luminance(v: vec3<f32>) -> f32 {
+    return dot(v, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+fn change_luminance(c_in: vec3<f32>, l_out: f32) -> vec3<f32> {
+    let l_in = luminance(c_in);
+    return c_in * (l_out / l_in);
+}
+
+fn reinhard_luminance(color: vec3<f32>) -> vec3<f32> {
+    let l_old = luminance(color);
+    let l_new = l_old / (1.0 + l_old);
+    return change_luminance(color, l_new);
+}
+
+// Krzysztof Narkowicz's fit of the ACES filmic curve
+// https://knarkowicz.wordpress.com/2016/01/06/aces-filmic-tone-mapping-curve/
+fn aces_filmic(color: vec3<f32>) -> vec3<f32> {
+    let a = 2.51;
+    let b = 0.03;
+    let c = 2.43;
+    let d = 0.59;
+    let e = 0.14;
+    return clamp((color * (a * color + b)) / (color * (c * color + d) + e), vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+[[stage(fragment)]]
+fn fragment([[builtin(position)]] position: vec4<f32>) -> [[location(0)]] vec4<f32> {
+    // The hdr texture has the size of the render target, like the framebuffer coordinates
+    let hdr_color = textureLoad(hdr_texture, vec2<i32>(position.xy), 0);
+    var color = hdr_color.rgb;
+
+#ifdef TONEMAP_REINHARD
+    color = reinhard(color);
+#endif
+#ifdef TONEMAP_REINHARD_LUMINANCE
+    // Avoid the NaN of pixels without any light
+    if (luminance(color) > 0.0) {
+        color = reinhard_luminance(color);
+    }
+#endif
+#ifdef TONEMAP_ACES
+    color = aces_filmic(color);
+#endif
+
+    return vec4<f32>(color, hdr_color.a);
+}
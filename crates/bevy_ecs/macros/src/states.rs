@@ -0,0 +1,43 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Path};
+
+pub fn derive_states(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let bevy_ecs_path: Path = crate::bevy_ecs_path();
+
+    let variants = match &ast.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return Error::new_spanned(&ast.ident, "States can only be derived for enums")
+                .into_compile_error()
+                .into()
+        }
+    };
+    if let Some(variant) = variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Error::new_spanned(
+            variant,
+            "States can only be derived for enums without fields",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let idents = variants.iter().map(|variant| &variant.ident);
+    let len = variants.len();
+
+    let enum_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+
+    TokenStream::from(quote! {
+        impl #impl_generics #bevy_ecs_path::schedule::States for #enum_name #type_generics #where_clause {
+            type Iter = ::std::array::IntoIter<Self, #len>;
+
+            fn variants() -> Self::Iter {
+                [#(Self::#idents,)*].into_iter()
+            }
+        }
+    })
+}
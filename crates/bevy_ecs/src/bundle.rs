@@ -97,6 +97,27 @@ pub unsafe trait Bundle: Send + Sync + 'static {
     fn get_components(self, func: impl FnMut(OwningPtr<'_>));
 }
 
+/// The components written by a [`BundleInserter`]: either a [`Bundle`], or a single component
+/// of a [`BundleInfo`] initialized with [`Bundles::init_dynamic_info`].
+pub(crate) trait DynamicBundle {
+    /// Calls `func` on each component, in the order of the [`BundleInfo`]'s components.
+    fn get_components(self, func: impl FnMut(OwningPtr<'_>));
+}
+
+impl<T: Bundle> DynamicBundle for T {
+    #[inline]
+    fn get_components(self, func: impl FnMut(OwningPtr<'_>)) {
+        Bundle::get_components(self, func);
+    }
+}
+
+impl DynamicBundle for OwningPtr<'_> {
+    #[inline]
+    fn get_components(self, mut func: impl FnMut(OwningPtr<'_>)) {
+        func(self);
+    }
+}
+
 macro_rules! tuple_impl {
     ($($name: ident),*) => {
         // SAFETY:
@@ -266,7 +287,7 @@ impl BundleInfo {
     /// `entity`, `bundle` must match this [`BundleInfo`]'s type
     #[inline]
     #[allow(clippy::too_many_arguments)]
-    unsafe fn write_components<T: Bundle>(
+    unsafe fn write_components<T: DynamicBundle>(
         &self,
         table: &mut Table,
         sparse_sets: &mut SparseSets,
@@ -417,7 +438,7 @@ impl<'a, 'b> BundleInserter<'a, 'b> {
     /// `entity` must currently exist in the source archetype for this inserter. `archetype_index`
     /// must be `entity`'s location in the archetype. `T` must match this [`BundleInfo`]'s type
     #[inline]
-    pub unsafe fn insert<T: Bundle>(
+    pub unsafe fn insert<T: DynamicBundle>(
         &mut self,
         entity: Entity,
         archetype_index: usize,
@@ -581,6 +602,9 @@ impl<'a, 'b> BundleSpawner<'a, 'b> {
 pub struct Bundles {
     bundle_infos: Vec<BundleInfo>,
     bundle_ids: HashMap<TypeId, BundleId>,
+    /// The bundles of a single component inserted with
+    /// [`EntityMut::insert_by_id`](crate::world::EntityMut::insert_by_id)
+    dynamic_bundle_ids: HashMap<ComponentId, BundleId>,
 }
 
 impl Bundles {
@@ -613,6 +637,31 @@ impl Bundles {
         // SAFETY: index either exists, or was initialized
         unsafe { self.bundle_infos.get_unchecked(id.0) }
     }
+
+    /// Initializes the [`BundleInfo`] of a bundle made of the single component `component_id`.
+    ///
+    /// # Safety
+    ///
+    /// `component_id` must be valid in `components`
+    pub(crate) unsafe fn init_dynamic_info(
+        &mut self,
+        components: &mut Components,
+        component_id: ComponentId,
+    ) -> &BundleInfo {
+        let bundle_infos = &mut self.bundle_infos;
+        let id = self
+            .dynamic_bundle_ids
+            .entry(component_id)
+            .or_insert_with(|| {
+                let id = BundleId(bundle_infos.len());
+                let bundle_info =
+                    initialize_bundle("dynamic component", vec![component_id], id, components);
+                bundle_infos.push(bundle_info);
+                id
+            });
+        // SAFETY: index either exists, or was initialized
+        self.bundle_infos.get_unchecked(id.0)
+    }
 }
 
 /// # Safety
@@ -1,9 +1,16 @@
 //! Types that detect when their internal data mutate.
 
-use crate::{component::ComponentTicks, ptr::PtrMut, system::Resource};
+use crate::{
+    component::ComponentTicks,
+    ptr::{Ptr, PtrMut},
+    system::Resource,
+};
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::Reflect;
-use std::ops::{Deref, DerefMut};
+use std::{
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
 
 /// The (arbitrarily chosen) minimum number of world tick increments between `check_tick` scans.
 ///
@@ -216,6 +223,100 @@ change_detection_impl!(Mut<'a, T>, T,);
 impl_into_inner!(Mut<'a, T>, T,);
 impl_debug!(Mut<'a, T>,);
 
+/// Shared borrow of an entity's component, with its change ticks.
+///
+/// Query for `Ref<T>` instead of `&T` to know whether the component was added or changed since
+/// the system last ran, without filtering the query with [`Changed`](crate::query::Changed) or
+/// [`Added`](crate::query::Added).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component, Debug)]
+/// struct Transform;
+///
+/// fn replicate_system(query: Query<(Entity, Ref<Transform>)>) {
+///     for (entity, transform) in query.iter() {
+///         if transform.is_changed() {
+///             println!("{:?} changed at tick {}: {:?}", entity, transform.last_changed(), *transform);
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(replicate_system);
+/// ```
+pub struct Ref<'a, T> {
+    pub(crate) value: &'a T,
+    pub(crate) component_ticks: &'a ComponentTicks,
+    pub(crate) last_change_tick: u32,
+    pub(crate) change_tick: u32,
+}
+
+impl<'a, T> Ref<'a, T> {
+    /// Returns `true` if this value was added after the system last ran.
+    #[inline]
+    pub fn is_added(&self) -> bool {
+        self.component_ticks
+            .is_added(self.last_change_tick, self.change_tick)
+    }
+
+    /// Returns `true` if this value was added or mutably dereferenced after the system last ran.
+    #[inline]
+    pub fn is_changed(&self) -> bool {
+        self.component_ticks
+            .is_changed(self.last_change_tick, self.change_tick)
+    }
+
+    /// Returns the change tick at which this value was last mutably dereferenced (or added).
+    #[inline]
+    pub fn last_changed(&self) -> u32 {
+        self.component_ticks.changed_tick()
+    }
+
+    /// Returns the [`ComponentTicks`] of this value.
+    #[inline]
+    pub fn ticks(&self) -> ComponentTicks {
+        *self.component_ticks
+    }
+
+    /// Returns the reference to the value, with the lifetime of the borrow.
+    #[inline]
+    pub fn into_inner(self) -> &'a T {
+        self.value
+    }
+}
+
+impl<'a, T> Clone for Ref<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value,
+            component_ticks: self.component_ticks,
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
+        }
+    }
+}
+
+impl<'a, T> Deref for Ref<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<'a, T> AsRef<T> for Ref<'a, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<'a, T: std::fmt::Debug> std::fmt::Debug for Ref<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Ref").field(self.value).finish()
+    }
+}
+
 /// Unique mutable borrow of a reflected component or resource
 #[cfg(feature = "bevy_reflect")]
 pub struct ReflectMut<'a> {
@@ -248,6 +349,19 @@ impl<'a> MutUntyped<'a> {
     pub fn into_inner(self) -> PtrMut<'a> {
         self.value
     }
+
+    /// Returns a pointer to the value, marking it as changed.
+    pub fn as_mut(&mut self) -> PtrMut<'_> {
+        self.set_changed();
+        // SAFETY: the pointer is valid, and unique as it borrows `self` mutably
+        unsafe { PtrMut::new(NonNull::new_unchecked(self.value.as_ptr())) }
+    }
+
+    /// Returns a read-only pointer to the value.
+    pub fn as_ref(&self) -> Ptr<'_> {
+        // SAFETY: the pointer is valid, and not mutated while `self` is borrowed
+        unsafe { Ptr::new(NonNull::new_unchecked(self.value.as_ptr())) }
+    }
 }
 
 impl DetectChanges for MutUntyped<'_> {
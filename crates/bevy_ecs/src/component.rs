@@ -557,6 +557,18 @@ impl ComponentTicks {
         ticks_since_system > ticks_since_change
     }
 
+    /// Returns the change tick at which the component was added.
+    #[inline]
+    pub fn added_tick(&self) -> u32 {
+        self.added
+    }
+
+    /// Returns the change tick at which the component was last mutably dereferenced (or added).
+    #[inline]
+    pub fn changed_tick(&self) -> u32 {
+        self.changed
+    }
+
     pub(crate) fn new(change_tick: u32) -> Self {
         Self {
             added: change_tick,
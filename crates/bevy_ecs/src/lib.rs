@@ -34,16 +34,17 @@ pub mod prelude {
         event::{EventReader, EventWriter, Events},
         query::{Added, AnyOf, ChangeTrackers, Changed, Or, QueryState, With, Without},
         schedule::{
-            AmbiguitySetLabel, ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion,
+            in_state, AmbiguitySetLabel, ExclusiveSystemDescriptorCoercion, IntoRunCondition,
+            NextState, OnEnter, OnExit, OnTransition, ParallelSystemDescriptorCoercion,
             RunCriteria, RunCriteriaDescriptorCoercion, RunCriteriaLabel, Schedule, Stage,
-            StageLabel, State, SystemLabel, SystemSet, SystemStage,
+            StageLabel, State, States, SystemLabel, SystemSet, SystemStage,
         },
         system::{
             Commands, In, IntoChainSystem, IntoExclusiveSystem, IntoSystem, Local, NonSend,
             NonSendMut, ParallelCommands, ParamSet, Query, RemovedComponents, Res, ResMut, System,
-            SystemParamFunction,
+            SystemId, SystemParamFunction,
         },
-        world::{FromWorld, Mut, World},
+        world::{FromWorld, Mut, Ref, World},
     };
 }
 
@@ -58,7 +59,7 @@ mod tests {
         component::{Component, ComponentId},
         entity::Entity,
         query::{Added, ChangeTrackers, Changed, FilteredAccess, With, Without, WorldQuery},
-        world::{Mut, World},
+        world::{Mut, Ref, World},
     };
     use bevy_tasks::{ComputeTaskPool, TaskPool};
     use std::{
@@ -1230,6 +1231,40 @@ mod tests {
         assert!(a_trackers.is_changed());
     }
 
+    #[test]
+    fn ref_query() {
+        let mut world = World::default();
+        let e1 = world.spawn().insert_bundle((A(0), B(0))).id();
+        let e2 = world.spawn().insert(SparseStored(0)).id();
+
+        let mut ref_query = world.query::<(Ref<A>, Option<Ref<SparseStored>>)>();
+        let mut sparse_query = world.query::<Ref<SparseStored>>();
+        let (a, _) = ref_query.single(&world);
+        assert_eq!(a.0, 0);
+        assert!(a.is_added());
+        assert!(a.is_changed());
+        let sparse = sparse_query.single(&world);
+        assert!(sparse.is_added());
+
+        world.clear_trackers();
+        let (a, _) = ref_query.single(&world);
+        assert!(!a.is_added());
+        assert!(!a.is_changed());
+        let added_tick = a.ticks().added_tick();
+
+        *world.get_mut(e1).unwrap() = A(1);
+        world.get_mut::<SparseStored>(e2).unwrap().0 = 1;
+        let (a, _) = ref_query.single(&world);
+        assert_eq!(a.0, 1);
+        assert!(!a.is_added());
+        assert!(a.is_changed());
+        assert_eq!(a.ticks().added_tick(), added_tick);
+        assert!(a.last_changed() > added_tick);
+        let sparse = sparse_query.single(&world);
+        assert_eq!(sparse.0, 1);
+        assert!(sparse.is_changed());
+    }
+
     #[test]
     fn exact_size_query() {
         let mut world = World::default();
@@ -0,0 +1,296 @@
+use crate::{
+    archetype::{ArchetypeGeneration, ArchetypeId},
+    change_detection::{MutUntyped, Ticks},
+    component::ComponentId,
+    entity::{Entity, EntityLocation},
+    world::{get_component, get_component_and_ticks, World, WorldId},
+};
+use bevy_ptr::{Ptr, UnsafeCellDeref};
+
+/// A query built at runtime from [`ComponentId`]s, for components that are not known at compile
+/// time, like the ones initialized with [`World::init_component_with_layout`].
+///
+/// It matches the entities having all the fetched components and the [`with`](Self::with)
+/// components, and none of the [`without`](Self::without) components. The fetched components
+/// are given as type-erased pointers, in the order of the [`ComponentId`]s given to
+/// [`DynamicQueryState::new`].
+///
+/// **You should prefer to use a typed [`QueryState`](crate::query::QueryState) where possible
+/// and only use this in cases where the actual component types are not known at compile time.**
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::DynamicQueryState;
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// #[derive(Component)]
+/// struct Dead;
+///
+/// let mut world = World::new();
+/// world.spawn().insert(Health(10.0));
+/// world.spawn().insert(Health(0.0)).insert(Dead);
+///
+/// let health = world.init_component::<Health>();
+/// let dead = world.init_component::<Dead>();
+/// let mut query = DynamicQueryState::new(&world, vec![health]).without(dead);
+/// query.for_each_mut(&mut world, |_entity, components| {
+///     // SAFETY: the first and only fetched component is a `Health`
+///     let health = unsafe { components[0].as_mut().deref_mut::<Health>() };
+///     health.0 += 1.0;
+/// });
+/// ```
+pub struct DynamicQueryState {
+    world_id: WorldId,
+    archetype_generation: ArchetypeGeneration,
+    matched_archetypes: Vec<ArchetypeId>,
+    fetch: Vec<ComponentId>,
+    with: Vec<ComponentId>,
+    without: Vec<ComponentId>,
+}
+
+impl DynamicQueryState {
+    /// Creates a query fetching the components of the given [`ComponentId`]s.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a component isn't initialized in the `world`, or is fetched more than once.
+    pub fn new(world: &World, fetch: Vec<ComponentId>) -> Self {
+        let mut deduped = fetch.clone();
+        deduped.sort();
+        deduped.dedup();
+        assert!(
+            deduped.len() == fetch.len(),
+            "DynamicQueryState fetches the same component more than once"
+        );
+        let state = DynamicQueryState {
+            world_id: world.id(),
+            archetype_generation: ArchetypeGeneration::initial(),
+            matched_archetypes: Vec::new(),
+            fetch,
+            with: Vec::new(),
+            without: Vec::new(),
+        };
+        state.validate_components(world, &state.fetch);
+        state
+    }
+
+    /// Only matches the entities having the component of the given [`ComponentId`], without
+    /// fetching it.
+    pub fn with(mut self, component_id: ComponentId) -> Self {
+        self.with.push(component_id);
+        self.reset_archetypes();
+        self
+    }
+
+    /// Only matches the entities without the component of the given [`ComponentId`].
+    pub fn without(mut self, component_id: ComponentId) -> Self {
+        self.without.push(component_id);
+        self.reset_archetypes();
+        self
+    }
+
+    /// Returns the [`ComponentId`]s of the fetched components, in order.
+    pub fn fetched_components(&self) -> &[ComponentId] {
+        &self.fetch
+    }
+
+    /// Takes all new archetypes into account, to match their entities.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `world.id()` does not equal the current [`DynamicQueryState`] internal id.
+    pub fn update_archetypes(&mut self, world: &World) {
+        self.validate_world(world);
+        let archetypes = world.archetypes();
+        let new_generation = archetypes.generation();
+        let old_generation = std::mem::replace(&mut self.archetype_generation, new_generation);
+
+        for archetype_index in old_generation.value()..new_generation.value() {
+            let archetype = &archetypes[ArchetypeId::new(archetype_index)];
+            if self
+                .fetch
+                .iter()
+                .chain(&self.with)
+                .all(|&id| archetype.contains(id))
+                && !self.without.iter().any(|&id| archetype.contains(id))
+            {
+                self.matched_archetypes.push(archetype.id());
+            }
+        }
+    }
+
+    #[inline]
+    pub fn validate_world(&self, world: &World) {
+        assert!(
+            world.id() == self.world_id,
+            "Attempted to use DynamicQueryState with a mismatched World. DynamicQueryStates can only be used with the World they were created from.",
+        );
+    }
+
+    /// Runs `func` on each matched entity, with pointers to its fetched components.
+    pub fn for_each(&mut self, world: &World, mut func: impl FnMut(Entity, &[Ptr<'_>])) {
+        self.update_archetypes(world);
+        let mut components = Vec::with_capacity(self.fetch.len());
+        for &archetype_id in &self.matched_archetypes {
+            for (index, &entity) in world.archetypes[archetype_id].entities().iter().enumerate() {
+                let location = EntityLocation {
+                    archetype_id,
+                    index,
+                };
+                components.clear();
+                components.extend(self.fetch.iter().map(|&component_id| {
+                    // SAFETY: the component ids are valid, and the matched archetype contains
+                    // them
+                    unsafe { get_component(world, component_id, entity, location) }.unwrap()
+                }));
+                func(entity, &components);
+            }
+        }
+    }
+
+    /// Runs `func` on each matched entity, with mutable pointers to its fetched components.
+    pub fn for_each_mut(
+        &mut self,
+        world: &mut World,
+        mut func: impl FnMut(Entity, &mut [MutUntyped<'_>]),
+    ) {
+        self.update_archetypes(world);
+        let world: &World = world;
+        let last_change_tick = world.last_change_tick();
+        let change_tick = world.read_change_tick();
+        let mut components = Vec::with_capacity(self.fetch.len());
+        for &archetype_id in &self.matched_archetypes {
+            for (index, &entity) in world.archetypes[archetype_id].entities().iter().enumerate() {
+                let location = EntityLocation {
+                    archetype_id,
+                    index,
+                };
+                components.clear();
+                components.extend(self.fetch.iter().map(|&component_id| {
+                    // SAFETY: the component ids are valid and not duplicated, the matched
+                    // archetype contains them, and world access is unique
+                    unsafe {
+                        let (value, ticks) =
+                            get_component_and_ticks(world, component_id, entity, location).unwrap();
+                        MutUntyped {
+                            value: value.assert_unique(),
+                            ticks: Ticks {
+                                component_ticks: ticks.deref_mut(),
+                                last_change_tick,
+                                change_tick,
+                            },
+                        }
+                    }
+                }));
+                func(entity, &mut components);
+            }
+        }
+    }
+
+    fn validate_components(&self, world: &World, component_ids: &[ComponentId]) {
+        for &component_id in component_ids {
+            assert!(
+                world.components().get_info(component_id).is_some(),
+                "DynamicQueryState used with the component id {:?} which doesn't exist in this world",
+                component_id
+            );
+        }
+    }
+
+    fn reset_archetypes(&mut self) {
+        self.archetype_generation = ArchetypeGeneration::initial();
+        self.matched_archetypes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DynamicQueryState;
+    use crate::{component::StorageType, world::World};
+    use bevy_ptr::OwningPtr;
+    use std::alloc::Layout;
+
+    #[test]
+    fn dynamic_components_and_query() {
+        let mut world = World::new();
+        // SAFETY: the components are `u32`s, which don't need to be dropped
+        let (a, b, c) = unsafe {
+            (
+                world.init_component_with_layout(
+                    "A",
+                    StorageType::Table,
+                    Layout::new::<u32>(),
+                    None,
+                ),
+                world.init_component_with_layout(
+                    "B",
+                    StorageType::SparseSet,
+                    Layout::new::<u32>(),
+                    None,
+                ),
+                world.init_component_with_layout(
+                    "C",
+                    StorageType::Table,
+                    Layout::new::<u32>(),
+                    None,
+                ),
+            )
+        };
+
+        let mut spawn = |components: &[(crate::component::ComponentId, u32)]| {
+            let mut entity = world.spawn();
+            for &(component_id, value) in components {
+                OwningPtr::make(value, |ptr| {
+                    // SAFETY: the components are `u32`s
+                    unsafe {
+                        entity.insert_by_id(component_id, ptr);
+                    }
+                });
+            }
+            entity.id()
+        };
+        let e1 = spawn(&[(a, 1), (b, 10)]);
+        let e2 = spawn(&[(a, 2), (b, 20), (c, 0)]);
+        let e3 = spawn(&[(a, 3), (a, 4)]);
+        spawn(&[(b, 40)]);
+
+        let mut query = DynamicQueryState::new(&world, vec![b, a]).without(c);
+        let mut results = Vec::new();
+        query.for_each(&world, |entity, components| {
+            // SAFETY: the components are `u32`s
+            let values: Vec<u32> = components
+                .iter()
+                .map(|ptr| unsafe { *ptr.deref::<u32>() })
+                .collect();
+            results.push((entity, values));
+        });
+        assert_eq!(results, vec![(e1, vec![10, 1])]);
+
+        let mut query = DynamicQueryState::new(&world, vec![a]);
+        query.for_each_mut(&mut world, |_, components| {
+            // SAFETY: the component is a `u32`
+            unsafe { *components[0].as_mut().deref_mut::<u32>() *= 2 };
+        });
+        let mut results = Vec::new();
+        query.for_each(&world, |entity, components| {
+            // SAFETY: the component is a `u32`
+            results.push((entity, unsafe { *components[0].deref::<u32>() }));
+        });
+        results.sort();
+        assert_eq!(results, vec![(e1, 2), (e2, 4), (e3, 8)]);
+    }
+
+    #[test]
+    #[should_panic = "DynamicQueryState fetches the same component more than once"]
+    fn dynamic_query_duplicate_fetch() {
+        let mut world = World::new();
+        // SAFETY: the component is a `u32`, which doesn't need to be dropped
+        let a = unsafe {
+            world.init_component_with_layout("A", StorageType::Table, Layout::new::<u32>(), None)
+        };
+        DynamicQueryState::new(&world, vec![a, a]);
+    }
+}
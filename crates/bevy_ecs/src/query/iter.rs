@@ -142,17 +142,14 @@ where
             entity_iter: entity_list.into_iter(),
         }
     }
-}
-
-impl<'w, 's, Q: WorldQuery, QF: Fetch<'w, State = Q::State>, F: WorldQuery, I: Iterator> Iterator
-    for QueryManyIter<'w, 's, Q, QF, F, I>
-where
-    I::Item: Borrow<Entity>,
-{
-    type Item = QF::Item;
 
+    /// Fetches the next query result, skipping the entities that don't match the query.
+    ///
+    /// # Safety
+    /// The entity list may contain duplicates: the caller must make sure a mutable item is not
+    /// returned while another item for the same entity is alive.
     #[inline(always)]
-    fn next(&mut self) -> Option<Self::Item> {
+    unsafe fn fetch_next_aliased_unchecked(&mut self) -> Option<QF::Item> {
         for entity in self.entity_iter.by_ref() {
             let location = match self.entities.get(*entity.borrow()) {
                 Some(location) => location,
@@ -190,6 +187,20 @@ where
         }
         None
     }
+}
+
+impl<'w, 's, Q: WorldQuery, QF: Fetch<'w, State = Q::State>, F: WorldQuery, I: Iterator> Iterator
+    for QueryManyIter<'w, 's, Q, QF, F, I>
+where
+    I::Item: Borrow<Entity>,
+{
+    type Item = QF::Item;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: the iterator was created with the safety requirements of `QueryManyIter::new`
+        unsafe { self.fetch_next_aliased_unchecked() }
+    }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (_, max_size) = self.entity_iter.size_hint();
@@ -197,6 +208,57 @@ where
     }
 }
 
+/// A lending iterator over mutable query results of a list of entities.
+///
+/// This struct is created by the [`Query::iter_many_mut`](crate::system::Query::iter_many_mut)
+/// method. It is not an [`Iterator`] because the entity list may contain the same entity more
+/// than once: each result must be dropped before the next one is fetched with
+/// [`fetch_next`](Self::fetch_next).
+pub struct QueryManyIterMut<'w, 's, Q: WorldQuery, F: WorldQuery, I: Iterator>
+where
+    I::Item: Borrow<Entity>,
+{
+    iter: QueryManyIter<'w, 's, Q, QueryFetch<'w, Q>, F, I>,
+}
+
+impl<'w, 's, Q: WorldQuery, F: WorldQuery, I: Iterator> QueryManyIterMut<'w, 's, Q, F, I>
+where
+    I::Item: Borrow<Entity>,
+{
+    /// # Safety
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    /// This does not validate that `world.id()` matches `query_state.world_id`. Calling this on a `world`
+    /// with a mismatched [`WorldId`](crate::world::WorldId) is unsound.
+    pub(crate) unsafe fn new<EntityList: IntoIterator<IntoIter = I>>(
+        world: &'w World,
+        query_state: &'s QueryState<Q, F>,
+        entity_list: EntityList,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> QueryManyIterMut<'w, 's, Q, F, I> {
+        QueryManyIterMut {
+            iter: QueryManyIter::new(
+                world,
+                query_state,
+                entity_list,
+                last_change_tick,
+                change_tick,
+            ),
+        }
+    }
+
+    /// Returns the next query result, or `None` once the entity list is exhausted.
+    ///
+    /// Entities that don't match the query are skipped.
+    #[inline(always)]
+    pub fn fetch_next(&mut self) -> Option<QueryItem<'_, Q>> {
+        // SAFETY: the returned item borrows `self`, so it is dropped before the next one is
+        // fetched, even for an entity listed twice.
+        unsafe { self.iter.fetch_next_aliased_unchecked().map(Q::shrink) }
+    }
+}
+
 pub struct QueryCombinationIter<'w, 's, Q: WorldQuery, F: WorldQuery, const K: usize> {
     tables: &'w Tables,
     archetypes: &'w Archetypes,
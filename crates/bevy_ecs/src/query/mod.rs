@@ -1,10 +1,12 @@
 mod access;
+mod dynamic;
 mod fetch;
 mod filter;
 mod iter;
 mod state;
 
 pub use access::*;
+pub use dynamic::*;
 pub use fetch::*;
 pub use filter::*;
 pub use iter::*;
@@ -697,5 +699,25 @@ mod tests {
             system.initialize(&mut world);
             system.run((), &mut world);
         }
+        {
+            fn system(has_a: Query<Entity, With<A>>, mut b_query: Query<&mut B>) {
+                let entities = has_a.iter().chain(has_a.iter());
+                let mut iter = b_query.iter_many_mut(entities);
+                while let Some(mut b) = iter.fetch_next() {
+                    b.0 += 1;
+                }
+            }
+            let mut system = IntoSystem::into_system(system);
+            system.initialize(&mut world);
+            system.run((), &mut world);
+        }
+        {
+            let b_values = world
+                .query_filtered::<&B, With<A>>()
+                .iter(&world)
+                .map(|b| b.0)
+                .collect::<Vec<_>>();
+            assert_eq!(b_values, vec![3, 3]);
+        }
     }
 }
@@ -16,7 +16,7 @@ use bevy_utils::tracing::Instrument;
 use fixedbitset::FixedBitSet;
 use std::{borrow::Borrow, fmt};
 
-use super::{QueryFetch, QueryItem, QueryManyIter, ROQueryFetch, ROQueryItem};
+use super::{QueryFetch, QueryItem, QueryManyIter, QueryManyIterMut, ROQueryFetch, ROQueryItem};
 
 /// Provides scoped access to a [`World`] state according to a given [`WorldQuery`] and query filter.
 pub struct QueryState<Q: WorldQuery, F: WorldQuery = ()> {
@@ -564,7 +564,7 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
     /// Returns an [`Iterator`] over the query results of a list of [`Entity`]'s.
     ///
     /// This can only return immutable data (mutable data will be cast to an immutable form).
-    /// See [`Self::many_for_each_mut`] for queries that contain at least one mutable component.
+    /// See [`Self::iter_many_mut`] for queries that contain at least one mutable component.
     ///
     #[inline]
     pub fn iter_many<'w, 's, EntityList: IntoIterator>(
@@ -587,6 +587,31 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
         }
     }
 
+    /// Returns a [`QueryManyIterMut`] over the mutable query results of a list of [`Entity`]'s.
+    ///
+    /// Results are fetched one at a time with [`QueryManyIterMut::fetch_next`].
+    #[inline]
+    pub fn iter_many_mut<'w, 's, EntityList: IntoIterator>(
+        &'s mut self,
+        world: &'w mut World,
+        entities: EntityList,
+    ) -> QueryManyIterMut<'w, 's, Q, F, EntityList::IntoIter>
+    where
+        EntityList::Item: Borrow<Entity>,
+    {
+        // SAFETY: query has unique world access
+        unsafe {
+            self.update_archetypes(world);
+            QueryManyIterMut::new(
+                world,
+                self,
+                entities,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
     /// Returns an [`Iterator`] over the query results for the given [`World`].
     ///
     /// # Safety
@@ -7,6 +7,7 @@ mod executor;
 mod executor_parallel;
 pub mod graph_utils;
 mod label;
+mod run_condition;
 mod run_criteria;
 mod stage;
 mod state;
@@ -18,6 +19,7 @@ pub use executor::*;
 pub use executor_parallel::*;
 pub use graph_utils::GraphNode;
 pub use label::*;
+pub use run_condition::*;
 pub use run_criteria::*;
 pub use stage::*;
 pub use state::*;
@@ -0,0 +1,303 @@
+use crate::{
+    archetype::ArchetypeComponentId,
+    component::ComponentId,
+    query::Access,
+    system::{IntoSystem, ReadOnlySystem, Res, Resource, System},
+    world::World,
+};
+use std::borrow::Cow;
+
+/// A boxed run condition: a read-only system returning whether a system should run.
+pub type BoxedRunCondition = Box<dyn System<In = (), Out = bool>>;
+
+/// A run condition, given to
+/// [`run_if`](crate::schedule::ParallelSystemDescriptorCoercion::run_if), and the methods
+/// combining run conditions.
+///
+/// A run condition is any [`ReadOnlySystem`] taking no input and returning a `bool`, such as a
+/// function whose parameters only read from the world. The run conditions of parallel systems
+/// are evaluated once the exclusive systems at the start of the stage have run, before any of the
+/// parallel systems run. The run conditions of exclusive systems are evaluated just before the
+/// system runs.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::not;
+/// struct Paused(bool);
+/// struct Score(u32);
+///
+/// fn is_paused(paused: Res<Paused>) -> bool {
+///     paused.0
+/// }
+///
+/// fn has_won(score: Res<Score>) -> bool {
+///     score.0 >= 100
+/// }
+///
+/// fn update_game() {}
+///
+/// SystemStage::parallel()
+///     .with_system(update_game.run_if(not(is_paused).and(not(has_won))));
+/// ```
+///
+/// Run conditions can't mutate the world:
+///
+/// ```compile_fail
+/// # use bevy_ecs::prelude::*;
+/// struct Score(u32);
+///
+/// fn reset_score(mut score: ResMut<Score>) -> bool {
+///     score.0 = 0;
+///     true
+/// }
+///
+/// fn update_game() {}
+///
+/// SystemStage::parallel().with_system(update_game.run_if(reset_score));
+/// ```
+pub trait IntoRunCondition<Params>: Sized {
+    /// The system this run condition is converted into.
+    type Condition: ReadOnlySystem<In = (), Out = bool>;
+
+    /// Converts this run condition into a system.
+    fn into_condition(self) -> Self::Condition;
+
+    /// Combines this condition with another one, returning `true` if both are `true`.
+    ///
+    /// The other condition is not evaluated if this one is `false`.
+    fn and<OtherParams, Other: IntoRunCondition<OtherParams>>(
+        self,
+        other: Other,
+    ) -> CombinedCondition<Self::Condition, Other::Condition> {
+        CombinedCondition::new(
+            self.into_condition(),
+            other.into_condition(),
+            ConditionOperator::And,
+        )
+    }
+
+    /// Combines this condition with another one, returning `true` if any of them is `true`.
+    ///
+    /// The other condition is not evaluated if this one is `true`.
+    fn or<OtherParams, Other: IntoRunCondition<OtherParams>>(
+        self,
+        other: Other,
+    ) -> CombinedCondition<Self::Condition, Other::Condition> {
+        CombinedCondition::new(
+            self.into_condition(),
+            other.into_condition(),
+            ConditionOperator::Or,
+        )
+    }
+}
+
+impl<Params, Condition> IntoRunCondition<Params> for Condition
+where
+    Condition: IntoSystem<(), bool, Params>,
+    Condition::System: ReadOnlySystem,
+{
+    type Condition = Condition::System;
+
+    fn into_condition(self) -> Self::Condition {
+        IntoSystem::into_system(self)
+    }
+}
+
+/// Inverts a run condition, returning `true` if it is `false`.
+pub fn not<Params, Condition: IntoRunCondition<Params>>(
+    condition: Condition,
+) -> NotCondition<Condition::Condition> {
+    let condition = condition.into_condition();
+    NotCondition {
+        name: Cow::Owned(format!("Not({})", condition.name())),
+        condition,
+    }
+}
+
+/// A run condition returning `true` if the resource exists.
+pub fn resource_exists<T: Resource>() -> impl FnMut(Option<Res<T>>) -> bool {
+    move |resource: Option<Res<T>>| resource.is_some()
+}
+
+/// A run condition returning `true` if the resource exists and is equal to `value`.
+pub fn resource_equals<T: Resource + PartialEq>(value: T) -> impl FnMut(Option<Res<T>>) -> bool {
+    move |resource: Option<Res<T>>| resource.map_or(false, |resource| *resource == value)
+}
+
+/// A run condition returning `true` if the resource has been added or changed since the last
+/// time the condition was evaluated.
+pub fn resource_changed<T: Resource>() -> impl FnMut(Option<Res<T>>) -> bool {
+    move |resource: Option<Res<T>>| resource.map_or(false, |resource| resource.is_changed())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConditionOperator {
+    And,
+    Or,
+}
+
+/// A run condition combining two run conditions, see [`IntoRunCondition`].
+pub struct CombinedCondition<ConditionA, ConditionB> {
+    condition_a: ConditionA,
+    condition_b: ConditionB,
+    operator: ConditionOperator,
+    name: Cow<'static, str>,
+    component_access: Access<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+}
+
+impl<ConditionA, ConditionB> CombinedCondition<ConditionA, ConditionB>
+where
+    ConditionA: System<In = (), Out = bool>,
+    ConditionB: System<In = (), Out = bool>,
+{
+    fn new(condition_a: ConditionA, condition_b: ConditionB, operator: ConditionOperator) -> Self {
+        CombinedCondition {
+            name: Cow::Owned(format!(
+                "{:?}({}, {})",
+                operator,
+                condition_a.name(),
+                condition_b.name()
+            )),
+            condition_a,
+            condition_b,
+            operator,
+            component_access: Default::default(),
+            archetype_component_access: Default::default(),
+        }
+    }
+}
+
+impl<ConditionA, ConditionB> System for CombinedCondition<ConditionA, ConditionB>
+where
+    ConditionA: System<In = (), Out = bool>,
+    ConditionB: System<In = (), Out = bool>,
+{
+    type In = ();
+    type Out = bool;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        &self.component_access
+    }
+
+    fn is_send(&self) -> bool {
+        self.condition_a.is_send() && self.condition_b.is_send()
+    }
+
+    unsafe fn run_unsafe(&mut self, _input: (), world: &World) -> bool {
+        let a = self.condition_a.run_unsafe((), world);
+        match self.operator {
+            ConditionOperator::And => a && self.condition_b.run_unsafe((), world),
+            ConditionOperator::Or => a || self.condition_b.run_unsafe((), world),
+        }
+    }
+
+    fn apply_buffers(&mut self, world: &mut World) {
+        self.condition_a.apply_buffers(world);
+        self.condition_b.apply_buffers(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.condition_a.initialize(world);
+        self.condition_b.initialize(world);
+        self.component_access
+            .extend(self.condition_a.component_access());
+        self.component_access
+            .extend(self.condition_b.component_access());
+    }
+
+    fn update_archetype_component_access(&mut self, world: &World) {
+        self.condition_a.update_archetype_component_access(world);
+        self.condition_b.update_archetype_component_access(world);
+
+        self.archetype_component_access
+            .extend(self.condition_a.archetype_component_access());
+        self.archetype_component_access
+            .extend(self.condition_b.archetype_component_access());
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        self.condition_a.check_change_tick(change_tick);
+        self.condition_b.check_change_tick(change_tick);
+    }
+}
+
+// SAFETY: Both conditions only read from the world
+unsafe impl<ConditionA, ConditionB> ReadOnlySystem for CombinedCondition<ConditionA, ConditionB>
+where
+    ConditionA: ReadOnlySystem<In = (), Out = bool>,
+    ConditionB: ReadOnlySystem<In = (), Out = bool>,
+{
+}
+
+/// A run condition inverting another run condition, see [`not`].
+pub struct NotCondition<Condition> {
+    condition: Condition,
+    name: Cow<'static, str>,
+}
+
+impl<Condition: System<In = (), Out = bool>> System for NotCondition<Condition> {
+    type In = ();
+    type Out = bool;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        self.condition.archetype_component_access()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        self.condition.component_access()
+    }
+
+    fn is_send(&self) -> bool {
+        self.condition.is_send()
+    }
+
+    unsafe fn run_unsafe(&mut self, _input: (), world: &World) -> bool {
+        !self.condition.run_unsafe((), world)
+    }
+
+    fn apply_buffers(&mut self, world: &mut World) {
+        self.condition.apply_buffers(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.condition.initialize(world);
+    }
+
+    fn update_archetype_component_access(&mut self, world: &World) {
+        self.condition.update_archetype_component_access(world);
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        self.condition.check_change_tick(change_tick);
+    }
+}
+
+// SAFETY: The inverted condition only reads from the world
+unsafe impl<Condition: ReadOnlySystem<In = (), Out = bool>> ReadOnlySystem
+    for NotCondition<Condition>
+{
+}
+
+/// Evaluates the run conditions of a system, in order until one of them is `false`.
+pub(crate) fn evaluate_conditions(conditions: &mut [BoxedRunCondition], world: &mut World) -> bool {
+    conditions.iter_mut().all(|condition| {
+        let should_run = condition.run((), world);
+        condition.apply_buffers(world);
+        should_run
+    })
+}
@@ -3,6 +3,7 @@ use crate::{
     component::ComponentId,
     prelude::IntoSystem,
     schedule::{
+        evaluate_conditions,
         graph_utils::{self, DependencyGraphError},
         BoxedRunCriteria, BoxedRunCriteriaLabel, BoxedSystemLabel, DuplicateLabelStrategy,
         ExclusiveSystemContainer, GraphNode, InsertionPoint, ParallelExecutor,
@@ -408,6 +409,9 @@ impl SystemStage {
                 container.set_run_criteria(new_indices[index]);
             }
             container.system_mut().initialize(world);
+            for condition in &mut container.conditions {
+                condition.initialize(world);
+            }
         }
         for index in self.uninitialized_before_commands.drain(..) {
             let container = &mut self.exclusive_before_commands[index];
@@ -415,6 +419,9 @@ impl SystemStage {
                 container.set_run_criteria(new_indices[index]);
             }
             container.system_mut().initialize(world);
+            for condition in &mut container.conditions {
+                condition.initialize(world);
+            }
         }
         for index in self.uninitialized_at_end.drain(..) {
             let container = &mut self.exclusive_at_end[index];
@@ -422,6 +429,9 @@ impl SystemStage {
                 container.set_run_criteria(new_indices[index]);
             }
             container.system_mut().initialize(world);
+            for condition in &mut container.conditions {
+                condition.initialize(world);
+            }
         }
         for index in self.uninitialized_parallel.drain(..) {
             let container = &mut self.parallel[index];
@@ -429,6 +439,9 @@ impl SystemStage {
                 container.set_run_criteria(new_indices[index]);
             }
             container.system_mut().initialize(world);
+            for condition in &mut container.conditions {
+                condition.initialize(world);
+            }
         }
     }
 
@@ -600,15 +613,27 @@ impl SystemStage {
             // Check all system change ticks.
             for exclusive_system in &mut self.exclusive_at_start {
                 exclusive_system.system_mut().check_change_tick(change_tick);
+                for condition in &mut exclusive_system.conditions {
+                    condition.check_change_tick(change_tick);
+                }
             }
             for exclusive_system in &mut self.exclusive_before_commands {
                 exclusive_system.system_mut().check_change_tick(change_tick);
+                for condition in &mut exclusive_system.conditions {
+                    condition.check_change_tick(change_tick);
+                }
             }
             for exclusive_system in &mut self.exclusive_at_end {
                 exclusive_system.system_mut().check_change_tick(change_tick);
+                for condition in &mut exclusive_system.conditions {
+                    condition.check_change_tick(change_tick);
+                }
             }
             for parallel_system in &mut self.parallel {
                 parallel_system.system_mut().check_change_tick(change_tick);
+                for condition in &mut parallel_system.conditions {
+                    condition.check_change_tick(change_tick);
+                }
             }
 
             // Check all component change ticks.
@@ -866,7 +891,9 @@ impl Stage for SystemStage {
 
                 // Run systems that want to be at the start of stage.
                 for container in &mut self.exclusive_at_start {
-                    if should_run(container, &self.run_criteria, default_should_run) {
+                    if should_run(container, &self.run_criteria, default_should_run)
+                        && evaluate_conditions(&mut container.conditions, world)
+                    {
                         #[cfg(feature = "trace")]
                         let _system_span = bevy_utils::tracing::info_span!(
                             "exclusive_system",
@@ -881,13 +908,16 @@ impl Stage for SystemStage {
                 // TODO: hard dependencies, nested sets, whatever... should be evaluated here.
                 for container in &mut self.parallel {
                     container.should_run =
-                        should_run(container, &self.run_criteria, default_should_run);
+                        should_run(container, &self.run_criteria, default_should_run)
+                            && evaluate_conditions(&mut container.conditions, world);
                 }
                 self.executor.run_systems(&mut self.parallel, world);
 
                 // Run systems that want to be between parallel systems and their command buffers.
                 for container in &mut self.exclusive_before_commands {
-                    if should_run(container, &self.run_criteria, default_should_run) {
+                    if should_run(container, &self.run_criteria, default_should_run)
+                        && evaluate_conditions(&mut container.conditions, world)
+                    {
                         #[cfg(feature = "trace")]
                         let _system_span = bevy_utils::tracing::info_span!(
                             "exclusive_system",
@@ -915,7 +945,9 @@ impl Stage for SystemStage {
 
                 // Run systems that want to be at the end of stage.
                 for container in &mut self.exclusive_at_end {
-                    if should_run(container, &self.run_criteria, default_should_run) {
+                    if should_run(container, &self.run_criteria, default_should_run)
+                        && evaluate_conditions(&mut container.conditions, world)
+                    {
                         #[cfg(feature = "trace")]
                         let _system_span = bevy_utils::tracing::info_span!(
                             "exclusive_system",
@@ -974,11 +1006,12 @@ impl Stage for SystemStage {
 mod tests {
     use crate::{
         schedule::{
-            BoxedSystemLabel, ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion,
-            RunCriteria, RunCriteriaDescriptorCoercion, ShouldRun, SingleThreadedExecutor, Stage,
-            SystemSet, SystemStage,
+            not, resource_exists, BoxedSystemLabel, ExclusiveSystemDescriptorCoercion,
+            IntoRunCondition, ParallelSystemDescriptorCoercion, RunCriteria,
+            RunCriteriaDescriptorCoercion, ShouldRun, SingleThreadedExecutor, Stage, SystemSet,
+            SystemStage,
         },
-        system::{In, IntoExclusiveSystem, Local, Query, ResMut},
+        system::{In, IntoExclusiveSystem, Local, Query, Res, ResMut},
         world::World,
     };
 
@@ -995,6 +1028,11 @@ mod tests {
         move |mut resource: ResMut<Vec<usize>>| resource.push(tag)
     }
 
+    fn every_other_time_condition(mut has_ran: Local<bool>) -> bool {
+        *has_ran = !*has_ran;
+        *has_ran
+    }
+
     fn every_other_time(mut has_ran: Local<bool>) -> ShouldRun {
         *has_ran = !*has_ran;
         if *has_ran {
@@ -1249,6 +1287,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exclusive_run_if() {
+        let mut world = World::new();
+        world.insert_resource(Vec::<usize>::new());
+        let mut stage = SystemStage::parallel()
+            .with_system(make_exclusive(0).exclusive_system().before("1"))
+            .with_system(
+                make_exclusive(1)
+                    .exclusive_system()
+                    .label("1")
+                    .run_if(every_other_time_condition),
+            )
+            .with_system(
+                make_exclusive(2)
+                    .exclusive_system()
+                    .after("1")
+                    .run_if(resource_exists::<u32>()),
+            );
+        stage.run(&mut world);
+        stage.run(&mut world);
+        world.insert_resource(0u32);
+        stage.set_executor(Box::new(SingleThreadedExecutor::default()));
+        stage.run(&mut world);
+        stage.run(&mut world);
+        assert_eq!(
+            *world.resource::<Vec<usize>>(),
+            vec![0, 1, 0, 0, 1, 2, 0, 2]
+        );
+    }
+
+    #[test]
+    fn exclusive_run_if_is_evaluated_before_the_system() {
+        let mut world = World::new();
+        world.insert_resource(Vec::<usize>::new());
+        // The condition of the exclusive system sees what the parallel system did in the same run
+        let mut stage = SystemStage::parallel()
+            .with_system(make_parallel(0))
+            .with_system(
+                make_exclusive(1)
+                    .exclusive_system()
+                    .at_end()
+                    .run_if(|resource: Res<Vec<usize>>| resource.len() == 1),
+            );
+        stage.run(&mut world);
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<Vec<usize>>(), vec![0, 1, 0]);
+    }
+
     #[test]
     #[should_panic]
     fn exclusive_cycle_1() {
@@ -1560,6 +1646,42 @@ mod tests {
         assert_eq!(stage.run_criteria.len(), 1);
     }
 
+    #[test]
+    fn parallel_run_if() {
+        let mut world = World::new();
+        world.insert_resource(Vec::<usize>::new());
+        let mut stage = SystemStage::parallel()
+            .with_system(make_parallel(0).label("0"))
+            .with_system(
+                make_parallel(1)
+                    .label("1")
+                    .after("0")
+                    .run_if(every_other_time_condition),
+            )
+            .with_system(
+                make_parallel(2)
+                    .label("2")
+                    .after("1")
+                    .run_if(resource_exists::<u32>().or(every_other_time_condition)),
+            )
+            .with_system(
+                make_parallel(3)
+                    .after("2")
+                    .run_if(not(resource_exists::<u32>()).and(every_other_time_condition))
+                    .run_if(every_other_time_condition),
+            );
+        stage.run(&mut world);
+        stage.run(&mut world);
+        world.insert_resource(0u32);
+        stage.set_executor(Box::new(SingleThreadedExecutor::default()));
+        stage.run(&mut world);
+        stage.run(&mut world);
+        assert_eq!(
+            *world.resource::<Vec<usize>>(),
+            vec![0, 1, 2, 3, 0, 0, 1, 2, 0, 2]
+        );
+    }
+
     #[test]
     #[should_panic]
     fn duplicate_run_criteria_label_panic() {
@@ -1,778 +1,400 @@
 use crate::{
     schedule::{
-        RunCriteriaDescriptor, RunCriteriaDescriptorCoercion, RunCriteriaLabel, ShouldRun,
-        SystemSet,
+        BoxedStageLabel, IntoSystemDescriptor, Stage, StageLabel, SystemDescriptor, SystemSet,
+        SystemStage,
     },
-    system::{In, IntoChainSystem, Local, Res, ResMut},
+    system::Res,
+    world::World,
 };
-use std::{
-    any::TypeId,
-    fmt::{self, Debug},
-    hash::Hash,
-};
-
-pub trait StateData: Send + Sync + Clone + Eq + Debug + Hash + 'static {}
-impl<T> StateData for T where T: Send + Sync + Clone + Eq + Debug + Hash + 'static {}
+pub use bevy_ecs_macros::States;
+use bevy_utils::{tracing::warn, HashMap};
+use std::{fmt::Debug, hash::Hash, mem};
 
-/// ### Stack based state machine
+/// Types that can define the states of an app, like the screens of a game.
 ///
-/// This state machine has four operations: Push, Pop, Set and Replace.
-/// * Push pushes a new state to the state stack, pausing the previous state
-/// * Pop removes the current state, and unpauses the last paused state
-/// * Set replaces the active state with a new one
-/// * Replace unwinds the state stack, and replaces the entire stack with a single new state
-#[derive(Debug)]
-pub struct State<T: StateData> {
-    transition: Option<StateTransition<T>>,
-    /// The current states in the stack.
-    ///
-    /// There is always guaranteed to be at least one.
-    stack: Vec<T>,
-    scheduled: Option<ScheduledOperation<T>>,
-    end_next_loop: bool,
-}
-
-#[derive(Debug)]
-enum StateTransition<T: StateData> {
-    PreStartup,
-    Startup,
-    // The parameter order is always (leaving, entering)
-    ExitingToResume(T, T),
-    ExitingFull(T, T),
-    Entering(T, T),
-    Resuming(T, T),
-    Pausing(T, T),
-}
+/// It can be derived for enums without fields, the initial state being the [`Default`] one.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+/// enum GameState {
+///     #[default]
+///     MainMenu,
+///     InGame,
+///     Paused,
+/// }
+///
+/// assert_eq!(GameState::variants().count(), 3);
+/// ```
+pub trait States: Send + Sync + Clone + PartialEq + Eq + Hash + Debug + Default + 'static {
+    type Iter: Iterator<Item = Self>;
 
-#[derive(Debug)]
-enum ScheduledOperation<T: StateData> {
-    Set(T),
-    Replace(T),
-    Pop,
-    Push(T),
+    /// Returns all the states, in the order of their declaration.
+    fn variants() -> Self::Iter;
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
-enum StateCallback {
-    Update,
-    InactiveUpdate,
-    InStackUpdate,
-    Enter,
-    Exit,
-    Pause,
-    Resume,
-}
+/// The current state of type `S`.
+///
+/// It is changed by setting the [`NextState`] resource: the transition is applied by
+/// [`apply_state_transition`], which runs the [`OnExit`], [`OnTransition`] and [`OnEnter`]
+/// schedules. Use [`in_state`] to only run a system in a given state.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct State<S: States>(S);
 
-impl StateCallback {
-    fn into_label<T>(self, state: T) -> StateRunCriteriaLabel<T>
-    where
-        T: StateData,
-    {
-        StateRunCriteriaLabel(state, self)
+impl<S: States> State<S> {
+    pub fn new(state: S) -> Self {
+        Self(state)
     }
-}
 
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
-struct StateRunCriteriaLabel<T>(T, StateCallback);
-impl<T> RunCriteriaLabel for StateRunCriteriaLabel<T>
-where
-    T: StateData,
-{
-    fn dyn_clone(&self) -> Box<dyn RunCriteriaLabel> {
-        Box::new(self.clone())
+    /// Returns the current state.
+    pub fn get(&self) -> &S {
+        &self.0
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
-struct DriverLabel(TypeId);
-impl RunCriteriaLabel for DriverLabel {
-    fn dyn_clone(&self) -> Box<dyn RunCriteriaLabel> {
-        Box::new(self.clone())
+impl<S: States> PartialEq<S> for State<S> {
+    fn eq(&self, other: &S) -> bool {
+        self.0 == *other
     }
 }
 
-impl DriverLabel {
-    fn of<T: 'static>() -> Self {
-        Self(TypeId::of::<T>())
+/// The state of type `S` to transition to, the next time [`apply_state_transition`] runs.
+///
+/// Setting it to the current state does nothing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NextState<S: States>(pub Option<S>);
+
+impl<S: States> Default for NextState<S> {
+    fn default() -> Self {
+        Self(None)
     }
 }
 
-impl<T> State<T>
-where
-    T: StateData,
-{
-    pub fn on_update(pred: T) -> RunCriteriaDescriptor {
-        let pred_clone = pred.clone();
-        (move |state: Res<State<T>>| {
-            state.stack.last().unwrap() == &pred && state.transition.is_none()
-        })
-        .chain(should_run_adapter::<T>)
-        .after(DriverLabel::of::<T>())
-        .label_discard_if_duplicate(StateCallback::Update.into_label(pred_clone))
+impl<S: States> NextState<S> {
+    /// Schedules a transition to the given state.
+    pub fn set(&mut self, state: S) {
+        self.0 = Some(state);
     }
+}
 
-    pub fn on_inactive_update(pred: T) -> RunCriteriaDescriptor {
-        let pred_clone = pred.clone();
-        (move |state: Res<State<T>>, mut is_inactive: Local<bool>| match &state.transition {
-            Some(StateTransition::Pausing(ref relevant, _))
-            | Some(StateTransition::Resuming(_, ref relevant)) => {
-                if relevant == &pred {
-                    *is_inactive = !*is_inactive;
-                }
-                false
-            }
-            Some(_) => false,
-            None => *is_inactive,
-        })
-        .chain(should_run_adapter::<T>)
-        .after(DriverLabel::of::<T>())
-        .label_discard_if_duplicate(StateCallback::InactiveUpdate.into_label(pred_clone))
-    }
+/// The label of the schedule run when entering a state, see [`Schedules`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnEnter<S: States>(pub S);
 
-    pub fn on_in_stack_update(pred: T) -> RunCriteriaDescriptor {
-        let pred_clone = pred.clone();
-        (move |state: Res<State<T>>, mut is_in_stack: Local<bool>| match &state.transition {
-            Some(StateTransition::Entering(ref relevant, _))
-            | Some(StateTransition::ExitingToResume(_, ref relevant))
-            | Some(StateTransition::ExitingFull(_, ref relevant)) => {
-                if relevant == &pred {
-                    *is_in_stack = !*is_in_stack;
-                }
-                false
-            }
-            Some(StateTransition::Startup) => {
-                if state.stack.last().unwrap() == &pred {
-                    *is_in_stack = !*is_in_stack;
-                }
-                false
-            }
-            Some(_) => false,
-            None => *is_in_stack,
-        })
-        .chain(should_run_adapter::<T>)
-        .after(DriverLabel::of::<T>())
-        .label_discard_if_duplicate(StateCallback::InStackUpdate.into_label(pred_clone))
+impl<S: States> StageLabel for OnEnter<S> {
+    fn dyn_clone(&self) -> Box<dyn StageLabel> {
+        Box::new(self.clone())
     }
+}
 
-    pub fn on_enter(pred: T) -> RunCriteriaDescriptor {
-        let pred_clone = pred.clone();
-        (move |state: Res<State<T>>| {
-            state
-                .transition
-                .as_ref()
-                .map_or(false, |transition| match transition {
-                    StateTransition::Entering(_, entering) => entering == &pred,
-                    StateTransition::Startup => state.stack.last().unwrap() == &pred,
-                    _ => false,
-                })
-        })
-        .chain(should_run_adapter::<T>)
-        .after(DriverLabel::of::<T>())
-        .label_discard_if_duplicate(StateCallback::Enter.into_label(pred_clone))
-    }
+/// The label of the schedule run when exiting a state, see [`Schedules`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnExit<S: States>(pub S);
 
-    pub fn on_exit(pred: T) -> RunCriteriaDescriptor {
-        let pred_clone = pred.clone();
-        (move |state: Res<State<T>>| {
-            state
-                .transition
-                .as_ref()
-                .map_or(false, |transition| match transition {
-                    StateTransition::ExitingToResume(exiting, _)
-                    | StateTransition::ExitingFull(exiting, _) => exiting == &pred,
-                    _ => false,
-                })
-        })
-        .chain(should_run_adapter::<T>)
-        .after(DriverLabel::of::<T>())
-        .label_discard_if_duplicate(StateCallback::Exit.into_label(pred_clone))
+impl<S: States> StageLabel for OnExit<S> {
+    fn dyn_clone(&self) -> Box<dyn StageLabel> {
+        Box::new(self.clone())
     }
+}
 
-    pub fn on_pause(pred: T) -> RunCriteriaDescriptor {
-        let pred_clone = pred.clone();
-        (move |state: Res<State<T>>| {
-            state
-                .transition
-                .as_ref()
-                .map_or(false, |transition| match transition {
-                    StateTransition::Pausing(pausing, _) => pausing == &pred,
-                    _ => false,
-                })
-        })
-        .chain(should_run_adapter::<T>)
-        .after(DriverLabel::of::<T>())
-        .label_discard_if_duplicate(StateCallback::Pause.into_label(pred_clone))
-    }
+/// The label of the schedule run when transitioning from a state to another, between the
+/// [`OnExit`] and [`OnEnter`] schedules, see [`Schedules`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnTransition<S: States> {
+    pub from: S,
+    pub to: S,
+}
 
-    pub fn on_resume(pred: T) -> RunCriteriaDescriptor {
-        let pred_clone = pred.clone();
-        (move |state: Res<State<T>>| {
-            state
-                .transition
-                .as_ref()
-                .map_or(false, |transition| match transition {
-                    StateTransition::Resuming(_, resuming) => resuming == &pred,
-                    _ => false,
-                })
-        })
-        .chain(should_run_adapter::<T>)
-        .after(DriverLabel::of::<T>())
-        .label_discard_if_duplicate(StateCallback::Resume.into_label(pred_clone))
+impl<S: States> StageLabel for OnTransition<S> {
+    fn dyn_clone(&self) -> Box<dyn StageLabel> {
+        Box::new(self.clone())
     }
+}
 
-    pub fn on_update_set(s: T) -> SystemSet {
-        SystemSet::new().with_run_criteria(Self::on_update(s))
-    }
+/// Stages that are run on demand with [`World::run_schedule`] rather than every frame, like
+/// the [`OnEnter`] and [`OnExit`] schedules of states.
+#[derive(Default)]
+pub struct Schedules {
+    stages: HashMap<BoxedStageLabel, SystemStage>,
+    /// The schedules taken out by [`World::run_schedule`], with the systems added to them while
+    /// they run
+    running: HashMap<BoxedStageLabel, Vec<PendingSystems>>,
+}
 
-    pub fn on_inactive_update_set(s: T) -> SystemSet {
-        SystemSet::new().with_run_criteria(Self::on_inactive_update(s))
-    }
+/// Systems added to a running schedule, added to it once it's done running.
+enum PendingSystems {
+    System(SystemDescriptor),
+    SystemSet(SystemSet),
+}
 
-    pub fn on_enter_set(s: T) -> SystemSet {
-        SystemSet::new().with_run_criteria(Self::on_enter(s))
+impl Schedules {
+    /// Returns `true` if there is a schedule with this label.
+    pub fn contains(&self, label: &dyn StageLabel) -> bool {
+        self.stages.contains_key(label) || self.running.contains_key(label)
     }
 
-    pub fn on_exit_set(s: T) -> SystemSet {
-        SystemSet::new().with_run_criteria(Self::on_exit(s))
+    /// Returns `true` if the schedule with this label is being run by [`World::run_schedule`].
+    pub fn is_running(&self, label: &dyn StageLabel) -> bool {
+        self.running.contains_key(label)
     }
 
-    pub fn on_pause_set(s: T) -> SystemSet {
-        SystemSet::new().with_run_criteria(Self::on_pause(s))
+    pub fn get(&self, label: &dyn StageLabel) -> Option<&SystemStage> {
+        self.stages.get(label)
     }
 
-    pub fn on_resume_set(s: T) -> SystemSet {
-        SystemSet::new().with_run_criteria(Self::on_resume(s))
+    pub fn get_mut(&mut self, label: &dyn StageLabel) -> Option<&mut SystemStage> {
+        self.stages.get_mut(label)
     }
 
-    /// Creates a driver set for the State.
+    /// Returns the schedule with this label, adding an empty parallel one if it doesn't exist.
     ///
-    /// Important note: this set must be inserted **before** all other state-dependant sets to work
-    /// properly!
-    pub fn get_driver() -> SystemSet {
-        SystemSet::default().with_run_criteria(state_cleaner::<T>.label(DriverLabel::of::<T>()))
-    }
-
-    pub fn new(initial: T) -> Self {
-        Self {
-            stack: vec![initial],
-            transition: Some(StateTransition::PreStartup),
-            scheduled: None,
-            end_next_loop: false,
-        }
-    }
-
-    /// Schedule a state change that replaces the active state with the given state.
-    /// This will fail if there is a scheduled operation, pending transition, or if the given
-    /// `state` matches the current state
-    pub fn set(&mut self, state: T) -> Result<(), StateError> {
-        if self.stack.last().unwrap() == &state {
-            return Err(StateError::AlreadyInState);
-        }
-
-        if self.scheduled.is_some() || self.transition.is_some() {
-            return Err(StateError::StateAlreadyQueued);
-        }
-
-        self.scheduled = Some(ScheduledOperation::Set(state));
-        Ok(())
+    /// A running schedule isn't accessible: [`Self::get`] and [`Self::get_mut`] return `None`,
+    /// and a schedule inserted in its place while it runs is dropped. Use [`Self::add_system`]
+    /// and [`Self::add_system_set`] to add systems to it instead.
+    pub fn get_or_insert(&mut self, label: impl StageLabel) -> &mut SystemStage {
+        self.stages
+            .entry(Box::new(label))
+            .or_insert_with(SystemStage::parallel)
     }
 
-    /// Same as [`Self::set`], but if there is already a next state, it will be overwritten
-    /// instead of failing
-    pub fn overwrite_set(&mut self, state: T) -> Result<(), StateError> {
-        if self.stack.last().unwrap() == &state {
-            return Err(StateError::AlreadyInState);
-        }
-
-        self.scheduled = Some(ScheduledOperation::Set(state));
-        Ok(())
-    }
-
-    /// Schedule a state change that replaces the full stack with the given state.
-    /// This will fail if there is a scheduled operation, pending transition, or if the given
-    /// `state` matches the current state
-    pub fn replace(&mut self, state: T) -> Result<(), StateError> {
-        if self.stack.last().unwrap() == &state {
-            return Err(StateError::AlreadyInState);
-        }
-
-        if self.scheduled.is_some() || self.transition.is_some() {
-            return Err(StateError::StateAlreadyQueued);
-        }
-
-        self.scheduled = Some(ScheduledOperation::Replace(state));
-        Ok(())
-    }
-
-    /// Same as [`Self::replace`], but if there is already a next state, it will be overwritten
-    /// instead of failing
-    pub fn overwrite_replace(&mut self, state: T) -> Result<(), StateError> {
-        if self.stack.last().unwrap() == &state {
-            return Err(StateError::AlreadyInState);
-        }
-
-        self.scheduled = Some(ScheduledOperation::Replace(state));
-        Ok(())
-    }
-
-    /// Same as [`Self::set`], but does a push operation instead of a next operation
-    pub fn push(&mut self, state: T) -> Result<(), StateError> {
-        if self.stack.last().unwrap() == &state {
-            return Err(StateError::AlreadyInState);
-        }
-
-        if self.scheduled.is_some() || self.transition.is_some() {
-            return Err(StateError::StateAlreadyQueued);
+    /// Adds a system to the schedule with this label.
+    ///
+    /// If the schedule is running, the system is added once it's done running.
+    pub fn add_system<Params>(
+        &mut self,
+        label: impl StageLabel,
+        system: impl IntoSystemDescriptor<Params>,
+    ) -> &mut Self {
+        match self.running.get_mut(&label as &dyn StageLabel) {
+            Some(pending) => pending.push(PendingSystems::System(system.into_descriptor())),
+            None => {
+                self.get_or_insert(label).add_system(system);
+            }
         }
-
-        self.scheduled = Some(ScheduledOperation::Push(state));
-        Ok(())
+        self
     }
 
-    /// Same as [`Self::push`], but if there is already a next state, it will be overwritten
-    /// instead of failing
-    pub fn overwrite_push(&mut self, state: T) -> Result<(), StateError> {
-        if self.stack.last().unwrap() == &state {
-            return Err(StateError::AlreadyInState);
+    /// Adds a [`SystemSet`] to the schedule with this label.
+    ///
+    /// If the schedule is running, the set is added once it's done running.
+    pub fn add_system_set(&mut self, label: impl StageLabel, system_set: SystemSet) -> &mut Self {
+        match self.running.get_mut(&label as &dyn StageLabel) {
+            Some(pending) => pending.push(PendingSystems::SystemSet(system_set)),
+            None => {
+                self.get_or_insert(label).add_system_set(system_set);
+            }
         }
-
-        self.scheduled = Some(ScheduledOperation::Push(state));
-        Ok(())
+        self
     }
+}
 
-    /// Same as [`Self::set`], but does a pop operation instead of a set operation
-    pub fn pop(&mut self) -> Result<(), StateError> {
-        if self.scheduled.is_some() || self.transition.is_some() {
-            return Err(StateError::StateAlreadyQueued);
-        }
-
-        if self.stack.len() == 1 {
-            return Err(StateError::StackEmpty);
+impl World {
+    /// Runs the schedule of the [`Schedules`] resource with this label.
+    ///
+    /// The schedule is taken out of the resource while it runs, and the systems added to it in
+    /// the meantime are added once it's done. It may run other schedules, but not itself: a
+    /// warning is logged instead, as well as when there is no schedule with this label.
+    pub fn run_schedule(&mut self, label: impl StageLabel) {
+        let mut schedules = match self.get_resource_mut::<Schedules>() {
+            Some(schedules) => schedules,
+            None => {
+                warn!(
+                    "Could not run the schedule {:?}: there is no Schedules resource",
+                    label
+                );
+                return;
+            }
+        };
+        if schedules.is_running(&label) {
+            warn!(
+                "Could not run the schedule {:?}: it is already running",
+                label
+            );
+            return;
         }
+        let (label, mut stage) = match schedules.stages.remove_entry(&label as &dyn StageLabel) {
+            Some(schedule) => schedule,
+            None => {
+                warn!("Could not run the schedule {:?}: it does not exist", label);
+                return;
+            }
+        };
+        schedules.running.insert(label.dyn_clone(), Vec::new());
 
-        self.scheduled = Some(ScheduledOperation::Pop);
-        Ok(())
-    }
+        stage.run(self);
 
-    /// Same as [`Self::pop`], but if there is already a next state, it will be overwritten
-    /// instead of failing
-    pub fn overwrite_pop(&mut self) -> Result<(), StateError> {
-        if self.stack.len() == 1 {
-            return Err(StateError::StackEmpty);
+        let mut schedules = self.resource_mut::<Schedules>();
+        for pending in schedules.running.remove(&label).unwrap_or_default() {
+            match pending {
+                PendingSystems::System(system) => stage.add_system(system),
+                PendingSystems::SystemSet(system_set) => stage.add_system_set(system_set),
+            };
         }
-        self.scheduled = Some(ScheduledOperation::Pop);
-        Ok(())
-    }
-
-    /// Schedule a state change that restarts the active state.
-    /// This will fail if there is a scheduled operation or a pending transition
-    pub fn restart(&mut self) -> Result<(), StateError> {
-        if self.scheduled.is_some() || self.transition.is_some() {
-            return Err(StateError::StateAlreadyQueued);
+        if schedules.stages.contains_key(&label) {
+            warn!(
+                "The schedule {:?} inserted while it was running is dropped",
+                label
+            );
         }
-
-        let state = self.stack.last().unwrap();
-        self.scheduled = Some(ScheduledOperation::Set(state.clone()));
-        Ok(())
-    }
-
-    /// Same as [`Self::restart`], but if there is already a scheduled state operation,
-    /// it will be overwritten instead of failing
-    pub fn overwrite_restart(&mut self) {
-        let state = self.stack.last().unwrap();
-        self.scheduled = Some(ScheduledOperation::Set(state.clone()));
-    }
-
-    pub fn current(&self) -> &T {
-        self.stack.last().unwrap()
-    }
-
-    pub fn inactives(&self) -> &[T] {
-        self.stack.split_last().map(|(_, rest)| rest).unwrap()
-    }
-
-    /// Clears the scheduled state operation.
-    pub fn clear_schedule(&mut self) {
-        self.scheduled = None;
+        schedules.stages.insert(label, stage);
     }
 }
 
-#[derive(Debug)]
-pub enum StateError {
-    AlreadyInState,
-    StateAlreadyQueued,
-    StackEmpty,
-}
-
-impl std::error::Error for StateError {}
-
-impl fmt::Display for StateError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            StateError::AlreadyInState => {
-                write!(f, "Attempted to change the state to the current state.")
-            }
-            StateError::StateAlreadyQueued => write!(
-                f,
-                "Attempted to queue a state change, but there was already a state queued."
-            ),
-            StateError::StackEmpty => {
-                write!(f, "Attempted to queue a pop, but there is nothing to pop.")
-            }
-        }
+/// Runs the schedule with this label if it exists, as a state doesn't need a schedule for each
+/// transition.
+fn run_state_schedule(world: &mut World, label: impl StageLabel) {
+    if matches!(world.get_resource::<Schedules>(), Some(schedules) if schedules.contains(&label)) {
+        world.run_schedule(label);
     }
 }
 
-fn should_run_adapter<T: StateData>(In(cmp_result): In<bool>, state: Res<State<T>>) -> ShouldRun {
-    if state.end_next_loop {
-        return ShouldRun::No;
-    }
-    if cmp_result {
-        ShouldRun::YesAndCheckAgain
-    } else {
-        ShouldRun::NoAndCheckAgain
-    }
+/// Applies the transition to the [`NextState`] of type `S`, if any, running the [`OnExit`],
+/// [`OnTransition`] and [`OnEnter`] schedules.
+///
+/// It is added to `CoreStage::StateTransitions` by `App::add_state`.
+pub fn apply_state_transition<S: States>(world: &mut World) {
+    let entered = match world.get_resource_mut::<NextState<S>>() {
+        Some(mut next_state) if next_state.0.is_some() => next_state.0.take().unwrap(),
+        _ => return,
+    };
+    if *world.resource::<State<S>>() == entered {
+        return;
+    }
+    let exited = mem::replace(&mut world.resource_mut::<State<S>>().0, entered.clone());
+    run_state_schedule(world, OnExit(exited.clone()));
+    run_state_schedule(
+        world,
+        OnTransition {
+            from: exited,
+            to: entered.clone(),
+        },
+    );
+    run_state_schedule(world, OnEnter(entered));
 }
 
-fn state_cleaner<T: StateData>(
-    mut state: ResMut<State<T>>,
-    mut prep_exit: Local<bool>,
-) -> ShouldRun {
-    if *prep_exit {
-        *prep_exit = false;
-        if state.scheduled.is_none() {
-            state.end_next_loop = true;
-            return ShouldRun::YesAndCheckAgain;
-        }
-    } else if state.end_next_loop {
-        state.end_next_loop = false;
-        return ShouldRun::No;
-    }
-    match state.scheduled.take() {
-        Some(ScheduledOperation::Set(next)) => {
-            state.transition = Some(StateTransition::ExitingFull(
-                state.stack.last().unwrap().clone(),
-                next,
-            ));
-        }
-        Some(ScheduledOperation::Replace(next)) => {
-            if state.stack.len() <= 1 {
-                state.transition = Some(StateTransition::ExitingFull(
-                    state.stack.last().unwrap().clone(),
-                    next,
-                ));
-            } else {
-                state.scheduled = Some(ScheduledOperation::Replace(next));
-                match state.transition.take() {
-                    Some(StateTransition::ExitingToResume(p, n)) => {
-                        state.stack.pop();
-                        state.transition = Some(StateTransition::Resuming(p, n));
-                    }
-                    _ => {
-                        state.transition = Some(StateTransition::ExitingToResume(
-                            state.stack[state.stack.len() - 1].clone(),
-                            state.stack[state.stack.len() - 2].clone(),
-                        ));
-                    }
-                }
-            }
-        }
-        Some(ScheduledOperation::Push(next)) => {
-            let last_type_id = state.stack.last().unwrap().clone();
-            state.transition = Some(StateTransition::Pausing(last_type_id, next));
-        }
-        Some(ScheduledOperation::Pop) => {
-            state.transition = Some(StateTransition::ExitingToResume(
-                state.stack[state.stack.len() - 1].clone(),
-                state.stack[state.stack.len() - 2].clone(),
-            ));
-        }
-        None => match state.transition.take() {
-            Some(StateTransition::ExitingFull(p, n)) => {
-                state.transition = Some(StateTransition::Entering(p, n.clone()));
-                *state.stack.last_mut().unwrap() = n;
-            }
-            Some(StateTransition::Pausing(p, n)) => {
-                state.transition = Some(StateTransition::Entering(p, n.clone()));
-                state.stack.push(n);
-            }
-            Some(StateTransition::ExitingToResume(p, n)) => {
-                state.stack.pop();
-                state.transition = Some(StateTransition::Resuming(p, n));
-            }
-            Some(StateTransition::PreStartup) => {
-                state.transition = Some(StateTransition::Startup);
-            }
-            _ => {}
-        },
-    };
-    if state.transition.is_none() {
-        *prep_exit = true;
-    }
+/// Runs the [`OnEnter`] schedule of the current state of type `S`.
+///
+/// It is added to the startup schedule by `App::add_state`, to enter the initial state.
+pub fn run_enter_schedule<S: States>(world: &mut World) {
+    let state = world.resource::<State<S>>().0.clone();
+    run_state_schedule(world, OnEnter(state));
+}
 
-    ShouldRun::YesAndCheckAgain
+/// A run condition returning `true` if the current state of type `S` is `state`.
+pub fn in_state<S: States>(state: S) -> impl FnMut(Res<State<S>>) -> bool {
+    move |current: Res<State<S>>| *current == state
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate as bevy_ecs;
     use crate::prelude::*;
 
-    #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+    #[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
     enum MyState {
+        #[default]
         S1,
         S2,
         S3,
-        S4,
-        S5,
-        S6,
-        Final,
+    }
+
+    fn log(message: &'static str) -> impl FnMut(ResMut<Vec<&'static str>>) {
+        move |mut log: ResMut<Vec<&'static str>>| log.push(message)
     }
 
     #[test]
-    fn state_test() {
-        let mut world = World::default();
+    fn state_variants() {
+        assert_eq!(
+            MyState::variants().collect::<Vec<_>>(),
+            vec![MyState::S1, MyState::S2, MyState::S3]
+        );
+    }
 
+    #[test]
+    fn state_transitions() {
+        let mut world = World::default();
         world.insert_resource(Vec::<&'static str>::new());
-        world.insert_resource(State::new(MyState::S1));
-
-        let mut stage = SystemStage::parallel();
-
-        stage.add_system_set(State::<MyState>::get_driver());
-        stage
-            .add_system_set(
-                State::on_enter_set(MyState::S1)
-                    .with_system(|mut r: ResMut<Vec<&'static str>>| r.push("startup")),
-            )
-            .add_system_set(State::on_update_set(MyState::S1).with_system(
-                |mut r: ResMut<Vec<&'static str>>, mut s: ResMut<State<MyState>>| {
-                    r.push("update S1");
-                    s.overwrite_replace(MyState::S2).unwrap();
-                },
-            ))
-            .add_system_set(
-                State::on_enter_set(MyState::S2)
-                    .with_system(|mut r: ResMut<Vec<&'static str>>| r.push("enter S2")),
-            )
-            .add_system_set(State::on_update_set(MyState::S2).with_system(
-                |mut r: ResMut<Vec<&'static str>>, mut s: ResMut<State<MyState>>| {
-                    r.push("update S2");
-                    s.overwrite_replace(MyState::S3).unwrap();
-                },
-            ))
-            .add_system_set(
-                State::on_exit_set(MyState::S2)
-                    .with_system(|mut r: ResMut<Vec<&'static str>>| r.push("exit S2")),
-            )
-            .add_system_set(
-                State::on_enter_set(MyState::S3)
-                    .with_system(|mut r: ResMut<Vec<&'static str>>| r.push("enter S3")),
-            )
-            .add_system_set(State::on_update_set(MyState::S3).with_system(
-                |mut r: ResMut<Vec<&'static str>>, mut s: ResMut<State<MyState>>| {
-                    r.push("update S3");
-                    s.overwrite_push(MyState::S4).unwrap();
-                },
-            ))
-            .add_system_set(
-                State::on_pause_set(MyState::S3)
-                    .with_system(|mut r: ResMut<Vec<&'static str>>| r.push("pause S3")),
-            )
-            .add_system_set(State::on_update_set(MyState::S4).with_system(
-                |mut r: ResMut<Vec<&'static str>>, mut s: ResMut<State<MyState>>| {
-                    r.push("update S4");
-                    s.overwrite_push(MyState::S5).unwrap();
+        world.init_resource::<State<MyState>>();
+        world.init_resource::<NextState<MyState>>();
+        let mut schedules = Schedules::default();
+        schedules
+            .add_system(OnEnter(MyState::S1), log("enter S1"))
+            .add_system(OnExit(MyState::S1), log("exit S1"))
+            .add_system(OnEnter(MyState::S2), log("enter S2"))
+            .add_system(
+                OnTransition {
+                    from: MyState::S1,
+                    to: MyState::S2,
                 },
-            ))
-            .add_system_set(State::on_inactive_update_set(MyState::S4).with_system(
-                (|mut r: ResMut<Vec<&'static str>>| r.push("inactive S4")).label("inactive s4"),
-            ))
-            .add_system_set(
-                State::on_update_set(MyState::S5).with_system(
-                    (|mut r: ResMut<Vec<&'static str>>, mut s: ResMut<State<MyState>>| {
-                        r.push("update S5");
-                        s.overwrite_push(MyState::S6).unwrap();
-                    })
-                    .after("inactive s4"),
-                ),
-            )
-            .add_system_set(
-                State::on_inactive_update_set(MyState::S5).with_system(
-                    (|mut r: ResMut<Vec<&'static str>>| r.push("inactive S5"))
-                        .label("inactive s5")
-                        .after("inactive s4"),
-                ),
-            )
-            .add_system_set(
-                State::on_update_set(MyState::S6).with_system(
-                    (|mut r: ResMut<Vec<&'static str>>, mut s: ResMut<State<MyState>>| {
-                        r.push("update S6");
-                        s.overwrite_push(MyState::Final).unwrap();
-                    })
-                    .after("inactive s5"),
-                ),
-            )
-            .add_system_set(
-                State::on_resume_set(MyState::S4)
-                    .with_system(|mut r: ResMut<Vec<&'static str>>| r.push("resume S4")),
+                log("S1 -> S2"),
             )
-            .add_system_set(
-                State::on_exit_set(MyState::S5)
-                    .with_system(|mut r: ResMut<Vec<&'static str>>| r.push("exit S4")),
+            .add_system(OnExit(MyState::S2), log("exit S2"))
+            .add_system(
+                OnEnter(MyState::S3),
+                |mut next_state: ResMut<NextState<MyState>>| next_state.set(MyState::S1),
             );
+        world.insert_resource(schedules);
 
-        const EXPECTED: &[&str] = &[
-            //
-            "startup",
-            "update S1",
-            //
-            "enter S2",
-            "update S2",
-            //
-            "exit S2",
-            "enter S3",
-            "update S3",
-            //
-            "pause S3",
-            "update S4",
-            //
-            "inactive S4",
-            "update S5",
-            //
-            "inactive S4",
-            "inactive S5",
-            "update S6",
-            //
-            "inactive S4",
-            "inactive S5",
-        ];
+        let mut stage = SystemStage::parallel()
+            .with_system(apply_state_transition::<MyState>.exclusive_system())
+            .with_system(log("update S1").run_if(in_state(MyState::S1)))
+            .with_system(log("update S2").run_if(in_state(MyState::S2)));
 
+        run_enter_schedule::<MyState>(&mut world);
         stage.run(&mut world);
-        let mut collected = world.resource_mut::<Vec<&'static str>>();
-        let mut count = 0;
-        for (found, expected) in collected.drain(..).zip(EXPECTED) {
-            assert_eq!(found, *expected);
-            count += 1;
-        }
-        // If not equal, some elements weren't executed
-        assert_eq!(EXPECTED.len(), count);
+        world.resource_mut::<NextState<MyState>>().set(MyState::S1);
+        stage.run(&mut world);
+        world.resource_mut::<NextState<MyState>>().set(MyState::S2);
+        stage.run(&mut world);
+        world.resource_mut::<NextState<MyState>>().set(MyState::S3);
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<State<MyState>>(), MyState::S3);
+        stage.run(&mut world);
+
         assert_eq!(
-            world.resource::<State<MyState>>().current(),
-            &MyState::Final
+            *world.resource::<Vec<&'static str>>(),
+            vec![
+                "enter S1",
+                "update S1",
+                "update S1",
+                "exit S1",
+                "S1 -> S2",
+                "enter S2",
+                "update S2",
+                "exit S2",
+                "enter S1",
+                "update S1",
+            ]
         );
     }
 
-    #[test]
-    fn issue_1753() {
-        #[derive(Clone, PartialEq, Eq, Debug, Hash)]
-        enum AppState {
-            Main,
-        }
-
-        fn should_run_once(mut flag: ResMut<bool>, test_name: Res<&'static str>) {
-            assert!(!*flag, "{:?}", *test_name);
-            *flag = true;
-        }
-
-        let mut world = World::new();
-        world.insert_resource(State::new(AppState::Main));
-        world.insert_resource(false);
-        world.insert_resource("control");
-        let mut stage = SystemStage::parallel().with_system(should_run_once);
-        stage.run(&mut world);
-        assert!(*world.resource::<bool>(), "after control");
+    #[derive(StageLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct MySchedule;
 
-        world.insert_resource(false);
-        world.insert_resource("test");
-        let mut stage = SystemStage::parallel()
-            .with_system_set(State::<AppState>::get_driver())
-            .with_system(should_run_once);
-        stage.run(&mut world);
-        assert!(*world.resource::<bool>(), "after test");
+    fn run_and_add_system(world: &mut World) {
+        world.resource_mut::<Vec<&'static str>>().push("run");
+        // A schedule doesn't run itself
+        world.run_schedule(MySchedule);
+        world
+            .resource_mut::<Schedules>()
+            .add_system(MySchedule, log("added"));
     }
 
     #[test]
-    fn restart_state_tests() {
-        #[derive(Clone, PartialEq, Eq, Debug, Hash)]
-        enum LoadState {
-            Load,
-            Finish,
-        }
-
-        #[derive(PartialEq, Eq, Debug)]
-        enum LoadStatus {
-            EnterLoad,
-            ExitLoad,
-            EnterFinish,
-        }
-
-        let mut world = World::new();
-        world.insert_resource(Vec::<LoadStatus>::new());
-        world.insert_resource(State::new(LoadState::Load));
-
-        let mut stage = SystemStage::parallel();
-        stage.add_system_set(State::<LoadState>::get_driver());
-
-        // Systems to track loading status
-        stage
-            .add_system_set(
-                State::on_enter_set(LoadState::Load)
-                    .with_system(|mut r: ResMut<Vec<LoadStatus>>| r.push(LoadStatus::EnterLoad)),
-            )
-            .add_system_set(
-                State::on_exit_set(LoadState::Load)
-                    .with_system(|mut r: ResMut<Vec<LoadStatus>>| r.push(LoadStatus::ExitLoad)),
-            )
-            .add_system_set(
-                State::on_enter_set(LoadState::Finish)
-                    .with_system(|mut r: ResMut<Vec<LoadStatus>>| r.push(LoadStatus::EnterFinish)),
-            );
-
-        stage.run(&mut world);
-
-        // A. Restart state
-        let mut state = world.resource_mut::<State<LoadState>>();
-        let result = state.restart();
-        assert!(matches!(result, Ok(())));
-        stage.run(&mut world);
-
-        // B. Restart state (overwrite schedule)
-        let mut state = world.resource_mut::<State<LoadState>>();
-        state.set(LoadState::Finish).unwrap();
-        state.overwrite_restart();
-        stage.run(&mut world);
+    fn systems_added_to_a_running_schedule_are_kept() {
+        let mut world = World::default();
+        world.insert_resource(Vec::<&'static str>::new());
+        let mut schedules = Schedules::default();
+        schedules.add_system(MySchedule, run_and_add_system.exclusive_system());
+        world.insert_resource(schedules);
 
-        // C. Fail restart state (transition already scheduled)
-        let mut state = world.resource_mut::<State<LoadState>>();
-        state.set(LoadState::Finish).unwrap();
-        let result = state.restart();
-        assert!(matches!(result, Err(StateError::StateAlreadyQueued)));
-        stage.run(&mut world);
+        world.run_schedule(MySchedule);
+        assert_eq!(*world.resource::<Vec<&'static str>>(), vec!["run"]);
+        assert!(!world.resource::<Schedules>().is_running(&MySchedule));
 
-        const EXPECTED: &[LoadStatus] = &[
-            LoadStatus::EnterLoad,
-            // A
-            LoadStatus::ExitLoad,
-            LoadStatus::EnterLoad,
-            // B
-            LoadStatus::ExitLoad,
-            LoadStatus::EnterLoad,
-            // C
-            LoadStatus::ExitLoad,
-            LoadStatus::EnterFinish,
-        ];
-
-        let mut collected = world.resource_mut::<Vec<LoadStatus>>();
-        let mut count = 0;
-        for (found, expected) in collected.drain(..).zip(EXPECTED) {
-            assert_eq!(found, *expected);
-            count += 1;
-        }
-        // If not equal, some elements weren't executed
-        assert_eq!(EXPECTED.len(), count);
-        assert_eq!(
-            world.resource::<State<LoadState>>().current(),
-            &LoadState::Finish
-        );
+        world.resource_mut::<Vec<&'static str>>().clear();
+        world.run_schedule(MySchedule);
+        assert_eq!(*world.resource::<Vec<&'static str>>(), vec!["run", "added"]);
     }
 }
@@ -2,8 +2,8 @@ use crate::{
     component::ComponentId,
     query::Access,
     schedule::{
-        BoxedAmbiguitySetLabel, BoxedRunCriteriaLabel, BoxedSystemLabel, ExclusiveSystemDescriptor,
-        GraphNode, ParallelSystemDescriptor,
+        BoxedAmbiguitySetLabel, BoxedRunCondition, BoxedRunCriteriaLabel, BoxedSystemLabel,
+        ExclusiveSystemDescriptor, GraphNode, ParallelSystemDescriptor,
     },
     system::{ExclusiveSystem, System},
 };
@@ -28,6 +28,7 @@ pub(super) struct ExclusiveSystemContainer {
     system: Box<dyn ExclusiveSystem>,
     pub(super) run_criteria_index: Option<usize>,
     pub(super) run_criteria_label: Option<BoxedRunCriteriaLabel>,
+    pub(super) conditions: Vec<BoxedRunCondition>,
     dependencies: Vec<usize>,
     labels: Vec<BoxedSystemLabel>,
     before: Vec<BoxedSystemLabel>,
@@ -41,6 +42,7 @@ impl ExclusiveSystemContainer {
             system: descriptor.system,
             run_criteria_index: None,
             run_criteria_label: None,
+            conditions: descriptor.conditions,
             dependencies: Vec::new(),
             labels: descriptor.labels,
             before: descriptor.before,
@@ -109,6 +111,7 @@ pub struct ParallelSystemContainer {
     system: Box<dyn System<In = (), Out = ()>>,
     pub(crate) run_criteria_index: Option<usize>,
     pub(crate) run_criteria_label: Option<BoxedRunCriteriaLabel>,
+    pub(crate) conditions: Vec<BoxedRunCondition>,
     pub(crate) should_run: bool,
     dependencies: Vec<usize>,
     labels: Vec<BoxedSystemLabel>,
@@ -124,6 +127,7 @@ impl ParallelSystemContainer {
             should_run: false,
             run_criteria_index: None,
             run_criteria_label: None,
+            conditions: descriptor.conditions,
             dependencies: Vec::new(),
             labels: descriptor.labels,
             before: descriptor.before,
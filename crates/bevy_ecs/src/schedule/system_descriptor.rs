@@ -1,7 +1,7 @@
 use crate::{
     schedule::{
-        AmbiguitySetLabel, BoxedAmbiguitySetLabel, BoxedSystemLabel, IntoRunCriteria,
-        RunCriteriaDescriptorOrLabel, SystemLabel,
+        AmbiguitySetLabel, BoxedAmbiguitySetLabel, BoxedRunCondition, BoxedSystemLabel,
+        IntoRunCondition, IntoRunCriteria, RunCriteriaDescriptorOrLabel, SystemLabel,
     },
     system::{
         AsSystemLabel, BoxedSystem, ExclusiveSystem, ExclusiveSystemCoerced, ExclusiveSystemFn,
@@ -98,6 +98,7 @@ impl IntoSystemDescriptor<()> for ExclusiveSystemCoerced {
 pub struct ParallelSystemDescriptor {
     pub(crate) system: BoxedSystem<(), ()>,
     pub(crate) run_criteria: Option<RunCriteriaDescriptorOrLabel>,
+    pub(crate) conditions: Vec<BoxedRunCondition>,
     pub(crate) labels: Vec<BoxedSystemLabel>,
     pub(crate) before: Vec<BoxedSystemLabel>,
     pub(crate) after: Vec<BoxedSystemLabel>,
@@ -109,6 +110,7 @@ fn new_parallel_descriptor(system: BoxedSystem<(), ()>) -> ParallelSystemDescrip
         labels: system.default_labels(),
         system,
         run_criteria: None,
+        conditions: Vec::new(),
         before: Vec::new(),
         after: Vec::new(),
         ambiguity_sets: Vec::new(),
@@ -123,6 +125,15 @@ pub trait ParallelSystemDescriptorCoercion<Params> {
         run_criteria: impl IntoRunCriteria<Marker>,
     ) -> ParallelSystemDescriptor;
 
+    /// Adds a run condition to the system: a read-only system returning whether it should run,
+    /// like [`resource_exists`](crate::schedule::resource_exists). There can be more than one, the
+    /// system only runs if all of them are `true`.
+    ///
+    /// Run conditions are evaluated after the run criteria, and only if they let the system run,
+    /// before any of the parallel systems of the stage run. They can be combined with
+    /// [`IntoRunCondition`].
+    fn run_if<Marker>(self, condition: impl IntoRunCondition<Marker>) -> ParallelSystemDescriptor;
+
     /// Assigns a label to the system; there can be more than one, and it doesn't have to be unique.
     fn label(self, label: impl SystemLabel) -> ParallelSystemDescriptor;
 
@@ -146,6 +157,14 @@ impl ParallelSystemDescriptorCoercion<()> for ParallelSystemDescriptor {
         self
     }
 
+    fn run_if<Marker>(
+        mut self,
+        condition: impl IntoRunCondition<Marker>,
+    ) -> ParallelSystemDescriptor {
+        self.conditions.push(Box::new(condition.into_condition()));
+        self
+    }
+
     fn label(mut self, label: impl SystemLabel) -> ParallelSystemDescriptor {
         self.labels.push(Box::new(label));
         self
@@ -179,6 +198,10 @@ where
             .with_run_criteria(run_criteria)
     }
 
+    fn run_if<Marker>(self, condition: impl IntoRunCondition<Marker>) -> ParallelSystemDescriptor {
+        new_parallel_descriptor(Box::new(IntoSystem::into_system(self))).run_if(condition)
+    }
+
     fn label(self, label: impl SystemLabel) -> ParallelSystemDescriptor {
         new_parallel_descriptor(Box::new(IntoSystem::into_system(self))).label(label)
     }
@@ -204,6 +227,10 @@ impl ParallelSystemDescriptorCoercion<()> for BoxedSystem<(), ()> {
         new_parallel_descriptor(self).with_run_criteria(run_criteria)
     }
 
+    fn run_if<Marker>(self, condition: impl IntoRunCondition<Marker>) -> ParallelSystemDescriptor {
+        new_parallel_descriptor(self).run_if(condition)
+    }
+
     fn label(self, label: impl SystemLabel) -> ParallelSystemDescriptor {
         new_parallel_descriptor(self).label(label)
     }
@@ -232,6 +259,7 @@ pub(crate) enum InsertionPoint {
 pub struct ExclusiveSystemDescriptor {
     pub(crate) system: Box<dyn ExclusiveSystem>,
     pub(crate) run_criteria: Option<RunCriteriaDescriptorOrLabel>,
+    pub(crate) conditions: Vec<BoxedRunCondition>,
     pub(crate) labels: Vec<BoxedSystemLabel>,
     pub(crate) before: Vec<BoxedSystemLabel>,
     pub(crate) after: Vec<BoxedSystemLabel>,
@@ -243,6 +271,7 @@ fn new_exclusive_descriptor(system: Box<dyn ExclusiveSystem>) -> ExclusiveSystem
     ExclusiveSystemDescriptor {
         system,
         run_criteria: None,
+        conditions: Vec::new(),
         labels: Vec::new(),
         before: Vec::new(),
         after: Vec::new(),
@@ -259,6 +288,14 @@ pub trait ExclusiveSystemDescriptorCoercion {
         run_criteria: impl IntoRunCriteria<Marker>,
     ) -> ExclusiveSystemDescriptor;
 
+    /// Adds a run condition to the system: a read-only system returning whether it should run,
+    /// like [`resource_exists`](crate::schedule::resource_exists). There can be more than one, the
+    /// system only runs if all of them are `true`.
+    ///
+    /// Run conditions are evaluated just before the system runs, after the run criteria and only
+    /// if they let the system run. They can be combined with [`IntoRunCondition`].
+    fn run_if<Marker>(self, condition: impl IntoRunCondition<Marker>) -> ExclusiveSystemDescriptor;
+
     /// Assigns a label to the system; there can be more than one, and it doesn't have to be unique.
     fn label(self, label: impl SystemLabel) -> ExclusiveSystemDescriptor;
 
@@ -292,6 +329,14 @@ impl ExclusiveSystemDescriptorCoercion for ExclusiveSystemDescriptor {
         self
     }
 
+    fn run_if<Marker>(
+        mut self,
+        condition: impl IntoRunCondition<Marker>,
+    ) -> ExclusiveSystemDescriptor {
+        self.conditions.push(Box::new(condition.into_condition()));
+        self
+    }
+
     fn label(mut self, label: impl SystemLabel) -> ExclusiveSystemDescriptor {
         self.labels.push(Box::new(label));
         self
@@ -339,6 +384,10 @@ where
         new_exclusive_descriptor(Box::new(self)).with_run_criteria(run_criteria)
     }
 
+    fn run_if<Marker>(self, condition: impl IntoRunCondition<Marker>) -> ExclusiveSystemDescriptor {
+        new_exclusive_descriptor(Box::new(self)).run_if(condition)
+    }
+
     fn label(self, label: impl SystemLabel) -> ExclusiveSystemDescriptor {
         new_exclusive_descriptor(Box::new(self)).label(label)
     }
@@ -1,7 +1,6 @@
 use crate::schedule::{
     AmbiguitySetLabel, BoxedAmbiguitySetLabel, BoxedSystemLabel, IntoRunCriteria,
-    IntoSystemDescriptor, RunCriteriaDescriptorOrLabel, State, StateData, SystemDescriptor,
-    SystemLabel,
+    IntoSystemDescriptor, RunCriteriaDescriptorOrLabel, SystemDescriptor, SystemLabel,
 };
 use crate::system::AsSystemLabel;
 
@@ -21,55 +20,6 @@ impl SystemSet {
         Default::default()
     }
 
-    pub fn on_update<T>(s: T) -> SystemSet
-    where
-        T: StateData,
-    {
-        Self::new().with_run_criteria(State::<T>::on_update(s))
-    }
-
-    pub fn on_inactive_update<T>(s: T) -> SystemSet
-    where
-        T: StateData,
-    {
-        Self::new().with_run_criteria(State::<T>::on_inactive_update(s))
-    }
-
-    pub fn on_in_stack_update<T>(s: T) -> SystemSet
-    where
-        T: StateData,
-    {
-        Self::new().with_run_criteria(State::<T>::on_in_stack_update(s))
-    }
-
-    pub fn on_enter<T>(s: T) -> SystemSet
-    where
-        T: StateData,
-    {
-        Self::new().with_run_criteria(State::<T>::on_enter(s))
-    }
-
-    pub fn on_exit<T>(s: T) -> SystemSet
-    where
-        T: StateData,
-    {
-        Self::new().with_run_criteria(State::<T>::on_exit(s))
-    }
-
-    pub fn on_pause<T>(s: T) -> SystemSet
-    where
-        T: StateData,
-    {
-        Self::new().with_run_criteria(State::<T>::on_pause(s))
-    }
-
-    pub fn on_resume<T>(s: T) -> SystemSet
-    where
-        T: StateData,
-    {
-        Self::new().with_run_criteria(State::<T>::on_resume(s))
-    }
-
     #[must_use]
     pub fn in_ambiguity_set(mut self, set: impl AmbiguitySetLabel) -> Self {
         self.ambiguity_sets.push(Box::new(set));
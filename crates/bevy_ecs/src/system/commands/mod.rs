@@ -12,7 +12,7 @@ pub use command_queue::CommandQueue;
 pub use parallel_scope::*;
 use std::marker::PhantomData;
 
-use super::Resource;
+use super::{Resource, RunSystem, SystemId};
 
 /// A [`World`] mutation.
 ///
@@ -383,6 +383,14 @@ impl<'w, 's> Commands<'w, 's> {
         });
     }
 
+    /// Runs a system registered with [`World::register_system`], then applies its commands.
+    ///
+    /// Note that commands do not take effect immediately: the system runs when this command
+    /// is applied. A warning is logged if the system is not registered.
+    pub fn run_system(&mut self, id: SystemId) {
+        self.queue.push(RunSystem { id });
+    }
+
     /// Adds a command directly to the command queue.
     ///
     /// `command` can be a built-in command, custom struct that implements [`Command`] or a closure
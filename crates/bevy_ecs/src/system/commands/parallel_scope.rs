@@ -82,6 +82,10 @@ unsafe impl SystemParamState for ParallelCommandsState {
 }
 
 impl<'w, 's> ParallelCommands<'w, 's> {
+    /// Runs `f` with [`Commands`] recording into the command queue of the current thread.
+    ///
+    /// The queues of all threads are applied together when the system's buffers are applied,
+    /// like those of [`Commands`].
     pub fn command_scope<R>(&self, f: impl FnOnce(Commands) -> R) -> R {
         let store = &self.state.thread_local_storage;
         let command_queue_cell = store.get_or_default();
@@ -96,3 +100,68 @@ impl<'w, 's> ParallelCommands<'w, 's> {
         r
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        entity::Entity,
+        system::{IntoSystem, ParallelCommands, Query, System},
+        world::World,
+    };
+    use bevy_tasks::{ComputeTaskPool, TaskPool};
+
+    #[derive(Component)]
+    struct A(u32);
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Component)]
+    struct Spawned(u32);
+
+    #[test]
+    fn parallel_commands() {
+        ComputeTaskPool::init(TaskPool::default);
+
+        fn parallel_system(query: Query<(Entity, &A)>, par_commands: ParallelCommands) {
+            query.par_for_each(2, |(entity, a)| {
+                par_commands.command_scope(|mut commands| {
+                    if a.0 % 2 == 0 {
+                        commands.entity(entity).insert(B);
+                        commands.spawn().insert(Spawned(a.0));
+                    } else {
+                        commands.entity(entity).despawn();
+                    }
+                });
+            });
+        }
+
+        let mut world = World::new();
+        for i in 0..10 {
+            world.spawn().insert(A(i));
+        }
+        let mut system = IntoSystem::into_system(parallel_system);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+        assert_eq!(world.entities().len(), 10);
+        system.apply_buffers(&mut world);
+
+        let mut with_b = world
+            .query::<(&A, &B)>()
+            .iter(&world)
+            .map(|(a, _)| a.0)
+            .collect::<Vec<_>>();
+        with_b.sort_unstable();
+        assert_eq!(with_b, vec![0, 2, 4, 6, 8]);
+        let mut spawned = world
+            .query::<&Spawned>()
+            .iter(&world)
+            .map(|spawned| spawned.0)
+            .collect::<Vec<_>>();
+        spawned.sort_unstable();
+        assert_eq!(spawned, vec![0, 2, 4, 6, 8]);
+        assert_eq!(world.entities().len(), 10);
+    }
+}
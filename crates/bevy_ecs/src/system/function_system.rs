@@ -6,8 +6,8 @@ use crate::{
     query::{Access, FilteredAccessSet},
     schedule::SystemLabel,
     system::{
-        check_system_change_tick, ReadOnlySystemParamFetch, System, SystemParam, SystemParamFetch,
-        SystemParamItem, SystemParamState,
+        check_system_change_tick, ReadOnlySystem, ReadOnlySystemParamFetch, System, SystemParam,
+        SystemParamFetch, SystemParamItem, SystemParamState,
     },
     world::{World, WorldId},
 };
@@ -487,6 +487,18 @@ impl<T> SystemLabel for SystemTypeIdLabel<T> {
     }
 }
 
+// SAFETY: The parameters of the function only read from the world
+unsafe impl<In, Out, Param, Marker, F> ReadOnlySystem for FunctionSystem<In, Out, Param, Marker, F>
+where
+    In: 'static,
+    Out: 'static,
+    Param: SystemParam + 'static,
+    Param::Fetch: ReadOnlySystemParamFetch,
+    Marker: 'static,
+    F: SystemParamFunction<In, Out, Param, Marker> + Send + Sync + 'static,
+{
+}
+
 /// A trait implemented for all functions that can be used as [`System`]s.
 ///
 /// This trait can be useful for making your own systems which accept other systems,
@@ -61,6 +61,15 @@ pub trait System: Send + Sync + 'static {
     }
 }
 
+/// A [`System`] that doesn't mutate the [`World`] when it runs, such as a
+/// [`FunctionSystem`](crate::system::FunctionSystem) with only read-only parameters.
+///
+/// # Safety
+///
+/// This must only be implemented for systems that only read from the world in
+/// [`System::run_unsafe`].
+pub unsafe trait ReadOnlySystem: System {}
+
 /// A convenience type alias for a boxed [`System`] trait object.
 pub type BoxedSystem<In = (), Out = ()> = Box<dyn System<In = In, Out = Out>>;
 
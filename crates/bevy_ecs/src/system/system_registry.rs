@@ -0,0 +1,231 @@
+use crate::{
+    system::{BoxedSystem, Command, IntoSystem},
+    world::World,
+};
+use bevy_utils::{tracing::warn, HashMap};
+use std::fmt;
+
+/// The id of a system registered with [`World::register_system`], used to run it on demand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SystemId(u64);
+
+struct RegisteredSystem {
+    initialized: bool,
+    /// `None` while the system is running
+    system: Option<BoxedSystem>,
+}
+
+/// The systems registered in a [`World`] with [`World::register_system`].
+///
+/// Systems keep their state, like [`Local`](crate::system::Local)s and change ticks, between
+/// runs.
+#[derive(Default)]
+pub struct SystemRegistry {
+    systems: HashMap<SystemId, RegisteredSystem>,
+    next_id: u64,
+}
+
+impl SystemRegistry {
+    /// Returns `true` if the system is registered.
+    pub fn contains(&self, id: SystemId) -> bool {
+        self.systems.contains_key(&id)
+    }
+
+    /// Returns the number of registered systems.
+    pub fn len(&self) -> usize {
+        self.systems.len()
+    }
+
+    /// Returns `true` if no system is registered.
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+}
+
+/// An error that occurs when running a system registered with [`World::register_system`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RegisteredSystemError {
+    /// The system is not registered, or has been removed.
+    SystemIdNotRegistered(SystemId),
+    /// The system tried to run itself, or was removed while running.
+    Recursive(SystemId),
+}
+
+impl std::error::Error for RegisteredSystemError {}
+
+impl fmt::Display for RegisteredSystemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegisteredSystemError::SystemIdNotRegistered(id) => {
+                write!(f, "The system {:?} is not registered.", id)
+            }
+            RegisteredSystemError::Recursive(id) => {
+                write!(f, "The system {:?} tried to run itself.", id)
+            }
+        }
+    }
+}
+
+impl World {
+    /// Registers a system, returning its [`SystemId`] to run it later with
+    /// [`World::run_system`] or [`Commands::run_system`](crate::system::Commands::run_system).
+    ///
+    /// The system is initialized the first time it runs, and keeps its state between runs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Default)]
+    /// struct Counter(u32);
+    ///
+    /// fn increment(mut counter: ResMut<Counter>) {
+    ///     counter.0 += 1;
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<Counter>();
+    /// let id = world.register_system(increment);
+    /// world.run_system(id).unwrap();
+    /// world.run_system(id).unwrap();
+    /// assert_eq!(world.resource::<Counter>().0, 2);
+    /// ```
+    pub fn register_system<Params>(&mut self, system: impl IntoSystem<(), (), Params>) -> SystemId {
+        self.register_boxed_system(Box::new(IntoSystem::into_system(system)))
+    }
+
+    /// Registers an already boxed system, see [`World::register_system`].
+    pub fn register_boxed_system(&mut self, system: BoxedSystem) -> SystemId {
+        let mut registry = self.get_resource_or_insert_with(SystemRegistry::default);
+        let id = SystemId(registry.next_id);
+        registry.next_id += 1;
+        registry.systems.insert(
+            id,
+            RegisteredSystem {
+                initialized: false,
+                system: Some(system),
+            },
+        );
+        id
+    }
+
+    /// Removes a registered system, returning it if it was registered and not running.
+    pub fn remove_system(&mut self, id: SystemId) -> Result<BoxedSystem, RegisteredSystemError> {
+        let registered_system = self
+            .get_resource_mut::<SystemRegistry>()
+            .and_then(|mut registry| registry.systems.remove(&id))
+            .ok_or(RegisteredSystemError::SystemIdNotRegistered(id))?;
+        registered_system
+            .system
+            .ok_or(RegisteredSystemError::Recursive(id))
+    }
+
+    /// Runs a system registered with [`World::register_system`], then applies its commands.
+    ///
+    /// A system can't run itself, but can run other registered systems.
+    pub fn run_system(&mut self, id: SystemId) -> Result<(), RegisteredSystemError> {
+        let (mut system, initialized) = {
+            let mut registry = self
+                .get_resource_mut::<SystemRegistry>()
+                .ok_or(RegisteredSystemError::SystemIdNotRegistered(id))?;
+            let registered_system = registry
+                .systems
+                .get_mut(&id)
+                .ok_or(RegisteredSystemError::SystemIdNotRegistered(id))?;
+            let system = registered_system
+                .system
+                .take()
+                .ok_or(RegisteredSystemError::Recursive(id))?;
+            (system, registered_system.initialized)
+        };
+
+        if !initialized {
+            system.initialize(self);
+        }
+        system.run((), self);
+        system.apply_buffers(self);
+
+        // The system may have been removed while it was running
+        if let Some(registered_system) = self
+            .get_resource_mut::<SystemRegistry>()
+            .and_then(|registry| registry.into_inner().systems.get_mut(&id))
+        {
+            registered_system.initialized = true;
+            registered_system.system = Some(system);
+        }
+        Ok(())
+    }
+}
+
+/// A [`Command`] running a system registered with [`World::register_system`].
+///
+/// See [`Commands::run_system`](crate::system::Commands::run_system).
+#[derive(Debug, Clone, Copy)]
+pub struct RunSystem {
+    pub id: SystemId,
+}
+
+impl Command for RunSystem {
+    fn write(self, world: &mut World) {
+        if let Err(error) = world.run_system(self.id) {
+            warn!("Could not run the registered system: {}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        system::{Commands, Local, Res, ResMut},
+        world::World,
+    };
+
+    use super::{RegisteredSystemError, SystemId};
+
+    #[derive(Default)]
+    struct Counter(u32);
+
+    #[test]
+    fn run_system_keeps_state() {
+        fn count_runs(mut runs: Local<u32>, mut counter: ResMut<Counter>) {
+            *runs += 1;
+            counter.0 = *runs;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let id = world.register_system(count_runs);
+        world.run_system(id).unwrap();
+        world.run_system(id).unwrap();
+        world.run_system(id).unwrap();
+        assert_eq!(world.resource::<Counter>().0, 3);
+
+        world.remove_system(id).unwrap();
+        assert_eq!(
+            world.run_system(id),
+            Err(RegisteredSystemError::SystemIdNotRegistered(id))
+        );
+    }
+
+    #[test]
+    fn run_system_from_commands() {
+        struct Callback(SystemId);
+
+        fn increment(mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        fn run_callback(mut commands: Commands, callback: Res<Callback>) {
+            commands.run_system(callback.0);
+            commands.run_system(callback.0);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let callback = world.register_system(increment);
+        world.insert_resource(Callback(callback));
+        let id = world.register_system(run_callback);
+        world.run_system(id).unwrap();
+        assert_eq!(world.resource::<Counter>().0, 2);
+    }
+}
@@ -253,6 +253,40 @@ impl<'w> EntityMut<'w> {
         self
     }
 
+    /// Inserts the component of the given [`ComponentId`] into the entity, replacing its
+    /// previous value if any.
+    ///
+    /// **You should prefer to use the typed API [`EntityMut::insert`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    ///
+    /// # Safety
+    /// - `component_id` must be valid in this entity's [`World`]
+    /// - `component` must point to a value of the layout of this component
+    pub unsafe fn insert_by_id(
+        &mut self,
+        component_id: ComponentId,
+        component: OwningPtr<'_>,
+    ) -> &mut Self {
+        let change_tick = self.world.change_tick();
+        let bundle_info = self
+            .world
+            .bundles
+            .init_dynamic_info(&mut self.world.components, component_id);
+        let mut bundle_inserter = bundle_info.get_bundle_inserter(
+            &mut self.world.entities,
+            &mut self.world.archetypes,
+            &mut self.world.components,
+            &mut self.world.storages,
+            self.location.archetype_id,
+            change_tick,
+        );
+        // SAFETY: location matches current entity. `component` matches `bundle_info`
+        self.location = bundle_inserter.insert(self.entity, self.location.index, component);
+
+        self
+    }
+
     // TODO: move to BundleInfo
     pub fn remove_bundle<T: Bundle>(&mut self) -> Option<T> {
         let archetypes = &mut self.world.archetypes;
@@ -583,7 +617,7 @@ pub(crate) unsafe fn get_component(
 /// # Safety
 /// Caller must ensure that `component_id` is valid
 #[inline]
-unsafe fn get_component_and_ticks(
+pub(crate) unsafe fn get_component_and_ticks(
     world: &World,
     component_id: ComponentId,
     entity: Entity,
@@ -2,7 +2,7 @@ mod entity_ref;
 mod spawn_batch;
 mod world_cell;
 
-pub use crate::change_detection::Mut;
+pub use crate::change_detection::{Mut, Ref};
 pub use entity_ref::*;
 pub use spawn_batch::*;
 pub use world_cell::*;
@@ -23,7 +23,9 @@ use crate::{
 use bevy_ptr::{OwningPtr, Ptr, UnsafeCellDeref};
 use bevy_utils::tracing::debug;
 use std::{
+    alloc::Layout,
     any::TypeId,
+    borrow::Cow,
     fmt,
     sync::atomic::{AtomicU32, Ordering},
 };
@@ -200,6 +202,32 @@ impl World {
             .init_component_with_descriptor(&mut self.storages, descriptor)
     }
 
+    /// Initializes a new component from its memory layout and returns the [`ComponentId`]
+    /// created for it.
+    ///
+    /// This is meant for components that are not Rust types, like the components defined by a
+    /// scripting language. They are accessed through their [`ComponentId`], with
+    /// [`EntityMut::insert_by_id`], [`EntityRef::get_by_id`] or a
+    /// [`DynamicQueryState`](crate::query::DynamicQueryState).
+    ///
+    /// # Safety
+    /// - the `drop` fn must be usable on a pointer with a value of the layout `layout`
+    /// - the component values must be safe to access from any thread (Send + Sync in rust terms)
+    pub unsafe fn init_component_with_layout(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        storage_type: StorageType,
+        layout: Layout,
+        drop: Option<for<'a> unsafe fn(OwningPtr<'a>)>,
+    ) -> ComponentId {
+        self.init_component_with_descriptor(ComponentDescriptor::new_with_layout(
+            name,
+            storage_type,
+            layout,
+            drop,
+        ))
+    }
+
     /// Returns the [`ComponentId`] of the given [`Component`] type `T`.
     ///
     /// The returned `ComponentId` is specific to the `World` instance
@@ -1,5 +1,6 @@
 mod converter;
 mod gilrs_system;
+mod rumble;
 
 use bevy_app::{App, CoreStage, Plugin, StartupStage};
 use bevy_ecs::schedule::ParallelSystemDescriptorCoercion;
@@ -7,6 +8,7 @@ use bevy_input::InputSystem;
 use bevy_utils::tracing::error;
 use gilrs::GilrsBuilder;
 use gilrs_system::{gilrs_event_startup_system, gilrs_event_system};
+use rumble::{play_gilrs_rumble, RunningRumbleEffects};
 
 #[derive(Default)]
 pub struct GilrsPlugin;
@@ -20,6 +22,7 @@ impl Plugin for GilrsPlugin {
         {
             Ok(gilrs) => {
                 app.insert_non_send_resource(gilrs)
+                    .init_non_send_resource::<RunningRumbleEffects>()
                     .add_startup_system_to_stage(
                         StartupStage::PreStartup,
                         gilrs_event_startup_system,
@@ -27,7 +30,8 @@ impl Plugin for GilrsPlugin {
                     .add_system_to_stage(
                         CoreStage::PreUpdate,
                         gilrs_event_system.before(InputSystem),
-                    );
+                    )
+                    .add_system_to_stage(CoreStage::PostUpdate, play_gilrs_rumble);
             }
             Err(err) => error!("Failed to start Gilrs. {}", err),
         }
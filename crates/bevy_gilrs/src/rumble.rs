@@ -0,0 +1,155 @@
+use crate::converter::convert_gamepad_id;
+use bevy_ecs::{event::EventReader, system::NonSendMut};
+use bevy_input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy_utils::{
+    tracing::{debug, warn},
+    Duration, HashMap, Instant,
+};
+use gilrs::{
+    ff::{self, BaseEffect, BaseEffectType, Repeat, Replay},
+    GamepadId, Gilrs,
+};
+
+/// A rumble effect that is currently playing.
+struct RunningRumble {
+    /// The time at which this effect should be stopped.
+    deadline: Instant,
+    /// The `gilrs` effect, which stops playing when dropped.
+    #[allow(dead_code)]
+    effect: ff::Effect,
+}
+
+/// The rumble effects playing on each gamepad, stored as a non-send resource.
+#[derive(Default)]
+pub(crate) struct RunningRumbleEffects {
+    rumbles: HashMap<GamepadId, Vec<RunningRumble>>,
+}
+
+fn to_gilrs_magnitude(ratio: f32) -> u16 {
+    (ratio.clamp(0.0, 1.0) * u16::MAX as f32) as u16
+}
+
+fn get_base_effects(
+    GamepadRumbleIntensity {
+        weak_motor,
+        strong_motor,
+    }: GamepadRumbleIntensity,
+    duration: Duration,
+) -> Vec<BaseEffect> {
+    let mut effects = Vec::new();
+    let scheduling = Replay {
+        play_for: duration.into(),
+        ..Default::default()
+    };
+    if strong_motor > 0.0 {
+        effects.push(BaseEffect {
+            kind: BaseEffectType::Strong {
+                magnitude: to_gilrs_magnitude(strong_motor),
+            },
+            scheduling,
+            ..Default::default()
+        });
+    }
+    if weak_motor > 0.0 {
+        effects.push(BaseEffect {
+            kind: BaseEffectType::Weak {
+                magnitude: to_gilrs_magnitude(weak_motor),
+            },
+            scheduling,
+            ..Default::default()
+        });
+    }
+    effects
+}
+
+fn handle_rumble_request(
+    running_rumbles: &mut RunningRumbleEffects,
+    gilrs: &mut Gilrs,
+    rumble: &GamepadRumbleRequest,
+    now: Instant,
+) -> Result<(), ff::Error> {
+    let gamepad = rumble.gamepad();
+    let gamepad_id = match gilrs
+        .gamepads()
+        .find(|(id, _)| convert_gamepad_id(*id) == gamepad)
+    {
+        Some((id, _)) => id,
+        None => {
+            debug!("Tried to rumble {:?}, but it doesn't exist", gamepad);
+            return Ok(());
+        }
+    };
+
+    match *rumble {
+        GamepadRumbleRequest::Stop { .. } => {
+            // `ff::Effect` stops playing when dropped
+            running_rumbles.rumbles.remove(&gamepad_id);
+        }
+        GamepadRumbleRequest::Add {
+            duration,
+            intensity,
+            ..
+        } => {
+            let mut effect_builder = ff::EffectBuilder::new();
+            for effect in get_base_effects(intensity, duration) {
+                effect_builder.add_effect(effect);
+            }
+            let effect = effect_builder
+                .gamepads(&[gamepad_id])
+                .repeat(Repeat::For(duration.into()))
+                .finish(gilrs)?;
+            effect.play()?;
+
+            running_rumbles
+                .rumbles
+                .entry(gamepad_id)
+                .or_default()
+                .push(RunningRumble {
+                    deadline: now + duration,
+                    effect,
+                });
+        }
+    }
+
+    Ok(())
+}
+
+/// Plays the [`GamepadRumbleRequest`]s with `gilrs`, and stops the rumbles that are done or
+/// whose gamepad disconnected.
+pub(crate) fn play_gilrs_rumble(
+    mut gilrs: NonSendMut<Gilrs>,
+    mut requests: EventReader<GamepadRumbleRequest>,
+    mut running_rumbles: NonSendMut<RunningRumbleEffects>,
+) {
+    let now = Instant::now();
+
+    // Remove the finished rumbles, and the ones of the disconnected gamepads
+    let gilrs = &mut *gilrs;
+    running_rumbles.rumbles.retain(|gamepad_id, rumbles| {
+        rumbles.retain(|rumble| rumble.deadline > now);
+        !rumbles.is_empty() && gilrs.connected_gamepad(*gamepad_id).is_some()
+    });
+
+    for rumble in requests.iter() {
+        match handle_rumble_request(&mut running_rumbles, gilrs, rumble, now) {
+            Ok(()) => {}
+            Err(ff::Error::FfNotSupported(_)) => {
+                debug!(
+                    "Tried to rumble {:?}, but it doesn't support force feedback",
+                    rumble.gamepad()
+                );
+            }
+            Err(ff::Error::Disconnected(_)) => {
+                debug!(
+                    "Tried to rumble {:?}, but it is disconnected",
+                    rumble.gamepad()
+                );
+            }
+            Err(err) => warn!(
+                "Tried to rumble {:?}, but an error occurred: {}",
+                rumble.gamepad(),
+                err
+            ),
+        }
+    }
+}
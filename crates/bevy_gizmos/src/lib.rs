@@ -0,0 +1,275 @@
+//! Immediate mode drawing of debug shapes, like the bounds of physics volumes or the paths of
+//! agents.
+//!
+//! Shapes drawn with the [`Gizmos`] system parameter are rendered as lines by all the 2D and 3D
+//! cameras for the current frame only, and must be drawn again every frame to stay visible.
+
+mod render;
+
+pub use render::*;
+
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{GizmoConfig, Gizmos};
+}
+
+use std::{f32::consts::TAU, marker::PhantomData};
+
+use bevy_app::prelude::*;
+use bevy_ecs::system::{ResMut, SystemParam};
+use bevy_math::{Quat, Vec2, Vec3};
+use bevy_render::color::Color;
+use bevy_transform::components::Transform;
+
+/// The number of line segments used to draw circles and spheres
+const CIRCLE_SEGMENTS: usize = 32;
+
+/// Adds drawing of the shapes of [`Gizmos`]
+#[derive(Default)]
+pub struct GizmoPlugin;
+
+impl Plugin for GizmoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GizmoConfig>()
+            .init_resource::<GizmoStorage>()
+            .add_system_to_stage(CoreStage::First, clear_gizmos);
+
+        build_gizmo_render(app);
+    }
+}
+
+/// The configuration of the rendering of [`Gizmos`].
+#[derive(Debug, Clone)]
+pub struct GizmoConfig {
+    /// Whether the gizmos are rendered
+    pub enabled: bool,
+    /// Whether the gizmos are rendered on top of the meshes of the 3D cameras, rather than being
+    /// hidden behind them
+    pub on_top: bool,
+}
+
+impl Default for GizmoConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            on_top: false,
+        }
+    }
+}
+
+/// The lines drawn with [`Gizmos`] this frame, as pairs of vertices.
+#[derive(Debug, Default)]
+pub struct GizmoStorage {
+    positions: Vec<[f32; 3]>,
+    colors: Vec<[f32; 4]>,
+}
+
+impl GizmoStorage {
+    /// The number of lines drawn this frame.
+    pub fn len(&self) -> usize {
+        self.positions.len() / 2
+    }
+
+    /// Returns `true` if no lines have been drawn this frame.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    fn add_line(&mut self, start: Vec3, end: Vec3, start_color: Color, end_color: Color) {
+        self.positions.push(start.to_array());
+        self.positions.push(end.to_array());
+        self.colors.push(start_color.as_linear_rgba_f32());
+        self.colors.push(end_color.as_linear_rgba_f32());
+    }
+}
+
+/// Clears the lines drawn the last frame.
+pub fn clear_gizmos(mut storage: ResMut<GizmoStorage>) {
+    storage.positions.clear();
+    storage.colors.clear();
+}
+
+/// A [`SystemParam`] drawing debug shapes for the current frame.
+///
+/// The 2D shapes are drawn in the XY plane, at a `z` of 0.
+///
+/// ```
+/// # use bevy_gizmos::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_render::prelude::*;
+/// fn draw_bounds(mut gizmos: Gizmos) {
+///     gizmos.sphere(Vec3::ZERO, Quat::IDENTITY, 2.0, Color::RED);
+///     gizmos.ray(Vec3::ZERO, Vec3::X, Color::GREEN);
+///     gizmos.rect_2d(Vec2::ZERO, 0.0, Vec2::splat(100.0), Color::BLUE);
+/// }
+/// # bevy_ecs::system::assert_is_system(draw_bounds);
+/// ```
+#[derive(SystemParam)]
+pub struct Gizmos<'w, 's> {
+    storage: ResMut<'w, GizmoStorage>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+impl<'w, 's> Gizmos<'w, 's> {
+    /// Draws a line from `start` to `end`.
+    pub fn line(&mut self, start: Vec3, end: Vec3, color: Color) {
+        self.storage.add_line(start, end, color, color);
+    }
+
+    /// Draws a line from `start` to `end`, its color blending from `start_color` to `end_color`.
+    pub fn line_gradient(&mut self, start: Vec3, end: Vec3, start_color: Color, end_color: Color) {
+        self.storage.add_line(start, end, start_color, end_color);
+    }
+
+    /// Draws the lines joining the consecutive positions.
+    pub fn linestrip(&mut self, positions: impl IntoIterator<Item = Vec3>, color: Color) {
+        let mut positions = positions.into_iter();
+        if let Some(mut start) = positions.next() {
+            for end in positions {
+                self.line(start, end, color);
+                start = end;
+            }
+        }
+    }
+
+    /// Draws a line from `start` along `vector`, ending at `start + vector`.
+    pub fn ray(&mut self, start: Vec3, vector: Vec3, color: Color) {
+        self.line(start, start + vector, color);
+    }
+
+    /// Draws a circle facing `normal`.
+    pub fn circle(&mut self, position: Vec3, normal: Vec3, radius: f32, color: Color) {
+        let rotation = Quat::from_rotation_arc(Vec3::Z, normal.normalize());
+        self.linestrip(
+            circle_points(radius).map(|point| position + rotation * point.extend(0.0)),
+            color,
+        );
+    }
+
+    /// Draws a wire sphere, as a circle around each of its rotated axes.
+    pub fn sphere(&mut self, position: Vec3, rotation: Quat, radius: f32, color: Color) {
+        for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+            self.circle(position, rotation * axis, radius, color);
+        }
+    }
+
+    /// Draws a rectangle of the given size, in the XY plane of its rotation.
+    pub fn rect(&mut self, position: Vec3, rotation: Quat, size: Vec2, color: Color) {
+        let [a, b, c, d] =
+            rect_corners(size).map(|corner| position + rotation * corner.extend(0.0));
+        self.linestrip([a, b, c, d, a], color);
+    }
+
+    /// Draws a wire box, as a cube of size 1 transformed by the transform.
+    pub fn cuboid(&mut self, transform: Transform, color: Color) {
+        let [a, b, c, d] = rect_corners(Vec2::ONE);
+        let bottom = [a, b, c, d].map(|corner| transform * corner.extend(-0.5));
+        let top = [a, b, c, d].map(|corner| transform * corner.extend(0.5));
+        for i in 0..4 {
+            let next = (i + 1) % 4;
+            self.line(bottom[i], bottom[next], color);
+            self.line(top[i], top[next], color);
+            self.line(bottom[i], top[i], color);
+        }
+    }
+
+    /// Draws a 2D line from `start` to `end`.
+    pub fn line_2d(&mut self, start: Vec2, end: Vec2, color: Color) {
+        self.line(start.extend(0.0), end.extend(0.0), color);
+    }
+
+    /// Draws the 2D lines joining the consecutive positions.
+    pub fn linestrip_2d(&mut self, positions: impl IntoIterator<Item = Vec2>, color: Color) {
+        self.linestrip(
+            positions.into_iter().map(|position| position.extend(0.0)),
+            color,
+        );
+    }
+
+    /// Draws a 2D line from `start` along `vector`, ending at `start + vector`.
+    pub fn ray_2d(&mut self, start: Vec2, vector: Vec2, color: Color) {
+        self.line_2d(start, start + vector, color);
+    }
+
+    /// Draws a 2D circle.
+    pub fn circle_2d(&mut self, position: Vec2, radius: f32, color: Color) {
+        self.linestrip_2d(circle_points(radius).map(|point| position + point), color);
+    }
+
+    /// Draws a 2D rectangle of the given size, rotated by `rotation` radians.
+    pub fn rect_2d(&mut self, position: Vec2, rotation: f32, size: Vec2, color: Color) {
+        let rotation = Vec2::from_angle(rotation);
+        let [a, b, c, d] = rect_corners(size).map(|corner| position + rotation.rotate(corner));
+        self.linestrip_2d([a, b, c, d, a], color);
+    }
+}
+
+/// The points around a circle centered on the origin, from and back to its first point.
+fn circle_points(radius: f32) -> impl Iterator<Item = Vec2> {
+    (0..=CIRCLE_SEGMENTS).map(move |i| {
+        let angle = i as f32 * TAU / CIRCLE_SEGMENTS as f32;
+        Vec2::new(angle.cos(), angle.sin()) * radius
+    })
+}
+
+/// The corners of a rectangle centered on the origin, counter clockwise from the bottom left.
+fn rect_corners(size: Vec2) -> [Vec2; 4] {
+    let half_size = size / 2.0;
+    [
+        Vec2::new(-half_size.x, -half_size.y),
+        Vec2::new(half_size.x, -half_size.y),
+        Vec2::new(half_size.x, half_size.y),
+        Vec2::new(-half_size.x, half_size.y),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        schedule::{Schedule, Stage, SystemStage},
+        world::World,
+    };
+    use bevy_math::{Quat, Vec2, Vec3};
+    use bevy_render::color::Color;
+    use bevy_transform::components::Transform;
+
+    use super::{clear_gizmos, GizmoStorage, Gizmos, CIRCLE_SEGMENTS};
+
+    fn draw(mut gizmos: Gizmos) {
+        gizmos.line(Vec3::ZERO, Vec3::X, Color::RED);
+        gizmos.rect_2d(Vec2::ZERO, 0.0, Vec2::ONE, Color::GREEN);
+        gizmos.cuboid(Transform::default(), Color::BLUE);
+        gizmos.sphere(Vec3::ZERO, Quat::IDENTITY, 1.0, Color::WHITE);
+    }
+
+    #[test]
+    fn gizmos_are_drawn_for_one_frame() {
+        let mut world = World::default();
+        world.init_resource::<GizmoStorage>();
+
+        let mut schedule = Schedule::default();
+        let mut first_stage = SystemStage::parallel();
+        first_stage.add_system(clear_gizmos);
+        schedule.add_stage("first", first_stage);
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(draw);
+        schedule.add_stage("update", update_stage);
+
+        schedule.run(&mut world);
+        let storage = world.resource::<GizmoStorage>();
+        assert_eq!(storage.len(), 1 + 4 + 12 + 3 * CIRCLE_SEGMENTS);
+        assert_eq!(storage.positions[0], [0.0, 0.0, 0.0]);
+        assert_eq!(storage.positions[1], [1.0, 0.0, 0.0]);
+
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<GizmoStorage>().len(),
+            1 + 4 + 12 + 3 * CIRCLE_SEGMENTS
+        );
+
+        let mut clear_stage = SystemStage::single(clear_gizmos);
+        clear_stage.run(&mut world);
+        assert!(world.resource::<GizmoStorage>().is_empty());
+    }
+}
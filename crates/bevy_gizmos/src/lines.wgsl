@@ -0,0 +1,26 @@
+struct View {
+    view_proj: mat4x4<f32>;
+};
+[[group(0), binding(0)]]
+var<uniform> view: View;
+
+struct VertexOutput {
+    [[location(0)]] color: vec4<f32>;
+    [[builtin(position)]] position: vec4<f32>;
+};
+
+[[stage(vertex)]]
+fn vertex(
+    [[location(0)]] vertex_position: vec3<f32>,
+    [[location(1)]] vertex_color: vec4<f32>,
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
+    out.color = vertex_color;
+    return out;
+}
+
+[[stage(fragment)]]
+fn fragment(in: VertexOutput) -> [[location(0)]] vec4<f32> {
+    return in.color;
+}
@@ -0,0 +1,344 @@
+use crate::{GizmoConfig, GizmoStorage};
+use bevy_app::App;
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_core_pipeline::{
+    core_2d::Transparent2d,
+    core_3d::{Camera3d, Transparent3d, ViewHdrTexture},
+};
+use bevy_ecs::{
+    prelude::*,
+    system::{lifetimeless::*, SystemParamItem},
+};
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    render_phase::{
+        AddRenderCommand, DrawFunctions, EntityRenderCommand, RenderCommandResult, RenderPhase,
+        SetItemPipeline, TrackedRenderPass,
+    },
+    render_resource::*,
+    renderer::{RenderDevice, RenderQueue},
+    texture::BevyDefault,
+    view::{Msaa, ViewUniform, ViewUniformOffset, ViewUniforms},
+    Extract, RenderApp, RenderStage,
+};
+use bevy_utils::FloatOrd;
+use bytemuck::{Pod, Zeroable};
+
+pub const LINES_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 7414812689238026784);
+
+pub fn build_gizmo_render(app: &mut App) {
+    load_internal_asset!(app, LINES_SHADER_HANDLE, "lines.wgsl", Shader::from_wgsl);
+
+    let render_app = match app.get_sub_app_mut(RenderApp) {
+        Ok(render_app) => render_app,
+        Err(_) => return,
+    };
+
+    render_app
+        .init_resource::<GizmoPipeline>()
+        .init_resource::<SpecializedRenderPipelines<GizmoPipeline>>()
+        .init_resource::<GizmoMeta>()
+        .init_resource::<ExtractedGizmos>()
+        .add_render_command::<Transparent2d, DrawGizmos>()
+        .add_render_command::<Transparent3d, DrawGizmos>()
+        .add_system_to_stage(RenderStage::Extract, extract_gizmos)
+        .add_system_to_stage(RenderStage::Prepare, prepare_gizmos)
+        .add_system_to_stage(RenderStage::Queue, queue_gizmos);
+}
+
+pub struct GizmoPipeline {
+    pub view_layout: BindGroupLayout,
+}
+
+impl FromWorld for GizmoPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let view_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::VERTEX,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(ViewUniform::min_size()),
+                },
+                count: None,
+            }],
+            label: Some("gizmo_view_layout"),
+        });
+
+        GizmoPipeline { view_layout }
+    }
+}
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct GizmoPipelineKey {
+    pub msaa_samples: u32,
+    /// Whether the lines are drawn in the main pass of a 3D camera, which has a depth buffer
+    pub depth: bool,
+    /// Whether the lines are drawn over the depth buffer rather than tested against it
+    pub on_top: bool,
+    /// Whether the lines are drawn in the [`ViewHdrTexture`] of a 3D camera
+    pub hdr: bool,
+}
+
+impl SpecializedRenderPipeline for GizmoPipeline {
+    type Key = GizmoPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let vertex_layout = VertexBufferLayout::from_vertex_formats(
+            VertexStepMode::Vertex,
+            vec![
+                // position
+                VertexFormat::Float32x3,
+                // color
+                VertexFormat::Float32x4,
+            ],
+        );
+        let depth_stencil = key.depth.then(|| DepthStencilState {
+            format: TextureFormat::Depth32Float,
+            depth_write_enabled: false,
+            depth_compare: if key.on_top {
+                CompareFunction::Always
+            } else {
+                // NOTE: Bevy uses reverse-z, nearer fragments have greater depths
+                CompareFunction::GreaterEqual
+            },
+            stencil: StencilState {
+                front: StencilFaceState::IGNORE,
+                back: StencilFaceState::IGNORE,
+                read_mask: 0,
+                write_mask: 0,
+            },
+            bias: DepthBiasState {
+                constant: 0,
+                slope_scale: 0.0,
+                clamp: 0.0,
+            },
+        });
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: LINES_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: Vec::new(),
+                buffers: vec![vertex_layout],
+            },
+            fragment: Some(FragmentState {
+                shader: LINES_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![ColorTargetState {
+                    format: if key.hdr {
+                        ViewHdrTexture::FORMAT
+                    } else {
+                        TextureFormat::bevy_default()
+                    },
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                }],
+            }),
+            layout: Some(vec![self.view_layout.clone()]),
+            primitive: PrimitiveState {
+                front_face: FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: PolygonMode::Fill,
+                conservative: false,
+                topology: PrimitiveTopology::LineList,
+                strip_index_format: None,
+            },
+            depth_stencil,
+            multisample: MultisampleState {
+                count: key.msaa_samples,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("gizmo_pipeline".into()),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct GizmoVertex {
+    pub position: [f32; 3],
+    pub color: [f32; 4],
+}
+
+/// The lines of the [`Gizmos`](crate::Gizmos) extracted for this frame.
+#[derive(Default)]
+pub struct ExtractedGizmos {
+    positions: Vec<[f32; 3]>,
+    colors: Vec<[f32; 4]>,
+    on_top: bool,
+}
+
+pub fn extract_gizmos(
+    mut extracted_gizmos: ResMut<ExtractedGizmos>,
+    storage: Extract<Res<GizmoStorage>>,
+    config: Extract<Res<GizmoConfig>>,
+) {
+    extracted_gizmos.positions.clear();
+    extracted_gizmos.colors.clear();
+    if config.enabled {
+        extracted_gizmos
+            .positions
+            .extend_from_slice(&storage.positions);
+        extracted_gizmos.colors.extend_from_slice(&storage.colors);
+    }
+    extracted_gizmos.on_top = config.on_top;
+}
+
+pub struct GizmoMeta {
+    vertices: BufferVec<GizmoVertex>,
+    view_bind_group: Option<BindGroup>,
+}
+
+impl Default for GizmoMeta {
+    fn default() -> Self {
+        Self {
+            vertices: BufferVec::new(BufferUsages::VERTEX),
+            view_bind_group: None,
+        }
+    }
+}
+
+pub fn prepare_gizmos(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut gizmo_meta: ResMut<GizmoMeta>,
+    extracted_gizmos: Res<ExtractedGizmos>,
+) {
+    gizmo_meta.vertices.clear();
+    for (position, color) in extracted_gizmos
+        .positions
+        .iter()
+        .zip(&extracted_gizmos.colors)
+    {
+        gizmo_meta.vertices.push(GizmoVertex {
+            position: *position,
+            color: *color,
+        });
+    }
+    gizmo_meta
+        .vertices
+        .write_buffer(&render_device, &render_queue);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_gizmos(
+    mut commands: Commands,
+    draw_functions_2d: Res<DrawFunctions<Transparent2d>>,
+    draw_functions_3d: Res<DrawFunctions<Transparent3d>>,
+    render_device: Res<RenderDevice>,
+    mut gizmo_meta: ResMut<GizmoMeta>,
+    view_uniforms: Res<ViewUniforms>,
+    gizmo_pipeline: Res<GizmoPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<GizmoPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    msaa: Res<Msaa>,
+    extracted_gizmos: Res<ExtractedGizmos>,
+    mut views_2d: Query<&mut RenderPhase<Transparent2d>>,
+    mut views_3d: Query<(&mut RenderPhase<Transparent3d>, Option<&Camera3d>)>,
+) {
+    if gizmo_meta.vertices.is_empty() {
+        return;
+    }
+    let view_binding = match view_uniforms.uniforms.binding() {
+        Some(view_binding) => view_binding,
+        None => return,
+    };
+    gizmo_meta.view_bind_group = Some(render_device.create_bind_group(&BindGroupDescriptor {
+        entries: &[BindGroupEntry {
+            binding: 0,
+            resource: view_binding,
+        }],
+        label: Some("gizmo_view_bind_group"),
+        layout: &gizmo_pipeline.view_layout,
+    }));
+    // The lines are all drawn at once, after everything else in the phase
+    let entity = commands.spawn().id();
+
+    let draw_gizmos_2d = draw_functions_2d.read().get_id::<DrawGizmos>().unwrap();
+    let pipeline_2d = pipelines.specialize(
+        &mut pipeline_cache,
+        &gizmo_pipeline,
+        GizmoPipelineKey {
+            msaa_samples: msaa.samples,
+            depth: false,
+            on_top: extracted_gizmos.on_top,
+            hdr: false,
+        },
+    );
+    for mut transparent_phase in views_2d.iter_mut() {
+        transparent_phase.add(Transparent2d {
+            sort_key: FloatOrd(f32::INFINITY),
+            entity,
+            pipeline: pipeline_2d,
+            draw_function: draw_gizmos_2d,
+            batch_range: None,
+        });
+    }
+
+    let draw_gizmos_3d = draw_functions_3d.read().get_id::<DrawGizmos>().unwrap();
+    for (mut transparent_phase, camera_3d) in views_3d.iter_mut() {
+        let pipeline_3d = pipelines.specialize(
+            &mut pipeline_cache,
+            &gizmo_pipeline,
+            GizmoPipelineKey {
+                msaa_samples: msaa.samples,
+                depth: true,
+                on_top: extracted_gizmos.on_top,
+                hdr: camera_3d.map_or(false, |camera_3d| camera_3d.hdr),
+            },
+        );
+        transparent_phase.add(Transparent3d {
+            distance: f32::INFINITY,
+            pipeline: pipeline_3d,
+            entity,
+            draw_function: draw_gizmos_3d,
+        });
+    }
+}
+
+pub type DrawGizmos = (SetItemPipeline, SetGizmoViewBindGroup<0>, DrawGizmoLines);
+
+pub struct SetGizmoViewBindGroup<const I: usize>;
+impl<const I: usize> EntityRenderCommand for SetGizmoViewBindGroup<I> {
+    type Param = (SRes<GizmoMeta>, SQuery<Read<ViewUniformOffset>>);
+
+    fn render<'w>(
+        view: Entity,
+        _item: Entity,
+        (gizmo_meta, view_query): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let view_uniform = view_query.get(view).unwrap();
+        pass.set_bind_group(
+            I,
+            gizmo_meta.into_inner().view_bind_group.as_ref().unwrap(),
+            &[view_uniform.offset],
+        );
+        RenderCommandResult::Success
+    }
+}
+
+pub struct DrawGizmoLines;
+impl EntityRenderCommand for DrawGizmoLines {
+    type Param = SRes<GizmoMeta>;
+
+    fn render<'w>(
+        _view: Entity,
+        _item: Entity,
+        gizmo_meta: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let vertices = &gizmo_meta.into_inner().vertices;
+        pass.set_vertex_buffer(0, vertices.buffer().unwrap().slice(..));
+        pass.draw(0..vertices.len() as u32, 0..1);
+        RenderCommandResult::Success
+    }
+}
@@ -0,0 +1,199 @@
+use bevy_app::App;
+use bevy_ecs::{
+    entity::Entity,
+    query::Added,
+    reflect::ReflectComponent,
+    world::{EntityMut, Mut, World},
+};
+use bevy_log::warn;
+use bevy_reflect::{ReflectDeserialize, TypeRegistryArc};
+use bevy_utils::HashMap;
+use gltf::json::Value;
+
+use crate::GltfExtras;
+
+/// A function inserting components on an entity from the value of a property of its
+/// [`GltfExtras`].
+pub type GltfExtrasMapping = Box<dyn Fn(&Value, &mut EntityMut) + Send + Sync>;
+
+/// Converts the properties of the [`GltfExtras`] of the entities spawned from a glTF scene into
+/// components, like the custom properties of the objects of a level authored in Blender.
+///
+/// The extras must be a JSON object. For each of its properties:
+/// - if a mapping was added for its name with
+///   [`add_gltf_extras_mapping`](AddGltfExtrasMapping::add_gltf_extras_mapping), the mapping is
+///   called with its value,
+/// - otherwise if its name is the short name (like `Health`) or the full name of a type
+///   registered with `#[reflect(Component, Deserialize)]`, its value is deserialized as that
+///   component and inserted on the entity.
+///
+/// Other properties are ignored, and can still be read from the [`GltfExtras`].
+#[derive(Default)]
+pub struct GltfExtrasMappings {
+    mappings: HashMap<String, GltfExtrasMapping>,
+}
+
+impl GltfExtrasMappings {
+    /// Add a mapping for the property `key` of the extras, replacing the previous one.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        mapping: impl Fn(&Value, &mut EntityMut) + Send + Sync + 'static,
+    ) {
+        self.mappings.insert(key.into(), Box::new(mapping));
+    }
+
+    /// Remove the mapping for the property `key` of the extras.
+    pub fn remove(&mut self, key: &str) -> Option<GltfExtrasMapping> {
+        self.mappings.remove(key)
+    }
+}
+
+/// Adds mappings converting the [`GltfExtras`] of glTF scenes into components
+pub trait AddGltfExtrasMapping {
+    /// Call `mapping` with the value of the property `key` of the extras of the entities
+    /// spawned from glTF scenes.
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::Component;
+    /// # use bevy_gltf::AddGltfExtrasMapping;
+    /// #[derive(Component)]
+    /// struct Health(f32);
+    ///
+    /// # let mut app = App::new();
+    /// // Blender exports the custom property `health` of an object as `{"health": 100}`
+    /// app.add_gltf_extras_mapping("health", |value, entity| {
+    ///     if let Some(health) = value.as_f64() {
+    ///         entity.insert(Health(health as f32));
+    ///     }
+    /// });
+    /// ```
+    fn add_gltf_extras_mapping(
+        &mut self,
+        key: impl Into<String>,
+        mapping: impl Fn(&Value, &mut EntityMut) + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl AddGltfExtrasMapping for App {
+    fn add_gltf_extras_mapping(
+        &mut self,
+        key: impl Into<String>,
+        mapping: impl Fn(&Value, &mut EntityMut) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(GltfExtrasMappings::default)
+            .insert(key, mapping);
+        self
+    }
+}
+
+/// System converting the [`GltfExtras`] added since its last run into components, with the
+/// [`GltfExtrasMappings`]
+pub fn map_gltf_extras(world: &mut World) {
+    let mut query = world.query_filtered::<(Entity, &GltfExtras), Added<GltfExtras>>();
+    let added_extras = query
+        .iter(world)
+        .filter_map(|(entity, extras)| {
+            match gltf::json::deserialize::from_str::<Value>(&extras.value) {
+                Ok(Value::Object(properties)) => Some((entity, properties)),
+                Ok(_) => None,
+                Err(err) => {
+                    warn!("Invalid glTF extras on {:?}: {}", entity, err);
+                    None
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    if added_extras.is_empty() {
+        return;
+    }
+
+    let type_registry = world.resource::<TypeRegistryArc>().clone();
+    let type_registry = type_registry.read();
+    world.resource_scope(|world, mappings: Mut<GltfExtrasMappings>| {
+        for (entity, properties) in added_extras {
+            for (key, value) in properties {
+                if let Some(mapping) = mappings.mappings.get(&key) {
+                    mapping(&value, &mut world.entity_mut(entity));
+                    continue;
+                }
+
+                let registration = match type_registry
+                    .get_with_short_name(&key)
+                    .or_else(|| type_registry.get_with_name(&key))
+                {
+                    Some(registration) => registration,
+                    None => continue,
+                };
+                let (reflect_component, reflect_deserialize) = match (
+                    registration.data::<ReflectComponent>(),
+                    registration.data::<ReflectDeserialize>(),
+                ) {
+                    (Some(reflect_component), Some(reflect_deserialize)) => {
+                        (reflect_component, reflect_deserialize)
+                    }
+                    _ => continue,
+                };
+                match reflect_deserialize.deserialize(value) {
+                    Ok(component) => reflect_component.add(world, entity, &*component),
+                    Err(err) => warn!(
+                        "Can't convert the glTF extras property {} of {:?} to a component: {}",
+                        key, entity, err
+                    ),
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{prelude::Component, reflect::ReflectComponent, world::World};
+    use bevy_reflect::{Reflect, ReflectDeserialize, TypeRegistryArc};
+    use serde::Deserialize;
+
+    use super::{map_gltf_extras, GltfExtrasMappings};
+    use crate::GltfExtras;
+
+    #[derive(Component, Reflect, Deserialize, Default, Debug, PartialEq)]
+    #[reflect(Component, Deserialize)]
+    struct Spin {
+        speed: f32,
+    }
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Health(f64);
+
+    #[test]
+    fn extras_mapped_to_components() {
+        let mut world = World::new();
+        let type_registry = TypeRegistryArc::default();
+        type_registry.write().register::<Spin>();
+        world.insert_resource(type_registry);
+        let mut mappings = GltfExtrasMappings::default();
+        mappings.insert("health", |value, entity| {
+            entity.insert(Health(value.as_f64().unwrap()));
+        });
+        world.insert_resource(mappings);
+
+        let entity = world
+            .spawn()
+            .insert(GltfExtras {
+                value: r#"{"health": 100, "Spin": {"speed": 2.0}, "label": "door"}"#.to_string(),
+            })
+            .id();
+        let no_extras = world
+            .spawn()
+            .insert(GltfExtras {
+                value: r#""not an object""#.to_string(),
+            })
+            .id();
+        map_gltf_extras(&mut world);
+
+        assert_eq!(world.get::<Health>(entity), Some(&Health(100.0)));
+        assert_eq!(world.get::<Spin>(entity), Some(&Spin { speed: 2.0 }));
+        assert_eq!(world.get::<Health>(no_extras), None);
+    }
+}
@@ -1,17 +1,22 @@
 #[cfg(feature = "bevy_animation")]
-use bevy_animation::AnimationClip;
+use bevy_animation::{AddAnimatedComponent, AnimationClip};
 use bevy_utils::HashMap;
 
+mod extras;
 mod loader;
+pub use extras::*;
 pub use loader::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::{AddAsset, Handle};
-use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_ecs::{
+    prelude::Component, reflect::ReflectComponent, schedule::ExclusiveSystemDescriptorCoercion,
+    system::IntoExclusiveSystem,
+};
 use bevy_pbr::StandardMaterial;
 use bevy_reflect::{Reflect, TypeUuid};
 use bevy_render::mesh::Mesh;
-use bevy_scene::Scene;
+use bevy_scene::{Scene, SceneSystem};
 
 /// Adds support for glTF file loading to the app.
 #[derive(Default)]
@@ -24,7 +29,17 @@ impl Plugin for GltfPlugin {
             .add_asset::<Gltf>()
             .add_asset::<GltfNode>()
             .add_asset::<GltfPrimitive>()
-            .add_asset::<GltfMesh>();
+            .add_asset::<GltfMesh>()
+            .init_resource::<GltfExtrasMappings>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                map_gltf_extras
+                    .exclusive_system()
+                    .at_end()
+                    .after(SceneSystem::SceneSpawner),
+            );
+        #[cfg(feature = "bevy_animation")]
+        app.add_animated_component::<bevy_render::mesh::morph::MorphWeights>();
     }
 }
 
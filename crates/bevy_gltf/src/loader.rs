@@ -19,6 +19,7 @@ use bevy_render::{
     },
     color::Color,
     mesh::{
+        morph::{MorphTarget, MorphWeights},
         skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
         Indices, Mesh, VertexAttributeValues,
     },
@@ -190,9 +191,24 @@ async fn load_gltf<'a, 'b>(
                         gltf::animation::util::ReadOutputs::Scales(scale) => {
                             bevy_animation::Keyframes::Scale(scale.map(Vec3::from).collect())
                         }
-                        gltf::animation::util::ReadOutputs::MorphTargetWeights(_) => {
-                            warn!("Morph animation property not yet supported");
-                            continue;
+                        gltf::animation::util::ReadOutputs::MorphTargetWeights(weights) => {
+                            let weights = weights.into_f32().collect::<Vec<_>>();
+                            // The weights of all the targets are stored one keyframe after the
+                            // other
+                            let target_count = weights.len() / keyframe_timestamps.len().max(1);
+                            let keyframes = weights
+                                .chunks(target_count.max(1))
+                                .map(|chunk| chunk.to_vec())
+                                .collect::<Vec<_>>();
+                            match bevy_animation::PropertyKeyframes::new::<MorphWeights, Vec<f32>>(
+                                "weights", keyframes,
+                            ) {
+                                Ok(keyframes) => bevy_animation::Keyframes::Property(keyframes),
+                                Err(err) => {
+                                    warn!("Invalid morph target weights animation: {}", err);
+                                    continue;
+                                }
+                            }
                         }
                     }
                 } else {
@@ -285,6 +301,18 @@ async fn load_gltf<'a, 'b>(
                 mesh.set_indices(Some(Indices::U32(indices.into_u32().collect())));
             };
 
+            let morph_targets = reader
+                .read_morph_targets()
+                .map(|(positions, normals, tangents)| MorphTarget {
+                    positions: positions.map(|v| v.collect()).unwrap_or_default(),
+                    normals: normals.map(|v| v.collect()).unwrap_or_default(),
+                    tangents: tangents.map(|v| v.collect()).unwrap_or_default(),
+                })
+                .collect::<Vec<_>>();
+            if !morph_targets.is_empty() {
+                mesh.set_morph_targets(morph_targets);
+            }
+
             if mesh.attribute(Mesh::ATTRIBUTE_NORMAL).is_none()
                 && matches!(mesh.primitive_topology(), PrimitiveTopology::TriangleList)
             {
@@ -765,6 +793,23 @@ fn load_node(
     // Map node index to entity
     node_index_to_entity_map.insert(gltf_node.index(), node.id());
 
+    // The weights of the morph targets are shared by all the primitives of the mesh
+    if let Some(mesh) = gltf_node.mesh() {
+        let target_count = mesh
+            .primitives()
+            .map(|primitive| primitive.morph_targets().len())
+            .max()
+            .unwrap_or(0);
+        if target_count > 0 {
+            let weights = gltf_node
+                .weights()
+                .or_else(|| mesh.weights())
+                .map(|weights| weights.to_vec())
+                .unwrap_or_else(|| vec![0.0; target_count]);
+            node.insert(MorphWeights { weights });
+        }
+    }
+
     node.with_children(|parent| {
         if let Some(mesh) = gltf_node.mesh() {
             // append primitives
@@ -16,10 +16,16 @@ pub use child_builder::*;
 mod systems;
 pub use systems::*;
 
+mod relations;
+pub use relations::*;
+
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{child_builder::*, components::*, hierarchy::*, HierarchyPlugin};
+    pub use crate::{
+        child_builder::*, components::*, hierarchy::*, BuildRelations, BuildWorldRelations,
+        HierarchyPlugin, Related, Relation, RelationPlugin, TargetedBy, Targets,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -0,0 +1,861 @@
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_ecs::{
+    archetype::{Archetype, ArchetypeComponentId},
+    component::{Component, ComponentId},
+    entity::{Entities, Entity},
+    query::{
+        Access, Fetch, FetchState, FilteredAccess, QueryFetch, ReadOnlyWorldQuery, WorldQuery,
+        WorldQueryGats,
+    },
+    storage::{Table, Tables},
+    system::{Command, Commands, EntityCommands, Query, RemovedComponents, ResMut},
+    world::{EntityMut, World},
+};
+use bevy_utils::{tracing::debug, HashMap};
+use core::slice;
+use smallvec::{smallvec, SmallVec};
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::Deref,
+    sync::{Arc, RwLock},
+};
+
+/// A kind of relationship between entities, like an inventory owning its items or a camera
+/// following its target.
+///
+/// An entity related to others stores them in its [`Targets<R>`] component, and each of them
+/// stores it in its [`TargetedBy<R>`] component. Both are kept in sync by [`relate`] and
+/// [`unrelate`], and by the [`RelationPlugin<R>`] when an entity is despawned.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_hierarchy::{BuildRelations, Related, RelatedBy, Relation, Targets};
+/// struct Owns;
+///
+/// impl Relation for Owns {}
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn setup(mut commands: Commands) {
+///     let sword = commands.spawn().id();
+///     commands.spawn().insert(Player).relate::<Owns>(sword);
+/// }
+///
+/// fn player_items(players: Query<&Targets<Owns>, With<Player>>) {
+///     for items in players.iter() {
+///         println!("The player owns {} items", items.len());
+///     }
+/// }
+///
+/// // The entities owning a player
+/// fn owners_of_players(owners: Query<Entity, Related<Owns, With<Player>>>) {}
+///
+/// // The entities owned by a player
+/// fn items_owned_by_players(items: Query<Entity, RelatedBy<Owns, With<Player>>>) {}
+/// # bevy_ecs::system::assert_is_system(setup);
+/// # bevy_ecs::system::assert_is_system(player_items);
+/// # bevy_ecs::system::assert_is_system(owners_of_players);
+/// # bevy_ecs::system::assert_is_system(items_owned_by_players);
+/// ```
+pub trait Relation: Send + Sync + 'static {}
+
+/// The entities this entity is related to by the [`Relation`] `R`.
+///
+/// It is maintained by [`relate`] and [`unrelate`], and removed when empty.
+#[derive(Component)]
+pub struct Targets<R: Relation> {
+    entities: SmallVec<[Entity; 4]>,
+    marker: PhantomData<R>,
+}
+
+/// The entities related to this entity by the [`Relation`] `R`: the reverse of [`Targets<R>`].
+///
+/// It is maintained by [`relate`] and [`unrelate`], and removed when empty.
+#[derive(Component)]
+pub struct TargetedBy<R: Relation> {
+    entities: SmallVec<[Entity; 4]>,
+    marker: PhantomData<R>,
+}
+
+macro_rules! impl_relation_component {
+    ($name:ident) => {
+        impl<R: Relation> RelationComponent for $name<R> {
+            fn new(entity: Entity) -> Self {
+                Self {
+                    entities: smallvec![entity],
+                    marker: PhantomData,
+                }
+            }
+
+            fn entities_mut(&mut self) -> &mut SmallVec<[Entity; 4]> {
+                &mut self.entities
+            }
+        }
+
+        impl<R: Relation> Deref for $name<R> {
+            type Target = [Entity];
+
+            fn deref(&self) -> &Self::Target {
+                &self.entities[..]
+            }
+        }
+
+        impl<'a, R: Relation> IntoIterator for &'a $name<R> {
+            type Item = <Self::IntoIter as Iterator>::Item;
+
+            type IntoIter = slice::Iter<'a, Entity>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.entities.iter()
+            }
+        }
+
+        impl<R: Relation> fmt::Debug for $name<R> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    .field(&self.entities)
+                    .finish()
+            }
+        }
+    };
+}
+
+trait RelationComponent: Component + Deref<Target = [Entity]> {
+    fn new(entity: Entity) -> Self;
+
+    fn entities_mut(&mut self) -> &mut SmallVec<[Entity; 4]>;
+}
+
+impl_relation_component!(Targets);
+impl_relation_component!(TargetedBy);
+
+/// The entities related to each entity by the [`Relation`] `R`, in either direction.
+///
+/// It mirrors the [`Targets<R>`] and [`TargetedBy<R>`] components, for the
+/// [`relation_cleanup_system`] to find the other side of the relations of the entities whose
+/// components were removed, e.g. when they are despawned.
+pub struct RelationIndex<R: Relation> {
+    related: HashMap<Entity, SmallVec<[Entity; 4]>>,
+    marker: PhantomData<R>,
+}
+
+impl<R: Relation> Default for RelationIndex<R> {
+    fn default() -> Self {
+        Self {
+            related: Default::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R: Relation> RelationIndex<R> {
+    /// Sets the entities related to `entity` from its relation components
+    fn update(&mut self, entity: Entity, targets: &[Entity], sources: &[Entity]) {
+        let mut related: SmallVec<[Entity; 4]> = targets.iter().copied().collect();
+        related.extend(
+            sources
+                .iter()
+                .copied()
+                .filter(|source| !targets.contains(source)),
+        );
+        if related.is_empty() {
+            self.related.remove(&entity);
+        } else {
+            self.related.insert(entity, related);
+        }
+    }
+
+    fn update_from_world(world: &mut World, entities: &[Entity]) {
+        let mut related = Vec::with_capacity(entities.len());
+        for &entity in entities {
+            let targets = world.get::<Targets<R>>(entity).map_or(&[][..], |c| &c[..]);
+            let sources = world
+                .get::<TargetedBy<R>>(entity)
+                .map_or(&[][..], |c| &c[..]);
+            related.push((entity, targets.to_vec(), sources.to_vec()));
+        }
+        let mut index = world.get_resource_or_insert_with(Self::default);
+        for (entity, targets, sources) in related {
+            index.update(entity, &targets, &sources);
+        }
+    }
+}
+
+fn add_relation_entity<C: RelationComponent>(world: &mut World, entity: Entity, other: Entity) {
+    if let Some(mut component) = world.get_mut::<C>(entity) {
+        if !component.contains(&other) {
+            component.entities_mut().push(other);
+        }
+    } else {
+        world.entity_mut(entity).insert(C::new(other));
+    }
+}
+
+fn remove_relation_entity<C: RelationComponent>(world: &mut World, entity: Entity, other: Entity) {
+    let is_empty = match world.get_mut::<C>(entity) {
+        Some(mut component) if component.contains(&other) => {
+            let entities = component.entities_mut();
+            entities.retain(|e| *e != other);
+            entities.is_empty()
+        }
+        _ => return,
+    };
+    if is_empty {
+        world.entity_mut(entity).remove::<C>();
+    }
+}
+
+/// Relates `source` to `target` by the [`Relation`] `R`, updating both their [`Targets<R>`] and
+/// [`TargetedBy<R>`] components.
+pub fn relate<R: Relation>(world: &mut World, source: Entity, target: Entity) {
+    if !world.entities().contains(source) || !world.entities().contains(target) {
+        debug!(
+            "Failed to relate {:?} to {:?}: the entity does not exist",
+            source, target
+        );
+        return;
+    }
+    add_relation_entity::<Targets<R>>(world, source, target);
+    add_relation_entity::<TargetedBy<R>>(world, target, source);
+    RelationIndex::<R>::update_from_world(world, &[source, target]);
+}
+
+/// Removes the [`Relation`] `R` from `source` to `target`, if any.
+pub fn unrelate<R: Relation>(world: &mut World, source: Entity, target: Entity) {
+    remove_relation_entity::<Targets<R>>(world, source, target);
+    remove_relation_entity::<TargetedBy<R>>(world, target, source);
+    RelationIndex::<R>::update_from_world(world, &[source, target]);
+}
+
+/// Removes all the [`Relation`]s `R` from and to `entity`.
+pub fn unrelate_all<R: Relation>(world: &mut World, entity: Entity) {
+    let mut related = vec![entity];
+    if let Some(targets) = world.entity_mut(entity).remove::<Targets<R>>() {
+        for target in targets.entities {
+            remove_relation_entity::<TargetedBy<R>>(world, target, entity);
+            related.push(target);
+        }
+    }
+    if let Some(sources) = world.entity_mut(entity).remove::<TargetedBy<R>>() {
+        for source in sources.entities {
+            remove_relation_entity::<Targets<R>>(world, source, entity);
+            related.push(source);
+        }
+    }
+    RelationIndex::<R>::update_from_world(world, &related);
+}
+
+/// Command that relates an entity to another, see [`relate`]
+pub struct Relate<R: Relation> {
+    /// Source entity of the relation
+    pub source: Entity,
+    /// Target entity of the relation
+    pub target: Entity,
+    marker: PhantomData<R>,
+}
+
+impl<R: Relation> Relate<R> {
+    /// Creates a command relating `source` to `target`
+    pub fn new(source: Entity, target: Entity) -> Self {
+        Self {
+            source,
+            target,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R: Relation> Command for Relate<R> {
+    fn write(self, world: &mut World) {
+        relate::<R>(world, self.source, self.target);
+    }
+}
+
+/// Command that removes the relation from an entity to another, see [`unrelate`]
+pub struct Unrelate<R: Relation> {
+    /// Source entity of the relation
+    pub source: Entity,
+    /// Target entity of the relation
+    pub target: Entity,
+    marker: PhantomData<R>,
+}
+
+impl<R: Relation> Unrelate<R> {
+    /// Creates a command removing the relation from `source` to `target`
+    pub fn new(source: Entity, target: Entity) -> Self {
+        Self {
+            source,
+            target,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R: Relation> Command for Unrelate<R> {
+    fn write(self, world: &mut World) {
+        unrelate::<R>(world, self.source, self.target);
+    }
+}
+
+/// Trait that defines relating an entity to others through [`Commands`]
+pub trait BuildRelations {
+    /// Relates this entity to `target` by the [`Relation`] `R`
+    fn relate<R: Relation>(&mut self, target: Entity) -> &mut Self;
+    /// Removes the [`Relation`] `R` from this entity to `target`
+    fn unrelate<R: Relation>(&mut self, target: Entity) -> &mut Self;
+}
+
+impl<'w, 's, 'a> BuildRelations for EntityCommands<'w, 's, 'a> {
+    fn relate<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        let source = self.id();
+        self.commands().add(Relate::<R>::new(source, target));
+        self
+    }
+
+    fn unrelate<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        let source = self.id();
+        self.commands().add(Unrelate::<R>::new(source, target));
+        self
+    }
+}
+
+/// Trait that defines relating an entity to others directly through the [`World`]
+pub trait BuildWorldRelations {
+    /// Relates this entity to `target` by the [`Relation`] `R`
+    fn relate<R: Relation>(&mut self, target: Entity) -> &mut Self;
+    /// Removes the [`Relation`] `R` from this entity to `target`
+    fn unrelate<R: Relation>(&mut self, target: Entity) -> &mut Self;
+}
+
+impl<'w> BuildWorldRelations for EntityMut<'w> {
+    fn relate<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        let source = self.id();
+        // SAFETY: the location is updated below
+        unsafe {
+            relate::<R>(self.world_mut(), source, target);
+            self.update_location();
+        }
+        self
+    }
+
+    fn unrelate<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        let source = self.id();
+        // SAFETY: the location is updated below
+        unsafe {
+            unrelate::<R>(self.world_mut(), source, target);
+            self.update_location();
+        }
+        self
+    }
+}
+
+/// Removes the despawned entities from the [`Targets<R>`] and [`TargetedBy<R>`] components, and
+/// the relations whose other side has been removed.
+///
+/// Only the entities whose relation components were removed, and the entities related to them
+/// according to the [`RelationIndex<R>`], are visited.
+pub fn relation_cleanup_system<R: Relation>(
+    mut commands: Commands,
+    removed_targets: RemovedComponents<Targets<R>>,
+    removed_targeted_by: RemovedComponents<TargetedBy<R>>,
+    mut index: ResMut<RelationIndex<R>>,
+    mut targets_query: Query<&mut Targets<R>>,
+    mut targeted_by_query: Query<&mut TargetedBy<R>>,
+) {
+    let mut removed: Vec<Entity> = removed_targets
+        .iter()
+        .chain(removed_targeted_by.iter())
+        .collect();
+    if removed.is_empty() {
+        return;
+    }
+    removed.sort_unstable();
+    removed.dedup();
+
+    let mut visited = removed.clone();
+    for entity in &removed {
+        if let Some(related) = index.related.get(entity) {
+            visited.extend(related.iter().copied());
+        }
+    }
+    visited.sort_unstable();
+    visited.dedup();
+
+    // Keep the sources still targeting each entity, then the targets still targeted by it, so
+    // that both sides match.
+    for &entity in &visited {
+        if let Ok(mut sources) = targeted_by_query.get_mut(entity) {
+            let is_source = |source: &Entity| {
+                targets_query
+                    .get(*source)
+                    .map_or(false, |targets| targets.contains(&entity))
+            };
+            if !sources.iter().all(is_source) {
+                sources.entities.retain(|source| is_source(source));
+                if sources.is_empty() {
+                    commands.entity(entity).remove::<TargetedBy<R>>();
+                }
+            }
+        }
+    }
+    for &entity in &visited {
+        if let Ok(mut targets) = targets_query.get_mut(entity) {
+            let is_target = |target: &Entity| {
+                targeted_by_query
+                    .get(*target)
+                    .map_or(false, |sources| sources.contains(&entity))
+            };
+            if !targets.iter().all(is_target) {
+                targets.entities.retain(|target| is_target(target));
+                if targets.is_empty() {
+                    commands.entity(entity).remove::<Targets<R>>();
+                }
+            }
+        }
+    }
+
+    for entity in visited {
+        let targets = targets_query.get(entity).map_or(&[][..], |c| &c[..]);
+        let sources = targeted_by_query.get(entity).map_or(&[][..], |c| &c[..]);
+        index.update(entity, targets, sources);
+    }
+}
+
+/// Keeps the relations `R` in sync when related entities are despawned, with the
+/// [`relation_cleanup_system`]
+pub struct RelationPlugin<R: Relation>(PhantomData<R>);
+
+impl<R: Relation> Default for RelationPlugin<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<R: Relation> Plugin for RelationPlugin<R> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RelationIndex<R>>()
+            .add_system_to_stage(CoreStage::PostUpdate, relation_cleanup_system::<R>);
+    }
+}
+
+/// Filter that selects entities related by the [`Relation`] `R` to at least one entity
+/// matching the filter `F`, i.e. whose [`Targets<R>`] contains an entity matching `F`.
+///
+/// `F` is checked against the components of the targets, so only archetypal filters like
+/// [`With`](bevy_ecs::query::With) and [`Without`](bevy_ecs::query::Without), and their
+/// combinations, are supported. Queries using a filter like
+/// [`Changed`](bevy_ecs::query::Changed) for `F` panic when they are created.
+pub type Related<R, F = ()> = RelationFilter<Targets<R>, F>;
+
+/// Filter that selects entities that at least one entity matching the filter `F` is related to
+/// by the [`Relation`] `R`, i.e. whose [`TargetedBy<R>`] contains an entity matching `F`.
+///
+/// This is the reverse of [`Related`], with the same restrictions on `F`.
+pub type RelatedBy<R, F = ()> = RelationFilter<TargetedBy<R>, F>;
+
+/// Filter that selects entities with a component `C` listing at least one entity matching the
+/// filter `F`, see [`Related`] and [`RelatedBy`].
+pub struct RelationFilter<C, F>(PhantomData<(C, F)>);
+
+/// The [`FetchState`] of [`RelationFilter`]
+#[doc(hidden)]
+pub struct RelationFilterState<C: Component, F: ReadOnlyWorldQuery> {
+    component_state: <&'static C as WorldQuery>::State,
+    filter_state: F::State,
+    /// Whether the archetype of each index matches the filter, extended when archetypes are
+    /// added to the world
+    matching_archetypes: RwLock<Arc<[bool]>>,
+}
+
+impl<C: Component, F: ReadOnlyWorldQuery> RelationFilterState<C, F> {
+    /// Returns whether each archetype of the world matches the filter
+    fn matching_archetypes(&self, world: &World) -> Arc<[bool]> {
+        let archetypes = world.archetypes();
+        {
+            let matching = self.matching_archetypes.read().unwrap();
+            if matching.len() == archetypes.len() {
+                return matching.clone();
+            }
+        }
+        let mut matching = self.matching_archetypes.write().unwrap();
+        // Archetypes are never removed, only the new ones need to be checked
+        let new_archetypes = archetypes.iter().skip(matching.len()).map(|archetype| {
+            self.filter_state
+                .matches_component_set(&|id| archetype.contains(id))
+        });
+        *matching = matching.iter().copied().chain(new_archetypes).collect();
+        matching.clone()
+    }
+}
+
+impl<C: Component, F: ReadOnlyWorldQuery> FetchState for RelationFilterState<C, F> {
+    fn init(world: &mut World) -> Self {
+        assert!(
+            <QueryFetch<'static, F> as Fetch<'static>>::IS_ARCHETYPAL,
+            "The filter of the entities related by {} must only depend on their components",
+            std::any::type_name::<C>()
+        );
+        Self {
+            component_state: FetchState::init(world),
+            filter_state: FetchState::init(world),
+            matching_archetypes: RwLock::new(Arc::new([])),
+        }
+    }
+
+    fn matches_component_set(&self, set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
+        self.component_state.matches_component_set(set_contains_id)
+    }
+}
+
+/// The [`Fetch`] of [`RelationFilter`]
+#[doc(hidden)]
+pub struct RelationFilterFetch<'w, C: Component, F> {
+    component_fetch: QueryFetch<'w, &'static C>,
+    entities: &'w Entities,
+    matching_archetypes: Arc<[bool]>,
+    marker: PhantomData<F>,
+}
+
+impl<C: Component, F> Clone for RelationFilterFetch<'_, C, F> {
+    fn clone(&self) -> Self {
+        Self {
+            component_fetch: self.component_fetch.clone(),
+            entities: self.entities,
+            matching_archetypes: self.matching_archetypes.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'w, C, F> RelationFilterFetch<'w, C, F>
+where
+    C: Component + Deref<Target = [Entity]>,
+{
+    #[inline]
+    fn matches(&self, related: &C) -> bool {
+        related.iter().any(|entity| {
+            self.entities.get(*entity).map_or(false, |location| {
+                self.matching_archetypes[location.archetype_id.index()]
+            })
+        })
+    }
+}
+
+// SAFETY: `ROQueryFetch<Self>` is the same as `QueryFetch<Self>`
+unsafe impl<C, F> WorldQuery for RelationFilter<C, F>
+where
+    C: Component + Deref<Target = [Entity]>,
+    F: ReadOnlyWorldQuery,
+{
+    type ReadOnly = Self;
+    type State = RelationFilterState<C, F>;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: bool) -> bool {
+        item
+    }
+}
+
+// SAFETY: access is read only
+unsafe impl<C, F> ReadOnlyWorldQuery for RelationFilter<C, F>
+where
+    C: Component + Deref<Target = [Entity]>,
+    F: ReadOnlyWorldQuery,
+{
+}
+
+impl<'w, C, F> WorldQueryGats<'w> for RelationFilter<C, F>
+where
+    C: Component + Deref<Target = [Entity]>,
+    F: ReadOnlyWorldQuery,
+{
+    type Fetch = RelationFilterFetch<'w, C, F>;
+    type _State = RelationFilterState<C, F>;
+}
+
+// SAFETY: the access of `&C` is reported, and only the archetypes of the related entities are
+// read otherwise
+unsafe impl<'w, C, F> Fetch<'w> for RelationFilterFetch<'w, C, F>
+where
+    C: Component + Deref<Target = [Entity]>,
+    F: ReadOnlyWorldQuery,
+{
+    type Item = bool;
+    type State = RelationFilterState<C, F>;
+
+    const IS_DENSE: bool = <QueryFetch<'w, &'static C>>::IS_DENSE;
+
+    const IS_ARCHETYPAL: bool = false;
+
+    unsafe fn init(
+        world: &'w World,
+        state: &Self::State,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        Self {
+            component_fetch: Fetch::init(
+                world,
+                &state.component_state,
+                last_change_tick,
+                change_tick,
+            ),
+            entities: world.entities(),
+            matching_archetypes: state.matching_archetypes(world),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    unsafe fn set_archetype(
+        &mut self,
+        state: &Self::State,
+        archetype: &'w Archetype,
+        tables: &'w Tables,
+    ) {
+        self.component_fetch
+            .set_archetype(&state.component_state, archetype, tables);
+    }
+
+    #[inline]
+    unsafe fn set_table(&mut self, state: &Self::State, table: &'w Table) {
+        self.component_fetch
+            .set_table(&state.component_state, table);
+    }
+
+    #[inline]
+    unsafe fn archetype_fetch(&mut self, archetype_index: usize) -> bool {
+        let related = self.component_fetch.archetype_fetch(archetype_index);
+        self.matches(related)
+    }
+
+    #[inline]
+    unsafe fn table_fetch(&mut self, table_row: usize) -> bool {
+        let related = self.component_fetch.table_fetch(table_row);
+        self.matches(related)
+    }
+
+    #[inline]
+    unsafe fn archetype_filter_fetch(&mut self, archetype_index: usize) -> bool {
+        self.archetype_fetch(archetype_index)
+    }
+
+    #[inline]
+    unsafe fn table_filter_fetch(&mut self, table_row: usize) -> bool {
+        self.table_fetch(table_row)
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        <QueryFetch<'w, &'static C>>::update_component_access(&state.component_state, access);
+    }
+
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        <QueryFetch<'w, &'static C>>::update_archetype_component_access(
+            &state.component_state,
+            archetype,
+            access,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        query::{With, Without},
+        system::CommandQueue,
+    };
+
+    struct Owns;
+
+    impl Relation for Owns {}
+
+    #[derive(Component)]
+    struct Player;
+
+    #[derive(Component)]
+    struct Sword;
+
+    fn sorted(entities: &[Entity]) -> Vec<Entity> {
+        let mut entities = entities.to_vec();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn relate_and_unrelate() {
+        let mut world = World::default();
+        let player = world.spawn().insert(Player).id();
+        let sword = world.spawn().insert(Sword).id();
+        let shield = world.spawn().id();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands
+            .entity(player)
+            .relate::<Owns>(sword)
+            .relate::<Owns>(shield)
+            .relate::<Owns>(sword);
+        queue.apply(&mut world);
+
+        assert_eq!(
+            sorted(world.get::<Targets<Owns>>(player).unwrap()),
+            sorted(&[sword, shield])
+        );
+        assert_eq!(&**world.get::<TargetedBy<Owns>>(sword).unwrap(), &[player]);
+        assert_eq!(&**world.get::<TargetedBy<Owns>>(shield).unwrap(), &[player]);
+
+        world.entity_mut(player).unrelate::<Owns>(shield);
+        assert_eq!(&**world.get::<Targets<Owns>>(player).unwrap(), &[sword]);
+        assert!(world.get::<TargetedBy<Owns>>(shield).is_none());
+
+        unrelate_all::<Owns>(&mut world, sword);
+        assert!(world.get::<Targets<Owns>>(player).is_none());
+        assert!(world.get::<TargetedBy<Owns>>(sword).is_none());
+    }
+
+    #[test]
+    fn related_filter() {
+        let mut world = World::default();
+        let player = world.spawn().insert(Player).id();
+        let npc = world.spawn().id();
+        let sword = world.spawn().insert(Sword).id();
+        let shield = world.spawn().id();
+        let bow = world.spawn().insert(Sword).id();
+        world.spawn().insert(Sword);
+        world.entity_mut(player).relate::<Owns>(shield);
+        world.entity_mut(sword).relate::<Owns>(player);
+        world.entity_mut(shield).relate::<Owns>(npc);
+        world
+            .entity_mut(bow)
+            .relate::<Owns>(npc)
+            .relate::<Owns>(player);
+
+        let mut query = world.query_filtered::<Entity, Related<Owns, With<Player>>>();
+        assert_eq!(
+            sorted(&query.iter(&world).collect::<Vec<_>>()),
+            sorted(&[sword, bow])
+        );
+
+        let mut query =
+            world.query_filtered::<Entity, (With<Sword>, Related<Owns, Without<Player>>)>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![bow]);
+
+        let mut query = world.query_filtered::<Entity, Related<Owns>>();
+        assert_eq!(query.iter(&world).count(), 4);
+    }
+
+    #[test]
+    fn related_by_filter() {
+        let mut world = World::default();
+        let player = world.spawn().insert(Player).id();
+        let npc = world.spawn().id();
+        let sword = world.spawn().insert(Sword).id();
+        let shield = world.spawn().id();
+        world
+            .entity_mut(player)
+            .relate::<Owns>(sword)
+            .relate::<Owns>(shield);
+        world.entity_mut(npc).relate::<Owns>(shield);
+
+        let mut query = world.query_filtered::<Entity, RelatedBy<Owns, With<Player>>>();
+        assert_eq!(
+            sorted(&query.iter(&world).collect::<Vec<_>>()),
+            sorted(&[sword, shield])
+        );
+
+        let mut query = world.query_filtered::<Entity, RelatedBy<Owns, Without<Player>>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![shield]);
+    }
+
+    #[test]
+    fn related_filter_matches_new_archetypes() {
+        #[derive(Component)]
+        struct Enchanted;
+
+        let mut world = World::default();
+        let player = world.spawn().insert(Player).id();
+        let sword = world.spawn().id();
+        world.entity_mut(player).relate::<Owns>(sword);
+
+        let mut query = world.query_filtered::<Entity, Related<Owns, With<Enchanted>>>();
+        assert_eq!(query.iter(&world).count(), 0);
+
+        // The sword moves to an archetype created after the query first ran
+        world.entity_mut(sword).insert(Enchanted);
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![player]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn related_filter_must_be_archetypal() {
+        let mut world = World::default();
+        world.query_filtered::<Entity, Related<Owns, bevy_ecs::query::Changed<Player>>>();
+    }
+
+    #[test]
+    fn despawned_entities_are_cleaned_up() {
+        let mut app = App::new();
+        app.add_plugin(RelationPlugin::<Owns>::default());
+        let world = &mut app.world;
+        let player = world.spawn().insert(Player).id();
+        let sword = world.spawn().id();
+        let shield = world.spawn().id();
+        world
+            .entity_mut(player)
+            .relate::<Owns>(sword)
+            .relate::<Owns>(shield);
+
+        app.world.despawn(sword);
+        app.update();
+        assert_eq!(
+            &**app.world.get::<Targets<Owns>>(player).unwrap(),
+            &[shield]
+        );
+
+        app.world.despawn(player);
+        app.update();
+        assert!(app.world.get::<TargetedBy<Owns>>(shield).is_none());
+
+        let index = app.world.resource::<RelationIndex<Owns>>();
+        assert!(index.related.is_empty());
+    }
+
+    #[test]
+    fn removed_components_are_cleaned_up() {
+        let mut app = App::new();
+        app.add_plugin(RelationPlugin::<Owns>::default());
+        let world = &mut app.world;
+        let player = world.spawn().insert(Player).id();
+        let npc = world.spawn().id();
+        let sword = world.spawn().id();
+        let shield = world.spawn().id();
+        world
+            .entity_mut(player)
+            .relate::<Owns>(sword)
+            .relate::<Owns>(shield);
+        world.entity_mut(npc).relate::<Owns>(shield);
+
+        // The shield isn't owned anymore, but the player still owns the sword
+        app.world.entity_mut(shield).remove::<TargetedBy<Owns>>();
+        app.update();
+        assert_eq!(&**app.world.get::<Targets<Owns>>(player).unwrap(), &[sword]);
+        assert!(app.world.get::<Targets<Owns>>(npc).is_none());
+        assert_eq!(
+            &**app.world.get::<TargetedBy<Owns>>(sword).unwrap(),
+            &[player]
+        );
+
+        let index = app.world.resource::<RelationIndex<Owns>>();
+        assert_eq!(sorted(&index.related[&player]), vec![sword]);
+        assert!(!index.related.contains_key(&npc));
+        assert!(!index.related.contains_key(&shield));
+    }
+}
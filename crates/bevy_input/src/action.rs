@@ -0,0 +1,438 @@
+use crate::{
+    gamepad::{Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    Axis, Input, InputSystem,
+};
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_ecs::{
+    schedule::ParallelSystemDescriptorCoercion,
+    system::{Res, ResMut},
+};
+use bevy_utils::HashMap;
+use std::{hash::Hash, marker::PhantomData};
+
+/// A logical action of a game, like `Jump` or `Confirm`, bound to physical inputs by an
+/// [`ActionMap`].
+///
+/// This is implemented for every type that can be used as an action, usually a fieldless enum:
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// enum MenuAction {
+///     Confirm,
+///     Cancel,
+/// }
+/// ```
+pub trait Action: Copy + Eq + Hash + Send + Sync + 'static {}
+
+impl<T: Copy + Eq + Hash + Send + Sync + 'static> Action for T {}
+
+/// A physical input triggering an [`Action`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputBinding {
+    /// A key of the keyboard, with a value of `1.0` while pressed.
+    Key(KeyCode),
+    /// A mouse button, with a value of `1.0` while pressed.
+    Mouse(MouseButton),
+    /// A button of the gamepads, with a value of `1.0` while pressed.
+    GamepadButton(GamepadButtonType),
+    /// An axis of the gamepads, with a value between `-1.0` and `1.0`.
+    ///
+    /// The values within `dead_zone` of `0.0` are ignored, and the values outside of it are
+    /// rescaled to still cover the whole range. The binding is pressed while its value is not
+    /// `0.0`.
+    GamepadAxis {
+        axis_type: GamepadAxisType,
+        dead_zone: f32,
+    },
+}
+
+impl InputBinding {
+    /// Binds the gamepad axis `axis_type` with the given `dead_zone`.
+    pub fn gamepad_axis(axis_type: GamepadAxisType, dead_zone: f32) -> Self {
+        InputBinding::GamepadAxis {
+            axis_type,
+            dead_zone,
+        }
+    }
+}
+
+impl From<KeyCode> for InputBinding {
+    fn from(key_code: KeyCode) -> Self {
+        InputBinding::Key(key_code)
+    }
+}
+
+impl From<MouseButton> for InputBinding {
+    fn from(mouse_button: MouseButton) -> Self {
+        InputBinding::Mouse(mouse_button)
+    }
+}
+
+impl From<GamepadButtonType> for InputBinding {
+    fn from(button_type: GamepadButtonType) -> Self {
+        InputBinding::GamepadButton(button_type)
+    }
+}
+
+/// The bindings of the actions of type `A` to physical inputs, used to update
+/// the [`ActionState<A>`] resource.
+///
+/// The bindings can be changed at runtime, and saved with the `serialize` feature to let
+/// the players rebind their controls.
+///
+/// ```
+/// # use bevy_input::{action::{ActionMap, InputBinding}, gamepad::{GamepadAxisType, GamepadButtonType}, keyboard::KeyCode};
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// enum PlayerAction {
+///     Jump,
+///     Move,
+/// }
+///
+/// let mut action_map = ActionMap::default();
+/// action_map
+///     .bind(PlayerAction::Jump, KeyCode::Space)
+///     .bind(PlayerAction::Jump, GamepadButtonType::South)
+///     .bind(
+///         PlayerAction::Move,
+///         InputBinding::gamepad_axis(GamepadAxisType::LeftStickX, 0.1),
+///     );
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionMap<A: Action> {
+    bindings: HashMap<A, Vec<InputBinding>>,
+    gamepad: Option<Gamepad>,
+}
+
+impl<A: Action> Default for ActionMap<A> {
+    fn default() -> Self {
+        Self {
+            bindings: Default::default(),
+            gamepad: None,
+        }
+    }
+}
+
+impl<A: Action> ActionMap<A> {
+    /// Binds `action` to `binding`, in addition to its other bindings.
+    pub fn bind(&mut self, action: A, binding: impl Into<InputBinding>) -> &mut Self {
+        let binding = binding.into();
+        let bindings = self.bindings.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+        self
+    }
+
+    /// Removes `binding` from the bindings of `action`, returning `true` if it was bound.
+    pub fn unbind(&mut self, action: A, binding: impl Into<InputBinding>) -> bool {
+        let binding = binding.into();
+        match self.bindings.get_mut(&action) {
+            Some(bindings) => {
+                let len = bindings.len();
+                bindings.retain(|bound| *bound != binding);
+                bindings.len() != len
+            }
+            None => false,
+        }
+    }
+
+    /// Removes all the bindings of `action`, returning them.
+    pub fn clear_bindings(&mut self, action: A) -> Vec<InputBinding> {
+        self.bindings.remove(&action).unwrap_or_default()
+    }
+
+    /// Replaces the bindings of `action` by `bindings`, for example after the player rebound it.
+    pub fn set_bindings(&mut self, action: A, bindings: impl IntoIterator<Item = InputBinding>) {
+        self.clear_bindings(action);
+        for binding in bindings {
+            self.bind(action, binding);
+        }
+    }
+
+    /// Returns the bindings of `action`.
+    pub fn bindings(&self, action: A) -> &[InputBinding] {
+        self.bindings
+            .get(&action)
+            .map(|bindings| bindings.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Iterates over the actions and their bindings.
+    pub fn iter(&self) -> impl Iterator<Item = (A, &[InputBinding])> + '_ {
+        self.bindings
+            .iter()
+            .map(|(action, bindings)| (*action, bindings.as_slice()))
+    }
+
+    /// Returns the gamepad the gamepad bindings are read from, or `None` if they are read from
+    /// all the connected gamepads.
+    pub fn gamepad(&self) -> Option<Gamepad> {
+        self.gamepad
+    }
+
+    /// Sets the gamepad the gamepad bindings are read from, see [`ActionMap::gamepad`].
+    pub fn set_gamepad(&mut self, gamepad: Option<Gamepad>) {
+        self.gamepad = gamepad;
+    }
+}
+
+/// The state of the actions of type `A`, updated from their [`ActionMap<A>`] at each frame.
+///
+/// An action is pressed while any of its bindings is pressed, and its value is the value of its
+/// binding with the largest magnitude.
+#[derive(Debug, Clone)]
+pub struct ActionState<A: Action> {
+    input: Input<A>,
+    values: HashMap<A, f32>,
+}
+
+impl<A: Action> Default for ActionState<A> {
+    fn default() -> Self {
+        Self {
+            input: Default::default(),
+            values: Default::default(),
+        }
+    }
+}
+
+impl<A: Action> ActionState<A> {
+    /// Returns `true` if `action` is pressed.
+    pub fn pressed(&self, action: A) -> bool {
+        self.input.pressed(action)
+    }
+
+    /// Returns `true` if `action` has just been pressed.
+    pub fn just_pressed(&self, action: A) -> bool {
+        self.input.just_pressed(action)
+    }
+
+    /// Returns `true` if `action` has just been released.
+    pub fn just_released(&self, action: A) -> bool {
+        self.input.just_released(action)
+    }
+
+    /// Returns the value of `action`, between `-1.0` and `1.0`, or `0.0` when it is not pressed.
+    pub fn value(&self, action: A) -> f32 {
+        self.values.get(&action).copied().unwrap_or_default()
+    }
+
+    /// Sets the value of `action` for this frame, pressing it if `value` is not `0.0` and
+    /// releasing it otherwise.
+    ///
+    /// This is called by [`action_state_system`] at the start of each frame, after
+    /// [`ActionState::clear`].
+    pub fn set(&mut self, action: A, value: f32) {
+        if value == 0.0 {
+            self.input.release(action);
+            self.values.remove(&action);
+        } else {
+            self.input.press(action);
+            self.values.insert(action, value);
+        }
+    }
+
+    /// Clears the `just_pressed` and `just_released` state of all the actions.
+    pub fn clear(&mut self) {
+        self.input.clear();
+    }
+}
+
+/// Updates the [`ActionState<A>`] resource from the current inputs and the bindings of the
+/// [`ActionMap<A>`] resource.
+#[allow(clippy::too_many_arguments)]
+pub fn action_state_system<A: Action>(
+    action_map: Res<ActionMap<A>>,
+    mut action_state: ResMut<ActionState<A>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_button_input: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+) {
+    let gamepads = || {
+        action_map.gamepad.into_iter().chain(
+            gamepads
+                .iter()
+                .copied()
+                .filter(|_| action_map.gamepad.is_none()),
+        )
+    };
+    let pressed_value = |pressed: bool| if pressed { 1.0 } else { 0.0 };
+
+    action_state.clear();
+    for (action, bindings) in action_map.iter() {
+        let value = bindings
+            .iter()
+            .map(|binding| match *binding {
+                InputBinding::Key(key_code) => pressed_value(keyboard_input.pressed(key_code)),
+                InputBinding::Mouse(mouse_button) => {
+                    pressed_value(mouse_button_input.pressed(mouse_button))
+                }
+                InputBinding::GamepadButton(button_type) => {
+                    pressed_value(gamepads().any(|gamepad| {
+                        gamepad_button_input.pressed(GamepadButton::new(gamepad, button_type))
+                    }))
+                }
+                InputBinding::GamepadAxis {
+                    axis_type,
+                    dead_zone,
+                } => gamepads()
+                    .filter_map(|gamepad| gamepad_axes.get(GamepadAxis::new(gamepad, axis_type)))
+                    .map(|value| apply_dead_zone(value, dead_zone))
+                    .fold(0.0, max_magnitude),
+            })
+            .fold(0.0, max_magnitude);
+        action_state.set(action, value);
+    }
+
+    // Release the actions that were pressed before their bindings were removed
+    let unbound_actions: Vec<A> = action_state
+        .values
+        .keys()
+        .filter(|action| action_map.bindings(**action).is_empty())
+        .copied()
+        .collect();
+    for action in unbound_actions {
+        action_state.set(action, 0.0);
+    }
+}
+
+fn max_magnitude(a: f32, b: f32) -> f32 {
+    if b.abs() > a.abs() {
+        b
+    } else {
+        a
+    }
+}
+
+fn apply_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    let dead_zone = dead_zone.clamp(0.0, 1.0);
+    if value.abs() <= dead_zone {
+        0.0
+    } else {
+        value.signum() * (value.abs() - dead_zone) / (1.0 - dead_zone)
+    }
+}
+
+/// Adds the [`ActionMap<A>`] and [`ActionState<A>`] resources, and updates the action state
+/// at the start of each frame after the [`InputSystem`]s.
+pub struct ActionPlugin<A: Action>(PhantomData<fn() -> A>);
+
+impl<A: Action> Default for ActionPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: Action> Plugin for ActionPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionMap<A>>()
+            .init_resource::<ActionState<A>>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                action_state_system::<A>.after(InputSystem),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_dead_zone, ActionMap, ActionPlugin, ActionState, InputBinding};
+    use crate::{
+        gamepad::{Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType},
+        keyboard::KeyCode,
+        Axis, Input, InputPlugin,
+    };
+    use bevy_app::App;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum TestAction {
+        Jump,
+        Move,
+    }
+
+    #[test]
+    fn bindings() {
+        let mut action_map = ActionMap::default();
+        action_map
+            .bind(TestAction::Jump, KeyCode::Space)
+            .bind(TestAction::Jump, KeyCode::Space)
+            .bind(TestAction::Jump, GamepadButtonType::South);
+        assert_eq!(
+            action_map.bindings(TestAction::Jump),
+            &[
+                InputBinding::Key(KeyCode::Space),
+                InputBinding::GamepadButton(GamepadButtonType::South)
+            ]
+        );
+        assert!(action_map.bindings(TestAction::Move).is_empty());
+
+        assert!(action_map.unbind(TestAction::Jump, KeyCode::Space));
+        assert!(!action_map.unbind(TestAction::Jump, KeyCode::Space));
+        action_map.set_bindings(TestAction::Jump, [InputBinding::Key(KeyCode::W)]);
+        assert_eq!(
+            action_map.bindings(TestAction::Jump),
+            &[InputBinding::Key(KeyCode::W)]
+        );
+    }
+
+    #[test]
+    fn dead_zone() {
+        assert_eq!(apply_dead_zone(0.05, 0.1), 0.0);
+        assert_eq!(apply_dead_zone(-0.1, 0.1), 0.0);
+        assert_eq!(apply_dead_zone(0.625, 0.25), 0.5);
+        assert_eq!(apply_dead_zone(-1.0, 0.25), -1.0);
+    }
+
+    #[test]
+    fn action_state() {
+        let mut app = App::new();
+        app.add_plugin(InputPlugin)
+            .add_plugin(ActionPlugin::<TestAction>::default());
+        app.world
+            .resource_mut::<ActionMap<TestAction>>()
+            .bind(TestAction::Jump, KeyCode::Space)
+            .bind(
+                TestAction::Move,
+                InputBinding::gamepad_axis(GamepadAxisType::LeftStickX, 0.25),
+            )
+            .set_gamepad(Some(Gamepad::new(0)));
+
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::Space);
+        app.world.resource_mut::<Axis<GamepadAxis>>().set(
+            GamepadAxis::new(Gamepad::new(0), GamepadAxisType::LeftStickX),
+            -0.625,
+        );
+        // Only the selected gamepad is read
+        app.world
+            .resource_mut::<Input<GamepadButton>>()
+            .press(GamepadButton::new(
+                Gamepad::new(1),
+                GamepadButtonType::South,
+            ));
+        app.update();
+
+        let action_state = app.world.resource::<ActionState<TestAction>>();
+        assert!(action_state.just_pressed(TestAction::Jump));
+        assert_eq!(action_state.value(TestAction::Jump), 1.0);
+        assert!(action_state.pressed(TestAction::Move));
+        assert_eq!(action_state.value(TestAction::Move), -0.5);
+
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .release(KeyCode::Space);
+        app.update();
+
+        let action_state = app.world.resource::<ActionState<TestAction>>();
+        assert!(!action_state.pressed(TestAction::Jump));
+        assert!(action_state.just_released(TestAction::Jump));
+        assert_eq!(action_state.value(TestAction::Jump), 0.0);
+        assert!(!action_state.just_pressed(TestAction::Move));
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{Axis, Input};
 use bevy_ecs::event::{EventReader, EventWriter};
 use bevy_ecs::system::{Res, ResMut};
-use bevy_utils::{tracing::info, HashMap, HashSet};
+use bevy_utils::{tracing::info, Duration, HashMap, HashSet};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -296,6 +296,101 @@ impl ButtonAxisSettings {
     }
 }
 
+/// The intensity at which a gamepad's force-feedback motors may rumble.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadRumbleIntensity {
+    /// The rumble intensity of the strong gamepad motor, between `0.0` and `1.0`.
+    ///
+    /// The strong motor is generally a low frequency motor on the left side of the gamepad.
+    pub strong_motor: f32,
+    /// The rumble intensity of the weak gamepad motor, between `0.0` and `1.0`.
+    ///
+    /// The weak motor is generally a high frequency motor on the right side of the gamepad.
+    pub weak_motor: f32,
+}
+
+impl GamepadRumbleIntensity {
+    /// Rumble both gamepad motors at maximum intensity.
+    pub const MAX: Self = GamepadRumbleIntensity {
+        strong_motor: 1.0,
+        weak_motor: 1.0,
+    };
+
+    /// Rumble the weak motor at maximum intensity.
+    pub const WEAK_MAX: Self = GamepadRumbleIntensity {
+        strong_motor: 0.0,
+        weak_motor: 1.0,
+    };
+
+    /// Rumble the strong motor at maximum intensity.
+    pub const STRONG_MAX: Self = GamepadRumbleIntensity {
+        strong_motor: 1.0,
+        weak_motor: 0.0,
+    };
+
+    /// Creates a new rumble intensity with the weak motor intensity set to the given value,
+    /// clamped between `0.0` and `1.0`.
+    pub fn weak_motor(intensity: f32) -> Self {
+        Self {
+            weak_motor: intensity.clamp(0.0, 1.0),
+            strong_motor: 0.0,
+        }
+    }
+
+    /// Creates a new rumble intensity with the strong motor intensity set to the given value,
+    /// clamped between `0.0` and `1.0`.
+    pub fn strong_motor(intensity: f32) -> Self {
+        Self {
+            strong_motor: intensity.clamp(0.0, 1.0),
+            weak_motor: 0.0,
+        }
+    }
+}
+
+/// An event requesting a force-feedback rumble on a [`Gamepad`].
+///
+/// The rumbles are played by the gamepad backend, like the `bevy_gilrs` crate, and are stopped
+/// when the gamepad disconnects.
+///
+/// ```
+/// # use bevy_input::gamepad::{Gamepad, GamepadRumbleIntensity, GamepadRumbleRequest};
+/// # use bevy_ecs::event::EventWriter;
+/// # use bevy_utils::Duration;
+/// fn rumble_on_hit(mut rumble_requests: EventWriter<GamepadRumbleRequest>) {
+///     rumble_requests.send(GamepadRumbleRequest::Add {
+///         gamepad: Gamepad::new(0),
+///         intensity: GamepadRumbleIntensity::strong_motor(0.5),
+///         duration: Duration::from_millis(200),
+///     });
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum GamepadRumbleRequest {
+    /// Adds a rumble to the gamepad, in addition to the rumbles already playing on it.
+    ///
+    /// The intensities of the rumbles playing at the same time are added up.
+    Add {
+        /// How long the gamepad should rumble.
+        duration: Duration,
+        /// How intense the rumble should be.
+        intensity: GamepadRumbleIntensity,
+        /// The gamepad to rumble.
+        gamepad: Gamepad,
+    },
+    /// Stops all the rumbles playing on the gamepad.
+    Stop { gamepad: Gamepad },
+}
+
+impl GamepadRumbleRequest {
+    /// Returns the gamepad targeted by this request.
+    pub fn gamepad(&self) -> Gamepad {
+        match self {
+            Self::Add { gamepad, .. } | Self::Stop { gamepad } => *gamepad,
+        }
+    }
+}
+
 /// Monitors gamepad connection and disconnection events, updating the [`Gamepads`] resource accordingly
 ///
 /// By default, runs during `CoreStage::PreUpdate` when added via [`InputPlugin`](crate::InputPlugin).
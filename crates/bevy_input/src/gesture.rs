@@ -0,0 +1,426 @@
+use crate::touch::{TouchInput, TouchPhase};
+use bevy_ecs::{
+    event::{EventReader, EventWriter},
+    system::{Res, ResMut},
+};
+use bevy_math::Vec2;
+use bevy_utils::{Duration, HashMap, Instant};
+
+/// A gesture recognized from the [`TouchInput`] events by the [`touch_gesture_system`].
+///
+/// The positions are in the same coordinates as the positions of the [`TouchInput`] events.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TouchGesture {
+    /// A finger touched the screen and was lifted quickly, without moving.
+    Tap { position: Vec2 },
+    /// A second [`TouchGesture::Tap`] quickly followed a first one at the same position.
+    ///
+    /// This is sent after the [`TouchGesture::Tap`] event of the second tap.
+    DoubleTap { position: Vec2 },
+    /// A finger touched the screen for a while without moving.
+    LongPress { position: Vec2 },
+    /// Two fingers moved closer or further apart.
+    Pinch {
+        /// The ratio between the distance of the fingers and their distance at the previous
+        /// [`TouchGesture::Pinch`] event, or when the second finger touched the screen.
+        ///
+        /// This is smaller than `1.0` when the fingers move closer, to zoom out.
+        scale: f32,
+        /// The middle of the two fingers.
+        center: Vec2,
+    },
+    /// A finger moved quickly in a direction before being lifted.
+    Swipe {
+        direction: SwipeDirection,
+        /// The average velocity of the finger during the swipe, in units per second.
+        velocity: Vec2,
+    },
+}
+
+/// The main direction of a [`TouchGesture::Swipe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl SwipeDirection {
+    /// Returns the direction of the largest component of `delta`.
+    pub fn from_delta(delta: Vec2) -> Self {
+        if delta.x.abs() >= delta.y.abs() {
+            if delta.x >= 0.0 {
+                SwipeDirection::Right
+            } else {
+                SwipeDirection::Left
+            }
+        } else if delta.y >= 0.0 {
+            SwipeDirection::Up
+        } else {
+            SwipeDirection::Down
+        }
+    }
+}
+
+/// The thresholds used to recognize the [`TouchGesture`]s.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GestureSettings {
+    /// The longest a finger can touch the screen for a [`TouchGesture::Tap`].
+    pub tap_max_duration: Duration,
+    /// The farthest a finger can move for a [`TouchGesture::Tap`] or a
+    /// [`TouchGesture::LongPress`].
+    pub tap_max_distance: f32,
+    /// The longest time between two taps of a [`TouchGesture::DoubleTap`].
+    pub double_tap_max_interval: Duration,
+    /// How long a finger must touch the screen for a [`TouchGesture::LongPress`].
+    pub long_press_duration: Duration,
+    /// The shortest distance a finger must move for a [`TouchGesture::Swipe`].
+    pub swipe_min_distance: f32,
+    /// The slowest average speed of a finger for a [`TouchGesture::Swipe`], in units per second.
+    pub swipe_min_speed: f32,
+    /// The smallest change of the distance between two fingers, relative to that distance,
+    /// sending a [`TouchGesture::Pinch`].
+    pub pinch_min_scale_change: f32,
+}
+
+impl Default for GestureSettings {
+    fn default() -> Self {
+        Self {
+            tap_max_duration: Duration::from_millis(300),
+            tap_max_distance: 10.0,
+            double_tap_max_interval: Duration::from_millis(300),
+            long_press_duration: Duration::from_millis(500),
+            swipe_min_distance: 50.0,
+            swipe_min_speed: 200.0,
+            pinch_min_scale_change: 0.01,
+        }
+    }
+}
+
+/// A finger followed by the [`TouchGestures`] recognizer.
+#[derive(Debug, Clone, Copy)]
+struct TrackedTouch {
+    start_time: Instant,
+    start_position: Vec2,
+    position: Vec2,
+    /// Set when the finger can't be a tap or a long press anymore, because it moved too far or
+    /// already sent a long press.
+    recognized: bool,
+    /// Set when another finger touched the screen at the same time, so that this finger can
+    /// only be part of a pinch.
+    multi_touch: bool,
+}
+
+/// The state of the gesture recognizer, updated by the [`touch_gesture_system`].
+#[derive(Debug, Default)]
+pub struct TouchGestures {
+    touches: HashMap<u64, TrackedTouch>,
+    /// The time and position of the last tap, which may be the first tap of a double tap.
+    last_tap: Option<(Instant, Vec2)>,
+    /// The distance between the fingers at the last [`TouchGesture::Pinch`] event.
+    pinch_distance: Option<f32>,
+}
+
+impl TouchGestures {
+    /// Processes a [`TouchInput`] event received at `now`, pushing the gestures it completes
+    /// to `gestures`.
+    pub fn process_touch_event(
+        &mut self,
+        event: &TouchInput,
+        now: Instant,
+        settings: &GestureSettings,
+        gestures: &mut Vec<TouchGesture>,
+    ) {
+        match event.phase {
+            TouchPhase::Started => {
+                self.touches.insert(
+                    event.id,
+                    TrackedTouch {
+                        start_time: now,
+                        start_position: event.position,
+                        position: event.position,
+                        recognized: false,
+                        multi_touch: false,
+                    },
+                );
+                if self.touches.len() > 1 {
+                    for touch in self.touches.values_mut() {
+                        touch.multi_touch = true;
+                    }
+                }
+                self.pinch_distance = self.pinch_fingers().map(|(a, b)| a.distance(b));
+            }
+            TouchPhase::Moved => {
+                if let Some(touch) = self.touches.get_mut(&event.id) {
+                    touch.position = event.position;
+                    if touch.start_position.distance(touch.position) > settings.tap_max_distance {
+                        // The finger may still be swiping, which is checked when it is lifted
+                        touch.recognized = true;
+                    }
+                }
+                self.recognize_pinch(settings, gestures);
+            }
+            TouchPhase::Ended => {
+                let touch = match self.touches.remove(&event.id) {
+                    Some(touch) => touch,
+                    None => return,
+                };
+                self.pinch_distance = self.pinch_fingers().map(|(a, b)| a.distance(b));
+                if touch.multi_touch {
+                    return;
+                }
+
+                let delta = event.position - touch.start_position;
+                let duration = now.duration_since(touch.start_time);
+                if !touch.recognized && duration <= settings.tap_max_duration {
+                    gestures.push(TouchGesture::Tap {
+                        position: event.position,
+                    });
+                    match self.last_tap.take() {
+                        Some((time, position))
+                            if now.duration_since(time) <= settings.double_tap_max_interval
+                                && position.distance(event.position)
+                                    <= settings.tap_max_distance =>
+                        {
+                            gestures.push(TouchGesture::DoubleTap {
+                                position: event.position,
+                            });
+                        }
+                        _ => self.last_tap = Some((now, event.position)),
+                    }
+                } else if delta.length() >= settings.swipe_min_distance {
+                    let velocity = delta / duration.as_secs_f32().max(f32::EPSILON);
+                    if velocity.length() >= settings.swipe_min_speed {
+                        gestures.push(TouchGesture::Swipe {
+                            direction: SwipeDirection::from_delta(delta),
+                            velocity,
+                        });
+                    }
+                }
+            }
+            TouchPhase::Cancelled => {
+                self.touches.remove(&event.id);
+                self.pinch_distance = self.pinch_fingers().map(|(a, b)| a.distance(b));
+            }
+        }
+    }
+
+    /// Recognizes the gestures depending only on time, like [`TouchGesture::LongPress`],
+    /// pushing them to `gestures`.
+    pub fn update(
+        &mut self,
+        now: Instant,
+        settings: &GestureSettings,
+        gestures: &mut Vec<TouchGesture>,
+    ) {
+        for touch in self.touches.values_mut() {
+            if !touch.recognized
+                && !touch.multi_touch
+                && now.duration_since(touch.start_time) >= settings.long_press_duration
+            {
+                touch.recognized = true;
+                gestures.push(TouchGesture::LongPress {
+                    position: touch.position,
+                });
+            }
+        }
+    }
+
+    /// Returns the positions of the fingers if exactly two fingers touch the screen.
+    fn pinch_fingers(&self) -> Option<(Vec2, Vec2)> {
+        if self.touches.len() != 2 {
+            return None;
+        }
+        let mut touches = self.touches.values();
+        Some((touches.next()?.position, touches.next()?.position))
+    }
+
+    fn recognize_pinch(&mut self, settings: &GestureSettings, gestures: &mut Vec<TouchGesture>) {
+        let (previous_distance, (a, b)) = match (self.pinch_distance, self.pinch_fingers()) {
+            (Some(previous_distance), Some(fingers)) => (previous_distance, fingers),
+            _ => return,
+        };
+        let distance = a.distance(b);
+        if previous_distance <= 0.0 {
+            self.pinch_distance = Some(distance);
+            return;
+        }
+        let scale = distance / previous_distance;
+        if (scale - 1.0).abs() >= settings.pinch_min_scale_change {
+            self.pinch_distance = Some(distance);
+            gestures.push(TouchGesture::Pinch {
+                scale,
+                center: (a + b) / 2.0,
+            });
+        }
+    }
+}
+
+/// Recognizes the [`TouchGesture`]s from the [`TouchInput`] events, with the thresholds of the
+/// [`GestureSettings`] resource.
+pub fn touch_gesture_system(
+    settings: Res<GestureSettings>,
+    mut touch_gestures: ResMut<TouchGestures>,
+    mut touch_input_events: EventReader<TouchInput>,
+    mut gesture_events: EventWriter<TouchGesture>,
+) {
+    let now = Instant::now();
+    let mut gestures = Vec::new();
+    for event in touch_input_events.iter() {
+        touch_gestures.process_touch_event(event, now, &settings, &mut gestures);
+    }
+    touch_gestures.update(now, &settings, &mut gestures);
+    gesture_events.send_batch(gestures.into_iter());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GestureSettings, SwipeDirection, TouchGesture, TouchGestures};
+    use crate::touch::{TouchInput, TouchPhase};
+    use bevy_math::Vec2;
+    use bevy_utils::{Duration, Instant};
+
+    struct TestTouches {
+        gestures: TouchGestures,
+        settings: GestureSettings,
+        start: Instant,
+    }
+
+    impl TestTouches {
+        fn new() -> Self {
+            Self {
+                gestures: TouchGestures::default(),
+                settings: GestureSettings::default(),
+                start: Instant::now(),
+            }
+        }
+
+        fn send(
+            &mut self,
+            millis: u64,
+            id: u64,
+            phase: TouchPhase,
+            x: f32,
+            y: f32,
+        ) -> Vec<TouchGesture> {
+            let event = TouchInput {
+                phase,
+                position: Vec2::new(x, y),
+                force: None,
+                id,
+            };
+            let now = self.start + Duration::from_millis(millis);
+            let mut gestures = Vec::new();
+            self.gestures
+                .process_touch_event(&event, now, &self.settings, &mut gestures);
+            self.gestures.update(now, &self.settings, &mut gestures);
+            gestures
+        }
+    }
+
+    #[test]
+    fn tap_and_double_tap() {
+        let mut touches = TestTouches::new();
+        assert!(touches
+            .send(0, 0, TouchPhase::Started, 10.0, 10.0)
+            .is_empty());
+        assert_eq!(
+            touches.send(100, 0, TouchPhase::Ended, 12.0, 10.0),
+            vec![TouchGesture::Tap {
+                position: Vec2::new(12.0, 10.0)
+            }]
+        );
+        touches.send(200, 1, TouchPhase::Started, 10.0, 10.0);
+        assert_eq!(
+            touches.send(250, 1, TouchPhase::Ended, 10.0, 10.0),
+            vec![
+                TouchGesture::Tap {
+                    position: Vec2::new(10.0, 10.0)
+                },
+                TouchGesture::DoubleTap {
+                    position: Vec2::new(10.0, 10.0)
+                }
+            ]
+        );
+
+        // Too slow for a tap
+        touches.send(1000, 0, TouchPhase::Started, 10.0, 10.0);
+        assert!(touches
+            .send(1400, 0, TouchPhase::Ended, 10.0, 10.0)
+            .is_empty());
+    }
+
+    #[test]
+    fn long_press() {
+        let mut touches = TestTouches::new();
+        touches.send(0, 0, TouchPhase::Started, 10.0, 10.0);
+        assert!(touches
+            .send(400, 0, TouchPhase::Moved, 11.0, 10.0)
+            .is_empty());
+        assert_eq!(
+            touches.send(500, 0, TouchPhase::Moved, 11.0, 10.0),
+            vec![TouchGesture::LongPress {
+                position: Vec2::new(11.0, 10.0)
+            }]
+        );
+        assert!(touches
+            .send(600, 0, TouchPhase::Moved, 11.0, 10.0)
+            .is_empty());
+        assert!(touches
+            .send(700, 0, TouchPhase::Ended, 11.0, 10.0)
+            .is_empty());
+    }
+
+    #[test]
+    fn swipe() {
+        let mut touches = TestTouches::new();
+        touches.send(0, 0, TouchPhase::Started, 0.0, 0.0);
+        touches.send(100, 0, TouchPhase::Moved, 0.0, -50.0);
+        assert_eq!(
+            touches.send(200, 0, TouchPhase::Ended, 0.0, -100.0),
+            vec![TouchGesture::Swipe {
+                direction: SwipeDirection::Down,
+                velocity: Vec2::new(0.0, -500.0)
+            }]
+        );
+
+        // Too slow for a swipe
+        touches.send(1000, 0, TouchPhase::Started, 0.0, 0.0);
+        touches.send(1500, 0, TouchPhase::Moved, 50.0, 0.0);
+        assert!(touches
+            .send(2000, 0, TouchPhase::Ended, 100.0, 0.0)
+            .is_empty());
+    }
+
+    #[test]
+    fn pinch() {
+        let mut touches = TestTouches::new();
+        touches.send(0, 0, TouchPhase::Started, 0.0, 0.0);
+        touches.send(10, 1, TouchPhase::Started, 100.0, 0.0);
+        assert_eq!(
+            touches.send(20, 1, TouchPhase::Moved, 200.0, 0.0),
+            vec![TouchGesture::Pinch {
+                scale: 2.0,
+                center: Vec2::new(100.0, 0.0)
+            }]
+        );
+        assert_eq!(
+            touches.send(30, 0, TouchPhase::Moved, 100.0, 0.0),
+            vec![TouchGesture::Pinch {
+                scale: 0.5,
+                center: Vec2::new(150.0, 0.0)
+            }]
+        );
+
+        // Lifting the fingers of a pinch isn't a tap, nor a long press
+        assert!(touches
+            .send(1000, 1, TouchPhase::Ended, 200.0, 0.0)
+            .is_empty());
+        assert!(touches
+            .send(1010, 0, TouchPhase::Ended, 100.0, 0.0)
+            .is_empty());
+    }
+}
@@ -1,5 +1,7 @@
+pub mod action;
 mod axis;
 pub mod gamepad;
+pub mod gesture;
 mod input;
 pub mod keyboard;
 pub mod mouse;
@@ -12,10 +14,12 @@ pub use input::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
+        action::{ActionMap, ActionPlugin, ActionState, InputBinding},
         gamepad::{
             Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, GamepadEvent,
-            GamepadEventType, Gamepads,
+            GamepadEventType, GamepadRumbleIntensity, GamepadRumbleRequest, Gamepads,
         },
+        gesture::{SwipeDirection, TouchGesture},
         keyboard::KeyCode,
         mouse::MouseButton,
         touch::{TouchInput, Touches},
@@ -24,6 +28,7 @@ pub mod prelude {
 }
 
 use bevy_app::prelude::*;
+use gesture::{touch_gesture_system, GestureSettings, TouchGesture, TouchGestures};
 use keyboard::{keyboard_input_system, KeyCode, KeyboardInput};
 use mouse::{mouse_button_input_system, MouseButton, MouseButtonInput, MouseMotion, MouseWheel};
 use prelude::Gamepads;
@@ -31,7 +36,7 @@ use touch::{touch_screen_input_system, TouchInput, Touches};
 
 use gamepad::{
     gamepad_connection_system, gamepad_event_system, GamepadAxis, GamepadButton, GamepadEvent,
-    GamepadEventRaw, GamepadSettings,
+    GamepadEventRaw, GamepadRumbleRequest, GamepadSettings,
 };
 
 /// Adds keyboard and mouse input to an App
@@ -63,6 +68,7 @@ impl Plugin for InputPlugin {
             // gamepad
             .add_event::<GamepadEvent>()
             .add_event::<GamepadEventRaw>()
+            .add_event::<GamepadRumbleRequest>()
             .init_resource::<GamepadSettings>()
             .init_resource::<Gamepads>()
             .init_resource::<Input<GamepadButton>>()
@@ -82,6 +88,14 @@ impl Plugin for InputPlugin {
             .add_system_to_stage(
                 CoreStage::PreUpdate,
                 touch_screen_input_system.label(InputSystem),
+            )
+            // gestures
+            .add_event::<TouchGesture>()
+            .init_resource::<GestureSettings>()
+            .init_resource::<TouchGestures>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                touch_gesture_system.label(InputSystem),
             );
     }
 }
@@ -16,6 +16,8 @@ use bevy_app::{PluginGroup, PluginGroupBuilder};
 /// * [`PbrPlugin`](bevy_pbr::PbrPlugin) - with feature `bevy_pbr`
 /// * [`UiPlugin`](bevy_ui::UiPlugin) - with feature `bevy_ui`
 /// * [`TextPlugin`](bevy_text::TextPlugin) - with feature `bevy_text`
+/// * [`PickingPlugin`](bevy_picking::PickingPlugin) - with feature `bevy_picking`
+/// * [`GizmoPlugin`](bevy_gizmos::GizmoPlugin) - with feature `bevy_gizmos`
 /// * [`AudioPlugin`](bevy_audio::AudioPlugin) - with feature `bevy_audio`
 /// * [`GilrsPlugin`](bevy_gilrs::GilrsPlugin) - with feature `bevy_gilrs`
 /// * [`GltfPlugin`](bevy_gltf::GltfPlugin) - with feature `bevy_gltf`
@@ -60,6 +62,12 @@ impl PluginGroup for DefaultPlugins {
         #[cfg(feature = "bevy_pbr")]
         group.add(bevy_pbr::PbrPlugin::default());
 
+        #[cfg(feature = "bevy_picking")]
+        group.add(bevy_picking::PickingPlugin::default());
+
+        #[cfg(feature = "bevy_gizmos")]
+        group.add(bevy_gizmos::GizmoPlugin::default());
+
         // NOTE: Load this after renderer initialization so that it knows about the supported
         // compressed texture formats
         #[cfg(feature = "bevy_gltf")]
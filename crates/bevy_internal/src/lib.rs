@@ -131,6 +131,18 @@ pub mod pbr {
     pub use bevy_pbr::*;
 }
 
+#[cfg(feature = "bevy_gizmos")]
+pub mod gizmos {
+    //! Immediate mode drawing of debug shapes.
+    pub use bevy_gizmos::*;
+}
+
+#[cfg(feature = "bevy_picking")]
+pub mod picking {
+    //! Picking of the meshes, sprites and UI nodes under the cursor.
+    pub use bevy_picking::*;
+}
+
 #[cfg(feature = "bevy_render")]
 pub mod render {
     //! Cameras, meshes, textures, shaders, and pipelines.
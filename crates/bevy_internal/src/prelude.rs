@@ -24,6 +24,14 @@ pub use crate::core_pipeline::prelude::*;
 #[cfg(feature = "bevy_pbr")]
 pub use crate::pbr::prelude::*;
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_gizmos")]
+pub use crate::gizmos::prelude::*;
+
+#[doc(hidden)]
+#[cfg(feature = "bevy_picking")]
+pub use crate::picking::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_render")]
 pub use crate::render::prelude::*;
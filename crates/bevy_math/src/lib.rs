@@ -6,12 +6,16 @@
 
 #![warn(missing_docs)]
 
+mod ray;
+
+pub use ray::Ray;
+
 /// The `bevy_math` prelude.
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        BVec2, BVec3, BVec4, EulerRot, IVec2, IVec3, IVec4, Mat3, Mat4, Quat, UVec2, UVec3, UVec4,
-        Vec2, Vec3, Vec4,
+        BVec2, BVec3, BVec4, EulerRot, IVec2, IVec3, IVec4, Mat3, Mat4, Quat, Ray, UVec2, UVec3,
+        UVec4, Vec2, Vec3, Vec4,
     };
 }
 
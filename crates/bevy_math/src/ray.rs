@@ -0,0 +1,56 @@
+use crate::Vec3;
+
+/// A ray is an infinite line starting at `origin`, going in `direction`.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    /// The origin of the ray.
+    pub origin: Vec3,
+    /// The direction of the ray.
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Returns the distance along the ray at which it intersects the plane going through
+    /// `plane_origin` with the normal `plane_normal`, if it intersects it in front of its origin.
+    #[inline]
+    pub fn intersect_plane(&self, plane_origin: Vec3, plane_normal: Vec3) -> Option<f32> {
+        let denominator = plane_normal.dot(self.direction);
+        if denominator.abs() > f32::EPSILON {
+            let distance = (plane_origin - self.origin).dot(plane_normal) / denominator;
+            if distance > f32::EPSILON {
+                return Some(distance);
+            }
+        }
+        None
+    }
+
+    /// Returns the point at the given distance along the ray.
+    #[inline]
+    pub fn get_point(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersect_plane() {
+        let ray = Ray {
+            origin: Vec3::ZERO,
+            direction: Vec3::Z,
+        };
+
+        // Orthogonal, and in front of the ray
+        assert_eq!(ray.intersect_plane(Vec3::Z, Vec3::Z), Some(1.0));
+        assert_eq!(ray.intersect_plane(Vec3::Z, Vec3::NEG_Z), Some(1.0));
+        assert_eq!(ray.get_point(1.0), Vec3::Z);
+
+        // Behind the ray
+        assert_eq!(ray.intersect_plane(Vec3::NEG_Z, Vec3::Z), None);
+
+        // Parallel to the ray
+        assert_eq!(ray.intersect_plane(Vec3::X, Vec3::X), None);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{DirectionalLight, Material, PointLight, SpotLight, StandardMaterial};
+use crate::{Cascades, DirectionalLight, Material, PointLight, SpotLight, StandardMaterial};
 use bevy_asset::Handle;
 use bevy_ecs::{bundle::Bundle, component::Component, reflect::ReflectComponent};
 use bevy_reflect::Reflect;
@@ -91,6 +91,7 @@ pub struct SpotLightBundle {
 #[derive(Debug, Bundle, Default)]
 pub struct DirectionalLightBundle {
     pub directional_light: DirectionalLight,
+    pub cascades: Cascades,
     pub frustum: Frustum,
     pub visible_entities: VisibleEntities,
     pub transform: Transform,
@@ -125,6 +125,7 @@ impl Plugin for PbrPlugin {
 
         app.register_type::<CubemapVisibleEntities>()
             .register_type::<DirectionalLight>()
+            .register_type::<Cascades>()
             .register_type::<PointLight>()
             .register_type::<SpotLight>()
             .add_plugin(MeshRenderPlugin)
@@ -153,11 +154,19 @@ impl Plugin for PbrPlugin {
                     .after(CameraUpdateSystem)
                     .after(ModifiesWindows),
             )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_directional_light_cascades
+                    .label(SimulationLightSystems::UpdateDirectionalLightCascades)
+                    .after(TransformSystem::TransformPropagate)
+                    .after(CameraUpdateSystem),
+            )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 update_directional_light_frusta
                     .label(SimulationLightSystems::UpdateLightFrusta)
-                    .after(TransformSystem::TransformPropagate),
+                    .after(TransformSystem::TransformPropagate)
+                    .after(SimulationLightSystems::UpdateDirectionalLightCascades),
             )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
@@ -13,12 +13,12 @@ use bevy_render::{
     view::{ComputedVisibility, RenderLayers, Visibility, VisibleEntities},
 };
 use bevy_transform::components::GlobalTransform;
-use bevy_utils::tracing::warn;
+use bevy_utils::{tracing::warn, HashMap};
 
 use crate::{
     calculate_cluster_factors, spot_light_projection_matrix, spot_light_view_matrix, CubeMapFace,
     CubemapVisibleEntities, ViewClusterBindings, CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
-    CUBE_MAP_FACES, MAX_UNIFORM_BUFFER_POINT_LIGHTS, POINT_LIGHT_NEAR_Z,
+    CUBE_MAP_FACES, MAX_CASCADES_PER_LIGHT, MAX_UNIFORM_BUFFER_POINT_LIGHTS, POINT_LIGHT_NEAR_Z,
 };
 
 /// A light that emits light in all directions from a central point.
@@ -176,6 +176,18 @@ pub struct DirectionalLight {
     /// A bias applied along the direction of the fragment's surface normal. It is scaled to the
     /// shadow map's texel size so that it is automatically adjusted to the orthographic projection.
     pub shadow_normal_bias: f32,
+    /// The number of cascades the view frustum of each camera is split into, each with its own
+    /// shadow map, so that shadows are sharp close to the camera and still cast far away. With a
+    /// single cascade, shadows are cast in the fixed `shadow_projection` instead. At most
+    /// [`MAX_CASCADES_PER_LIGHT`] cascades are used.
+    pub shadow_cascade_count: usize,
+    /// How the distances between the cascades are distributed, from `0.0` for uniform distances
+    /// to `1.0` for logarithmic distances, which give more resolution close to the camera.
+    pub shadow_cascade_lambda: f32,
+    /// The distance from the camera up to which shadows are cast with several cascades.
+    pub shadow_cascade_max_distance: f32,
+    /// Tints the surfaces lit by the light with a color per cascade, to tune the cascades.
+    pub debug_shadow_cascades: bool,
 }
 
 impl Default for DirectionalLight {
@@ -196,6 +208,10 @@ impl Default for DirectionalLight {
             },
             shadow_depth_bias: Self::DEFAULT_SHADOW_DEPTH_BIAS,
             shadow_normal_bias: Self::DEFAULT_SHADOW_NORMAL_BIAS,
+            shadow_cascade_count: 1,
+            shadow_cascade_lambda: 0.8,
+            shadow_cascade_max_distance: size,
+            debug_shadow_cascades: false,
         }
     }
 }
@@ -205,6 +221,32 @@ impl DirectionalLight {
     pub const DEFAULT_SHADOW_NORMAL_BIAS: f32 = 0.6;
 }
 
+/// The shadow map cascades of a [`DirectionalLight`] for each camera, updated by
+/// [`update_directional_light_cascades`].
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Cascades {
+    /// The cascades of each camera, from the closest to the camera to the furthest
+    #[reflect(ignore)]
+    pub cascades: HashMap<Entity, Vec<Cascade>>,
+}
+
+/// The orthographic projection of the shadow map of a [`DirectionalLight`] used for the part of
+/// the view frustum of a camera up to `far_bound`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Cascade {
+    /// The transform from the space of the shadow map to world space
+    pub view_transform: Mat4,
+    /// The orthographic projection of the shadow map
+    pub projection: Mat4,
+    /// The transform from world space to the clip space of the shadow map
+    pub view_projection: Mat4,
+    /// The distance from the camera up to which the cascade is used
+    pub far_bound: f32,
+    /// The size of a texel of the shadow map in world units
+    pub texel_size: f32,
+}
+
 #[derive(Clone, Debug, Reflect)]
 #[reflect(Resource)]
 pub struct DirectionalLightShadowMap {
@@ -251,6 +293,7 @@ pub struct NotShadowReceiver;
 pub enum SimulationLightSystems {
     AddClusters,
     AssignLightsToClusters,
+    UpdateDirectionalLightCascades,
     UpdateLightFrusta,
     CheckLightVisibility,
 }
@@ -1409,18 +1452,202 @@ fn project_to_plane_y(y_light: Sphere, y_plane: Plane, is_orthographic: bool) ->
     })
 }
 
+/// Returns the distances from the camera of the far bounds of `count` cascades splitting the view
+/// frustum between `near` and `far`, blending uniform and logarithmic distances with `lambda`.
+fn cascade_far_bounds(near: f32, far: f32, count: usize, lambda: f32) -> Vec<f32> {
+    (1..=count)
+        .map(|i| {
+            let fraction = i as f32 / count as f32;
+            let uniform = near + (far - near) * fraction;
+            let logarithmic = near * (far / near).powf(fraction);
+            lambda * logarithmic + (1.0 - lambda) * uniform
+        })
+        .collect()
+}
+
+/// Returns the corners of the part of the view frustum between the distances `near` and `far`
+/// from the camera, in the view space of the camera.
+fn frustum_slice_corners(
+    inverse_projection: &Mat4,
+    is_orthographic: bool,
+    near: f32,
+    far: f32,
+) -> [Vec3; 8] {
+    let mut corners = [Vec3::ZERO; 8];
+    for (i, ndc) in [
+        Vec2::new(-1.0, -1.0),
+        Vec2::new(1.0, -1.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(-1.0, 1.0),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        // NOTE: The near plane is at a depth of 1.0 with reversed z
+        let near_plane_corner = inverse_projection.project_point3(ndc.extend(1.0));
+        for (j, distance) in [near, far].into_iter().enumerate() {
+            corners[i + 4 * j] = if is_orthographic {
+                near_plane_corner.truncate().extend(-distance)
+            } else {
+                near_plane_corner * (distance / -near_plane_corner.z)
+            };
+        }
+    }
+    corners
+}
+
+impl DirectionalLight {
+    /// Computes the single cascade in the fixed `shadow_projection`, used by lights with a single
+    /// cascade or without [`Cascades`].
+    pub(crate) fn fixed_cascade(
+        &self,
+        light_transform: &GlobalTransform,
+        shadow_map_size: usize,
+    ) -> Cascade {
+        // NOTE: A directional light seems to have to have an eye position on the line along the direction of the light
+        // through the world origin. I (Rob Swain) do not yet understand why it cannot be translated away from this.
+        let view = Mat4::look_at_rh(Vec3::ZERO, light_transform.forward(), Vec3::Y);
+        // NOTE: This orthographic projection defines the volume within which shadows from a directional light can be cast
+        let projection = self.shadow_projection.get_projection_matrix();
+        // Calulate the directional light shadow map texel size using the largest x,y dimension of
+        // the orthographic projection divided by the shadow map resolution
+        // NOTE: When using various PCF kernel sizes, this will need to be adjusted, according to:
+        // https://catlikecoding.com/unity/tutorials/custom-srp/directional-shadows/
+        let largest_dimension = (self.shadow_projection.right - self.shadow_projection.left)
+            .max(self.shadow_projection.top - self.shadow_projection.bottom);
+        Cascade {
+            view_transform: view.inverse(),
+            projection,
+            // NOTE: * view is correct, it should not be view.inverse() here
+            view_projection: projection * view,
+            far_bound: f32::MAX,
+            texel_size: largest_dimension / shadow_map_size as f32,
+        }
+    }
+
+    /// Computes the cascades of the shadow maps of the light for a camera.
+    fn cascades(
+        &self,
+        light_transform: &GlobalTransform,
+        camera_transform: &GlobalTransform,
+        camera_projection: &Mat4,
+        shadow_map_size: usize,
+    ) -> Vec<Cascade> {
+        if self.shadow_cascade_count <= 1 {
+            return vec![self.fixed_cascade(light_transform, shadow_map_size)];
+        }
+
+        let is_orthographic = camera_projection.w_axis.w == 1.0;
+        let inverse_projection = camera_projection.inverse();
+        let camera_near = -inverse_projection.project_point3(Vec3::Z).z;
+        let near = camera_near.max(f32::EPSILON);
+        let far = self.shadow_cascade_max_distance.max(near);
+
+        let light_to_world = Mat4::from_quat(light_transform.rotation);
+        let world_to_light = light_to_world.inverse();
+        let camera_to_light = world_to_light * camera_transform.compute_matrix();
+
+        let mut near_bound = camera_near;
+        cascade_far_bounds(
+            near,
+            far,
+            self.shadow_cascade_count.min(MAX_CASCADES_PER_LIGHT),
+            self.shadow_cascade_lambda,
+        )
+        .into_iter()
+        .map(|far_bound| {
+            let corners =
+                frustum_slice_corners(&inverse_projection, is_orthographic, near_bound, far_bound)
+                    .map(|corner| camera_to_light.transform_point3(corner));
+            near_bound = far_bound;
+
+            // Fit the cascade to the bounding sphere of the part of the frustum, so that its size
+            // doesn't change when the camera rotates, and move it by whole texels so that the
+            // shadows don't flicker when the camera moves
+            let mut center =
+                corners.iter().fold(Vec3::ZERO, |sum, corner| sum + *corner) / corners.len() as f32;
+            let radius = corners
+                .iter()
+                .map(|corner| corner.distance(center))
+                .fold(0.0, f32::max);
+            let radius = (radius * 16.0).ceil() / 16.0;
+            let texel_size = 2.0 * radius / shadow_map_size as f32;
+            center.x = (center.x / texel_size).floor() * texel_size;
+            center.y = (center.y / texel_size).floor() * texel_size;
+
+            // NOTE: near and far are swapped to invert the depth range from [0,1] to [1,0]. The
+            // shadow casters between the light and the near plane are clamped to the near plane
+            // when rendering the shadow map.
+            let projection = Mat4::orthographic_rh(
+                center.x - radius,
+                center.x + radius,
+                center.y - radius,
+                center.y + radius,
+                radius - center.z,
+                -radius - center.z,
+            );
+            Cascade {
+                view_transform: light_to_world,
+                projection,
+                view_projection: projection * world_to_light,
+                far_bound,
+                texel_size,
+            }
+        })
+        .collect()
+    }
+}
+
+pub fn update_directional_light_cascades(
+    directional_light_shadow_map: Res<DirectionalLightShadowMap>,
+    views: Query<(Entity, &GlobalTransform, &Camera)>,
+    mut lights: Query<(
+        &GlobalTransform,
+        &DirectionalLight,
+        &mut Cascades,
+        &Visibility,
+    )>,
+) {
+    for (transform, directional_light, mut cascades, visibility) in lights.iter_mut() {
+        cascades.cascades.clear();
+        if !directional_light.shadows_enabled || !visibility.is_visible {
+            continue;
+        }
+
+        for (view_entity, view_transform, camera) in views.iter() {
+            if !camera.is_active {
+                continue;
+            }
+            cascades.cascades.insert(
+                view_entity,
+                directional_light.cascades(
+                    transform,
+                    view_transform,
+                    &camera.projection_matrix(),
+                    directional_light_shadow_map.size,
+                ),
+            );
+        }
+    }
+}
+
 pub fn update_directional_light_frusta(
     mut views: Query<
         (
             &GlobalTransform,
             &DirectionalLight,
+            Option<&Cascades>,
             &mut Frustum,
             &Visibility,
         ),
-        Or<(Changed<GlobalTransform>, Changed<DirectionalLight>)>,
+        Or<(
+            Changed<GlobalTransform>,
+            Changed<DirectionalLight>,
+            Changed<Cascades>,
+        )>,
     >,
 ) {
-    for (transform, directional_light, mut frustum, visibility) in views.iter_mut() {
+    for (transform, directional_light, cascades, mut frustum, visibility) in views.iter_mut() {
         // The frustum is used for culling meshes to the light for shadow mapping
         // so if shadow mapping is disabled for this light, then the frustum is
         // not needed.
@@ -1428,14 +1655,47 @@ pub fn update_directional_light_frusta(
             continue;
         }
 
-        let view_projection = directional_light.shadow_projection.get_projection_matrix()
-            * transform.compute_matrix().inverse();
-        *frustum = Frustum::from_view_projection(
-            &view_projection,
-            &transform.translation,
-            &transform.back(),
-            directional_light.shadow_projection.far(),
-        );
+        // Lights without cascades use the fixed projection, like lights with a single cascade
+        let cascades = match cascades {
+            Some(cascades) if directional_light.shadow_cascade_count > 1 => cascades,
+            _ => {
+                let view_projection = directional_light.shadow_projection.get_projection_matrix()
+                    * transform.compute_matrix().inverse();
+                *frustum = Frustum::from_view_projection(
+                    &view_projection,
+                    &transform.translation,
+                    &transform.back(),
+                    directional_light.shadow_projection.far(),
+                );
+                continue;
+            }
+        };
+
+        // Cull to the box around the cascades of all the cameras, in the space of the light
+        let world_to_light = Mat4::from_quat(transform.rotation).inverse();
+        let mut min = Vec3::splat(f32::MAX);
+        let mut max = Vec3::splat(f32::MIN);
+        for cascade in cascades.cascades.values().flatten() {
+            let cascade_to_light = world_to_light * cascade.view_transform;
+            let inverse_projection = cascade.projection.inverse();
+            for corner in [Vec3::new(-1.0, -1.0, 0.0), Vec3::new(1.0, 1.0, 1.0)] {
+                let corner =
+                    cascade_to_light.transform_point3(inverse_projection.project_point3(corner));
+                min = min.min(corner);
+                max = max.max(corner);
+            }
+        }
+        if min.x > max.x {
+            continue;
+        }
+
+        let view_projection =
+            Mat4::orthographic_rh(min.x, max.x, min.y, max.y, -min.z, -max.z) * world_to_light;
+        let mut light_frustum =
+            Frustum::from_view_projection(&view_projection, &Vec3::ZERO, &transform.back(), -min.z);
+        // The shadow casters between the light and the cascades cast shadows in them
+        light_frustum.planes[4] = Plane::new(transform.forward().extend(f32::MAX));
+        *frustum = light_frustum;
     }
 }
 
@@ -1740,6 +2000,55 @@ pub fn check_light_mesh_visibility(
 mod test {
     use super::*;
 
+    #[test]
+    fn cascade_far_bounds_distribution() {
+        assert_eq!(cascade_far_bounds(1.0, 100.0, 2, 0.0), vec![50.5, 100.0]);
+        assert_eq!(cascade_far_bounds(1.0, 100.0, 2, 1.0), vec![10.0, 100.0]);
+    }
+
+    #[test]
+    fn cascades_cover_view_frustum() {
+        let light = DirectionalLight {
+            shadow_cascade_count: 4,
+            shadow_cascade_max_distance: 50.0,
+            ..Default::default()
+        };
+        let light_transform = GlobalTransform::from_rotation(Quat::from_euler(
+            bevy_math::EulerRot::XYZ,
+            -1.0,
+            0.4,
+            0.0,
+        ));
+        let camera_transform = GlobalTransform::from_xyz(3.0, 2.0, 5.0)
+            .looking_at(Vec3::new(-4.0, 0.0, -8.0), Vec3::Y);
+        let camera_projection =
+            Mat4::perspective_infinite_reverse_rh(std::f32::consts::FRAC_PI_4, 1.5, 0.1);
+
+        let cascades = light.cascades(
+            &light_transform,
+            &camera_transform,
+            &camera_projection,
+            1024,
+        );
+        assert_eq!(cascades.len(), 4);
+        assert_eq!(cascades[3].far_bound, 50.0);
+
+        let inverse_projection = camera_projection.inverse();
+        let mut near_bound = 0.1;
+        for cascade in &cascades {
+            assert!(cascade.far_bound > near_bound);
+            for corner in
+                frustum_slice_corners(&inverse_projection, false, near_bound, cascade.far_bound)
+            {
+                let world_corner = camera_transform.compute_matrix().transform_point3(corner);
+                let ndc = cascade.view_projection.project_point3(world_corner);
+                assert!(ndc.x.abs() <= 1.0 && ndc.y.abs() <= 1.0, "{:?}", ndc);
+                assert!((0.0..=1.0).contains(&ndc.z), "{:?}", ndc);
+            }
+            near_bound = cascade.far_bound;
+        }
+    }
+
     fn test_cluster_tiling(config: ClusterConfig, screen_size: UVec2) -> Clusters {
         let dims = config.dimensions_for_screen_size(screen_size);
 
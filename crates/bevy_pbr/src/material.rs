@@ -4,7 +4,7 @@ use crate::{
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::{AddAsset, AssetEvent, AssetServer, Assets, Handle};
-use bevy_core_pipeline::core_3d::{AlphaMask3d, Opaque3d, Transparent3d};
+use bevy_core_pipeline::core_3d::{AlphaMask3d, Camera3d, Opaque3d, Transparent3d};
 use bevy_ecs::{
     entity::Entity,
     event::EventReader,
@@ -325,6 +325,7 @@ pub fn queue_material_meshes<M: Material>(
     mut views: Query<(
         &ExtractedView,
         &VisibleEntities,
+        Option<&Camera3d>,
         &mut RenderPhase<Opaque3d>,
         &mut RenderPhase<AlphaMask3d>,
         &mut RenderPhase<Transparent3d>,
@@ -332,8 +333,14 @@ pub fn queue_material_meshes<M: Material>(
 ) where
     M::Data: PartialEq + Eq + Hash + Clone,
 {
-    for (view, visible_entities, mut opaque_phase, mut alpha_mask_phase, mut transparent_phase) in
-        views.iter_mut()
+    for (
+        view,
+        visible_entities,
+        camera_3d,
+        mut opaque_phase,
+        mut alpha_mask_phase,
+        mut transparent_phase,
+    ) in views.iter_mut()
     {
         let draw_opaque_pbr = opaque_draw_functions
             .read()
@@ -349,7 +356,8 @@ pub fn queue_material_meshes<M: Material>(
             .unwrap();
 
         let rangefinder = view.rangefinder3d();
-        let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples);
+        let view_key = MeshPipelineKey::from_msaa_samples(msaa.samples)
+            | MeshPipelineKey::from_hdr(camera_3d.map_or(false, |camera_3d| camera_3d.hdr));
 
         for visible_entity in &visible_entities.entities {
             if let Ok((material_handle, mesh_handle, mesh_uniform)) =
@@ -359,7 +367,7 @@ pub fn queue_material_meshes<M: Material>(
                     if let Some(mesh) = render_meshes.get(mesh_handle) {
                         let mut mesh_key =
                             MeshPipelineKey::from_primitive_topology(mesh.primitive_topology)
-                                | msaa_key;
+                                | view_key;
                         let alpha_mode = material.properties.alpha_mode;
                         if let AlphaMode::Blend = alpha_mode {
                             mesh_key |= MeshPipelineKey::TRANSPARENT_MAIN_PASS;
@@ -38,5 +38,10 @@ fn vertex(vertex: Vertex) -> VertexOutput {
 
     var out: VertexOutput;
     out.clip_position = mesh_position_local_to_clip(model, vec4<f32>(vertex.position, 1.0));
+#ifdef DEPTH_CLAMP_ORTHO
+    // Clamp the shadow casters in front of the near plane of the orthographic projection to the
+    // near plane, which is at a depth of 1.0 with reversed z, instead of clipping them
+    out.clip_position.z = min(out.clip_position.z, 1.0);
+#endif
     return out;
 }
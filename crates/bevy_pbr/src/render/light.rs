@@ -1,8 +1,8 @@
 use crate::{
-    point_light_order, AmbientLight, Clusters, CubemapVisibleEntities, DirectionalLight,
-    DirectionalLightShadowMap, DrawMesh, GlobalVisiblePointLights, MeshPipeline, NotShadowCaster,
-    PointLight, PointLightShadowMap, SetMeshBindGroup, SpotLight, VisiblePointLights,
-    SHADOW_SHADER_HANDLE,
+    point_light_order, AmbientLight, Cascade, Cascades, Clusters, CubemapVisibleEntities,
+    DirectionalLight, DirectionalLightShadowMap, DrawMesh, GlobalVisiblePointLights, MeshPipeline,
+    NotShadowCaster, PointLight, PointLightShadowMap, SetMeshBindGroup, SpotLight,
+    VisiblePointLights, SHADOW_SHADER_HANDLE,
 };
 use bevy_asset::Handle;
 use bevy_core_pipeline::core_3d::Transparent3d;
@@ -12,7 +12,7 @@ use bevy_ecs::{
 };
 use bevy_math::{Mat4, UVec3, UVec4, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles};
 use bevy_render::{
-    camera::{Camera, CameraProjection},
+    camera::Camera,
     color::Color,
     mesh::{Mesh, MeshVertexBufferLayout},
     render_asset::RenderAssets,
@@ -66,10 +66,13 @@ pub struct ExtractedDirectionalLight {
     color: Color,
     illuminance: f32,
     direction: Vec3,
-    projection: Mat4,
     shadows_enabled: bool,
     shadow_depth_bias: f32,
     shadow_normal_bias: f32,
+    cascade_count: usize,
+    cascades: HashMap<Entity, Vec<Cascade>>,
+    fixed_cascade: Option<Cascade>,
+    debug_cascades: bool,
 }
 
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
@@ -174,13 +177,22 @@ bitflags::bitflags! {
 }
 
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
-pub struct GpuDirectionalLight {
+pub struct GpuDirectionalCascade {
     view_projection: Mat4,
+    texel_size: f32,
+    far_bound: f32,
+}
+
+#[derive(Copy, Clone, ShaderType, Default, Debug)]
+pub struct GpuDirectionalLight {
+    cascades: [GpuDirectionalCascade; MAX_CASCADES_PER_LIGHT],
     color: Vec4,
     dir_to_light: Vec3,
     flags: u32,
     shadow_depth_bias: f32,
     shadow_normal_bias: f32,
+    num_cascades: u32,
+    depth_texture_base_index: u32,
 }
 
 // NOTE: These must match the bit flags in bevy_pbr2/src/render/pbr.frag!
@@ -188,6 +200,7 @@ bitflags::bitflags! {
     #[repr(transparent)]
     struct DirectionalLightFlags: u32 {
         const SHADOWS_ENABLED            = (1 << 0);
+        const DEBUG_CASCADES             = (1 << 1);
         const NONE                       = 0;
         const UNINITIALIZED              = 0xFFFF;
     }
@@ -211,6 +224,7 @@ pub struct GpuLights {
 // NOTE: this must be kept in sync with the same constants in pbr.frag
 pub const MAX_UNIFORM_BUFFER_POINT_LIGHTS: usize = 256;
 pub const MAX_DIRECTIONAL_LIGHTS: usize = 1;
+pub const MAX_CASCADES_PER_LIGHT: usize = 4;
 pub const SHADOW_FORMAT: TextureFormat = TextureFormat::Depth32Float;
 
 pub struct ShadowPipeline {
@@ -278,6 +292,7 @@ bitflags::bitflags! {
     #[repr(transparent)]
     pub struct ShadowPipelineKey: u32 {
         const NONE               = 0;
+        const DEPTH_CLAMP_ORTHO  = (1 << 0);
         const PRIMITIVE_TOPOLOGY_RESERVED_BITS = ShadowPipelineKey::PRIMITIVE_TOPOLOGY_MASK_BITS << ShadowPipelineKey::PRIMITIVE_TOPOLOGY_SHIFT_BITS;
     }
 }
@@ -320,6 +335,10 @@ impl SpecializedMeshPipeline for ShadowPipeline {
         let mut bind_group_layout = vec![self.view_layout.clone()];
         let mut shader_defs = Vec::new();
 
+        if key.contains(ShadowPipelineKey::DEPTH_CLAMP_ORTHO) {
+            shader_defs.push(String::from("DEPTH_CLAMP_ORTHO"));
+        }
+
         if layout.contains(Mesh::ATTRIBUTE_JOINT_INDEX)
             && layout.contains(Mesh::ATTRIBUTE_JOINT_WEIGHT)
         {
@@ -419,6 +438,7 @@ pub fn extract_lights(
                 Entity,
                 &DirectionalLight,
                 &VisibleEntities,
+                Option<&Cascades>,
                 &GlobalTransform,
                 &Visibility,
             ),
@@ -519,25 +539,30 @@ pub fn extract_lights(
     *previous_spot_lights_len = spot_lights_values.len();
     commands.insert_or_spawn_batch(spot_lights_values);
 
-    for (entity, directional_light, visible_entities, transform, visibility) in
+    for (entity, directional_light, visible_entities, cascades, transform, visibility) in
         directional_lights.iter()
     {
         if !visibility.is_visible {
             continue;
         }
 
-        // Calulate the directional light shadow map texel size using the largest x,y dimension of
-        // the orthographic projection divided by the shadow map resolution
-        // NOTE: When using various PCF kernel sizes, this will need to be adjusted, according to:
-        // https://catlikecoding.com/unity/tutorials/custom-srp/directional-shadows/
-        let largest_dimension = (directional_light.shadow_projection.right
-            - directional_light.shadow_projection.left)
-            .max(
-                directional_light.shadow_projection.top
-                    - directional_light.shadow_projection.bottom,
-            );
-        let directional_light_texel_size =
-            largest_dimension / directional_light_shadow_map.size as f32;
+        // Lights without cascades use a single cascade in the fixed `shadow_projection`, in every
+        // view
+        let (cascade_count, cascades, fixed_cascade) = match cascades {
+            Some(cascades) => (
+                directional_light
+                    .shadow_cascade_count
+                    .clamp(1, MAX_CASCADES_PER_LIGHT),
+                cascades.cascades.clone(),
+                None,
+            ),
+            None => (
+                1,
+                HashMap::default(),
+                Some(directional_light.fixed_cascade(transform, directional_light_shadow_map.size)),
+            ),
+        };
+
         // TODO: As above
         let render_visible_entities = visible_entities.clone();
         commands.get_or_spawn(entity).insert_bundle((
@@ -545,13 +570,15 @@ pub fn extract_lights(
                 color: directional_light.color,
                 illuminance: directional_light.illuminance,
                 direction: transform.forward(),
-                projection: directional_light.shadow_projection.get_projection_matrix(),
                 shadows_enabled: directional_light.shadows_enabled,
                 shadow_depth_bias: directional_light.shadow_depth_bias,
+                // The normal bias is scaled to the texel size of each cascade in the shader.
                 // The factor of SQRT_2 is for the worst-case diagonal offset
-                shadow_normal_bias: directional_light.shadow_normal_bias
-                    * directional_light_texel_size
-                    * std::f32::consts::SQRT_2,
+                shadow_normal_bias: directional_light.shadow_normal_bias * std::f32::consts::SQRT_2,
+                cascade_count,
+                cascades,
+                fixed_cascade,
+                debug_cascades: directional_light.debug_shadow_cascades,
             },
             render_visible_entities,
         ));
@@ -673,6 +700,7 @@ pub struct LightMeta {
 pub enum LightEntity {
     Directional {
         light_entity: Entity,
+        cascade_index: usize,
     },
     Point {
         light_entity: Entity,
@@ -779,10 +807,13 @@ pub fn prepare_lights(
 
     let point_light_shadow_maps_count = point_light_count.min(max_texture_cubes);
 
+    // Each cascade of the directional lights has its own shadow map
     let directional_shadow_maps_count = directional_lights
         .iter()
+        .take(MAX_DIRECTIONAL_LIGHTS)
         .filter(|(_, light)| light.shadows_enabled)
-        .count()
+        .map(|(_, light)| light.cascade_count)
+        .sum::<usize>()
         .min(max_texture_array_layers);
 
     let spot_light_shadow_maps_count = point_lights
@@ -1060,6 +1091,7 @@ pub fn prepare_lights(
             view_lights.push(view_light_entity);
         }
 
+        let mut directional_depth_texture_index = 0;
         for (i, (light_entity, light)) in directional_lights
             .iter()
             .enumerate()
@@ -1081,30 +1113,55 @@ pub fn prepare_lights(
             let exposure = 1.0 / (f32::powf(2.0, ev100) * 1.2);
             let intensity = light.illuminance * exposure;
 
-            // NOTE: A directional light seems to have to have an eye position on the line along the direction of the light
-            // through the world origin. I (Rob Swain) do not yet understand why it cannot be translated away from this.
-            let view = Mat4::look_at_rh(Vec3::ZERO, light.direction, Vec3::Y);
-            // NOTE: This orthographic projection defines the volume within which shadows from a directional light can be cast
-            let projection = light.projection;
-
             let mut flags = DirectionalLightFlags::NONE;
             if light.shadows_enabled {
                 flags |= DirectionalLightFlags::SHADOWS_ENABLED;
             }
+            if light.debug_cascades {
+                flags |= DirectionalLightFlags::DEBUG_CASCADES;
+            }
+
+            // The cascades are computed for each camera, and limited by the layers of the shadow
+            // map texture
+            let cascades = match light
+                .cascades
+                .get(&entity)
+                .map(Vec::as_slice)
+                .or_else(|| light.fixed_cascade.as_ref().map(std::slice::from_ref))
+            {
+                Some(cascades) if light.shadows_enabled => {
+                    let available_layers =
+                        directional_shadow_maps_count - directional_depth_texture_index;
+                    &cascades[..cascades.len().min(available_layers)]
+                }
+                _ => &[],
+            };
+            let depth_texture_base_index = directional_depth_texture_index;
+            directional_depth_texture_index += cascades.len();
+
+            let mut gpu_cascades = [GpuDirectionalCascade::default(); MAX_CASCADES_PER_LIGHT];
+            for (gpu_cascade, cascade) in gpu_cascades.iter_mut().zip(cascades) {
+                *gpu_cascade = GpuDirectionalCascade {
+                    view_projection: cascade.view_projection,
+                    texel_size: cascade.texel_size,
+                    far_bound: cascade.far_bound,
+                };
+            }
 
             gpu_lights.directional_lights[i] = GpuDirectionalLight {
+                cascades: gpu_cascades,
                 // premultiply color by intensity
                 // we don't use the alpha at all, so no reason to multiply only [0..3]
                 color: Vec4::from_slice(&light.color.as_linear_rgba_f32()) * intensity,
                 dir_to_light,
-                // NOTE: * view is correct, it should not be view.inverse() here
-                view_projection: projection * view,
                 flags: flags.bits,
                 shadow_depth_bias: light.shadow_depth_bias,
                 shadow_normal_bias: light.shadow_normal_bias,
+                num_cascades: cascades.len() as u32,
+                depth_texture_base_index: depth_texture_base_index as u32,
             };
 
-            if light.shadows_enabled {
+            for (cascade_index, cascade) in cascades.iter().enumerate() {
                 let depth_texture_view =
                     directional_light_depth_texture
                         .texture
@@ -1115,7 +1172,7 @@ pub fn prepare_lights(
                             aspect: TextureAspect::All,
                             base_mip_level: 0,
                             mip_level_count: None,
-                            base_array_layer: i as u32,
+                            base_array_layer: (depth_texture_base_index + cascade_index) as u32,
                             array_layer_count: NonZeroU32::new(1),
                         });
 
@@ -1124,16 +1181,22 @@ pub fn prepare_lights(
                     .insert_bundle((
                         ShadowView {
                             depth_texture_view,
-                            pass_name: format!("shadow pass directional light {}", i),
+                            pass_name: format!(
+                                "shadow pass directional light {} cascade {}",
+                                i, cascade_index
+                            ),
                         },
                         ExtractedView {
                             width: directional_light_shadow_map.size as u32,
                             height: directional_light_shadow_map.size as u32,
-                            transform: GlobalTransform::from_matrix(view.inverse()),
-                            projection,
+                            transform: GlobalTransform::from_matrix(cascade.view_transform),
+                            projection: cascade.projection,
                         },
                         RenderPhase::<Shadow>::default(),
-                        LightEntity::Directional { light_entity },
+                        LightEntity::Directional {
+                            light_entity,
+                            cascade_index,
+                        },
                     ))
                     .id();
                 view_lights.push(view_light_entity);
@@ -1570,8 +1633,14 @@ pub fn queue_shadows(
         for view_light_entity in view_lights.lights.iter().copied() {
             let (light_entity, mut shadow_phase) =
                 view_light_shadow_phases.get_mut(view_light_entity).unwrap();
+            // Shadow casters between a directional light and the near plane of its orthographic
+            // projection are clamped to the near plane instead of being clipped
+            let light_key = match light_entity {
+                LightEntity::Directional { .. } => ShadowPipelineKey::DEPTH_CLAMP_ORTHO,
+                _ => ShadowPipelineKey::NONE,
+            };
             let visible_entities = match light_entity {
-                LightEntity::Directional { light_entity } => directional_light_entities
+                LightEntity::Directional { light_entity, .. } => directional_light_entities
                     .get(*light_entity)
                     .expect("Failed to get directional light visible entities"),
                 LightEntity::Point {
@@ -1590,8 +1659,8 @@ pub fn queue_shadows(
             for entity in visible_entities.iter().copied() {
                 if let Ok(mesh_handle) = casting_meshes.get(entity) {
                     if let Some(mesh) = render_meshes.get(mesh_handle) {
-                        let key =
-                            ShadowPipelineKey::from_primitive_topology(mesh.primitive_topology);
+                        let key = light_key
+                            | ShadowPipelineKey::from_primitive_topology(mesh.primitive_topology);
                         let pipeline_id = pipelines.specialize(
                             &mut pipeline_cache,
                             &shadow_pipeline,
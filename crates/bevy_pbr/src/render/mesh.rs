@@ -5,6 +5,7 @@ use crate::{
 };
 use bevy_app::Plugin;
 use bevy_asset::{load_internal_asset, Assets, Handle, HandleUntyped};
+use bevy_core_pipeline::core_3d::ViewHdrTexture;
 use bevy_ecs::{
     prelude::*,
     system::{lifetimeless::*, SystemParamItem, SystemState},
@@ -490,6 +491,7 @@ bitflags::bitflags! {
     pub struct MeshPipelineKey: u32 {
         const NONE                        = 0;
         const TRANSPARENT_MAIN_PASS       = (1 << 0);
+        const HDR                         = (1 << 1);
         const MSAA_RESERVED_BITS          = MeshPipelineKey::MSAA_MASK_BITS << MeshPipelineKey::MSAA_SHIFT_BITS;
         const PRIMITIVE_TOPOLOGY_RESERVED_BITS = MeshPipelineKey::PRIMITIVE_TOPOLOGY_MASK_BITS << MeshPipelineKey::PRIMITIVE_TOPOLOGY_SHIFT_BITS;
     }
@@ -506,6 +508,17 @@ impl MeshPipelineKey {
         MeshPipelineKey::from_bits(msaa_bits).unwrap()
     }
 
+    /// The key of the views of cameras with [`Camera3d::hdr`](bevy_core_pipeline::core_3d::Camera3d::hdr),
+    /// whose main pass renders to a [`ViewHdrTexture`]. Every pipeline drawing in the view must use
+    /// it, or it won't match the format of the texture.
+    pub fn from_hdr(hdr: bool) -> Self {
+        if hdr {
+            MeshPipelineKey::HDR
+        } else {
+            MeshPipelineKey::NONE
+        }
+    }
+
     pub fn msaa_samples(&self) -> u32 {
         ((self.bits >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS) + 1
     }
@@ -574,6 +587,14 @@ impl SpecializedMeshPipeline for MeshPipeline {
 
         let vertex_buffer_layout = layout.get_layout(&vertex_attributes)?;
 
+        // Hdr views are tonemapped after the main pass
+        let format = if key.contains(MeshPipelineKey::HDR) {
+            ViewHdrTexture::FORMAT
+        } else {
+            shader_defs.push(String::from("TONEMAP_IN_SHADER"));
+            TextureFormat::bevy_default()
+        };
+
         let (label, blend, depth_write_enabled);
         if key.contains(MeshPipelineKey::TRANSPARENT_MAIN_PASS) {
             label = "transparent_mesh_pipeline".into();
@@ -602,7 +623,7 @@ impl SpecializedMeshPipeline for MeshPipeline {
                 shader_defs,
                 entry_point: "fragment".into(),
                 targets: vec![ColorTargetState {
-                    format: TextureFormat::bevy_default(),
+                    format,
                     blend,
                     write_mask: ColorWrites::ALL,
                 }],
@@ -911,4 +932,12 @@ mod tests {
             assert_eq!(MeshPipelineKey::from_msaa_samples(i).msaa_samples(), i);
         }
     }
+
+    #[test]
+    fn mesh_key_hdr() {
+        assert_eq!(MeshPipelineKey::from_hdr(false), MeshPipelineKey::NONE);
+        let key = MeshPipelineKey::from_hdr(true) | MeshPipelineKey::from_msaa_samples(4);
+        assert!(key.contains(MeshPipelineKey::HDR));
+        assert_eq!(key.msaa_samples(), 4);
+    }
 }
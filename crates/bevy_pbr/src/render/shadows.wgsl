@@ -98,15 +98,32 @@ fn fetch_spot_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: ve
     #endif
 }
 
-fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
+fn get_cascade_index(light_id: u32, view_z: f32) -> u32 {
     let light = lights.directional_lights[light_id];
 
-    // The normal bias is scaled to the texel size.
-    let normal_offset = light.shadow_normal_bias * surface_normal.xyz;
+    for (var i: u32 = 0u; i < light.num_cascades; i = i + 1u) {
+        if (-view_z < lights.directional_lights[light_id].cascades[i].far_bound) {
+            return i;
+        }
+    }
+    return light.num_cascades;
+}
+
+fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>, view_z: f32) -> f32 {
+    let light = lights.directional_lights[light_id];
+    let cascade_index = get_cascade_index(light_id, view_z);
+    // No shadow beyond the last cascade
+    if (cascade_index >= light.num_cascades) {
+        return 1.0;
+    }
+    let cascade = lights.directional_lights[light_id].cascades[cascade_index];
+
+    // The normal bias is scaled to the texel size of the cascade.
+    let normal_offset = light.shadow_normal_bias * cascade.texel_size * surface_normal.xyz;
     let depth_offset = light.shadow_depth_bias * light.direction_to_light.xyz;
     let offset_position = vec4<f32>(frag_position.xyz + normal_offset + depth_offset, frag_position.w);
 
-    let offset_position_clip = light.view_projection * offset_position;
+    let offset_position_clip = cascade.view_projection * offset_position;
     if (offset_position_clip.w <= 0.0) {
         return 1.0;
     }
@@ -129,6 +146,25 @@ fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_nor
 #ifdef NO_ARRAY_TEXTURES_SUPPORT
     return textureSampleCompareLevel(directional_shadow_textures, directional_shadow_textures_sampler, light_local, depth);
 #else
-    return textureSampleCompareLevel(directional_shadow_textures, directional_shadow_textures_sampler, light_local, i32(light_id), depth);
+    return textureSampleCompareLevel(directional_shadow_textures, directional_shadow_textures_sampler, light_local, i32(light.depth_texture_base_index + cascade_index), depth);
 #endif
 }
+
+// Returns a color per cascade of the directional light at the fragment, white beyond the last
+// cascade
+fn cascade_debug_color(light_id: u32, view_z: f32) -> vec3<f32> {
+    let cascade_index = get_cascade_index(light_id, view_z);
+    if (cascade_index >= lights.directional_lights[light_id].num_cascades) {
+        return vec3<f32>(1.0);
+    }
+    if (cascade_index == 0u) {
+        return vec3<f32>(1.0, 0.25, 0.25);
+    }
+    if (cascade_index == 1u) {
+        return vec3<f32>(0.25, 1.0, 0.25);
+    }
+    if (cascade_index == 2u) {
+        return vec3<f32>(0.25, 0.25, 1.0);
+    }
+    return vec3<f32>(1.0, 1.0, 0.25);
+}
@@ -0,0 +1,212 @@
+//! The systems hit testing the [`Pickable`] entities against the pointer
+
+use crate::{ray_aabb_intersection, ray_mesh_intersection, PickHit, Pickable, Picking};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    entity::Entity,
+    query::{AnyOf, With},
+    system::{Query, Res, ResMut},
+};
+use bevy_math::{Ray, Vec2};
+use bevy_render::{
+    mesh::Mesh,
+    primitives::Aabb,
+    texture::Image,
+    view::{ComputedVisibility, Visibility},
+};
+use bevy_sprite::{Anchor, Mesh2dHandle, Sprite, TextureAtlas, TextureAtlasSprite};
+use bevy_transform::components::GlobalTransform;
+use bevy_ui::{CalculatedClip, Node, WorldUiNodes};
+
+/// Hit tests the UI nodes under the cursor, in front of the world.
+///
+/// The nodes rendered to a [`WorldUi`](bevy_ui::WorldUi) aren't picked, their surface is.
+pub fn pick_ui_nodes(
+    mut picking: ResMut<Picking>,
+    world_ui_nodes: Res<WorldUiNodes>,
+    node_query: Query<
+        (
+            Entity,
+            &Node,
+            &GlobalTransform,
+            &Visibility,
+            Option<&CalculatedClip>,
+        ),
+        With<Pickable>,
+    >,
+) {
+    let cursor_position = match picking.cursor_position() {
+        Some(cursor_position) => cursor_position,
+        None => return,
+    };
+    for (entity, node, transform, visibility, clip) in node_query.iter() {
+        if !visibility.is_visible || world_ui_nodes.root(entity).is_some() {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let mut min = position - node.size / 2.0;
+        let mut max = position + node.size / 2.0;
+        if let Some(clip) = clip {
+            min = min.max(clip.clip.min);
+            max = max.min(clip.clip.max);
+        }
+        if (min.x..max.x).contains(&cursor_position.x)
+            && (min.y..max.y).contains(&cursor_position.y)
+        {
+            picking.add_hit(PickHit {
+                entity,
+                position: cursor_position.extend(transform.translation.z),
+                distance: -1.0 - transform.translation.z,
+            });
+        }
+    }
+}
+
+/// Hit tests the sprites under the cursor, as rectangles in the XY plane of their transform.
+pub fn pick_sprites(
+    mut picking: ResMut<Picking>,
+    images: Res<Assets<Image>>,
+    texture_atlases: Res<Assets<TextureAtlas>>,
+    sprite_query: Query<
+        (
+            Entity,
+            &Sprite,
+            &Handle<Image>,
+            &GlobalTransform,
+            &ComputedVisibility,
+        ),
+        With<Pickable>,
+    >,
+    atlas_sprite_query: Query<
+        (
+            Entity,
+            &TextureAtlasSprite,
+            &Handle<TextureAtlas>,
+            &GlobalTransform,
+            &ComputedVisibility,
+        ),
+        With<Pickable>,
+    >,
+) {
+    let ray = match picking.ray() {
+        Some(ray) => ray,
+        None => return,
+    };
+    for (entity, sprite, image, transform, visibility) in sprite_query.iter() {
+        if !visibility.is_visible {
+            continue;
+        }
+        let size = sprite
+            .custom_size
+            .or_else(|| images.get(image).map(|image| image.size()));
+        if let Some(size) = size {
+            if let Some(hit) = pick_rect(&ray, entity, transform, size, &sprite.anchor) {
+                picking.add_hit(hit);
+            }
+        }
+    }
+    for (entity, sprite, atlas, transform, visibility) in atlas_sprite_query.iter() {
+        if !visibility.is_visible {
+            continue;
+        }
+        let size = sprite.custom_size.or_else(|| {
+            texture_atlases
+                .get(atlas)
+                .and_then(|atlas| atlas.textures.get(sprite.index))
+                .map(|rect| rect.size())
+        });
+        if let Some(size) = size {
+            if let Some(hit) = pick_rect(&ray, entity, transform, size, &sprite.anchor) {
+                picking.add_hit(hit);
+            }
+        }
+    }
+}
+
+/// Hit tests a rectangle of the given size in the XY plane of the transform, placed around the
+/// transform by its anchor.
+fn pick_rect(
+    ray: &Ray,
+    entity: Entity,
+    transform: &GlobalTransform,
+    size: Vec2,
+    anchor: &Anchor,
+) -> Option<PickHit> {
+    let local_ray = to_local_ray(ray, transform);
+    if local_ray.direction.z.abs() < f32::EPSILON {
+        return None;
+    }
+    let distance = -local_ray.origin.z / local_ray.direction.z;
+    if distance <= 0.0 {
+        return None;
+    }
+    let point = local_ray.get_point(distance).truncate();
+    let min = (Vec2::splat(-0.5) - anchor.as_vec()) * size;
+    let max = (Vec2::splat(0.5) - anchor.as_vec()) * size;
+    if (min.x..max.x).contains(&point.x) && (min.y..max.y).contains(&point.y) {
+        Some(PickHit {
+            entity,
+            position: ray.get_point(distance),
+            distance,
+        })
+    } else {
+        None
+    }
+}
+
+/// Hit tests the triangles of the 3D and 2D meshes under the cursor.
+///
+/// The bounding box of the meshes is tested first when they have an [`Aabb`].
+pub fn pick_meshes(
+    mut picking: ResMut<Picking>,
+    meshes: Res<Assets<Mesh>>,
+    mesh_query: Query<
+        (
+            Entity,
+            AnyOf<(&Handle<Mesh>, &Mesh2dHandle)>,
+            Option<&Aabb>,
+            &GlobalTransform,
+            &ComputedVisibility,
+        ),
+        With<Pickable>,
+    >,
+) {
+    let ray = match picking.ray() {
+        Some(ray) => ray,
+        None => return,
+    };
+    for (entity, (mesh_3d, mesh_2d), aabb, transform, visibility) in mesh_query.iter() {
+        if !visibility.is_visible {
+            continue;
+        }
+        let local_ray = to_local_ray(&ray, transform);
+        if let Some(aabb) = aabb {
+            if ray_aabb_intersection(&local_ray, aabb).is_none() {
+                continue;
+            }
+        }
+        let mesh = match mesh_3d.or_else(|| mesh_2d.map(|mesh_2d| &mesh_2d.0)) {
+            Some(mesh) => meshes.get(mesh),
+            None => None,
+        };
+        let distance = mesh.and_then(|mesh| ray_mesh_intersection(mesh, &local_ray));
+        if let Some(distance) = distance {
+            picking.add_hit(PickHit {
+                entity,
+                position: ray.get_point(distance),
+                distance,
+            });
+        }
+    }
+}
+
+/// Transforms the ray to the space of the transform.
+///
+/// The direction is not normalized, distances along the ray are the same in both spaces.
+fn to_local_ray(ray: &Ray, transform: &GlobalTransform) -> Ray {
+    let world_to_local = transform.compute_matrix().inverse();
+    Ray {
+        origin: world_to_local.transform_point3(ray.origin),
+        direction: world_to_local.transform_vector3(ray.direction),
+    }
+}
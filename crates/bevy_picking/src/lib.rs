@@ -0,0 +1,244 @@
+//! Picking of the entities under the cursor: meshes, sprites and UI nodes.
+//!
+//! Entities with the [`Pickable`] component are hit tested against a ray cast from the cursor of
+//! the primary window through the camera rendered last to it, and [`PointerOver`],
+//! [`PointerOut`] and [`PointerClick`] events are sent for the topmost entity under the cursor.
+
+mod backend;
+mod mesh;
+
+pub use backend::*;
+pub use mesh::*;
+
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{Pickable, Picking, PointerClick, PointerOut, PointerOver};
+}
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    entity::Entity,
+    event::EventWriter,
+    prelude::Component,
+    reflect::ReflectComponent,
+    schedule::{ParallelSystemDescriptorCoercion, SystemLabel},
+    system::{Query, Res, ResMut},
+};
+use bevy_input::{mouse::MouseButton, touch::Touches, Input, InputSystem};
+use bevy_math::{Ray, Vec2, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::camera::{Camera, RenderTarget};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::FloatOrd;
+use bevy_window::{WindowId, Windows};
+
+/// Adds picking of the [`Pickable`] entities under the cursor
+#[derive(Default)]
+pub struct PickingPlugin;
+
+/// The labels of the picking systems, which run in [`CoreStage::PreUpdate`]
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub enum PickingSystem {
+    /// After this label, the ray from the cursor has been updated for this frame
+    Pointer,
+    /// The systems hit testing entities against the pointer, adding their hits to [`Picking`]
+    Backend,
+    /// After this label, the hovered entity has been updated and the pointer events have been
+    /// sent for this frame
+    Events,
+}
+
+impl Plugin for PickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Pickable>()
+            .init_resource::<Picking>()
+            .add_event::<PointerOver>()
+            .add_event::<PointerOut>()
+            .add_event::<PointerClick>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                update_pointer_system
+                    .label(PickingSystem::Pointer)
+                    .after(InputSystem),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                pick_ui_nodes
+                    .label(PickingSystem::Backend)
+                    .after(PickingSystem::Pointer),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                pick_sprites
+                    .label(PickingSystem::Backend)
+                    .after(PickingSystem::Pointer),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                pick_meshes
+                    .label(PickingSystem::Backend)
+                    .after(PickingSystem::Pointer),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                pointer_events_system
+                    .label(PickingSystem::Events)
+                    .after(PickingSystem::Backend),
+            );
+    }
+}
+
+/// Marks an entity that can be picked with the cursor: a mesh with an
+/// [`Aabb`](bevy_render::primitives::Aabb), a sprite or a UI node.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component, Default)]
+pub struct Pickable;
+
+/// An entity hit by the pointer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PickHit {
+    pub entity: Entity,
+    /// The position of the hit in world space, or in logical pixels from the bottom left of the
+    /// window for UI nodes
+    pub position: Vec3,
+    /// The distance from the camera to the hit along the ray of the pointer
+    ///
+    /// UI nodes are drawn in front of the world, their hits have a negative distance decreasing
+    /// as they are drawn higher.
+    pub distance: f32,
+}
+
+/// The state of the pointer, and the [`Pickable`] entities it hits.
+#[derive(Debug, Default)]
+pub struct Picking {
+    cursor_position: Option<Vec2>,
+    ray: Option<Ray>,
+    hits: Vec<PickHit>,
+    hovered: Option<PickHit>,
+    pressed: Option<Entity>,
+}
+
+impl Picking {
+    /// The position of the cursor, or of the first touch, in logical pixels from the bottom left
+    /// of the primary window.
+    pub fn cursor_position(&self) -> Option<Vec2> {
+        self.cursor_position
+    }
+
+    /// The ray cast from the cursor through the camera rendered last to the primary window.
+    pub fn ray(&self) -> Option<Ray> {
+        self.ray
+    }
+
+    /// The topmost entity under the cursor.
+    pub fn hovered(&self) -> Option<&PickHit> {
+        self.hovered.as_ref()
+    }
+
+    /// The entities under the cursor this frame, from the topmost.
+    pub fn hits(&self) -> &[PickHit] {
+        &self.hits
+    }
+
+    /// Adds a hit of the pointer, for picking backends.
+    pub fn add_hit(&mut self, hit: PickHit) {
+        self.hits.push(hit);
+    }
+}
+
+/// Sent when the pointer starts hovering an entity.
+#[derive(Debug, Clone, Copy)]
+pub struct PointerOver {
+    pub entity: Entity,
+}
+
+/// Sent when the pointer stops hovering an entity.
+#[derive(Debug, Clone, Copy)]
+pub struct PointerOut {
+    pub entity: Entity,
+}
+
+/// Sent when an entity is clicked, pressing and releasing the left mouse button or a touch over
+/// it.
+#[derive(Debug, Clone, Copy)]
+pub struct PointerClick {
+    pub entity: Entity,
+    /// The position of the hit when the button was released, see [`PickHit::position`]
+    pub position: Vec3,
+}
+
+/// Updates the position of the cursor and casts its ray, clearing the hits of the last frame.
+pub fn update_pointer_system(
+    mut picking: ResMut<Picking>,
+    windows: Res<Windows>,
+    touches_input: Res<Touches>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+) {
+    picking.hits.clear();
+    picking.cursor_position = windows
+        .get_primary()
+        .and_then(|window| window.cursor_position())
+        .or_else(|| touches_input.first_pressed_position());
+    // The cursor looks through the camera rendered last to the window
+    let window_camera = camera_query
+        .iter()
+        .filter(|(camera, _)| {
+            camera.is_active && camera.target == RenderTarget::Window(WindowId::primary())
+        })
+        .max_by_key(|(camera, _)| camera.priority);
+    picking.ray = match (picking.cursor_position, window_camera) {
+        (Some(cursor_position), Some((camera, camera_transform))) => {
+            camera.viewport_to_world(camera_transform, cursor_position)
+        }
+        _ => None,
+    };
+}
+
+/// Finds the topmost entity hit by the pointer, and sends the pointer events.
+pub fn pointer_events_system(
+    mut picking: ResMut<Picking>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    touches_input: Res<Touches>,
+    mut over_events: EventWriter<PointerOver>,
+    mut out_events: EventWriter<PointerOut>,
+    mut click_events: EventWriter<PointerClick>,
+) {
+    picking
+        .hits
+        .sort_by_key(|hit| (FloatOrd(hit.distance), hit.entity));
+    let hovered = picking.hits.first().copied();
+
+    let last_hovered = picking.hovered;
+    let previous = last_hovered.map(|hit| hit.entity);
+    let current = hovered.map(|hit| hit.entity);
+    if previous != current {
+        if let Some(entity) = previous {
+            out_events.send(PointerOut { entity });
+        }
+        if let Some(entity) = current {
+            over_events.send(PointerOver { entity });
+        }
+    }
+    picking.hovered = hovered;
+
+    if mouse_button_input.just_pressed(MouseButton::Left) || touches_input.any_just_pressed() {
+        picking.pressed = current;
+    }
+    let touch_released = touches_input.any_just_released();
+    if mouse_button_input.just_released(MouseButton::Left) || touch_released {
+        // A released touch no longer points at anything, the entity it last hovered is clicked
+        let released_over = if touch_released {
+            hovered.or(last_hovered)
+        } else {
+            hovered
+        };
+        if let (Some(pressed), Some(hit)) = (picking.pressed.take(), released_over) {
+            if pressed == hit.entity {
+                click_events.send(PointerClick {
+                    entity: hit.entity,
+                    position: hit.position,
+                });
+            }
+        }
+    }
+}
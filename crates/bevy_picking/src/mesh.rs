@@ -0,0 +1,159 @@
+use bevy_math::{Ray, Vec3};
+use bevy_render::{
+    mesh::{Mesh, VertexAttributeValues},
+    primitives::Aabb,
+    render_resource::PrimitiveTopology,
+};
+
+/// Returns the distance along the ray to the closest triangle of the mesh it intersects, with the
+/// ray in the space of the mesh.
+///
+/// Only meshes with a [`PrimitiveTopology::TriangleList`] can be intersected. Triangles are hit
+/// from both sides.
+pub fn ray_mesh_intersection(mesh: &Mesh, ray: &Ray) -> Option<f32> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return None;
+    }
+    let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(positions)) => positions,
+        _ => return None,
+    };
+    let triangle = |indices: [usize; 3]| {
+        let vertex = |index: usize| positions.get(index).copied().map(Vec3::from);
+        Some([
+            vertex(indices[0])?,
+            vertex(indices[1])?,
+            vertex(indices[2])?,
+        ])
+    };
+
+    let intersect = |indices: &mut dyn Iterator<Item = usize>| {
+        let mut closest: Option<f32> = None;
+        while let (Some(a), Some(b), Some(c)) = (indices.next(), indices.next(), indices.next()) {
+            let distance =
+                triangle([a, b, c]).and_then(|triangle| ray_triangle_intersection(ray, &triangle));
+            if let Some(distance) = distance {
+                closest = Some(closest.map_or(distance, |closest| closest.min(distance)));
+            }
+        }
+        closest
+    };
+    match mesh.indices() {
+        Some(indices) => intersect(&mut indices.iter()),
+        None => intersect(&mut (0..positions.len())),
+    }
+}
+
+/// Returns the distance along the ray to the point where it enters the triangle, hit from both
+/// sides, using the Möller–Trumbore algorithm.
+pub fn ray_triangle_intersection(ray: &Ray, triangle: &[Vec3; 3]) -> Option<f32> {
+    let edge_1 = triangle[1] - triangle[0];
+    let edge_2 = triangle[2] - triangle[0];
+    let p = ray.direction.cross(edge_2);
+    let determinant = edge_1.dot(p);
+    // The ray is parallel to the triangle
+    if determinant.abs() < f32::EPSILON {
+        return None;
+    }
+    let inverse_determinant = 1.0 / determinant;
+
+    let t = ray.origin - triangle[0];
+    let u = t.dot(p) * inverse_determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = t.cross(edge_1);
+    let v = ray.direction.dot(q) * inverse_determinant;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let distance = edge_2.dot(q) * inverse_determinant;
+    (distance > f32::EPSILON).then(|| distance)
+}
+
+/// Returns the distance along the ray to the point where it enters the box, or `0.0` if its
+/// origin is inside the box.
+pub fn ray_aabb_intersection(ray: &Ray, aabb: &Aabb) -> Option<f32> {
+    let min = Vec3::from(aabb.min());
+    let max = Vec3::from(aabb.max());
+    // The distances at which the ray crosses the planes of the faces along each axis
+    let inverse_direction = ray.direction.recip();
+    let t_1 = (min - ray.origin) * inverse_direction;
+    let t_2 = (max - ray.origin) * inverse_direction;
+    let enter = t_1.min(t_2).max_element();
+    let exit = t_1.max(t_2).min_element();
+    (exit >= enter.max(0.0)).then(|| enter.max(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_render::mesh::shape;
+
+    #[test]
+    fn ray_hits_triangle_from_both_sides() {
+        let triangle = [Vec3::ZERO, Vec3::X, Vec3::Y];
+        let ray = Ray {
+            origin: Vec3::new(0.2, 0.2, 2.0),
+            direction: Vec3::NEG_Z,
+        };
+        assert_eq!(ray_triangle_intersection(&ray, &triangle), Some(2.0));
+
+        let ray = Ray {
+            origin: Vec3::new(0.2, 0.2, -2.0),
+            direction: Vec3::Z,
+        };
+        assert_eq!(ray_triangle_intersection(&ray, &triangle), Some(2.0));
+
+        // Outside of the triangle, and behind the ray
+        let ray = Ray {
+            origin: Vec3::new(0.8, 0.8, 2.0),
+            direction: Vec3::NEG_Z,
+        };
+        assert_eq!(ray_triangle_intersection(&ray, &triangle), None);
+        let ray = Ray {
+            origin: Vec3::new(0.2, 0.2, 2.0),
+            direction: Vec3::Z,
+        };
+        assert_eq!(ray_triangle_intersection(&ray, &triangle), None);
+    }
+
+    #[test]
+    fn ray_hits_closest_face_of_mesh() {
+        let mesh = Mesh::from(shape::Cube { size: 2.0 });
+        let ray = Ray {
+            origin: Vec3::new(0.5, 0.5, 5.0),
+            direction: Vec3::NEG_Z,
+        };
+        assert_eq!(ray_mesh_intersection(&mesh, &ray), Some(4.0));
+
+        let ray = Ray {
+            origin: Vec3::new(0.5, 5.0, 5.0),
+            direction: Vec3::NEG_Z,
+        };
+        assert_eq!(ray_mesh_intersection(&mesh, &ray), None);
+    }
+
+    #[test]
+    fn ray_hits_aabb() {
+        let aabb = Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0));
+        let ray = Ray {
+            origin: Vec3::new(0.0, 0.0, 5.0),
+            direction: Vec3::NEG_Z,
+        };
+        assert_eq!(ray_aabb_intersection(&ray, &aabb), Some(4.0));
+
+        // From inside the box
+        let ray = Ray {
+            origin: Vec3::ZERO,
+            direction: Vec3::X,
+        };
+        assert_eq!(ray_aabb_intersection(&ray, &aabb), Some(0.0));
+
+        let ray = Ray {
+            origin: Vec3::new(0.0, 2.0, 5.0),
+            direction: Vec3::NEG_Z,
+        };
+        assert_eq!(ray_aabb_intersection(&ray, &aabb), None);
+    }
+}
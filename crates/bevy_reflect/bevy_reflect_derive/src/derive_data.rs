@@ -2,12 +2,18 @@ use crate::container_attributes::ReflectTraits;
 use crate::field_attributes::{parse_field_attrs, ReflectFieldAttr};
 use crate::utility::get_bevy_reflect_path;
 use crate::{REFLECT_ATTRIBUTE_NAME, REFLECT_VALUE_ATTRIBUTE_NAME};
-use syn::{Data, DataStruct, DeriveInput, Field, Fields, Generics, Ident, Meta, Path};
+use syn::parse_quote;
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{
+    Data, DataEnum, DataStruct, DeriveInput, Field, Fields, Generics, Ident, Meta, Path, Variant,
+};
 
 pub(crate) enum DeriveType {
     Struct,
     TupleStruct,
     UnitStruct,
+    Enum,
     Value,
 }
 
@@ -21,6 +27,41 @@ pub(crate) struct StructField<'a> {
     pub index: usize,
 }
 
+/// Represents a variant on an enum.
+pub(crate) struct EnumVariant<'a> {
+    /// The raw variant.
+    pub data: &'a Variant,
+    /// The fields of the variant.
+    pub fields: EnumVariantFields<'a>,
+}
+
+/// The fields of an enum variant, see [`EnumVariant`].
+pub(crate) enum EnumVariantFields<'a> {
+    Named(Vec<StructField<'a>>),
+    Unnamed(Vec<StructField<'a>>),
+    Unit,
+}
+
+impl<'a> EnumVariant<'a> {
+    /// Get an iterator over the active fields of the variant
+    pub fn active_fields(&self) -> impl Iterator<Item = &StructField<'a>> {
+        self.fields().iter().filter(|field| !field.attrs.ignore)
+    }
+
+    /// Get an iterator over the ignored fields of the variant
+    pub fn ignored_fields(&self) -> impl Iterator<Item = &StructField<'a>> {
+        self.fields().iter().filter(|field| field.attrs.ignore)
+    }
+
+    /// The complete set of fields in this variant.
+    pub fn fields(&self) -> &[StructField<'a>] {
+        match &self.fields {
+            EnumVariantFields::Named(fields) | EnumVariantFields::Unnamed(fields) => fields,
+            EnumVariantFields::Unit => &[],
+        }
+    }
+}
+
 /// Data used by derive macros for `Reflect` and `FromReflect`
 ///
 /// # Example
@@ -42,6 +83,7 @@ pub(crate) struct ReflectDeriveData<'a> {
     type_name: &'a Ident,
     generics: &'a Generics,
     fields: Vec<StructField<'a>>,
+    variants: Vec<EnumVariant<'a>>,
     bevy_reflect_path: Path,
 }
 
@@ -52,6 +94,7 @@ impl<'a> ReflectDeriveData<'a> {
             derive_type: DeriveType::Value,
             generics: &input.generics,
             fields: Vec::new(),
+            variants: Vec::new(),
             traits: ReflectTraits::default(),
             bevy_reflect_path: get_bevy_reflect_path(),
         };
@@ -104,35 +147,17 @@ impl<'a> ReflectDeriveData<'a> {
                 }
                 return Ok(output);
             }
+            Data::Enum(DataEnum { variants, .. }) if !force_reflect_value => {
+                output.derive_type = DeriveType::Enum;
+                output.variants = parse_variants(variants)?;
+                return Ok(output);
+            }
             _ => {
                 return Ok(output);
             }
         };
 
-        let mut errors: Option<syn::Error> = None;
-        output.fields = fields
-            .iter()
-            .enumerate()
-            .map(|(index, field)| {
-                let attrs = parse_field_attrs(&field.attrs).unwrap_or_else(|err| {
-                    if let Some(ref mut errors) = errors {
-                        errors.combine(err);
-                    } else {
-                        errors = Some(err);
-                    }
-                    ReflectFieldAttr::default()
-                });
-
-                StructField {
-                    index,
-                    attrs,
-                    data: field,
-                }
-            })
-            .collect::<Vec<StructField>>();
-        if let Some(errs) = errors {
-            return Err(errs);
-        }
+        output.fields = parse_fields(fields)?;
 
         Ok(output)
     }
@@ -180,6 +205,34 @@ impl<'a> ReflectDeriveData<'a> {
         &self.fields
     }
 
+    /// The variants of this enum.
+    pub fn variants(&self) -> &[EnumVariant<'a>] {
+        &self.variants
+    }
+
+    /// The generics of this enum, requiring the types of the active fields of its variants to
+    /// implement `FromReflect` when the enum is generic, so that its variants can be created from
+    /// reflected values.
+    pub fn enum_generics(&self) -> Generics {
+        let mut generics = self.generics.clone();
+        if generics.params.is_empty() {
+            return generics;
+        }
+        let bevy_reflect_path = &self.bevy_reflect_path;
+        let where_clause = generics.make_where_clause();
+        for field in self
+            .variants
+            .iter()
+            .flat_map(|variant| variant.active_fields())
+        {
+            let ty = &field.data.ty;
+            where_clause
+                .predicates
+                .push(parse_quote!(#ty: #bevy_reflect_path::FromReflect));
+        }
+        generics
+    }
+
     /// The cached `bevy_reflect` path.
     pub fn bevy_reflect_path(&self) -> &Path {
         &self.bevy_reflect_path
@@ -195,3 +248,51 @@ impl<'a> ReflectDeriveData<'a> {
         )
     }
 }
+
+fn parse_fields(fields: &Punctuated<Field, Comma>) -> Result<Vec<StructField>, syn::Error> {
+    let mut errors: Option<syn::Error> = None;
+    let fields = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let attrs = parse_field_attrs(&field.attrs).unwrap_or_else(|err| {
+                if let Some(ref mut errors) = errors {
+                    errors.combine(err);
+                } else {
+                    errors = Some(err);
+                }
+                ReflectFieldAttr::default()
+            });
+
+            StructField {
+                index,
+                attrs,
+                data: field,
+            }
+        })
+        .collect::<Vec<StructField>>();
+    if let Some(errs) = errors {
+        return Err(errs);
+    }
+
+    Ok(fields)
+}
+
+fn parse_variants(variants: &Punctuated<Variant, Comma>) -> Result<Vec<EnumVariant>, syn::Error> {
+    variants
+        .iter()
+        .map(|variant| {
+            let fields = match &variant.fields {
+                Fields::Named(fields) => EnumVariantFields::Named(parse_fields(&fields.named)?),
+                Fields::Unnamed(fields) => {
+                    EnumVariantFields::Unnamed(parse_fields(&fields.unnamed)?)
+                }
+                Fields::Unit => EnumVariantFields::Unit,
+            };
+            Ok(EnumVariant {
+                data: variant,
+                fields,
+            })
+        })
+        .collect()
+}
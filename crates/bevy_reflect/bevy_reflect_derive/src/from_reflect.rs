@@ -1,4 +1,5 @@
 use crate::container_attributes::REFLECT_DEFAULT;
+use crate::derive_data::EnumVariantFields;
 use crate::field_attributes::DefaultBehavior;
 use crate::ReflectDeriveData;
 use proc_macro::TokenStream;
@@ -16,6 +17,32 @@ pub(crate) fn impl_tuple_struct(derive_data: &ReflectDeriveData) -> TokenStream
     impl_struct_internal(derive_data, true)
 }
 
+/// Implements `FromReflect` for the given enum
+pub(crate) fn impl_enum(derive_data: &ReflectDeriveData) -> TokenStream {
+    let enum_name = derive_data.type_name();
+    let bevy_reflect_path = derive_data.bevy_reflect_path();
+
+    let ref_enum = Ident::new("__ref_enum", Span::call_site());
+    let (variant_names, variant_constructors) = get_variant_constructors(derive_data, &ref_enum);
+
+    let generics = derive_data.enum_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    TokenStream::from(quote! {
+        impl #impl_generics #bevy_reflect_path::FromReflect for #enum_name #ty_generics #where_clause {
+            fn from_reflect(reflect: &dyn #bevy_reflect_path::Reflect) -> Option<Self> {
+                if let #bevy_reflect_path::ReflectRef::Enum(#ref_enum) = reflect.reflect_ref() {
+                    match #bevy_reflect_path::Enum::variant_name(#ref_enum) {
+                        #(#variant_names => Some(#variant_constructors),)*
+                        _ => None,
+                    }
+                } else {
+                    None
+                }
+            }
+        }
+    })
+}
+
 /// Implements `FromReflect` for the given value type
 pub(crate) fn impl_value(
     type_name: &Ident,
@@ -215,3 +242,78 @@ fn get_field_accessor(field: &Field, index: usize, is_tuple: bool) -> Lit {
             .unwrap_or_else(|| Lit::Str(LitStr::new(&index.to_string(), Span::call_site())))
     }
 }
+
+/// Get the names of the variants of an enum, and the expressions creating each variant from the
+/// fields of the `&dyn Enum` named `ref_enum`.
+///
+/// The expressions use `?` to return `None` when a required field is missing or of the wrong type,
+/// so they must be evaluated in a context returning an `Option`.
+pub(crate) fn get_variant_constructors(
+    derive_data: &ReflectDeriveData,
+    ref_enum: &Ident,
+) -> (Vec<String>, Vec<proc_macro2::TokenStream>) {
+    let bevy_reflect_path = derive_data.bevy_reflect_path();
+
+    derive_data
+        .variants()
+        .iter()
+        .map(|variant| {
+            let ident = &variant.data.ident;
+            let is_tuple = matches!(variant.fields, EnumVariantFields::Unnamed(_));
+
+            let active_fields = variant
+                .active_fields()
+                .enumerate()
+                .map(|(active_index, field)| {
+                    let member = get_ident(field.data, field.index, is_tuple);
+                    let ty = field.data.ty.clone();
+                    let get_field = if is_tuple {
+                        quote! {
+                            #bevy_reflect_path::Enum::field_at(#ref_enum, #active_index)
+                        }
+                    } else {
+                        let accessor = get_field_accessor(field.data, field.index, false);
+                        quote! {
+                            #bevy_reflect_path::Enum::field(#ref_enum, #accessor)
+                        }
+                    };
+
+                    let value = match &field.attrs.default {
+                        DefaultBehavior::Func(path) => quote! {
+                            if let Some(field) = #get_field {
+                                <#ty as #bevy_reflect_path::FromReflect>::from_reflect(field)?
+                            } else {
+                                #path()
+                            }
+                        },
+                        DefaultBehavior::Default => quote! {
+                            if let Some(field) = #get_field {
+                                <#ty as #bevy_reflect_path::FromReflect>::from_reflect(field)?
+                            } else {
+                                Default::default()
+                            }
+                        },
+                        DefaultBehavior::Required => quote! {
+                            <#ty as #bevy_reflect_path::FromReflect>::from_reflect(#get_field?)?
+                        },
+                    };
+                    quote! { #member: #value }
+                });
+            let ignored_fields = variant.ignored_fields().map(|field| {
+                let member = get_ident(field.data, field.index, is_tuple);
+                match &field.attrs.default {
+                    DefaultBehavior::Func(path) => quote! { #member: #path() },
+                    _ => quote! { #member: Default::default() },
+                }
+            });
+
+            let constructor = quote! {
+                Self::#ident {
+                    #(#active_fields,)*
+                    #(#ignored_fields,)*
+                }
+            };
+            (ident.to_string(), constructor)
+        })
+        .unzip()
+}
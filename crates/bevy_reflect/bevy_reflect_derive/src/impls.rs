@@ -1,7 +1,9 @@
 use crate::container_attributes::ReflectTraits;
+use crate::derive_data::EnumVariantFields;
+use crate::from_reflect::get_variant_constructors;
 use crate::ReflectDeriveData;
 use proc_macro::TokenStream;
-use proc_macro2::Ident;
+use proc_macro2::{Ident, Span};
 use quote::quote;
 use syn::{Generics, Index, Member, Path};
 
@@ -354,6 +356,335 @@ pub(crate) fn impl_tuple_struct(derive_data: &ReflectDeriveData) -> TokenStream
     })
 }
 
+/// Implements `Enum`, `GetTypeRegistration`, and `Reflect` for the given derive data.
+pub(crate) fn impl_enum(derive_data: &ReflectDeriveData) -> TokenStream {
+    let bevy_reflect_path = derive_data.bevy_reflect_path();
+    let enum_name = derive_data.type_name();
+    let generics = derive_data.enum_generics();
+
+    let mut variant_infos = Vec::new();
+    let mut field_arms = Vec::new();
+    let mut field_mut_arms = Vec::new();
+    let mut field_at_arms = Vec::new();
+    let mut field_at_mut_arms = Vec::new();
+    let mut index_of_arms = Vec::new();
+    let mut name_at_arms = Vec::new();
+    let mut field_len_arms = Vec::new();
+    let mut variant_name_arms = Vec::new();
+    let mut variant_type_arms = Vec::new();
+
+    for variant in derive_data.variants() {
+        let ident = &variant.data.ident;
+        let name = ident.to_string();
+        let is_tuple = matches!(variant.fields, EnumVariantFields::Unnamed(_));
+
+        let field_members = variant
+            .active_fields()
+            .map(|field| {
+                if is_tuple {
+                    Member::Unnamed(Index::from(field.index))
+                } else {
+                    Member::Named(field.data.ident.clone().unwrap())
+                }
+            })
+            .collect::<Vec<_>>();
+        let field_names = variant
+            .active_fields()
+            .map(|field| {
+                field
+                    .data
+                    .ident
+                    .as_ref()
+                    .map(|ident| ident.to_string())
+                    .unwrap_or_else(|| field.index.to_string())
+            })
+            .collect::<Vec<_>>();
+        let field_types = variant
+            .active_fields()
+            .map(|field| field.data.ty.clone())
+            .collect::<Vec<_>>();
+        let field_count = field_members.len();
+        let field_indices = (0..field_count).collect::<Vec<usize>>();
+        let field_bindings = field_indices
+            .iter()
+            .map(|index| Ident::new(&format!("__field_{}", index), Span::call_site()))
+            .collect::<Vec<_>>();
+
+        let (variant_type, variant_info) = match variant.fields {
+            EnumVariantFields::Named(_) => (
+                quote!(Struct),
+                quote! {
+                    #bevy_reflect_path::VariantInfo::Struct(#bevy_reflect_path::StructVariantInfo::new(#name, &[
+                        #(#bevy_reflect_path::NamedField::new::<#field_types, _>(#field_names),)*
+                    ]))
+                },
+            ),
+            EnumVariantFields::Unnamed(_) => (
+                quote!(Tuple),
+                quote! {
+                    #bevy_reflect_path::VariantInfo::Tuple(#bevy_reflect_path::TupleVariantInfo::new(#name, &[
+                        #(#bevy_reflect_path::UnnamedField::new::<#field_types>(#field_indices),)*
+                    ]))
+                },
+            ),
+            EnumVariantFields::Unit => (
+                quote!(Unit),
+                quote! {
+                    #bevy_reflect_path::VariantInfo::Unit(#bevy_reflect_path::UnitVariantInfo::new(#name))
+                },
+            ),
+        };
+        variant_infos.push(variant_info);
+
+        let pattern = quote! { Self::#ident { #(#field_members: #field_bindings,)* .. } };
+        if field_count == 0 {
+            field_arms.push(quote! { Self::#ident { .. } => None, });
+            field_at_arms.push(quote! { Self::#ident { .. } => None, });
+            index_of_arms.push(quote! { Self::#ident { .. } => None, });
+            name_at_arms.push(quote! { Self::#ident { .. } => None, });
+        } else {
+            if is_tuple {
+                field_arms.push(quote! { Self::#ident { .. } => None, });
+                index_of_arms.push(quote! { Self::#ident { .. } => None, });
+                name_at_arms.push(quote! { Self::#ident { .. } => None, });
+            } else {
+                field_arms.push(quote! {
+                    #pattern => match name {
+                        #(#field_names => Some(#field_bindings),)*
+                        _ => None,
+                    },
+                });
+                index_of_arms.push(quote! {
+                    Self::#ident { .. } => match name {
+                        #(#field_names => Some(#field_indices),)*
+                        _ => None,
+                    },
+                });
+                name_at_arms.push(quote! {
+                    Self::#ident { .. } => match index {
+                        #(#field_indices => Some(#field_names),)*
+                        _ => None,
+                    },
+                });
+            }
+            field_at_arms.push(quote! {
+                #pattern => match index {
+                    #(#field_indices => Some(#field_bindings),)*
+                    _ => None,
+                },
+            });
+        }
+        field_len_arms.push(quote! { Self::#ident { .. } => #field_count, });
+        variant_name_arms.push(quote! { Self::#ident { .. } => #name, });
+        variant_type_arms.push(
+            quote! { Self::#ident { .. } => #bevy_reflect_path::VariantType::#variant_type, },
+        );
+    }
+    // The arms returning mutable references only differ by the type of the bindings
+    field_mut_arms.extend(field_arms.iter().cloned());
+    field_at_mut_arms.extend(field_at_arms.iter().cloned());
+
+    let ref_enum = Ident::new("__ref_enum", Span::call_site());
+    let (variant_names, variant_constructors) = get_variant_constructors(derive_data, &ref_enum);
+
+    let hash_fn = derive_data.traits().get_hash_impl(bevy_reflect_path);
+    let partial_eq_fn = derive_data
+        .traits()
+        .get_partial_eq_impl(bevy_reflect_path)
+        .unwrap_or_else(|| {
+            quote! {
+                fn reflect_partial_eq(&self, value: &dyn #bevy_reflect_path::Reflect) -> Option<bool> {
+                    #bevy_reflect_path::enum_partial_eq(self, value)
+                }
+            }
+        });
+    let debug_fn = derive_data.traits().get_debug_impl();
+
+    let typed_impl = impl_typed(
+        enum_name,
+        &generics,
+        quote! {
+            let variants = [#(#variant_infos,)*];
+            let info = #bevy_reflect_path::EnumInfo::new::<Self>(&variants);
+            #bevy_reflect_path::TypeInfo::Enum(info)
+        },
+        bevy_reflect_path,
+    );
+
+    let get_type_registration_impl = crate::registration::impl_get_type_registration(
+        enum_name,
+        bevy_reflect_path,
+        derive_data.traits().idents(),
+        &generics,
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    TokenStream::from(quote! {
+        #get_type_registration_impl
+
+        #typed_impl
+
+        impl #impl_generics #bevy_reflect_path::Enum for #enum_name #ty_generics #where_clause {
+            fn field(&self, name: &str) -> Option<&dyn #bevy_reflect_path::Reflect> {
+                match self {
+                    #(#field_arms)*
+                }
+            }
+
+            fn field_at(&self, index: usize) -> Option<&dyn #bevy_reflect_path::Reflect> {
+                match self {
+                    #(#field_at_arms)*
+                }
+            }
+
+            fn field_mut(&mut self, name: &str) -> Option<&mut dyn #bevy_reflect_path::Reflect> {
+                match self {
+                    #(#field_mut_arms)*
+                }
+            }
+
+            fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn #bevy_reflect_path::Reflect> {
+                match self {
+                    #(#field_at_mut_arms)*
+                }
+            }
+
+            fn index_of(&self, name: &str) -> Option<usize> {
+                match self {
+                    #(#index_of_arms)*
+                }
+            }
+
+            fn name_at(&self, index: usize) -> Option<&str> {
+                match self {
+                    #(#name_at_arms)*
+                }
+            }
+
+            fn iter_fields(&self) -> #bevy_reflect_path::VariantFieldIter {
+                #bevy_reflect_path::VariantFieldIter::new(self)
+            }
+
+            fn field_len(&self) -> usize {
+                match self {
+                    #(#field_len_arms)*
+                }
+            }
+
+            fn variant_name(&self) -> &str {
+                match self {
+                    #(#variant_name_arms)*
+                }
+            }
+
+            fn variant_type(&self) -> #bevy_reflect_path::VariantType {
+                match self {
+                    #(#variant_type_arms)*
+                }
+            }
+
+            fn clone_dynamic(&self) -> #bevy_reflect_path::DynamicEnum {
+                #bevy_reflect_path::DynamicEnum::from_ref(self)
+            }
+        }
+
+        impl #impl_generics #bevy_reflect_path::Reflect for #enum_name #ty_generics #where_clause {
+            #[inline]
+            fn type_name(&self) -> &str {
+                std::any::type_name::<Self>()
+            }
+
+            #[inline]
+            fn get_type_info(&self) -> &'static #bevy_reflect_path::TypeInfo {
+                <Self as #bevy_reflect_path::Typed>::type_info()
+            }
+
+            #[inline]
+            fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+                self
+            }
+
+            #[inline]
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+
+            #[inline]
+            fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+                self
+            }
+
+            #[inline]
+            fn as_reflect(&self) -> &dyn #bevy_reflect_path::Reflect {
+                self
+            }
+
+            #[inline]
+            fn as_reflect_mut(&mut self) -> &mut dyn #bevy_reflect_path::Reflect {
+                self
+            }
+
+            #[inline]
+            fn clone_value(&self) -> Box<dyn #bevy_reflect_path::Reflect> {
+                Box::new(#bevy_reflect_path::Enum::clone_dynamic(self))
+            }
+
+            #[inline]
+            fn set(&mut self, value: Box<dyn #bevy_reflect_path::Reflect>) -> Result<(), Box<dyn #bevy_reflect_path::Reflect>> {
+                *self = value.take()?;
+                Ok(())
+            }
+
+            fn apply(&mut self, value: &dyn #bevy_reflect_path::Reflect) {
+                if let #bevy_reflect_path::ReflectRef::Enum(#ref_enum) = value.reflect_ref() {
+                    if #bevy_reflect_path::Enum::variant_name(self) == #ref_enum.variant_name() {
+                        // Same variant -> apply the fields
+                        for (i, field) in #ref_enum.iter_fields().enumerate() {
+                            let self_field = match field.name() {
+                                Some(name) => #bevy_reflect_path::Enum::field_mut(self, name),
+                                None => #bevy_reflect_path::Enum::field_at_mut(self, i),
+                            };
+                            if let Some(self_field) = self_field {
+                                self_field.apply(field.value());
+                            }
+                        }
+                    } else {
+                        // Different variant -> create it from the fields
+                        let variant: Option<Self> = (|| match #ref_enum.variant_name() {
+                            #(#variant_names => Some(#variant_constructors),)*
+                            _ => None,
+                        })();
+                        match variant {
+                            Some(variant) => *self = variant,
+                            None => panic!(
+                                "Failed to apply variant `{}` to enum `{}`.",
+                                #ref_enum.variant_name(),
+                                std::any::type_name::<Self>()
+                            ),
+                        }
+                    }
+                } else {
+                    panic!("Attempted to apply non-enum type to enum type.");
+                }
+            }
+
+            fn reflect_ref(&self) -> #bevy_reflect_path::ReflectRef {
+                #bevy_reflect_path::ReflectRef::Enum(self)
+            }
+
+            fn reflect_mut(&mut self) -> #bevy_reflect_path::ReflectMut {
+                #bevy_reflect_path::ReflectMut::Enum(self)
+            }
+
+            #hash_fn
+
+            #partial_eq_fn
+
+            #debug_fn
+        }
+    })
+}
+
 /// Implements `GetTypeRegistration` and `Reflect` for the given type data.
 pub(crate) fn impl_value(
     type_name: &Ident,
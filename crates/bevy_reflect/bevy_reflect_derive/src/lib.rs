@@ -47,6 +47,7 @@ pub fn derive_reflect(input: TokenStream) -> TokenStream {
     match derive_data.derive_type() {
         DeriveType::Struct | DeriveType::UnitStruct => impls::impl_struct(&derive_data),
         DeriveType::TupleStruct => impls::impl_tuple_struct(&derive_data),
+        DeriveType::Enum => impls::impl_enum(&derive_data),
         DeriveType::Value => impls::impl_value(
             derive_data.type_name(),
             derive_data.generics(),
@@ -76,6 +77,7 @@ pub fn derive_from_reflect(input: TokenStream) -> TokenStream {
     match derive_data.derive_type() {
         DeriveType::Struct | DeriveType::UnitStruct => from_reflect::impl_struct(&derive_data),
         DeriveType::TupleStruct => from_reflect::impl_tuple_struct(&derive_data),
+        DeriveType::Enum => from_reflect::impl_enum(&derive_data),
         DeriveType::Value => from_reflect::impl_value(
             derive_data.type_name(),
             &ast.generics,
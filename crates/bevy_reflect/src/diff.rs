@@ -0,0 +1,451 @@
+use crate::{Enum, List, Map, Reflect, ReflectMut, ReflectRef, Struct};
+use thiserror::Error;
+
+/// A structural delta between two reflected values, containing only what changed.
+///
+/// A patch is created with [`Reflect::diff`], and applied with [`Reflect::apply_patch`]. Applying
+/// the patch returned by `a.diff(b)` to `a` makes it equal to `b`, so patches can be used to
+/// store the overrides of a scene, the undo history of an editor, or to replicate changes over the
+/// network.
+///
+/// Values without nested fields are compared with [`Reflect::reflect_partial_eq`], so the values
+/// that don't reflect `PartialEq` are always replaced.
+///
+/// ```
+/// use bevy_reflect::{Reflect, ReflectPatch};
+///
+/// #[derive(Reflect, Clone, Debug, PartialEq)]
+/// struct Player {
+///     name: String,
+///     health: f32,
+///     inventory: Vec<String>,
+/// }
+///
+/// # fn main() {
+/// let before = Player {
+///     name: String::from("Ferris"),
+///     health: 100.0,
+///     inventory: vec![String::from("sword")],
+/// };
+/// let mut after = before.clone();
+/// after.health = 80.0;
+/// after.inventory.push(String::from("shield"));
+///
+/// let patch = before.diff(&after);
+/// let mut player = before.clone();
+/// player.apply_patch(&patch).unwrap();
+/// assert_eq!(player, after);
+///
+/// // The patch from `after` to `before` undoes the changes
+/// player.apply_patch(&after.diff(&before)).unwrap();
+/// assert_eq!(player, before);
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum ReflectPatch {
+    /// The value didn't change.
+    Unchanged,
+    /// The value is replaced by another value.
+    ///
+    /// This is used for the values without nested fields, for the variants of an enum that
+    /// changed, and for the values whose type changed.
+    Replace(Box<dyn Reflect>),
+    /// The fields of a struct or of a struct variant that changed, by name.
+    Struct(Vec<(String, ReflectPatch)>),
+    /// The fields of a tuple, tuple struct, or tuple variant, or the elements of an array, that
+    /// changed, by index.
+    Tuple(Vec<(usize, ReflectPatch)>),
+    /// The new length of a list, and the elements that changed or were added, by index.
+    List {
+        len: usize,
+        elements: Vec<(usize, ReflectPatch)>,
+    },
+    /// The keys of the entries of a map that were removed, and the entries that changed or were
+    /// added.
+    Map {
+        removed: Vec<Box<dyn Reflect>>,
+        changed: Vec<(Box<dyn Reflect>, ReflectPatch)>,
+    },
+}
+
+/// An error returned when a [`ReflectPatch`] doesn't match the value it is applied to.
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum ReflectPatchError {
+    #[error("the value doesn't have a field named `{0}`")]
+    InvalidField(String),
+    #[error("the value doesn't have a field or element at index {0}")]
+    InvalidIndex(usize),
+    #[error("the map doesn't have an entry for the patched key")]
+    InvalidKey,
+    #[error("the patch doesn't match the kind of the value `{0}`")]
+    MismatchedKind(String),
+}
+
+impl ReflectPatch {
+    /// Returns the patch turning `from` into `to`.
+    pub fn diff(from: &dyn Reflect, to: &dyn Reflect) -> ReflectPatch {
+        if from.type_name() != to.type_name() {
+            return ReflectPatch::Replace(to.clone_value());
+        }
+
+        let patch = match (from.reflect_ref(), to.reflect_ref()) {
+            (ReflectRef::Struct(from), ReflectRef::Struct(to)) => diff_struct(from, to),
+            (ReflectRef::TupleStruct(from), ReflectRef::TupleStruct(to))
+                if from.field_len() == to.field_len() =>
+            {
+                Some(ReflectPatch::Tuple(diff_fields(
+                    from.iter_fields().zip(to.iter_fields()),
+                )))
+            }
+            (ReflectRef::Tuple(from), ReflectRef::Tuple(to))
+                if from.field_len() == to.field_len() =>
+            {
+                Some(ReflectPatch::Tuple(diff_fields(
+                    from.iter_fields().zip(to.iter_fields()),
+                )))
+            }
+            (ReflectRef::Array(from), ReflectRef::Array(to)) if from.len() == to.len() => {
+                Some(ReflectPatch::Tuple(diff_fields(from.iter().zip(to.iter()))))
+            }
+            (ReflectRef::List(from), ReflectRef::List(to)) => Some(diff_list(from, to)),
+            (ReflectRef::Map(from), ReflectRef::Map(to)) => Some(diff_map(from, to)),
+            (ReflectRef::Enum(from), ReflectRef::Enum(to)) => diff_enum(from, to),
+            _ => match from.reflect_partial_eq(to) {
+                Some(true) => Some(ReflectPatch::Unchanged),
+                _ => None,
+            },
+        };
+
+        match patch {
+            Some(patch) if patch.is_unchanged() => ReflectPatch::Unchanged,
+            Some(patch) => patch,
+            None => ReflectPatch::Replace(to.clone_value()),
+        }
+    }
+
+    /// Returns `true` if the patch doesn't change anything.
+    pub fn is_unchanged(&self) -> bool {
+        match self {
+            ReflectPatch::Unchanged => true,
+            ReflectPatch::Replace(_) => false,
+            ReflectPatch::Struct(fields) => fields.is_empty(),
+            ReflectPatch::Tuple(fields) => fields.is_empty(),
+            // The length of a list is only stored to remove its last elements
+            ReflectPatch::List { .. } | ReflectPatch::Map { .. } => false,
+        }
+    }
+
+    /// Applies the patch to `value`.
+    ///
+    /// The patch is applied field by field, so `value` is left partially patched when an error
+    /// is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a [`ReflectPatch::Replace`] can't be [applied](Reflect::apply) to the value it
+    /// replaces.
+    pub fn apply(&self, value: &mut dyn Reflect) -> Result<(), ReflectPatchError> {
+        match self {
+            ReflectPatch::Unchanged => Ok(()),
+            ReflectPatch::Replace(new_value) => {
+                value.apply(new_value.as_ref());
+                Ok(())
+            }
+            ReflectPatch::Struct(fields) => {
+                let type_name = value.type_name().to_string();
+                if !matches!(
+                    value.reflect_ref(),
+                    ReflectRef::Struct(_) | ReflectRef::Enum(_)
+                ) {
+                    return Err(ReflectPatchError::MismatchedKind(type_name));
+                }
+                for (name, patch) in fields {
+                    let field = match value.reflect_mut() {
+                        ReflectMut::Struct(value) => value.field_mut(name),
+                        ReflectMut::Enum(value) => value.field_mut(name),
+                        _ => return Err(ReflectPatchError::MismatchedKind(type_name)),
+                    };
+                    patch.apply(
+                        field.ok_or_else(|| ReflectPatchError::InvalidField(name.clone()))?,
+                    )?;
+                }
+                Ok(())
+            }
+            ReflectPatch::Tuple(fields) => {
+                let type_name = value.type_name().to_string();
+                if !matches!(
+                    value.reflect_ref(),
+                    ReflectRef::TupleStruct(_)
+                        | ReflectRef::Tuple(_)
+                        | ReflectRef::Array(_)
+                        | ReflectRef::Enum(_)
+                ) {
+                    return Err(ReflectPatchError::MismatchedKind(type_name));
+                }
+                for (index, patch) in fields {
+                    let field = match value.reflect_mut() {
+                        ReflectMut::TupleStruct(value) => value.field_mut(*index),
+                        ReflectMut::Tuple(value) => value.field_mut(*index),
+                        ReflectMut::Array(value) => value.get_mut(*index),
+                        ReflectMut::Enum(value) => value.field_at_mut(*index),
+                        _ => return Err(ReflectPatchError::MismatchedKind(type_name)),
+                    };
+                    patch.apply(field.ok_or(ReflectPatchError::InvalidIndex(*index))?)?;
+                }
+                Ok(())
+            }
+            ReflectPatch::List { len, elements } => {
+                let type_name = value.type_name().to_string();
+                let list = match value.reflect_mut() {
+                    ReflectMut::List(list) => list,
+                    _ => return Err(ReflectPatchError::MismatchedKind(type_name)),
+                };
+                while list.len() > *len {
+                    list.pop();
+                }
+                for (index, patch) in elements {
+                    let len = list.len();
+                    match (list.get_mut(*index), patch) {
+                        (Some(element), patch) => patch.apply(element)?,
+                        (None, ReflectPatch::Replace(element)) if *index == len => {
+                            list.push(element.clone_value());
+                        }
+                        (None, _) => return Err(ReflectPatchError::InvalidIndex(*index)),
+                    }
+                }
+                Ok(())
+            }
+            ReflectPatch::Map { removed, changed } => {
+                let type_name = value.type_name().to_string();
+                let map = match value.reflect_mut() {
+                    ReflectMut::Map(map) => map,
+                    _ => return Err(ReflectPatchError::MismatchedKind(type_name)),
+                };
+                for key in removed {
+                    map.remove(key.as_ref());
+                }
+                for (key, patch) in changed {
+                    match (map.get_mut(key.as_ref()), patch) {
+                        (Some(entry), patch) => patch.apply(entry)?,
+                        (None, ReflectPatch::Replace(entry)) => {
+                            map.insert_boxed(key.clone_value(), entry.clone_value());
+                        }
+                        (None, _) => return Err(ReflectPatchError::InvalidKey),
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Returns the patches of the pairs of fields that changed, with their index.
+fn diff_fields<'a>(
+    fields: impl Iterator<Item = (&'a dyn Reflect, &'a dyn Reflect)>,
+) -> Vec<(usize, ReflectPatch)> {
+    fields
+        .map(|(from, to)| ReflectPatch::diff(from, to))
+        .enumerate()
+        .filter(|(_, patch)| !patch.is_unchanged())
+        .collect()
+}
+
+fn diff_struct(from: &dyn Struct, to: &dyn Struct) -> Option<ReflectPatch> {
+    if from.field_len() != to.field_len() {
+        return None;
+    }
+    let mut fields = Vec::new();
+    for (index, to_field) in to.iter_fields().enumerate() {
+        let name = to.name_at(index)?;
+        let patch = ReflectPatch::diff(from.field(name)?, to_field);
+        if !patch.is_unchanged() {
+            fields.push((name.to_string(), patch));
+        }
+    }
+    Some(ReflectPatch::Struct(fields))
+}
+
+fn diff_list(from: &dyn List, to: &dyn List) -> ReflectPatch {
+    if from.len() == to.len() {
+        let elements = diff_fields(from.iter().zip(to.iter()));
+        if elements.is_empty() {
+            return ReflectPatch::Unchanged;
+        }
+        return ReflectPatch::List {
+            len: to.len(),
+            elements,
+        };
+    }
+
+    let mut elements = diff_fields(from.iter().zip(to.iter()));
+    elements.extend(
+        to.iter()
+            .enumerate()
+            .skip(from.len())
+            .map(|(index, element)| (index, ReflectPatch::Replace(element.clone_value()))),
+    );
+    ReflectPatch::List {
+        len: to.len(),
+        elements,
+    }
+}
+
+fn diff_map(from: &dyn Map, to: &dyn Map) -> ReflectPatch {
+    let removed = from
+        .iter()
+        .filter(|(key, _)| to.get(*key).is_none())
+        .map(|(key, _)| key.clone_value())
+        .collect::<Vec<_>>();
+    let changed = to
+        .iter()
+        .filter_map(|(key, to_value)| {
+            let patch = match from.get(key) {
+                Some(from_value) => ReflectPatch::diff(from_value, to_value),
+                None => ReflectPatch::Replace(to_value.clone_value()),
+            };
+            (!patch.is_unchanged()).then(|| (key.clone_value(), patch))
+        })
+        .collect::<Vec<_>>();
+
+    if removed.is_empty() && changed.is_empty() {
+        ReflectPatch::Unchanged
+    } else {
+        ReflectPatch::Map { removed, changed }
+    }
+}
+
+fn diff_enum(from: &dyn Enum, to: &dyn Enum) -> Option<ReflectPatch> {
+    if from.variant_name() != to.variant_name()
+        || from.variant_type() != to.variant_type()
+        || from.field_len() != to.field_len()
+    {
+        return None;
+    }
+
+    let mut fields = Vec::new();
+    let mut indices = Vec::new();
+    for (index, to_field) in to.iter_fields().enumerate() {
+        match to_field.name() {
+            Some(name) => {
+                let patch = ReflectPatch::diff(from.field(name)?, to_field.value());
+                if !patch.is_unchanged() {
+                    fields.push((name.to_string(), patch));
+                }
+            }
+            None => {
+                let patch = ReflectPatch::diff(from.field_at(index)?, to_field.value());
+                if !patch.is_unchanged() {
+                    indices.push((index, patch));
+                }
+            }
+        }
+    }
+
+    if indices.is_empty() {
+        Some(ReflectPatch::Struct(fields))
+    } else {
+        Some(ReflectPatch::Tuple(indices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_reflect;
+    use crate::*;
+    use bevy_utils::HashMap;
+
+    #[derive(Reflect, FromReflect, Clone, Debug, PartialEq)]
+    enum Shape {
+        Circle { radius: f32 },
+        Rect(f32, f32),
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Foo {
+        a: u32,
+        b: (String, [u8; 2]),
+        c: Vec<u32>,
+        d: HashMap<String, u32>,
+        e: Shape,
+    }
+
+    fn foo() -> Foo {
+        let mut d = HashMap::default();
+        d.insert(String::from("one"), 1);
+        d.insert(String::from("two"), 2);
+        Foo {
+            a: 1,
+            b: (String::from("b"), [1, 2]),
+            c: vec![1, 2, 3],
+            d,
+            e: Shape::Circle { radius: 1.0 },
+        }
+    }
+
+    #[test]
+    fn diff_unchanged() {
+        assert!(foo().diff(&foo()).is_unchanged());
+    }
+
+    #[test]
+    fn diff_changed_fields() {
+        let mut other = foo();
+        other.a = 2;
+        other.b.1[1] = 3;
+
+        let patch = foo().diff(&other);
+        if let ReflectPatch::Struct(fields) = &patch {
+            assert_eq!(fields.len(), 2);
+            assert_eq!(fields[0].0, "a");
+            assert!(matches!(&fields[1], (name, ReflectPatch::Tuple(_)) if name == "b"));
+        } else {
+            panic!("Expected `ReflectPatch::Struct`");
+        }
+
+        let mut value = foo();
+        value.apply_patch(&patch).unwrap();
+        assert_eq!(value, other);
+    }
+
+    #[test]
+    fn diff_lists_maps_and_enums() {
+        let mut other = foo();
+        other.c = vec![1, 4];
+        other.d.remove("one");
+        other.d.insert(String::from("two"), 20);
+        other.d.insert(String::from("three"), 3);
+        other.e = Shape::Rect(1.0, 2.0);
+
+        let mut value = foo();
+        value.apply_patch(&foo().diff(&other)).unwrap();
+        assert_eq!(value, other);
+
+        // Growing lists and same enum variants
+        let mut grown = other.clone();
+        grown.c.extend([5, 6]);
+        grown.e = Shape::Rect(1.0, 3.0);
+        let patch = other.diff(&grown);
+        value.apply_patch(&patch).unwrap();
+        assert_eq!(value, grown);
+
+        // Undo
+        value.apply_patch(&grown.diff(&foo())).unwrap();
+        assert_eq!(value, foo());
+    }
+
+    #[test]
+    fn diff_dynamic_values() {
+        let mut other = foo();
+        other.a = 5;
+        let patch = foo().diff(other.clone_value().as_ref());
+
+        let mut dynamic = foo().clone_value();
+        dynamic.apply_patch(&patch).unwrap();
+        assert!(dynamic.reflect_partial_eq(&other).unwrap());
+
+        assert_eq!(
+            5u32.apply_patch(&ReflectPatch::Struct(Vec::new())),
+            Err(ReflectPatchError::MismatchedKind(String::from("u32")))
+        );
+    }
+}
@@ -0,0 +1,800 @@
+use crate::utility::NonGenericTypeInfoCell;
+use crate::{
+    DynamicInfo, DynamicStruct, DynamicTuple, NamedField, Reflect, ReflectMut, ReflectRef, Struct,
+    Tuple, TypeInfo, Typed, UnnamedField,
+};
+use bevy_utils::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::{
+    any::{Any, TypeId},
+    borrow::Cow,
+    slice::Iter,
+};
+
+/// A reflected Rust enum.
+///
+/// The fields of the current variant can be addressed by index, and by name for struct variants.
+/// A unit variant has no fields.
+///
+/// This trait is automatically implemented for `enum` types when using `#[derive(Reflect)]`.
+/// The types of the fields of the variants must implement [`FromReflect`](crate::FromReflect), so
+/// that [applying](Reflect::apply) another variant can create its fields.
+///
+/// # Example
+///
+/// ```
+/// use bevy_reflect::{Enum, Reflect, VariantType};
+///
+/// #[derive(Reflect)]
+/// enum Shape {
+///     Circle { radius: f32 },
+///     Square(f32),
+///     Point,
+/// }
+///
+/// # fn main() {
+/// let shape = Shape::Circle { radius: 2.0 };
+///
+/// assert_eq!(shape.variant_name(), "Circle");
+/// assert_eq!(shape.variant_type(), VariantType::Struct);
+/// assert_eq!(shape.field_len(), 1);
+///
+/// let radius = shape.field("radius").unwrap();
+/// assert_eq!(radius.downcast_ref::<f32>(), Some(&2.0));
+/// # }
+/// ```
+///
+/// # Migrating from value enums
+///
+/// `#[derive(Reflect)]` used to reflect enums as opaque values, (de)serialized with their
+/// [`Serialize`](serde::Serialize) and [`Deserialize`](serde::Deserialize) implementations.
+/// Derived enums, as well as [`Option`], are now serialized as their variant and its fields, which
+/// changes the format of scenes containing them, like `Color` and `Val`:
+///
+/// ```ron
+/// // Before
+/// "color": {
+///     "type": "bevy_render::color::Color",
+///     "value": Rgba(red: 1.0, green: 0.0, blue: 0.0, alpha: 1.0),
+/// },
+/// // After
+/// "color": {
+///     "type": "bevy_render::color::Color",
+///     "enum": {
+///         "variant": "Rgba",
+///         "struct": {
+///             "red": { "type": "f32", "value": 1.0 },
+///             "green": { "type": "f32", "value": 0.0 },
+///             "blue": { "type": "f32", "value": 0.0 },
+///             "alpha": { "type": "f32", "value": 1.0 },
+///         },
+///     },
+/// },
+/// ```
+///
+/// Enums whose fields don't implement [`FromReflect`](crate::FromReflect), or that should keep
+/// their previous format, can opt out with `#[reflect_value(...)]`, like `RenderTarget` does.
+pub trait Enum: Reflect {
+    /// Returns a reference to the value of the field of the current struct variant named `name`
+    /// as a `&dyn Reflect`.
+    fn field(&self, name: &str) -> Option<&dyn Reflect>;
+
+    /// Returns a reference to the value of the field of the current variant with index `index`
+    /// as a `&dyn Reflect`.
+    fn field_at(&self, index: usize) -> Option<&dyn Reflect>;
+
+    /// Returns a mutable reference to the value of the field of the current struct variant
+    /// named `name` as a `&mut dyn Reflect`.
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect>;
+
+    /// Returns a mutable reference to the value of the field of the current variant with index
+    /// `index` as a `&mut dyn Reflect`.
+    fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect>;
+
+    /// Returns the index of the field of the current struct variant named `name`.
+    fn index_of(&self, name: &str) -> Option<usize>;
+
+    /// Returns the name of the field of the current struct variant with index `index`.
+    fn name_at(&self, index: usize) -> Option<&str>;
+
+    /// Returns an iterator over the fields of the current variant.
+    fn iter_fields(&self) -> VariantFieldIter;
+
+    /// Returns the number of fields of the current variant.
+    fn field_len(&self) -> usize;
+
+    /// Returns the name of the current variant.
+    fn variant_name(&self) -> &str;
+
+    /// Returns the [kind](VariantType) of the current variant.
+    fn variant_type(&self) -> VariantType;
+
+    /// Clones the enum into a [`DynamicEnum`].
+    fn clone_dynamic(&self) -> DynamicEnum;
+
+    /// Returns `true` if the current variant is of the given [kind](VariantType).
+    fn is_variant(&self, variant_type: VariantType) -> bool {
+        self.variant_type() == variant_type
+    }
+
+    /// Returns the full path of the current variant, like `my_crate::Shape::Circle`.
+    fn variant_path(&self) -> String {
+        format!("{}::{}", self.type_name(), self.variant_name())
+    }
+}
+
+/// The kind of a variant of a reflected enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VariantType {
+    /// A variant with named fields, like `Circle { radius: f32 }`.
+    Struct,
+    /// A variant with unnamed fields, like `Square(f32)`.
+    Tuple,
+    /// A variant without fields, like `Point`.
+    Unit,
+}
+
+/// A field of the current variant of a reflected enum, returned by [`VariantFieldIter`].
+pub enum VariantField<'a> {
+    Struct(&'a str, &'a dyn Reflect),
+    Tuple(&'a dyn Reflect),
+}
+
+impl<'a> VariantField<'a> {
+    /// The name of the field, for struct variants.
+    pub fn name(&self) -> Option<&'a str> {
+        match self {
+            VariantField::Struct(name, _) => Some(name),
+            VariantField::Tuple(_) => None,
+        }
+    }
+
+    /// The value of the field.
+    pub fn value(&self) -> &'a dyn Reflect {
+        match self {
+            VariantField::Struct(_, value) | VariantField::Tuple(value) => *value,
+        }
+    }
+}
+
+/// An iterator over the fields of the current variant of a reflected enum.
+pub struct VariantFieldIter<'a> {
+    pub(crate) container: &'a dyn Enum,
+    pub(crate) index: usize,
+}
+
+impl<'a> VariantFieldIter<'a> {
+    pub fn new(container: &'a dyn Enum) -> Self {
+        VariantFieldIter {
+            container,
+            index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for VariantFieldIter<'a> {
+    type Item = VariantField<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = match self.container.variant_type() {
+            VariantType::Struct => {
+                let name = self.container.name_at(self.index)?;
+                Some(VariantField::Struct(name, self.container.field(name)?))
+            }
+            VariantType::Tuple => Some(VariantField::Tuple(self.container.field_at(self.index)?)),
+            VariantType::Unit => None,
+        };
+        self.index += 1;
+        value
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.container.field_len();
+        (size, Some(size))
+    }
+}
+
+impl<'a> ExactSizeIterator for VariantFieldIter<'a> {}
+
+/// A container for compile-time enum info.
+#[derive(Clone, Debug)]
+pub struct EnumInfo {
+    type_name: &'static str,
+    type_id: TypeId,
+    variants: Box<[VariantInfo]>,
+    variant_indices: HashMap<&'static str, usize>,
+}
+
+impl EnumInfo {
+    /// Create a new [`EnumInfo`].
+    ///
+    /// # Arguments
+    ///
+    /// * `variants`: The variants of this enum in the order they are defined
+    ///
+    pub fn new<TEnum: Enum>(variants: &[VariantInfo]) -> Self {
+        let variant_indices = variants
+            .iter()
+            .enumerate()
+            .map(|(index, variant)| (variant.name(), index))
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            type_name: std::any::type_name::<TEnum>(),
+            type_id: TypeId::of::<TEnum>(),
+            variants: variants.to_vec().into_boxed_slice(),
+            variant_indices,
+        }
+    }
+
+    /// Get the variant with the given name.
+    pub fn variant(&self, name: &str) -> Option<&VariantInfo> {
+        self.variant_indices
+            .get(name)
+            .map(|index| &self.variants[*index])
+    }
+
+    /// Get the variant at the given index.
+    pub fn variant_at(&self, index: usize) -> Option<&VariantInfo> {
+        self.variants.get(index)
+    }
+
+    /// Get the index of the variant with the given name.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.variant_indices.get(name).copied()
+    }
+
+    /// Returns the full path of the variant with the given name, like `my_crate::Shape::Circle`.
+    pub fn variant_path(&self, name: &str) -> String {
+        format!("{}::{}", self.type_name(), name)
+    }
+
+    /// Iterate over the variants of this enum.
+    pub fn iter(&self) -> Iter<'_, VariantInfo> {
+        self.variants.iter()
+    }
+
+    /// The number of variants in this enum.
+    pub fn variant_len(&self) -> usize {
+        self.variants.len()
+    }
+
+    /// The [type name] of the enum.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The [`TypeId`] of the enum.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Check if the given type matches the enum type.
+    pub fn is<T: Any>(&self) -> bool {
+        TypeId::of::<T>() == self.type_id
+    }
+}
+
+/// A container for compile-time info of an enum variant.
+#[derive(Clone, Debug)]
+pub enum VariantInfo {
+    Struct(StructVariantInfo),
+    Tuple(TupleVariantInfo),
+    Unit(UnitVariantInfo),
+}
+
+impl VariantInfo {
+    /// The name of the variant.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Struct(info) => info.name(),
+            Self::Tuple(info) => info.name(),
+            Self::Unit(info) => info.name(),
+        }
+    }
+
+    /// The [kind](VariantType) of the variant.
+    pub fn variant_type(&self) -> VariantType {
+        match self {
+            Self::Struct(_) => VariantType::Struct,
+            Self::Tuple(_) => VariantType::Tuple,
+            Self::Unit(_) => VariantType::Unit,
+        }
+    }
+}
+
+/// A container for compile-time info of a struct variant.
+#[derive(Clone, Debug)]
+pub struct StructVariantInfo {
+    name: &'static str,
+    fields: Box<[NamedField]>,
+    field_indices: HashMap<Cow<'static, str>, usize>,
+}
+
+impl StructVariantInfo {
+    /// Create a new [`StructVariantInfo`].
+    pub fn new(name: &'static str, fields: &[NamedField]) -> Self {
+        let field_indices = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| (field.name().clone(), index))
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            name,
+            fields: fields.to_vec().into_boxed_slice(),
+            field_indices,
+        }
+    }
+
+    /// The name of the variant.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Get the field with the given name.
+    pub fn field(&self, name: &str) -> Option<&NamedField> {
+        self.field_indices
+            .get(name)
+            .map(|index| &self.fields[*index])
+    }
+
+    /// Get the field at the given index.
+    pub fn field_at(&self, index: usize) -> Option<&NamedField> {
+        self.fields.get(index)
+    }
+
+    /// Get the index of the field with the given name.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.field_indices.get(name).copied()
+    }
+
+    /// Iterate over the fields of this variant.
+    pub fn iter(&self) -> Iter<'_, NamedField> {
+        self.fields.iter()
+    }
+
+    /// The total number of fields in this variant.
+    pub fn field_len(&self) -> usize {
+        self.fields.len()
+    }
+}
+
+/// A container for compile-time info of a tuple variant.
+#[derive(Clone, Debug)]
+pub struct TupleVariantInfo {
+    name: &'static str,
+    fields: Box<[UnnamedField]>,
+}
+
+impl TupleVariantInfo {
+    /// Create a new [`TupleVariantInfo`].
+    pub fn new(name: &'static str, fields: &[UnnamedField]) -> Self {
+        Self {
+            name,
+            fields: fields.to_vec().into_boxed_slice(),
+        }
+    }
+
+    /// The name of the variant.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Get the field at the given index.
+    pub fn field_at(&self, index: usize) -> Option<&UnnamedField> {
+        self.fields.get(index)
+    }
+
+    /// Iterate over the fields of this variant.
+    pub fn iter(&self) -> Iter<'_, UnnamedField> {
+        self.fields.iter()
+    }
+
+    /// The total number of fields in this variant.
+    pub fn field_len(&self) -> usize {
+        self.fields.len()
+    }
+}
+
+/// A container for compile-time info of a unit variant.
+#[derive(Clone, Debug)]
+pub struct UnitVariantInfo {
+    name: &'static str,
+}
+
+impl UnitVariantInfo {
+    /// Create a new [`UnitVariantInfo`].
+    pub fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+
+    /// The name of the variant.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// The variant of a [`DynamicEnum`], and its fields.
+#[derive(Default)]
+pub enum DynamicVariant {
+    #[default]
+    Unit,
+    Tuple(DynamicTuple),
+    Struct(DynamicStruct),
+}
+
+impl DynamicVariant {
+    fn clone_dynamic(&self) -> Self {
+        match self {
+            DynamicVariant::Unit => DynamicVariant::Unit,
+            DynamicVariant::Tuple(tuple) => DynamicVariant::Tuple(tuple.clone_dynamic()),
+            DynamicVariant::Struct(dyn_struct) => {
+                DynamicVariant::Struct(dyn_struct.clone_dynamic())
+            }
+        }
+    }
+}
+
+impl From<()> for DynamicVariant {
+    fn from(_: ()) -> Self {
+        DynamicVariant::Unit
+    }
+}
+
+impl From<DynamicTuple> for DynamicVariant {
+    fn from(tuple: DynamicTuple) -> Self {
+        DynamicVariant::Tuple(tuple)
+    }
+}
+
+impl From<DynamicStruct> for DynamicVariant {
+    fn from(dyn_struct: DynamicStruct) -> Self {
+        DynamicVariant::Struct(dyn_struct)
+    }
+}
+
+/// An enum whose variant can be set at runtime.
+///
+/// ```
+/// use bevy_reflect::{DynamicEnum, DynamicTuple, Reflect};
+///
+/// #[derive(Reflect, Debug, PartialEq)]
+/// enum Shape {
+///     Square(f32),
+///     Point,
+/// }
+///
+/// # fn main() {
+/// let mut tuple = DynamicTuple::default();
+/// tuple.insert(3.0f32);
+/// let dynamic = DynamicEnum::new(std::any::type_name::<Shape>(), "Square", tuple);
+///
+/// let mut shape = Shape::Point;
+/// shape.apply(&dynamic);
+/// assert_eq!(shape, Shape::Square(3.0));
+/// # }
+/// ```
+#[derive(Default)]
+pub struct DynamicEnum {
+    name: String,
+    variant_name: String,
+    variant: DynamicVariant,
+}
+
+impl DynamicEnum {
+    /// Create a new [`DynamicEnum`] of the enum type `name`, with the given variant.
+    pub fn new<I: Into<String>, V: Into<DynamicVariant>>(
+        name: I,
+        variant_name: I,
+        variant: V,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            variant_name: variant_name.into(),
+            variant: variant.into(),
+        }
+    }
+
+    /// Create a new [`DynamicEnum`] from the current variant of an [`Enum`].
+    pub fn from_ref<TEnum: Enum + ?Sized>(value: &TEnum) -> Self {
+        let variant = match value.variant_type() {
+            VariantType::Struct => {
+                let mut dyn_struct = DynamicStruct::default();
+                for field in value.iter_fields() {
+                    dyn_struct.insert_boxed(field.name().unwrap(), field.value().clone_value());
+                }
+                DynamicVariant::Struct(dyn_struct)
+            }
+            VariantType::Tuple => {
+                let mut tuple = DynamicTuple::default();
+                for field in value.iter_fields() {
+                    tuple.insert_boxed(field.value().clone_value());
+                }
+                DynamicVariant::Tuple(tuple)
+            }
+            VariantType::Unit => DynamicVariant::Unit,
+        };
+        Self::new(value.type_name(), value.variant_name(), variant)
+    }
+
+    /// Returns the type name of the enum.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the type name of the enum.
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    /// Sets the current variant of the enum.
+    pub fn set_variant<I: Into<String>, V: Into<DynamicVariant>>(&mut self, name: I, variant: V) {
+        self.variant_name = name.into();
+        self.variant = variant.into();
+    }
+}
+
+impl Enum for DynamicEnum {
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        match &self.variant {
+            DynamicVariant::Struct(dyn_struct) => dyn_struct.field(name),
+            _ => None,
+        }
+    }
+
+    fn field_at(&self, index: usize) -> Option<&dyn Reflect> {
+        match &self.variant {
+            DynamicVariant::Tuple(tuple) => tuple.field(index),
+            DynamicVariant::Struct(dyn_struct) => dyn_struct.field_at(index),
+            DynamicVariant::Unit => None,
+        }
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        match &mut self.variant {
+            DynamicVariant::Struct(dyn_struct) => dyn_struct.field_mut(name),
+            _ => None,
+        }
+    }
+
+    fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+        match &mut self.variant {
+            DynamicVariant::Tuple(tuple) => tuple.field_mut(index),
+            DynamicVariant::Struct(dyn_struct) => dyn_struct.field_at_mut(index),
+            DynamicVariant::Unit => None,
+        }
+    }
+
+    fn index_of(&self, name: &str) -> Option<usize> {
+        match &self.variant {
+            DynamicVariant::Struct(dyn_struct) => {
+                (0..dyn_struct.field_len()).find(|index| dyn_struct.name_at(*index) == Some(name))
+            }
+            _ => None,
+        }
+    }
+
+    fn name_at(&self, index: usize) -> Option<&str> {
+        match &self.variant {
+            DynamicVariant::Struct(dyn_struct) => dyn_struct.name_at(index),
+            _ => None,
+        }
+    }
+
+    fn iter_fields(&self) -> VariantFieldIter {
+        VariantFieldIter::new(self)
+    }
+
+    fn field_len(&self) -> usize {
+        match &self.variant {
+            DynamicVariant::Tuple(tuple) => tuple.field_len(),
+            DynamicVariant::Struct(dyn_struct) => dyn_struct.field_len(),
+            DynamicVariant::Unit => 0,
+        }
+    }
+
+    fn variant_name(&self) -> &str {
+        &self.variant_name
+    }
+
+    fn variant_type(&self) -> VariantType {
+        match &self.variant {
+            DynamicVariant::Struct(_) => VariantType::Struct,
+            DynamicVariant::Tuple(_) => VariantType::Tuple,
+            DynamicVariant::Unit => VariantType::Unit,
+        }
+    }
+
+    fn clone_dynamic(&self) -> DynamicEnum {
+        DynamicEnum {
+            name: self.name.clone(),
+            variant_name: self.variant_name.clone(),
+            variant: self.variant.clone_dynamic(),
+        }
+    }
+}
+
+impl Reflect for DynamicEnum {
+    #[inline]
+    fn type_name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    fn get_type_info(&self) -> &'static TypeInfo {
+        <Self as Typed>::type_info()
+    }
+
+    #[inline]
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn as_reflect(&self) -> &dyn Reflect {
+        self
+    }
+
+    #[inline]
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        self
+    }
+
+    #[inline]
+    fn clone_value(&self) -> Box<dyn Reflect> {
+        Box::new(self.clone_dynamic())
+    }
+
+    #[inline]
+    fn reflect_ref(&self) -> ReflectRef {
+        ReflectRef::Enum(self)
+    }
+
+    #[inline]
+    fn reflect_mut(&mut self) -> ReflectMut {
+        ReflectMut::Enum(self)
+    }
+
+    fn apply(&mut self, value: &dyn Reflect) {
+        if let ReflectRef::Enum(value) = value.reflect_ref() {
+            if Enum::variant_name(self) == value.variant_name() {
+                for (index, field) in value.iter_fields().enumerate() {
+                    let self_field = match field.name() {
+                        Some(name) => Enum::field_mut(self, name),
+                        None => Enum::field_at_mut(self, index),
+                    };
+                    if let Some(self_field) = self_field {
+                        self_field.apply(field.value());
+                    }
+                }
+            } else {
+                let dynamic = DynamicEnum::from_ref(value);
+                self.set_variant(dynamic.variant_name, dynamic.variant);
+            }
+        } else {
+            panic!("Attempted to apply non-enum type to enum type.");
+        }
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+        enum_partial_eq(self, value)
+    }
+
+    fn debug(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DynamicEnum(")?;
+        enum_debug(self, f)?;
+        write!(f, ")")
+    }
+}
+
+impl Debug for DynamicEnum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.debug(f)
+    }
+}
+
+impl Typed for DynamicEnum {
+    fn type_info() -> &'static TypeInfo {
+        static CELL: NonGenericTypeInfoCell = NonGenericTypeInfoCell::new();
+        CELL.get_or_set(|| TypeInfo::Dynamic(DynamicInfo::new::<Self>()))
+    }
+}
+
+/// Compares an [`Enum`] with a [`Reflect`] value.
+///
+/// Returns true if and only if all of the following are true:
+/// - `b` is an enum;
+/// - `b` is the same variant as `a`;
+/// - [`Reflect::reflect_partial_eq`] returns `Some(true)` for the pairwise fields of `a` and `b`.
+///
+/// Returns [`None`] if the comparison couldn't even be performed.
+#[inline]
+pub fn enum_partial_eq<TEnum: Enum + ?Sized>(a: &TEnum, b: &dyn Reflect) -> Option<bool> {
+    let dyn_enum = if let ReflectRef::Enum(dyn_enum) = b.reflect_ref() {
+        dyn_enum
+    } else {
+        return Some(false);
+    };
+
+    if a.variant_name() != dyn_enum.variant_name()
+        || a.variant_type() != dyn_enum.variant_type()
+        || a.field_len() != dyn_enum.field_len()
+    {
+        return Some(false);
+    }
+
+    for (index, field) in dyn_enum.iter_fields().enumerate() {
+        let a_field = match field.name() {
+            Some(name) => a.field(name),
+            None => a.field_at(index),
+        };
+        if let Some(a_field) = a_field {
+            let eq_result = a_field.reflect_partial_eq(field.value());
+            if let failed @ (Some(false) | None) = eq_result {
+                return failed;
+            }
+        } else {
+            return Some(false);
+        }
+    }
+
+    Some(true)
+}
+
+/// The default debug formatter for [`Enum`] types.
+///
+/// # Example
+/// ```
+/// use bevy_reflect::Reflect;
+/// #[derive(Reflect)]
+/// enum MyEnum {
+///     A { value: usize },
+/// }
+///
+/// let my_enum: &dyn Reflect = &MyEnum::A { value: 123 };
+/// println!("{:#?}", my_enum);
+///
+/// // Output:
+///
+/// // A {
+/// //   value: 123,
+/// // }
+/// ```
+#[inline]
+pub fn enum_debug(dyn_enum: &dyn Enum, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match dyn_enum.variant_type() {
+        VariantType::Struct => {
+            let mut debug = f.debug_struct(dyn_enum.variant_name());
+            for field in dyn_enum.iter_fields() {
+                debug.field(field.name().unwrap(), &field.value() as &dyn Debug);
+            }
+            debug.finish()
+        }
+        VariantType::Tuple => {
+            let mut debug = f.debug_tuple(dyn_enum.variant_name());
+            for field in dyn_enum.iter_fields() {
+                debug.field(&field.value() as &dyn Debug);
+            }
+            debug.finish()
+        }
+        VariantType::Unit => f.write_str(dyn_enum.variant_name()),
+    }
+}
@@ -1,8 +1,10 @@
 use crate as bevy_reflect;
 use crate::{
-    map_apply, map_partial_eq, Array, ArrayInfo, ArrayIter, DynamicMap, FromReflect, FromType,
-    GetTypeRegistration, List, ListInfo, Map, MapInfo, MapIter, Reflect, ReflectDeserialize,
-    ReflectMut, ReflectRef, ReflectSerialize, TypeInfo, TypeRegistration, Typed, ValueInfo,
+    enum_partial_eq, map_apply, map_partial_eq, Array, ArrayInfo, ArrayIter, DynamicEnum,
+    DynamicMap, Enum, EnumInfo, FromReflect, FromType, GetTypeRegistration, List, ListInfo, Map,
+    MapInfo, MapIter, Reflect, ReflectDeserialize, ReflectMut, ReflectRef, ReflectSerialize,
+    TupleVariantInfo, TypeInfo, TypeRegistration, Typed, UnitVariantInfo, UnnamedField, ValueInfo,
+    VariantFieldIter, VariantInfo, VariantType,
 };
 
 use crate::utility::{GenericTypeInfoCell, NonGenericTypeInfoCell};
@@ -33,7 +35,6 @@ impl_reflect_value!(isize(Debug, Hash, PartialEq, Serialize, Deserialize));
 impl_reflect_value!(f32(Debug, PartialEq, Serialize, Deserialize));
 impl_reflect_value!(f64(Debug, PartialEq, Serialize, Deserialize));
 impl_reflect_value!(String(Debug, Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(Option<T: Serialize + Clone + for<'de> Deserialize<'de> + Reflect + 'static>(Serialize, Deserialize));
 impl_reflect_value!(HashSet<T: Serialize + Hash + Eq + Clone + for<'de> Deserialize<'de> + Send + Sync + 'static>(Serialize, Deserialize));
 impl_reflect_value!(Range<T: Serialize + Clone + for<'de> Deserialize<'de> + Send + Sync + 'static>(Serialize, Deserialize));
 impl_reflect_value!(Duration(Debug, Hash, PartialEq, Serialize, Deserialize));
@@ -55,9 +56,6 @@ impl_from_reflect_value!(isize);
 impl_from_reflect_value!(f32);
 impl_from_reflect_value!(f64);
 impl_from_reflect_value!(String);
-impl_from_reflect_value!(
-    Option<T: Serialize + Clone + for<'de> Deserialize<'de> + Reflect + 'static>
-);
 impl_from_reflect_value!(
     HashSet<T: Serialize + Hash + Eq + Clone + for<'de> Deserialize<'de> + Send + Sync + 'static>
 );
@@ -103,6 +101,10 @@ impl<T: FromReflect> List for Vec<T> {
         });
         Vec::push(self, value);
     }
+
+    fn pop(&mut self) -> Option<Box<dyn Reflect>> {
+        Vec::pop(self).map(|value| Box::new(value) as Box<dyn Reflect>)
+    }
 }
 
 impl<T: FromReflect> Reflect for Vec<T> {
@@ -256,6 +258,14 @@ impl<K: FromReflect + Eq + Hash, V: FromReflect> Map for HashMap<K, V> {
         self.insert(key, value)
             .map(|old_value| Box::new(old_value) as Box<dyn Reflect>)
     }
+
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let value = match key.downcast_ref::<K>() {
+            Some(key) => HashMap::remove(self, key),
+            None => HashMap::remove(self, &K::from_reflect(key)?),
+        };
+        value.map(|value| Box::new(value) as Box<dyn Reflect>)
+    }
 }
 
 impl<K: FromReflect + Eq + Hash, V: FromReflect> Reflect for HashMap<K, V> {
@@ -492,6 +502,194 @@ impl_array_get_type_registration! {
     30 31 32
 }
 
+impl<T: FromReflect> Enum for Option<T> {
+    fn field(&self, _name: &str) -> Option<&dyn Reflect> {
+        None
+    }
+
+    fn field_at(&self, index: usize) -> Option<&dyn Reflect> {
+        match (self, index) {
+            (Some(value), 0) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn field_mut(&mut self, _name: &str) -> Option<&mut dyn Reflect> {
+        None
+    }
+
+    fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+        match (self, index) {
+            (Some(value), 0) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn index_of(&self, _name: &str) -> Option<usize> {
+        None
+    }
+
+    fn name_at(&self, _index: usize) -> Option<&str> {
+        None
+    }
+
+    fn iter_fields(&self) -> VariantFieldIter {
+        VariantFieldIter::new(self)
+    }
+
+    #[inline]
+    fn field_len(&self) -> usize {
+        match self {
+            Some(..) => 1,
+            None => 0,
+        }
+    }
+
+    #[inline]
+    fn variant_name(&self) -> &str {
+        match self {
+            Some(..) => "Some",
+            None => "None",
+        }
+    }
+
+    #[inline]
+    fn variant_type(&self) -> VariantType {
+        match self {
+            Some(..) => VariantType::Tuple,
+            None => VariantType::Unit,
+        }
+    }
+
+    fn clone_dynamic(&self) -> DynamicEnum {
+        DynamicEnum::from_ref(self)
+    }
+}
+
+impl<T: FromReflect> Reflect for Option<T> {
+    #[inline]
+    fn type_name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    #[inline]
+    fn get_type_info(&self) -> &'static TypeInfo {
+        <Self as Typed>::type_info()
+    }
+
+    #[inline]
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn as_reflect(&self) -> &dyn Reflect {
+        self
+    }
+
+    #[inline]
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        self
+    }
+
+    fn apply(&mut self, value: &dyn Reflect) {
+        if let ReflectRef::Enum(value) = value.reflect_ref() {
+            if Enum::variant_name(self) == value.variant_name() {
+                // Same variant -> apply the field
+                if let (Some(self_field), Some(field)) = (self.field_at_mut(0), value.field_at(0)) {
+                    self_field.apply(field);
+                }
+            } else {
+                // Different variant -> create it from the field
+                *self = match value.variant_name() {
+                    "Some" => Some(value.field_at(0).and_then(T::from_reflect).unwrap_or_else(
+                        || {
+                            panic!(
+                                "Field in `Some` variant of {} should be of type {}",
+                                std::any::type_name::<Option<T>>(),
+                                std::any::type_name::<T>()
+                            )
+                        },
+                    )),
+                    "None" => None,
+                    name => panic!(
+                        "Failed to apply variant `{}` to enum `{}`.",
+                        name,
+                        std::any::type_name::<Self>()
+                    ),
+                };
+            }
+        } else {
+            panic!("Attempted to apply non-enum type to enum type.");
+        }
+    }
+
+    #[inline]
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+
+    fn reflect_ref(&self) -> ReflectRef {
+        ReflectRef::Enum(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut {
+        ReflectMut::Enum(self)
+    }
+
+    #[inline]
+    fn clone_value(&self) -> Box<dyn Reflect> {
+        Box::new(Enum::clone_dynamic(self))
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+        enum_partial_eq(self, value)
+    }
+}
+
+impl<T: FromReflect> FromReflect for Option<T> {
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        if let ReflectRef::Enum(ref_enum) = reflect.reflect_ref() {
+            match ref_enum.variant_name() {
+                "Some" => Some(Some(T::from_reflect(ref_enum.field_at(0)?)?)),
+                "None" => Some(None),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: FromReflect> Typed for Option<T> {
+    fn type_info() -> &'static TypeInfo {
+        static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
+        CELL.get_or_insert::<Self, _>(|| {
+            let none_variant = VariantInfo::Unit(UnitVariantInfo::new("None"));
+            let some_variant =
+                VariantInfo::Tuple(TupleVariantInfo::new("Some", &[UnnamedField::new::<T>(0)]));
+            TypeInfo::Enum(EnumInfo::new::<Self>(&[none_variant, some_variant]))
+        })
+    }
+}
+
+impl<T: FromReflect> GetTypeRegistration for Option<T> {
+    fn get_type_registration() -> TypeRegistration {
+        TypeRegistration::of::<Option<T>>()
+    }
+}
+
 impl Reflect for Cow<'static, str> {
     fn type_name(&self) -> &str {
         std::any::type_name::<Self>()
@@ -593,7 +791,10 @@ impl FromReflect for Cow<'static, str> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Reflect, ReflectSerialize, TypeRegistry};
+    use crate::{
+        Enum, FromReflect, Reflect, ReflectSerialize, TypeInfo, TypeRegistry, VariantInfo,
+        VariantType,
+    };
     use bevy_utils::HashMap;
     use std::f32::consts::{PI, TAU};
 
@@ -669,10 +870,63 @@ mod tests {
     }
 
     #[test]
-    fn should_not_partial_eq_option() {
-        // Option<T> does not contain a `PartialEq` implementation, so it should return `None`
+    fn should_partial_eq_option() {
         let a: &dyn Reflect = &Some(123);
         let b: &dyn Reflect = &Some(123);
-        assert_eq!(None, a.reflect_partial_eq(b));
+        let c: &dyn Reflect = &Some(321);
+        let d: &dyn Reflect = &None::<i32>;
+        assert_eq!(Some(true), a.reflect_partial_eq(b));
+        assert_eq!(Some(false), a.reflect_partial_eq(c));
+        assert_eq!(Some(false), a.reflect_partial_eq(d));
+        assert_eq!(Some(true), d.reflect_partial_eq(&None::<i32>));
+    }
+
+    #[test]
+    fn option_should_impl_enum() {
+        let mut value = Some(123usize);
+
+        assert!(value.reflect_partial_eq(&Some(123usize)).unwrap());
+        assert!(!value.reflect_partial_eq(&Some(321usize)).unwrap());
+
+        assert_eq!("Some", value.variant_name());
+        assert_eq!("core::option::Option<usize>::Some", value.variant_path());
+
+        if value.is_variant(VariantType::Tuple) {
+            if let Some(field) = value
+                .field_at_mut(0)
+                .and_then(|field| field.downcast_mut::<usize>())
+            {
+                *field = 321;
+            }
+        } else {
+            panic!("expected `VariantType::Tuple`");
+        }
+        assert_eq!(Some(321), value);
+
+        value.apply(&None::<usize>);
+        assert_eq!(None, value);
+        value.apply(&Some(1usize).clone_dynamic());
+        assert_eq!(Some(1), value);
+
+        assert_eq!(
+            Some(Some(2usize)),
+            Option::<usize>::from_reflect(&Some(2usize).clone_dynamic())
+        );
+        assert_eq!(
+            Some(None),
+            Option::<usize>::from_reflect(&None::<usize>.clone_dynamic())
+        );
+        assert_eq!(None, Option::<usize>::from_reflect(&2usize));
+
+        if let TypeInfo::Enum(info) = value.get_type_info() {
+            assert_eq!(info.variant_len(), 2);
+            if let Some(VariantInfo::Tuple(variant)) = info.variant("Some") {
+                assert!(variant.field_at(0).unwrap().is::<usize>());
+            } else {
+                panic!("Expected `VariantInfo::Tuple`");
+            }
+        } else {
+            panic!("Expected `TypeInfo::Enum`");
+        }
     }
 }
@@ -1,6 +1,8 @@
 #![doc = include_str!("../README.md")]
 
 mod array;
+mod diff;
+mod enum_trait;
 mod fields;
 mod list;
 mod map;
@@ -34,12 +36,14 @@ pub mod prelude {
     pub use crate::std_traits::*;
     #[doc(hidden)]
     pub use crate::{
-        reflect_trait, GetField, GetTupleStructField, Reflect, ReflectDeserialize,
+        reflect_trait, Enum, GetField, GetTupleStructField, Reflect, ReflectDeserialize,
         ReflectSerialize, Struct, TupleStruct,
     };
 }
 
 pub use array::*;
+pub use diff::*;
+pub use enum_trait::*;
 pub use fields::*;
 pub use impls::*;
 pub use list::*;
@@ -488,6 +492,316 @@ mod tests {
         assert!(foo.reflect_partial_eq(&dynamic_struct).unwrap());
     }
 
+    #[test]
+    fn reflect_enum() {
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        enum Shape {
+            Circle { radius: f32 },
+            Rect(f32, #[reflect(ignore)] u8, f32),
+            Point,
+        }
+
+        let mut shape = Shape::Circle { radius: 1.0 };
+        assert_eq!(shape.variant_name(), "Circle");
+        assert_eq!(shape.variant_type(), VariantType::Struct);
+        assert_eq!(
+            shape.variant_path(),
+            format!("{}::Circle", shape.type_name())
+        );
+        assert_eq!(shape.field_len(), 1);
+        assert_eq!(shape.index_of("radius"), Some(0));
+        assert_eq!(shape.name_at(0), Some("radius"));
+        *shape
+            .field_mut("radius")
+            .unwrap()
+            .downcast_mut::<f32>()
+            .unwrap() = 2.0;
+        assert_eq!(shape, Shape::Circle { radius: 2.0 });
+
+        // Same variant -> the fields are applied
+        let mut dyn_struct = DynamicStruct::default();
+        dyn_struct.insert("radius", 3.0f32);
+        shape.apply(&DynamicEnum::new(
+            std::any::type_name::<Shape>(),
+            "Circle",
+            dyn_struct,
+        ));
+        assert_eq!(shape, Shape::Circle { radius: 3.0 });
+
+        // Different variant -> the variant is created, with the ignored fields set to default
+        shape.apply(&Shape::Rect(1.0, 5, 2.0));
+        assert_eq!(shape, Shape::Rect(1.0, 0, 2.0));
+        assert_eq!(shape.field_len(), 2);
+        assert_eq!(shape.field_at(1).unwrap().downcast_ref::<f32>(), Some(&2.0));
+        assert!(shape.field("0").is_none());
+
+        shape.apply(&Shape::Point);
+        assert_eq!(shape, Shape::Point);
+        assert!(shape.is_variant(VariantType::Unit));
+
+        let dynamic = Shape::Rect(4.0, 0, 5.0).clone_dynamic();
+        assert_eq!(dynamic.name(), std::any::type_name::<Shape>());
+        assert!(dynamic
+            .reflect_partial_eq(&Shape::Rect(4.0, 1, 5.0))
+            .unwrap());
+        assert!(!dynamic.reflect_partial_eq(&Shape::Point).unwrap());
+        assert_eq!(
+            Shape::from_reflect(&dynamic),
+            Some(Shape::Rect(4.0, 0, 5.0))
+        );
+        assert_eq!(Shape::from_reflect(&DynamicStruct::default()), None);
+
+        assert_eq!(
+            format!("{:?}", &Shape::Circle { radius: 1.0 } as &dyn Reflect),
+            "Circle { radius: 1.0 }"
+        );
+
+        #[derive(Reflect, Debug, PartialEq)]
+        enum Maybe<T> {
+            Just(T),
+            Nothing,
+        }
+
+        let mut maybe = Maybe::<String>::Nothing;
+        maybe.apply(&Maybe::Just(String::from("value")));
+        assert_eq!(maybe, Maybe::Just(String::from("value")));
+        if let TypeInfo::Enum(info) = maybe.get_type_info() {
+            assert!(info.is::<Maybe<String>>());
+            assert_eq!(info.variant_len(), 2);
+            assert_eq!(info.index_of("Nothing"), Some(1));
+            if let Some(VariantInfo::Tuple(variant)) = info.variant("Just") {
+                assert!(variant.field_at(0).unwrap().is::<String>());
+            } else {
+                panic!("Expected `VariantInfo::Tuple`");
+            }
+        } else {
+            panic!("Expected `TypeInfo::Enum`");
+        }
+    }
+
+    #[test]
+    fn reflect_enum_serialize() {
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        enum Shape {
+            Circle { radius: f32 },
+            Rect(f32, f32),
+            Point,
+        }
+
+        #[derive(Reflect)]
+        struct Foo {
+            shapes: Vec<Shape>,
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<f32>();
+        registry.register::<Shape>();
+
+        let foo = Foo {
+            shapes: vec![
+                Shape::Circle { radius: 1.0 },
+                Shape::Rect(2.0, 3.0),
+                Shape::Point,
+            ],
+        };
+
+        let serializer = ReflectSerializer::new(&foo, &registry);
+        let serialized = to_string_pretty(&serializer, PrettyConfig::default()).unwrap();
+
+        let mut deserializer = Deserializer::from_str(&serialized).unwrap();
+        let reflect_deserializer = ReflectDeserializer::new(&registry);
+        let value = reflect_deserializer.deserialize(&mut deserializer).unwrap();
+        assert!(foo.reflect_partial_eq(value.as_ref()).unwrap());
+
+        let mut shape = Shape::Point;
+        let mut deserializer = Deserializer::from_str(
+            r#"{
+                "type": "bevy_reflect::tests::reflect_enum_serialize::Shape",
+                "enum": {
+                    "variant": "Circle",
+                    "struct": {
+                        "radius": {
+                            "type": "f32",
+                            "value": 4.0,
+                        },
+                    },
+                },
+            }"#,
+        )
+        .unwrap();
+        let value = ReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        shape.apply(value.as_ref());
+        assert_eq!(shape, Shape::Circle { radius: 4.0 });
+    }
+
+    #[test]
+    fn reflect_enum_round_trip() {
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        enum Shape {
+            Circle { radius: f32 },
+            Rect(f32, f32),
+            Point,
+        }
+
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        enum Outline {
+            Shape(Shape, Option<f32>),
+            Nested { outline: Option<Vec<Shape>> },
+        }
+
+        // Reflected as a value, it keeps its serde representation
+        #[derive(
+            Reflect, FromReflect, Debug, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize,
+        )]
+        #[reflect_value(PartialEq, Serialize, Deserialize)]
+        enum Opaque {
+            Value(u8),
+        }
+
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        struct Foo {
+            outlines: Vec<Outline>,
+            opaque: Opaque,
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<f32>();
+        registry.register::<u8>();
+        registry.register::<Opaque>();
+
+        let foo = Foo {
+            outlines: vec![
+                Outline::Shape(Shape::Circle { radius: 1.0 }, Some(0.5)),
+                Outline::Shape(Shape::Point, None),
+                Outline::Nested {
+                    outline: Some(vec![Shape::Rect(2.0, 3.0), Shape::Point]),
+                },
+                Outline::Nested { outline: None },
+            ],
+            opaque: Opaque::Value(7),
+        };
+
+        let serializer = ReflectSerializer::new(&foo, &registry);
+        let serialized = to_string_pretty(&serializer, PrettyConfig::default()).unwrap();
+        assert!(serialized.contains(r#""value": Value(7)"#));
+
+        let mut deserializer = Deserializer::from_str(&serialized).unwrap();
+        let value = ReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(Foo::from_reflect(value.as_ref()), Some(foo));
+    }
+
+    #[test]
+    fn reflect_dynamic_enum() {
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        enum Shape {
+            Circle { radius: f32 },
+            Rect(f32, f32),
+            Point,
+        }
+
+        let mut dynamic = Shape::Circle { radius: 1.0 }.clone_dynamic();
+        assert_eq!(dynamic.variant_name(), "Circle");
+        assert_eq!(dynamic.variant_type(), VariantType::Struct);
+        assert_eq!(dynamic.index_of("radius"), Some(0));
+        assert_eq!(dynamic.name_at(0), Some("radius"));
+        assert!(dynamic.field_at(1).is_none());
+
+        // Same variant -> the fields are applied
+        dynamic.apply(&Shape::Circle { radius: 2.0 });
+        assert_eq!(
+            dynamic.field("radius").unwrap().downcast_ref::<f32>(),
+            Some(&2.0)
+        );
+
+        // Different variant -> the variant is replaced
+        dynamic.apply(&Shape::Rect(3.0, 4.0));
+        assert_eq!(dynamic.variant_type(), VariantType::Tuple);
+        assert_eq!(dynamic.field_len(), 2);
+        assert!(dynamic.field("radius").is_none());
+        *dynamic
+            .field_at_mut(1)
+            .unwrap()
+            .downcast_mut::<f32>()
+            .unwrap() = 5.0;
+        assert_eq!(Shape::from_reflect(&dynamic), Some(Shape::Rect(3.0, 5.0)));
+
+        dynamic.set_variant("Point", ());
+        assert_eq!(dynamic.field_len(), 0);
+        assert_eq!(dynamic.iter_fields().count(), 0);
+        let mut shape = Shape::Circle { radius: 1.0 };
+        shape.apply(&dynamic);
+        assert_eq!(shape, Shape::Point);
+
+        let cloned = dynamic.clone_value();
+        assert!(cloned.reflect_partial_eq(&Shape::Point).unwrap());
+        assert_eq!(format!("{:?}", cloned), "DynamicEnum(Point)");
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to apply variant `Triangle`")]
+    fn reflect_enum_apply_unknown_variant() {
+        #[derive(Reflect, Debug)]
+        enum Shape {
+            Point,
+        }
+
+        let mut shape = Shape::Point;
+        shape.apply(&DynamicEnum::new(
+            std::any::type_name::<Shape>(),
+            "Triangle",
+            (),
+        ));
+    }
+
+    #[test]
+    fn reflect_enum_partial_eq_and_hash() {
+        #[derive(Reflect, Debug, PartialEq)]
+        enum Shape {
+            Circle { radius: f32 },
+            Rect(f32, f32),
+            Point,
+        }
+
+        // Without registered traits, the fields are compared and the enum isn't hashable
+        let circle = Shape::Circle { radius: 1.0 };
+        assert!(circle
+            .reflect_partial_eq(&Shape::Circle { radius: 1.0 })
+            .unwrap());
+        assert!(!circle
+            .reflect_partial_eq(&Shape::Circle { radius: 2.0 })
+            .unwrap());
+        assert!(!circle.reflect_partial_eq(&Shape::Rect(1.0, 1.0)).unwrap());
+        assert!(!circle.reflect_partial_eq(&1.0f32).unwrap());
+        assert!(circle.reflect_partial_eq(&circle.clone_dynamic()).unwrap());
+        assert!(Shape::Point.reflect_hash().is_none());
+
+        // A field that can't be compared can't compare the whole enum
+        #[derive(Reflect)]
+        enum Unknown {
+            Value(std::ops::Range<u32>),
+        }
+        assert!(Unknown::Value(0..1)
+            .reflect_partial_eq(&Unknown::Value(0..1))
+            .is_none());
+
+        #[derive(Reflect, Hash, PartialEq)]
+        #[reflect(Hash, PartialEq)]
+        enum Key {
+            A(u32),
+            B,
+        }
+
+        assert!(Key::A(1).reflect_partial_eq(&Key::A(1)).unwrap());
+        assert!(!Key::A(1).reflect_partial_eq(&Key::B).unwrap());
+        assert_eq!(Key::A(1).reflect_hash(), Key::A(1).reflect_hash());
+        assert_ne!(Key::A(1).reflect_hash(), Key::A(2).reflect_hash());
+        assert_ne!(Key::A(1).reflect_hash(), Key::B.reflect_hash());
+    }
+
     #[test]
     fn reflect_downcast() {
         #[derive(Reflect, Clone, Debug, PartialEq)]
@@ -830,7 +1144,8 @@ mod tests {
             a_struct: SomeStruct,
             a_tuple_struct: SomeTupleStruct,
             custom: CustomDebug,
-            unknown: Option<String>,
+            an_enum: Option<String>,
+            unknown: std::ops::Range<u32>,
             #[reflect(ignore)]
             #[allow(dead_code)]
             ignored: isize,
@@ -866,7 +1181,8 @@ mod tests {
             },
             a_tuple_struct: SomeTupleStruct(String::from("A Tuple Struct!")),
             custom: CustomDebug,
-            unknown: Some(String::from("Enums aren't supported yet :(")),
+            an_enum: Some(String::from("An enum!")),
+            unknown: 0..1,
             ignored: 321,
         };
 
@@ -894,7 +1210,10 @@ bevy_reflect::tests::should_reflect_debug::Test {
         "A Tuple Struct!",
     ),
     custom: Cool debug!,
-    unknown: Reflect(core::option::Option<alloc::string::String>),
+    an_enum: Some(
+        "An enum!",
+    ),
+    unknown: Reflect(core::ops::range::Range<u32>),
 }"#;
 
         assert_eq!(expected, format!("\n{:#?}", reflected));
@@ -15,6 +15,14 @@ pub trait List: Reflect + Array {
     /// Appends an element to the list.
     fn push(&mut self, value: Box<dyn Reflect>);
 
+    /// Removes the last element from the list and returns it, or [`None`] if it is empty.
+    ///
+    /// The default implementation doesn't support removing elements, and always returns
+    /// [`None`].
+    fn pop(&mut self) -> Option<Box<dyn Reflect>> {
+        None
+    }
+
     /// Clones the list, producing a [`DynamicList`].
     fn clone_dynamic(&self) -> DynamicList {
         DynamicList {
@@ -151,6 +159,10 @@ impl List for DynamicList {
         DynamicList::push_box(self, value);
     }
 
+    fn pop(&mut self) -> Option<Box<dyn Reflect>> {
+        self.values.pop()
+    }
+
     fn clone_dynamic(&self) -> DynamicList {
         DynamicList {
             name: self.name.clone(),
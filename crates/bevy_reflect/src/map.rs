@@ -54,6 +54,16 @@ pub trait Map: Reflect {
         key: Box<dyn Reflect>,
         value: Box<dyn Reflect>,
     ) -> Option<Box<dyn Reflect>>;
+
+    /// Removes an entry from the map.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    /// If the map did have this key present, the removed value is returned.
+    ///
+    /// The default implementation doesn't support removing entries, and always returns [`None`].
+    fn remove(&mut self, _key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        None
+    }
 }
 
 /// A container for compile-time map info.
@@ -226,6 +236,19 @@ impl Map for DynamicMap {
             }
         }
     }
+
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let index = self
+            .indices
+            .remove(&key.reflect_hash().expect(HASH_ERROR))?;
+        let (_key, value) = self.values.remove(index);
+        for other_index in self.indices.values_mut() {
+            if *other_index > index {
+                *other_index -= 1;
+            }
+        }
+        Some(value)
+    }
 }
 
 impl Reflect for DynamicMap {
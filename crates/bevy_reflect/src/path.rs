@@ -1,6 +1,6 @@
 use std::num::ParseIntError;
 
-use crate::{Reflect, ReflectMut, ReflectRef};
+use crate::{Reflect, ReflectMut, ReflectRef, VariantType};
 use thiserror::Error;
 
 /// An error returned from a failed path string query.
@@ -15,7 +15,9 @@ pub enum ReflectPathError<'a> {
         index: usize,
         tuple_struct_index: usize,
     },
-    #[error("the current list doesn't have a value at the given index")]
+    #[error("the current tuple doesn't have a field with the given index")]
+    InvalidTupleIndex { index: usize, tuple_index: usize },
+    #[error("the current list or array doesn't have a value at the given index")]
     InvalidListIndex { index: usize, list_index: usize },
     #[error("encountered an unexpected token")]
     UnexpectedToken { index: usize, token: &'a str },
@@ -36,7 +38,9 @@ pub enum ReflectPathError<'a> {
 /// Path strings use Rust syntax:
 /// - [`Struct`] items are accessed with a dot and a field name: `.field_name`
 /// - [`TupleStruct`] and [`Tuple`] items are accessed with a dot and a number: `.0`
-/// - [`List`] items are accessed with brackets: `[0]`
+/// - [`List`] and [`Array`] items are accessed with brackets: `[0]`
+/// - The fields of the current variant of an [`Enum`] are accessed like the fields of a struct
+///   or of a tuple struct, depending on the variant
 ///
 /// If the initial path element is a field of a struct, tuple struct, or tuple,
 /// the initial '.' may be omitted.
@@ -45,10 +49,14 @@ pub enum ReflectPathError<'a> {
 /// 2-tuples (like a `Vec<(T, U)>`), the path string `foo[3].0` would access tuple
 /// element 0 of element 3 of `foo`.
 ///
+/// When the same path is used many times, it can be parsed once into a [`ParsedPath`].
+///
 /// [`Struct`]: crate::Struct
 /// [`TupleStruct`]: crate::TupleStruct
 /// [`Tuple`]: crate::Tuple
 /// [`List`]: crate::List
+/// [`Array`]: crate::Array
+/// [`Enum`]: crate::Enum
 pub trait GetPath {
     /// Returns a reference to the value specified by `path`.
     ///
@@ -104,65 +112,11 @@ impl<T: Reflect> GetPath for T {
 
 impl GetPath for dyn Reflect {
     fn path<'r, 'p>(&'r self, path: &'p str) -> Result<&'r dyn Reflect, ReflectPathError<'p>> {
-        let mut index = 0;
         let mut current: &dyn Reflect = self;
-        while let Some(token) = next_token(path, &mut index) {
-            let current_index = index;
-            match token {
-                Token::Dot => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        current = read_field(current, value, current_index)?;
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
-                }
-                Token::OpenBracket => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        match current.reflect_ref() {
-                            ReflectRef::List(reflect_list) => {
-                                let list_index = value.parse::<usize>()?;
-                                let list_item = reflect_list.get(list_index).ok_or(
-                                    ReflectPathError::InvalidListIndex {
-                                        index: current_index,
-                                        list_index,
-                                    },
-                                )?;
-                                current = list_item;
-                            }
-                            _ => {
-                                return Err(ReflectPathError::ExpectedList {
-                                    index: current_index,
-                                })
-                            }
-                        }
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
-
-                    if let Some(Token::CloseBracket) = next_token(path, &mut index) {
-                    } else {
-                        return Err(ReflectPathError::ExpectedToken {
-                            index: current_index,
-                            token: "]",
-                        });
-                    }
-                }
-                Token::CloseBracket => {
-                    return Err(ReflectPathError::UnexpectedToken {
-                        index: current_index,
-                        token: "]",
-                    })
-                }
-                Token::Ident(value) => {
-                    current = read_field(current, value, current_index)?;
-                }
-            }
+        for access in PathParser::new(path) {
+            let (access, index) = access?;
+            current = access.read(current, index)?;
         }
-
         Ok(current)
     }
 
@@ -170,124 +124,332 @@ impl GetPath for dyn Reflect {
         &'r mut self,
         path: &'p str,
     ) -> Result<&'r mut dyn Reflect, ReflectPathError<'p>> {
-        let mut index = 0;
         let mut current: &mut dyn Reflect = self;
-        while let Some(token) = next_token(path, &mut index) {
-            let current_index = index;
-            match token {
-                Token::Dot => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        current = read_field_mut(current, value, current_index)?;
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
-                }
-                Token::OpenBracket => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        match current.reflect_mut() {
-                            ReflectMut::List(reflect_list) => {
-                                let list_index = value.parse::<usize>()?;
-                                let list_item = reflect_list.get_mut(list_index).ok_or(
-                                    ReflectPathError::InvalidListIndex {
-                                        index: current_index,
-                                        list_index,
-                                    },
-                                )?;
-                                current = list_item;
-                            }
-                            _ => {
-                                return Err(ReflectPathError::ExpectedStruct {
-                                    index: current_index,
-                                })
-                            }
-                        }
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
+        for access in PathParser::new(path) {
+            let (access, index) = access?;
+            current = access.read_mut(current, index)?;
+        }
+        Ok(current)
+    }
+}
 
-                    if let Some(Token::CloseBracket) = next_token(path, &mut index) {
-                    } else {
-                        return Err(ReflectPathError::ExpectedToken {
-                            index: current_index,
-                            token: "]",
-                        });
-                    }
-                }
-                Token::CloseBracket => {
-                    return Err(ReflectPathError::UnexpectedToken {
-                        index: current_index,
-                        token: "]",
-                    })
-                }
-                Token::Ident(value) => {
-                    current = read_field_mut(current, value, current_index)?;
-                }
-            }
+/// A path string parsed once, to access the same nested value of many reflected values
+/// without parsing the path again, like the fields targeted by an animation.
+///
+/// See [`GetPath`] for the syntax of the path strings.
+///
+/// ```
+/// # use bevy_reflect::{ParsedPath, Reflect};
+/// #[derive(Reflect)]
+/// struct Foo {
+///     bar: Vec<(f32, f32)>,
+/// }
+///
+/// let path = ParsedPath::parse("bar[1].0").unwrap();
+/// let mut foo = Foo {
+///     bar: vec![(0.0, 1.0), (2.0, 3.0)],
+/// };
+/// assert_eq!(path.get_element::<f32>(&foo), Ok(&2.0));
+/// *path.get_element_mut::<f32>(&mut foo).unwrap() = 4.0;
+/// assert_eq!(foo.bar[1].0, 4.0);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParsedPath(Box<[(Access, usize)]>);
+
+impl ParsedPath {
+    /// Parses a path string, see [`GetPath`] for its syntax.
+    pub fn parse(path: &str) -> Result<Self, ReflectPathError<'_>> {
+        let accesses = PathParser::new(path)
+            .map(|access| access.map(|(access, index)| (access.to_owned(), index)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ParsedPath(accesses.into_boxed_slice()))
+    }
+
+    /// Returns a reference to the value at this path in `root`.
+    pub fn element<'r, 'p>(
+        &'p self,
+        root: &'r dyn Reflect,
+    ) -> Result<&'r dyn Reflect, ReflectPathError<'p>> {
+        let mut current = root;
+        for (access, index) in self.0.iter() {
+            current = access.as_ref().read(current, *index)?;
         }
+        Ok(current)
+    }
 
+    /// Returns a mutable reference to the value at this path in `root`.
+    pub fn element_mut<'r, 'p>(
+        &'p self,
+        root: &'r mut dyn Reflect,
+    ) -> Result<&'r mut dyn Reflect, ReflectPathError<'p>> {
+        let mut current = root;
+        for (access, index) in self.0.iter() {
+            current = access.as_ref().read_mut(current, *index)?;
+        }
         Ok(current)
     }
+
+    /// Returns a statically typed reference to the value at this path in `root`.
+    pub fn get_element<'r, 'p, T: Reflect>(
+        &'p self,
+        root: &'r dyn Reflect,
+    ) -> Result<&'r T, ReflectPathError<'p>> {
+        self.element(root).and_then(|p| {
+            p.downcast_ref::<T>()
+                .ok_or(ReflectPathError::InvalidDowncast)
+        })
+    }
+
+    /// Returns a statically typed mutable reference to the value at this path in `root`.
+    pub fn get_element_mut<'r, 'p, T: Reflect>(
+        &'p self,
+        root: &'r mut dyn Reflect,
+    ) -> Result<&'r mut T, ReflectPathError<'p>> {
+        self.element_mut(root).and_then(|p| {
+            p.downcast_mut::<T>()
+                .ok_or(ReflectPathError::InvalidDowncast)
+        })
+    }
+}
+
+/// A single step of a parsed path.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Access {
+    Field(String),
+    ListIndex(usize),
+}
+
+impl Access {
+    fn as_ref(&self) -> AccessRef<'_> {
+        match self {
+            Access::Field(field) => AccessRef::Field(field),
+            Access::ListIndex(list_index) => AccessRef::ListIndex(*list_index),
+        }
+    }
+}
+
+/// A single step of a path, borrowing from the path string.
+#[derive(Clone, Copy)]
+enum AccessRef<'a> {
+    Field(&'a str),
+    ListIndex(usize),
 }
 
-fn read_field<'r, 'p>(
-    current: &'r dyn Reflect,
-    field: &'p str,
-    current_index: usize,
-) -> Result<&'r dyn Reflect, ReflectPathError<'p>> {
-    match current.reflect_ref() {
-        ReflectRef::Struct(reflect_struct) => {
-            Ok(reflect_struct
+impl<'a> AccessRef<'a> {
+    fn to_owned(self) -> Access {
+        match self {
+            AccessRef::Field(field) => Access::Field(field.to_string()),
+            AccessRef::ListIndex(list_index) => Access::ListIndex(list_index),
+        }
+    }
+
+    fn read<'r>(
+        self,
+        current: &'r dyn Reflect,
+        current_index: usize,
+    ) -> Result<&'r dyn Reflect, ReflectPathError<'a>> {
+        match (self, current.reflect_ref()) {
+            (AccessRef::Field(field), ReflectRef::Struct(reflect_struct)) => reflect_struct
                 .field(field)
                 .ok_or(ReflectPathError::InvalidField {
                     index: current_index,
                     field,
-                })?)
-        }
-        ReflectRef::TupleStruct(reflect_struct) => {
-            let tuple_index = field.parse::<usize>()?;
-            Ok(reflect_struct.field(tuple_index).ok_or(
-                ReflectPathError::InvalidTupleStructIndex {
+                }),
+            (AccessRef::Field(field), ReflectRef::TupleStruct(reflect_struct)) => {
+                let tuple_index = field.parse::<usize>()?;
+                reflect_struct
+                    .field(tuple_index)
+                    .ok_or(ReflectPathError::InvalidTupleStructIndex {
+                        index: current_index,
+                        tuple_struct_index: tuple_index,
+                    })
+            }
+            (AccessRef::Field(field), ReflectRef::Tuple(reflect_tuple)) => {
+                let tuple_index = field.parse::<usize>()?;
+                reflect_tuple
+                    .field(tuple_index)
+                    .ok_or(ReflectPathError::InvalidTupleIndex {
+                        index: current_index,
+                        tuple_index,
+                    })
+            }
+            (AccessRef::Field(field), ReflectRef::Enum(reflect_enum)) => {
+                match reflect_enum.variant_type() {
+                    VariantType::Tuple => {
+                        let tuple_index = field.parse::<usize>()?;
+                        reflect_enum.field_at(tuple_index).ok_or(
+                            ReflectPathError::InvalidTupleIndex {
+                                index: current_index,
+                                tuple_index,
+                            },
+                        )
+                    }
+                    _ => reflect_enum
+                        .field(field)
+                        .ok_or(ReflectPathError::InvalidField {
+                            index: current_index,
+                            field,
+                        }),
+                }
+            }
+            (AccessRef::Field(_), _) => Err(ReflectPathError::ExpectedStruct {
+                index: current_index,
+            }),
+            (AccessRef::ListIndex(list_index), ReflectRef::List(reflect_list)) => reflect_list
+                .get(list_index)
+                .ok_or(ReflectPathError::InvalidListIndex {
                     index: current_index,
-                    tuple_struct_index: tuple_index,
-                },
-            )?)
+                    list_index,
+                }),
+            (AccessRef::ListIndex(list_index), ReflectRef::Array(reflect_array)) => reflect_array
+                .get(list_index)
+                .ok_or(ReflectPathError::InvalidListIndex {
+                    index: current_index,
+                    list_index,
+                }),
+            (AccessRef::ListIndex(_), _) => Err(ReflectPathError::ExpectedList {
+                index: current_index,
+            }),
         }
-        _ => Err(ReflectPathError::ExpectedStruct {
-            index: current_index,
-        }),
     }
-}
 
-fn read_field_mut<'r, 'p>(
-    current: &'r mut dyn Reflect,
-    field: &'p str,
-    current_index: usize,
-) -> Result<&'r mut dyn Reflect, ReflectPathError<'p>> {
-    match current.reflect_mut() {
-        ReflectMut::Struct(reflect_struct) => {
-            Ok(reflect_struct
+    fn read_mut<'r>(
+        self,
+        current: &'r mut dyn Reflect,
+        current_index: usize,
+    ) -> Result<&'r mut dyn Reflect, ReflectPathError<'a>> {
+        match (self, current.reflect_mut()) {
+            (AccessRef::Field(field), ReflectMut::Struct(reflect_struct)) => reflect_struct
                 .field_mut(field)
                 .ok_or(ReflectPathError::InvalidField {
                     index: current_index,
                     field,
-                })?)
-        }
-        ReflectMut::TupleStruct(reflect_struct) => {
-            let tuple_index = field.parse::<usize>()?;
-            Ok(reflect_struct.field_mut(tuple_index).ok_or(
-                ReflectPathError::InvalidTupleStructIndex {
+                }),
+            (AccessRef::Field(field), ReflectMut::TupleStruct(reflect_struct)) => {
+                let tuple_index = field.parse::<usize>()?;
+                reflect_struct.field_mut(tuple_index).ok_or(
+                    ReflectPathError::InvalidTupleStructIndex {
+                        index: current_index,
+                        tuple_struct_index: tuple_index,
+                    },
+                )
+            }
+            (AccessRef::Field(field), ReflectMut::Tuple(reflect_tuple)) => {
+                let tuple_index = field.parse::<usize>()?;
+                reflect_tuple
+                    .field_mut(tuple_index)
+                    .ok_or(ReflectPathError::InvalidTupleIndex {
+                        index: current_index,
+                        tuple_index,
+                    })
+            }
+            (AccessRef::Field(field), ReflectMut::Enum(reflect_enum)) => {
+                match reflect_enum.variant_type() {
+                    VariantType::Tuple => {
+                        let tuple_index = field.parse::<usize>()?;
+                        reflect_enum.field_at_mut(tuple_index).ok_or(
+                            ReflectPathError::InvalidTupleIndex {
+                                index: current_index,
+                                tuple_index,
+                            },
+                        )
+                    }
+                    _ => reflect_enum
+                        .field_mut(field)
+                        .ok_or(ReflectPathError::InvalidField {
+                            index: current_index,
+                            field,
+                        }),
+                }
+            }
+            (AccessRef::Field(_), _) => Err(ReflectPathError::ExpectedStruct {
+                index: current_index,
+            }),
+            (AccessRef::ListIndex(list_index), ReflectMut::List(reflect_list)) => reflect_list
+                .get_mut(list_index)
+                .ok_or(ReflectPathError::InvalidListIndex {
                     index: current_index,
-                    tuple_struct_index: tuple_index,
-                },
-            )?)
+                    list_index,
+                }),
+            (AccessRef::ListIndex(list_index), ReflectMut::Array(reflect_array)) => reflect_array
+                .get_mut(list_index)
+                .ok_or(ReflectPathError::InvalidListIndex {
+                    index: current_index,
+                    list_index,
+                }),
+            (AccessRef::ListIndex(_), _) => Err(ReflectPathError::ExpectedList {
+                index: current_index,
+            }),
         }
-        _ => Err(ReflectPathError::ExpectedStruct {
-            index: current_index,
-        }),
+    }
+}
+
+/// An iterator over the [accesses](AccessRef) of a path string, with the index of each
+/// access in the string.
+struct PathParser<'a> {
+    path: &'a str,
+    index: usize,
+}
+
+impl<'a> PathParser<'a> {
+    fn new(path: &'a str) -> Self {
+        PathParser { path, index: 0 }
+    }
+
+    fn next_access(&mut self) -> Option<Result<(AccessRef<'a>, usize), ReflectPathError<'a>>> {
+        let token = next_token(self.path, &mut self.index)?;
+        let current_index = self.index;
+        let access = match token {
+            Token::Dot => {
+                if let Some(Token::Ident(value)) = next_token(self.path, &mut self.index) {
+                    Ok(AccessRef::Field(value))
+                } else {
+                    Err(ReflectPathError::ExpectedIdent {
+                        index: current_index,
+                    })
+                }
+            }
+            Token::OpenBracket => {
+                let list_index =
+                    if let Some(Token::Ident(value)) = next_token(self.path, &mut self.index) {
+                        value.parse::<usize>().map_err(ReflectPathError::from)
+                    } else {
+                        Err(ReflectPathError::ExpectedIdent {
+                            index: current_index,
+                        })
+                    };
+
+                match (list_index, next_token(self.path, &mut self.index)) {
+                    (Ok(list_index), Some(Token::CloseBracket)) => {
+                        Ok(AccessRef::ListIndex(list_index))
+                    }
+                    (Ok(_), _) => Err(ReflectPathError::ExpectedToken {
+                        index: current_index,
+                        token: "]",
+                    }),
+                    (Err(err), _) => Err(err),
+                }
+            }
+            Token::CloseBracket => Err(ReflectPathError::UnexpectedToken {
+                index: current_index,
+                token: "]",
+            }),
+            Token::Ident(value) => Ok(AccessRef::Field(value)),
+        };
+        Some(access.map(|access| (access, current_index)))
+    }
+}
+
+impl<'a> Iterator for PathParser<'a> {
+    type Item = Result<(AccessRef<'a>, usize), ReflectPathError<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let access = self.next_access();
+        if let Some(Err(_)) = access {
+            // Stop parsing after the first error
+            self.index = self.path.len();
+        }
+        access
     }
 }
 
@@ -415,4 +577,149 @@ mod tests {
             Err(ReflectPathError::IndexParseError(_))
         ));
     }
+
+    #[test]
+    fn reflect_path_nested_containers() {
+        #[derive(Reflect, FromReflect)]
+        enum Value {
+            None,
+            Pair(f32, f32),
+            Named { value: (u8, [u16; 3]) },
+        }
+
+        #[derive(Reflect, FromReflect)]
+        struct Style {
+            color: [f32; 4],
+        }
+
+        #[derive(Reflect, FromReflect)]
+        struct Section {
+            value: String,
+            style: Style,
+        }
+
+        #[derive(Reflect)]
+        struct Text {
+            sections: Vec<Section>,
+            values: Vec<Value>,
+        }
+
+        let section = |value: &str| Section {
+            value: value.to_string(),
+            style: Style {
+                color: [1.0, 1.0, 1.0, 1.0],
+            },
+        };
+        let mut text = Text {
+            sections: vec![section("a"), section("b"), section("c")],
+            values: vec![
+                Value::None,
+                Value::Pair(1.0, 2.0),
+                Value::Named {
+                    value: (3, [4, 5, 6]),
+                },
+            ],
+        };
+
+        *text
+            .get_path_mut::<[f32; 4]>("sections[2].style.color")
+            .unwrap() = [1.0, 0.0, 0.0, 1.0];
+        assert_eq!(text.sections[2].style.color, [1.0, 0.0, 0.0, 1.0]);
+        *text
+            .get_path_mut::<f32>("sections[1].style.color[3]")
+            .unwrap() = 0.5;
+        assert_eq!(text.sections[1].style.color[3], 0.5);
+        assert_eq!(text.get_path::<String>("sections[0].value").unwrap(), "a");
+
+        assert_eq!(*text.get_path::<f32>("values[1].1").unwrap(), 2.0);
+        assert_eq!(*text.get_path::<u8>("values[2].value.0").unwrap(), 3);
+        *text.get_path_mut::<u16>("values[2].value.1[2]").unwrap() = 7;
+        assert!(matches!(
+            text.values[2],
+            Value::Named {
+                value: (3, [4, 5, 7])
+            }
+        ));
+
+        assert_eq!(
+            text.path("sections[3]").err().unwrap(),
+            ReflectPathError::InvalidListIndex {
+                index: 9,
+                list_index: 3
+            }
+        );
+        assert_eq!(
+            text.path("sections[0].style.color[4]").err().unwrap(),
+            ReflectPathError::InvalidListIndex {
+                index: 24,
+                list_index: 4
+            }
+        );
+        assert_eq!(
+            text.path("values[1].2").err().unwrap(),
+            ReflectPathError::InvalidTupleIndex {
+                index: 10,
+                tuple_index: 2
+            }
+        );
+        assert_eq!(
+            text.path("values[0].value").err().unwrap(),
+            ReflectPathError::InvalidField {
+                index: 10,
+                field: "value"
+            }
+        );
+        assert_eq!(
+            text.path("values[2].value.2").err().unwrap(),
+            ReflectPathError::InvalidTupleIndex {
+                index: 16,
+                tuple_index: 2
+            }
+        );
+        assert_eq!(
+            text.path("sections[0").err().unwrap(),
+            ReflectPathError::ExpectedToken {
+                index: 9,
+                token: "]"
+            }
+        );
+    }
+
+    #[test]
+    fn parsed_path() {
+        #[derive(Reflect)]
+        struct A {
+            x: Vec<(f32, usize)>,
+        }
+
+        let path = ParsedPath::parse("x[1].0").unwrap();
+
+        let mut a = A {
+            x: vec![(1.0, 2), (3.0, 4)],
+        };
+        assert_eq!(*path.get_element::<f32>(&a).unwrap(), 3.0);
+        *path.get_element_mut::<f32>(&mut a).unwrap() = 5.0;
+        assert_eq!(a.x[1].0, 5.0);
+        assert_eq!(
+            path.get_element::<usize>(&a).err().unwrap(),
+            ReflectPathError::InvalidDowncast
+        );
+
+        let path = ParsedPath::parse("x[2]").unwrap();
+        assert_eq!(
+            path.element(&a).err().unwrap(),
+            ReflectPathError::InvalidListIndex {
+                index: 2,
+                list_index: 2
+            }
+        );
+
+        assert_eq!(
+            ParsedPath::parse("x]").err().unwrap(),
+            ReflectPathError::UnexpectedToken {
+                index: 2,
+                token: "]"
+            }
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use crate::{
-    array_debug, list_debug, map_debug, serde::Serializable, struct_debug, tuple_debug,
-    tuple_struct_debug, Array, List, Map, Struct, Tuple, TupleStruct, TypeInfo, Typed, ValueInfo,
+    array_debug, enum_debug, list_debug, map_debug, serde::Serializable, struct_debug, tuple_debug,
+    tuple_struct_debug, Array, Enum, List, Map, ReflectPatch, ReflectPatchError, Struct, Tuple,
+    TupleStruct, TypeInfo, Typed, ValueInfo,
 };
 use std::{
     any::{self, Any, TypeId},
@@ -23,6 +24,7 @@ pub enum ReflectRef<'a> {
     List(&'a dyn List),
     Array(&'a dyn Array),
     Map(&'a dyn Map),
+    Enum(&'a dyn Enum),
     Value(&'a dyn Reflect),
 }
 
@@ -39,16 +41,17 @@ pub enum ReflectMut<'a> {
     List(&'a mut dyn List),
     Array(&'a mut dyn Array),
     Map(&'a mut dyn Map),
+    Enum(&'a mut dyn Enum),
     Value(&'a mut dyn Reflect),
 }
 
 /// A reflected Rust type.
 ///
 /// Methods for working with particular kinds of Rust type are available using the [`List`], [`Map`],
-/// [`Struct`], [`TupleStruct`], and [`Tuple`] subtraits.
+/// [`Struct`], [`TupleStruct`], [`Tuple`], and [`Enum`] subtraits.
 ///
-/// When using `#[derive(Reflect)]` with a struct or tuple struct, the suitable subtrait for that
-/// type (`Struct` or `TupleStruct`) is derived automatically.
+/// When using `#[derive(Reflect)]` with a struct, tuple struct or enum, the suitable subtrait for
+/// that type (`Struct`, `TupleStruct` or `Enum`) is derived automatically.
 pub trait Reflect: Any + Send + Sync {
     /// Returns the [type name][std::any::type_name] of the underlying type.
     fn type_name(&self) -> &str;
@@ -155,6 +158,20 @@ pub trait Reflect: Any + Send + Sync {
         None
     }
 
+    /// Returns a [`ReflectPatch`] containing the changes turning this value into `value`.
+    ///
+    /// See [`ReflectPatch`] for more details.
+    fn diff(&self, value: &dyn Reflect) -> ReflectPatch {
+        ReflectPatch::diff(self.as_reflect(), value)
+    }
+
+    /// Applies a [`ReflectPatch`] returned by [`Reflect::diff`] to this value.
+    ///
+    /// See [`ReflectPatch::apply`] for more details.
+    fn apply_patch(&mut self, patch: &ReflectPatch) -> Result<(), ReflectPatchError> {
+        patch.apply(self.as_reflect_mut())
+    }
+
     /// Debug formatter for the value.
     ///
     /// Any value that is not an implementor of other `Reflect` subtraits
@@ -170,6 +187,7 @@ pub trait Reflect: Any + Send + Sync {
             ReflectRef::List(dyn_list) => list_debug(dyn_list, f),
             ReflectRef::Array(dyn_array) => array_debug(dyn_array, f),
             ReflectRef::Map(dyn_map) => map_debug(dyn_map, f),
+            ReflectRef::Enum(dyn_enum) => enum_debug(dyn_enum, f),
             _ => write!(f, "Reflect({})", self.type_name()),
         }
     }
@@ -1,6 +1,7 @@
 use crate::{
-    serde::type_fields, DynamicArray, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
-    DynamicTupleStruct, Map, Reflect, ReflectDeserialize, TypeRegistry,
+    serde::type_fields, DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicStruct,
+    DynamicTuple, DynamicTupleStruct, DynamicVariant, Map, Reflect, ReflectDeserialize,
+    TypeRegistry,
 };
 use erased_serde::Deserializer;
 use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
@@ -203,6 +204,16 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
                     })?;
                     return Ok(Box::new(array));
                 }
+                type_fields::ENUM => {
+                    let type_name = type_name
+                        .take()
+                        .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
+                    let mut dynamic_enum = map.next_value_seed(EnumDeserializer {
+                        registry: self.registry,
+                    })?;
+                    dynamic_enum.set_name(type_name);
+                    return Ok(Box::new(dynamic_enum));
+                }
                 type_fields::VALUE => {
                     let type_name = type_name
                         .take()
@@ -507,3 +518,73 @@ impl<'a, 'de> Visitor<'de> for TupleVisitor<'a> {
         Ok(tuple)
     }
 }
+
+struct EnumDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EnumDeserializer<'a> {
+    type Value = DynamicEnum;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(EnumVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct EnumVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
+    type Value = DynamicEnum;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("enum value")
+    }
+
+    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let mut variant_name: Option<String> = None;
+        let mut variant = DynamicVariant::Unit;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                type_fields::VARIANT => {
+                    variant_name = Some(map.next_value()?);
+                }
+                type_fields::STRUCT => {
+                    variant = DynamicVariant::Struct(map.next_value_seed(StructDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                type_fields::TUPLE => {
+                    variant = DynamicVariant::Tuple(map.next_value_seed(TupleDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                _ => {
+                    return Err(de::Error::unknown_field(
+                        key.as_str(),
+                        &[
+                            type_fields::VARIANT,
+                            type_fields::STRUCT,
+                            type_fields::TUPLE,
+                        ],
+                    ))
+                }
+            }
+        }
+
+        let variant_name =
+            variant_name.ok_or_else(|| de::Error::missing_field(type_fields::VARIANT))?;
+        let mut dynamic_enum = DynamicEnum::default();
+        dynamic_enum.set_variant(variant_name, variant);
+        Ok(dynamic_enum)
+    }
+}
@@ -12,5 +12,7 @@ pub(crate) mod type_fields {
     pub const TUPLE: &str = "tuple";
     pub const LIST: &str = "list";
     pub const ARRAY: &str = "array";
+    pub const ENUM: &str = "enum";
+    pub const VARIANT: &str = "variant";
     pub const VALUE: &str = "value";
 }
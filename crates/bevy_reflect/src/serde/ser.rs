@@ -1,6 +1,6 @@
 use crate::{
-    serde::type_fields, Array, List, Map, Reflect, ReflectRef, ReflectSerialize, Struct, Tuple,
-    TupleStruct, TypeRegistry,
+    serde::type_fields, Array, Enum, List, Map, Reflect, ReflectRef, ReflectSerialize, Struct,
+    Tuple, TupleStruct, TypeRegistry, VariantType,
 };
 use serde::{
     ser::{SerializeMap, SerializeSeq},
@@ -84,6 +84,11 @@ impl<'a> Serialize for ReflectSerializer<'a> {
                 registry: self.registry,
             }
             .serialize(serializer),
+            ReflectRef::Enum(value) => EnumSerializer {
+                enum_value: value,
+                registry: self.registry,
+            }
+            .serialize(serializer),
             ReflectRef::Value(value) => ReflectValueSerializer {
                 registry: self.registry,
                 value,
@@ -366,3 +371,96 @@ impl<'a> Serialize for ArrayValueSerializer<'a> {
         state.end()
     }
 }
+
+pub struct EnumSerializer<'a> {
+    pub enum_value: &'a dyn Enum,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for EnumSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(2))?;
+        state.serialize_entry(type_fields::TYPE, self.enum_value.type_name())?;
+        state.serialize_entry(
+            type_fields::ENUM,
+            &EnumValueSerializer {
+                enum_value: self.enum_value,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+/// Serializes the current variant of an enum as its name, followed by its fields as a map for
+/// struct variants or as a sequence for tuple variants.
+pub struct EnumValueSerializer<'a> {
+    pub enum_value: &'a dyn Enum,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for EnumValueSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant_type = self.enum_value.variant_type();
+        let len = if variant_type == VariantType::Unit {
+            1
+        } else {
+            2
+        };
+        let mut state = serializer.serialize_map(Some(len))?;
+        state.serialize_entry(type_fields::VARIANT, self.enum_value.variant_name())?;
+        match variant_type {
+            VariantType::Struct => state.serialize_entry(
+                type_fields::STRUCT,
+                &VariantFieldsSerializer {
+                    enum_value: self.enum_value,
+                    registry: self.registry,
+                },
+            )?,
+            VariantType::Tuple => state.serialize_entry(
+                type_fields::TUPLE,
+                &VariantFieldsSerializer {
+                    enum_value: self.enum_value,
+                    registry: self.registry,
+                },
+            )?,
+            VariantType::Unit => {}
+        }
+        state.end()
+    }
+}
+
+pub struct VariantFieldsSerializer<'a> {
+    pub enum_value: &'a dyn Enum,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for VariantFieldsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.enum_value.variant_type() == VariantType::Struct {
+            let mut state = serializer.serialize_map(Some(self.enum_value.field_len()))?;
+            for field in self.enum_value.iter_fields() {
+                state.serialize_entry(
+                    field.name().unwrap(),
+                    &ReflectSerializer::new(field.value(), self.registry),
+                )?;
+            }
+            state.end()
+        } else {
+            let mut state = serializer.serialize_seq(Some(self.enum_value.field_len()))?;
+            for field in self.enum_value.iter_fields() {
+                state.serialize_element(&ReflectSerializer::new(field.value(), self.registry))?;
+            }
+            state.end()
+        }
+    }
+}
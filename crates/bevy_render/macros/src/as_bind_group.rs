@@ -4,20 +4,24 @@ use proc_macro2::{Ident, Span};
 use quote::quote;
 use syn::{
     parse::ParseStream, parse_macro_input, token::Comma, Data, DataStruct, DeriveInput, Field,
-    Fields, LitInt,
+    Fields, LitInt, Token,
 };
 
 const BINDING_ATTRIBUTE_NAME: &str = "binding";
 const UNIFORM_ATTRIBUTE_NAME: &str = "uniform";
 const TEXTURE_ATTRIBUTE_NAME: &str = "texture";
 const SAMPLER_ATTRIBUTE_NAME: &str = "sampler";
+const STORAGE_ATTRIBUTE_NAME: &str = "storage";
+const STORAGE_TEXTURE_ATTRIBUTE_NAME: &str = "storage_texture";
 const BIND_GROUP_DATA_ATTRIBUTE_NAME: &str = "bind_group_data";
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 enum BindingType {
     Uniform,
     Texture,
     Sampler,
+    Storage { read_only: bool },
+    StorageTexture { image_format: Ident, access: Ident },
 }
 
 #[derive(Clone)]
@@ -130,27 +134,68 @@ pub fn derive_as_bind_group(input: TokenStream) -> TokenStream {
                 continue;
             };
 
-            let binding_type = if attr_ident == UNIFORM_ATTRIBUTE_NAME {
-                BindingType::Uniform
-            } else if attr_ident == TEXTURE_ATTRIBUTE_NAME {
-                BindingType::Texture
-            } else if attr_ident == SAMPLER_ATTRIBUTE_NAME {
-                BindingType::Sampler
-            } else {
+            if ![
+                UNIFORM_ATTRIBUTE_NAME,
+                TEXTURE_ATTRIBUTE_NAME,
+                SAMPLER_ATTRIBUTE_NAME,
+                STORAGE_ATTRIBUTE_NAME,
+                STORAGE_TEXTURE_ATTRIBUTE_NAME,
+            ]
+            .iter()
+            .any(|name| attr_ident == name)
+            {
                 continue;
-            };
+            }
 
-            let binding_index = attr
+            // The binding index, followed by options like `read_only` or `access = ReadWrite`
+            let (binding_index, options) = attr
                 .parse_args_with(|input: ParseStream| {
                     let binding_index = input
                         .parse::<LitInt>()
                         .and_then(|i| i.base10_parse::<u32>())
                         .expect("binding index was not a valid u32");
-                    Ok(binding_index)
+                    let mut options = Vec::new();
+                    while input.parse::<Comma>().is_ok() {
+                        let name = input.parse::<Ident>()?;
+                        let value = if input.parse::<Token![=]>().is_ok() {
+                            Some(input.parse::<Ident>()?)
+                        } else {
+                            None
+                        };
+                        options.push((name, value));
+                    }
+                    Ok((binding_index, options))
                 })
                 .unwrap_or_else(|_| {
                     panic!("Invalid `{}` attribute format", BINDING_ATTRIBUTE_NAME)
                 });
+            let option = |name: &str| {
+                options
+                    .iter()
+                    .find(|(option, _)| option == name)
+                    .map(|(_, value)| value.clone())
+            };
+
+            let binding_type = if attr_ident == UNIFORM_ATTRIBUTE_NAME {
+                BindingType::Uniform
+            } else if attr_ident == TEXTURE_ATTRIBUTE_NAME {
+                BindingType::Texture
+            } else if attr_ident == SAMPLER_ATTRIBUTE_NAME {
+                BindingType::Sampler
+            } else if attr_ident == STORAGE_ATTRIBUTE_NAME {
+                BindingType::Storage {
+                    read_only: option("read_only").is_some(),
+                }
+            } else {
+                BindingType::StorageTexture {
+                    image_format: option("image_format").flatten().unwrap_or_else(|| {
+                        panic!("`{STORAGE_TEXTURE_ATTRIBUTE_NAME}` attributes must have an `image_format = FORMAT` option")
+                    }),
+                    access: option("access")
+                        .flatten()
+                        .unwrap_or_else(|| Ident::new("ReadWrite", Span::call_site())),
+                }
+            };
 
             let field_name = field.ident.as_ref().unwrap();
             let required_len = binding_index as usize + 1;
@@ -175,7 +220,7 @@ pub fn derive_as_bind_group(input: TokenStream) -> TokenStream {
                                 }
                             });
                             BindingState::Occupied {
-                            binding_type,
+                            binding_type: binding_type.clone(),
                             ident: field_name,
                         }},
                     }
@@ -245,6 +290,54 @@ pub fn derive_as_bind_group(input: TokenStream) -> TokenStream {
                         }
                     });
                 }
+                BindingType::Storage { read_only } => {
+                    binding_impls.push(quote! {
+                        #render_path::render_resource::OwnedBindingResource::Buffer(self.#field_name.clone())
+                    });
+
+                    let visibility = if read_only {
+                        quote! { #render_path::render_resource::ShaderStages::all() }
+                    } else {
+                        // Writable storage buffers can't be bound in vertex shaders
+                        quote! { #render_path::render_resource::ShaderStages::FRAGMENT | #render_path::render_resource::ShaderStages::COMPUTE }
+                    };
+                    binding_layouts.push(quote!{
+                        #render_path::render_resource::BindGroupLayoutEntry {
+                            binding: #binding_index,
+                            visibility: #visibility,
+                            ty: #render_path::render_resource::BindingType::Buffer {
+                                ty: #render_path::render_resource::BufferBindingType::Storage { read_only: #read_only },
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        }
+                    });
+                }
+                BindingType::StorageTexture {
+                    image_format,
+                    access,
+                } => {
+                    binding_impls.push(quote! {
+                        #render_path::render_resource::OwnedBindingResource::TextureView({
+                            let handle: &#asset_path::Handle<#render_path::texture::Image> = &self.#field_name;
+                            images.get(handle).ok_or_else(|| #render_path::render_resource::AsBindGroupError::RetryNextUpdate)?.texture_view.clone()
+                        })
+                    });
+
+                    binding_layouts.push(quote!{
+                        #render_path::render_resource::BindGroupLayoutEntry {
+                            binding: #binding_index,
+                            visibility: #render_path::render_resource::ShaderStages::FRAGMENT | #render_path::render_resource::ShaderStages::COMPUTE,
+                            ty: #render_path::render_resource::BindingType::StorageTexture {
+                                access: #render_path::render_resource::StorageTextureAccess::#access,
+                                format: #render_path::render_resource::TextureFormat::#image_format,
+                                view_dimension: #render_path::render_resource::TextureViewDimension::D2,
+                            },
+                            count: None,
+                        }
+                    });
+                }
             }
         }
     }
@@ -16,7 +16,10 @@ pub fn derive_extract_resource(input: TokenStream) -> TokenStream {
     extract_resource::derive_extract_resource(input)
 }
 
-#[proc_macro_derive(AsBindGroup, attributes(uniform, texture, sampler, bind_group_data))]
+#[proc_macro_derive(
+    AsBindGroup,
+    attributes(uniform, texture, sampler, storage, storage_texture, bind_group_data)
+)]
 pub fn derive_as_bind_group(input: TokenStream) -> TokenStream {
     as_bind_group::derive_as_bind_group(input)
 }
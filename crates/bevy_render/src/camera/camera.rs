@@ -17,8 +17,8 @@ use bevy_ecs::{
     reflect::ReflectComponent,
     system::{Commands, ParamSet, Query, Res},
 };
-use bevy_math::{Mat4, UVec2, Vec2, Vec3};
-use bevy_reflect::prelude::*;
+use bevy_math::{Mat4, Ray, UVec2, Vec2, Vec3};
+use bevy_reflect::{prelude::*, FromReflect};
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::HashSet;
 use bevy_window::{WindowCreated, WindowId, WindowResized, Windows};
@@ -32,7 +32,7 @@ use wgpu::Extent3d;
 /// You can overlay multiple cameras in a single window using viewports to create effects like
 /// split screen, minimaps, and character viewers.
 // TODO: remove reflect_value when possible
-#[derive(Reflect, Debug, Clone, Serialize, Deserialize)]
+#[derive(Reflect, FromReflect, Debug, Clone, Serialize, Deserialize)]
 #[reflect_value(Default, Serialize, Deserialize)]
 pub struct Viewport {
     /// The physical position to render this viewport to within the [`RenderTarget`] of this [`Camera`].
@@ -226,6 +226,33 @@ impl Camera {
             None
         }
     }
+
+    /// Returns a ray going from the camera through the given viewport-space position, the
+    /// inverse of [`world_to_viewport`](Self::world_to_viewport).
+    ///
+    /// The viewport-space position is in logical pixels, with its origin in the bottom left
+    /// corner of the viewport, as with [`Window::cursor_position`](bevy_window::Window::cursor_position).
+    pub fn viewport_to_world(
+        &self,
+        camera_transform: &GlobalTransform,
+        viewport_position: Vec2,
+    ) -> Option<Ray> {
+        let target_size = self.logical_viewport_size()?;
+        let ndc = viewport_position * 2.0 / target_size - Vec2::ONE;
+
+        let ndc_to_world =
+            camera_transform.compute_matrix() * self.computed.projection_matrix.inverse();
+        // The depth is reversed, with the near plane at 1. The far plane may be at infinity, so a
+        // point in between is used for the direction.
+        let near = ndc_to_world.project_point3(ndc.extend(1.0));
+        let middle = ndc_to_world.project_point3(ndc.extend(0.5));
+        let direction = (middle - near).normalize();
+
+        (!near.is_nan() && !direction.is_nan()).then(|| Ray {
+            origin: near,
+            direction,
+        })
+    }
 }
 
 /// Configures the [`RenderGraph`](crate::render_graph::RenderGraph) name assigned to be run for a given [`Camera`] entity.
@@ -242,7 +269,9 @@ impl CameraRenderGraph {
 
 /// The "target" that a [`Camera`] will render to. For example, this could be a [`Window`](bevy_window::Window)
 /// swapchain or an [`Image`].
+// Reflected as a value, as `WindowId` doesn't implement `Reflect`
 #[derive(Debug, Clone, Reflect, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[reflect_value(PartialEq, Hash)]
 pub enum RenderTarget {
     /// Window to which the camera's view is rendered.
     Window(WindowId),
@@ -308,7 +337,7 @@ impl RenderTarget {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, Reflect, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, Reflect, FromReflect, Serialize, Deserialize)]
 #[reflect_value(Serialize, Deserialize)]
 pub enum DepthCalculation {
     /// Pythagorean distance; works everywhere, more expensive to compute.
@@ -90,6 +90,41 @@ impl HslRepresentation {
     }
 }
 
+pub struct OklabRepresentation;
+impl OklabRepresentation {
+    /// converts a color in OkLab space to linear sRGB space
+    #[inline]
+    pub fn oklab_to_linear_srgb(lightness: f32, a: f32, b: f32) -> [f32; 3] {
+        // https://bottosson.github.io/posts/oklab/#converting-from-linear-srgb-to-oklab, inverted
+        let l_ = lightness + 0.396_337_78 * a + 0.215_803_76 * b;
+        let m_ = lightness - 0.105_561_346 * a - 0.063_854_17 * b;
+        let s_ = lightness - 0.089_484_18 * a - 1.291_485_5 * b;
+        let (l, m, s) = (l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_);
+
+        [
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+        ]
+    }
+
+    /// converts a color in linear sRGB space to OkLab space
+    #[inline]
+    pub fn linear_srgb_to_oklab([red, green, blue]: [f32; 3]) -> (f32, f32, f32) {
+        // https://bottosson.github.io/posts/oklab/#converting-from-linear-srgb-to-oklab
+        let l = 0.412_221_46 * red + 0.536_332_55 * green + 0.051_445_995 * blue;
+        let m = 0.211_903_5 * red + 0.680_699_5 * green + 0.107_396_96 * blue;
+        let s = 0.088_302_46 * red + 0.281_718_85 * green + 0.629_978_7 * blue;
+        let (l_, m_, s_) = (l.cbrt(), m.cbrt(), s.cbrt());
+
+        (
+            0.210_454_26 * l_ + 0.793_617_8 * m_ - 0.004_072_047 * s_,
+            1.977_998_5 * l_ - 2.428_592_2 * m_ + 0.450_593_7 * s_,
+            0.025_904_037 * l_ + 0.782_771_77 * m_ - 0.808_675_77 * s_,
+        )
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -202,4 +237,34 @@ mod test {
         assert_eq!((saturation * 100.0).round() as u32, 83);
         assert_eq!((lightness * 100.0).round() as u32, 51);
     }
+
+    #[test]
+    fn oklab_linear_srgb_roundtrip() {
+        // "truth" from https://bottosson.github.io/posts/oklab/
+
+        // white
+        let (lightness, a, b) = OklabRepresentation::linear_srgb_to_oklab([1.0, 1.0, 1.0]);
+        assert_eq!((lightness * 1000.0).round() as i32, 1000);
+        assert_eq!((a * 1000.0).round() as i32, 0);
+        assert_eq!((b * 1000.0).round() as i32, 0);
+
+        // red
+        let (lightness, a, b) = OklabRepresentation::linear_srgb_to_oklab([1.0, 0.0, 0.0]);
+        assert_eq!((lightness * 1000.0).round() as i32, 628);
+        assert_eq!((a * 1000.0).round() as i32, 225);
+        assert_eq!((b * 1000.0).round() as i32, 126);
+
+        for color in [
+            [0.0, 0.0, 0.0],
+            [0.2, 0.5, 0.8],
+            [1.0, 0.0, 1.0],
+            [0.9, 0.9, 0.1],
+        ] {
+            let (lightness, a, b) = OklabRepresentation::linear_srgb_to_oklab(color);
+            let roundtrip = OklabRepresentation::oklab_to_linear_srgb(lightness, a, b);
+            for (channel, roundtrip) in color.iter().zip(roundtrip) {
+                assert!((channel - roundtrip).abs() < 1e-4);
+            }
+        }
+    }
 }
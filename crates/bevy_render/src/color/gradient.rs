@@ -0,0 +1,130 @@
+use crate::color::{Color, ColorSpace};
+use serde::{Deserialize, Serialize};
+
+/// A color gradient, interpolating between colors keyed at positions between 0.0 and 1.0.
+///
+/// The colors are interpolated in the [`ColorSpace::Oklab`] colorspace by default, where the
+/// gradient looks uniform. Use [`ColorGradient::with_space`] to interpolate in another colorspace.
+///
+/// ```
+/// # use bevy_render::color::{Color, ColorGradient};
+/// // from green to red through yellow, for a health bar
+/// let gradient = ColorGradient::new()
+///     .with_stop(0.0, Color::RED)
+///     .with_stop(0.5, Color::YELLOW)
+///     .with_stop(1.0, Color::GREEN);
+///
+/// assert_eq!(gradient.sample(0.5).as_rgba_u32(), Color::YELLOW.as_rgba_u32());
+/// // before the first stop and after the last one, the gradient is clamped
+/// assert_eq!(gradient.sample(2.0).as_rgba_u32(), Color::GREEN.as_rgba_u32());
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColorGradient {
+    /// The stops, sorted by position
+    stops: Vec<(f32, Color)>,
+    space: ColorSpace,
+}
+
+impl ColorGradient {
+    /// Creates a gradient without stops, interpolating in [`ColorSpace::Oklab`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the gradient interpolating in `space`.
+    #[must_use]
+    pub fn with_space(mut self, space: ColorSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Returns the gradient with the `color` stop at `position`, see [`ColorGradient::add_stop`].
+    #[must_use]
+    pub fn with_stop(mut self, position: f32, color: Color) -> Self {
+        self.add_stop(position, color);
+        self
+    }
+
+    /// Adds the `color` stop at `position`, usually in [0.0, 1.0]. A stop added at the position
+    /// of another stop is placed after it, to create a hard transition between their colors.
+    pub fn add_stop(&mut self, position: f32, color: Color) -> &mut Self {
+        let index = self
+            .stops
+            .partition_point(|(stop_position, _)| *stop_position <= position);
+        self.stops.insert(index, (position, color));
+        self
+    }
+
+    /// The colorspace the colors are interpolated in
+    pub fn space(&self) -> ColorSpace {
+        self.space
+    }
+
+    /// The positions and colors of the stops, sorted by position
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+    /// Samples the color of the gradient at `t`, interpolated between the two closest stops.
+    ///
+    /// Before the first stop and after the last one, the color of that stop is returned.
+    /// Returns [`Color::NONE`] if the gradient has no stop.
+    pub fn sample(&self, t: f32) -> Color {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Color::NONE,
+        };
+        if t <= first.0 {
+            return first.1;
+        }
+        if t >= last.0 {
+            return last.1;
+        }
+
+        // there is a stop before and after `t`
+        let next = self
+            .stops
+            .partition_point(|(stop_position, _)| *stop_position <= t);
+        let (start_position, start_color) = self.stops[next - 1];
+        if t == start_position {
+            return start_color;
+        }
+        let (end_position, end_color) = self.stops[next];
+        let s = (t - start_position) / (end_position - start_position);
+        start_color.lerp(end_color, s, self.space)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_between_stops() {
+        let gradient = ColorGradient::new()
+            .with_space(ColorSpace::Srgb)
+            .with_stop(1.0, Color::WHITE)
+            .with_stop(0.0, Color::BLACK)
+            .with_stop(0.5, Color::RED);
+
+        assert_eq!(gradient.sample(-1.0), Color::BLACK);
+        assert_eq!(gradient.sample(0.25), Color::rgb(0.5, 0.0, 0.0));
+        assert_eq!(gradient.sample(0.5), Color::RED);
+        assert_eq!(gradient.sample(0.75), Color::rgb(1.0, 0.5, 0.5));
+        assert_eq!(gradient.sample(2.0), Color::WHITE);
+        assert_eq!(ColorGradient::new().sample(0.5), Color::NONE);
+    }
+
+    #[test]
+    fn hard_transition() {
+        let gradient = ColorGradient::new()
+            .with_space(ColorSpace::Srgb)
+            .with_stop(0.0, Color::RED)
+            .with_stop(0.5, Color::RED)
+            .with_stop(0.5, Color::BLUE)
+            .with_stop(1.0, Color::BLUE);
+
+        assert_eq!(gradient.sample(0.49), Color::RED);
+        assert_eq!(gradient.sample(0.5), Color::BLUE);
+    }
+}
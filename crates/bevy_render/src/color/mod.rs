@@ -1,8 +1,10 @@
 mod colorspace;
+mod gradient;
 
 pub use colorspace::*;
+pub use gradient::*;
 
-use crate::color::{HslRepresentation, SrgbColorSpace};
+use crate::color::{HslRepresentation, OklabRepresentation, SrgbColorSpace};
 use bevy_math::{Vec3, Vec4};
 use bevy_reflect::{FromReflect, Reflect, ReflectDeserialize, ReflectSerialize};
 use serde::{Deserialize, Serialize};
@@ -185,6 +187,26 @@ impl Color {
         }
     }
 
+    /// New `Color` from OkLab colorspace, a perceptual colorspace where the lightness and the
+    /// chroma of the colors change uniformly, as perceived by the human eye.
+    ///
+    /// `lightness` is in [0.0, 1.0], and `a` and `b` are roughly in [-0.4, 0.4]. The color is
+    /// converted to the linear RGB colorspace, as `Color::RgbaLinear`.
+    pub fn oklab(lightness: f32, a: f32, b: f32) -> Color {
+        Color::oklaba(lightness, a, b, 1.0)
+    }
+
+    /// New `Color` from OkLab colorspace, see [`Color::oklab`].
+    pub fn oklaba(lightness: f32, a: f32, b: f32, alpha: f32) -> Color {
+        let [red, green, blue] = OklabRepresentation::oklab_to_linear_srgb(lightness, a, b);
+        Color::RgbaLinear {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
     /// New `Color` from sRGB colorspace.
     pub fn hex<T: AsRef<str>>(hex: T) -> Result<Color, HexColorError> {
         let hex = hex.as_ref();
@@ -527,6 +549,68 @@ impl Color {
         }
     }
 
+    /// Converts a `Color` to a `[f32; 4]` from OkLab colorspace, as lightness, a, b and alpha
+    pub fn as_oklaba_f32(self: Color) -> [f32; 4] {
+        let [red, green, blue, alpha] = self.as_linear_rgba_f32();
+        let (lightness, a, b) = OklabRepresentation::linear_srgb_to_oklab([red, green, blue]);
+        [lightness, a, b, alpha]
+    }
+
+    /// Linearly interpolates between `self` and `other` in the colorspace `space`, by `t` in
+    /// [0.0, 1.0].
+    ///
+    /// Interpolating in [`ColorSpace::Srgb`] is what most image editors do, but darkens the
+    /// intermediate colors. [`ColorSpace::Oklab`] keeps a perceptually uniform lightness and
+    /// saturation, for gradients and fades. Hues are interpolated along the shortest arc in
+    /// [`ColorSpace::Hsl`].
+    ///
+    /// The returned color has the variant of `space`, `Color::RgbaLinear` for OkLab.
+    pub fn lerp(self, other: Color, t: f32, space: ColorSpace) -> Color {
+        match space {
+            ColorSpace::Srgb => {
+                let [red, green, blue, alpha] = Vec4::from(self.as_rgba_f32())
+                    .lerp(Vec4::from(other.as_rgba_f32()), t)
+                    .to_array();
+                Color::rgba(red, green, blue, alpha)
+            }
+            ColorSpace::LinearRgb => {
+                let [red, green, blue, alpha] = Vec4::from(self.as_linear_rgba_f32())
+                    .lerp(Vec4::from(other.as_linear_rgba_f32()), t)
+                    .to_array();
+                Color::rgba_linear(red, green, blue, alpha)
+            }
+            ColorSpace::Hsl => {
+                let [mut hue, saturation, lightness, alpha] = self.as_hsla_f32();
+                let [mut other_hue, other_saturation, other_lightness, other_alpha] =
+                    other.as_hsla_f32();
+                // the hue of a gray is meaningless, keep the hue of the other color
+                if saturation == 0.0 {
+                    hue = other_hue;
+                } else if other_saturation == 0.0 {
+                    other_hue = hue;
+                }
+                let mut hue_difference = (other_hue - hue) % 360.0;
+                if hue_difference > 180.0 {
+                    hue_difference -= 360.0;
+                } else if hue_difference < -180.0 {
+                    hue_difference += 360.0;
+                }
+                Color::hsla(
+                    (hue + hue_difference * t).rem_euclid(360.0),
+                    saturation + (other_saturation - saturation) * t,
+                    lightness + (other_lightness - lightness) * t,
+                    alpha + (other_alpha - alpha) * t,
+                )
+            }
+            ColorSpace::Oklab => {
+                let [lightness, a, b, alpha] = Vec4::from(self.as_oklaba_f32())
+                    .lerp(Vec4::from(other.as_oklaba_f32()), t)
+                    .to_array();
+                Color::oklaba(lightness, a, b, alpha)
+            }
+        }
+    }
+
     /// Converts Color to a u32 from sRGB colorspace.
     ///
     /// Maps the RGBA channels in RGBA order to a little-endian byte array (GPUs are little-endian).
@@ -620,6 +704,21 @@ impl Color {
     }
 }
 
+/// A colorspace to interpolate colors in, see [`Color::lerp`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+#[reflect_value(PartialEq, Hash, Serialize, Deserialize)]
+pub enum ColorSpace {
+    /// The sRGB colorspace, of `Color::Rgba`
+    Srgb,
+    /// The linear RGB colorspace, of `Color::RgbaLinear`
+    LinearRgb,
+    /// The HSL representation of the sRGB colorspace, of `Color::Hsla`
+    Hsl,
+    /// The perceptual OkLab colorspace, see [`Color::oklab`]
+    #[default]
+    Oklab,
+}
+
 impl Default for Color {
     fn default() -> Self {
         Color::WHITE
@@ -1378,4 +1477,46 @@ mod tests {
 
         assert_eq!(starting_color * transformation, mutated_color,);
     }
+
+    #[test]
+    fn lerp_in_colorspaces() {
+        let (start, end) = (Color::rgb(1.0, 0.0, 0.0), Color::rgb(0.0, 0.0, 1.0));
+
+        assert_eq!(
+            start.lerp(end, 0.5, ColorSpace::Srgb),
+            Color::rgb(0.5, 0.0, 0.5)
+        );
+        assert_eq!(
+            start.lerp(end, 0.5, ColorSpace::LinearRgb),
+            Color::rgb_linear(0.5, 0.0, 0.5)
+        );
+        // the shortest arc between red and blue goes through magenta
+        assert_eq!(
+            start.lerp(end, 0.5, ColorSpace::Hsl),
+            Color::hsl(300.0, 1.0, 0.5)
+        );
+        // grays keep the hue of the other color
+        assert_eq!(
+            Color::GRAY
+                .as_hsla()
+                .lerp(end, 0.5, ColorSpace::Hsl)
+                .as_hsla_f32()[0],
+            240.0
+        );
+
+        let [lightness, ..] = start.as_oklaba_f32();
+        let [other_lightness, ..] = end.as_oklaba_f32();
+        let [middle_lightness, ..] = start.lerp(end, 0.5, ColorSpace::Oklab).as_oklaba_f32();
+        assert!((middle_lightness - (lightness + other_lightness) / 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn oklab_roundtrip() {
+        let color = Color::rgba(0.2, 0.4, 0.6, 0.8);
+        let [lightness, a, b, alpha] = color.as_oklaba_f32();
+        let roundtrip = Color::oklaba(lightness, a, b, alpha).as_rgba_f32();
+        for (channel, roundtrip) in color.as_rgba_f32().iter().zip(roundtrip) {
+            assert!((channel - roundtrip).abs() < 1e-4);
+        }
+    }
 }
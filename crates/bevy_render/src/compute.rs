@@ -0,0 +1,477 @@
+use crate::{
+    gpu_readback::GpuReadbacks,
+    main_graph,
+    render_asset::RenderAssets,
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext},
+    render_resource::{
+        AsBindGroup, AsBindGroupError, BindGroupLayout, Buffer, BufferAddress,
+        CachedComputePipelineId, ComputePassDescriptor, ComputePipelineDescriptor, PipelineCache,
+        PreparedBindGroup, ShaderRef,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::{FallbackImage, Image},
+    Extract, RenderApp, RenderStage,
+};
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::AssetServer;
+use bevy_ecs::{
+    event::{EventReader, EventWriter},
+    system::{Commands, Res},
+    world::{FromWorld, World},
+};
+use bevy_math::UVec3;
+use crossbeam_channel::{Receiver, Sender};
+use std::{
+    borrow::Cow,
+    marker::PhantomData,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A compute shader dispatched every frame by the renderer, with the bind group created from the
+/// fields of this type.
+///
+/// Insert the value as a resource of the main world and add a [`ComputeShaderPlugin`] for it to
+/// dispatch the shader with the current value of the resource. The bind group is created when the
+/// resource changes, with [`AsBindGroup`]: the fields are usually storage buffers or images the
+/// shader writes, with the `storage` and `storage_texture` attributes.
+///
+/// ```
+/// # use bevy_math::UVec3;
+/// # use bevy_render::{compute::ComputeShader, render_resource::*};
+/// #[derive(AsBindGroup, Clone)]
+/// struct Simulation {
+///     #[storage(0)]
+///     particles: Buffer,
+///     particle_count: u32,
+/// }
+///
+/// impl ComputeShader for Simulation {
+///     fn shader() -> ShaderRef {
+///         "shaders/simulation.wgsl".into()
+///     }
+///
+///     fn workgroups(&self) -> Option<UVec3> {
+///         // The shader has a workgroup size of 64
+///         Some(UVec3::new((self.particle_count + 63) / 64, 1, 1))
+///     }
+/// }
+/// ```
+pub trait ComputeShader: AsBindGroup + Clone + Send + Sync + 'static {
+    /// Returns this compute shader's shader.
+    fn shader() -> ShaderRef;
+
+    /// Returns the name of the function of the [`ComputeShader::shader`] that is dispatched.
+    fn entry_point() -> Cow<'static, str> {
+        Cow::Borrowed("main")
+    }
+
+    /// Returns the number of workgroups dispatched in each dimension, or [`None`] to skip this
+    /// frame.
+    fn workgroups(&self) -> Option<UVec3>;
+
+    /// Returns the buffers read back on the CPU after the next dispatch following a
+    /// [`ComputeReadbackRequest`], with the number of bytes read from each of them. The values are
+    /// then sent to the main world as [`ComputeReadback`] events.
+    ///
+    /// The buffers must have been created with the
+    /// [`BufferUsages::COPY_SRC`](crate::render_resource::BufferUsages::COPY_SRC) usage.
+    fn readback_buffers(&self) -> Vec<(Buffer, BufferAddress)> {
+        Vec::new()
+    }
+}
+
+/// Dispatches the [`ComputeShader`] `C` every frame, when it is inserted as a resource.
+pub struct ComputeShaderPlugin<C: ComputeShader>(PhantomData<C>);
+
+impl<C: ComputeShader> Default for ComputeShaderPlugin<C> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: ComputeShader> Plugin for ComputeShaderPlugin<C> {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        app.add_event::<ComputeReadback<C>>()
+            .add_event::<ComputeReadbackRequest<C>>()
+            .insert_resource(ComputeReadbackReceiver::<C>(receiver))
+            .add_system_to_stage(CoreStage::First, send_compute_readbacks::<C>);
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(ComputeReadbackSender::<C>(sender))
+                .init_resource::<ComputeShaderPipeline<C>>()
+                .init_resource::<ComputeReadbackRequested<C>>()
+                .add_system_to_stage(RenderStage::Extract, extract_compute_shader::<C>)
+                .add_system_to_stage(RenderStage::Extract, extract_compute_readback_requests::<C>)
+                .add_system_to_stage(RenderStage::Queue, queue_compute_shader_bind_group::<C>);
+
+            let node_name = std::any::type_name::<C>();
+            let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+            render_graph.add_node(node_name, ComputeNode::<C>::default());
+            render_graph
+                .add_node_edge(node_name, main_graph::node::CAMERA_DRIVER)
+                .unwrap();
+        }
+    }
+}
+
+/// The bind group layout and the pipeline of the [`ComputeShader`] `C`
+pub struct ComputeShaderPipeline<C: ComputeShader> {
+    pub layout: BindGroupLayout,
+    pub pipeline: CachedComputePipelineId,
+    marker: PhantomData<C>,
+}
+
+impl<C: ComputeShader> FromWorld for ComputeShaderPipeline<C> {
+    fn from_world(world: &mut World) -> Self {
+        let layout = C::bind_group_layout(world.resource::<RenderDevice>());
+        let shader = match C::shader() {
+            ShaderRef::Default => panic!(
+                "The compute shader {} has no default shader",
+                std::any::type_name::<C>()
+            ),
+            ShaderRef::Handle(handle) => handle,
+            ShaderRef::Path(path) => world.resource::<AssetServer>().load(path),
+        };
+        let pipeline = world
+            .resource_mut::<PipelineCache>()
+            .queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some(std::any::type_name::<C>().into()),
+                layout: Some(vec![layout.clone()]),
+                shader,
+                shader_defs: vec![],
+                entry_point: C::entry_point(),
+            });
+        ComputeShaderPipeline {
+            layout,
+            pipeline,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// The bind group created from the [`ComputeShader`] `C` resource of the render world
+pub struct ComputeShaderBindGroup<C: ComputeShader>(pub PreparedBindGroup<C>);
+
+/// The values read back from one of the [`ComputeShader::readback_buffers`] of `C` after a
+/// dispatch.
+pub struct ComputeReadback<C: ComputeShader> {
+    /// The index of the buffer in [`ComputeShader::readback_buffers`]
+    pub index: usize,
+    /// The bytes read from the buffer
+    pub data: Vec<u8>,
+    marker: PhantomData<fn() -> C>,
+}
+
+/// Event requesting to read back the [`ComputeShader::readback_buffers`] of `C` after its next
+/// dispatch, which are then sent as [`ComputeReadback`] events.
+///
+/// Several requests sent before the same dispatch are answered by a single readback.
+pub struct ComputeReadbackRequest<C: ComputeShader>(PhantomData<fn() -> C>);
+
+impl<C: ComputeShader> Default for ComputeReadbackRequest<C> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Whether a [`ComputeReadbackRequest`] of `C` is waiting for the next dispatch
+struct ComputeReadbackRequested<C: ComputeShader> {
+    requested: AtomicBool,
+    marker: PhantomData<fn() -> C>,
+}
+
+impl<C: ComputeShader> Default for ComputeReadbackRequested<C> {
+    fn default() -> Self {
+        Self {
+            requested: AtomicBool::new(false),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<C: ComputeShader> ComputeReadbackRequested<C> {
+    /// Returns whether a readback was requested, answering the request.
+    fn take(&self) -> bool {
+        self.requested.swap(false, Ordering::Relaxed)
+    }
+}
+
+struct ComputeReadbackSender<C: ComputeShader>(Sender<ComputeReadback<C>>);
+
+struct ComputeReadbackReceiver<C: ComputeShader>(Receiver<ComputeReadback<C>>);
+
+/// System sending the [`ComputeReadback`]s of `C` read since its last run as events
+fn send_compute_readbacks<C: ComputeShader>(
+    receiver: Res<ComputeReadbackReceiver<C>>,
+    mut events: EventWriter<ComputeReadback<C>>,
+) {
+    events.send_batch(receiver.0.try_iter());
+}
+
+/// System extracting the [`ComputeShader`] `C` resource when it changed, or removing it from the
+/// render world when it was removed from the main world
+pub fn extract_compute_shader<C: ComputeShader>(
+    mut commands: Commands,
+    compute_shader: Extract<Option<Res<C>>>,
+) {
+    match &*compute_shader {
+        Some(compute_shader) if compute_shader.is_changed() => {
+            commands.insert_resource(C::clone(compute_shader));
+            commands.remove_resource::<ComputeShaderBindGroup<C>>();
+        }
+        Some(_) => {}
+        None => {
+            commands.remove_resource::<C>();
+            commands.remove_resource::<ComputeShaderBindGroup<C>>();
+        }
+    }
+}
+
+/// System extracting the [`ComputeReadbackRequest`]s of `C`, kept until the next dispatch
+fn extract_compute_readback_requests<C: ComputeShader>(
+    mut requests: Extract<EventReader<ComputeReadbackRequest<C>>>,
+    requested: Res<ComputeReadbackRequested<C>>,
+) {
+    if requests.iter().count() > 0 {
+        requested.requested.store(true, Ordering::Relaxed);
+    }
+}
+
+/// System creating the [`ComputeShaderBindGroup`] of `C` when it changed
+pub fn queue_compute_shader_bind_group<C: ComputeShader>(
+    mut commands: Commands,
+    compute_shader: Option<Res<C>>,
+    bind_group: Option<Res<ComputeShaderBindGroup<C>>>,
+    pipeline: Res<ComputeShaderPipeline<C>>,
+    render_device: Res<RenderDevice>,
+    images: Res<RenderAssets<Image>>,
+    fallback_image: Res<FallbackImage>,
+) {
+    let compute_shader = match compute_shader {
+        Some(compute_shader) if bind_group.is_none() => compute_shader,
+        _ => return,
+    };
+    match compute_shader.as_bind_group(&pipeline.layout, &render_device, &images, &fallback_image) {
+        Ok(prepared) => commands.insert_resource(ComputeShaderBindGroup(prepared)),
+        // Try again once the images are loaded
+        Err(AsBindGroupError::RetryNextUpdate) => {}
+    }
+}
+
+/// Dispatches the [`ComputeShader`] `C`, once its pipeline and its bind group are ready, then
+/// reads back its [`ComputeShader::readback_buffers`] if a [`ComputeReadbackRequest`] is pending.
+pub struct ComputeNode<C: ComputeShader>(PhantomData<C>);
+
+impl<C: ComputeShader> Default for ComputeNode<C> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: ComputeShader> Node for ComputeNode<C> {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let (compute_shader, bind_group) = match (
+            world.get_resource::<C>(),
+            world.get_resource::<ComputeShaderBindGroup<C>>(),
+        ) {
+            (Some(compute_shader), Some(bind_group)) => (compute_shader, &bind_group.0),
+            _ => return Ok(()),
+        };
+        let workgroups = match compute_shader.workgroups() {
+            Some(workgroups) => workgroups,
+            None => return Ok(()),
+        };
+        let pipeline_id = world.resource::<ComputeShaderPipeline<C>>().pipeline;
+        let pipeline = match world
+            .resource::<PipelineCache>()
+            .get_compute_pipeline(pipeline_id)
+        {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+
+        {
+            let mut pass =
+                render_context
+                    .command_encoder
+                    .begin_compute_pass(&ComputePassDescriptor {
+                        label: Some(std::any::type_name::<C>()),
+                    });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group.bind_group, &[]);
+            pass.dispatch(workgroups.x, workgroups.y, workgroups.z);
+        }
+
+        if !world.resource::<ComputeReadbackRequested<C>>().take() {
+            return Ok(());
+        }
+        let readback_buffers = compute_shader.readback_buffers();
+        let gpu_readbacks = world.resource::<GpuReadbacks>();
+        let sender = &world.resource::<ComputeReadbackSender<C>>().0;
+        for (index, (buffer, size)) in readback_buffers.into_iter().enumerate() {
+            let sender = sender.clone();
+            gpu_readbacks.read_buffer(
+                &render_context.render_device,
+                &mut render_context.command_encoder,
+                &buffer,
+                size,
+                move |data| {
+                    let readback = ComputeReadback {
+                        index,
+                        data: data.to_vec(),
+                        marker: PhantomData,
+                    };
+                    // The receiver is only dropped with the main world
+                    let _ = sender.send(readback);
+                },
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        render_resource::{BindGroupLayout, BindGroupLayoutDescriptor},
+        MainWorld,
+    };
+    use bevy_ecs::event::Events;
+    use bevy_ecs::system::{IntoSystem, System};
+
+    #[derive(Clone)]
+    struct TestShader(u32);
+
+    impl AsBindGroup for TestShader {
+        type Data = ();
+
+        fn as_bind_group(
+            &self,
+            _layout: &BindGroupLayout,
+            _render_device: &RenderDevice,
+            _images: &RenderAssets<Image>,
+            _fallback_image: &FallbackImage,
+        ) -> Result<PreparedBindGroup<Self>, AsBindGroupError> {
+            Err(AsBindGroupError::RetryNextUpdate)
+        }
+
+        fn bind_group_layout(render_device: &RenderDevice) -> BindGroupLayout {
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[],
+            })
+        }
+    }
+
+    impl ComputeShader for TestShader {
+        fn shader() -> ShaderRef {
+            ShaderRef::Default
+        }
+
+        fn workgroups(&self) -> Option<UVec3> {
+            Some(UVec3::new(self.0, 1, 1))
+        }
+    }
+
+    /// Runs an extract system on the render world, reading from its [`MainWorld`]
+    fn run_extract(system: &mut dyn System<In = (), Out = ()>, render_world: &mut World) {
+        system.run((), render_world);
+        system.apply_buffers(render_world);
+        render_world.resource_mut::<MainWorld>().clear_trackers();
+    }
+
+    #[test]
+    fn compute_shader_is_extracted_when_changed() {
+        let mut render_world = World::new();
+        render_world.insert_resource(MainWorld(World::new()));
+        let mut extract = IntoSystem::into_system(extract_compute_shader::<TestShader>);
+        extract.initialize(&mut render_world);
+
+        render_world
+            .resource_mut::<MainWorld>()
+            .insert_resource(TestShader(1));
+        run_extract(&mut extract, &mut render_world);
+        assert_eq!(render_world.resource::<TestShader>().0, 1);
+
+        // Unchanged values are not extracted again
+        render_world.resource_mut::<TestShader>().0 = 0;
+        run_extract(&mut extract, &mut render_world);
+        assert_eq!(render_world.resource::<TestShader>().0, 0);
+
+        render_world
+            .resource_mut::<MainWorld>()
+            .resource_mut::<TestShader>()
+            .0 = 2;
+        run_extract(&mut extract, &mut render_world);
+        assert_eq!(render_world.resource::<TestShader>().0, 2);
+
+        render_world
+            .resource_mut::<MainWorld>()
+            .remove_resource::<TestShader>();
+        run_extract(&mut extract, &mut render_world);
+        assert!(!render_world.contains_resource::<TestShader>());
+    }
+
+    #[test]
+    fn readbacks_are_requested_until_the_next_dispatch() {
+        let mut main_world = World::new();
+        main_world.init_resource::<Events<ComputeReadbackRequest<TestShader>>>();
+        let mut render_world = World::new();
+        render_world.init_resource::<ComputeReadbackRequested<TestShader>>();
+        render_world.insert_resource(MainWorld(main_world));
+        let mut extract = IntoSystem::into_system(extract_compute_readback_requests::<TestShader>);
+        extract.initialize(&mut render_world);
+
+        run_extract(&mut extract, &mut render_world);
+        assert!(!render_world
+            .resource::<ComputeReadbackRequested<TestShader>>()
+            .take());
+
+        // Two requests before the same dispatch are answered by one readback
+        let mut main_world = render_world.resource_mut::<MainWorld>();
+        let mut requests = main_world.resource_mut::<Events<ComputeReadbackRequest<TestShader>>>();
+        requests.send_default();
+        requests.send_default();
+        run_extract(&mut extract, &mut render_world);
+        let requested = render_world.resource::<ComputeReadbackRequested<TestShader>>();
+        assert!(requested.take());
+        assert!(!requested.take());
+    }
+
+    #[test]
+    fn readbacks_are_sent_as_events() {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let mut world = World::new();
+        world.init_resource::<Events<ComputeReadback<TestShader>>>();
+        world.insert_resource(ComputeReadbackReceiver::<TestShader>(receiver));
+        for index in 0..2 {
+            sender
+                .send(ComputeReadback {
+                    index,
+                    data: vec![index as u8],
+                    marker: PhantomData,
+                })
+                .unwrap();
+        }
+
+        let mut system = IntoSystem::into_system(send_compute_readbacks::<TestShader>);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+
+        let events = world.resource::<Events<ComputeReadback<TestShader>>>();
+        let readbacks = events
+            .get_reader()
+            .iter(events)
+            .map(|readback| (readback.index, readback.data.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(readbacks, vec![(0, vec![0]), (1, vec![1])]);
+    }
+}
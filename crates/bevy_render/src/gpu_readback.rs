@@ -0,0 +1,321 @@
+use crate::{
+    render_resource::{
+        Buffer, BufferAddress, BufferDescriptor, BufferUsages, CommandEncoder, MapMode,
+    },
+    renderer::RenderDevice,
+    texture::TextureFormatPixelInfo,
+    RenderApp, RenderStage,
+};
+use bevy_app::{App, Plugin};
+use bevy_ecs::system::Res;
+use futures_lite::future;
+use parking_lot::Mutex;
+use std::num::NonZeroU32;
+use std::{future::Future, pin::Pin};
+use wgpu::{
+    BufferAsyncError, Extent3d, ImageCopyBuffer, ImageDataLayout, Maintain, Texture, TextureFormat,
+    COPY_BYTES_PER_ROW_ALIGNMENT,
+};
+
+type ReadbackCallback = Box<dyn FnOnce(&[u8]) + Send + Sync>;
+type MapFuture = Pin<Box<dyn Future<Output = Result<(), BufferAsyncError>> + Send>>;
+
+/// Adds the [`GpuReadbacks`] resource to the render world, and maps the buffers it reads back
+/// once the GPU wrote them.
+#[derive(Default)]
+pub struct GpuReadbackPlugin;
+
+impl Plugin for GpuReadbackPlugin {
+    fn build(&self, app: &mut App) {
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<GpuReadbacks>()
+                .add_system_to_stage(RenderStage::Cleanup, map_gpu_readbacks);
+        }
+    }
+}
+
+/// The number of frames a staging buffer is kept unused before being destroyed
+const MAX_IDLE_FRAMES: u32 = 60;
+
+struct Readback {
+    buffer: Buffer,
+    /// The size of `buffer`
+    capacity: BufferAddress,
+    /// The number of bytes copied at the start of `buffer`
+    size: BufferAddress,
+    on_read: ReadbackCallback,
+}
+
+#[derive(Default)]
+struct Readbacks {
+    /// Readbacks copied by the commands of the current frame
+    queued: Vec<Readback>,
+    /// Readbacks whose buffer is being mapped
+    mapping: Vec<(Readback, MapFuture)>,
+    /// The staging buffers that were read, to be reused by the next readbacks
+    free: StagingBuffers<Buffer>,
+}
+
+/// A pool of buffers, reused for any size they can hold.
+struct StagingBuffers<B> {
+    /// The buffers with their size and the number of frames they have been unused for
+    buffers: Vec<(B, BufferAddress, u32)>,
+}
+
+impl<B> Default for StagingBuffers<B> {
+    fn default() -> Self {
+        Self {
+            buffers: Vec::new(),
+        }
+    }
+}
+
+impl<B> StagingBuffers<B> {
+    /// Takes the smallest buffer that holds at least `size` bytes, with its size.
+    fn take(&mut self, size: BufferAddress) -> Option<(B, BufferAddress)> {
+        let index = self
+            .buffers
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, capacity, _))| *capacity >= size)
+            .min_by_key(|(_, (_, capacity, _))| *capacity)
+            .map(|(index, _)| index)?;
+        let (buffer, capacity, _) = self.buffers.swap_remove(index);
+        Some((buffer, capacity))
+    }
+
+    /// Gives back a buffer of `capacity` bytes to be reused.
+    fn recycle(&mut self, buffer: B, capacity: BufferAddress) {
+        self.buffers.push((buffer, capacity, 0));
+    }
+
+    /// Counts one more frame for the unused buffers, and drops the ones that have been unused
+    /// for more than [`MAX_IDLE_FRAMES`].
+    fn age(&mut self) {
+        self.buffers.retain_mut(|(_, _, idle_frames)| {
+            *idle_frames += 1;
+            *idle_frames <= MAX_IDLE_FRAMES
+        });
+    }
+}
+
+/// Reads the content of GPU buffers back on the CPU without stalling the rendering.
+///
+/// The buffers are copied by the commands of the current frame into buffers the CPU can read,
+/// which are mapped once the GPU executed those commands, usually a frame or two later. These
+/// staging buffers are reused by later readbacks of the same size or smaller.
+#[derive(Default)]
+pub struct GpuReadbacks {
+    readbacks: Mutex<Readbacks>,
+}
+
+impl GpuReadbacks {
+    /// Copies the first `size` bytes of `source` with the `command_encoder`, and calls `on_read`
+    /// with them once they are available on the CPU.
+    ///
+    /// `source` must have been created with the [`BufferUsages::COPY_SRC`] usage, and `size` must
+    /// be a multiple of 4.
+    pub fn read_buffer(
+        &self,
+        render_device: &RenderDevice,
+        command_encoder: &mut CommandEncoder,
+        source: &Buffer,
+        size: BufferAddress,
+        on_read: impl FnOnce(&[u8]) + Send + Sync + 'static,
+    ) {
+        let mut readbacks = self.readbacks.lock();
+        let (buffer, capacity) = readbacks.staging_buffer(render_device, size);
+        command_encoder.copy_buffer_to_buffer(source, 0, &buffer, 0, size);
+        readbacks.queued.push(Readback {
+            buffer,
+            capacity,
+            size,
+            on_read: Box::new(on_read),
+        });
+    }
+
+    /// Copies the first mip level and layer of the 2d `source` texture with the
+    /// `command_encoder`, and calls `on_read` with its rows of pixels once they are available on
+    /// the CPU.
+    ///
+    /// `source` must have been created with the [`TextureUsages::COPY_SRC`](wgpu::TextureUsages)
+    /// usage, and must have an uncompressed `format`.
+    pub fn read_texture(
+        &self,
+        render_device: &RenderDevice,
+        command_encoder: &mut CommandEncoder,
+        source: &Texture,
+        size: Extent3d,
+        format: TextureFormat,
+        on_read: impl FnOnce(&[u8]) + Send + Sync + 'static,
+    ) {
+        let row_size = size.width as usize * format.pixel_size();
+        let padded_row_size = padded_row_size(row_size);
+        let buffer_size = (padded_row_size * size.height as usize) as BufferAddress;
+        let mut readbacks = self.readbacks.lock();
+        let (buffer, capacity) = readbacks.staging_buffer(render_device, buffer_size);
+        command_encoder.copy_texture_to_buffer(
+            source.as_image_copy(),
+            ImageCopyBuffer {
+                buffer: &buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: NonZeroU32::new(padded_row_size as u32),
+                    rows_per_image: None,
+                },
+            },
+            Extent3d {
+                depth_or_array_layers: 1,
+                ..size
+            },
+        );
+        readbacks.queued.push(Readback {
+            buffer,
+            capacity,
+            size: buffer_size,
+            on_read: Box::new(move |data| on_read(&unpad_rows(data, row_size))),
+        });
+    }
+}
+
+impl Readbacks {
+    /// Reuses a free staging buffer of at least `size` bytes, or creates one.
+    fn staging_buffer(
+        &mut self,
+        render_device: &RenderDevice,
+        size: BufferAddress,
+    ) -> (Buffer, BufferAddress) {
+        self.free.take(size).unwrap_or_else(|| {
+            let buffer = render_device.create_buffer(&BufferDescriptor {
+                label: Some("gpu_readback_buffer"),
+                size,
+                usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            (buffer, size)
+        })
+    }
+}
+
+/// The size of a row of `row_size` bytes in a buffer a texture is copied to, which rows must be
+/// aligned to [`COPY_BYTES_PER_ROW_ALIGNMENT`].
+fn padded_row_size(row_size: usize) -> usize {
+    let align = COPY_BYTES_PER_ROW_ALIGNMENT as usize;
+    (row_size + align - 1) / align * align
+}
+
+/// Removes the padding at the end of the rows of a texture copied to a buffer.
+fn unpad_rows(data: &[u8], row_size: usize) -> Vec<u8> {
+    let padded_row_size = padded_row_size(row_size);
+    if padded_row_size == row_size {
+        return data.to_vec();
+    }
+    data.chunks(padded_row_size)
+        .flat_map(|row| &row[..row_size])
+        .copied()
+        .collect()
+}
+
+/// System mapping the buffers copied during this frame, which commands were submitted in
+/// [`RenderStage::Render`], and calling back the readbacks whose buffer was mapped.
+pub fn map_gpu_readbacks(render_device: Res<RenderDevice>, gpu_readbacks: Res<GpuReadbacks>) {
+    let mut readbacks = gpu_readbacks.readbacks.lock();
+    let Readbacks {
+        queued,
+        mapping,
+        free,
+    } = &mut *readbacks;
+    free.age();
+    if queued.is_empty() && mapping.is_empty() {
+        return;
+    }
+
+    for readback in queued.drain(..) {
+        let map_future = Box::pin(
+            readback
+                .buffer
+                .slice(..readback.size)
+                .map_async(MapMode::Read),
+        );
+        mapping.push((readback, map_future));
+    }
+    render_device.poll(Maintain::Poll);
+
+    let mut index = 0;
+    while index < mapping.len() {
+        let result = match future::block_on(future::poll_once(&mut mapping[index].1)) {
+            Some(result) => result,
+            None => {
+                index += 1;
+                continue;
+            }
+        };
+        let (readback, _) = mapping.swap_remove(index);
+        if result.is_ok() {
+            let data = readback
+                .buffer
+                .slice(..readback.size)
+                .get_mapped_range()
+                .to_vec();
+            readback.buffer.unmap();
+            free.recycle(readback.buffer, readback.capacity);
+            (readback.on_read)(&data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpad_texture_rows() {
+        assert_eq!(padded_row_size(0), 0);
+        assert_eq!(padded_row_size(4), 256);
+        assert_eq!(padded_row_size(256), 256);
+        assert_eq!(padded_row_size(260), 512);
+
+        // two rows of three 4 bytes pixels
+        let mut data = vec![0; 512];
+        data[..12].copy_from_slice(&[1; 12]);
+        data[256..268].copy_from_slice(&[2; 12]);
+        let mut expected = vec![1; 12];
+        expected.extend([2; 12]);
+        assert_eq!(unpad_rows(&data, 12), expected);
+
+        let data = (0..=255).collect::<Vec<u8>>();
+        assert_eq!(unpad_rows(&data, 256), data);
+    }
+
+    #[test]
+    fn staging_buffers_are_reused() {
+        let mut buffers = StagingBuffers::default();
+        assert_eq!(buffers.take(4), None);
+        buffers.recycle("large", 1024);
+        buffers.recycle("small", 16);
+        buffers.recycle("medium", 256);
+
+        // The smallest buffer that fits is reused
+        assert_eq!(buffers.take(100), Some(("medium", 256)));
+        assert_eq!(buffers.take(2048), None);
+        assert_eq!(buffers.take(16), Some(("small", 16)));
+        assert_eq!(buffers.take(16), Some(("large", 1024)));
+        assert!(buffers.buffers.is_empty());
+    }
+
+    #[test]
+    fn unused_staging_buffers_are_dropped() {
+        let mut buffers = StagingBuffers::default();
+        buffers.recycle("unused", 16);
+        for _ in 0..MAX_IDLE_FRAMES {
+            buffers.age();
+        }
+        buffers.recycle("used", 16);
+        assert_eq!(buffers.buffers.len(), 2);
+
+        buffers.age();
+        assert_eq!(buffers.take(16), Some(("used", 16)));
+        assert!(buffers.buffers.is_empty());
+    }
+}
@@ -2,9 +2,11 @@ extern crate core;
 
 pub mod camera;
 pub mod color;
+pub mod compute;
 pub mod extract_component;
 mod extract_param;
 pub mod extract_resource;
+pub mod gpu_readback;
 pub mod mesh;
 pub mod primitives;
 pub mod rangefinder;
@@ -14,6 +16,7 @@ pub mod render_phase;
 pub mod render_resource;
 pub mod renderer;
 pub mod settings;
+pub mod testing;
 pub mod texture;
 pub mod view;
 
@@ -36,13 +39,14 @@ pub use once_cell;
 use crate::{
     camera::CameraPlugin,
     color::Color,
+    gpu_readback::GpuReadbackPlugin,
     mesh::MeshPlugin,
     primitives::{CubemapFrusta, Frustum},
     render_graph::RenderGraph,
     render_resource::{PipelineCache, Shader, ShaderLoader},
     renderer::render_system,
     texture::ImagePlugin,
-    view::{ViewPlugin, WindowRenderPlugin},
+    view::{ScreenshotPlugin, ViewPlugin, WindowRenderPlugin},
 };
 use bevy_app::{App, AppLabel, Plugin};
 use bevy_asset::{AddAsset, AssetServer};
@@ -109,6 +113,7 @@ impl DerefMut for MainWorld {
 pub mod main_graph {
     pub mod node {
         pub const CAMERA_DRIVER: &str = "camera_driver";
+        pub const SCREENSHOT: &str = "screenshot";
     }
 }
 
@@ -310,6 +315,8 @@ impl Plugin for RenderPlugin {
             .add_plugin(CameraPlugin)
             .add_plugin(ViewPlugin)
             .add_plugin(MeshPlugin)
+            .add_plugin(GpuReadbackPlugin)
+            .add_plugin(ScreenshotPlugin)
             // NOTE: Load this after renderer initialization so that it knows about the supported
             // compressed texture formats
             .add_plugin(ImagePlugin);
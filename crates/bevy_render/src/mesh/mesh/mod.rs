@@ -1,4 +1,5 @@
 mod conversions;
+pub mod morph;
 pub mod skinning;
 pub use wgpu::PrimitiveTopology;
 
@@ -35,6 +36,7 @@ pub struct Mesh {
     /// which allows easy stable VertexBuffers (i.e. same buffer order)
     attributes: BTreeMap<MeshVertexAttributeId, MeshAttributeData>,
     indices: Option<Indices>,
+    morph_targets: Vec<morph::MorphTarget>,
 }
 
 /// Contains geometry in the form of a mesh.
@@ -91,6 +93,7 @@ impl Mesh {
             primitive_topology,
             attributes: Default::default(),
             indices: None,
+            morph_targets: Vec::new(),
         }
     }
 
@@ -100,20 +103,27 @@ impl Mesh {
     }
 
     /// Sets the data for a vertex attribute (position, normal etc.). The name will
-    /// often be one of the associated constants such as [`Mesh::ATTRIBUTE_POSITION`].
+    /// often be one of the associated constants such as [`Mesh::ATTRIBUTE_POSITION`],
+    /// or a custom [`MeshVertexAttribute`] read by the shader of a custom material.
+    ///
+    /// # Panics
+    /// Panics when the format of the values does not match the attribute's format.
     #[inline]
     pub fn insert_attribute(
         &mut self,
         attribute: MeshVertexAttribute,
         values: impl Into<VertexAttributeValues>,
     ) {
-        self.attributes.insert(
-            attribute.id,
-            MeshAttributeData {
-                attribute,
-                values: values.into(),
-            },
+        let values = values.into();
+        let values_format = VertexFormat::from(&values);
+        assert_eq!(
+            values_format, attribute.format,
+            "Failed to insert the values of the {} attribute: their format is {:?} but the attribute's format is {:?}",
+            attribute.name, values_format, attribute.format
         );
+
+        self.attributes
+            .insert(attribute.id, MeshAttributeData { attribute, values });
     }
 
     /// Removes the data for a vertex attribute
@@ -187,6 +197,19 @@ impl Mesh {
         self.indices.as_mut()
     }
 
+    /// Sets the [`MorphTarget`](morph::MorphTarget)s of the mesh, displacing its vertices by
+    /// the [`MorphWeights`](morph::MorphWeights) of the entities using it.
+    #[inline]
+    pub fn set_morph_targets(&mut self, morph_targets: Vec<morph::MorphTarget>) {
+        self.morph_targets = morph_targets;
+    }
+
+    /// Retrieves the [`MorphTarget`](morph::MorphTarget)s of the mesh.
+    #[inline]
+    pub fn morph_targets(&self) -> &[morph::MorphTarget] {
+        &self.morph_targets
+    }
+
     /// Computes and returns the index data of the mesh as bytes.
     /// This is used to transform the index data into a GPU friendly format.
     pub fn get_index_buffer_bytes(&self) -> Option<&[u8]> {
@@ -290,6 +313,18 @@ impl Mesh {
             None => return,
         };
 
+        for target in &mut self.morph_targets {
+            for displacements in [
+                &mut target.positions,
+                &mut target.normals,
+                &mut target.tangents,
+            ] {
+                if !displacements.is_empty() {
+                    *displacements = duplicate(displacements, indices.iter());
+                }
+            }
+        }
+
         for attributes in self.attributes.values_mut() {
             let indices = indices.iter();
             match &mut attributes.values {
@@ -390,6 +425,13 @@ impl Mesh {
     }
 }
 
+/// A named vertex attribute of a [`Mesh`], with the format of its values.
+///
+/// Besides the built-in attributes like [`Mesh::ATTRIBUTE_POSITION`], meshes can have custom
+/// attributes with any per-vertex data. They are included in the vertex buffer of the mesh, and
+/// custom materials can check that a mesh has them with the [`MeshVertexBufferLayout`] they are
+/// specialized for, and read them at a location of their shader with
+/// [`get_attribute`](InnerMeshVertexBufferLayout::get_attribute).
 #[derive(Debug, Clone)]
 pub struct MeshVertexAttribute {
     /// The friendly name of the vertex attribute
@@ -451,31 +493,16 @@ impl InnerMeshVertexBufferLayout {
         &self.layout
     }
 
+    /// Returns a [`VertexBufferLayout`] with the `attribute_descriptors` of this layout at their
+    /// shader location, or an error if one of them is missing.
     pub fn get_layout(
         &self,
         attribute_descriptors: &[VertexAttributeDescriptor],
     ) -> Result<VertexBufferLayout, MissingVertexAttributeError> {
-        let mut attributes = Vec::with_capacity(attribute_descriptors.len());
-        for attribute_descriptor in attribute_descriptors.iter() {
-            if let Some(index) = self
-                .attribute_ids
-                .iter()
-                .position(|id| *id == attribute_descriptor.id)
-            {
-                let layout_attribute = &self.layout.attributes[index];
-                attributes.push(VertexAttribute {
-                    format: layout_attribute.format,
-                    offset: layout_attribute.offset,
-                    shader_location: attribute_descriptor.shader_location,
-                });
-            } else {
-                return Err(MissingVertexAttributeError {
-                    id: attribute_descriptor.id,
-                    name: attribute_descriptor.name,
-                    pipeline_type: None,
-                });
-            }
-        }
+        let attributes = attribute_descriptors
+            .iter()
+            .map(|attribute_descriptor| self.get_attribute(attribute_descriptor))
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(VertexBufferLayout {
             array_stride: self.layout.array_stride,
@@ -483,6 +510,33 @@ impl InnerMeshVertexBufferLayout {
             attributes,
         })
     }
+
+    /// Returns the [`VertexAttribute`] of this layout described by `attribute_descriptor`, or an
+    /// error if it is missing.
+    ///
+    /// It can be appended to the attributes of a [`VertexBufferLayout`] returned by
+    /// [`get_layout`](Self::get_layout), for example for a material to read a custom attribute
+    /// in addition to the attributes of the mesh pipeline.
+    pub fn get_attribute(
+        &self,
+        attribute_descriptor: &VertexAttributeDescriptor,
+    ) -> Result<VertexAttribute, MissingVertexAttributeError> {
+        let index = self
+            .attribute_ids
+            .iter()
+            .position(|id| *id == attribute_descriptor.id)
+            .ok_or(MissingVertexAttributeError {
+                id: attribute_descriptor.id,
+                name: attribute_descriptor.name,
+                pipeline_type: None,
+            })?;
+        let layout_attribute = &self.layout.attributes[index];
+        Ok(VertexAttribute {
+            format: layout_attribute.format,
+            offset: layout_attribute.offset,
+            shader_location: attribute_descriptor.shader_location,
+        })
+    }
 }
 
 #[derive(Error, Debug)]
@@ -994,3 +1048,39 @@ fn generate_tangents_for_mesh(mesh: &Mesh) -> Result<Vec<[f32; 4]>, GenerateTang
 
     Ok(mikktspace_mesh.tangents)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Mesh, MeshVertexAttribute};
+    use crate::render_resource::{PrimitiveTopology, VertexFormat};
+
+    const ATTRIBUTE_HEAT: MeshVertexAttribute =
+        MeshVertexAttribute::new("Vertex_Heat", 1054613, VertexFormat::Float32);
+
+    #[test]
+    fn custom_attribute_layout() {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0, 0.0, 0.0]; 3]);
+        mesh.insert_attribute(ATTRIBUTE_HEAT, vec![0.0f32, 0.5, 1.0]);
+
+        let layout = mesh.get_mesh_vertex_buffer_layout();
+        assert!(layout.contains(ATTRIBUTE_HEAT));
+        assert_eq!(layout.layout().array_stride, 16);
+        let attribute = layout
+            .get_attribute(&ATTRIBUTE_HEAT.at_shader_location(7))
+            .unwrap();
+        assert_eq!(attribute.offset, 12);
+        assert_eq!(attribute.format, VertexFormat::Float32);
+        assert_eq!(attribute.shader_location, 7);
+        assert!(layout
+            .get_attribute(&Mesh::ATTRIBUTE_NORMAL.at_shader_location(1))
+            .is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_attribute_with_wrong_format() {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(ATTRIBUTE_HEAT, vec![[0.0f32, 0.0]; 3]);
+    }
+}
@@ -0,0 +1,322 @@
+use super::{Mesh, VertexAttributeValues};
+use crate::primitives::Aabb;
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    prelude::{Changed, ReflectComponent},
+    system::{Commands, ParamSet, Query, ResMut},
+};
+use bevy_hierarchy::{Children, Parent};
+use bevy_reflect::Reflect;
+use bevy_utils::HashMap;
+
+/// The displacements of the vertices of a [`Mesh`] for one of its morph targets, like a facial
+/// expression of a character. Each list is either empty or has one value per vertex.
+///
+/// The mesh is displaced by each of its targets scaled by a weight, see [`MorphWeights`].
+#[derive(Debug, Clone, Default)]
+pub struct MorphTarget {
+    /// Displacements of [`Mesh::ATTRIBUTE_POSITION`]
+    pub positions: Vec<[f32; 3]>,
+    /// Displacements of [`Mesh::ATTRIBUTE_NORMAL`]
+    pub normals: Vec<[f32; 3]>,
+    /// Displacements of the `xyz` of [`Mesh::ATTRIBUTE_TANGENT`]
+    pub tangents: Vec<[f32; 3]>,
+}
+
+/// The weights of the morph targets of the [`Mesh`] of an entity, or of the meshes of its
+/// children like the primitives of a glTF mesh.
+///
+/// When the weights change, the meshes are morphed on the CPU into a copy of the mesh used only by
+/// the entity, see [`MorphedMesh`].
+#[derive(Component, Debug, Default, Clone, Reflect)]
+#[reflect(Component)]
+pub struct MorphWeights {
+    /// The weight of each [`MorphTarget`]
+    pub weights: Vec<f32>,
+}
+
+/// Added to the entities whose [`Mesh`] was morphed by their [`MorphWeights`], their
+/// `Handle<Mesh>` being replaced by a handle to a copy of the mesh they don't share.
+///
+/// Replacing the `Handle<Mesh>` of the entity morphs the new mesh instead.
+#[derive(Component, Debug, Clone)]
+pub struct MorphedMesh {
+    /// The mesh with its morph targets, that isn't displaced
+    pub base: Handle<Mesh>,
+    /// The morphed copy of the mesh
+    pub morphed: Handle<Mesh>,
+}
+
+impl Mesh {
+    /// Sets the positions, normals and tangents of `mesh` to the ones of this mesh, displaced by
+    /// its [`MorphTarget`]s scaled by `weights`. The normals are normalized after being
+    /// displaced.
+    pub fn morph_into(&self, weights: &[f32], mesh: &mut Mesh) {
+        fn displace(
+            values: &mut [[f32; 3]],
+            targets: &[MorphTarget],
+            weights: &[f32],
+            displacements: impl Fn(&MorphTarget) -> &[[f32; 3]],
+        ) {
+            for (target, weight) in targets.iter().zip(weights) {
+                if *weight == 0.0 {
+                    continue;
+                }
+                for (value, displacement) in values.iter_mut().zip(displacements(target)) {
+                    value[0] += displacement[0] * weight;
+                    value[1] += displacement[1] * weight;
+                    value[2] += displacement[2] * weight;
+                }
+            }
+        }
+
+        if let Some(VertexAttributeValues::Float32x3(positions)) =
+            self.attribute(Mesh::ATTRIBUTE_POSITION)
+        {
+            let mut positions = positions.clone();
+            displace(&mut positions, &self.morph_targets, weights, |target| {
+                &target.positions
+            });
+            mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        }
+        if let Some(VertexAttributeValues::Float32x3(normals)) =
+            self.attribute(Mesh::ATTRIBUTE_NORMAL)
+        {
+            let mut normals = normals.clone();
+            displace(&mut normals, &self.morph_targets, weights, |target| {
+                &target.normals
+            });
+            for normal in &mut normals {
+                let length =
+                    (normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]).sqrt();
+                if length > 0.0 {
+                    normal.iter_mut().for_each(|value| *value /= length);
+                }
+            }
+            mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        }
+        if let Some(VertexAttributeValues::Float32x4(tangents)) =
+            self.attribute(Mesh::ATTRIBUTE_TANGENT)
+        {
+            let mut xyz = tangents
+                .iter()
+                .map(|tangent| [tangent[0], tangent[1], tangent[2]])
+                .collect::<Vec<_>>();
+            displace(&mut xyz, &self.morph_targets, weights, |target| {
+                &target.tangents
+            });
+            let tangents = xyz
+                .into_iter()
+                .zip(tangents)
+                .map(|(xyz, tangent)| [xyz[0], xyz[1], xyz[2], tangent[3]])
+                .collect::<Vec<_>>();
+            mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, tangents);
+        }
+    }
+}
+
+/// System morphing the [`Mesh`]es of the entities whose [`MorphWeights`] changed, and of their
+/// children, as well as the ones whose `Handle<Mesh>` was replaced. Their [`Aabb`] is updated to
+/// the morphed mesh.
+#[allow(clippy::type_complexity)]
+pub fn morph_meshes(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    changed_weights_query: Query<(Entity, Option<&Children>), Changed<MorphWeights>>,
+    weights_query: Query<&MorphWeights>,
+    mut mesh_queries: ParamSet<(
+        Query<(Entity, Option<&Parent>), Changed<Handle<Mesh>>>,
+        Query<(&mut Handle<Mesh>, Option<&MorphedMesh>, Option<&mut Aabb>)>,
+    )>,
+) {
+    // The entities to morph, with the entity of their weights and whether the weights changed
+    let mut morphed_entities = HashMap::default();
+    for (entity, parent) in mesh_queries.p0().iter() {
+        let weights_entity = std::iter::once(entity)
+            .chain(parent.map(|parent| parent.0))
+            .find(|entity| weights_query.contains(*entity));
+        if let Some(weights_entity) = weights_entity {
+            morphed_entities.insert(entity, (weights_entity, false));
+        }
+    }
+    for (entity, children) in changed_weights_query.iter() {
+        for morphed_entity in std::iter::once(entity).chain(children.into_iter().flatten().copied())
+        {
+            morphed_entities.insert(morphed_entity, (entity, true));
+        }
+    }
+
+    let mut mesh_query = mesh_queries.p1();
+    for (entity, (weights_entity, weights_changed)) in morphed_entities {
+        let (mut handle, morphed, aabb) = match mesh_query.get_mut(entity) {
+            Ok(mesh) => mesh,
+            Err(_) => continue,
+        };
+        let weights = match weights_query.get(weights_entity) {
+            Ok(weights) => weights,
+            Err(_) => continue,
+        };
+        // The handle is still the one of the morphed copy, unless it was replaced by a new mesh
+        let morphed = morphed.filter(|morphed| *handle == morphed.morphed);
+        if morphed.is_some() && !weights_changed {
+            continue;
+        }
+        let base_handle = match morphed {
+            Some(morphed) => morphed.base.clone(),
+            None => handle.clone(),
+        };
+        let base = match meshes.get(&base_handle) {
+            Some(base) if !base.morph_targets().is_empty() => base,
+            _ => continue,
+        };
+        let bounds = match morphed {
+            Some(_) => {
+                let mut morphed_attributes = Mesh::new(base.primitive_topology());
+                base.morph_into(&weights.weights, &mut morphed_attributes);
+                meshes.get_mut(&*handle).and_then(|mesh| {
+                    mesh.attributes.extend(morphed_attributes.attributes);
+                    mesh.compute_aabb()
+                })
+            }
+            None => {
+                // Morph a copy of the mesh, as it may be shared with other entities
+                let mut mesh = base.clone();
+                mesh.set_morph_targets(Vec::new());
+                base.morph_into(&weights.weights, &mut mesh);
+                let bounds = mesh.compute_aabb();
+                *handle = meshes.add(mesh);
+                commands.entity(entity).insert(MorphedMesh {
+                    base: base_handle,
+                    morphed: handle.clone_weak(),
+                });
+                bounds
+            }
+        };
+        // Entities without bounds get them from `calculate_bounds`
+        if let (Some(mut aabb), Some(bounds)) = (aabb, bounds) {
+            *aabb = bounds;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::PrimitiveTopology;
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_math::Vec3A;
+    use bevy_tasks::IoTaskPool;
+
+    /// A triangle with a single target moving its first vertex along `displacement`.
+    fn morphed_triangle(displacement: [f32; 3]) -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        );
+        mesh.set_morph_targets(vec![MorphTarget {
+            positions: vec![displacement, [0.0; 3], [0.0; 3]],
+            ..Default::default()
+        }]);
+        mesh
+    }
+
+    #[test]
+    fn morph_into() {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 3]);
+        mesh.set_morph_targets(vec![
+            MorphTarget {
+                positions: vec![[0.0, 0.0, 1.0], [0.0, 0.0, 0.0], [0.0, 0.0, 0.0]],
+                normals: vec![[1.0, 0.0, -1.0], [0.0, 0.0, 0.0], [0.0, 0.0, 0.0]],
+                ..Default::default()
+            },
+            MorphTarget {
+                positions: vec![[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 0.0, 0.0]],
+                ..Default::default()
+            },
+        ]);
+
+        let mut morphed = mesh.clone();
+        mesh.morph_into(&[0.5, 1.0], &mut morphed);
+        assert!(matches!(
+            morphed.attribute(Mesh::ATTRIBUTE_POSITION),
+            Some(VertexAttributeValues::Float32x3(positions))
+                if positions == &[[0.0, 0.0, 0.5], [3.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+        ));
+        let half_sqrt = 0.5f32.sqrt();
+        assert!(matches!(
+            morphed.attribute(Mesh::ATTRIBUTE_NORMAL),
+            Some(VertexAttributeValues::Float32x3(normals))
+                if (normals[0][0] - half_sqrt).abs() < 1e-6
+                    && (normals[0][2] - half_sqrt).abs() < 1e-6
+                    && normals[1] == [0.0, 0.0, 1.0]
+        ));
+
+        // The displacements aren't accumulated
+        mesh.morph_into(&[0.0, 0.0], &mut morphed);
+        assert!(matches!(
+            morphed.attribute(Mesh::ATTRIBUTE_POSITION),
+            Some(VertexAttributeValues::Float32x3(positions))
+                if positions == &[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+        ));
+    }
+
+    #[test]
+    fn morphed_meshes_follow_their_weights_and_handle() {
+        IoTaskPool::init(Default::default);
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .add_system(morph_meshes);
+        let mut meshes = app.world.resource_mut::<Assets<Mesh>>();
+        let up = meshes.add(morphed_triangle([0.0, 0.0, 2.0]));
+        let down = meshes.add(morphed_triangle([0.0, 0.0, -4.0]));
+        let entity = app
+            .world
+            .spawn()
+            .insert_bundle((
+                up.clone(),
+                MorphWeights { weights: vec![0.5] },
+                Aabb::default(),
+            ))
+            .id();
+        let max_z = |app: &App| app.world.get::<Aabb>(entity).unwrap().max().z;
+
+        app.update();
+        let morphed = app.world.get::<MorphedMesh>(entity).unwrap().clone();
+        assert_eq!(morphed.base, up);
+        assert_eq!(
+            *app.world.get::<Handle<Mesh>>(entity).unwrap(),
+            morphed.morphed
+        );
+        assert_eq!(max_z(&app), 1.0);
+
+        // The copy is morphed again when the weights change
+        app.world.get_mut::<MorphWeights>(entity).unwrap().weights[0] = 1.0;
+        app.update();
+        assert_eq!(
+            *app.world.get::<Handle<Mesh>>(entity).unwrap(),
+            morphed.morphed
+        );
+        assert_eq!(max_z(&app), 2.0);
+
+        // A new mesh is morphed into a new copy
+        *app.world.get_mut::<Handle<Mesh>>(entity).unwrap() = down.clone();
+        app.update();
+        let morphed = app.world.get::<MorphedMesh>(entity).unwrap();
+        assert_eq!(morphed.base, down);
+        assert_ne!(morphed.morphed, down);
+        assert_eq!(
+            app.world.get::<Aabb>(entity).unwrap().min(),
+            Vec3A::new(0.0, 0.0, -4.0)
+        );
+    }
+}
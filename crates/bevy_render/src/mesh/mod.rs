@@ -5,9 +5,11 @@ pub mod shape;
 
 pub use mesh::*;
 
-use crate::render_asset::RenderAssetPlugin;
-use bevy_app::{App, Plugin};
+use crate::{render_asset::RenderAssetPlugin, view::VisibilitySystems};
+use bevy_app::{App, CoreStage, Plugin};
 use bevy_asset::AddAsset;
+use bevy_ecs::schedule::ParallelSystemDescriptorCoercion;
+use bevy_transform::TransformSystem;
 
 /// Adds the [`Mesh`] as an asset and makes sure that they are extracted and prepared for the GPU.
 pub struct MeshPlugin;
@@ -17,6 +19,13 @@ impl Plugin for MeshPlugin {
         app.add_asset::<Mesh>()
             .add_asset::<skinning::SkinnedMeshInverseBindposes>()
             .register_type::<skinning::SkinnedMesh>()
-            .add_plugin(RenderAssetPlugin::<Mesh>::default());
+            .register_type::<morph::MorphWeights>()
+            .add_plugin(RenderAssetPlugin::<Mesh>::default())
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                morph::morph_meshes
+                    .after(TransformSystem::TransformPropagate)
+                    .before(VisibilitySystems::CalculateBounds),
+            );
     }
 }
@@ -121,6 +121,17 @@ impl Deref for BindGroup {
 ///     most fields should be a [`Handle<Image>`](bevy_asset::Handle) or [`Option<Handle<Image>>`]. If the value of an [`Option<Handle<Image>>`] is
 ///     [`None`], the [`FallbackImage`] resource will be used instead. This attribute can be used in conjunction with a `texture` binding attribute
 ///     (with a different binding index) if a binding of the texture for the [`Image`] is also required.
+/// * `storage(BINDING_INDEX)` or `storage(BINDING_INDEX, read_only)`
+///     * This field's [`Buffer`] will be bound as a storage buffer in shaders, for example to be written by a compute shader
+///     (see [`ComputeShader`](crate::compute::ComputeShader)). The buffer must have been created with the
+///     [`BufferUsages::STORAGE`](crate::render_resource::BufferUsages::STORAGE) usage. Unless it is `read_only`, it is
+///     only visible to fragment and compute shaders.
+/// * `storage_texture(BINDING_INDEX, image_format = FORMAT)` or `storage_texture(BINDING_INDEX, image_format = FORMAT, access = ACCESS)`
+///     * This field's [`Handle<Image>`](bevy_asset::Handle) will be used to look up the matching [`Texture`](crate::render_resource::Texture)
+///     GPU resource, which will be bound as a 2d storage texture in shaders. `FORMAT` is the [`TextureFormat`](crate::render_resource::TextureFormat)
+///     of the image, which must have been created with the [`TextureUsages::STORAGE_BINDING`](crate::render_resource::TextureUsages::STORAGE_BINDING)
+///     usage, and `ACCESS` is a [`StorageTextureAccess`](crate::render_resource::StorageTextureAccess), `ReadWrite` by default.
+///     It is only visible to fragment and compute shaders.
 ///
 /// Note that fields without field-level binding attributes will be ignored.
 /// ```
@@ -84,6 +84,16 @@ impl TextureView {
         self.id
     }
 
+    /// Returns the texture of the [`SurfaceTexture`](wgpu::SurfaceTexture) of the texture view if
+    /// it is of that type.
+    #[inline]
+    pub fn surface_texture(&self) -> Option<&wgpu::Texture> {
+        match &self.value {
+            TextureViewValue::TextureView(_) => None,
+            TextureViewValue::SurfaceTexture { texture, .. } => Some(&texture.texture),
+        }
+    }
+
     /// Returns the [`SurfaceTexture`](wgpu::SurfaceTexture) of the texture view if it is of that type.
     #[inline]
     pub fn take_surface_texture(self) -> Option<wgpu::SurfaceTexture> {
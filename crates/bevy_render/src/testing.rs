@@ -0,0 +1,305 @@
+//! Utilities to render an [`App`] without windows in tests, and compare the rendered frames to
+//! reference images.
+//!
+//! ```no_run
+//! # use bevy_asset::Assets;
+//! # use bevy_render::{prelude::*, testing};
+//! # fn spawn_scene_rendering_to(app: &mut bevy_app::App, target: bevy_asset::Handle<Image>) {}
+//! // Returns `None` when there is no GPU to render with, to skip the test
+//! let mut app = match testing::headless_app() {
+//!     Some(app) => app,
+//!     None => return,
+//! };
+//! let target = app
+//!     .world
+//!     .resource_mut::<Assets<Image>>()
+//!     .add(testing::render_target_image(256, 64));
+//! spawn_scene_rendering_to(&mut app, target.clone());
+//!
+//! let image = testing::render_frames(&mut app, &target, 3);
+//! testing::compare_to_reference(&image, "tests/reference/scene.png", 2).unwrap();
+//! ```
+//!
+//! The reference images are written by running the tests with the
+//! `BEVY_UPDATE_REFERENCE_IMAGES` environment variable set. They are read and written as PNG,
+//! which requires the `png` feature.
+
+use crate::{
+    camera::RenderTarget, prelude::Image, settings::WgpuSettings, view::ScreenshotManager,
+    RenderPlugin,
+};
+use bevy_app::App;
+use bevy_asset::{AssetPlugin, Handle};
+use bevy_core::CorePlugin;
+use bevy_hierarchy::HierarchyPlugin;
+use bevy_transform::TransformPlugin;
+use bevy_utils::tracing::warn;
+use bevy_window::{WindowPlugin, WindowSettings};
+use parking_lot::Mutex;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+use wgpu::{Extent3d, TextureDimension, TextureFormat, TextureUsages};
+
+/// The environment variable to set to write the reference images instead of comparing them
+pub const UPDATE_REFERENCE_IMAGES_VAR: &str = "BEVY_UPDATE_REFERENCE_IMAGES";
+
+/// The number of frames [`render_frames`] waits for the GPU to read the rendered frame back
+const MAX_READBACK_FRAMES: u32 = 10;
+
+/// Returns `true` if a GPU adapter can be used for rendering without a window, with the
+/// [`WgpuSettings`] taken from the environment.
+///
+/// CI machines without a GPU can usually render with a software adapter, like `lavapipe` with
+/// `WGPU_BACKEND=vulkan` or `llvmpipe` with `WGPU_BACKEND=gl`.
+pub fn headless_adapter_available() -> bool {
+    let options = WgpuSettings::default();
+    let backends = match options.backends {
+        Some(backends) => backends,
+        None => return false,
+    };
+    let instance = wgpu::Instance::new(backends);
+    futures_lite::future::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: options.power_preference,
+        compatible_surface: None,
+        ..Default::default()
+    }))
+    .is_some()
+}
+
+/// Creates an [`App`] with the plugins needed to render to images, but without any window, or
+/// returns [`None`] if there is no GPU adapter to render with.
+///
+/// Add the plugins of the rendered scene, like the core pipeline and UI plugins, and cameras
+/// rendering to images created with [`render_target_image`]. The app is updated manually, with
+/// [`render_frames`].
+pub fn headless_app() -> Option<App> {
+    if !headless_adapter_available() {
+        warn!("No GPU adapter available to render headless, skipping");
+        return None;
+    }
+
+    let mut app = App::new();
+    app.insert_resource(WindowSettings {
+        add_primary_window: false,
+        exit_on_all_closed: false,
+        ..Default::default()
+    })
+    .add_plugin(CorePlugin)
+    .add_plugin(TransformPlugin)
+    .add_plugin(HierarchyPlugin)
+    .add_plugin(WindowPlugin)
+    .add_plugin(AssetPlugin)
+    .add_plugin(RenderPlugin);
+    Some(app)
+}
+
+/// Creates an image of `width` by `height` pixels that cameras can render to, and that can be
+/// read back with [`render_frames`]. It is cleared to transparent black.
+pub fn render_target_image(width: u32, height: u32) -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+        | TextureUsages::COPY_DST
+        | TextureUsages::COPY_SRC
+        | TextureUsages::RENDER_ATTACHMENT;
+    image
+}
+
+/// Updates `app` for `frames` frames, and returns the last frame rendered to the `target` image.
+///
+/// The frame is read back with the [`ScreenshotManager`], the app is updated a few more frames
+/// until it is available on the CPU.
+///
+/// # Panics
+/// Panics if `frames` is 0, or if the frame couldn't be read back, for example because `target`
+/// isn't an image created with [`render_target_image`].
+pub fn render_frames(app: &mut App, target: &Handle<Image>, frames: u32) -> Image {
+    assert!(frames > 0, "At least one frame must be rendered");
+    for _ in 1..frames {
+        app.update();
+    }
+
+    let frame = Arc::new(Mutex::new(None));
+    let frame_sender = frame.clone();
+    app.world
+        .resource_mut::<ScreenshotManager>()
+        .take_screenshot(RenderTarget::Image(target.clone()), move |image| {
+            *frame_sender.lock() = Some(image);
+        });
+    for _ in 0..MAX_READBACK_FRAMES {
+        app.update();
+        if let Some(image) = frame.lock().take() {
+            return image;
+        }
+    }
+    panic!(
+        "The frame rendered to {:?} wasn't read back after {} frames",
+        target, MAX_READBACK_FRAMES
+    );
+}
+
+/// An error returned when an image doesn't match its reference image.
+#[derive(Error, Debug)]
+pub enum ReferenceImageError {
+    #[error("the image format {0:?} cannot be compared")]
+    UnsupportedFormat(TextureFormat),
+    #[error("reference image {0} not found, set BEVY_UPDATE_REFERENCE_IMAGES to create it")]
+    MissingReference(PathBuf),
+    #[error("failed to read or write image {path}: {error}")]
+    Io {
+        path: PathBuf,
+        error: image::ImageError,
+    },
+    #[error("the image is {actual:?} pixels instead of {expected:?}, see {actual_path}")]
+    SizeMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+        actual_path: PathBuf,
+    },
+    #[error(
+        "{pixels} pixels differ by up to {max_difference} from the reference, see {actual_path}"
+    )]
+    Mismatch {
+        pixels: usize,
+        max_difference: u8,
+        actual_path: PathBuf,
+    },
+}
+
+/// Compares `image` to the reference image at `path`, allowing each channel of the pixels to
+/// differ by `tolerance`, as different GPUs don't rasterize exactly the same.
+///
+/// If they differ, `image` is written next to the reference with the `.actual.png` extension, to
+/// inspect it. If the [`UPDATE_REFERENCE_IMAGES_VAR`] environment variable is set, `image` is
+/// written to `path` instead.
+pub fn compare_to_reference(
+    image: &Image,
+    path: impl AsRef<Path>,
+    tolerance: u8,
+) -> Result<(), ReferenceImageError> {
+    let path = path.as_ref();
+    let actual = image
+        .try_into_dynamic()
+        .ok_or(ReferenceImageError::UnsupportedFormat(
+            image.texture_descriptor.format,
+        ))?
+        .into_rgba8();
+
+    if std::env::var_os(UPDATE_REFERENCE_IMAGES_VAR).is_some() {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        return actual.save(path).map_err(|error| ReferenceImageError::Io {
+            path: path.to_owned(),
+            error,
+        });
+    }
+    if !path.exists() {
+        return Err(ReferenceImageError::MissingReference(path.to_owned()));
+    }
+    let expected = image::open(path)
+        .map_err(|error| ReferenceImageError::Io {
+            path: path.to_owned(),
+            error,
+        })?
+        .into_rgba8();
+
+    let actual_path = path.with_extension("actual.png");
+    let result = if actual.dimensions() != expected.dimensions() {
+        Err(ReferenceImageError::SizeMismatch {
+            expected: expected.dimensions(),
+            actual: actual.dimensions(),
+            actual_path: actual_path.clone(),
+        })
+    } else {
+        let (pixels, max_difference) = count_different_pixels(&actual, &expected, tolerance);
+        if pixels == 0 {
+            Ok(())
+        } else {
+            Err(ReferenceImageError::Mismatch {
+                pixels,
+                max_difference,
+                actual_path: actual_path.clone(),
+            })
+        }
+    };
+    if result.is_err() {
+        actual
+            .save(&actual_path)
+            .map_err(|error| ReferenceImageError::Io {
+                path: actual_path,
+                error,
+            })?;
+    }
+    result
+}
+
+/// Returns the number of pixels with a channel differing by more than `tolerance`, and the
+/// largest difference of a channel.
+fn count_different_pixels(
+    actual: &image::RgbaImage,
+    expected: &image::RgbaImage,
+    tolerance: u8,
+) -> (usize, u8) {
+    let mut pixels = 0;
+    let mut max_difference = 0;
+    for (actual, expected) in actual.pixels().zip(expected.pixels()) {
+        let difference = actual
+            .0
+            .iter()
+            .zip(expected.0.iter())
+            .map(|(a, b)| a.abs_diff(*b))
+            .max()
+            .unwrap_or(0);
+        if difference > tolerance {
+            pixels += 1;
+        }
+        max_difference = max_difference.max(difference);
+    }
+    (pixels, max_difference)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bevy_asset::Assets;
+
+    #[test]
+    fn count_pixels_over_tolerance() {
+        let expected =
+            image::RgbaImage::from_raw(2, 1, vec![0, 0, 0, 255, 100, 100, 100, 255]).unwrap();
+        let actual =
+            image::RgbaImage::from_raw(2, 1, vec![2, 0, 0, 255, 100, 110, 100, 255]).unwrap();
+
+        assert_eq!(count_different_pixels(&actual, &expected, 10), (0, 10));
+        assert_eq!(count_different_pixels(&actual, &expected, 2), (1, 10));
+        assert_eq!(count_different_pixels(&actual, &expected, 1), (2, 10));
+    }
+
+    #[test]
+    fn read_back_render_target() {
+        let mut app = match headless_app() {
+            Some(app) => app,
+            None => return,
+        };
+        let mut image = render_target_image(3, 2);
+        image.data = (0..24).collect();
+        let target = app.world.resource_mut::<Assets<Image>>().add(image);
+
+        let frame = render_frames(&mut app, &target, 1);
+        assert_eq!(frame.texture_descriptor.size.width, 3);
+        assert_eq!(frame.texture_descriptor.size.height, 2);
+        assert_eq!(frame.data, (0..24).collect::<Vec<u8>>());
+    }
+}
@@ -17,6 +17,7 @@ use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::system::{lifetimeless::SRes, SystemParamItem};
 use bevy_math::Vec2;
 use bevy_reflect::TypeUuid;
+use bevy_window::WindowIcon;
 use std::hash::Hash;
 use thiserror::Error;
 use wgpu::{
@@ -350,6 +351,32 @@ impl Image {
             })
     }
 
+    /// Converts the image to a [`DynamicImage`](image::DynamicImage) of the `image` crate, to
+    /// process it or save it to a file.
+    ///
+    /// Only 8 bit R, RG, RGBA and BGRA formats are supported, `None` is returned for the others.
+    pub fn try_into_dynamic(&self) -> Option<image::DynamicImage> {
+        super::image_texture_conversion::texture_to_image(self)
+    }
+
+    /// Converts the image to a [`WindowIcon`], to display it in the title bar of a window and in
+    /// the taskbar with [`Window::set_icon`](bevy_window::Window::set_icon).
+    ///
+    /// Returns `None` if the image can't be converted to 8 bit RGBA, see [`Image::convert`].
+    pub fn to_window_icon(&self) -> Option<WindowIcon> {
+        let converted = match self.texture_descriptor.format {
+            TextureFormat::Rgba8UnormSrgb | TextureFormat::Rgba8Unorm => None,
+            _ => Some(self.convert(TextureFormat::Rgba8UnormSrgb)?),
+        };
+        let image = converted.as_ref().unwrap_or(self);
+        WindowIcon::from_rgba(
+            image.data.clone(),
+            image.texture_descriptor.size.width,
+            image.texture_descriptor.size.height,
+        )
+        .ok()
+    }
+
     /// Load a bytes buffer in a [`Image`], according to type `image_type`, using the `image`
     /// crate
     pub fn from_buffer(
@@ -800,4 +827,29 @@ mod test {
         let image = Image::default();
         assert_eq!(Vec2::ONE, image.size());
     }
+    #[test]
+    fn image_to_window_icon() {
+        let size = Extent3d {
+            width: 4,
+            height: 2,
+            depth_or_array_layers: 1,
+        };
+        let image = Image::new_fill(
+            size,
+            TextureDimension::D2,
+            &[255, 0, 0, 255],
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        let icon = image.to_window_icon().unwrap();
+        assert_eq!((icon.width(), icon.height()), (4, 2));
+        assert_eq!(&icon.rgba()[..4], &[255, 0, 0, 255]);
+
+        let image = Image::new_fill(
+            size,
+            TextureDimension::D2,
+            &[0; 16],
+            TextureFormat::Rgba32Float,
+        );
+        assert!(image.to_window_icon().is_none());
+    }
 }
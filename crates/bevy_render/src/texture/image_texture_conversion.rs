@@ -183,12 +183,23 @@ pub(crate) fn texture_to_image(texture: &Image) -> Option<DynamicImage> {
             texture.data.clone(),
         )
         .map(DynamicImage::ImageLumaA8),
-        TextureFormat::Rgba8UnormSrgb => ImageBuffer::from_raw(
+        TextureFormat::Rgba8UnormSrgb | TextureFormat::Rgba8Unorm => ImageBuffer::from_raw(
             texture.texture_descriptor.size.width,
             texture.texture_descriptor.size.height,
             texture.data.clone(),
         )
         .map(DynamicImage::ImageRgba8),
+        // The usual format of window surfaces
+        TextureFormat::Bgra8UnormSrgb | TextureFormat::Bgra8Unorm => ImageBuffer::from_raw(
+            texture.texture_descriptor.size.width,
+            texture.texture_descriptor.size.height,
+            texture
+                .data
+                .chunks_exact(4)
+                .flat_map(|pixel| [pixel[2], pixel[1], pixel[0], pixel[3]])
+                .collect(),
+        )
+        .map(DynamicImage::ImageRgba8),
         _ => None,
     }
 }
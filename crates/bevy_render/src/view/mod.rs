@@ -1,6 +1,8 @@
+pub mod screenshot;
 pub mod visibility;
 pub mod window;
 
+pub use screenshot::*;
 pub use visibility::*;
 use wgpu::{
     Color, Extent3d, Operations, RenderPassColorAttachment, TextureDescriptor, TextureDimension,
@@ -0,0 +1,220 @@
+use crate::{
+    camera::RenderTarget,
+    gpu_readback::GpuReadbacks,
+    main_graph::node,
+    prelude::Image,
+    render_asset::RenderAssets,
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext},
+    renderer::RenderContext,
+    texture::BevyDefault,
+    view::ExtractedWindows,
+    Extract, RenderApp, RenderStage,
+};
+use bevy_app::{App, Plugin};
+use bevy_asset::Assets;
+use bevy_ecs::prelude::*;
+use bevy_tasks::IoTaskPool;
+use bevy_utils::tracing::{error, info, warn};
+use bevy_window::WindowId;
+use parking_lot::Mutex;
+use std::path::Path;
+use wgpu::{Extent3d, TextureDimension, TextureFormat, TextureUsages};
+
+type ScreenshotCallback = Box<dyn FnOnce(Image) + Send + Sync>;
+
+/// Adds the [`ScreenshotManager`] resource, and copies the render targets it captures at the end
+/// of the render graph.
+#[derive(Default)]
+pub struct ScreenshotPlugin;
+
+impl Plugin for ScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScreenshotManager>();
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_system_to_stage(RenderStage::Extract, extract_screenshots);
+
+            let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+            render_graph.add_node(node::SCREENSHOT, ScreenshotNode);
+            render_graph
+                .add_node_edge(node::CAMERA_DRIVER, node::SCREENSHOT)
+                .unwrap();
+        }
+    }
+}
+
+/// Captures the frames rendered to windows and to images, and reads them back on the CPU.
+///
+/// The frames are copied at the end of the render graph of the frame they were requested during,
+/// and read back without stalling the rendering, a frame or two later.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::{camera::RenderTarget, view::ScreenshotManager};
+/// fn take_screenshot(mut screenshots: ResMut<ScreenshotManager>) {
+///     screenshots.save_screenshot_to_disk(RenderTarget::default(), "screenshot.png");
+/// }
+/// # bevy_ecs::system::assert_is_system(take_screenshot);
+/// ```
+#[derive(Default)]
+pub struct ScreenshotManager {
+    requests: Mutex<Vec<(RenderTarget, ScreenshotCallback)>>,
+}
+
+impl ScreenshotManager {
+    /// Captures the frame rendered to `target`, and calls `callback` with it from the render
+    /// world once it is available on the CPU.
+    ///
+    /// The [`Image`] has the format of the target, usually
+    /// [`TextureFormat::Bgra8UnormSrgb`] for windows. The texture of an image target must have
+    /// the [`TextureUsages::COPY_SRC`] usage.
+    pub fn take_screenshot(
+        &mut self,
+        target: RenderTarget,
+        callback: impl FnOnce(Image) + Send + Sync + 'static,
+    ) {
+        self.requests.get_mut().push((target, Box::new(callback)));
+    }
+
+    /// Captures the frame rendered to `target`, and saves it to the file at `path` on the
+    /// [`IoTaskPool`]. The image format is deduced from the extension of `path`, and must be
+    /// enabled with its cargo feature, like `png`.
+    pub fn save_screenshot_to_disk(&mut self, target: RenderTarget, path: impl AsRef<Path>) {
+        let path = path.as_ref().to_owned();
+        self.take_screenshot(target, move |image| {
+            IoTaskPool::get()
+                .spawn(async move {
+                    let dynamic_image = match image.try_into_dynamic() {
+                        Some(dynamic_image) => dynamic_image,
+                        None => {
+                            error!(
+                                "Cannot save screenshot, format {:?} is not supported",
+                                image.texture_descriptor.format
+                            );
+                            return;
+                        }
+                    };
+                    match dynamic_image.save(&path) {
+                        Ok(()) => info!("Screenshot saved to {}", path.display()),
+                        Err(err) => error!("Cannot save screenshot to {}: {}", path.display(), err),
+                    }
+                })
+                .detach();
+        });
+    }
+}
+
+/// The screenshots requested during the extracted frame, captured by the [`ScreenshotNode`]
+#[derive(Default)]
+pub struct ExtractedScreenshots {
+    requests: Mutex<Vec<(RenderTarget, ScreenshotCallback)>>,
+}
+
+impl ExtractedScreenshots {
+    /// Returns `true` if a screenshot of the window was requested during this frame.
+    pub fn is_requested(&self, window: WindowId) -> bool {
+        self.requests
+            .lock()
+            .iter()
+            .any(|(target, _)| *target == RenderTarget::Window(window))
+    }
+}
+
+/// System extracting the screenshots requested with the [`ScreenshotManager`] during this frame
+pub fn extract_screenshots(
+    mut extracted_screenshots: ResMut<ExtractedScreenshots>,
+    screenshot_manager: Extract<Res<ScreenshotManager>>,
+    images: Extract<Res<Assets<Image>>>,
+) {
+    let extracted_requests = extracted_screenshots.requests.get_mut();
+    extracted_requests.clear();
+    for (target, callback) in screenshot_manager.requests.lock().drain(..) {
+        if let RenderTarget::Image(handle) = &target {
+            let copyable = images.get(handle).map_or(false, |image| {
+                image
+                    .texture_descriptor
+                    .usage
+                    .contains(TextureUsages::COPY_SRC)
+            });
+            if !copyable {
+                warn!(
+                    "Cannot take a screenshot of image {:?}, its texture doesn't have the COPY_SRC usage",
+                    handle
+                );
+                continue;
+            }
+        }
+        extracted_requests.push((target, callback));
+    }
+}
+
+/// A [`Node`] copying the render targets of the [`ExtractedScreenshots`] to read them back, run
+/// after the cameras rendered them.
+pub struct ScreenshotNode;
+
+impl Node for ScreenshotNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let requests =
+            std::mem::take(&mut *world.resource::<ExtractedScreenshots>().requests.lock());
+        if requests.is_empty() {
+            return Ok(());
+        }
+
+        let windows = world.resource::<ExtractedWindows>();
+        let images = world.resource::<RenderAssets<Image>>();
+        let gpu_readbacks = world.resource::<GpuReadbacks>();
+        for (target, callback) in requests {
+            let source = match &target {
+                RenderTarget::Window(id) => windows.get(id).and_then(|window| {
+                    let texture = window.swap_chain_texture.as_ref()?.surface_texture()?;
+                    let size = Extent3d {
+                        width: window.physical_width,
+                        height: window.physical_height,
+                        depth_or_array_layers: 1,
+                    };
+                    Some((texture, size, TextureFormat::bevy_default()))
+                }),
+                RenderTarget::Image(handle) => images.get(handle).map(|image| {
+                    let size = Extent3d {
+                        width: image.size.x as u32,
+                        height: image.size.y as u32,
+                        depth_or_array_layers: 1,
+                    };
+                    (&*image.texture, size, image.texture_format)
+                }),
+            };
+            let (texture, size, format) = match source {
+                Some(source) => source,
+                None => {
+                    warn!(
+                        "Cannot take a screenshot of {:?}, it wasn't rendered this frame",
+                        target
+                    );
+                    continue;
+                }
+            };
+
+            gpu_readbacks.read_texture(
+                &render_context.render_device,
+                &mut render_context.command_encoder,
+                texture,
+                size,
+                format,
+                move |data| {
+                    callback(Image::new(
+                        size,
+                        TextureDimension::D2,
+                        data.to_vec(),
+                        format,
+                    ))
+                },
+            );
+        }
+        Ok(())
+    }
+}
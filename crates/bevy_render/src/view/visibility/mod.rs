@@ -45,7 +45,12 @@ impl Default for ComputedVisibility {
     }
 }
 
-/// Use this component to opt-out of built-in frustum culling for Mesh entities
+/// Use this component to opt-out of built-in frustum culling for Mesh entities, and for sprites
+/// and text in 2d scenes.
+///
+/// The entities without an [`Aabb`] aren't culled either: they are computed for meshes by
+/// [`calculate_bounds`], and by the systems labelled [`VisibilitySystems::CalculateBounds`] for the
+/// other entities.
 #[derive(Component)]
 pub struct NoFrustumCulling;
 
@@ -58,9 +63,6 @@ pub struct NoFrustumCulling;
 ///
 /// This component is intended to be attached to the same entity as the [`Camera`] and
 /// the [`Frustum`] defining the view.
-///
-/// Currently this component is ignored by the sprite renderer, so sprite rendering
-/// is not optimized per view.
 #[derive(Clone, Component, Default, Debug, Reflect)]
 #[reflect(Component)]
 pub struct VisibleEntities {
@@ -84,6 +86,7 @@ impl VisibleEntities {
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum VisibilitySystems {
+    /// Label for the systems computing the [`Aabb`] of the entities which are frustum culled
     CalculateBounds,
     UpdateOrthographicFrusta,
     UpdatePerspectiveFrusta,
@@ -134,15 +137,28 @@ impl Plugin for VisibilityPlugin {
     }
 }
 
+/// System computing the [`Aabb`] of the meshes which don't have one yet, or which
+/// `Handle<Mesh>` changed.
 pub fn calculate_bounds(
     mut commands: Commands,
     meshes: Res<Assets<Mesh>>,
-    without_aabb: Query<(Entity, &Handle<Mesh>), (Without<Aabb>, Without<NoFrustumCulling>)>,
+    mut mesh_query: Query<
+        (Entity, &Handle<Mesh>, Option<&mut Aabb>),
+        (
+            Or<(Without<Aabb>, Changed<Handle<Mesh>>)>,
+            Without<NoFrustumCulling>,
+        ),
+    >,
 ) {
-    for (entity, mesh_handle) in without_aabb.iter() {
-        if let Some(mesh) = meshes.get(mesh_handle) {
-            if let Some(aabb) = mesh.compute_aabb() {
-                commands.entity(entity).insert(aabb);
+    for (entity, mesh_handle, aabb) in mesh_query.iter_mut() {
+        let bounds = match meshes.get(mesh_handle).and_then(Mesh::compute_aabb) {
+            Some(bounds) => bounds,
+            None => continue,
+        };
+        match aabb {
+            Some(mut aabb) => *aabb = bounds,
+            None => {
+                commands.entity(entity).insert(bounds);
             }
         }
     }
@@ -2,6 +2,7 @@ use crate::{
     render_resource::TextureView,
     renderer::{RenderDevice, RenderInstance},
     texture::BevyDefault,
+    view::ExtractedScreenshots,
     Extract, RenderApp, RenderStage,
 };
 use bevy_app::{App, Plugin};
@@ -28,6 +29,7 @@ impl Plugin for WindowRenderPlugin {
             render_app
                 .init_resource::<ExtractedWindows>()
                 .init_resource::<WindowSurfaces>()
+                .init_resource::<ExtractedScreenshots>()
                 .init_resource::<NonSendMarker>()
                 .add_system_to_stage(RenderStage::Extract, extract_windows)
                 .add_system_to_stage(
@@ -118,6 +120,8 @@ pub struct WindowSurfaces {
     surfaces: HashMap<WindowId, wgpu::Surface>,
     /// List of windows that we have already called the initial `configure_surface` for
     configured_windows: HashSet<WindowId>,
+    /// List of windows whose surface can be copied, once a screenshot of them was requested
+    copyable_windows: HashSet<WindowId>,
 }
 
 /// Creates and (re)configures window surfaces, and obtains a swapchain texture for rendering.
@@ -149,6 +153,7 @@ pub fn prepare_windows(
     mut window_surfaces: ResMut<WindowSurfaces>,
     render_device: Res<RenderDevice>,
     render_instance: Res<RenderInstance>,
+    screenshots: Res<ExtractedScreenshots>,
 ) {
     let window_surfaces = window_surfaces.deref_mut();
     for window in windows.windows.values_mut() {
@@ -160,11 +165,21 @@ pub fn prepare_windows(
                 render_instance.create_surface(&window.handle.get_handle())
             });
 
+        // Surfaces are only made copyable for screenshots when needed, as not all backends
+        // support it
+        let became_copyable = screenshots.is_requested(window.id)
+            && window_surfaces.copyable_windows.insert(window.id);
+        let usage = if window_surfaces.copyable_windows.contains(&window.id) {
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC
+        } else {
+            wgpu::TextureUsages::RENDER_ATTACHMENT
+        };
+
         let swap_chain_descriptor = wgpu::SurfaceConfiguration {
             format: TextureFormat::bevy_default(),
             width: window.physical_width,
             height: window.physical_height,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage,
             present_mode: match window.present_mode {
                 PresentMode::Fifo => wgpu::PresentMode::Fifo,
                 PresentMode::Mailbox => wgpu::PresentMode::Mailbox,
@@ -173,7 +188,10 @@ pub fn prepare_windows(
         };
 
         // Do the initial surface configuration if it hasn't been configured yet
-        if window_surfaces.configured_windows.insert(window.id) || window.size_changed {
+        if window_surfaces.configured_windows.insert(window.id)
+            || window.size_changed
+            || became_copyable
+        {
             render_device.configure_surface(surface, &swap_chain_descriptor);
         }
 
@@ -0,0 +1,852 @@
+//! A compact binary serialization format for scenes, see [`serialize_binary`].
+
+use bevy_utils::HashMap;
+use serde::{
+    de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor},
+    ser::{self, Serialize},
+};
+use std::fmt;
+
+const MAGIC: &[u8; 4] = b"BSCN";
+const VERSION: u8 = 1;
+
+const TAG_NONE: u8 = 0;
+const TAG_UNIT: u8 = 1;
+const TAG_FALSE: u8 = 2;
+const TAG_TRUE: u8 = 3;
+const TAG_UNSIGNED: u8 = 4;
+const TAG_SIGNED: u8 = 5;
+const TAG_F32: u8 = 6;
+const TAG_F64: u8 = 7;
+const TAG_CHAR: u8 = 8;
+const TAG_STR: u8 = 9;
+const TAG_STR_REF: u8 = 10;
+const TAG_BYTES: u8 = 11;
+const TAG_SOME: u8 = 12;
+const TAG_SEQ: u8 = 13;
+const TAG_MAP: u8 = 14;
+const TAG_END: u8 = 15;
+const TAG_ENUM: u8 = 16;
+
+/// An error that occurs when serializing or deserializing a binary scene.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BinarySceneError {
+    /// An error reported by a [`Serialize`] or [`Deserialize`](serde::Deserialize)
+    /// implementation.
+    Message(String),
+    /// The data doesn't start with the header of binary scenes, or was written by a newer
+    /// version.
+    InvalidHeader,
+    /// The data ended before the end of a value.
+    UnexpectedEnd,
+    /// The data contains an unknown value tag, or a tag that isn't valid at this position.
+    InvalidTag(u8),
+    /// The data contains a string that isn't valid UTF-8, or an invalid `char`.
+    InvalidString,
+    /// The data continues after the end of the serialized value.
+    TrailingBytes,
+    /// 128-bit integers are not supported.
+    UnsupportedType,
+}
+
+impl std::error::Error for BinarySceneError {}
+
+impl fmt::Display for BinarySceneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BinarySceneError::Message(message) => f.write_str(message),
+            BinarySceneError::InvalidHeader => f.write_str("invalid binary scene header"),
+            BinarySceneError::UnexpectedEnd => f.write_str("unexpected end of binary scene"),
+            BinarySceneError::InvalidTag(tag) => write!(f, "invalid value tag {}", tag),
+            BinarySceneError::InvalidString => f.write_str("invalid UTF-8 string or char"),
+            BinarySceneError::TrailingBytes => f.write_str("trailing bytes after binary scene"),
+            BinarySceneError::UnsupportedType => f.write_str("128-bit integers are not supported"),
+        }
+    }
+}
+
+impl ser::Error for BinarySceneError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        BinarySceneError::Message(msg.to_string())
+    }
+}
+
+impl de::Error for BinarySceneError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        BinarySceneError::Message(msg.to_string())
+    }
+}
+
+/// Serialize a given Rust data structure into a compact binary format, much smaller and faster to
+/// load than ron.
+///
+/// The format is self-describing, so that it supports everything reflection can serialize: each
+/// value starts with a tag byte, integers are variable-length encoded, and repeated strings like
+/// type and field names are only written once, then referred to by index.
+///
+/// It is read back with [`deserialize_binary`].
+pub fn serialize_binary<S>(serialize: S) -> Result<Vec<u8>, BinarySceneError>
+where
+    S: Serialize,
+{
+    let mut serializer = BinarySerializer {
+        output: MAGIC.to_vec(),
+        strings: HashMap::default(),
+    };
+    serializer.output.push(VERSION);
+    serialize.serialize(&mut serializer)?;
+    Ok(serializer.output)
+}
+
+/// Deserialize a value serialized with [`serialize_binary`], using a [`DeserializeSeed`] like
+/// [`SceneDeserializer`](crate::serde::SceneDeserializer).
+pub fn deserialize_binary<'de, S>(seed: S, bytes: &'de [u8]) -> Result<S::Value, BinarySceneError>
+where
+    S: DeserializeSeed<'de>,
+{
+    let input = match bytes.strip_prefix(MAGIC) {
+        Some([version, input @ ..]) if *version <= VERSION => input,
+        _ => return Err(BinarySceneError::InvalidHeader),
+    };
+    let mut deserializer = BinaryDeserializer {
+        input,
+        strings: Vec::new(),
+    };
+    let value = seed.deserialize(&mut deserializer)?;
+    if deserializer.input.is_empty() {
+        Ok(value)
+    } else {
+        Err(BinarySceneError::TrailingBytes)
+    }
+}
+
+struct BinarySerializer {
+    output: Vec<u8>,
+    strings: HashMap<String, u64>,
+}
+
+impl BinarySerializer {
+    fn write_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.output.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.output.push(value as u8);
+    }
+
+    fn write_str(&mut self, value: &str) {
+        if let Some(&index) = self.strings.get(value) {
+            self.output.push(TAG_STR_REF);
+            self.write_varint(index);
+        } else {
+            let index = self.strings.len() as u64;
+            self.strings.insert(value.to_string(), index);
+            self.output.push(TAG_STR);
+            self.write_varint(value.len() as u64);
+            self.output.extend_from_slice(value.as_bytes());
+        }
+    }
+
+    fn write_variant(&mut self, variant: &str) {
+        self.output.push(TAG_ENUM);
+        self.write_str(variant);
+    }
+}
+
+impl ser::Serializer for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinarySceneError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), BinarySceneError> {
+        self.output.push(if v { TAG_TRUE } else { TAG_FALSE });
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), BinarySceneError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), BinarySceneError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), BinarySceneError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), BinarySceneError> {
+        self.output.push(TAG_SIGNED);
+        // zigzag encoding, so that small negative numbers are small too
+        self.write_varint(((v << 1) ^ (v >> 63)) as u64);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), BinarySceneError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), BinarySceneError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), BinarySceneError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), BinarySceneError> {
+        self.output.push(TAG_UNSIGNED);
+        self.write_varint(v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), BinarySceneError> {
+        self.output.push(TAG_F32);
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), BinarySceneError> {
+        self.output.push(TAG_F64);
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), BinarySceneError> {
+        self.output.push(TAG_CHAR);
+        self.write_varint(v as u64);
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), BinarySceneError> {
+        self.write_str(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), BinarySceneError> {
+        self.output.push(TAG_BYTES);
+        self.write_varint(v.len() as u64);
+        self.output.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), BinarySceneError> {
+        self.output.push(TAG_NONE);
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), BinarySceneError> {
+        self.output.push(TAG_SOME);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), BinarySceneError> {
+        self.output.push(TAG_UNIT);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), BinarySceneError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), BinarySceneError> {
+        self.write_variant(variant);
+        self.serialize_unit()
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), BinarySceneError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), BinarySceneError> {
+        self.write_variant(variant);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self, BinarySceneError> {
+        self.output.push(TAG_SEQ);
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self, BinarySceneError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self, BinarySceneError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self, BinarySceneError> {
+        self.write_variant(variant);
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self, BinarySceneError> {
+        self.output.push(TAG_MAP);
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self, BinarySceneError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self, BinarySceneError> {
+        self.write_variant(variant);
+        self.serialize_map(Some(len))
+    }
+}
+
+impl ser::SerializeSeq for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinarySceneError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.output.push(TAG_END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinarySceneError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinarySceneError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinarySceneError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeMap for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinarySceneError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.output.push(TAG_END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinarySceneError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.write_str(key);
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+impl ser::SerializeStructVariant for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinarySceneError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.write_str(key);
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+struct BinaryDeserializer<'de> {
+    input: &'de [u8],
+    strings: Vec<String>,
+}
+
+impl<'de> BinaryDeserializer<'de> {
+    fn peek_tag(&self) -> Result<u8, BinarySceneError> {
+        self.input
+            .first()
+            .copied()
+            .ok_or(BinarySceneError::UnexpectedEnd)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'de [u8], BinarySceneError> {
+        if self.input.len() < len {
+            return Err(BinarySceneError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    fn read_tag(&mut self) -> Result<u8, BinarySceneError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_varint(&mut self) -> Result<u64, BinarySceneError> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_tag()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(BinarySceneError::InvalidTag(TAG_UNSIGNED))
+    }
+
+    fn read_len(&mut self) -> Result<usize, BinarySceneError> {
+        let len = self.read_varint()? as usize;
+        if len > self.input.len() {
+            return Err(BinarySceneError::UnexpectedEnd);
+        }
+        Ok(len)
+    }
+
+    /// Reads the string after a [`TAG_STR`] or [`TAG_STR_REF`] tag, returning its index.
+    fn read_str(&mut self, tag: u8) -> Result<usize, BinarySceneError> {
+        match tag {
+            TAG_STR => {
+                let len = self.read_len()?;
+                let bytes = self.read_bytes(len)?;
+                let string = std::str::from_utf8(bytes)
+                    .map_err(|_| BinarySceneError::InvalidString)?
+                    .to_string();
+                self.strings.push(string);
+                Ok(self.strings.len() - 1)
+            }
+            TAG_STR_REF => {
+                let index = self.read_varint()? as usize;
+                if index < self.strings.len() {
+                    Ok(index)
+                } else {
+                    Err(BinarySceneError::InvalidTag(TAG_STR_REF))
+                }
+            }
+            tag => Err(BinarySceneError::InvalidTag(tag)),
+        }
+    }
+
+    fn expect_tag(&mut self, expected: u8) -> Result<(), BinarySceneError> {
+        match self.read_tag()? {
+            tag if tag == expected => Ok(()),
+            tag => Err(BinarySceneError::InvalidTag(tag)),
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut BinaryDeserializer<'de> {
+    type Error = BinarySceneError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinarySceneError> {
+        match self.read_tag()? {
+            TAG_NONE => visitor.visit_none(),
+            TAG_UNIT => visitor.visit_unit(),
+            TAG_FALSE => visitor.visit_bool(false),
+            TAG_TRUE => visitor.visit_bool(true),
+            TAG_UNSIGNED => visitor.visit_u64(self.read_varint()?),
+            TAG_SIGNED => {
+                let v = self.read_varint()?;
+                visitor.visit_i64((v >> 1) as i64 ^ -((v & 1) as i64))
+            }
+            TAG_F32 => {
+                let bytes = self.read_bytes(4)?;
+                visitor.visit_f32(f32::from_le_bytes(bytes.try_into().unwrap()))
+            }
+            TAG_F64 => {
+                let bytes = self.read_bytes(8)?;
+                visitor.visit_f64(f64::from_le_bytes(bytes.try_into().unwrap()))
+            }
+            TAG_CHAR => {
+                let v = self.read_varint()?;
+                let v = u32::try_from(v)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or(BinarySceneError::InvalidString)?;
+                visitor.visit_char(v)
+            }
+            tag @ (TAG_STR | TAG_STR_REF) => {
+                let index = self.read_str(tag)?;
+                visitor.visit_str(&self.strings[index])
+            }
+            TAG_BYTES => {
+                let len = self.read_len()?;
+                visitor.visit_borrowed_bytes(self.read_bytes(len)?)
+            }
+            TAG_SOME => visitor.visit_some(self),
+            TAG_SEQ => {
+                let mut compound = Compound::new(self);
+                let value = visitor.visit_seq(&mut compound)?;
+                compound.end()?;
+                Ok(value)
+            }
+            TAG_MAP => {
+                let mut compound = Compound::new(self);
+                let value = visitor.visit_map(&mut compound)?;
+                compound.end()?;
+                Ok(value)
+            }
+            TAG_ENUM => visitor.visit_enum(Enum { deserializer: self }),
+            tag => Err(BinarySceneError::InvalidTag(tag)),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinarySceneError> {
+        match self.peek_tag()? {
+            TAG_NONE => {
+                self.read_tag()?;
+                visitor.visit_none()
+            }
+            TAG_SOME => {
+                self.read_tag()?;
+                visitor.visit_some(self)
+            }
+            tag => Err(BinarySceneError::InvalidTag(tag)),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, BinarySceneError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, BinarySceneError> {
+        self.expect_tag(TAG_ENUM)?;
+        visitor.visit_enum(Enum { deserializer: self })
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, BinarySceneError> {
+        Err(BinarySceneError::UnsupportedType)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, BinarySceneError> {
+        Err(BinarySceneError::UnsupportedType)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// The elements of a sequence or map, until the [`TAG_END`] tag.
+struct Compound<'a, 'de> {
+    deserializer: &'a mut BinaryDeserializer<'de>,
+    ended: bool,
+}
+
+impl<'a, 'de> Compound<'a, 'de> {
+    fn new(deserializer: &'a mut BinaryDeserializer<'de>) -> Self {
+        Self {
+            deserializer,
+            ended: false,
+        }
+    }
+
+    /// Reads the [`TAG_END`] tag if the visitor didn't read all the elements, like the ones of
+    /// tuples which stop after their last element.
+    fn end(self) -> Result<(), BinarySceneError> {
+        if self.ended {
+            Ok(())
+        } else {
+            self.deserializer.expect_tag(TAG_END)
+        }
+    }
+}
+
+impl<'a, 'de> SeqAccess<'de> for Compound<'a, 'de> {
+    type Error = BinarySceneError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, BinarySceneError> {
+        if self.ended {
+            return Ok(None);
+        }
+        if self.deserializer.peek_tag()? == TAG_END {
+            self.deserializer.read_tag()?;
+            self.ended = true;
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+}
+
+impl<'a, 'de> MapAccess<'de> for Compound<'a, 'de> {
+    type Error = BinarySceneError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, BinarySceneError> {
+        self.next_element_seed(seed)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, BinarySceneError> {
+        seed.deserialize(&mut *self.deserializer)
+    }
+}
+
+struct Enum<'a, 'de> {
+    deserializer: &'a mut BinaryDeserializer<'de>,
+}
+
+impl<'a, 'de> EnumAccess<'de> for Enum<'a, 'de> {
+    type Error = BinarySceneError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), BinarySceneError> {
+        let variant = seed.deserialize(&mut *self.deserializer)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'a, 'de> VariantAccess<'de> for Enum<'a, 'de> {
+    type Error = BinarySceneError;
+
+    fn unit_variant(self) -> Result<(), BinarySceneError> {
+        self.deserializer.expect_tag(TAG_UNIT)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, BinarySceneError> {
+        seed.deserialize(self.deserializer)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, BinarySceneError> {
+        self.deserializer.expect_tag(TAG_SEQ)?;
+        let mut compound = Compound::new(self.deserializer);
+        let value = visitor.visit_seq(&mut compound)?;
+        compound.end()?;
+        Ok(value)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, BinarySceneError> {
+        self.deserializer.expect_tag(TAG_MAP)?;
+        let mut compound = Compound::new(self.deserializer);
+        let value = visitor.visit_map(&mut compound)?;
+        compound.end()?;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deserialize_binary, serialize_binary, BinarySceneError};
+    use crate::{serde::SceneDeserializer, DynamicScene};
+    use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+    use serde::{de::DeserializeSeed, Deserialize, Serialize};
+    use std::marker::PhantomData;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f32),
+        Rect { width: f32, height: f64 },
+        Line(i8, i64),
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Data {
+        name: String,
+        id: u64,
+        offset: i32,
+        visible: bool,
+        letter: char,
+        parent: Option<u32>,
+        shapes: Vec<Shape>,
+        pair: (u8, String),
+        unit: (),
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let data = Data {
+            name: "scene".to_string(),
+            id: u64::MAX,
+            offset: -300,
+            visible: true,
+            letter: 'é',
+            parent: None,
+            shapes: vec![
+                Shape::Empty,
+                Shape::Circle(1.5),
+                Shape::Rect {
+                    width: 2.0,
+                    height: -3.0,
+                },
+                Shape::Line(-1, i64::MIN),
+            ],
+            pair: (7, "scene".to_string()),
+            unit: (),
+        };
+        let bytes = serialize_binary(&data).unwrap();
+        let deserialized: Data = deserialize_binary(PhantomData, &bytes).unwrap();
+        assert_eq!(deserialized, data);
+
+        assert_eq!(
+            deserialize_binary::<PhantomData<Data>>(PhantomData, &bytes[..bytes.len() - 1]),
+            Err(BinarySceneError::UnexpectedEnd)
+        );
+        assert_eq!(
+            deserialize_binary::<PhantomData<Data>>(PhantomData, b"RON"),
+            Err(BinarySceneError::InvalidHeader)
+        );
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Player {
+        name: String,
+        health: f32,
+        inventory: Vec<u32>,
+    }
+
+    #[test]
+    fn binary_scene_round_trip() {
+        let mut world = World::new();
+        world.spawn().insert(Player {
+            name: "Bevy".to_string(),
+            health: 0.75,
+            inventory: vec![4, 8, 15],
+        });
+        world.spawn().insert(Player::default());
+        let registry = TypeRegistryArc::default();
+        registry.write().register::<Player>();
+        registry.write().register::<String>();
+        registry.write().register::<Vec<u32>>();
+
+        let scene = DynamicScene::from_world(&world, &registry);
+        let bytes = scene.serialize_binary(&registry).unwrap();
+        let ron = scene.serialize_ron(&registry).unwrap();
+        assert!(bytes.len() < ron.len() / 2);
+
+        // Dynamic lists lose their type name when deserialized, compare with a ron round trip
+        let mut ron_deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+        let from_ron = SceneDeserializer {
+            type_registry: &registry.read(),
+        }
+        .deserialize(&mut ron_deserializer)
+        .unwrap();
+        let from_binary = deserialize_binary(
+            SceneDeserializer {
+                type_registry: &registry.read(),
+            },
+            &bytes,
+        )
+        .unwrap();
+        assert_eq!(
+            from_binary.serialize_ron(&registry).unwrap(),
+            from_ron.serialize_ron(&registry).unwrap()
+        );
+    }
+}
@@ -1,11 +1,16 @@
-use crate::{serde::SceneSerializer, Scene, SceneSpawnError};
+use crate::{
+    serde::SceneSerializer, BinarySceneError, DynamicSceneBuilder, Scene, SceneFilter,
+    SceneSpawnError,
+};
 use anyhow::Result;
+use bevy_asset::Handle;
 use bevy_ecs::{
-    entity::EntityMap,
+    entity::{Entity, EntityMap},
+    query::WorldQuery,
     reflect::{ReflectComponent, ReflectMapEntities},
     world::World,
 };
-use bevy_reflect::{Reflect, TypeRegistryArc, TypeUuid};
+use bevy_reflect::{GetPath, Reflect, ReflectRef, TypeRegistryArc, TypeUuid};
 use serde::Serialize;
 
 /// A collection of serializable dynamic entities, each with its own run-time defined set of components.
@@ -28,6 +33,37 @@ pub struct DynamicEntity {
     /// A vector of boxed components that belong to the given entity and
     /// implement the `Reflect` trait.
     pub components: Vec<Box<dyn Reflect>>,
+    /// Other scenes spawned as children of this entity, with their overrides.
+    pub scenes: Vec<DynamicSceneInstance>,
+}
+
+/// A reference to another scene, spawned as a child of a [`DynamicEntity`] of this scene.
+///
+/// The [`overrides`](Self::overrides) change the components of the referenced scene for this
+/// instance only, so that variants of a prefab scene (like an enemy with more health or another
+/// texture) don't have to duplicate the whole scene.
+pub struct DynamicSceneInstance {
+    /// The asset path of the referenced scene.
+    pub path: String,
+    /// The handle of the referenced scene. It is set from the [`path`](Self::path) when the
+    /// scene is loaded by the [`SceneLoader`](crate::SceneLoader).
+    pub handle: Handle<DynamicScene>,
+    /// The changes made to the components of the referenced scene.
+    pub overrides: Vec<SceneOverride>,
+}
+
+/// Overrides a field of a component of an entity spawned from a [`DynamicSceneInstance`].
+pub struct SceneOverride {
+    /// The identifier of the entity in the referenced scene.
+    pub entity: u32,
+    /// The type name of the component.
+    pub component: String,
+    /// The reflection path of the overridden field in the component, as used by
+    /// [`GetPath`]. An empty path overrides the whole component, adding it if the entity
+    /// doesn't have it.
+    pub path: String,
+    /// The value applied to the field.
+    pub value: Box<dyn Reflect>,
 }
 
 impl DynamicScene {
@@ -50,6 +86,7 @@ impl DynamicScene {
                 scene.entities.push(DynamicEntity {
                     entity: entity.id(),
                     components: Vec::new(),
+                    scenes: Vec::new(),
                 });
             }
 
@@ -75,6 +112,17 @@ impl DynamicScene {
         scene
     }
 
+    /// Create a new dynamic scene from the entities of `world` matching the query filter `F`,
+    /// with only their component types allowed by `filter`.
+    ///
+    /// Use a [`DynamicSceneBuilder`] to select the extracted entities in other ways.
+    pub fn from_query<F: WorldQuery>(world: &mut World, filter: SceneFilter) -> Self {
+        let entities: Vec<Entity> = world.query_filtered::<Entity, F>().iter(world).collect();
+        let mut builder = DynamicSceneBuilder::from_world(world).with_filter(filter);
+        builder.extract_entities(entities.into_iter());
+        builder.build()
+    }
+
     /// Write the dynamic entities and their corresponding components to the given world.
     ///
     /// This method will return a `SceneSpawnError` if either a type is not registered
@@ -83,6 +131,19 @@ impl DynamicScene {
         &self,
         world: &mut World,
         entity_map: &mut EntityMap,
+    ) -> Result<(), SceneSpawnError> {
+        self.write_to_world_with_filter(world, entity_map, &SceneFilter::AllowAll)
+    }
+
+    /// Write the dynamic entities and only their components allowed by `filter` to the given
+    /// world, see [`DynamicScene::write_to_world`].
+    ///
+    /// This is useful to load a save game while keeping some components of the world as is.
+    pub fn write_to_world_with_filter(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityMap,
+        filter: &SceneFilter,
     ) -> Result<(), SceneSpawnError> {
         let registry = world.resource::<TypeRegistryArc>().clone();
         let type_registry = registry.read();
@@ -92,7 +153,7 @@ impl DynamicScene {
             // or spawn a new entity with a transiently unique id if there is
             // no corresponding entry.
             let entity = *entity_map
-                .entry(bevy_ecs::entity::Entity::from_raw(scene_entity.entity))
+                .entry(Entity::from_raw(scene_entity.entity))
                 .or_insert_with(|| world.spawn().id());
 
             // Apply/ add each component to the given entity.
@@ -108,6 +169,9 @@ impl DynamicScene {
                             type_name: component.type_name().to_string(),
                         }
                     })?;
+                if !filter.is_allowed_by_id(registration.type_id()) {
+                    continue;
+                }
 
                 // If the entity already has the given component attached,
                 // just apply the (possibly) new value, otherwise add the
@@ -139,6 +203,82 @@ impl DynamicScene {
     pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    /// Serialize this dynamic scene into a compact binary format, see
+    /// [`serialize_binary`](crate::serialize_binary).
+    pub fn serialize_binary(
+        &self,
+        registry: &TypeRegistryArc,
+    ) -> Result<Vec<u8>, BinarySceneError> {
+        SceneSerializer::new(self, registry).to_binary()
+    }
+}
+
+impl DynamicSceneInstance {
+    /// Applies the [`overrides`](Self::overrides) to the entities spawned from the referenced
+    /// scene, found in `entity_map`.
+    pub fn apply_overrides(
+        &self,
+        world: &mut World,
+        entity_map: &EntityMap,
+    ) -> Result<(), SceneSpawnError> {
+        let registry = world.resource::<TypeRegistryArc>().clone();
+        let type_registry = registry.read();
+
+        for scene_override in &self.overrides {
+            let invalid_override = |message: String| SceneSpawnError::InvalidOverride {
+                entity: scene_override.entity,
+                type_name: scene_override.component.clone(),
+                message,
+            };
+            let entity = entity_map
+                .get(Entity::from_raw(scene_override.entity))
+                .map_err(|_| invalid_override("the scene has no such entity".to_string()))?;
+            let registration = type_registry
+                .get_with_name(&scene_override.component)
+                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                    type_name: scene_override.component.clone(),
+                })?;
+            let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+                SceneSpawnError::UnregisteredComponent {
+                    type_name: scene_override.component.clone(),
+                }
+            })?;
+
+            if scene_override.path.is_empty() {
+                if world
+                    .entity(entity)
+                    .contains_type_id(registration.type_id())
+                {
+                    reflect_component.apply(world, entity, &*scene_override.value);
+                } else {
+                    reflect_component.add(world, entity, &*scene_override.value);
+                }
+                continue;
+            }
+
+            let mut component = reflect_component
+                .reflect_mut(world, entity)
+                .ok_or_else(|| invalid_override("the entity has no such component".to_string()))?;
+            let field = component
+                .path_mut(&scene_override.path)
+                .map_err(|err| invalid_override(format!("`{}`: {}", scene_override.path, err)))?;
+            // Applying a value of another type panics, so check it first
+            if let ReflectRef::Value(_) = field.reflect_ref() {
+                if field.type_name() != scene_override.value.type_name() {
+                    return Err(invalid_override(format!(
+                        "`{}` is a `{}`, not a `{}`",
+                        scene_override.path,
+                        field.type_name(),
+                        scene_override.value.type_name()
+                    )));
+                }
+            }
+            field.apply(&*scene_override.value);
+        }
+
+        Ok(())
+    }
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
@@ -0,0 +1,329 @@
+use crate::{DynamicEntity, DynamicScene};
+use bevy_ecs::{component::Component, entity::Entity, reflect::ReflectComponent, world::World};
+use bevy_reflect::TypeRegistryArc;
+use bevy_utils::HashSet;
+use std::{any::TypeId, collections::BTreeMap};
+
+/// Selects the component types that are extracted into a [`DynamicScene`] by a
+/// [`DynamicSceneBuilder`], or written to the world by [`DynamicScene::write_to_world_with_filter`].
+///
+/// This is used to skip the components that are only meaningful at runtime, like computed
+/// layouts or render data, when saving a game.
+#[derive(Clone, Debug, Default)]
+pub enum SceneFilter {
+    /// All the component types are allowed.
+    #[default]
+    AllowAll,
+    /// Only these component types are allowed.
+    Allow(HashSet<TypeId>),
+    /// All the component types but these are allowed.
+    Deny(HashSet<TypeId>),
+}
+
+impl SceneFilter {
+    /// Allows the component type `T`. If no type was allowed before, all the other types are
+    /// denied.
+    #[must_use]
+    pub fn allow<T: Component>(self) -> Self {
+        self.allow_by_id(TypeId::of::<T>())
+    }
+
+    /// Allows the component type with the given [`TypeId`], see [`SceneFilter::allow`].
+    #[must_use]
+    pub fn allow_by_id(self, type_id: TypeId) -> Self {
+        match self {
+            SceneFilter::AllowAll => SceneFilter::Allow([type_id].into_iter().collect()),
+            SceneFilter::Allow(mut types) => {
+                types.insert(type_id);
+                SceneFilter::Allow(types)
+            }
+            SceneFilter::Deny(mut types) => {
+                types.remove(&type_id);
+                SceneFilter::Deny(types)
+            }
+        }
+    }
+
+    /// Denies the component type `T`. If only some types were allowed, `T` is not allowed
+    /// anymore, otherwise all the other types stay allowed.
+    #[must_use]
+    pub fn deny<T: Component>(self) -> Self {
+        self.deny_by_id(TypeId::of::<T>())
+    }
+
+    /// Denies the component type with the given [`TypeId`], see [`SceneFilter::deny`].
+    #[must_use]
+    pub fn deny_by_id(self, type_id: TypeId) -> Self {
+        match self {
+            SceneFilter::AllowAll => SceneFilter::Deny([type_id].into_iter().collect()),
+            SceneFilter::Allow(mut types) => {
+                types.remove(&type_id);
+                SceneFilter::Allow(types)
+            }
+            SceneFilter::Deny(mut types) => {
+                types.insert(type_id);
+                SceneFilter::Deny(types)
+            }
+        }
+    }
+
+    /// Returns `true` if the component type `T` is allowed.
+    pub fn is_allowed<T: Component>(&self) -> bool {
+        self.is_allowed_by_id(TypeId::of::<T>())
+    }
+
+    /// Returns `true` if the component type with the given [`TypeId`] is allowed.
+    pub fn is_allowed_by_id(&self, type_id: TypeId) -> bool {
+        match self {
+            SceneFilter::AllowAll => true,
+            SceneFilter::Allow(types) => types.contains(&type_id),
+            SceneFilter::Deny(types) => !types.contains(&type_id),
+        }
+    }
+}
+
+/// A [`DynamicScene`] builder, extracting only some entities of a [`World`], and only the
+/// component types allowed by a [`SceneFilter`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::{Reflect, TypeRegistryArc};
+/// # use bevy_scene::DynamicSceneBuilder;
+/// # #[derive(Component, Reflect, Default)]
+/// # #[reflect(Component)]
+/// # struct Player;
+/// # #[derive(Component, Reflect, Default)]
+/// # #[reflect(Component)]
+/// # struct CachedPath;
+/// # let mut world = World::new();
+/// # world.insert_resource(TypeRegistryArc::default());
+/// # let player = world.spawn().insert_bundle((Player, CachedPath)).id();
+/// let mut builder = DynamicSceneBuilder::from_world(&world).deny::<CachedPath>();
+/// builder.extract_entity(player);
+/// let dynamic_scene = builder.build();
+/// ```
+pub struct DynamicSceneBuilder<'w> {
+    entities: BTreeMap<u32, DynamicEntity>,
+    filter: SceneFilter,
+    type_registry: TypeRegistryArc,
+    world: &'w World,
+}
+
+impl<'w> DynamicSceneBuilder<'w> {
+    /// Prepares a builder extracting entities from `world`, with the types registered in its
+    /// [`TypeRegistryArc`] resource.
+    pub fn from_world(world: &'w World) -> Self {
+        Self::from_world_with_type_registry(world, world.resource::<TypeRegistryArc>().clone())
+    }
+
+    /// Prepares a builder extracting entities from `world`, with the types registered in
+    /// `type_registry`.
+    pub fn from_world_with_type_registry(world: &'w World, type_registry: TypeRegistryArc) -> Self {
+        DynamicSceneBuilder {
+            entities: BTreeMap::default(),
+            filter: SceneFilter::default(),
+            type_registry,
+            world,
+        }
+    }
+
+    /// Sets the [`SceneFilter`] of the component types extracted from now on.
+    #[must_use]
+    pub fn with_filter(mut self, filter: SceneFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Allows the component type `T`, see [`SceneFilter::allow`].
+    #[must_use]
+    pub fn allow<T: Component>(mut self) -> Self {
+        self.filter = self.filter.allow::<T>();
+        self
+    }
+
+    /// Denies the component type `T`, see [`SceneFilter::deny`].
+    #[must_use]
+    pub fn deny<T: Component>(mut self) -> Self {
+        self.filter = self.filter.deny::<T>();
+        self
+    }
+
+    /// Extracts the allowed reflected components of `entity`. Extracting an entity again only
+    /// adds the components that were not extracted yet.
+    pub fn extract_entity(&mut self, entity: Entity) -> &mut Self {
+        self.extract_entities(std::iter::once(entity))
+    }
+
+    /// Extracts the allowed reflected components of `entities`, see
+    /// [`DynamicSceneBuilder::extract_entity`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_reflect::TypeRegistryArc;
+    /// # use bevy_scene::DynamicSceneBuilder;
+    /// # #[derive(Component)]
+    /// # struct Saved;
+    /// # let mut world = World::new();
+    /// # world.insert_resource(TypeRegistryArc::default());
+    /// let mut query = world.query_filtered::<Entity, With<Saved>>();
+    /// let mut builder = DynamicSceneBuilder::from_world(&world);
+    /// builder.extract_entities(query.iter(&world));
+    /// let dynamic_scene = builder.build();
+    /// ```
+    pub fn extract_entities(&mut self, entities: impl Iterator<Item = Entity>) -> &mut Self {
+        let type_registry = self.type_registry.read();
+
+        for entity in entities {
+            let entity_ref = match self.world.get_entity(entity) {
+                Some(entity_ref) => entity_ref,
+                None => continue,
+            };
+            let dynamic_entity =
+                self.entities
+                    .entry(entity.id())
+                    .or_insert_with(|| DynamicEntity {
+                        entity: entity.id(),
+                        components: Vec::new(),
+                        scenes: Vec::new(),
+                    });
+
+            for component_id in entity_ref.archetype().components() {
+                let type_id = match self
+                    .world
+                    .components()
+                    .get_info(component_id)
+                    .and_then(|info| info.type_id())
+                {
+                    Some(type_id) if self.filter.is_allowed_by_id(type_id) => type_id,
+                    _ => continue,
+                };
+                let component = type_registry
+                    .get(type_id)
+                    .and_then(|registration| registration.data::<ReflectComponent>())
+                    .and_then(|reflect_component| reflect_component.reflect(self.world, entity));
+                if let Some(component) = component {
+                    if !dynamic_entity
+                        .components
+                        .iter()
+                        .any(|extracted| extracted.type_name() == component.type_name())
+                    {
+                        dynamic_entity.components.push(component.clone_value());
+                    }
+                }
+            }
+        }
+
+        drop(type_registry);
+        self
+    }
+
+    /// Consumes the builder, returning the [`DynamicScene`] of the extracted entities.
+    pub fn build(self) -> DynamicScene {
+        DynamicScene {
+            entities: self.entities.into_values().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DynamicScene, DynamicSceneBuilder, SceneFilter};
+    use bevy_ecs::{entity::EntityMap, prelude::*, reflect::ReflectComponent};
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+    use std::any::type_name;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Position(f32);
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Velocity(f32);
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Saved;
+
+    fn test_world() -> World {
+        let mut world = World::new();
+        let type_registry = TypeRegistryArc::default();
+        {
+            let mut type_registry = type_registry.write();
+            type_registry.register::<Position>();
+            type_registry.register::<Velocity>();
+            type_registry.register::<Saved>();
+        }
+        world.insert_resource(type_registry);
+        world
+    }
+
+    #[test]
+    fn extract_filtered_entities() {
+        let mut world = test_world();
+        let saved = world
+            .spawn()
+            .insert_bundle((Position(1.0), Velocity(2.0), Saved))
+            .id();
+        world.spawn().insert_bundle((Position(3.0), Velocity(4.0)));
+
+        let scene = DynamicScene::from_query::<With<Saved>>(
+            &mut world,
+            SceneFilter::default().deny::<Velocity>(),
+        );
+        assert_eq!(scene.entities.len(), 1);
+        assert_eq!(scene.entities[0].entity, saved.id());
+        assert_eq!(scene.entities[0].components.len(), 2);
+        assert!(scene.entities[0]
+            .components
+            .iter()
+            .all(|component| component.type_name() != type_name::<Velocity>()));
+
+        // Extracting an entity twice doesn't duplicate its components
+        let mut builder = DynamicSceneBuilder::from_world(&world).allow::<Velocity>();
+        builder.extract_entity(saved).extract_entity(saved);
+        let scene = builder.build();
+        assert_eq!(scene.entities.len(), 1);
+        assert_eq!(scene.entities[0].components.len(), 1);
+        assert!(scene.entities[0].components[0].type_name() == type_name::<Velocity>());
+    }
+
+    #[test]
+    fn write_filtered_components() {
+        let mut world = test_world();
+        world
+            .spawn()
+            .insert_bundle((Position(1.0), Velocity(2.0), Saved));
+        let scene = DynamicScene::from_world(&world, world.resource());
+
+        let mut other_world = test_world();
+        scene
+            .write_to_world_with_filter(
+                &mut other_world,
+                &mut EntityMap::default(),
+                &SceneFilter::default().allow::<Position>(),
+            )
+            .unwrap();
+        let mut query = other_world.query::<(&Position, Option<&Velocity>, Option<&Saved>)>();
+        let (position, velocity, saved) = query.single(&other_world);
+        assert_eq!(position.0, 1.0);
+        assert!(velocity.is_none() && saved.is_none());
+    }
+
+    #[test]
+    fn scene_filter() {
+        let filter = SceneFilter::default();
+        assert!(filter.is_allowed::<Position>());
+
+        let filter = filter.allow::<Position>();
+        assert!(filter.is_allowed::<Position>());
+        assert!(!filter.is_allowed::<Velocity>());
+        let filter = filter.deny::<Position>();
+        assert!(!filter.is_allowed::<Position>());
+
+        let filter = SceneFilter::default().deny::<Position>();
+        assert!(!filter.is_allowed::<Position>());
+        assert!(filter.is_allowed::<Velocity>());
+        let filter = filter.allow::<Position>();
+        assert!(filter.is_allowed::<Position>());
+    }
+}
@@ -1,24 +1,40 @@
+mod binary;
 mod bundle;
 mod dynamic_scene;
+mod dynamic_scene_builder;
 mod scene;
 mod scene_loader;
 mod scene_spawner;
 pub mod serde;
 
+pub use binary::*;
 pub use bundle::*;
 pub use dynamic_scene::*;
+pub use dynamic_scene_builder::*;
 pub use scene::*;
 pub use scene_loader::*;
 pub use scene_spawner::*;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{DynamicScene, DynamicSceneBundle, Scene, SceneBundle, SceneSpawner};
+    pub use crate::{
+        DynamicScene, DynamicSceneBuilder, DynamicSceneBundle, Scene, SceneBundle, SceneSpawner,
+    };
 }
 
 use bevy_app::prelude::*;
 use bevy_asset::AddAsset;
-use bevy_ecs::{schedule::ExclusiveSystemDescriptorCoercion, system::IntoExclusiveSystem};
+use bevy_ecs::{
+    schedule::{ExclusiveSystemDescriptorCoercion, SystemLabel},
+    system::IntoExclusiveSystem,
+};
+
+/// Label for the systems of the scene plugin
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub enum SceneSystem {
+    /// Spawns the scenes queued in the [`SceneSpawner`]
+    SceneSpawner,
+}
 
 #[derive(Default)]
 pub struct ScenePlugin;
@@ -28,10 +44,14 @@ impl Plugin for ScenePlugin {
         app.add_asset::<DynamicScene>()
             .add_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_loader::<BinarySceneLoader>()
             .init_resource::<SceneSpawner>()
             .add_system_to_stage(
                 CoreStage::PreUpdate,
-                scene_spawner_system.exclusive_system().at_end(),
+                scene_spawner_system
+                    .exclusive_system()
+                    .at_end()
+                    .label(SceneSystem::SceneSpawner),
             )
             // Systems `*_bundle_spawner` must run before `scene_spawner_system`
             .add_system_to_stage(CoreStage::PreUpdate, scene_spawner);
@@ -1,6 +1,6 @@
-use crate::serde::SceneDeserializer;
+use crate::{deserialize_binary, serde::SceneDeserializer, DynamicScene};
 use anyhow::Result;
-use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset};
 use bevy_ecs::world::{FromWorld, World};
 use bevy_reflect::TypeRegistryArc;
 use bevy_utils::BoxedFuture;
@@ -32,7 +32,8 @@ impl AssetLoader for SceneLoader {
                 type_registry: &*self.type_registry.read(),
             };
             let scene = scene_deserializer.deserialize(&mut deserializer)?;
-            load_context.set_default_asset(LoadedAsset::new(scene));
+            let scene = load_nested_scenes(scene, load_context);
+            load_context.set_default_asset(scene);
             Ok(())
         })
     }
@@ -41,3 +42,59 @@ impl AssetLoader for SceneLoader {
         &["scn", "scn.ron"]
     }
 }
+
+/// Loads scenes serialized in the binary format of [`serialize_binary`](crate::serialize_binary),
+/// from `.bscn` files.
+#[derive(Debug)]
+pub struct BinarySceneLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BinarySceneLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<TypeRegistryArc>();
+        BinarySceneLoader {
+            type_registry: (*type_registry).clone(),
+        }
+    }
+}
+
+impl AssetLoader for BinarySceneLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let scene_deserializer = SceneDeserializer {
+                type_registry: &*self.type_registry.read(),
+            };
+            let scene = deserialize_binary(scene_deserializer, bytes)?;
+            let scene = load_nested_scenes(scene, load_context);
+            load_context.set_default_asset(scene);
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bscn"]
+    }
+}
+
+/// Sets the handles of the scenes nested in `scene`, loading them as its dependencies.
+fn load_nested_scenes(
+    mut scene: DynamicScene,
+    load_context: &LoadContext,
+) -> LoadedAsset<DynamicScene> {
+    let mut dependencies = Vec::new();
+    for instance in scene
+        .entities
+        .iter_mut()
+        .flat_map(|entity| entity.scenes.iter_mut())
+    {
+        let asset_path = AssetPath::from(instance.path.as_str()).to_owned();
+        instance.handle = load_context.get_handle(asset_path.clone());
+        dependencies.push(asset_path);
+    }
+    LoadedAsset::new(scene).with_dependencies(dependencies)
+}
@@ -1,5 +1,5 @@
 use crate::{DynamicScene, Scene};
-use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
 use bevy_ecs::{
     entity::{Entity, EntityMap},
     event::{Events, ManualEventReader},
@@ -7,15 +7,31 @@ use bevy_ecs::{
     system::Command,
     world::{Mut, World},
 };
-use bevy_hierarchy::{AddChild, Parent};
+use bevy_hierarchy::{AddChild, Children, Parent};
 use bevy_reflect::TypeRegistryArc;
 use bevy_utils::{tracing::error, HashMap};
 use thiserror::Error;
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 struct InstanceInfo {
     entity_map: EntityMap,
+    /// The instances of the scenes nested in this scene, by the scene entity hosting them and
+    /// their index in its [`scenes`](crate::DynamicEntity::scenes).
+    nested_instances: HashMap<(u32, usize), InstanceInfo>,
+}
+
+impl InstanceInfo {
+    /// All the entities of the instance, including the ones of its nested scenes.
+    fn entities(&self) -> Box<dyn Iterator<Item = Entity> + '_> {
+        Box::new(
+            self.entity_map.values().chain(
+                self.nested_instances
+                    .values()
+                    .flat_map(|nested_instance| nested_instance.entities()),
+            ),
+        )
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -50,6 +66,14 @@ pub enum SceneSpawnError {
     NonExistentScene { handle: Handle<DynamicScene> },
     #[error("scene does not exist")]
     NonExistentRealScene { handle: Handle<Scene> },
+    #[error("scene is nested in itself")]
+    RecursiveScene { handle: Handle<DynamicScene> },
+    #[error("invalid override of `{type_name}` on the nested scene entity {entity}: {message}")]
+    InvalidOverride {
+        entity: u32,
+        type_name: String,
+        message: String,
+    },
 }
 
 impl SceneSpawner {
@@ -107,7 +131,7 @@ impl SceneSpawner {
 
     pub fn despawn_instance_sync(&mut self, world: &mut World, instance_id: &InstanceId) {
         if let Some(instance) = self.spawned_instances.remove(instance_id) {
-            for entity in instance.entity_map.values() {
+            for entity in instance.entities() {
                 let _ = world.despawn(entity);
             }
         }
@@ -118,11 +142,10 @@ impl SceneSpawner {
         world: &mut World,
         scene_handle: &Handle<DynamicScene>,
     ) -> Result<(), SceneSpawnError> {
-        let mut entity_map = EntityMap::default();
-        Self::spawn_dynamic_internal(world, scene_handle, &mut entity_map)?;
+        let mut instance_info = InstanceInfo::default();
+        Self::spawn_dynamic_internal(world, scene_handle, &mut instance_info)?;
         let instance_id = InstanceId::new();
-        self.spawned_instances
-            .insert(instance_id, InstanceInfo { entity_map });
+        self.spawned_instances.insert(instance_id, instance_info);
         let spawned = self
             .spawned_dynamic_scenes
             .entry(scene_handle.clone())
@@ -134,19 +157,85 @@ impl SceneSpawner {
     fn spawn_dynamic_internal(
         world: &mut World,
         scene_handle: &Handle<DynamicScene>,
-        entity_map: &mut EntityMap,
+        instance_info: &mut InstanceInfo,
     ) -> Result<(), SceneSpawnError> {
         world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
-            let scene =
-                scenes
-                    .get(scene_handle)
-                    .ok_or_else(|| SceneSpawnError::NonExistentScene {
-                        handle: scene_handle.clone_weak(),
-                    })?;
-            scene.write_to_world(world, entity_map)
+            // Check that the nested scenes are loaded before writing anything to the world, so
+            // that the spawn can be retried later
+            Self::check_nested_scenes(&scenes, scene_handle, &mut Vec::new())?;
+            Self::write_nested_scenes(world, &scenes, scene_handle, instance_info)
         })
     }
 
+    fn check_nested_scenes(
+        scenes: &Assets<DynamicScene>,
+        scene_handle: &Handle<DynamicScene>,
+        ancestors: &mut Vec<HandleId>,
+    ) -> Result<(), SceneSpawnError> {
+        let scene = scenes
+            .get(scene_handle)
+            .ok_or_else(|| SceneSpawnError::NonExistentScene {
+                handle: scene_handle.clone_weak(),
+            })?;
+        if ancestors.contains(&scene_handle.id) {
+            return Err(SceneSpawnError::RecursiveScene {
+                handle: scene_handle.clone_weak(),
+            });
+        }
+        ancestors.push(scene_handle.id);
+        for nested_scene in scene.entities.iter().flat_map(|entity| &entity.scenes) {
+            Self::check_nested_scenes(scenes, &nested_scene.handle, ancestors)?;
+        }
+        ancestors.pop();
+        Ok(())
+    }
+
+    fn write_nested_scenes(
+        world: &mut World,
+        scenes: &Assets<DynamicScene>,
+        scene_handle: &Handle<DynamicScene>,
+        instance_info: &mut InstanceInfo,
+    ) -> Result<(), SceneSpawnError> {
+        let scene = scenes.get(scene_handle).unwrap();
+        scene.write_to_world(world, &mut instance_info.entity_map)?;
+
+        for scene_entity in &scene.entities {
+            let host = instance_info
+                .entity_map
+                .get(Entity::from_raw(scene_entity.entity))
+                .unwrap();
+            for (index, nested_scene) in scene_entity.scenes.iter().enumerate() {
+                let nested_instance = instance_info
+                    .nested_instances
+                    .entry((scene_entity.entity, index))
+                    .or_default();
+                Self::write_nested_scenes(world, scenes, &nested_scene.handle, nested_instance)?;
+                nested_scene.apply_overrides(world, &nested_instance.entity_map)?;
+
+                // Add the roots of the nested scene as children of the hosting entity. They can
+                // already have it as parent when the scene is reloaded, but the children of the
+                // host may have been overwritten
+                for entity in nested_instance.entity_map.values() {
+                    let is_child = match world.get::<Parent>(entity) {
+                        Some(parent) if parent.0 == host => world
+                            .get::<Children>(host)
+                            .map_or(false, |children| children.contains(&entity)),
+                        Some(_) => true,
+                        None => false,
+                    };
+                    if !is_child {
+                        AddChild {
+                            parent: host,
+                            child: entity,
+                        }
+                        .write(world);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn spawn_sync(
         &mut self,
         world: &mut World,
@@ -161,9 +250,7 @@ impl SceneSpawner {
         scene_handle: Handle<Scene>,
         instance_id: InstanceId,
     ) -> Result<InstanceId, SceneSpawnError> {
-        let mut instance_info = InstanceInfo {
-            entity_map: EntityMap::default(),
-        };
+        let mut instance_info = InstanceInfo::default();
         let type_registry = world.resource::<TypeRegistryArc>().clone();
         let type_registry = type_registry.read();
         world.resource_scope(|world, scenes: Mut<Assets<Scene>>| {
@@ -229,11 +316,7 @@ impl SceneSpawner {
             if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
                 for instance_id in spawned_instances.iter() {
                     if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        Self::spawn_dynamic_internal(
-                            world,
-                            scene_handle,
-                            &mut instance_info.entity_map,
-                        )?;
+                        Self::spawn_dynamic_internal(world, scene_handle, instance_info)?;
                     }
                 }
             }
@@ -262,12 +345,11 @@ impl SceneSpawner {
         let scenes_to_spawn = std::mem::take(&mut self.dynamic_scenes_to_spawn);
 
         for (scene_handle, instance_id) in scenes_to_spawn {
-            let mut entity_map = EntityMap::default();
+            let mut instance_info = InstanceInfo::default();
 
-            match Self::spawn_dynamic_internal(world, &scene_handle, &mut entity_map) {
+            match Self::spawn_dynamic_internal(world, &scene_handle, &mut instance_info) {
                 Ok(_) => {
-                    self.spawned_instances
-                        .insert(instance_id, InstanceInfo { entity_map });
+                    self.spawned_instances.insert(instance_id, instance_info);
                     let spawned = self
                         .spawned_dynamic_scenes
                         .entry(scene_handle.clone())
@@ -339,7 +421,7 @@ impl SceneSpawner {
     ) -> Option<impl Iterator<Item = Entity> + '_> {
         self.spawned_instances
             .get(&instance_id)
-            .map(|instance| instance.entity_map.values())
+            .map(|instance| instance.entities())
     }
 }
 
@@ -371,3 +453,123 @@ pub fn scene_spawner_system(world: &mut World) {
         scene_spawner.set_scene_instance_parent_sync(world);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde::SceneDeserializer;
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_ecs::prelude::*;
+    use bevy_reflect::Reflect;
+    use bevy_tasks::IoTaskPool;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Health {
+        value: f32,
+    }
+
+    const BASE_SCENE: &str = r#"[
+  (
+    entity: 0,
+    components: [
+      {
+        "type": "bevy_scene::scene_spawner::tests::Health",
+        "struct": {
+          "value": {
+            "type": "f32",
+            "value": 100.0,
+          },
+        },
+      },
+    ],
+  ),
+  (
+    entity: 1,
+    components: [],
+  ),
+]"#;
+
+    const VARIANT_SCENE: &str = r#"[
+  (
+    entity: 0,
+    components: [],
+    scenes: [
+      (
+        scene: "enemy.scn",
+        overrides: [
+          (
+            entity: 0,
+            component: "bevy_scene::scene_spawner::tests::Health",
+            path: "value",
+            value: {
+              "type": "f32",
+              "value": 250.0,
+            },
+          ),
+        ],
+      ),
+    ],
+  ),
+]"#;
+
+    fn load_scene(app: &App, ron: &str) -> DynamicScene {
+        let mut deserializer = ron::de::Deserializer::from_str(ron).unwrap();
+        SceneDeserializer {
+            type_registry: &app.world.resource::<TypeRegistryArc>().read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap()
+    }
+
+    #[test]
+    fn spawn_nested_scene_with_overrides() {
+        IoTaskPool::init(Default::default);
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin)
+            .add_asset::<DynamicScene>()
+            .init_resource::<SceneSpawner>()
+            .register_type::<Health>();
+
+        let base = load_scene(&app, BASE_SCENE);
+        let mut variant = load_scene(&app, VARIANT_SCENE);
+        assert_eq!(variant.entities[0].scenes[0].path, "enemy.scn");
+        assert_eq!(
+            variant.serialize_ron(app.world.resource()).unwrap(),
+            VARIANT_SCENE
+        );
+
+        let mut scenes = app.world.resource_mut::<Assets<DynamicScene>>();
+        variant.entities[0].scenes[0].handle = scenes.add(base);
+        let variant = scenes.add(variant);
+        app.world
+            .resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
+                scene_spawner.spawn_dynamic_sync(world, &variant)
+            })
+            .unwrap();
+
+        let mut health = app.world.query::<(&Health, &Parent)>();
+        let (health, parent) = health.single(&app.world);
+        assert_eq!(health.value, 250.0);
+        let children = app.world.get::<Children>(parent.0).unwrap();
+        assert_eq!(children.len(), 2);
+        assert_eq!(app.world.entities().len(), 3);
+
+        // Overriding a field with a value of another type fails
+        let mut scenes = app.world.resource_mut::<Assets<DynamicScene>>();
+        let scene_override =
+            &mut scenes.get_mut(&variant).unwrap().entities[0].scenes[0].overrides[0];
+        scene_override.value = Box::new(250.0f64);
+        let result = app
+            .world
+            .resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
+                scene_spawner.spawn_dynamic_sync(world, &variant)
+            });
+        assert!(matches!(
+            result,
+            Err(SceneSpawnError::InvalidOverride { entity: 0, .. })
+        ));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{DynamicEntity, DynamicScene};
+use crate::{
+    serialize_binary, BinarySceneError, DynamicEntity, DynamicScene, DynamicSceneInstance,
+    SceneOverride,
+};
 use anyhow::Result;
 use bevy_reflect::{
     serde::{ReflectDeserializer, ReflectSerializer},
@@ -19,6 +22,12 @@ impl<'a> SceneSerializer<'a> {
     pub fn new(scene: &'a DynamicScene, registry: &'a TypeRegistryArc) -> Self {
         SceneSerializer { scene, registry }
     }
+
+    /// Serializes the scene into the compact binary format of [`serialize_binary`], loaded from
+    /// `.bscn` files.
+    pub fn to_binary(&self) -> Result<Vec<u8>, BinarySceneError> {
+        serialize_binary(self)
+    }
 }
 
 impl<'a> Serialize for SceneSerializer<'a> {
@@ -47,7 +56,8 @@ impl<'a> Serialize for EntitySerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_struct(ENTITY_STRUCT, 2)?;
+        let has_scenes = !self.entity.scenes.is_empty();
+        let mut state = serializer.serialize_struct(ENTITY_STRUCT, 2 + has_scenes as usize)?;
         state.serialize_field(ENTITY_FIELD_ENTITY, &self.entity.entity)?;
         state.serialize_field(
             ENTITY_FIELD_COMPONENTS,
@@ -56,6 +66,102 @@ impl<'a> Serialize for EntitySerializer<'a> {
                 registry: self.registry,
             },
         )?;
+        if has_scenes {
+            state.serialize_field(
+                ENTITY_FIELD_SCENES,
+                &SceneInstancesSerializer {
+                    scenes: &self.entity.scenes,
+                    registry: self.registry,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+pub struct SceneInstancesSerializer<'a> {
+    pub scenes: &'a [DynamicSceneInstance],
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for SceneInstancesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.scenes.len()))?;
+        for scene in self.scenes {
+            state.serialize_element(&SceneInstanceSerializer {
+                scene,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+pub struct SceneInstanceSerializer<'a> {
+    pub scene: &'a DynamicSceneInstance,
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for SceneInstanceSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(SCENE_INSTANCE_STRUCT, 2)?;
+        state.serialize_field(SCENE_INSTANCE_FIELD_SCENE, &self.scene.path)?;
+        state.serialize_field(
+            SCENE_INSTANCE_FIELD_OVERRIDES,
+            &OverridesSerializer {
+                overrides: &self.scene.overrides,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct OverridesSerializer<'a> {
+    pub overrides: &'a [SceneOverride],
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for OverridesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.overrides.len()))?;
+        for scene_override in self.overrides {
+            state.serialize_element(&OverrideSerializer {
+                scene_override,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+pub struct OverrideSerializer<'a> {
+    pub scene_override: &'a SceneOverride,
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for OverrideSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(OVERRIDE_STRUCT, 4)?;
+        state.serialize_field(OVERRIDE_FIELD_ENTITY, &self.scene_override.entity)?;
+        state.serialize_field(OVERRIDE_FIELD_COMPONENT, &self.scene_override.component)?;
+        state.serialize_field(OVERRIDE_FIELD_PATH, &self.scene_override.path)?;
+        state.serialize_field(
+            OVERRIDE_FIELD_VALUE,
+            &ReflectSerializer::new(&*self.scene_override.value, &*self.registry.read()),
+        )?;
         state.end()
     }
 }
@@ -139,7 +245,11 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
     {
         deserializer.deserialize_struct(
             ENTITY_STRUCT,
-            &[ENTITY_FIELD_ENTITY, ENTITY_FIELD_COMPONENTS],
+            &[
+                ENTITY_FIELD_ENTITY,
+                ENTITY_FIELD_COMPONENTS,
+                ENTITY_FIELD_SCENES,
+            ],
             SceneEntityVisitor {
                 registry: self.type_registry,
             },
@@ -152,11 +262,13 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
 enum EntityField {
     Entity,
     Components,
+    Scenes,
 }
 
 pub const ENTITY_STRUCT: &str = "Entity";
 pub const ENTITY_FIELD_ENTITY: &str = "entity";
 pub const ENTITY_FIELD_COMPONENTS: &str = "components";
+pub const ENTITY_FIELD_SCENES: &str = "scenes";
 
 struct SceneEntityVisitor<'a> {
     pub registry: &'a TypeRegistry,
@@ -175,6 +287,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
     {
         let mut id = None;
         let mut components = None;
+        let mut scenes = None;
         while let Some(key) = map.next_key()? {
             match key {
                 EntityField::Entity => {
@@ -192,6 +305,15 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
                         registry: self.registry,
                     })?);
                 }
+                EntityField::Scenes => {
+                    if scenes.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_FIELD_SCENES));
+                    }
+
+                    scenes = Some(map.next_value_seed(SceneInstanceVecDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
             }
         }
 
@@ -205,6 +327,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
         Ok(DynamicEntity {
             entity: *entity,
             components,
+            scenes: scenes.unwrap_or_default(),
         })
     }
 }
@@ -249,3 +372,264 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
         Ok(dynamic_properties)
     }
 }
+
+pub struct SceneInstanceVecDeserializer<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneInstanceVecDeserializer<'a> {
+    type Value = Vec<DynamicSceneInstance>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneInstanceSeqVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct SceneInstanceSeqVisitor<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneInstanceSeqVisitor<'a> {
+    type Value = Vec<DynamicSceneInstance>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of scene instances")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut scenes = Vec::new();
+        while let Some(scene) = seq.next_element_seed(SceneInstanceDeserializer {
+            registry: self.registry,
+        })? {
+            scenes.push(scene);
+        }
+
+        Ok(scenes)
+    }
+}
+
+pub struct SceneInstanceDeserializer<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneInstanceDeserializer<'a> {
+    type Value = DynamicSceneInstance;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            SCENE_INSTANCE_STRUCT,
+            &[SCENE_INSTANCE_FIELD_SCENE, SCENE_INSTANCE_FIELD_OVERRIDES],
+            SceneInstanceVisitor {
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneInstanceField {
+    Scene,
+    Overrides,
+}
+
+pub const SCENE_INSTANCE_STRUCT: &str = "SceneInstance";
+pub const SCENE_INSTANCE_FIELD_SCENE: &str = "scene";
+pub const SCENE_INSTANCE_FIELD_OVERRIDES: &str = "overrides";
+
+struct SceneInstanceVisitor<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneInstanceVisitor<'a> {
+    type Value = DynamicSceneInstance;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("scene instance")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut path = None;
+        let mut overrides = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SceneInstanceField::Scene => {
+                    if path.is_some() {
+                        return Err(Error::duplicate_field(SCENE_INSTANCE_FIELD_SCENE));
+                    }
+                    path = Some(map.next_value::<String>()?);
+                }
+                SceneInstanceField::Overrides => {
+                    if overrides.is_some() {
+                        return Err(Error::duplicate_field(SCENE_INSTANCE_FIELD_OVERRIDES));
+                    }
+                    overrides = Some(map.next_value_seed(OverrideVecDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+            }
+        }
+
+        let path = path.ok_or_else(|| Error::missing_field(SCENE_INSTANCE_FIELD_SCENE))?;
+        Ok(DynamicSceneInstance {
+            path,
+            handle: Default::default(),
+            overrides: overrides.unwrap_or_default(),
+        })
+    }
+}
+
+pub struct OverrideVecDeserializer<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for OverrideVecDeserializer<'a> {
+    type Value = Vec<SceneOverride>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(OverrideSeqVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct OverrideSeqVisitor<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for OverrideSeqVisitor<'a> {
+    type Value = Vec<SceneOverride>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of overrides")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut overrides = Vec::new();
+        while let Some(scene_override) = seq.next_element_seed(OverrideDeserializer {
+            registry: self.registry,
+        })? {
+            overrides.push(scene_override);
+        }
+
+        Ok(overrides)
+    }
+}
+
+pub struct OverrideDeserializer<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for OverrideDeserializer<'a> {
+    type Value = SceneOverride;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            OVERRIDE_STRUCT,
+            &[
+                OVERRIDE_FIELD_ENTITY,
+                OVERRIDE_FIELD_COMPONENT,
+                OVERRIDE_FIELD_PATH,
+                OVERRIDE_FIELD_VALUE,
+            ],
+            OverrideVisitor {
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum OverrideField {
+    Entity,
+    Component,
+    Path,
+    Value,
+}
+
+pub const OVERRIDE_STRUCT: &str = "Override";
+pub const OVERRIDE_FIELD_ENTITY: &str = "entity";
+pub const OVERRIDE_FIELD_COMPONENT: &str = "component";
+pub const OVERRIDE_FIELD_PATH: &str = "path";
+pub const OVERRIDE_FIELD_VALUE: &str = "value";
+
+struct OverrideVisitor<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for OverrideVisitor<'a> {
+    type Value = SceneOverride;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("override")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entity = None;
+        let mut component = None;
+        let mut path = None;
+        let mut value = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                OverrideField::Entity => {
+                    if entity.is_some() {
+                        return Err(Error::duplicate_field(OVERRIDE_FIELD_ENTITY));
+                    }
+                    entity = Some(map.next_value::<u32>()?);
+                }
+                OverrideField::Component => {
+                    if component.is_some() {
+                        return Err(Error::duplicate_field(OVERRIDE_FIELD_COMPONENT));
+                    }
+                    component = Some(map.next_value::<String>()?);
+                }
+                OverrideField::Path => {
+                    if path.is_some() {
+                        return Err(Error::duplicate_field(OVERRIDE_FIELD_PATH));
+                    }
+                    path = Some(map.next_value::<String>()?);
+                }
+                OverrideField::Value => {
+                    if value.is_some() {
+                        return Err(Error::duplicate_field(OVERRIDE_FIELD_VALUE));
+                    }
+                    value = Some(map.next_value_seed(ReflectDeserializer::new(self.registry))?);
+                }
+            }
+        }
+
+        Ok(SceneOverride {
+            entity: entity.ok_or_else(|| Error::missing_field(OVERRIDE_FIELD_ENTITY))?,
+            component: component.ok_or_else(|| Error::missing_field(OVERRIDE_FIELD_COMPONENT))?,
+            path: path.unwrap_or_default(),
+            value: value.ok_or_else(|| Error::missing_field(OVERRIDE_FIELD_VALUE))?,
+        })
+    }
+}
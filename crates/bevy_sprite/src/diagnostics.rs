@@ -0,0 +1,105 @@
+use crate::DrawSprite;
+use bevy_app::{App, Plugin};
+use bevy_core_pipeline::core_2d::Transparent2d;
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    render_phase::{batch_phase_system, DrawFunctions, RenderPhase},
+    RenderApp, RenderStage,
+};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Adds "sprite count" and "sprite batch count" diagnostics to an App, the number of sprites drawn
+/// during the last frame and the number of draw calls they needed, summed over all the views.
+///
+/// The glyphs of 2d text are drawn as sprites, and are counted too.
+#[derive(Default)]
+pub struct SpriteDiagnosticsPlugin;
+
+impl Plugin for SpriteDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let stats = SpriteRenderStats::default();
+        app.insert_resource(stats.clone())
+            .add_startup_system(Self::setup_system)
+            .add_system(Self::diagnostic_system);
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.insert_resource(stats).add_system_to_stage(
+                RenderStage::PhaseSort,
+                count_sprite_batches.after(batch_phase_system::<Transparent2d>),
+            );
+        }
+    }
+}
+
+impl SpriteDiagnosticsPlugin {
+    pub const SPRITE_COUNT: DiagnosticId =
+        DiagnosticId::from_u128(29966715351194491352667989386954665688);
+    pub const SPRITE_BATCH_COUNT: DiagnosticId =
+        DiagnosticId::from_u128(238770283570713917080296756003475819909);
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(Self::SPRITE_COUNT, "sprite_count", 20));
+        diagnostics.add(Diagnostic::new(
+            Self::SPRITE_BATCH_COUNT,
+            "sprite_batch_count",
+            20,
+        ));
+    }
+
+    pub fn diagnostic_system(mut diagnostics: ResMut<Diagnostics>, stats: Res<SpriteRenderStats>) {
+        diagnostics.add_measurement(Self::SPRITE_COUNT, || stats.sprites() as f64);
+        diagnostics.add_measurement(Self::SPRITE_BATCH_COUNT, || stats.batches() as f64);
+    }
+}
+
+#[derive(Default)]
+struct SpriteRenderStatsInner {
+    sprites: AtomicUsize,
+    batches: AtomicUsize,
+}
+
+/// The number of sprites and sprite batches of the last rendered frame, shared by the main world
+/// and the render world
+#[derive(Default, Clone)]
+pub struct SpriteRenderStats(Arc<SpriteRenderStatsInner>);
+
+impl SpriteRenderStats {
+    /// The number of sprites drawn during the last frame
+    pub fn sprites(&self) -> usize {
+        self.0.sprites.load(Ordering::Relaxed)
+    }
+
+    /// The number of batches the sprites of the last frame were drawn in, one for each draw call
+    pub fn batches(&self) -> usize {
+        self.0.batches.load(Ordering::Relaxed)
+    }
+}
+
+/// System counting the sprites and the sprite batches of the [`Transparent2d`] phases, once their
+/// items are batched
+pub fn count_sprite_batches(
+    stats: Res<SpriteRenderStats>,
+    draw_functions: Res<DrawFunctions<Transparent2d>>,
+    phases: Query<&RenderPhase<Transparent2d>>,
+) {
+    let draw_sprite = match draw_functions.read().get_id::<DrawSprite>() {
+        Some(draw_sprite) => draw_sprite,
+        None => return,
+    };
+    let (mut sprites, mut batches) = (0, 0);
+    for phase in phases.iter() {
+        for item in &phase.items {
+            if item.draw_function != draw_sprite {
+                continue;
+            }
+            batches += 1;
+            sprites += item.batch_range.as_ref().map_or(0, |range| range.len());
+        }
+    }
+    stats.0.sprites.store(sprites, Ordering::Relaxed);
+    stats.0.batches.store(batches, Ordering::Relaxed);
+}
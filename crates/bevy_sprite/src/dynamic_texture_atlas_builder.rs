@@ -2,7 +2,7 @@ use crate::{Rect, TextureAtlas};
 use bevy_asset::Assets;
 use bevy_math::Vec2;
 use bevy_render::texture::{Image, TextureFormatPixelInfo};
-use guillotiere::{size2, Allocation, AtlasAllocator};
+use guillotiere::{size2, AllocId, Allocation, AtlasAllocator};
 
 pub struct DynamicTextureAtlasBuilder {
     pub atlas_allocator: AtlasAllocator,
@@ -23,6 +23,19 @@ impl DynamicTextureAtlasBuilder {
         textures: &mut Assets<Image>,
         texture: &Image,
     ) -> Option<usize> {
+        self.allocate_texture(texture_atlas, textures, texture)
+            .map(|(index, _)| index)
+    }
+
+    /// Adds a texture like [`add_texture`](Self::add_texture), and also returns its allocation in
+    /// the `atlas_allocator`, to free its space with
+    /// [`AtlasAllocator::deallocate`] once it isn't used anymore.
+    pub fn allocate_texture(
+        &mut self,
+        texture_atlas: &mut TextureAtlas,
+        textures: &mut Assets<Image>,
+        texture: &Image,
+    ) -> Option<(usize, AllocId)> {
         let allocation = self.atlas_allocator.allocate(size2(
             texture.texture_descriptor.size.width as i32 + self.padding,
             texture.texture_descriptor.size.height as i32 + self.padding,
@@ -33,7 +46,7 @@ impl DynamicTextureAtlasBuilder {
             let mut rect: Rect = allocation.rectangle.into();
             rect.max.x -= self.padding as f32;
             rect.max.y -= self.padding as f32;
-            Some(texture_atlas.add_texture(rect))
+            Some((texture_atlas.add_texture(rect), allocation.id))
         } else {
             None
         }
@@ -1,11 +1,14 @@
 mod bundle;
+mod diagnostics;
 mod dynamic_texture_atlas_builder;
 mod mesh2d;
 mod rect;
 mod render;
+mod runtime_texture_atlas;
 mod sprite;
 mod texture_atlas;
 mod texture_atlas_builder;
+mod tilemap;
 
 pub mod collide_aabb;
 
@@ -13,31 +16,42 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         bundle::{SpriteBundle, SpriteSheetBundle},
+        runtime_texture_atlas::{RuntimeAtlasSprite, RuntimeTextureAtlases},
         sprite::Sprite,
         texture_atlas::{TextureAtlas, TextureAtlasSprite},
+        tilemap::{Tile, TileRotation, Tilemap, TilemapBundle},
         ColorMaterial, ColorMesh2dBundle, TextureAtlasBuilder,
     };
 }
 
 pub use bundle::*;
+pub use diagnostics::*;
 pub use dynamic_texture_atlas_builder::*;
 pub use mesh2d::*;
 pub use rect::*;
 pub use render::*;
+pub use runtime_texture_atlas::*;
 pub use sprite::*;
 pub use texture_atlas::*;
 pub use texture_atlas_builder::*;
+pub use tilemap::*;
 
 use bevy_app::prelude::*;
-use bevy_asset::{AddAsset, Assets, HandleUntyped};
+use bevy_asset::{AddAsset, AssetEvent, Assets, Handle, HandleUntyped};
 use bevy_core_pipeline::core_2d::Transparent2d;
-use bevy_ecs::schedule::{ParallelSystemDescriptorCoercion, SystemLabel};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec2;
 use bevy_reflect::TypeUuid;
 use bevy_render::{
+    mesh::Mesh,
+    primitives::Aabb,
     render_phase::AddRenderCommand,
     render_resource::{Shader, SpecializedRenderPipelines},
+    texture::Image,
+    view::{NoFrustumCulling, VisibilitySystems},
     RenderApp, RenderStage,
 };
+use bevy_utils::HashSet;
 
 #[derive(Default)]
 pub struct SpritePlugin;
@@ -58,8 +72,19 @@ impl Plugin for SpritePlugin {
         app.add_asset::<TextureAtlas>()
             .register_type::<Sprite>()
             .register_type::<Mesh2dHandle>()
+            .register_type::<RuntimeAtlasSprite>()
+            .init_resource::<RuntimeTextureAtlases>()
             .add_plugin(Mesh2dRenderPlugin)
-            .add_plugin(ColorMaterialPlugin);
+            .add_plugin(ColorMaterialPlugin)
+            .add_plugin(TilemapPlugin)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                pack_runtime_atlas_sprites.before(VisibilitySystems::CalculateBounds),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                calculate_bounds_2d.label(VisibilitySystems::CalculateBounds),
+            );
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
@@ -79,3 +104,287 @@ impl Plugin for SpritePlugin {
         };
     }
 }
+
+/// System computing the [`Aabb`] of the sprites, texture atlas sprites and 2d meshes which don't
+/// have one yet or which changed, for them to be frustum culled by
+/// [`check_visibility`](bevy_render::view::check_visibility).
+///
+/// The [`Aabb`]s are also updated when the meshes, images or texture atlases they are computed
+/// from are modified. A sprite's [`Aabb`] is removed while its image is loading, it isn't culled
+/// until it's loaded.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn calculate_bounds_2d(
+    mut commands: Commands,
+    meshes: Res<Assets<Mesh>>,
+    images: Res<Assets<Image>>,
+    atlases: Res<Assets<TextureAtlas>>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut atlas_events: EventReader<AssetEvent<TextureAtlas>>,
+    mut changed: ParamSet<(
+        Query<
+            (Entity, &Mesh2dHandle, Option<&mut Aabb>),
+            (
+                Or<(Without<Aabb>, Changed<Mesh2dHandle>)>,
+                Without<NoFrustumCulling>,
+            ),
+        >,
+        Query<
+            (Entity, &Sprite, &Handle<Image>, Option<&mut Aabb>),
+            (
+                Or<(Without<Aabb>, Changed<Sprite>, Changed<Handle<Image>>)>,
+                Without<NoFrustumCulling>,
+            ),
+        >,
+        Query<
+            (
+                Entity,
+                &TextureAtlasSprite,
+                &Handle<TextureAtlas>,
+                Option<&mut Aabb>,
+            ),
+            (
+                Or<(
+                    Without<Aabb>,
+                    Changed<TextureAtlasSprite>,
+                    Changed<Handle<TextureAtlas>>,
+                )>,
+                Without<NoFrustumCulling>,
+            ),
+        >,
+    )>,
+    all_meshes: Query<(Entity, &Mesh2dHandle), Without<NoFrustumCulling>>,
+    all_sprites: Query<(Entity, &Sprite, &Handle<Image>), Without<NoFrustumCulling>>,
+    all_atlas_sprites: Query<
+        (Entity, &TextureAtlasSprite, &Handle<TextureAtlas>),
+        Without<NoFrustumCulling>,
+    >,
+) {
+    let mesh_bounds = |handle: &Mesh2dHandle| meshes.get(&handle.0).and_then(Mesh::compute_aabb);
+    let sprite_bounds = |sprite: &Sprite, handle: &Handle<Image>| {
+        let size = sprite
+            .custom_size
+            .or_else(|| images.get(handle).map(Image::size));
+        size.map(|size| sprite_aabb(size, sprite.anchor.as_vec()))
+    };
+    let atlas_sprite_bounds = |sprite: &TextureAtlasSprite, handle: &Handle<TextureAtlas>| {
+        let size = sprite.custom_size.or_else(|| {
+            atlases
+                .get(handle)
+                .and_then(|atlas| atlas.textures.get(sprite.index))
+                .map(Rect::size)
+        });
+        size.map(|size| sprite_aabb(size, sprite.anchor.as_vec()))
+    };
+
+    let mut set_aabb =
+        |entity: Entity, aabb: Option<Mut<Aabb>>, bounds: Option<Aabb>| match (aabb, bounds) {
+            (Some(mut aabb), Some(bounds)) => *aabb = bounds,
+            (None, Some(bounds)) => {
+                commands.entity(entity).insert(bounds);
+            }
+            (Some(_), None) => {
+                commands.entity(entity).remove::<Aabb>();
+            }
+            (None, None) => {}
+        };
+    for (entity, mesh_handle, aabb) in changed.p0().iter_mut() {
+        set_aabb(entity, aabb, mesh_bounds(mesh_handle));
+    }
+    for (entity, sprite, image_handle, aabb) in changed.p1().iter_mut() {
+        set_aabb(entity, aabb, sprite_bounds(sprite, image_handle));
+    }
+    for (entity, sprite, atlas_handle, aabb) in changed.p2().iter_mut() {
+        set_aabb(entity, aabb, atlas_sprite_bounds(sprite, atlas_handle));
+    }
+
+    // The entities using modified assets are only looked for when there are any
+    let mut update_aabb = |entity: Entity, bounds: Option<Aabb>| match bounds {
+        Some(bounds) => {
+            commands.entity(entity).insert(bounds);
+        }
+        None => {
+            commands.entity(entity).remove::<Aabb>();
+        }
+    };
+    let modified_meshes = modified_assets(&mut mesh_events);
+    if !modified_meshes.is_empty() {
+        for (entity, mesh_handle) in all_meshes.iter() {
+            if modified_meshes.contains(&mesh_handle.0) {
+                update_aabb(entity, mesh_bounds(mesh_handle));
+            }
+        }
+    }
+    let modified_images = modified_assets(&mut image_events);
+    if !modified_images.is_empty() {
+        for (entity, sprite, image_handle) in all_sprites.iter() {
+            if sprite.custom_size.is_none() && modified_images.contains(image_handle) {
+                update_aabb(entity, sprite_bounds(sprite, image_handle));
+            }
+        }
+    }
+    let modified_atlases = modified_assets(&mut atlas_events);
+    if !modified_atlases.is_empty() {
+        for (entity, sprite, atlas_handle) in all_atlas_sprites.iter() {
+            if sprite.custom_size.is_none() && modified_atlases.contains(atlas_handle) {
+                update_aabb(entity, atlas_sprite_bounds(sprite, atlas_handle));
+            }
+        }
+    }
+}
+
+/// Collects the assets that were modified or removed since the last time `events` were read.
+fn modified_assets<T: bevy_asset::Asset>(
+    events: &mut EventReader<AssetEvent<T>>,
+) -> HashSet<Handle<T>> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { .. } => None,
+            AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
+                Some(handle.clone_weak())
+            }
+        })
+        .collect()
+}
+
+/// The bounds of a sprite of `size` around its `anchor`, see [`Anchor::as_vec`]
+fn sprite_aabb(size: Vec2, anchor: Vec2) -> Aabb {
+    let min = (Vec2::splat(-0.5) - anchor) * size;
+    let max = (Vec2::splat(0.5) - anchor) * size;
+    Aabb::from_min_max(min.extend(0.0), max.extend(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::AssetPlugin;
+    use bevy_math::Vec3;
+    use bevy_render::{
+        mesh::shape,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    };
+    use bevy_tasks::IoTaskPool;
+
+    fn image(width: u32, height: u32) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 0],
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+
+    fn bounds_app() -> App {
+        IoTaskPool::init(Default::default);
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin)
+            .add_asset::<Image>()
+            .add_asset::<Mesh>()
+            .add_asset::<TextureAtlas>()
+            .add_system_to_stage(CoreStage::PostUpdate, calculate_bounds_2d);
+        app
+    }
+
+    fn aabb_size(app: &App, entity: Entity) -> Option<Vec3> {
+        app.world
+            .get::<Aabb>(entity)
+            .map(|aabb| Vec3::from(aabb.half_extents) * 2.0)
+    }
+
+    #[test]
+    fn sprite_aabb_is_around_the_anchor() {
+        let size = Vec2::new(20.0, 10.0);
+        let aabb = sprite_aabb(size, Anchor::Center.as_vec());
+        assert_eq!(aabb.min(), Vec3::new(-10.0, -5.0, 0.0).into());
+        assert_eq!(aabb.max(), Vec3::new(10.0, 5.0, 0.0).into());
+
+        let aabb = sprite_aabb(size, Anchor::BottomLeft.as_vec());
+        assert_eq!(aabb.min(), Vec3::ZERO.into());
+        assert_eq!(aabb.max(), Vec3::new(20.0, 10.0, 0.0).into());
+
+        let aabb = sprite_aabb(size, Anchor::Custom(Vec2::new(0.25, -0.5)).as_vec());
+        assert_eq!(aabb.min(), Vec3::new(-15.0, 0.0, 0.0).into());
+        assert_eq!(aabb.max(), Vec3::new(5.0, 10.0, 0.0).into());
+    }
+
+    #[test]
+    fn sprite_aabb_follows_its_image() {
+        let mut app = bounds_app();
+        let handle = app.world.resource_mut::<Assets<Image>>().add(image(4, 2));
+        let sprite = app
+            .world
+            .spawn()
+            .insert_bundle((Sprite::default(), handle.clone()))
+            .id();
+        app.update();
+        assert_eq!(aabb_size(&app, sprite), Some(Vec3::new(4.0, 2.0, 0.0)));
+
+        // The image is modified, e.g. when it is reloaded
+        *app.world
+            .resource_mut::<Assets<Image>>()
+            .get_mut(&handle)
+            .unwrap() = image(8, 6);
+        app.update();
+        app.update();
+        assert_eq!(aabb_size(&app, sprite), Some(Vec3::new(8.0, 6.0, 0.0)));
+
+        // Sprites with a custom size ignore their image
+        app.world.get_mut::<Sprite>(sprite).unwrap().custom_size = Some(Vec2::ONE);
+        app.update();
+        assert_eq!(aabb_size(&app, sprite), Some(Vec3::new(1.0, 1.0, 0.0)));
+
+        app.world.resource_mut::<Assets<Image>>().remove(&handle);
+        app.update();
+        app.update();
+        assert_eq!(aabb_size(&app, sprite), Some(Vec3::new(1.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn mesh_aabb_follows_its_mesh() {
+        let mut app = bounds_app();
+        let mut meshes = app.world.resource_mut::<Assets<Mesh>>();
+        let small = meshes.add(shape::Quad::new(Vec2::new(2.0, 4.0)).into());
+        let large = meshes.add(shape::Quad::new(Vec2::new(10.0, 20.0)).into());
+        let entity = app.world.spawn().insert(Mesh2dHandle(small.clone())).id();
+        app.update();
+        assert_eq!(aabb_size(&app, entity), Some(Vec3::new(2.0, 4.0, 0.0)));
+
+        app.world.get_mut::<Mesh2dHandle>(entity).unwrap().0 = large;
+        app.update();
+        assert_eq!(aabb_size(&app, entity), Some(Vec3::new(10.0, 20.0, 0.0)));
+
+        app.world.get_mut::<Mesh2dHandle>(entity).unwrap().0 = small.clone();
+        app.update();
+        *app.world
+            .resource_mut::<Assets<Mesh>>()
+            .get_mut(&small)
+            .unwrap() = shape::Quad::new(Vec2::new(6.0, 6.0)).into();
+        app.update();
+        app.update();
+        assert_eq!(aabb_size(&app, entity), Some(Vec3::new(6.0, 6.0, 0.0)));
+    }
+
+    #[test]
+    fn sprites_are_not_culled_while_loading() {
+        let mut app = bounds_app();
+        let handle = Handle::<Image>::weak(bevy_asset::HandleId::random::<Image>());
+        let sprite = app
+            .world
+            .spawn()
+            .insert_bundle((Sprite::default(), handle.clone()))
+            .id();
+        app.update();
+        assert_eq!(aabb_size(&app, sprite), None);
+
+        app.world
+            .resource_mut::<Assets<Image>>()
+            .set_untracked(&handle, image(3, 3));
+        app.update();
+        assert_eq!(aabb_size(&app, sprite), Some(Vec3::new(3.0, 3.0, 0.0)));
+    }
+}
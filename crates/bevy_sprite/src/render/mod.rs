@@ -22,7 +22,10 @@ use bevy_render::{
     render_resource::*,
     renderer::{RenderDevice, RenderQueue},
     texture::{BevyDefault, Image},
-    view::{Msaa, ViewUniform, ViewUniformOffset, ViewUniforms, Visibility},
+    view::{
+        ComputedVisibility, Msaa, ViewUniform, ViewUniformOffset, ViewUniforms, Visibility,
+        VisibleEntities,
+    },
     Extract,
 };
 use bevy_transform::components::GlobalTransform;
@@ -30,6 +33,7 @@ use bevy_utils::FloatOrd;
 use bevy_utils::HashMap;
 use bytemuck::{Pod, Zeroable};
 use copyless::VecHelper;
+use fixedbitset::FixedBitSet;
 
 pub struct SpritePipeline {
     view_layout: BindGroupLayout,
@@ -89,7 +93,6 @@ bitflags::bitflags! {
     // MSAA uses the highest 6 bits for the MSAA sample count - 1 to support up to 64x MSAA.
     pub struct SpritePipelineKey: u32 {
         const NONE                        = 0;
-        const COLORED                     = (1 << 0);
         const MSAA_RESERVED_BITS          = SpritePipelineKey::MSAA_MASK_BITS << SpritePipelineKey::MSAA_SHIFT_BITS;
     }
 }
@@ -112,36 +115,32 @@ impl SpecializedRenderPipeline for SpritePipeline {
     type Key = SpritePipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
-        let mut formats = vec![
-            // position
-            VertexFormat::Float32x3,
-            // uv
-            VertexFormat::Float32x2,
-        ];
-
-        if key.contains(SpritePipelineKey::COLORED) {
-            // color
-            formats.push(VertexFormat::Float32x4);
-        }
-
-        let vertex_layout =
-            VertexBufferLayout::from_vertex_formats(VertexStepMode::Vertex, formats);
-
-        let mut shader_defs = Vec::new();
-        if key.contains(SpritePipelineKey::COLORED) {
-            shader_defs.push("COLORED".to_string());
-        }
+        let instance_layout = VertexBufferLayout::from_vertex_formats(
+            VertexStepMode::Instance,
+            vec![
+                // x axis
+                VertexFormat::Float32x3,
+                // y axis
+                VertexFormat::Float32x3,
+                // translation
+                VertexFormat::Float32x3,
+                // uv offset and scale
+                VertexFormat::Float32x4,
+                // color
+                VertexFormat::Float32x4,
+            ],
+        );
 
         RenderPipelineDescriptor {
             vertex: VertexState {
                 shader: SPRITE_SHADER_HANDLE.typed::<Shader>(),
                 entry_point: "vertex".into(),
-                shader_defs: shader_defs.clone(),
-                buffers: vec![vertex_layout],
+                shader_defs: Vec::new(),
+                buffers: vec![instance_layout],
             },
             fragment: Some(FragmentState {
                 shader: SPRITE_SHADER_HANDLE.typed::<Shader>(),
-                shader_defs,
+                shader_defs: Vec::new(),
                 entry_point: "fragment".into(),
                 targets: vec![ColorTargetState {
                     format: TextureFormat::bevy_default(),
@@ -172,6 +171,9 @@ impl SpecializedRenderPipeline for SpritePipeline {
 
 #[derive(Component, Clone, Copy)]
 pub struct ExtractedSprite {
+    /// The entity of the sprite, checked against the [`VisibleEntities`] of each view, or `None`
+    /// for sprites that aren't frustum culled, which are drawn in every view
+    pub entity: Option<Entity>,
     pub transform: GlobalTransform,
     pub color: Color,
     /// Select an area of the texture
@@ -222,10 +224,21 @@ pub fn extract_sprite_events(
 pub fn extract_sprites(
     mut extracted_sprites: ResMut<ExtractedSprites>,
     texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
-    sprite_query: Extract<Query<(&Visibility, &Sprite, &GlobalTransform, &Handle<Image>)>>,
+    sprite_query: Extract<
+        Query<(
+            Entity,
+            &Visibility,
+            Option<&ComputedVisibility>,
+            &Sprite,
+            &GlobalTransform,
+            &Handle<Image>,
+        )>,
+    >,
     atlas_query: Extract<
         Query<(
+            Entity,
             &Visibility,
+            Option<&ComputedVisibility>,
             &TextureAtlasSprite,
             &GlobalTransform,
             &Handle<TextureAtlas>,
@@ -233,12 +246,15 @@ pub fn extract_sprites(
     >,
 ) {
     extracted_sprites.sprites.clear();
-    for (visibility, sprite, transform, handle) in sprite_query.iter() {
-        if !visibility.is_visible {
+    for (entity, visibility, computed_visibility, sprite, transform, handle) in sprite_query.iter()
+    {
+        let (visible, entity) = sprite_visibility(entity, visibility, computed_visibility);
+        if !visible {
             continue;
         }
         // PERF: we don't check in this function that the `Image` asset is ready, since it should be in most cases and hashing the handle is expensive
         extracted_sprites.sprites.alloc().init(ExtractedSprite {
+            entity,
             color: sprite.color,
             transform: *transform,
             // Use the full texture
@@ -251,13 +267,17 @@ pub fn extract_sprites(
             anchor: sprite.anchor.as_vec(),
         });
     }
-    for (visibility, atlas_sprite, transform, texture_atlas_handle) in atlas_query.iter() {
-        if !visibility.is_visible {
+    for (entity, visibility, computed_visibility, atlas_sprite, transform, texture_atlas_handle) in
+        atlas_query.iter()
+    {
+        let (visible, entity) = sprite_visibility(entity, visibility, computed_visibility);
+        if !visible {
             continue;
         }
         if let Some(texture_atlas) = texture_atlases.get(texture_atlas_handle) {
             let rect = Some(texture_atlas.textures[atlas_sprite.index as usize]);
             extracted_sprites.sprites.alloc().init(ExtractedSprite {
+                entity,
                 color: atlas_sprite.color,
                 transform: *transform,
                 // Select the area in the texture atlas
@@ -273,57 +293,35 @@ pub fn extract_sprites(
     }
 }
 
+/// The instance data of a sprite: its quad spans the `x_axis` and `y_axis` from the `translation`
 #[repr(C)]
 #[derive(Copy, Clone, Pod, Zeroable)]
-struct SpriteVertex {
-    pub position: [f32; 3],
-    pub uv: [f32; 2],
-}
-
-#[repr(C)]
-#[derive(Copy, Clone, Pod, Zeroable)]
-struct ColoredSpriteVertex {
-    pub position: [f32; 3],
-    pub uv: [f32; 2],
+struct SpriteInstance {
+    pub x_axis: [f32; 3],
+    pub y_axis: [f32; 3],
+    pub translation: [f32; 3],
+    /// The uv of the top left corner of the quad, and the uv size of the quad
+    pub uv_offset_scale: [f32; 4],
     pub color: [f32; 4],
 }
 
 pub struct SpriteMeta {
-    vertices: BufferVec<SpriteVertex>,
-    colored_vertices: BufferVec<ColoredSpriteVertex>,
+    instances: BufferVec<SpriteInstance>,
     view_bind_group: Option<BindGroup>,
 }
 
 impl Default for SpriteMeta {
     fn default() -> Self {
         Self {
-            vertices: BufferVec::new(BufferUsages::VERTEX),
-            colored_vertices: BufferVec::new(BufferUsages::VERTEX),
+            instances: BufferVec::new(BufferUsages::VERTEX),
             view_bind_group: None,
         }
     }
 }
 
-const QUAD_INDICES: [usize; 6] = [0, 2, 3, 0, 1, 2];
-
-const QUAD_VERTEX_POSITIONS: [Vec2; 4] = [
-    Vec2::new(-0.5, -0.5),
-    Vec2::new(0.5, -0.5),
-    Vec2::new(0.5, 0.5),
-    Vec2::new(-0.5, 0.5),
-];
-
-const QUAD_UVS: [Vec2; 4] = [
-    Vec2::new(0., 1.),
-    Vec2::new(1., 1.),
-    Vec2::new(1., 0.),
-    Vec2::new(0., 0.),
-];
-
 #[derive(Component, Eq, PartialEq, Copy, Clone)]
 pub struct SpriteBatch {
     image_handle_id: HandleId,
-    colored: bool,
 }
 
 #[derive(Default)]
@@ -331,6 +329,79 @@ pub struct ImageBindGroups {
     values: HashMap<Handle<Image>, BindGroup>,
 }
 
+/// Sorts sprites by z for correct transparency and then by handle to improve batching
+fn sprite_order(a: &ExtractedSprite, b: &ExtractedSprite) -> Ordering {
+    match a
+        .transform
+        .translation
+        .z
+        .partial_cmp(&b.transform.translation.z)
+    {
+        Some(Ordering::Equal) | None => a.image_handle_id.cmp(&b.image_handle_id),
+        Some(other) => other,
+    }
+}
+
+/// Returns whether a sprite is visible, and its entity if it is frustum culled.
+///
+/// Sprites without a [`ComputedVisibility`] are only hidden through their [`Visibility`], and
+/// are drawn in every view.
+fn sprite_visibility(
+    entity: Entity,
+    visibility: &Visibility,
+    computed_visibility: Option<&ComputedVisibility>,
+) -> (bool, Option<Entity>) {
+    match computed_visibility {
+        Some(computed_visibility) => (computed_visibility.is_visible, Some(entity)),
+        None => (visibility.is_visible, None),
+    }
+}
+
+/// Calculates the instance data of a sprite whose image is of `image_size` pixels
+fn sprite_instance(extracted_sprite: &ExtractedSprite, image_size: Vec2) -> SpriteInstance {
+    // By default, the quad shows the whole texture, at the size of the texture
+    let (rect_min, rect_size) = match extracted_sprite.rect {
+        Some(rect) => (rect.min, rect.size()),
+        None => (Vec2::ZERO, image_size),
+    };
+    let mut uv_offset = rect_min / image_size;
+    let mut uv_scale = rect_size / image_size;
+    if extracted_sprite.flip_x {
+        uv_offset.x += uv_scale.x;
+        uv_scale.x = -uv_scale.x;
+    }
+    if extracted_sprite.flip_y {
+        uv_offset.y += uv_scale.y;
+        uv_scale.y = -uv_scale.y;
+    }
+
+    // Override the size if a custom one is specified
+    let quad_size = extracted_sprite.custom_size.unwrap_or(rect_size);
+
+    // Apply size, anchor and global transform
+    let matrix = extracted_sprite.transform.compute_matrix();
+    let x_axis = matrix.x_axis.truncate() * quad_size.x;
+    let y_axis = matrix.y_axis.truncate() * quad_size.y;
+    let bottom_left = Vec2::splat(-0.5) - extracted_sprite.anchor;
+    let translation = matrix.w_axis.truncate() + x_axis * bottom_left.x + y_axis * bottom_left.y;
+
+    SpriteInstance {
+        x_axis: x_axis.into(),
+        y_axis: y_axis.into(),
+        translation: translation.into(),
+        uv_offset_scale: [uv_offset.x, uv_offset.y, uv_scale.x, uv_scale.y],
+        color: extracted_sprite.color.as_linear_rgba_f32(),
+    }
+}
+
+/// System queuing the extracted sprites in the [`Transparent2d`] phase of the views they are
+/// visible from.
+///
+/// The sprites are sorted by z for correct transparency, then by image: the sprites of a view which
+/// are consecutive in this order and share an image form a batch, drawn with a single draw call
+/// once its items are merged by [`batch_phase_system`](bevy_render::render_phase::batch_phase_system).
+/// A batch is only broken by a sprite with a different image, or by another phase item between
+/// its sprites.
 #[allow(clippy::too_many_arguments)]
 pub fn queue_sprites(
     mut commands: Commands,
@@ -346,7 +417,7 @@ pub fn queue_sprites(
     gpu_images: Res<RenderAssets<Image>>,
     msaa: Res<Msaa>,
     mut extracted_sprites: ResMut<ExtractedSprites>,
-    mut views: Query<&mut RenderPhase<Transparent2d>>,
+    mut views: Query<(&VisibleEntities, &mut RenderPhase<Transparent2d>)>,
     events: Res<SpriteAssetEvents>,
 ) {
     // If an image has changed, the GpuImage has (probably) changed
@@ -362,9 +433,8 @@ pub fn queue_sprites(
     if let Some(view_binding) = view_uniforms.uniforms.binding() {
         let sprite_meta = &mut sprite_meta;
 
-        // Clear the vertex buffers
-        sprite_meta.vertices.clear();
-        sprite_meta.colored_vertices.clear();
+        // Clear the instance buffer
+        sprite_meta.instances.clear();
 
         sprite_meta.view_bind_group = Some(render_device.create_bind_group(&BindGroupDescriptor {
             entries: &[BindGroupEntry {
@@ -378,40 +448,27 @@ pub fn queue_sprites(
         let draw_sprite_function = draw_functions.read().get_id::<DrawSprite>().unwrap();
         let key = SpritePipelineKey::from_msaa_samples(msaa.samples);
         let pipeline = pipelines.specialize(&mut pipeline_cache, &sprite_pipeline, key);
-        let colored_pipeline = pipelines.specialize(
-            &mut pipeline_cache,
-            &sprite_pipeline,
-            key | SpritePipelineKey::COLORED,
-        );
 
-        // Vertex buffer indices
+        let extracted_sprites = &mut extracted_sprites.sprites;
+        let image_bind_groups = &mut *image_bind_groups;
+
+        extracted_sprites.sort_unstable_by(sprite_order);
+
+        // Instance buffer index
         let mut index = 0;
-        let mut colored_index = 0;
 
-        // FIXME: VisibleEntities is ignored
-        for mut transparent_phase in views.iter_mut() {
-            let extracted_sprites = &mut extracted_sprites.sprites;
-            let image_bind_groups = &mut *image_bind_groups;
+        // The extracted sprites are visible from at least one view, only the sprites visible from
+        // the current view are queued in its phase
+        let mut view_entities = FixedBitSet::default();
+        for (visible_entities, mut transparent_phase) in views.iter_mut() {
+            view_entities.clear();
+            view_entities.extend(visible_entities.iter().map(|entity| entity.id() as usize));
 
             transparent_phase.items.reserve(extracted_sprites.len());
 
-            // Sort sprites by z for correct transparency and then by handle to improve batching
-            extracted_sprites.sort_unstable_by(|a, b| {
-                match a
-                    .transform
-                    .translation
-                    .z
-                    .partial_cmp(&b.transform.translation.z)
-                {
-                    Some(Ordering::Equal) | None => a.image_handle_id.cmp(&b.image_handle_id),
-                    Some(other) => other,
-                }
-            });
-
             // Impossible starting values that will be replaced on the first iteration
             let mut current_batch = SpriteBatch {
                 image_handle_id: HandleId::Id(Uuid::nil(), u64::MAX),
-                colored: false,
             };
             let mut current_batch_entity = Entity::from_raw(u32::MAX);
             let mut current_image_size = Vec2::ZERO;
@@ -421,9 +478,14 @@ pub fn queue_sprites(
             // Batches are merged later (in `batch_phase_system()`), so that they can be interrupted
             // by any other phase item (and they can interrupt other items from batching).
             for extracted_sprite in extracted_sprites.iter() {
+                if let Some(entity) = extracted_sprite.entity {
+                    if !view_entities.contains(entity.id() as usize) {
+                        continue;
+                    }
+                }
+
                 let new_batch = SpriteBatch {
                     image_handle_id: extracted_sprite.image_handle_id,
-                    colored: extracted_sprite.color != Color::WHITE,
                 };
                 if new_batch != current_batch {
                     // Set-up a new possible batch
@@ -461,90 +523,26 @@ pub fn queue_sprites(
                     }
                 }
 
-                // Calculate vertex data for this item
-
-                let mut uvs = QUAD_UVS;
-                if extracted_sprite.flip_x {
-                    uvs = [uvs[1], uvs[0], uvs[3], uvs[2]];
-                }
-                if extracted_sprite.flip_y {
-                    uvs = [uvs[3], uvs[2], uvs[1], uvs[0]];
-                }
-
-                // By default, the size of the quad is the size of the texture
-                let mut quad_size = current_image_size;
-
-                // If a rect is specified, adjust UVs and the size of the quad
-                if let Some(rect) = extracted_sprite.rect {
-                    let rect_size = rect.size();
-                    for uv in &mut uvs {
-                        *uv = (rect.min + *uv * rect_size) / current_image_size;
-                    }
-                    quad_size = rect_size;
-                }
-
-                // Override the size if a custom one is specified
-                if let Some(custom_size) = extracted_sprite.custom_size {
-                    quad_size = custom_size;
-                }
-
-                // Apply size and global transform
-                let positions = QUAD_VERTEX_POSITIONS.map(|quad_pos| {
-                    extracted_sprite
-                        .transform
-                        .mul_vec3(((quad_pos - extracted_sprite.anchor) * quad_size).extend(0.))
-                        .into()
+                // Store the instance data and add the item to the render phase
+                sprite_meta
+                    .instances
+                    .push(sprite_instance(extracted_sprite, current_image_size));
+                let item_start = index;
+                index += 1;
+                let item_end = index;
+
+                transparent_phase.add(Transparent2d {
+                    draw_function: draw_sprite_function,
+                    pipeline,
+                    entity: current_batch_entity,
+                    // These items will be sorted by depth with other phase items
+                    sort_key: FloatOrd(extracted_sprite.transform.translation.z),
+                    batch_range: Some(item_start..item_end),
                 });
-
-                // These items will be sorted by depth with other phase items
-                let sort_key = FloatOrd(extracted_sprite.transform.translation.z);
-
-                // Store the vertex data and add the item to the render phase
-                if current_batch.colored {
-                    for i in QUAD_INDICES {
-                        sprite_meta.colored_vertices.push(ColoredSpriteVertex {
-                            position: positions[i],
-                            uv: uvs[i].into(),
-                            color: extracted_sprite.color.as_linear_rgba_f32(),
-                        });
-                    }
-                    let item_start = colored_index;
-                    colored_index += QUAD_INDICES.len() as u32;
-                    let item_end = colored_index;
-
-                    transparent_phase.add(Transparent2d {
-                        draw_function: draw_sprite_function,
-                        pipeline: colored_pipeline,
-                        entity: current_batch_entity,
-                        sort_key,
-                        batch_range: Some(item_start..item_end),
-                    });
-                } else {
-                    for i in QUAD_INDICES {
-                        sprite_meta.vertices.push(SpriteVertex {
-                            position: positions[i],
-                            uv: uvs[i].into(),
-                        });
-                    }
-                    let item_start = index;
-                    index += QUAD_INDICES.len() as u32;
-                    let item_end = index;
-
-                    transparent_phase.add(Transparent2d {
-                        draw_function: draw_sprite_function,
-                        pipeline,
-                        entity: current_batch_entity,
-                        sort_key,
-                        batch_range: Some(item_start..item_end),
-                    });
-                }
             }
         }
         sprite_meta
-            .vertices
-            .write_buffer(&render_device, &render_queue);
-        sprite_meta
-            .colored_vertices
+            .instances
             .write_buffer(&render_device, &render_queue);
     }
 }
@@ -602,22 +600,135 @@ impl<const I: usize> EntityRenderCommand for SetSpriteTextureBindGroup<I> {
 
 pub struct DrawSpriteBatch;
 impl<P: BatchedPhaseItem> RenderCommand<P> for DrawSpriteBatch {
-    type Param = (SRes<SpriteMeta>, SQuery<Read<SpriteBatch>>);
+    type Param = SRes<SpriteMeta>;
 
     fn render<'w>(
         _view: Entity,
         item: &P,
-        (sprite_meta, query_batch): SystemParamItem<'w, '_, Self::Param>,
+        sprite_meta: SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
-        let sprite_batch = query_batch.get(item.entity()).unwrap();
         let sprite_meta = sprite_meta.into_inner();
-        if sprite_batch.colored {
-            pass.set_vertex_buffer(0, sprite_meta.colored_vertices.buffer().unwrap().slice(..));
-        } else {
-            pass.set_vertex_buffer(0, sprite_meta.vertices.buffer().unwrap().slice(..));
-        }
-        pass.draw(item.batch_range().as_ref().unwrap().clone(), 0..1);
+        pass.set_vertex_buffer(0, sprite_meta.instances.buffer().unwrap().slice(..));
+        pass.draw(0..6, item.batch_range().as_ref().unwrap().clone());
         RenderCommandResult::Success
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Anchor;
+    use bevy_math::{Quat, Vec3};
+
+    fn extracted_sprite(z: f32, image: u64) -> ExtractedSprite {
+        ExtractedSprite {
+            entity: Some(Entity::from_raw(0)),
+            transform: GlobalTransform::from_xyz(0.0, 0.0, z),
+            color: Color::WHITE,
+            rect: None,
+            custom_size: None,
+            image_handle_id: HandleId::Id(Uuid::nil(), image),
+            flip_x: false,
+            flip_y: false,
+            anchor: Anchor::Center.as_vec(),
+        }
+    }
+
+    #[test]
+    fn sprites_are_sorted_by_z_then_image() {
+        let mut sprites = [
+            extracted_sprite(1.0, 0),
+            extracted_sprite(0.0, 1),
+            extracted_sprite(0.0, 0),
+            extracted_sprite(1.0, 1),
+            extracted_sprite(0.0, 1),
+            extracted_sprite(-1.0, 1),
+        ];
+        sprites.sort_unstable_by(sprite_order);
+        let order = sprites
+            .iter()
+            .map(|sprite| (sprite.transform.translation.z, sprite.image_handle_id))
+            .collect::<Vec<_>>();
+        let id = |image| HandleId::Id(Uuid::nil(), image);
+        assert_eq!(
+            order,
+            vec![
+                (-1.0, id(1)),
+                (0.0, id(0)),
+                (0.0, id(1)),
+                (0.0, id(1)),
+                (1.0, id(0)),
+                (1.0, id(1)),
+            ]
+        );
+
+        // The sprites with the same z and image are consecutive, and share a batch
+        let batches = sprites
+            .iter()
+            .map(|sprite| SpriteBatch {
+                image_handle_id: sprite.image_handle_id,
+            })
+            .collect::<Vec<_>>();
+        let batch_count = 1 + batches.windows(2).filter(|pair| pair[0] != pair[1]).count();
+        assert_eq!(batch_count, 5);
+    }
+
+    #[test]
+    fn sprites_without_computed_visibility_are_drawn_in_every_view() {
+        let entity = Entity::from_raw(3);
+        let hidden = Visibility { is_visible: false };
+        let culled = ComputedVisibility::default();
+        assert_eq!(
+            sprite_visibility(entity, &Visibility::default(), None),
+            (true, None)
+        );
+        assert_eq!(sprite_visibility(entity, &hidden, None), (false, None));
+        assert_eq!(
+            sprite_visibility(entity, &Visibility::default(), Some(&culled)),
+            (culled.is_visible, Some(entity))
+        );
+    }
+
+    #[test]
+    fn sprite_instance_of_a_rect() {
+        let mut sprite = extracted_sprite(0.0, 0);
+        sprite.rect = Some(Rect {
+            min: Vec2::new(16.0, 0.0),
+            max: Vec2::new(32.0, 8.0),
+        });
+        sprite.color = Color::RED;
+        let instance = sprite_instance(&sprite, Vec2::new(64.0, 32.0));
+        assert_eq!(instance.uv_offset_scale, [0.25, 0.0, 0.25, 0.25]);
+        // The quad has the size of the rect, around the center of the sprite
+        assert_eq!(instance.x_axis, [16.0, 0.0, 0.0]);
+        assert_eq!(instance.y_axis, [0.0, 8.0, 0.0]);
+        assert_eq!(instance.translation, [-8.0, -4.0, 0.0]);
+        assert_eq!(instance.color, Color::RED.as_linear_rgba_f32());
+
+        // Flipping starts the uvs at the other side of the rect
+        sprite.flip_x = true;
+        sprite.flip_y = true;
+        let instance = sprite_instance(&sprite, Vec2::new(64.0, 32.0));
+        assert_eq!(instance.uv_offset_scale, [0.5, 0.25, -0.25, -0.25]);
+    }
+
+    #[test]
+    fn sprite_instance_is_transformed_around_its_anchor() {
+        let mut sprite = extracted_sprite(2.0, 0);
+        sprite.custom_size = Some(Vec2::new(10.0, 20.0));
+        sprite.anchor = Anchor::BottomLeft.as_vec();
+        sprite.transform = GlobalTransform {
+            translation: Vec3::new(5.0, 5.0, 2.0),
+            rotation: Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            scale: Vec3::new(2.0, 1.0, 1.0),
+        };
+        let instance = sprite_instance(&sprite, Vec2::new(64.0, 32.0));
+        // The whole image is shown, at the custom size
+        assert_eq!(instance.uv_offset_scale, [0.0, 0.0, 1.0, 1.0]);
+        assert!(Vec3::from(instance.x_axis).abs_diff_eq(Vec3::new(0.0, 20.0, 0.0), 1e-5));
+        assert!(Vec3::from(instance.y_axis).abs_diff_eq(Vec3::new(-20.0, 0.0, 0.0), 1e-5));
+        // The bottom left corner is at the translation
+        assert_eq!(instance.translation, [5.0, 5.0, 2.0]);
+    }
+}
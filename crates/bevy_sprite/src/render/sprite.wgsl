@@ -5,28 +5,42 @@ struct View {
 [[group(0), binding(0)]]
 var<uniform> view: View;
 
+struct Sprite {
+    [[location(0)]] x_axis: vec3<f32>;
+    [[location(1)]] y_axis: vec3<f32>;
+    [[location(2)]] translation: vec3<f32>;
+    [[location(3)]] uv_offset_scale: vec4<f32>;
+    [[location(4)]] color: vec4<f32>;
+};
+
 struct VertexOutput {
     [[location(0)]] uv: vec2<f32>;
-#ifdef COLORED
     [[location(1)]] color: vec4<f32>;
-#endif
     [[builtin(position)]] position: vec4<f32>;
 };
 
 [[stage(vertex)]]
 fn vertex(
-    [[location(0)]] vertex_position: vec3<f32>,
-    [[location(1)]] vertex_uv: vec2<f32>,
-#ifdef COLORED
-    [[location(2)]] vertex_color: vec4<f32>,
-#endif
+    [[builtin(vertex_index)]] vertex_index: u32,
+    sprite: Sprite,
 ) -> VertexOutput {
+    // The corners of the two triangles of the sprite's quad
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+
     var out: VertexOutput;
-    out.uv = vertex_uv;
-    out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
-#ifdef COLORED
-    out.color = vertex_color;
-#endif
+    // Textures are sampled from the top left corner
+    out.uv = sprite.uv_offset_scale.xy + vec2<f32>(corner.x, 1.0 - corner.y) * sprite.uv_offset_scale.zw;
+    out.color = sprite.color;
+    let position = sprite.translation + sprite.x_axis * corner.x + sprite.y_axis * corner.y;
+    out.position = view.view_proj * vec4<f32>(position, 1.0);
     return out;
 }
 
@@ -37,9 +51,5 @@ var sprite_sampler: sampler;
 
 [[stage(fragment)]]
 fn fragment(in: VertexOutput) -> [[location(0)]] vec4<f32> {
-    var color = textureSample(sprite_texture, sprite_sampler, in.uv);
-#ifdef COLORED
-    color = in.color * color;
-#endif
-    return color;
+    return in.color * textureSample(sprite_texture, sprite_sampler, in.uv);
 }
@@ -0,0 +1,372 @@
+use crate::{DynamicTextureAtlasBuilder, TextureAtlas, TextureAtlasSprite};
+use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
+use bevy_ecs::prelude::*;
+use bevy_log::warn;
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use bevy_render::{
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    texture::{Image, TextureFormatPixelInfo},
+};
+use bevy_utils::{HashMap, HashSet};
+use guillotiere::AllocId;
+
+/// Draws a sprite with an image packed at runtime in the [`RuntimeTextureAtlases`], instead of
+/// a sprite of its own.
+///
+/// Insert it on an entity with a [`SpriteSheetBundle`](crate::SpriteSheetBundle): once the image
+/// is loaded and packed, its `Handle<TextureAtlas>` and the index of its [`TextureAtlasSprite`]
+/// are set by [`pack_runtime_atlas_sprites`]. The sprites sharing an atlas page are drawn in a
+/// single batch, even when their images were loaded separately, like modded or user-generated
+/// sprites.
+#[derive(Component, Debug, Default, Clone, Reflect)]
+#[reflect(Component)]
+pub struct RuntimeAtlasSprite(pub Handle<Image>);
+
+/// Where an image was packed by the [`RuntimeTextureAtlases`]
+#[derive(Debug, Clone)]
+pub struct PackedImage {
+    /// The atlas of the page the image was packed in
+    pub atlas: Handle<TextureAtlas>,
+    /// The index of the image in the atlas, for a [`TextureAtlasSprite`]
+    pub index: usize,
+}
+
+struct RuntimeAtlasPage {
+    atlas: Handle<TextureAtlas>,
+    builder: DynamicTextureAtlasBuilder,
+    /// The allocations of the images packed in the page, by their index in the atlas
+    allocations: HashMap<usize, AllocId>,
+}
+
+/// Packs images into texture atlases at runtime, as they are loaded.
+///
+/// The images are packed in pages of `page_size`: when an image doesn't fit in the existing pages,
+/// a new page is added. When a packed image is modified, it is packed again and the sprites using
+/// it are moved to its new place, the space it used in its page being freed for other images, like
+/// the space of the removed images.
+pub struct RuntimeTextureAtlases {
+    page_size: Vec2,
+    padding: i32,
+    format: TextureFormat,
+    pages: Vec<RuntimeAtlasPage>,
+    packed_images: HashMap<HandleId, PackedImage>,
+    /// Images that can't be packed, because they are bigger than a page or can't be converted
+    rejected_images: HashSet<HandleId>,
+}
+
+impl Default for RuntimeTextureAtlases {
+    fn default() -> Self {
+        Self::new(Vec2::new(1024., 1024.), 0, TextureFormat::Rgba8UnormSrgb)
+    }
+}
+
+impl RuntimeTextureAtlases {
+    /// Creates atlases with pages of `page_size` pixels in the texture `format`, with `padding`
+    /// pixels between the packed images.
+    pub fn new(page_size: Vec2, padding: i32, format: TextureFormat) -> Self {
+        Self {
+            page_size,
+            padding,
+            format,
+            pages: Vec::new(),
+            packed_images: HashMap::default(),
+            rejected_images: HashSet::default(),
+        }
+    }
+
+    /// Returns where `image` was packed, if it was.
+    pub fn get(&self, image: &Handle<Image>) -> Option<&PackedImage> {
+        self.packed_images.get(&image.id)
+    }
+
+    /// Returns the atlases of the pages, in the order they were added.
+    pub fn atlases(&self) -> impl Iterator<Item = &Handle<TextureAtlas>> {
+        self.pages.iter().map(|page| &page.atlas)
+    }
+
+    /// Packs `image` if it wasn't already, and returns where it was packed, or [`None`] if it isn't
+    /// loaded yet or can't be packed.
+    pub fn pack(
+        &mut self,
+        image: &Handle<Image>,
+        images: &mut Assets<Image>,
+        atlases: &mut Assets<TextureAtlas>,
+    ) -> Option<&PackedImage> {
+        if self.packed_images.contains_key(&image.id) {
+            return self.packed_images.get(&image.id);
+        }
+        if self.rejected_images.contains(&image.id) {
+            return None;
+        }
+        let texture = images.get(image)?;
+        let texture = if texture.texture_descriptor.format == self.format {
+            texture.clone()
+        } else if let Some(converted) = texture.convert(self.format) {
+            converted
+        } else {
+            warn!(
+                "Can't pack an image in the runtime texture atlases, its format {:?} can't be converted to {:?}",
+                texture.texture_descriptor.format, self.format
+            );
+            self.rejected_images.insert(image.id);
+            return None;
+        };
+        let size = texture.size() + Vec2::splat(self.padding as f32);
+        if size.x > self.page_size.x || size.y > self.page_size.y {
+            warn!(
+                "Can't pack an image of {} in the runtime texture atlases, their pages are {}",
+                texture.size(),
+                self.page_size
+            );
+            self.rejected_images.insert(image.id);
+            return None;
+        }
+
+        let mut packed_image = None;
+        for page in &mut self.pages {
+            let atlas = atlases.get_mut(&page.atlas)?;
+            if let Some((index, allocation)) =
+                page.builder.allocate_texture(atlas, images, &texture)
+            {
+                page.allocations.insert(index, allocation);
+                packed_image = Some(PackedImage {
+                    atlas: page.atlas.clone(),
+                    index,
+                });
+                break;
+            }
+        }
+        let packed_image = match packed_image {
+            Some(packed_image) => packed_image,
+            None => {
+                let mut page = self.add_page(images, atlases);
+                let atlas = atlases.get_mut(&page.atlas)?;
+                let (index, allocation) = page.builder.allocate_texture(atlas, images, &texture)?;
+                page.allocations.insert(index, allocation);
+                let atlas = page.atlas.clone();
+                self.pages.push(page);
+                PackedImage { atlas, index }
+            }
+        };
+        Some(self.packed_images.entry(image.id).or_insert(packed_image))
+    }
+
+    fn add_page(
+        &self,
+        images: &mut Assets<Image>,
+        atlases: &mut Assets<TextureAtlas>,
+    ) -> RuntimeAtlasPage {
+        let (width, height) = (self.page_size.x as u32, self.page_size.y as u32);
+        let texture = images.add(Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            vec![0; self.format.pixel_size() * (width * height) as usize],
+            self.format,
+        ));
+        RuntimeAtlasPage {
+            atlas: atlases.add(TextureAtlas::new_empty(texture, self.page_size)),
+            builder: DynamicTextureAtlasBuilder::new(self.page_size, self.padding),
+            allocations: HashMap::default(),
+        }
+    }
+
+    /// Forgets where `image` was packed, for it to be packed again, and frees its space in its page.
+    fn unpack(&mut self, image: HandleId) {
+        self.rejected_images.remove(&image);
+        let packed_image = match self.packed_images.remove(&image) {
+            Some(packed_image) => packed_image,
+            None => return,
+        };
+        let page = self
+            .pages
+            .iter_mut()
+            .find(|page| page.atlas == packed_image.atlas);
+        if let Some(page) = page {
+            if let Some(allocation) = page.allocations.remove(&packed_image.index) {
+                page.builder.atlas_allocator.deallocate(allocation);
+            }
+        }
+    }
+}
+
+/// System packing the images of the [`RuntimeAtlasSprite`]s in the [`RuntimeTextureAtlases`], and
+/// setting the atlas and the index of their [`TextureAtlasSprite`]
+pub fn pack_runtime_atlas_sprites(
+    mut runtime_atlases: ResMut<RuntimeTextureAtlases>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut images: ResMut<Assets<Image>>,
+    mut atlases: ResMut<Assets<TextureAtlas>>,
+    mut sprites: Query<(
+        &RuntimeAtlasSprite,
+        &mut Handle<TextureAtlas>,
+        &mut TextureAtlasSprite,
+    )>,
+) {
+    for event in image_events.iter() {
+        match event {
+            AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
+                runtime_atlases.unpack(handle.id);
+            }
+            AssetEvent::Created { .. } => {}
+        }
+    }
+
+    for (runtime_sprite, mut atlas, mut sprite) in sprites.iter_mut() {
+        let packed_image = match runtime_atlases.pack(&runtime_sprite.0, &mut images, &mut atlases)
+        {
+            Some(packed_image) => packed_image,
+            None => continue,
+        };
+        // Only trigger change detection when the sprite moved
+        if *atlas != packed_image.atlas {
+            *atlas = packed_image.atlas.clone();
+        }
+        if sprite.index != packed_image.index {
+            sprite.index = packed_image.index;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::{App, CoreStage};
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_tasks::IoTaskPool;
+
+    fn image(width: u32, height: u32, color: [u8; 4]) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &color,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+
+    fn atlas_app(page_size: Vec2) -> App {
+        IoTaskPool::init(Default::default);
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin)
+            .add_asset::<Image>()
+            .add_asset::<TextureAtlas>()
+            .insert_resource(RuntimeTextureAtlases::new(
+                page_size,
+                0,
+                TextureFormat::Rgba8UnormSrgb,
+            ))
+            .add_system_to_stage(CoreStage::PostUpdate, pack_runtime_atlas_sprites);
+        app
+    }
+
+    fn pack(app: &mut App, image: &Handle<Image>) -> Option<PackedImage> {
+        let world = &mut app.world;
+        world.resource_scope(|world, mut runtime_atlases: Mut<RuntimeTextureAtlases>| {
+            world.resource_scope(|world, mut atlases: Mut<Assets<TextureAtlas>>| {
+                let mut images = world.resource_mut::<Assets<Image>>();
+                runtime_atlases
+                    .pack(image, &mut images, &mut atlases)
+                    .cloned()
+            })
+        })
+    }
+
+    #[test]
+    fn images_are_packed_in_pages() {
+        let mut app = atlas_app(Vec2::new(8.0, 8.0));
+        let mut images = app.world.resource_mut::<Assets<Image>>();
+        let red = images.add(image(8, 4, [255, 0, 0, 255]));
+        let blue = images.add(image(8, 4, [0, 0, 255, 255]));
+        let green = images.add(image(2, 2, [0, 255, 0, 255]));
+        let large = images.add(image(16, 2, [0, 0, 0, 255]));
+
+        let packed_red = pack(&mut app, &red).unwrap();
+        let packed_blue = pack(&mut app, &blue).unwrap();
+        assert_eq!(packed_red.atlas, packed_blue.atlas);
+        assert_ne!(packed_red.index, packed_blue.index);
+        // Packing again returns the same place
+        assert_eq!(pack(&mut app, &red).unwrap().index, packed_red.index);
+
+        // The first page is full
+        let packed_green = pack(&mut app, &green).unwrap();
+        assert_ne!(packed_green.atlas, packed_red.atlas);
+        assert_eq!(
+            app.world
+                .resource::<RuntimeTextureAtlases>()
+                .atlases()
+                .count(),
+            2
+        );
+
+        // The pixels of the images are copied in the page
+        let atlases = app.world.resource::<Assets<TextureAtlas>>();
+        let images = app.world.resource::<Assets<Image>>();
+        let atlas = atlases.get(&packed_blue.atlas).unwrap();
+        let rect = atlas.textures[packed_blue.index];
+        assert_eq!(rect.max - rect.min, Vec2::new(8.0, 4.0));
+        let page = images.get(&atlas.texture).unwrap();
+        let pixel = (rect.min.y as usize * 8 + rect.min.x as usize) * 4;
+        assert_eq!(page.data[pixel..pixel + 4], [0, 0, 255, 255]);
+
+        // Images larger than a page are never packed
+        assert!(pack(&mut app, &large).is_none());
+        assert!(app
+            .world
+            .resource::<RuntimeTextureAtlases>()
+            .rejected_images
+            .contains(&large.id));
+    }
+
+    #[test]
+    fn modified_images_free_their_space() {
+        let mut app = atlas_app(Vec2::new(8.0, 8.0));
+        let image_handle =
+            app.world
+                .resource_mut::<Assets<Image>>()
+                .add(image(8, 8, [255, 0, 0, 255]));
+        let sprite = app
+            .world
+            .spawn()
+            .insert_bundle((
+                RuntimeAtlasSprite(image_handle.clone()),
+                Handle::<TextureAtlas>::default(),
+                TextureAtlasSprite::default(),
+            ))
+            .id();
+        app.update();
+        let packed = pack(&mut app, &image_handle).unwrap();
+        assert_eq!(
+            *app.world.get::<Handle<TextureAtlas>>(sprite).unwrap(),
+            packed.atlas
+        );
+
+        // The image fills the page, it's packed again in the space it used
+        let mut images = app.world.resource_mut::<Assets<Image>>();
+        images.get_mut(&image_handle).unwrap().data[0] = 0;
+        // The event is sent at the end of the frame
+        app.update();
+        app.update();
+        let repacked = pack(&mut app, &image_handle).unwrap();
+        assert_eq!(repacked.atlas, packed.atlas);
+        assert_ne!(repacked.index, packed.index);
+        assert_eq!(
+            app.world
+                .resource::<RuntimeTextureAtlases>()
+                .atlases()
+                .count(),
+            1
+        );
+        assert_eq!(
+            app.world.get::<TextureAtlasSprite>(sprite).unwrap().index,
+            repacked.index
+        );
+    }
+}
@@ -0,0 +1,464 @@
+mod render;
+
+pub use render::*;
+
+use crate::TextureAtlas;
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::{load_internal_asset, Handle, HandleUntyped};
+use bevy_core_pipeline::core_2d::Transparent2d;
+use bevy_ecs::prelude::*;
+use bevy_math::{UVec2, Vec2};
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    color::Color,
+    extract_component::UniformComponentPlugin,
+    primitives::Aabb,
+    render_phase::AddRenderCommand,
+    render_resource::{Shader, SpecializedRenderPipelines},
+    view::{ComputedVisibility, NoFrustumCulling, Visibility, VisibilitySystems},
+    RenderApp, RenderStage,
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub const TILEMAP_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9183045472735102369);
+
+/// Adds the rendering of the [`Tilemap`]s.
+#[derive(Default)]
+pub struct TilemapPlugin;
+
+impl Plugin for TilemapPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            TILEMAP_SHADER_HANDLE,
+            "tilemap.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.add_plugin(UniformComponentPlugin::<TilemapUniform>::default())
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                calculate_tilemap_bounds.label(VisibilitySystems::CalculateBounds),
+            );
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<TilemapPipeline>()
+                .init_resource::<SpecializedRenderPipelines<TilemapPipeline>>()
+                .init_resource::<GpuTilemaps>()
+                .init_resource::<TilemapImageBindGroups>()
+                .add_render_command::<Transparent2d, DrawTilemap>()
+                .add_system_to_stage(RenderStage::Extract, extract_tilemaps)
+                .add_system_to_stage(RenderStage::Prepare, prepare_tilemap_chunks)
+                .add_system_to_stage(RenderStage::Queue, queue_tilemap_bind_group)
+                .add_system_to_stage(RenderStage::Queue, queue_tilemaps);
+        }
+    }
+}
+
+/// A rotation of a [`Tile`], counter-clockwise by a quarter turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TileRotation {
+    #[default]
+    None,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+/// A tile of a [`Tilemap`], drawn with an image of the tilemap's [`TextureAtlas`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tile {
+    /// The index of the tile's image in the [`TextureAtlas`]
+    pub index: usize,
+    /// The tile's color tint
+    pub color: Color,
+    /// Flip the tile along the X axis
+    pub flip_x: bool,
+    /// Flip the tile along the Y axis
+    pub flip_y: bool,
+    /// Rotate the tile, after flipping it
+    pub rotation: TileRotation,
+}
+
+impl Default for Tile {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            color: Color::WHITE,
+            flip_x: false,
+            flip_y: false,
+            rotation: TileRotation::None,
+        }
+    }
+}
+
+impl Tile {
+    pub fn new(index: usize) -> Tile {
+        Self {
+            index,
+            ..Default::default()
+        }
+    }
+}
+
+/// Source of the generations of the chunks: a chunk's generation is unique to its content, even
+/// across tilemaps.
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// A grid of [`Tile`]s, drawn with the images of a [`TextureAtlas`].
+///
+/// The tile at `(0, 0)` is at the bottom left corner of the tilemap, which is the origin of its
+/// [`Transform`]. The tiles are rendered by chunks of `chunk_size` tiles, and only the chunks whose
+/// tiles changed are updated on the GPU: changing a few tiles of a large tilemap every frame is
+/// cheap.
+///
+/// A tilemap is a single layer of tiles, layers are stacked by spawning a tilemap for each of them,
+/// ordered by the `z` of their [`Transform`].
+#[derive(Component, Debug, Clone)]
+pub struct Tilemap {
+    size: UVec2,
+    tile_size: Vec2,
+    chunk_size: UVec2,
+    tiles: Vec<Option<Tile>>,
+    /// Changed when any tile changes
+    generation: u64,
+    /// Changed when a tile of the chunk changes
+    chunk_generations: Vec<u64>,
+}
+
+impl Default for Tilemap {
+    fn default() -> Self {
+        Self::new(UVec2::ZERO, Vec2::ONE)
+    }
+}
+
+impl Tilemap {
+    /// The default number of tiles of the chunks, in each dimension
+    pub const DEFAULT_CHUNK_SIZE: UVec2 = UVec2::new(32, 32);
+
+    /// Creates an empty tilemap of `size` tiles of `tile_size` pixels.
+    pub fn new(size: UVec2, tile_size: Vec2) -> Self {
+        let mut tilemap = Self {
+            size,
+            tile_size,
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+            tiles: vec![None; (size.x * size.y) as usize],
+            generation: 0,
+            chunk_generations: Vec::new(),
+        };
+        tilemap.reset_chunks();
+        tilemap
+    }
+
+    /// Renders the tilemap by chunks of `chunk_size` tiles instead of
+    /// [`Tilemap::DEFAULT_CHUNK_SIZE`].
+    ///
+    /// Smaller chunks are cheaper to update when their tiles change, larger chunks are cheaper to
+    /// draw.
+    pub fn with_chunk_size(mut self, chunk_size: UVec2) -> Self {
+        assert!(
+            chunk_size.x > 0 && chunk_size.y > 0,
+            "The chunks of a tilemap can't be empty"
+        );
+        self.chunk_size = chunk_size;
+        self.reset_chunks();
+        self
+    }
+
+    /// The number of tiles of the tilemap, in each dimension
+    #[inline]
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    /// The size of a tile, in pixels
+    #[inline]
+    pub fn tile_size(&self) -> Vec2 {
+        self.tile_size
+    }
+
+    /// The number of tiles of a chunk, in each dimension
+    #[inline]
+    pub fn chunk_size(&self) -> UVec2 {
+        self.chunk_size
+    }
+
+    /// The number of chunks of the tilemap, in each dimension
+    pub fn chunk_count(&self) -> UVec2 {
+        (self.size + self.chunk_size - UVec2::ONE) / self.chunk_size
+    }
+
+    /// Returns the tile at `position`, or [`None`] if there is no tile or `position` is outside the
+    /// tilemap.
+    pub fn get(&self, position: UVec2) -> Option<&Tile> {
+        let index = self.tile_index(position)?;
+        self.tiles[index].as_ref()
+    }
+
+    /// Returns the tile at `position`, or [`None`] if there is no tile or `position` is outside the
+    /// tilemap.
+    ///
+    /// The chunk of the tile is updated on the GPU, even if the tile isn't modified.
+    pub fn get_mut(&mut self, position: UVec2) -> Option<&mut Tile> {
+        let index = self.tile_index(position)?;
+        if self.tiles[index].is_some() {
+            self.mark_changed(position);
+        }
+        self.tiles[index].as_mut()
+    }
+
+    /// Sets the tile at `position`, or removes it with [`None`], and returns the previous tile.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is outside the tilemap.
+    pub fn set(&mut self, position: UVec2, tile: impl Into<Option<Tile>>) -> Option<Tile> {
+        let index = self.tile_index(position).unwrap_or_else(|| {
+            panic!(
+                "The tile position {} is outside the tilemap of size {}",
+                position, self.size
+            )
+        });
+        self.mark_changed(position);
+        std::mem::replace(&mut self.tiles[index], tile.into())
+    }
+
+    /// Sets all the tiles of the tilemap to `tile`, or removes them with [`None`].
+    pub fn fill(&mut self, tile: impl Into<Option<Tile>>) {
+        let tile = tile.into();
+        self.tiles.iter_mut().for_each(|t| *t = tile);
+        self.reset_chunks();
+    }
+
+    /// Returns an iterator over the tiles of the tilemap, with their position.
+    pub fn iter(&self) -> impl Iterator<Item = (UVec2, &Tile)> {
+        let width = self.size.x;
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, tile)| {
+                let position = UVec2::new(index as u32 % width, index as u32 / width);
+                tile.as_ref().map(|tile| (position, tile))
+            })
+    }
+
+    /// Returns the center of the tile at `position`, relative to the tilemap's [`Transform`].
+    pub fn tile_center(&self, position: UVec2) -> Vec2 {
+        (position.as_vec2() + Vec2::splat(0.5)) * self.tile_size
+    }
+
+    /// Returns the position of the tile containing `point`, relative to the tilemap's
+    /// [`Transform`], or [`None`] if `point` is outside the tilemap.
+    pub fn tile_at(&self, point: Vec2) -> Option<UVec2> {
+        let position = (point / self.tile_size).floor();
+        if position.x < 0.0 || position.y < 0.0 {
+            return None;
+        }
+        let position = position.as_uvec2();
+        if position.x < self.size.x && position.y < self.size.y {
+            Some(position)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the tiles of the chunk at `chunk`, with their position.
+    pub fn chunk_tiles(&self, chunk: UVec2) -> impl Iterator<Item = (UVec2, &Tile)> {
+        let min = chunk * self.chunk_size;
+        let max = (min + self.chunk_size).min(self.size);
+        (min.y..max.y).flat_map(move |y| {
+            (min.x..max.x).filter_map(move |x| {
+                let position = UVec2::new(x, y);
+                self.tiles[(y * self.size.x + x) as usize]
+                    .as_ref()
+                    .map(|tile| (position, tile))
+            })
+        })
+    }
+
+    /// Returns the bounds of the chunk at `chunk`, relative to the tilemap's [`Transform`].
+    pub fn chunk_aabb(&self, chunk: UVec2) -> Aabb {
+        let min = chunk * self.chunk_size;
+        let max = (min + self.chunk_size).min(self.size);
+        Aabb::from_min_max(
+            (min.as_vec2() * self.tile_size).extend(0.0),
+            (max.as_vec2() * self.tile_size).extend(0.0),
+        )
+    }
+
+    /// A value changed when any tile of the tilemap changes
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// A value changed when any tile of the chunk at `chunk` changes
+    pub fn chunk_generation(&self, chunk: UVec2) -> u64 {
+        self.chunk_generations[(chunk.y * self.chunk_count().x + chunk.x) as usize]
+    }
+
+    fn tile_index(&self, position: UVec2) -> Option<usize> {
+        (position.x < self.size.x && position.y < self.size.y)
+            .then(|| (position.y * self.size.x + position.x) as usize)
+    }
+
+    fn mark_changed(&mut self, position: UVec2) {
+        let chunk = position / self.chunk_size;
+        let chunk_index = (chunk.y * self.chunk_count().x + chunk.x) as usize;
+        self.generation = next_generation();
+        self.chunk_generations[chunk_index] = self.generation;
+    }
+
+    /// Gives a new generation to all the chunks
+    fn reset_chunks(&mut self) {
+        let chunk_count = self.chunk_count();
+        self.generation = next_generation();
+        self.chunk_generations = vec![self.generation; (chunk_count.x * chunk_count.y) as usize];
+    }
+}
+
+/// A Bundle of components for drawing a [`Tilemap`] with the images of a [`TextureAtlas`]
+#[derive(Bundle, Clone, Default)]
+pub struct TilemapBundle {
+    pub tilemap: Tilemap,
+    /// A handle to the texture atlas that holds the tile images
+    pub texture_atlas: Handle<TextureAtlas>,
+    /// The position of the bottom left corner of the tilemap
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    /// User indication of whether an entity is visible
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+}
+
+/// System computing the [`Aabb`] of the [`Tilemap`]s which don't have one yet or which changed,
+/// for them to be frustum culled by [`check_visibility`](bevy_render::view::check_visibility).
+pub fn calculate_tilemap_bounds(
+    mut commands: Commands,
+    mut tilemaps: Query<
+        (Entity, &Tilemap, Option<&mut Aabb>),
+        (
+            Or<(Without<Aabb>, Changed<Tilemap>)>,
+            Without<NoFrustumCulling>,
+        ),
+    >,
+) {
+    for (entity, tilemap, aabb) in tilemaps.iter_mut() {
+        let max = tilemap.size.as_vec2() * tilemap.tile_size;
+        let bounds = Aabb::from_min_max(Vec2::ZERO.extend(0.0), max.extend(0.0));
+        match aabb {
+            Some(mut aabb) => *aabb = bounds,
+            None => {
+                commands.entity(entity).insert(bounds);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Vec3;
+
+    fn tilemap() -> Tilemap {
+        Tilemap::new(UVec2::new(5, 3), Vec2::new(16.0, 8.0)).with_chunk_size(UVec2::new(2, 2))
+    }
+
+    #[test]
+    fn chunk_count_rounds_up() {
+        assert_eq!(tilemap().chunk_count(), UVec2::new(3, 2));
+        let tilemap = Tilemap::new(UVec2::new(64, 33), Vec2::ONE);
+        assert_eq!(tilemap.chunk_count(), UVec2::new(2, 2));
+        assert_eq!(Tilemap::default().chunk_count(), UVec2::ZERO);
+    }
+
+    #[test]
+    fn tile_at_point() {
+        let tilemap = tilemap();
+        assert_eq!(tilemap.tile_at(Vec2::ZERO), Some(UVec2::ZERO));
+        assert_eq!(
+            tilemap.tile_at(Vec2::new(17.0, 7.9)),
+            Some(UVec2::new(1, 0))
+        );
+        assert_eq!(
+            tilemap.tile_at(Vec2::new(79.9, 23.9)),
+            Some(UVec2::new(4, 2))
+        );
+        assert_eq!(
+            tilemap.tile_at(tilemap.tile_center(UVec2::new(3, 1))),
+            Some(UVec2::new(3, 1))
+        );
+
+        // Outside the tilemap
+        assert_eq!(tilemap.tile_at(Vec2::new(-0.1, 4.0)), None);
+        assert_eq!(tilemap.tile_at(Vec2::new(4.0, -0.1)), None);
+        assert_eq!(tilemap.tile_at(Vec2::new(80.0, 4.0)), None);
+        assert_eq!(tilemap.tile_at(Vec2::new(4.0, 24.0)), None);
+    }
+
+    #[test]
+    fn chunk_tiles_and_bounds() {
+        let mut tilemap = tilemap();
+        tilemap.set(UVec2::new(1, 1), Tile::new(1));
+        tilemap.set(UVec2::new(2, 0), Tile::new(2));
+        tilemap.set(UVec2::new(4, 2), Tile::new(3));
+
+        let indices = |chunk| {
+            tilemap
+                .chunk_tiles(chunk)
+                .map(|(position, tile)| (position, tile.index))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(indices(UVec2::new(0, 0)), vec![(UVec2::new(1, 1), 1)]);
+        assert_eq!(indices(UVec2::new(1, 0)), vec![(UVec2::new(2, 0), 2)]);
+        assert_eq!(indices(UVec2::new(2, 0)), vec![]);
+        // The last chunks are cut by the edges of the tilemap
+        assert_eq!(indices(UVec2::new(2, 1)), vec![(UVec2::new(4, 2), 3)]);
+
+        let aabb = tilemap.chunk_aabb(UVec2::new(2, 1));
+        assert_eq!(Vec3::from(aabb.min()), Vec3::new(64.0, 16.0, 0.0));
+        assert_eq!(Vec3::from(aabb.max()), Vec3::new(80.0, 24.0, 0.0));
+    }
+
+    #[test]
+    fn only_the_changed_chunks_get_a_new_generation() {
+        let mut tilemap = tilemap();
+        let generation = tilemap.generation();
+        let chunk_generation = |tilemap: &Tilemap, x, y| tilemap.chunk_generation(UVec2::new(x, y));
+        let other_chunk = chunk_generation(&tilemap, 0, 0);
+
+        tilemap.set(UVec2::new(3, 2), Tile::new(1));
+        assert_ne!(tilemap.generation(), generation);
+        assert_eq!(chunk_generation(&tilemap, 1, 1), tilemap.generation());
+        assert_eq!(chunk_generation(&tilemap, 0, 0), other_chunk);
+
+        // Getting a missing tile doesn't change anything
+        let generation = tilemap.generation();
+        assert!(tilemap.get_mut(UVec2::new(0, 0)).is_none());
+        assert_eq!(tilemap.generation(), generation);
+        tilemap.get_mut(UVec2::new(3, 2)).unwrap().color = Color::RED;
+        assert_ne!(tilemap.generation(), generation);
+        assert_eq!(chunk_generation(&tilemap, 0, 0), other_chunk);
+
+        // Filling the tilemap changes all the chunks
+        tilemap.fill(Tile::new(2));
+        for (x, y) in [(0, 0), (1, 1), (2, 0)] {
+            assert_eq!(chunk_generation(&tilemap, x, y), tilemap.generation());
+        }
+        assert_ne!(tilemap.generation(), generation);
+    }
+
+    #[test]
+    #[should_panic]
+    fn setting_a_tile_outside_panics() {
+        tilemap().set(UVec2::new(5, 0), Tile::new(0));
+    }
+}
@@ -0,0 +1,709 @@
+use crate::{
+    Mesh2dPipeline, Mesh2dPipelineKey, SetMesh2dViewBindGroup, SpriteAssetEvents, TextureAtlas,
+    TileRotation, Tilemap, TILEMAP_SHADER_HANDLE,
+};
+use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
+use bevy_core_pipeline::core_2d::Transparent2d;
+use bevy_ecs::{
+    prelude::*,
+    system::{lifetimeless::*, SystemParamItem},
+};
+use bevy_math::{Mat4, UVec2, Vec2};
+use bevy_render::{
+    extract_component::{ComponentUniforms, DynamicUniformIndex},
+    primitives::{Aabb, Frustum},
+    render_asset::RenderAssets,
+    render_phase::{
+        DrawFunctions, EntityRenderCommand, RenderCommandResult, RenderPhase, SetItemPipeline,
+        TrackedRenderPass,
+    },
+    render_resource::*,
+    renderer::{RenderDevice, RenderQueue},
+    texture::{BevyDefault, Image},
+    view::{ComputedVisibility, ExtractedView, Msaa, VisibleEntities},
+    Extract,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::{FloatOrd, HashMap, HashSet};
+use bytemuck::{Pod, Zeroable};
+
+// NOTE: These must match the bit flags in bevy_sprite/src/tilemap/tilemap.wgsl!
+bitflags::bitflags! {
+    #[repr(transparent)]
+    struct TileFlags: u32 {
+        const FLIP_X                     = (1 << 0);
+        const FLIP_Y                     = (1 << 1);
+        const ROTATION_RESERVED_BITS     = TileFlags::ROTATION_MASK_BITS << TileFlags::ROTATION_SHIFT_BITS;
+    }
+}
+
+impl TileFlags {
+    const ROTATION_MASK_BITS: u32 = 0b11;
+    const ROTATION_SHIFT_BITS: u32 = 2;
+
+    fn from_rotation(rotation: TileRotation) -> Self {
+        let quarter_turns = match rotation {
+            TileRotation::None => 0,
+            TileRotation::Rotate90 => 1,
+            TileRotation::Rotate180 => 2,
+            TileRotation::Rotate270 => 3,
+        };
+        TileFlags::from_bits(quarter_turns << Self::ROTATION_SHIFT_BITS).unwrap()
+    }
+}
+
+/// The instance data of a tile
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct TileInstance {
+    /// The position of the tile in the tilemap
+    position: [f32; 2],
+    uv_min: [f32; 2],
+    uv_max: [f32; 2],
+    color: [f32; 4],
+    flags: u32,
+}
+
+/// The transform of a [`Tilemap`] and the size of its tiles
+#[derive(Component, ShaderType, Clone)]
+pub struct TilemapUniform {
+    pub transform: Mat4,
+    pub tile_size: Vec2,
+}
+
+/// A visible [`Tilemap`], drawn with the chunks of its [`GpuTilemap`]
+#[derive(Component, Clone, Copy)]
+pub struct ExtractedTilemap {
+    /// Handle to the `Image` of the tilemap's texture atlas
+    pub image_handle_id: HandleId,
+}
+
+#[derive(Default)]
+struct GpuTilemapChunk {
+    /// The generation of the chunk whose tiles are in the buffer
+    generation: Option<u64>,
+    /// The bounds of the chunk, relative to the tilemap's transform
+    aabb: Aabb,
+    buffer: Option<Buffer>,
+    /// The number of tiles the buffer can hold
+    capacity: usize,
+    instance_count: u32,
+    /// Tiles to write in the buffer in [`RenderStage::Prepare`](bevy_render::RenderStage::Prepare)
+    pending: Option<Vec<TileInstance>>,
+}
+
+/// The tile buffers of a [`Tilemap`], one for each of its chunks.
+#[derive(Default)]
+pub struct GpuTilemap {
+    atlas: Option<HandleId>,
+    generation: Option<u64>,
+    chunks: Vec<GpuTilemapChunk>,
+    /// The indices of the chunks drawn in each view, with tiles in the view's frustum
+    visible_chunks: HashMap<Entity, Vec<usize>>,
+}
+
+impl GpuTilemap {
+    /// Returns the indices of the chunks with tiles inside the `frustum`, for a tilemap with the
+    /// `transform`.
+    fn visible_chunks(&self, frustum: &Frustum, transform: &Mat4) -> Vec<usize> {
+        self.chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, chunk)| {
+                chunk.instance_count > 0 && frustum.intersects_obb(&chunk.aabb, transform, false)
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+/// The [`GpuTilemap`]s of the render world, kept between frames: only the chunks whose tiles
+/// changed are updated.
+#[derive(Default)]
+pub struct GpuTilemaps {
+    tilemaps: HashMap<Entity, GpuTilemap>,
+}
+
+/// System extracting the visible [`Tilemap`]s, with the tiles of their chunks that changed since
+/// they were last extracted
+pub fn extract_tilemaps(
+    mut commands: Commands,
+    mut previous_len: Local<usize>,
+    mut gpu_tilemaps: ResMut<GpuTilemaps>,
+    mut atlas_events: Extract<EventReader<AssetEvent<TextureAtlas>>>,
+    texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
+    tilemaps: Extract<
+        Query<(
+            Entity,
+            &ComputedVisibility,
+            &GlobalTransform,
+            &Tilemap,
+            &Handle<TextureAtlas>,
+        )>,
+    >,
+) {
+    // The tiles of the tilemaps using a modified atlas have to be written again
+    let modified_atlases: HashSet<HandleId> = atlas_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { handle } => Some(handle.id),
+            _ => None,
+        })
+        .collect();
+    // Forget the chunks of the despawned tilemaps
+    gpu_tilemaps
+        .tilemaps
+        .retain(|entity, _| tilemaps.contains(*entity));
+
+    let mut values = Vec::with_capacity(*previous_len);
+    for (entity, computed_visibility, transform, tilemap, atlas_handle) in tilemaps.iter() {
+        if !computed_visibility.is_visible {
+            continue;
+        }
+        let atlas = match texture_atlases.get(atlas_handle) {
+            Some(atlas) => atlas,
+            None => continue,
+        };
+
+        let chunk_count = tilemap.chunk_count();
+        let gpu_tilemap = gpu_tilemaps.tilemaps.entry(entity).or_default();
+        if gpu_tilemap.atlas != Some(atlas_handle.id)
+            || modified_atlases.contains(&atlas_handle.id)
+            || gpu_tilemap.chunks.len() != (chunk_count.x * chunk_count.y) as usize
+        {
+            gpu_tilemap.atlas = Some(atlas_handle.id);
+            gpu_tilemap.generation = None;
+            gpu_tilemap.chunks.clear();
+            gpu_tilemap
+                .chunks
+                .resize_with((chunk_count.x * chunk_count.y) as usize, Default::default);
+        }
+
+        if gpu_tilemap.generation != Some(tilemap.generation()) {
+            for (chunk_index, gpu_chunk) in gpu_tilemap.chunks.iter_mut().enumerate() {
+                let chunk = UVec2::new(
+                    chunk_index as u32 % chunk_count.x,
+                    chunk_index as u32 / chunk_count.x,
+                );
+                let generation = tilemap.chunk_generation(chunk);
+                if gpu_chunk.generation == Some(generation) {
+                    continue;
+                }
+                gpu_chunk.generation = Some(generation);
+                gpu_chunk.aabb = tilemap.chunk_aabb(chunk);
+                gpu_chunk.pending = Some(chunk_instances(tilemap, chunk, atlas));
+            }
+            gpu_tilemap.generation = Some(tilemap.generation());
+        }
+
+        values.push((
+            entity,
+            (
+                ExtractedTilemap {
+                    image_handle_id: atlas.texture.id,
+                },
+                TilemapUniform {
+                    transform: transform.compute_matrix(),
+                    tile_size: tilemap.tile_size(),
+                },
+            ),
+        ));
+    }
+    *previous_len = values.len();
+    commands.insert_or_spawn_batch(values);
+}
+
+/// The instances of the tiles of the chunk at `chunk` of the `tilemap`, skipping the tiles
+/// outside of the `atlas`
+fn chunk_instances(tilemap: &Tilemap, chunk: UVec2, atlas: &TextureAtlas) -> Vec<TileInstance> {
+    tilemap
+        .chunk_tiles(chunk)
+        .filter_map(|(position, tile)| {
+            let rect = atlas.textures.get(tile.index)?;
+            let mut flags = TileFlags::from_rotation(tile.rotation);
+            flags.set(TileFlags::FLIP_X, tile.flip_x);
+            flags.set(TileFlags::FLIP_Y, tile.flip_y);
+            Some(TileInstance {
+                position: position.as_vec2().into(),
+                uv_min: (rect.min / atlas.size).into(),
+                uv_max: (rect.max / atlas.size).into(),
+                color: tile.color.as_linear_rgba_f32(),
+                flags: flags.bits,
+            })
+        })
+        .collect()
+}
+
+/// System writing the tiles of the chunks extracted this frame in their buffer
+pub fn prepare_tilemap_chunks(
+    mut gpu_tilemaps: ResMut<GpuTilemaps>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let chunks = gpu_tilemaps
+        .tilemaps
+        .values_mut()
+        .flat_map(|gpu_tilemap| gpu_tilemap.chunks.iter_mut());
+    for chunk in chunks {
+        let instances = match chunk.pending.take() {
+            Some(instances) => instances,
+            None => continue,
+        };
+        chunk.instance_count = instances.len() as u32;
+        if instances.is_empty() {
+            continue;
+        }
+        match &chunk.buffer {
+            Some(buffer) if chunk.capacity >= instances.len() => {
+                render_queue.write_buffer(buffer, 0, bytemuck::cast_slice(&instances));
+            }
+            _ => {
+                chunk.buffer = Some(
+                    render_device.create_buffer_with_data(&BufferInitDescriptor {
+                        label: Some("tilemap_chunk_buffer"),
+                        contents: bytemuck::cast_slice(&instances),
+                        usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                    }),
+                );
+                chunk.capacity = instances.len();
+            }
+        }
+    }
+}
+
+pub struct TilemapPipeline {
+    pub view_layout: BindGroupLayout,
+    pub tilemap_layout: BindGroupLayout,
+    pub texture_layout: BindGroupLayout,
+}
+
+impl FromWorld for TilemapPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let view_layout = world.resource::<Mesh2dPipeline>().view_layout.clone();
+        let render_device = world.resource::<RenderDevice>();
+
+        let tilemap_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::VERTEX,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(TilemapUniform::min_size()),
+                },
+                count: None,
+            }],
+            label: Some("tilemap_layout"),
+        });
+
+        let texture_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+            label: Some("tilemap_texture_layout"),
+        });
+
+        TilemapPipeline {
+            view_layout,
+            tilemap_layout,
+            texture_layout,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for TilemapPipeline {
+    type Key = Mesh2dPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let instance_layout = VertexBufferLayout::from_vertex_formats(
+            VertexStepMode::Instance,
+            vec![
+                // position
+                VertexFormat::Float32x2,
+                // uv_min
+                VertexFormat::Float32x2,
+                // uv_max
+                VertexFormat::Float32x2,
+                // color
+                VertexFormat::Float32x4,
+                // flags
+                VertexFormat::Uint32,
+            ],
+        );
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: TILEMAP_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: Vec::new(),
+                buffers: vec![instance_layout],
+            },
+            fragment: Some(FragmentState {
+                shader: TILEMAP_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![ColorTargetState {
+                    format: TextureFormat::bevy_default(),
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                }],
+            }),
+            layout: Some(vec![
+                self.view_layout.clone(),
+                self.tilemap_layout.clone(),
+                self.texture_layout.clone(),
+            ]),
+            primitive: PrimitiveState {
+                front_face: FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: PolygonMode::Fill,
+                conservative: false,
+                topology: PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+            },
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("tilemap_pipeline".into()),
+        }
+    }
+}
+
+pub struct TilemapBindGroup {
+    pub value: BindGroup,
+}
+
+pub fn queue_tilemap_bind_group(
+    mut commands: Commands,
+    tilemap_pipeline: Res<TilemapPipeline>,
+    render_device: Res<RenderDevice>,
+    tilemap_uniforms: Res<ComponentUniforms<TilemapUniform>>,
+) {
+    if let Some(binding) = tilemap_uniforms.uniforms().binding() {
+        commands.insert_resource(TilemapBindGroup {
+            value: render_device.create_bind_group(&BindGroupDescriptor {
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: binding,
+                }],
+                label: Some("tilemap_bind_group"),
+                layout: &tilemap_pipeline.tilemap_layout,
+            }),
+        });
+    }
+}
+
+#[derive(Default)]
+pub struct TilemapImageBindGroups {
+    values: HashMap<Handle<Image>, BindGroup>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_tilemaps(
+    draw_functions: Res<DrawFunctions<Transparent2d>>,
+    render_device: Res<RenderDevice>,
+    tilemap_pipeline: Res<TilemapPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<TilemapPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    mut image_bind_groups: ResMut<TilemapImageBindGroups>,
+    gpu_images: Res<RenderAssets<Image>>,
+    msaa: Res<Msaa>,
+    events: Res<SpriteAssetEvents>,
+    mut gpu_tilemaps: ResMut<GpuTilemaps>,
+    tilemaps: Query<(&ExtractedTilemap, &TilemapUniform)>,
+    mut views: Query<(
+        Entity,
+        &ExtractedView,
+        &VisibleEntities,
+        &mut RenderPhase<Transparent2d>,
+    )>,
+) {
+    // If an image has changed, the GpuImage has (probably) changed
+    for event in &events.images {
+        match event {
+            AssetEvent::Created { .. } => None,
+            AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
+                image_bind_groups.values.remove(handle)
+            }
+        };
+    }
+
+    let draw_tilemap = draw_functions.read().get_id::<DrawTilemap>().unwrap();
+    let key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples);
+    let pipeline = pipelines.specialize(&mut pipeline_cache, &tilemap_pipeline, key);
+
+    for gpu_tilemap in gpu_tilemaps.tilemaps.values_mut() {
+        gpu_tilemap.visible_chunks.clear();
+    }
+
+    for (view_entity, view, visible_entities, mut transparent_phase) in views.iter_mut() {
+        let view_projection = view.projection * view.transform.compute_matrix().inverse();
+        // The far plane is not used to cull the chunks
+        let frustum = Frustum::from_view_projection(
+            &view_projection,
+            &view.transform.translation,
+            &view.transform.back(),
+            0.0,
+        );
+
+        for visible_entity in visible_entities.iter() {
+            let (tilemap, uniform) = match tilemaps.get(*visible_entity) {
+                Ok(tilemap) => tilemap,
+                Err(_) => continue,
+            };
+            let gpu_tilemap = match gpu_tilemaps.tilemaps.get_mut(visible_entity) {
+                Some(gpu_tilemap) => gpu_tilemap,
+                None => continue,
+            };
+            // The tilemap is visible, but maybe only a few of its chunks
+            let visible_chunks = gpu_tilemap.visible_chunks(&frustum, &uniform.transform);
+            if visible_chunks.is_empty() {
+                continue;
+            }
+            let image_handle = Handle::weak(tilemap.image_handle_id);
+            let gpu_image = match gpu_images.get(&image_handle) {
+                Some(gpu_image) => gpu_image,
+                // Skip the tilemap if the texture is not ready
+                None => continue,
+            };
+            image_bind_groups
+                .values
+                .entry(image_handle)
+                .or_insert_with(|| {
+                    render_device.create_bind_group(&BindGroupDescriptor {
+                        entries: &[
+                            BindGroupEntry {
+                                binding: 0,
+                                resource: BindingResource::TextureView(&gpu_image.texture_view),
+                            },
+                            BindGroupEntry {
+                                binding: 1,
+                                resource: BindingResource::Sampler(&gpu_image.sampler),
+                            },
+                        ],
+                        label: Some("tilemap_texture_bind_group"),
+                        layout: &tilemap_pipeline.texture_layout,
+                    })
+                });
+            gpu_tilemap
+                .visible_chunks
+                .insert(view_entity, visible_chunks);
+
+            transparent_phase.add(Transparent2d {
+                entity: *visible_entity,
+                draw_function: draw_tilemap,
+                pipeline,
+                // This item is sorted by depth with other phase items
+                sort_key: FloatOrd(uniform.transform.w_axis.z),
+                // This item is not batched
+                batch_range: None,
+            });
+        }
+    }
+}
+
+pub type DrawTilemap = (
+    SetItemPipeline,
+    SetMesh2dViewBindGroup<0>,
+    SetTilemapBindGroup<1>,
+    SetTilemapTextureBindGroup<2>,
+    DrawTilemapChunks,
+);
+
+pub struct SetTilemapBindGroup<const I: usize>;
+impl<const I: usize> EntityRenderCommand for SetTilemapBindGroup<I> {
+    type Param = (
+        SRes<TilemapBindGroup>,
+        SQuery<Read<DynamicUniformIndex<TilemapUniform>>>,
+    );
+    #[inline]
+    fn render<'w>(
+        _view: Entity,
+        item: Entity,
+        (tilemap_bind_group, tilemap_query): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let tilemap_index = tilemap_query.get(item).unwrap();
+        pass.set_bind_group(
+            I,
+            &tilemap_bind_group.into_inner().value,
+            &[tilemap_index.index()],
+        );
+        RenderCommandResult::Success
+    }
+}
+
+pub struct SetTilemapTextureBindGroup<const I: usize>;
+impl<const I: usize> EntityRenderCommand for SetTilemapTextureBindGroup<I> {
+    type Param = (SRes<TilemapImageBindGroups>, SQuery<Read<ExtractedTilemap>>);
+    #[inline]
+    fn render<'w>(
+        _view: Entity,
+        item: Entity,
+        (image_bind_groups, tilemap_query): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let tilemap = tilemap_query.get(item).unwrap();
+        match image_bind_groups
+            .into_inner()
+            .values
+            .get(&Handle::weak(tilemap.image_handle_id))
+        {
+            Some(bind_group) => {
+                pass.set_bind_group(I, bind_group, &[]);
+                RenderCommandResult::Success
+            }
+            None => RenderCommandResult::Failure,
+        }
+    }
+}
+
+/// Draws the tiles of each chunk of a [`Tilemap`] visible in the view as instances of a quad
+pub struct DrawTilemapChunks;
+impl EntityRenderCommand for DrawTilemapChunks {
+    type Param = SRes<GpuTilemaps>;
+    #[inline]
+    fn render<'w>(
+        view: Entity,
+        item: Entity,
+        gpu_tilemaps: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let gpu_tilemap = match gpu_tilemaps.into_inner().tilemaps.get(&item) {
+            Some(gpu_tilemap) => gpu_tilemap,
+            None => return RenderCommandResult::Failure,
+        };
+        let visible_chunks = match gpu_tilemap.visible_chunks.get(&view) {
+            Some(visible_chunks) => visible_chunks,
+            None => return RenderCommandResult::Success,
+        };
+        for chunk in visible_chunks
+            .iter()
+            .map(|index| &gpu_tilemap.chunks[*index])
+        {
+            if let (Some(buffer), 1..) = (&chunk.buffer, chunk.instance_count) {
+                pass.set_vertex_buffer(0, buffer.slice(..));
+                pass.draw(0..6, 0..chunk.instance_count);
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Rect, Tile};
+    use bevy_math::Vec3;
+    use bevy_render::color::Color;
+    use bevy_transform::components::Transform;
+
+    #[test]
+    fn chunk_instances_are_in_the_atlas() {
+        let mut atlas = TextureAtlas::new_empty(Default::default(), Vec2::new(32.0, 16.0));
+        atlas.add_texture(Rect {
+            min: Vec2::ZERO,
+            max: Vec2::new(16.0, 16.0),
+        });
+        atlas.add_texture(Rect {
+            min: Vec2::new(16.0, 0.0),
+            max: Vec2::new(32.0, 8.0),
+        });
+
+        let mut tilemap =
+            Tilemap::new(UVec2::new(4, 4), Vec2::ONE).with_chunk_size(UVec2::new(2, 2));
+        tilemap.set(
+            UVec2::new(3, 2),
+            Tile {
+                index: 1,
+                color: Color::RED,
+                flip_y: true,
+                rotation: TileRotation::Rotate270,
+                ..Default::default()
+            },
+        );
+        tilemap.set(UVec2::new(2, 3), Tile::new(0));
+        // Outside of the atlas
+        tilemap.set(UVec2::new(3, 3), Tile::new(2));
+        tilemap.set(UVec2::new(0, 0), Tile::new(0));
+
+        let instances = chunk_instances(&tilemap, UVec2::new(1, 1), &atlas);
+        assert_eq!(instances.len(), 2);
+        let [first, second] = [instances[0], instances[1]];
+        assert_eq!(first.position, [3.0, 2.0]);
+        assert_eq!(first.uv_min, [0.5, 0.0]);
+        assert_eq!(first.uv_max, [1.0, 0.5]);
+        assert_eq!(first.color, Color::RED.as_linear_rgba_f32());
+        assert_eq!(
+            first.flags,
+            (TileFlags::FLIP_Y | TileFlags::from_rotation(TileRotation::Rotate270)).bits
+        );
+        assert_eq!(second.position, [2.0, 3.0]);
+        assert_eq!(second.uv_min, [0.0, 0.0]);
+        assert_eq!(second.uv_max, [0.5, 1.0]);
+        assert_eq!(second.flags, 0);
+    }
+
+    #[test]
+    fn only_the_chunks_in_the_frustum_are_visible() {
+        let mut tilemap =
+            Tilemap::new(UVec2::new(8, 4), Vec2::splat(10.0)).with_chunk_size(UVec2::new(2, 2));
+        tilemap.fill(Tile::new(0));
+        // The chunk at (3, 1) is empty
+        for position in [(6, 2), (7, 2), (6, 3), (7, 3)] {
+            tilemap.set(UVec2::from(position), None);
+        }
+        let chunk_count = tilemap.chunk_count();
+        let gpu_tilemap = GpuTilemap {
+            chunks: (0..chunk_count.y)
+                .flat_map(|y| (0..chunk_count.x).map(move |x| UVec2::new(x, y)))
+                .map(|chunk| GpuTilemapChunk {
+                    aabb: tilemap.chunk_aabb(chunk),
+                    instance_count: tilemap.chunk_tiles(chunk).count() as u32,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        // A view of the pixels from (0, 0) to (45, 25)
+        let projection = Mat4::orthographic_rh(-22.5, 22.5, -12.5, 12.5, 0.0, 1000.0);
+        let view = Transform::from_xyz(22.5, 12.5, 100.0).compute_matrix();
+        let frustum = Frustum::from_view_projection(
+            &(projection * view.inverse()),
+            &Vec3::new(22.5, 12.5, 100.0),
+            &Vec3::Z,
+            1000.0,
+        );
+        assert_eq!(
+            gpu_tilemap.visible_chunks(&frustum, &Mat4::IDENTITY),
+            vec![0, 1, 2, 4, 5, 6]
+        );
+
+        // Moving the tilemap to the left, only its right chunks are visible
+        let transform = Mat4::from_translation(Vec3::new(-40.0, 0.0, 0.0));
+        assert_eq!(
+            gpu_tilemap.visible_chunks(&frustum, &transform),
+            vec![2, 3, 6]
+        );
+    }
+}
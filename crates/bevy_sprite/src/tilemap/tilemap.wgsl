@@ -0,0 +1,76 @@
+#import bevy_sprite::mesh2d_view_bindings
+
+struct Tilemap {
+    transform: mat4x4<f32>;
+    tile_size: vec2<f32>;
+};
+[[group(1), binding(0)]]
+var<uniform> tilemap: Tilemap;
+
+// NOTE: Bit flags of the tiles, they must match the flags in bevy_sprite/src/tilemap/render.rs!
+let TILE_FLAGS_FLIP_X_BIT: u32 = 1u;
+let TILE_FLAGS_FLIP_Y_BIT: u32 = 2u;
+let TILE_FLAGS_ROTATION_MASK_BITS: u32 = 3u;
+let TILE_FLAGS_ROTATION_SHIFT_BITS: u32 = 2u;
+
+struct Tile {
+    [[location(0)]] position: vec2<f32>;
+    [[location(1)]] uv_min: vec2<f32>;
+    [[location(2)]] uv_max: vec2<f32>;
+    [[location(3)]] color: vec4<f32>;
+    [[location(4)]] flags: u32;
+};
+
+struct VertexOutput {
+    [[builtin(position)]] position: vec4<f32>;
+    [[location(0)]] uv: vec2<f32>;
+    [[location(1)]] color: vec4<f32>;
+};
+
+[[stage(vertex)]]
+fn vertex(
+    [[builtin(vertex_index)]] vertex_index: u32,
+    tile: Tile,
+) -> VertexOutput {
+    // The corners of the two triangles of the tile's quad
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+
+    // The point of the tile's image shown at this corner, undoing the rotation then the flips
+    var point = corner;
+    let quarter_turns = (tile.flags >> TILE_FLAGS_ROTATION_SHIFT_BITS) & TILE_FLAGS_ROTATION_MASK_BITS;
+    for (var i = 0u; i < quarter_turns; i = i + 1u) {
+        point = vec2<f32>(point.y, 1.0 - point.x);
+    }
+    if ((tile.flags & TILE_FLAGS_FLIP_X_BIT) != 0u) {
+        point.x = 1.0 - point.x;
+    }
+    if ((tile.flags & TILE_FLAGS_FLIP_Y_BIT) != 0u) {
+        point.y = 1.0 - point.y;
+    }
+
+    var out: VertexOutput;
+    // Textures are sampled from the top left corner
+    out.uv = mix(tile.uv_min, tile.uv_max, vec2<f32>(point.x, 1.0 - point.y));
+    out.color = tile.color;
+    let world_position = tilemap.transform * vec4<f32>((tile.position + corner) * tilemap.tile_size, 0.0, 1.0);
+    out.position = view.view_proj * world_position;
+    return out;
+}
+
+[[group(2), binding(0)]]
+var tile_texture: texture_2d<f32>;
+[[group(2), binding(1)]]
+var tile_sampler: sampler;
+
+[[stage(fragment)]]
+fn fragment(in: VertexOutput) -> [[location(0)]] vec4<f32> {
+    return in.color * textureSample(tile_texture, tile_sampler, in.uv);
+}
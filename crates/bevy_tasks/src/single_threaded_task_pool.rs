@@ -4,6 +4,8 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use crate::{TaskContext, TaskHandle};
+
 /// Used to create a TaskPool
 #[derive(Debug, Default, Clone)]
 pub struct TaskPoolBuilder {}
@@ -113,6 +115,21 @@ impl TaskPool {
     {
         self.spawn(future)
     }
+
+    /// Spawns a static future built by `f` on the JS event loop, returning a [`TaskHandle`] to
+    /// poll its output, read its progress and cancel it from systems. `f` is given the
+    /// [`TaskContext`] the future uses to check if it was cancelled and to report its progress.
+    ///
+    /// Dropping the [`TaskHandle`] cancels the task.
+    pub fn spawn_cancellable<T, Fut>(&self, f: impl FnOnce(TaskContext) -> Fut) -> TaskHandle<T>
+    where
+        T: 'static,
+        Fut: Future<Output = T> + 'static,
+    {
+        let (handle, context) = TaskHandle::new();
+        self.spawn(handle.wrap(f(context))).detach();
+        handle
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,144 @@
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// The state shared by a [`TaskHandle`] and the [`TaskContext`] of its task.
+#[derive(Debug, Default)]
+struct TaskControl {
+    cancelled: AtomicBool,
+    finished: AtomicBool,
+    /// The bits of the `f32` progress
+    progress: AtomicU32,
+}
+
+/// A handle to a task spawned with `TaskPool::spawn_cancellable`, to manage a long-running
+/// background job from systems.
+///
+/// The handle can be polled for the output of the task with [`TaskHandle::take_output`], without
+/// blocking. The task can report its progress, read with [`TaskHandle::progress`], and can be
+/// asked to stop with [`TaskHandle::cancel`]: cancellation is cooperative, the task has to check
+/// [`TaskContext::is_cancelled`] and return early.
+///
+/// Dropping the handle cancels the task too. On platforms with threads, the task is also dropped
+/// at its next `.await` point, like a dropped [`Task`](crate::Task).
+#[derive(Debug)]
+pub struct TaskHandle<T> {
+    control: Arc<TaskControl>,
+    output: Arc<Mutex<Option<T>>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    _task: Option<crate::Task<()>>,
+}
+
+impl<T> TaskHandle<T> {
+    /// Creates a handle and the [`TaskContext`] given to its task.
+    pub(crate) fn new() -> (Self, TaskContext) {
+        let control = Arc::new(TaskControl::default());
+        let handle = Self {
+            control: control.clone(),
+            output: Arc::new(Mutex::new(None)),
+            #[cfg(not(target_arch = "wasm32"))]
+            _task: None,
+        };
+        (handle, TaskContext { control })
+    }
+
+    /// Wraps the future of the task, to store its output in the handle. The returned future must
+    /// be spawned on a task pool.
+    pub(crate) fn wrap(&self, future: impl Future<Output = T>) -> impl Future<Output = ()> {
+        let control = self.control.clone();
+        let output = self.output.clone();
+        async move {
+            let value = future.await;
+            *output.lock().unwrap() = Some(value);
+            control.finished.store(true, Ordering::Release);
+        }
+    }
+
+    /// Keeps the spawned task, to drop it with the handle.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn with_task(mut self, task: crate::Task<()>) -> Self {
+        self._task = Some(task);
+        self
+    }
+
+    /// Asks the task to stop. The task keeps running until it checks
+    /// [`TaskContext::is_cancelled`], and its output is still available once it returns.
+    pub fn cancel(&self) {
+        self.control.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Returns `true` if [`TaskHandle::cancel`] was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.control.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` once the task returned, even if its output was already taken.
+    pub fn is_finished(&self) -> bool {
+        self.control.finished.load(Ordering::Acquire)
+    }
+
+    /// The last progress reported by the task with [`TaskContext::set_progress`], 0 until then.
+    pub fn progress(&self) -> f32 {
+        f32::from_bits(self.control.progress.load(Ordering::Relaxed))
+    }
+
+    /// Takes the output of the task if it returned, without blocking. The output is only returned
+    /// once, [`None`] is returned afterwards.
+    pub fn take_output(&mut self) -> Option<T> {
+        if !self.is_finished() {
+            return None;
+        }
+        self.output.lock().unwrap().take()
+    }
+}
+
+impl<T> Drop for TaskHandle<T> {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Given to a task spawned with `TaskPool::spawn_cancellable`, to check if its [`TaskHandle`]
+/// cancelled it and to report its progress.
+///
+/// ```
+/// # use bevy_tasks::TaskPool;
+/// let pool = TaskPool::new();
+/// let mut handle = pool.spawn_cancellable(|context| async move {
+///     let mut sum = 0u64;
+///     for i in 0..1000 {
+///         if context.is_cancelled() {
+///             return None;
+///         }
+///         sum += i;
+///         context.set_progress(i as f32 / 1000.0);
+///     }
+///     Some(sum)
+/// });
+/// # while !handle.is_finished() {}
+/// # assert_eq!(handle.take_output(), Some(Some(499500)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TaskContext {
+    control: Arc<TaskControl>,
+}
+
+impl TaskContext {
+    /// Returns `true` if the [`TaskHandle`] of the task was cancelled or dropped. The task should
+    /// stop and return as soon as possible.
+    pub fn is_cancelled(&self) -> bool {
+        self.control.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Reports the progress of the task, read with [`TaskHandle::progress`]. It is usually
+    /// between 0 and 1.
+    pub fn set_progress(&self, progress: f32) {
+        self.control
+            .progress
+            .store(progress.to_bits(), Ordering::Relaxed);
+    }
+}
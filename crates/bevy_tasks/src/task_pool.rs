@@ -8,7 +8,7 @@ use std::{
 
 use futures_lite::{future, pin};
 
-use crate::Task;
+use crate::{Task, TaskContext, TaskHandle};
 
 /// Used to create a [`TaskPool`]
 #[derive(Debug, Default, Clone)]
@@ -228,6 +228,21 @@ impl TaskPool {
     {
         Task::new(TaskPool::LOCAL_EXECUTOR.with(|executor| executor.spawn(future)))
     }
+
+    /// Spawns a static future built by `f` onto the thread pool, returning a [`TaskHandle`] to poll
+    /// its output, read its progress and cancel it from systems. `f` is given the [`TaskContext`]
+    /// the future uses to check if it was cancelled and to report its progress.
+    ///
+    /// Dropping the [`TaskHandle`] cancels the task.
+    pub fn spawn_cancellable<T, Fut>(&self, f: impl FnOnce(TaskContext) -> Fut) -> TaskHandle<T>
+    where
+        T: Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let (handle, context) = TaskHandle::new();
+        let task = self.spawn(handle.wrap(f(context)));
+        handle.with_task(task)
+    }
 }
 
 impl Default for TaskPool {
@@ -407,4 +422,57 @@ mod tests {
         assert!(!thread_check_failed.load(Ordering::Acquire));
         assert_eq!(count.load(Ordering::Acquire), 200);
     }
+
+    #[test]
+    fn test_spawn_cancellable() {
+        let pool = TaskPool::new();
+        let mut handle = pool.spawn_cancellable(|context| async move {
+            context.set_progress(0.5);
+            while !context.is_cancelled() {
+                std::thread::yield_now();
+            }
+            42
+        });
+
+        while handle.progress() < 0.5 {
+            std::thread::yield_now();
+        }
+        assert!(!handle.is_finished());
+        assert_eq!(handle.take_output(), None);
+
+        handle.cancel();
+        while !handle.is_finished() {
+            std::thread::yield_now();
+        }
+        assert_eq!(handle.take_output(), Some(42));
+        // The output is only taken once
+        assert_eq!(handle.take_output(), None);
+        assert!(handle.is_finished());
+    }
+
+    #[test]
+    fn test_drop_task_handle_cancels() {
+        let pool = TaskPool::new();
+        let started = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let started = started.clone();
+            let stopped = stopped.clone();
+            pool.spawn_cancellable(|context| async move {
+                started.store(true, Ordering::Release);
+                while !context.is_cancelled() {
+                    std::thread::yield_now();
+                }
+                stopped.store(true, Ordering::Release);
+            })
+        };
+
+        while !started.load(Ordering::Acquire) {
+            std::thread::yield_now();
+        }
+        drop(handle);
+        while !stopped.load(Ordering::Acquire) {
+            std::thread::yield_now();
+        }
+    }
 }
@@ -0,0 +1,246 @@
+use std::ops::Range;
+
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo, Level};
+
+use crate::{TextDirection, TextSection};
+
+/// A piece of a laid-out line, in visual (left-to-right display) order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VisualRun {
+    /// The index of the section this run was taken from.
+    pub section_index: usize,
+    /// The bytes of the run in the value of its section, in logical order.
+    pub range: Range<usize>,
+    /// Whether the run is right-to-left, in which case its glyphs are displayed in reverse order.
+    pub rtl: bool,
+}
+
+/// Returns whether the base direction of the text is right-to-left.
+///
+/// With [`TextDirection::Auto`], it is the direction of the first strongly directional character
+/// of the first paragraph, or left-to-right if there is none.
+pub(crate) fn is_rtl(sections: &[TextSection], direction: TextDirection) -> bool {
+    match direction {
+        TextDirection::LeftToRight => false,
+        TextDirection::RightToLeft => true,
+        TextDirection::Auto => {
+            // Rule P2, stopping at the end of the first paragraph
+            let mut isolate_level = 0;
+            for c in sections.iter().flat_map(|section| section.value.chars()) {
+                match bidi_class(c) {
+                    BidiClass::LRI | BidiClass::RLI | BidiClass::FSI => isolate_level += 1,
+                    BidiClass::PDI if isolate_level > 0 => isolate_level -= 1,
+                    BidiClass::L if isolate_level == 0 => return false,
+                    BidiClass::R | BidiClass::AL if isolate_level == 0 => return true,
+                    BidiClass::B => return false,
+                    _ => {}
+                }
+            }
+            false
+        }
+    }
+}
+
+/// Splits each laid-out line of `sections` into runs in visual order, following the Unicode
+/// Bidirectional Algorithm.
+///
+/// `lines` are the byte ranges of the lines in the value of all the sections joined together,
+/// after wrapping, as rule L1 and the reordering of rule L2 apply to each line on its own.
+///
+/// Returns `None` if the text contains no right-to-left content, in which case the lines can be
+/// displayed in logical order.
+pub(crate) fn visual_runs(
+    sections: &[TextSection],
+    direction: TextDirection,
+    lines: &[Range<usize>],
+) -> Option<Vec<Vec<VisualRun>>> {
+    let mut text = String::new();
+    let mut section_starts = Vec::with_capacity(sections.len());
+    for section in sections {
+        section_starts.push(text.len());
+        text.push_str(&section.value);
+    }
+
+    let default_level = match direction {
+        TextDirection::Auto => None,
+        TextDirection::LeftToRight => Some(Level::ltr()),
+        TextDirection::RightToLeft => Some(Level::rtl()),
+    };
+    let bidi_info = BidiInfo::new(&text, default_level);
+    if !bidi_info.has_rtl() {
+        return None;
+    }
+
+    let lines = lines
+        .iter()
+        .map(|line| {
+            let mut runs = Vec::new();
+            let paragraph = match bidi_info
+                .paragraphs
+                .iter()
+                .find(|paragraph| paragraph.range.contains(&line.start))
+            {
+                Some(paragraph) => paragraph,
+                None => return runs,
+            };
+            // Paragraph separators end lines, and are never displayed
+            let mut line = line.start..line.end.min(paragraph.range.end);
+            while let Some(c) = text[line.clone()].chars().next_back() {
+                if !matches!(c, '\n' | '\r' | '\u{2029}') {
+                    break;
+                }
+                line.end -= c.len_utf8();
+            }
+            if line.is_empty() {
+                return runs;
+            }
+
+            let (levels, level_runs) = bidi_info.visual_runs(paragraph, line);
+            for range in level_runs {
+                let rtl = levels[range.start].is_rtl();
+                push_runs(&mut runs, &section_starts, text.len(), range, rtl);
+            }
+            runs
+        })
+        .collect();
+    Some(lines)
+}
+
+/// Pushes the part of the text in `range` as runs, split at section boundaries.
+fn push_runs(
+    runs: &mut Vec<VisualRun>,
+    section_starts: &[usize],
+    text_len: usize,
+    range: Range<usize>,
+    rtl: bool,
+) {
+    let mut pieces = Vec::new();
+    for (section_index, &section_start) in section_starts.iter().enumerate() {
+        let section_end = section_starts
+            .get(section_index + 1)
+            .copied()
+            .unwrap_or(text_len);
+        let start = range.start.max(section_start);
+        let end = range.end.min(section_end);
+        if start >= end {
+            continue;
+        }
+        pieces.push(VisualRun {
+            section_index,
+            range: start - section_start..end - section_start,
+            rtl,
+        });
+    }
+
+    // The sections making up a right-to-left run are displayed in reverse order as well
+    if rtl {
+        pieces.reverse();
+    }
+    runs.extend(pieces);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TextStyle;
+
+    fn sections(values: &[&str]) -> Vec<TextSection> {
+        values
+            .iter()
+            .map(|value| TextSection::new(*value, TextStyle::default()))
+            .collect()
+    }
+
+    fn run(section_index: usize, range: Range<usize>, rtl: bool) -> VisualRun {
+        VisualRun {
+            section_index,
+            range,
+            rtl,
+        }
+    }
+
+    #[test]
+    fn left_to_right_text_is_not_reordered() {
+        let sections = sections(&["hello ", "world"]);
+        assert_eq!(
+            visual_runs(
+                &sections,
+                TextDirection::Auto,
+                std::slice::from_ref(&(0..11))
+            ),
+            None
+        );
+        assert!(!is_rtl(&sections, TextDirection::Auto));
+        assert!(is_rtl(&sections, TextDirection::RightToLeft));
+    }
+
+    #[test]
+    fn mixed_directions() {
+        // "abc " then 3 hebrew letters of 2 bytes each, then " def"
+        let sections = sections(&["abc אבג def"]);
+        assert!(!is_rtl(&sections, TextDirection::Auto));
+        assert_eq!(
+            visual_runs(
+                &sections,
+                TextDirection::Auto,
+                std::slice::from_ref(&(0..14))
+            ),
+            Some(vec![vec![
+                run(0, 0..4, false),
+                run(0, 4..10, true),
+                run(0, 10..14, false),
+            ]])
+        );
+
+        // In a right-to-left paragraph, the left-to-right runs are displayed from right to left
+        assert_eq!(
+            visual_runs(
+                &sections,
+                TextDirection::RightToLeft,
+                std::slice::from_ref(&(0..14))
+            ),
+            Some(vec![vec![
+                run(0, 11..14, false),
+                run(0, 3..11, true),
+                run(0, 0..3, false),
+            ]])
+        );
+    }
+
+    #[test]
+    fn sections_of_a_right_to_left_run_are_reversed() {
+        let sections = sections(&["אב", "גד"]);
+        assert!(is_rtl(&sections, TextDirection::Auto));
+        assert_eq!(
+            visual_runs(
+                &sections,
+                TextDirection::Auto,
+                std::slice::from_ref(&(0..8))
+            ),
+            Some(vec![vec![run(1, 0..4, true), run(0, 0..4, true)]])
+        );
+    }
+
+    #[test]
+    fn wrapped_lines_are_reordered_separately() {
+        // wrapped after the first word: the first logical line stays on top, and each line is
+        // reversed on its own, with the number kept left-to-right
+        let sections = sections(&["אבג דהו 12"]);
+        assert_eq!(
+            visual_runs(&sections, TextDirection::Auto, &[0..7, 7..16]),
+            Some(vec![
+                vec![run(0, 0..7, true)],
+                vec![run(0, 14..16, false), run(0, 7..14, true)],
+            ])
+        );
+    }
+
+    #[test]
+    fn paragraphs_have_their_own_direction() {
+        let sections = sections(&["abc\nאבג"]);
+        assert_eq!(
+            visual_runs(&sections, TextDirection::Auto, &[0..4, 4..10]),
+            Some(vec![vec![run(0, 0..3, false)], vec![run(0, 4..10, true)]])
+        );
+    }
+}
@@ -1,21 +1,68 @@
-use ab_glyph::{FontArc, FontVec, InvalidFont, OutlinedGlyph};
+use ab_glyph::{
+    point, Font as _, FontArc, FontVec, Glyph, GlyphId, GlyphImageFormat, InvalidFont,
+    OutlinedGlyph, Rect, VariableFont,
+};
 use bevy_reflect::TypeUuid;
 use bevy_render::{
     render_resource::{Extent3d, TextureDimension, TextureFormat},
     texture::Image,
 };
+use image::{imageops::FilterType, ImageFormat, RgbaImage};
+use ttf_parser::{colr, RgbaColor};
+
+use crate::FontAxis;
 
 #[derive(Debug, TypeUuid)]
 #[uuid = "97059ac6-c9ba-4da9-95b6-bed82c3ce198"]
 pub struct Font {
     pub font: FontArc,
+    /// The index of the face in its font collection, 0 for single font files
+    pub(crate) face_index: u32,
+    /// Whether the font has a `COLR` table, whose glyphs are made of layers of colored outlines
+    pub(crate) has_color_layers: bool,
 }
 
 impl Font {
     pub fn try_from_bytes(font_data: Vec<u8>) -> Result<Self, InvalidFont> {
-        let font = FontVec::try_from_vec(font_data)?;
+        Self::try_from_bytes_and_index(font_data, 0)
+    }
+
+    /// Loads the face at `index` of a font collection, e.g. a `.ttc` file.
+    pub fn try_from_bytes_and_index(font_data: Vec<u8>, index: u32) -> Result<Self, InvalidFont> {
+        let has_color_layers = ttf_parser::RawFace::parse(&font_data, index)
+            .map_or(false, |face| {
+                face.table(ttf_parser::Tag::from_bytes(b"COLR")).is_some()
+            });
+        let font = FontVec::try_from_vec_and_index(font_data, index)?;
         let font = FontArc::new(font);
-        Ok(Font { font })
+        Ok(Font {
+            font,
+            face_index: index,
+            has_color_layers,
+        })
+    }
+
+    /// The index of the face in its font collection, 0 for single font files.
+    pub fn face_index(&self) -> u32 {
+        self.face_index
+    }
+
+    /// Returns an instance of this font with the given variation axes set.
+    ///
+    /// Axes that the font doesn't have are ignored.
+    pub fn with_variations(&self, variations: &[(FontAxis, f32)]) -> FontArc {
+        if variations.is_empty() {
+            return self.font.clone();
+        }
+        match FontVec::try_from_vec_and_index(self.font.font_data().to_vec(), self.face_index) {
+            Ok(mut font) => {
+                for (axis, value) in variations {
+                    font.set_variation(&axis.0, *value);
+                }
+                FontArc::new(font)
+            }
+            Err(_) => self.font.clone(),
+        }
     }
 
     pub fn get_outlined_glyph_texture(outlined_glyph: OutlinedGlyph) -> Image {
@@ -42,4 +89,406 @@ impl Font {
             TextureFormat::Rgba8UnormSrgb,
         )
     }
+
+    /// Returns the layers of a glyph made of colored outlines, as found in `COLR` emoji fonts,
+    /// from bottom to top, with their colors from the default palette.
+    ///
+    /// Layers painted with the foreground color are white, like the other glyphs before they
+    /// are tinted. Gradients are painted with the color of their first stop, and the transforms
+    /// of `COLR` version 1 glyphs are not applied.
+    pub fn color_glyph_layers(&self, glyph_id: GlyphId) -> Option<Vec<(GlyphId, RgbaColor)>> {
+        if !self.has_color_layers {
+            return None;
+        }
+        let face = ttf_parser::Face::parse(self.font.font_data(), self.face_index).ok()?;
+        let mut painter = LayerPainter::default();
+        face.paint_color_glyph(
+            ttf_parser::GlyphId(glyph_id.0),
+            0,
+            RgbaColor::new(255, 255, 255, 255),
+            &mut painter,
+        )?;
+        Some(painter.layers).filter(|layers| !layers.is_empty())
+    }
+
+    /// Returns the pixel bounds of a glyph that is drawn in color instead of being tinted by the
+    /// color of its text: a glyph made of [layers](Font::color_glyph_layers) of colored outlines,
+    /// or a glyph stored as a color bitmap, as found in `CBDT` and `sbix` emoji fonts.
+    ///
+    /// Returns `None` if the glyph has no color layers nor color bitmap.
+    pub fn color_glyph_bounds(&self, glyph: &Glyph) -> Option<Rect> {
+        if let Some(layers) = self.color_glyph_layers(glyph.id) {
+            return self
+                .outline_layers(glyph, &layers)
+                .map(|layer| layer.0.px_bounds())
+                .reduce(|a, b| Rect {
+                    min: point(a.min.x.min(b.min.x), a.min.y.min(b.min.y)),
+                    max: point(a.max.x.max(b.max.x), a.max.y.max(b.max.y)),
+                });
+        }
+        let image = self
+            .font
+            .glyph_raster_image2(glyph.id, glyph.scale.y as u16)?;
+        if !matches!(
+            image.format,
+            GlyphImageFormat::Png | GlyphImageFormat::BitmapPremulBgra32
+        ) {
+            return None;
+        }
+        let scale = glyph.scale.y / image.pixels_per_em as f32;
+        let width = (image.width as f32 * scale).round().max(1.0);
+        let height = (image.height as f32 * scale).round().max(1.0);
+        // The origin is the offset of the bottom left corner of the image from the glyph position
+        let min = point(
+            (glyph.position.x + image.origin.x * scale).round(),
+            (glyph.position.y - image.origin.y * scale).round() - height,
+        );
+        Some(Rect {
+            min,
+            max: point(min.x + width, min.y + height),
+        })
+    }
+
+    /// Outlines the layers of a color glyph at the position and scale of `glyph`.
+    fn outline_layers<'a>(
+        &'a self,
+        glyph: &'a Glyph,
+        layers: &'a [(GlyphId, RgbaColor)],
+    ) -> impl Iterator<Item = (OutlinedGlyph, RgbaColor)> + 'a {
+        layers.iter().filter_map(|(id, color)| {
+            let layer = Glyph {
+                id: *id,
+                ..glyph.clone()
+            };
+            Some((self.font.outline_glyph(layer)?, *color))
+        })
+    }
+
+    /// Draws the colored layers of a glyph on top of each other.
+    fn get_layered_glyph_texture(
+        &self,
+        glyph: &Glyph,
+        layers: &[(GlyphId, RgbaColor)],
+        bounds: Rect,
+    ) -> RgbaImage {
+        let width = bounds.width() as u32;
+        let height = bounds.height() as u32;
+        let mut rgba = vec![[0.0f32; 4]; (width * height) as usize];
+        for (outlined_glyph, color) in self.outline_layers(glyph, layers) {
+            let layer_bounds = outlined_glyph.px_bounds();
+            let offset_x = (layer_bounds.min.x - bounds.min.x) as u32;
+            let offset_y = (layer_bounds.min.y - bounds.min.y) as u32;
+            let color = [color.red, color.green, color.blue, color.alpha].map(|c| c as f32 / 255.0);
+            outlined_glyph.draw(|x, y, coverage| {
+                let (x, y) = (x + offset_x, y + offset_y);
+                if x >= width || y >= height {
+                    return;
+                }
+                // Blend the layer over the ones below it
+                let dst = &mut rgba[(y * width + x) as usize];
+                let src_alpha = color[3] * coverage;
+                let alpha = src_alpha + dst[3] * (1.0 - src_alpha);
+                if alpha > 0.0 {
+                    for c in 0..3 {
+                        dst[c] =
+                            (color[c] * src_alpha + dst[c] * dst[3] * (1.0 - src_alpha)) / alpha;
+                    }
+                }
+                dst[3] = alpha;
+            });
+        }
+        let data = rgba
+            .iter()
+            .flat_map(|pixel| pixel.map(|c| (c * 255.0).round() as u8))
+            .collect();
+        RgbaImage::from_raw(width, height, data).unwrap()
+    }
+
+    /// Draws the layers or decodes the color bitmap of a glyph, in an image of the size given by
+    /// [`Font::color_glyph_bounds`].
+    pub fn get_color_glyph_texture(&self, glyph: &Glyph) -> Option<Image> {
+        let bounds = self.color_glyph_bounds(glyph)?;
+        let width = bounds.width() as u32;
+        let height = bounds.height() as u32;
+        let to_image = |rgba: RgbaImage| {
+            Image::new(
+                Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                rgba.into_raw(),
+                TextureFormat::Rgba8UnormSrgb,
+            )
+        };
+        if let Some(layers) = self.color_glyph_layers(glyph.id) {
+            return Some(to_image(
+                self.get_layered_glyph_texture(glyph, &layers, bounds),
+            ));
+        }
+
+        let image = self
+            .font
+            .glyph_raster_image2(glyph.id, glyph.scale.y as u16)?;
+        let rgba = match image.format {
+            GlyphImageFormat::Png => {
+                image::load_from_memory_with_format(image.data, ImageFormat::Png)
+                    .ok()?
+                    .into_rgba8()
+            }
+            GlyphImageFormat::BitmapPremulBgra32 => {
+                let data = image
+                    .data
+                    .chunks_exact(4)
+                    .flat_map(|bgra| {
+                        let unpremultiply = |c: u8| match bgra[3] {
+                            0 => 0,
+                            a => ((c as u32 * 255) / a as u32).min(255) as u8,
+                        };
+                        [
+                            unpremultiply(bgra[2]),
+                            unpremultiply(bgra[1]),
+                            unpremultiply(bgra[0]),
+                            bgra[3],
+                        ]
+                    })
+                    .collect();
+                RgbaImage::from_raw(image.width as u32, image.height as u32, data)?
+            }
+            _ => return None,
+        };
+        let rgba = image::imageops::resize(&rgba, width, height, FilterType::Triangle);
+        Some(to_image(rgba))
+    }
+}
+
+/// Collects the layers of a `COLR` glyph, with the color they are painted with.
+#[derive(Default)]
+struct LayerPainter {
+    outline: Option<ttf_parser::GlyphId>,
+    layers: Vec<(GlyphId, RgbaColor)>,
+}
+
+impl<'a> colr::Painter<'a> for LayerPainter {
+    fn outline_glyph(&mut self, glyph_id: ttf_parser::GlyphId) {
+        self.outline = Some(glyph_id);
+    }
+
+    fn paint(&mut self, paint: colr::Paint<'a>) {
+        let color = match paint {
+            colr::Paint::Solid(color) => Some(color),
+            colr::Paint::LinearGradient(gradient) => {
+                gradient.stops(0, &[]).next().map(|stop| stop.color)
+            }
+            colr::Paint::RadialGradient(gradient) => {
+                gradient.stops(0, &[]).next().map(|stop| stop.color)
+            }
+            colr::Paint::SweepGradient(gradient) => {
+                gradient.stops(0, &[]).next().map(|stop| stop.color)
+            }
+        };
+        if let (Some(outline), Some(color)) = (self.outline, color) {
+            self.layers.push((GlyphId(outline.0), color));
+        }
+    }
+
+    fn push_clip(&mut self) {}
+
+    fn push_clip_box(&mut self, _: colr::ClipBox) {}
+
+    fn pop_clip(&mut self) {}
+
+    fn push_layer(&mut self, _: colr::CompositeMode) {}
+
+    fn pop_layer(&mut self) {}
+
+    fn push_transform(&mut self, _: ttf_parser::Transform) {}
+
+    fn pop_transform(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ab_glyph::ScaleFont;
+
+    const FIRA_SANS: &[u8] = include_bytes!("../../../assets/fonts/FiraSans-Bold.ttf");
+    const FIRA_MONO: &[u8] = include_bytes!("../../../assets/fonts/FiraMono-Medium.ttf");
+
+    /// Builds a font collection from single font files, moving their tables after the header.
+    fn font_collection(faces: &[&[u8]]) -> Vec<u8> {
+        let header_len = 12 + 4 * faces.len();
+        let mut collection = Vec::new();
+        collection.extend_from_slice(b"ttcf");
+        collection.extend_from_slice(&[0, 1, 0, 0]);
+        collection.extend_from_slice(&(faces.len() as u32).to_be_bytes());
+        let mut offset = header_len;
+        for face in faces {
+            collection.extend_from_slice(&(offset as u32).to_be_bytes());
+            offset += face.len();
+        }
+        for face in faces {
+            let face_offset = collection.len() as u32;
+            let mut face = face.to_vec();
+            let table_count = u16::from_be_bytes([face[4], face[5]]) as usize;
+            for table in 0..table_count {
+                let record = 12 + 16 * table + 8;
+                let table_offset = u32::from_be_bytes(face[record..record + 4].try_into().unwrap());
+                face[record..record + 4]
+                    .copy_from_slice(&(table_offset + face_offset).to_be_bytes());
+            }
+            collection.extend_from_slice(&face);
+        }
+        collection
+    }
+
+    /// Adds `COLR` and `CPAL` tables to a font, drawing `base` with the layers in the default
+    /// palette, given as RGBA colors.
+    fn with_color_layers(face: &[u8], base: GlyphId, layers: &[(GlyphId, [u8; 4])]) -> Vec<u8> {
+        let mut colr = Vec::new();
+        colr.extend_from_slice(&0u16.to_be_bytes());
+        colr.extend_from_slice(&1u16.to_be_bytes());
+        colr.extend_from_slice(&14u32.to_be_bytes());
+        colr.extend_from_slice(&20u32.to_be_bytes());
+        colr.extend_from_slice(&(layers.len() as u16).to_be_bytes());
+        colr.extend_from_slice(&base.0.to_be_bytes());
+        colr.extend_from_slice(&0u16.to_be_bytes());
+        colr.extend_from_slice(&(layers.len() as u16).to_be_bytes());
+        for (index, (layer, _)) in layers.iter().enumerate() {
+            colr.extend_from_slice(&layer.0.to_be_bytes());
+            colr.extend_from_slice(&(index as u16).to_be_bytes());
+        }
+
+        let mut cpal = Vec::new();
+        cpal.extend_from_slice(&0u16.to_be_bytes());
+        cpal.extend_from_slice(&(layers.len() as u16).to_be_bytes());
+        cpal.extend_from_slice(&1u16.to_be_bytes());
+        cpal.extend_from_slice(&(layers.len() as u16).to_be_bytes());
+        cpal.extend_from_slice(&14u32.to_be_bytes());
+        cpal.extend_from_slice(&0u16.to_be_bytes());
+        for (_, [r, g, b, a]) in layers {
+            cpal.extend_from_slice(&[*b, *g, *r, *a]);
+        }
+
+        // Make room for two more table records, then append the tables
+        let table_count = u16::from_be_bytes([face[4], face[5]]) as usize;
+        let records_end = 12 + 16 * table_count;
+        let mut font = face[..records_end].to_vec();
+        font[4..6].copy_from_slice(&(table_count as u16 + 2).to_be_bytes());
+        for table in 0..table_count {
+            let record = 12 + 16 * table + 8;
+            let table_offset = u32::from_be_bytes(font[record..record + 4].try_into().unwrap());
+            font[record..record + 4].copy_from_slice(&(table_offset + 32).to_be_bytes());
+        }
+        let mut tables = face[records_end..].to_vec();
+        tables.resize((tables.len() + 3) & !3, 0);
+        let colr_offset = records_end + 32 + tables.len();
+        let cpal_offset = colr_offset + ((colr.len() + 3) & !3);
+        // Table records are sorted by tag
+        let mut records = vec![
+            (*b"COLR", colr_offset, colr.len()),
+            (*b"CPAL", cpal_offset, cpal.len()),
+        ];
+        for table in 0..table_count {
+            let record = &font[12 + 16 * table..12 + 16 * table + 16];
+            records.push((
+                record[0..4].try_into().unwrap(),
+                u32::from_be_bytes(record[8..12].try_into().unwrap()) as usize,
+                u32::from_be_bytes(record[12..16].try_into().unwrap()) as usize,
+            ));
+        }
+        records.sort_by_key(|record| record.0);
+        font.truncate(12);
+        for (tag, offset, len) in records {
+            font.extend_from_slice(&tag);
+            font.extend_from_slice(&0u32.to_be_bytes());
+            font.extend_from_slice(&(offset as u32).to_be_bytes());
+            font.extend_from_slice(&(len as u32).to_be_bytes());
+        }
+        font.extend_from_slice(&tables);
+        colr.resize((colr.len() + 3) & !3, 0);
+        font.extend_from_slice(&colr);
+        font.extend_from_slice(&cpal);
+        font
+    }
+
+    fn advance(font: &FontArc, c: char) -> f32 {
+        let font = font.as_scaled(100.0);
+        font.h_advance(font.glyph_id(c))
+    }
+
+    #[test]
+    fn variations_keep_face_index() {
+        let collection = font_collection(&[FIRA_SANS, FIRA_MONO]);
+        let mono = Font::try_from_bytes_and_index(collection, 1).unwrap();
+        assert_eq!(mono.face_index(), 1);
+        // the second face is the monospace one
+        assert_eq!(advance(&mono.font, 'i'), advance(&mono.font, 'W'));
+
+        let instance = mono.with_variations(&[(FontAxis::WEIGHT, 700.0)]);
+        assert_eq!(advance(&instance, 'i'), advance(&mono.font, 'i'));
+        assert_eq!(advance(&instance, 'i'), advance(&instance, 'W'));
+    }
+
+    #[test]
+    fn layered_color_glyphs() {
+        let plain = Font::try_from_bytes(FIRA_SANS.to_vec()).unwrap();
+        let [a, o, i] = ['A', 'O', 'I'].map(|c| plain.font.glyph_id(c));
+        assert!(plain.color_glyph_layers(a).is_none());
+
+        const RED: [u8; 4] = [255, 0, 0, 255];
+        const BLUE: [u8; 4] = [0, 0, 255, 255];
+        let font =
+            Font::try_from_bytes(with_color_layers(FIRA_SANS, a, &[(o, RED), (i, BLUE)])).unwrap();
+        let layers = font.color_glyph_layers(a).unwrap();
+        assert_eq!(
+            layers
+                .iter()
+                .map(|(id, color)| (*id, [color.red, color.green, color.blue, color.alpha]))
+                .collect::<Vec<_>>(),
+            vec![(o, RED), (i, BLUE)]
+        );
+        // Glyphs without layers keep being drawn from their outline
+        assert!(font.color_glyph_bounds(&o.with_scale(64.0)).is_none());
+
+        let glyph = a.with_scale_and_position(64.0, point(10.0, 50.0));
+        let bounds = font.color_glyph_bounds(&glyph).unwrap();
+        let o_bounds = font
+            .font
+            .outline_glyph(Glyph {
+                id: o,
+                ..glyph.clone()
+            })
+            .unwrap()
+            .px_bounds();
+        let i_bounds = font
+            .font
+            .outline_glyph(Glyph {
+                id: i,
+                ..glyph.clone()
+            })
+            .unwrap()
+            .px_bounds();
+        assert_eq!(bounds.min.x, o_bounds.min.x.min(i_bounds.min.x));
+        assert_eq!(bounds.max.x, o_bounds.max.x.max(i_bounds.max.x));
+        assert_eq!(bounds.min.y, o_bounds.min.y.min(i_bounds.min.y));
+        assert_eq!(bounds.max.y, o_bounds.max.y.max(i_bounds.max.y));
+
+        let texture = font.get_color_glyph_texture(&glyph).unwrap();
+        assert_eq!(texture.texture_descriptor.size.width, bounds.width() as u32);
+        assert_eq!(
+            texture.texture_descriptor.size.height,
+            bounds.height() as u32
+        );
+        let pixels = texture.data.chunks(4).collect::<Vec<_>>();
+        assert!(pixels.contains(&&RED[..]));
+        assert!(pixels.contains(&&BLUE[..]));
+        assert!(pixels.contains(&&[0, 0, 0, 0][..]));
+        // The `I` is drawn on top of the `O`
+        let width = texture.texture_descriptor.size.width as usize;
+        let x = ((i_bounds.min.x + i_bounds.max.x) / 2.0 - bounds.min.x) as usize;
+        let y = (i_bounds.max.y - 1.0 - bounds.min.y) as usize;
+        assert_eq!(pixels[y * width + x], &BLUE[..]);
+    }
 }
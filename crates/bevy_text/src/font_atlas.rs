@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use ab_glyph::{GlyphId, Point};
 use bevy_asset::{Assets, Handle};
 use bevy_math::Vec2;
@@ -43,6 +45,7 @@ pub struct FontAtlas {
     pub dynamic_texture_atlas_builder: DynamicTextureAtlasBuilder,
     pub glyph_to_atlas_index: HashMap<(GlyphId, SubpixelOffset), usize>,
     pub texture_atlas: Handle<TextureAtlas>,
+    pub(crate) last_used: AtomicU64,
 }
 
 impl FontAtlas {
@@ -66,9 +69,16 @@ impl FontAtlas {
             texture_atlas: texture_atlases.add(texture_atlas),
             glyph_to_atlas_index: HashMap::default(),
             dynamic_texture_atlas_builder: DynamicTextureAtlasBuilder::new(size, 1),
+            last_used: AtomicU64::new(0),
         }
     }
 
+    /// Returns the value of the owning [`FontAtlasSet`](crate::FontAtlasSet)'s use counter the
+    /// last time a glyph was looked up in this atlas.
+    pub fn last_used(&self) -> u64 {
+        self.last_used.load(Ordering::Relaxed)
+    }
+
     pub fn get_glyph_index(
         &self,
         glyph_id: GlyphId,
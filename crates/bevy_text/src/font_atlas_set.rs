@@ -1,19 +1,50 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use crate::{error::TextError, Font, FontAtlas};
-use ab_glyph::{GlyphId, OutlinedGlyph, Point};
+use ab_glyph::{Glyph, GlyphId, OutlinedGlyph, Point};
 use bevy_asset::{Assets, Handle};
 use bevy_math::Vec2;
 use bevy_reflect::TypeUuid;
 use bevy_render::texture::Image;
-use bevy_sprite::TextureAtlas;
+use bevy_sprite::{Rect, TextureAtlas};
 use bevy_utils::FloatOrd;
 use bevy_utils::HashMap;
 
 type FontSizeKey = FloatOrd;
 
+/// Controls how glyphs are cached in [`FontAtlasSet`]s.
+#[derive(Debug, Clone)]
+pub struct FontAtlasSettings {
+    /// The width and height in pixels of newly created atlas textures.
+    ///
+    /// Glyphs that are larger than this get an atlas of their own, sized to fit.
+    pub atlas_size: u32,
+    /// The maximum number of atlas textures kept by each [`FontAtlasSet`], across all font sizes.
+    ///
+    /// When a new atlas is needed and the limit has been reached, the least recently used atlas
+    /// is removed along with its texture. Text using glyphs from it is laid out again, so that the
+    /// glyphs get rasterized into a new atlas. If `None`, atlases are never removed.
+    pub max_atlases: Option<usize>,
+}
+
+impl Default for FontAtlasSettings {
+    fn default() -> Self {
+        Self {
+            atlas_size: 512,
+            max_atlases: None,
+        }
+    }
+}
+
 #[derive(TypeUuid)]
 #[uuid = "73ba778b-b6b5-4f45-982d-d21b6b86ace2"]
 pub struct FontAtlasSet {
     font_atlases: HashMap<FontSizeKey, Vec<FontAtlas>>,
+    /// Incremented every time a glyph is looked up, used to find the least recently used atlas.
+    use_counter: AtomicU64,
+    /// The value of `use_counter` when the layout of the text in flight started, see
+    /// [`FontAtlasSet::start_layout`].
+    layout_start: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -22,10 +53,24 @@ pub struct GlyphAtlasInfo {
     pub glyph_index: usize,
 }
 
+impl GlyphAtlasInfo {
+    /// Returns the UV coordinates of the glyph inside its atlas texture, in the `0..1` range.
+    pub fn uv_rect(&self, texture_atlases: &Assets<TextureAtlas>) -> Option<Rect> {
+        let atlas = texture_atlases.get(&self.texture_atlas)?;
+        let rect = atlas.textures.get(self.glyph_index)?;
+        Some(Rect {
+            min: rect.min / atlas.size,
+            max: rect.max / atlas.size,
+        })
+    }
+}
+
 impl Default for FontAtlasSet {
     fn default() -> Self {
         FontAtlasSet {
             font_atlases: HashMap::with_capacity_and_hasher(1, Default::default()),
+            use_counter: AtomicU64::new(0),
+            layout_start: 0,
         }
     }
 }
@@ -35,6 +80,13 @@ impl FontAtlasSet {
         self.font_atlases.iter()
     }
 
+    /// Marks the start of the layout of a text. The atlases holding the glyphs looked up from
+    /// now on are pinned until the next call: they are not evicted when adding glyphs, so that
+    /// the glyphs already placed for the text stay valid.
+    pub fn start_layout(&mut self) {
+        self.layout_start = self.use_counter.load(Ordering::Relaxed);
+    }
+
     pub fn has_glyph(&self, glyph_id: GlyphId, glyph_position: Point, font_size: f32) -> bool {
         self.font_atlases
             .get(&FloatOrd(font_size))
@@ -50,23 +102,27 @@ impl FontAtlasSet {
         texture_atlases: &mut Assets<TextureAtlas>,
         textures: &mut Assets<Image>,
         outlined_glyph: OutlinedGlyph,
+        settings: &FontAtlasSettings,
+    ) -> Result<GlyphAtlasInfo, TextError> {
+        let glyph = outlined_glyph.glyph().clone();
+        let glyph_texture = Font::get_outlined_glyph_texture(outlined_glyph);
+        self.add_glyph_texture_to_atlas(texture_atlases, textures, &glyph, glyph_texture, settings)
+    }
+
+    /// Adds an already rasterized glyph to the atlases, e.g. a color glyph from
+    /// [`Font::get_color_glyph_texture`].
+    pub fn add_glyph_texture_to_atlas(
+        &mut self,
+        texture_atlases: &mut Assets<TextureAtlas>,
+        textures: &mut Assets<Image>,
+        glyph: &Glyph,
+        glyph_texture: Image,
+        settings: &FontAtlasSettings,
     ) -> Result<GlyphAtlasInfo, TextError> {
-        let glyph = outlined_glyph.glyph();
         let glyph_id = glyph.id;
         let glyph_position = glyph.position;
         let font_size = glyph.scale.y;
-        let font_atlases = self
-            .font_atlases
-            .entry(FloatOrd(font_size))
-            .or_insert_with(|| {
-                vec![FontAtlas::new(
-                    textures,
-                    texture_atlases,
-                    Vec2::splat(512.0),
-                )]
-            });
-        let glyph_texture = Font::get_outlined_glyph_texture(outlined_glyph);
-        let add_char_to_font_atlas = |atlas: &mut FontAtlas| -> bool {
+        let mut add_char_to_font_atlas = |atlas: &mut FontAtlas| -> bool {
             atlas.add_glyph(
                 textures,
                 texture_atlases,
@@ -75,21 +131,34 @@ impl FontAtlasSet {
                 &glyph_texture,
             )
         };
-        if !font_atlases.iter_mut().any(add_char_to_font_atlas) {
+        let added = self
+            .font_atlases
+            .get_mut(&FloatOrd(font_size))
+            .map_or(false, |font_atlases| {
+                font_atlases.iter_mut().any(&mut add_char_to_font_atlas)
+            });
+        if !added {
+            if let Some(max_atlases) = settings.max_atlases {
+                // Go over the limit rather than evict glyphs of the text in flight, until the
+                // next text is laid out
+                while self.len() >= max_atlases.max(1)
+                    && self.evict_least_recently_used(texture_atlases, textures)
+                {}
+            }
+
             // Find the largest dimension of the glyph, either its width or its height
             let glyph_max_size: u32 = glyph_texture
                 .texture_descriptor
                 .size
                 .height
                 .max(glyph_texture.texture_descriptor.size.width);
-            // Pick the higher  of 512 or the smallest power of 2 greater than glyph_max_size
-            let containing = (1u32 << (32 - glyph_max_size.leading_zeros())).max(512) as f32;
-            font_atlases.push(FontAtlas::new(
-                textures,
-                texture_atlases,
-                Vec2::new(containing, containing),
-            ));
-            if !font_atlases.last_mut().unwrap().add_glyph(
+            // Pick the higher of the atlas size or the smallest power of 2 greater than
+            // glyph_max_size
+            let containing =
+                (1u32 << (32 - glyph_max_size.leading_zeros())).max(settings.atlas_size) as f32;
+            let mut font_atlas =
+                FontAtlas::new(textures, texture_atlases, Vec2::new(containing, containing));
+            if !font_atlas.add_glyph(
                 textures,
                 texture_atlases,
                 glyph_id,
@@ -98,6 +167,10 @@ impl FontAtlasSet {
             ) {
                 return Err(TextError::FailedToAddGlyph(glyph_id));
             }
+            self.font_atlases
+                .entry(FloatOrd(font_size))
+                .or_default()
+                .push(font_atlas);
         }
 
         Ok(self
@@ -119,7 +192,11 @@ impl FontAtlasSet {
                     .find_map(|atlas| {
                         atlas
                             .get_glyph_index(glyph_id, position.into())
-                            .map(|glyph_index| (glyph_index, atlas.texture_atlas.clone_weak()))
+                            .map(|glyph_index| {
+                                let tick = self.use_counter.fetch_add(1, Ordering::Relaxed);
+                                atlas.last_used.store(tick, Ordering::Relaxed);
+                                (glyph_index, atlas.texture_atlas.clone_weak())
+                            })
                     })
                     .map(|(glyph_index, texture_atlas)| GlyphAtlasInfo {
                         texture_atlas,
@@ -127,4 +204,184 @@ impl FontAtlasSet {
                     })
             })
     }
+
+    /// Returns the number of atlases in this set, across all font sizes.
+    pub fn len(&self) -> usize {
+        self.font_atlases.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes the atlas whose glyphs were looked up the longest time ago, along with its texture,
+    /// unless it is pinned by [`FontAtlasSet::start_layout`].
+    ///
+    /// Returns whether an atlas was removed.
+    fn evict_least_recently_used(
+        &mut self,
+        texture_atlases: &mut Assets<TextureAtlas>,
+        textures: &mut Assets<Image>,
+    ) -> bool {
+        let layout_start = self.layout_start;
+        let least_recently_used = self
+            .font_atlases
+            .iter()
+            .flat_map(|(font_size, font_atlases)| {
+                font_atlases
+                    .iter()
+                    .enumerate()
+                    .map(move |(index, atlas)| (*font_size, index, atlas.last_used()))
+            })
+            .filter(|(_, _, last_used)| *last_used < layout_start)
+            .min_by_key(|(_, _, last_used)| *last_used);
+
+        if let Some((font_size, index, _)) = least_recently_used {
+            let font_atlases = self.font_atlases.get_mut(&font_size).unwrap();
+            let atlas = font_atlases.remove(index);
+            if font_atlases.is_empty() {
+                self.font_atlases.remove(&font_size);
+            }
+            if let Some(texture_atlas) = texture_atlases.remove(&atlas.texture_atlas) {
+                textures.remove(&texture_atlas.texture);
+            }
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ab_glyph::point;
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_ecs::world::Mut;
+    use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+    use bevy_tasks::IoTaskPool;
+
+    /// Each atlas is only large enough to hold a single glyph
+    const SETTINGS: FontAtlasSettings = FontAtlasSettings {
+        atlas_size: 16,
+        max_atlases: Some(2),
+    };
+
+    fn assets_app() -> App {
+        IoTaskPool::init(Default::default);
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin)
+            .add_asset::<Image>()
+            .add_asset::<TextureAtlas>();
+        app
+    }
+
+    fn glyph(id: u16) -> Glyph {
+        Glyph {
+            id: GlyphId(id),
+            scale: 12.0.into(),
+            position: point(0.0, 0.0),
+        }
+    }
+
+    fn add_glyph(app: &mut App, font_atlas_set: &mut FontAtlasSet, id: u16) {
+        let texture = Image::new_fill(
+            Extent3d {
+                width: 12,
+                height: 12,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[255, 255, 255, 255],
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        app.world
+            .resource_scope(|world, mut texture_atlases: Mut<Assets<TextureAtlas>>| {
+                let mut textures = world.resource_mut::<Assets<Image>>();
+                font_atlas_set
+                    .add_glyph_texture_to_atlas(
+                        &mut texture_atlases,
+                        &mut textures,
+                        &glyph(id),
+                        texture,
+                        &SETTINGS,
+                    )
+                    .unwrap();
+            });
+    }
+
+    fn has_glyph(font_atlas_set: &FontAtlasSet, id: u16) -> bool {
+        font_atlas_set.has_glyph(GlyphId(id), point(0.0, 0.0), 12.0)
+    }
+
+    fn look_up_glyph(font_atlas_set: &FontAtlasSet, id: u16) {
+        assert!(font_atlas_set
+            .get_glyph_atlas_info(12.0, GlyphId(id), point(0.0, 0.0))
+            .is_some());
+    }
+
+    #[test]
+    fn evicts_least_recently_used_atlas() {
+        let mut app = assets_app();
+        let mut font_atlas_set = FontAtlasSet::default();
+        font_atlas_set.start_layout();
+        add_glyph(&mut app, &mut font_atlas_set, 1);
+        add_glyph(&mut app, &mut font_atlas_set, 2);
+        assert_eq!(font_atlas_set.len(), 2);
+
+        // The first glyph is used again by the next text, so the second one is evicted
+        font_atlas_set.start_layout();
+        look_up_glyph(&font_atlas_set, 1);
+        add_glyph(&mut app, &mut font_atlas_set, 3);
+        assert_eq!(font_atlas_set.len(), 2);
+        assert!(has_glyph(&font_atlas_set, 1));
+        assert!(!has_glyph(&font_atlas_set, 2));
+        assert!(has_glyph(&font_atlas_set, 3));
+        assert_eq!(app.world.resource::<Assets<TextureAtlas>>().len(), 2);
+    }
+
+    #[test]
+    fn glyphs_of_the_text_in_flight_are_not_evicted() {
+        let mut app = assets_app();
+        let mut font_atlas_set = FontAtlasSet::default();
+        font_atlas_set.start_layout();
+        add_glyph(&mut app, &mut font_atlas_set, 1);
+        add_glyph(&mut app, &mut font_atlas_set, 2);
+        add_glyph(&mut app, &mut font_atlas_set, 3);
+        assert_eq!(font_atlas_set.len(), 3);
+        assert!((1..=3).all(|id| has_glyph(&font_atlas_set, id)));
+
+        // The atlases over the limit are evicted with the next text, oldest first
+        font_atlas_set.start_layout();
+        add_glyph(&mut app, &mut font_atlas_set, 4);
+        assert_eq!(font_atlas_set.len(), 2);
+        assert!(!has_glyph(&font_atlas_set, 1));
+        assert!(!has_glyph(&font_atlas_set, 2));
+        assert!(has_glyph(&font_atlas_set, 3));
+        assert!(has_glyph(&font_atlas_set, 4));
+    }
+
+    #[test]
+    fn glyph_uv_rects_are_normalized() {
+        let mut app = assets_app();
+        let mut font_atlas_set = FontAtlasSet::default();
+        font_atlas_set.start_layout();
+        add_glyph(&mut app, &mut font_atlas_set, 1);
+        let info = font_atlas_set
+            .get_glyph_atlas_info(12.0, GlyphId(1), point(0.0, 0.0))
+            .unwrap();
+
+        let texture_atlases = app.world.resource::<Assets<TextureAtlas>>();
+        let uv_rect = info.uv_rect(texture_atlases).unwrap();
+        // The glyph is 12 pixels wide in a 16 pixels wide atlas
+        assert!(uv_rect.min.cmpge(Vec2::ZERO).all() && uv_rect.max.cmple(Vec2::ONE).all());
+        assert_eq!(uv_rect.size(), Vec2::splat(0.75));
+
+        let missing = GlyphAtlasInfo {
+            texture_atlas: Handle::default(),
+            glyph_index: 0,
+        };
+        assert_eq!(missing.uv_rect(texture_atlases), None);
+    }
 }
@@ -1,17 +1,20 @@
-use ab_glyph::{Font as _, FontArc, Glyph, ScaleFont as _};
+use ab_glyph::{Font as _, FontArc, Glyph, OutlinedGlyph, ScaleFont as _};
 use bevy_asset::{Assets, Handle};
 use bevy_math::Vec2;
-use bevy_render::texture::Image;
+use bevy_render::{color::Color, texture::Image};
 use bevy_sprite::TextureAtlas;
 use glyph_brush_layout::{
     FontId, GlyphPositioner, Layout, SectionGeometry, SectionGlyph, SectionText, ToSectionText,
 };
 
-use crate::{error::TextError, Font, FontAtlasSet, GlyphAtlasInfo, TextAlignment};
+use crate::{
+    error::TextError, Font, FontAtlasSet, FontAtlasSettings, GlyphAtlasInfo, TextAlignment,
+};
 
 pub struct GlyphBrush {
     fonts: Vec<FontArc>,
     handles: Vec<Handle<Font>>,
+    font_atlas_sets: Vec<Handle<FontAtlasSet>>,
     latest_font_id: FontId,
 }
 
@@ -20,6 +23,7 @@ impl Default for GlyphBrush {
         GlyphBrush {
             fonts: Vec::new(),
             handles: Vec::new(),
+            font_atlas_sets: Vec::new(),
             latest_font_id: FontId(0),
         }
     }
@@ -51,6 +55,7 @@ impl GlyphBrush {
         fonts: &Assets<Font>,
         texture_atlases: &mut Assets<TextureAtlas>,
         textures: &mut Assets<Image>,
+        font_atlas_settings: &FontAtlasSettings,
     ) -> Result<Vec<PositionedGlyph>, TextError> {
         if glyphs.is_empty() {
             return Ok(Vec::new());
@@ -61,21 +66,30 @@ impl GlyphBrush {
             .map(|section| {
                 let handle = &self.handles[section.font_id.0];
                 let font = fonts.get(handle).ok_or(TextError::NoSuchFont)?;
+                // The font registered in the brush may be an instance of a variable font
+                let font = Font {
+                    font: self.fonts[section.font_id.0].clone(),
+                    face_index: font.face_index(),
+                    has_color_layers: font.has_color_layers,
+                };
                 let font_size = section.scale.y;
-                Ok((
-                    handle,
-                    font,
-                    font_size,
-                    ab_glyph::Font::as_scaled(&font.font, font_size),
-                ))
+                Ok((&self.font_atlas_sets[section.font_id.0], font, font_size))
             })
             .collect::<Result<Vec<_>, _>>()?;
 
+        // Keep the atlases holding the glyphs of this text from being evicted while it is laid out
+        for (font_atlas_set, _, _) in &sections_data {
+            if let Some(font_atlas_set) = font_atlas_set_storage.get_mut(font_atlas_set) {
+                font_atlas_set.start_layout();
+            }
+        }
+
         let mut max_y = std::f32::MIN;
         let mut min_x = std::f32::MAX;
         for sg in &glyphs {
             let glyph = &sg.glyph;
-            let scaled_font = sections_data[sg.section_index].3;
+            let section_data = &sections_data[sg.section_index];
+            let scaled_font = ab_glyph::Font::as_scaled(&section_data.1.font, section_data.2);
             max_y = max_y.max(glyph.position.y - scaled_font.descent());
             min_x = min_x.min(glyph.position.x);
         }
@@ -93,43 +107,101 @@ impl GlyphBrush {
             let glyph_id = glyph.id;
             let glyph_position = glyph.position;
             let adjust = GlyphPlacementAdjuster::new(&mut glyph);
-            let section_data = sections_data[sg.section_index];
-            if let Some(outlined_glyph) = section_data.1.font.outline_glyph(glyph) {
-                let bounds = outlined_glyph.px_bounds();
-                let handle_font_atlas: Handle<FontAtlasSet> = section_data.0.as_weak();
-                let font_atlas_set = font_atlas_set_storage
-                    .get_or_insert_with(handle_font_atlas, FontAtlasSet::default);
-
-                let atlas_info = font_atlas_set
-                    .get_glyph_atlas_info(section_data.2, glyph_id, glyph_position)
-                    .map(Ok)
-                    .unwrap_or_else(|| {
-                        font_atlas_set.add_glyph_to_atlas(texture_atlases, textures, outlined_glyph)
-                    })?;
-
-                let texture_atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
-                let glyph_rect = texture_atlas.textures[atlas_info.glyph_index as usize];
-                let size = Vec2::new(glyph_rect.width(), glyph_rect.height());
-
-                let x = bounds.min.x + size.x / 2.0 - min_x;
-                let y = max_y - bounds.max.y + size.y / 2.0;
-                let position = adjust.position(Vec2::new(x, y));
-
-                positioned_glyphs.push(PositionedGlyph {
-                    position,
-                    size,
-                    atlas_info,
-                    section_index: sg.section_index,
-                    byte_index,
-                });
-            }
+            let section_data = &sections_data[sg.section_index];
+            let font = &section_data.1;
+            // Color glyphs may also have an outline, used where color isn't supported
+            let (bounds, source) = match font.color_glyph_bounds(&glyph) {
+                Some(bounds) => (bounds, GlyphSource::Color),
+                None => match font.font.outline_glyph(glyph.clone()) {
+                    Some(outlined_glyph) => (
+                        outlined_glyph.px_bounds(),
+                        GlyphSource::Outline(outlined_glyph),
+                    ),
+                    None => continue,
+                },
+            };
+            let is_color = matches!(source, GlyphSource::Color);
+
+            let handle_font_atlas: Handle<FontAtlasSet> = section_data.0.clone_weak();
+            let font_atlas_set =
+                font_atlas_set_storage.get_or_insert_with(handle_font_atlas, FontAtlasSet::default);
+
+            let atlas_info =
+                match font_atlas_set.get_glyph_atlas_info(section_data.2, glyph_id, glyph_position)
+                {
+                    Some(atlas_info) => atlas_info,
+                    None => match source {
+                        GlyphSource::Outline(outlined_glyph) => font_atlas_set.add_glyph_to_atlas(
+                            texture_atlases,
+                            textures,
+                            outlined_glyph,
+                            font_atlas_settings,
+                        )?,
+                        GlyphSource::Color => match font.get_color_glyph_texture(&glyph) {
+                            Some(glyph_texture) => font_atlas_set.add_glyph_texture_to_atlas(
+                                texture_atlases,
+                                textures,
+                                &glyph,
+                                glyph_texture,
+                                font_atlas_settings,
+                            )?,
+                            None => continue,
+                        },
+                    },
+                };
+
+            let texture_atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
+            let glyph_rect = texture_atlas.textures[atlas_info.glyph_index as usize];
+            let size = Vec2::new(glyph_rect.width(), glyph_rect.height());
+
+            let x = bounds.min.x + size.x / 2.0 - min_x;
+            let y = max_y - bounds.max.y + size.y / 2.0;
+            let position = adjust.position(Vec2::new(x, y));
+
+            positioned_glyphs.push(PositionedGlyph {
+                position,
+                size,
+                atlas_info,
+                section_index: sg.section_index,
+                byte_index,
+                reading_index: 0,
+                is_color,
+            });
+        }
+
+        // Glyphs are in visual order, which differs from reading order for bidirectional text
+        let mut reading_order: Vec<usize> = (0..positioned_glyphs.len()).collect();
+        reading_order.sort_unstable_by_key(|&index| {
+            let glyph = &positioned_glyphs[index];
+            (glyph.section_index, glyph.byte_index)
+        });
+        for (reading_index, index) in reading_order.into_iter().enumerate() {
+            positioned_glyphs[index].reading_index = reading_index;
         }
         Ok(positioned_glyphs)
     }
 
+    /// Returns the font registered under `font_id`.
+    pub fn font(&self, font_id: FontId) -> &FontArc {
+        &self.fonts[font_id.0]
+    }
+
     pub fn add_font(&mut self, handle: Handle<Font>, font: FontArc) -> FontId {
+        let font_atlas_set = handle.as_weak();
+        self.add_font_instance(handle, font, font_atlas_set)
+    }
+
+    /// Adds an instance of the font behind `handle`, e.g. with variations applied, whose glyphs
+    /// are cached in their own [`FontAtlasSet`].
+    pub fn add_font_instance(
+        &mut self,
+        handle: Handle<Font>,
+        font: FontArc,
+        font_atlas_set: Handle<FontAtlasSet>,
+    ) -> FontId {
         self.fonts.push(font);
         self.handles.push(handle);
+        self.font_atlas_sets.push(font_atlas_set);
         let font_id = self.latest_font_id;
         self.latest_font_id = FontId(font_id.0 + 1);
         font_id
@@ -143,6 +215,31 @@ pub struct PositionedGlyph {
     pub atlas_info: GlyphAtlasInfo,
     pub section_index: usize,
     pub byte_index: usize,
+    /// The index of the glyph in reading order, which differs from the visual order of
+    /// [`TextLayoutInfo::glyphs`](crate::TextLayoutInfo::glyphs) for bidirectional text.
+    pub reading_index: usize,
+    /// Whether the glyph is a color bitmap, e.g. an emoji, that shouldn't be tinted by the color
+    /// of its section.
+    pub is_color: bool,
+}
+
+impl PositionedGlyph {
+    /// Returns the color this glyph should be tinted with, given the color of its section.
+    ///
+    /// Color glyphs are only affected by the alpha of the section color.
+    pub fn tint(&self, section_color: Color) -> Color {
+        if self.is_color {
+            Color::rgba(1.0, 1.0, 1.0, section_color.a())
+        } else {
+            section_color
+        }
+    }
+}
+
+/// Where the texture of a glyph comes from when it isn't in an atlas yet.
+enum GlyphSource {
+    Outline(OutlinedGlyph),
+    Color,
 }
 
 #[cfg(feature = "subpixel_glyph_atlas")]
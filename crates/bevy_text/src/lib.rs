@@ -1,12 +1,20 @@
+mod bidi;
 mod error;
 mod font;
 mod font_atlas;
 mod font_atlas_set;
 mod font_loader;
 mod glyph_brush;
+mod localization;
 mod pipeline;
+mod shaping;
 mod text;
 mod text2d;
+#[cfg(feature = "text3d")]
+mod text3d;
+
+#[cfg(feature = "system_fonts")]
+pub mod system_fonts;
 
 pub use error::*;
 pub use font::*;
@@ -14,27 +22,43 @@ pub use font_atlas::*;
 pub use font_atlas_set::*;
 pub use font_loader::*;
 pub use glyph_brush::*;
+pub use localization::*;
 pub use pipeline::*;
 pub use text::*;
 pub use text2d::*;
+#[cfg(feature = "text3d")]
+pub use text3d::*;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        Font, HorizontalAlign, Text, Text2dBundle, TextAlignment, TextError, TextSection,
-        TextStyle, VerticalAlign,
+        Font, FontAxis, HorizontalAlign, LocalizationPlugin, LocalizedText, Text, Text2dBundle,
+        TextAlignment, TextDirection, TextError, TextGlyphModifiers, TextLayoutInfo, TextReveal,
+        TextSection, TextStyle, VerticalAlign,
     };
+
+    #[cfg(feature = "text3d")]
+    #[doc(hidden)]
+    pub use crate::{Text3d, Text3dBundle};
 }
 
 use bevy_app::prelude::*;
 use bevy_asset::AddAsset;
-use bevy_ecs::{entity::Entity, schedule::ParallelSystemDescriptorCoercion};
-use bevy_render::{RenderApp, RenderStage};
+use bevy_ecs::{
+    entity::Entity,
+    schedule::{ParallelSystemDescriptorCoercion, SystemLabel},
+};
+use bevy_render::{view::VisibilitySystems, RenderApp, RenderStage};
 use bevy_sprite::SpriteSystem;
 use bevy_window::ModifiesWindows;
 
 pub type DefaultTextPipeline = TextPipeline<Entity>;
 
+/// Label for the systems that lay out [`Text`], for systems that modify it in
+/// [`CoreStage::PostUpdate`] to run before.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub struct TextLayoutSystem;
+
 #[derive(Default)]
 pub struct TextPlugin;
 
@@ -43,13 +67,45 @@ impl Plugin for TextPlugin {
         app.add_asset::<Font>()
             .add_asset::<FontAtlasSet>()
             .register_type::<Text>()
+            .register_type::<FontAxis>()
+            .register_type::<TextDirection>()
+            .register_type::<TextReveal>()
             .register_type::<VerticalAlign>()
             .register_type::<HorizontalAlign>()
             .init_asset_loader::<FontLoader>()
             .insert_resource(DefaultTextPipeline::default())
+            .init_resource::<FontAtlasSettings>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_text2d_layout
+                    .label(TextLayoutSystem)
+                    .after(ModifiesWindows),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                calculate_text2d_bounds
+                    .after(TextLayoutSystem)
+                    .label(VisibilitySystems::CalculateBounds),
+            );
+
+        #[cfg(feature = "text3d")]
+        app.register_type::<Text3d>()
+            .init_resource::<Text3dMaterials>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_text3d_layout.label(TextLayoutSystem),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_text3d_meshes
+                    .after(update_text3d_layout)
+                    .before(bevy_transform::TransformSystem::TransformPropagate),
+            )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
-                update_text2d_layout.after(ModifiesWindows),
+                billboard_text3d
+                    .after(bevy_transform::TransformSystem::TransformPropagate)
+                    .before(bevy_render::view::VisibilitySystems::CheckVisibility),
             );
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
@@ -0,0 +1,334 @@
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_ecs::{
+    prelude::Component,
+    query::ChangeTrackers,
+    reflect::ReflectComponent,
+    schedule::ParallelSystemDescriptorCoercion,
+    system::{Query, Res},
+};
+use bevy_reflect::{prelude::*, FromReflect};
+use bevy_utils::{tracing::warn, HashMap};
+
+use crate::{Text, TextLayoutSystem, TextSection, TextStyle};
+
+/// Adds support for [`LocalizedText`], whose sections are resolved through the [`Localization`]
+/// resource.
+///
+/// If no [`Localization`] resource was inserted, an empty [`MessageCatalog`] is used.
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_ecs::system::Commands;
+/// # use bevy_text::{
+/// #     Localization, LocalizationPlugin, LocalizedText, MessageCatalog, Text, TextStyle,
+/// # };
+/// let messages = MessageCatalog::default()
+///     .with_message("en-US", "score", "Score: { $points }")
+///     .with_message("fr-FR", "score", "Points : { $points }")
+///     .with_fallback_locale("en-US");
+///
+/// App::new()
+///     .insert_resource(Localization::new("fr-FR", messages))
+///     .add_plugin(LocalizationPlugin)
+///     .add_startup_system(|mut commands: Commands| {
+///         commands.spawn().insert(Text::default()).insert(
+///             LocalizedText::new("score", TextStyle::default()).with_arg("points", 0),
+///         );
+///     });
+/// ```
+#[derive(Default)]
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<LocalizedText>()
+            .register_type::<LocalizedSection>()
+            .init_resource::<Localization>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_localized_text.before(TextLayoutSystem),
+            );
+    }
+}
+
+/// Looks up localized messages.
+///
+/// Implement this to plug a localization library, such as Fluent, into [`Localization`].
+pub trait Localizer: Send + Sync + 'static {
+    /// Returns the message for `key` in the given locale, with `args` substituted into it, or
+    /// `None` if there is no such message.
+    fn localize(&self, locale: &str, key: &str, args: &[(String, String)]) -> Option<String>;
+}
+
+/// The active locale, along with the [`Localizer`] used to resolve [`LocalizedText`].
+///
+/// All [`LocalizedText`] is resolved again whenever this resource changes, e.g. when switching
+/// locales with [`Localization::set_locale`].
+pub struct Localization {
+    locale: String,
+    localizer: Box<dyn Localizer>,
+}
+
+impl Localization {
+    pub fn new(locale: impl Into<String>, localizer: impl Localizer) -> Self {
+        Self {
+            locale: locale.into(),
+            localizer: Box::new(localizer),
+        }
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    pub fn set_locale(&mut self, locale: impl Into<String>) {
+        self.locale = locale.into();
+    }
+
+    /// Returns the message for `key` in the active locale, or `None` if there is no such message.
+    pub fn localize(&self, key: &str, args: &[(String, String)]) -> Option<String> {
+        self.localizer.localize(&self.locale, key, args)
+    }
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        Self::new("en-US", MessageCatalog::default())
+    }
+}
+
+/// A simple [`Localizer`] storing messages in memory.
+///
+/// Arguments are referenced in messages using Fluent's placeable syntax, e.g.
+/// `"Score: { $points }"`. Messages missing from a locale are looked up in the fallback locale,
+/// if one is set.
+#[derive(Debug, Default, Clone)]
+pub struct MessageCatalog {
+    messages: HashMap<String, HashMap<String, String>>,
+    fallback_locale: Option<String>,
+}
+
+impl MessageCatalog {
+    /// Adds the message for `key` in the given locale, replacing any previous one.
+    pub fn insert(
+        &mut self,
+        locale: impl Into<String>,
+        key: impl Into<String>,
+        message: impl Into<String>,
+    ) -> &mut Self {
+        self.messages
+            .entry(locale.into())
+            .or_default()
+            .insert(key.into(), message.into());
+        self
+    }
+
+    /// Returns this catalog with the given message added.
+    #[must_use]
+    pub fn with_message(
+        mut self,
+        locale: impl Into<String>,
+        key: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        self.insert(locale, key, message);
+        self
+    }
+
+    /// Returns this catalog with messages missing from a locale looked up in `locale` instead.
+    #[must_use]
+    pub fn with_fallback_locale(mut self, locale: impl Into<String>) -> Self {
+        self.fallback_locale = Some(locale.into());
+        self
+    }
+}
+
+impl Localizer for MessageCatalog {
+    fn localize(&self, locale: &str, key: &str, args: &[(String, String)]) -> Option<String> {
+        let message = self
+            .messages
+            .get(locale)
+            .and_then(|messages| messages.get(key))
+            .or_else(|| {
+                self.fallback_locale
+                    .as_ref()
+                    .and_then(|fallback| self.messages.get(fallback))
+                    .and_then(|messages| messages.get(key))
+            })?;
+        Some(format_message(message, args))
+    }
+}
+
+/// Replaces the `{ $name }` placeables of `message` with the value of the matching argument.
+/// Placeables without a matching argument are left as they are.
+fn format_message(message: &str, args: &[(String, String)]) -> String {
+    let mut formatted = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        formatted.push_str(&rest[..start]);
+        let placeable = &rest[start..=end];
+        let value = placeable[1..placeable.len() - 1]
+            .trim()
+            .strip_prefix('$')
+            .and_then(|name| args.iter().find(|(arg, _)| arg == name))
+            .map(|(_, value)| value.as_str());
+        formatted.push_str(value.unwrap_or(placeable));
+        rest = &rest[end + 1..];
+    }
+    formatted.push_str(rest);
+    formatted
+}
+
+/// Text whose content is looked up in the [`Localization`] resource.
+///
+/// Each frame where this component or the [`Localization`] changed, the sections of the
+/// entity's [`Text`] are replaced with the resolved messages. Keys without a message are
+/// displayed as they are.
+#[derive(Component, Debug, Default, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct LocalizedText {
+    pub sections: Vec<LocalizedSection>,
+}
+
+impl LocalizedText {
+    /// Constructs a [`LocalizedText`] with (initially) one section.
+    pub fn new(key: impl Into<String>, style: TextStyle) -> Self {
+        Self {
+            sections: vec![LocalizedSection::new(key, style)],
+        }
+    }
+
+    /// Sets the argument `name` of the first section, replacing any previous value for it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no sections.
+    #[must_use]
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.sections[0].set_arg(name, value);
+        self
+    }
+}
+
+/// A message of a [`LocalizedText`], resolved to a single [`TextSection`].
+#[derive(Debug, Default, Clone, FromReflect, Reflect)]
+pub struct LocalizedSection {
+    pub key: String,
+    pub args: Vec<(String, String)>,
+    pub style: TextStyle,
+}
+
+impl LocalizedSection {
+    pub fn new(key: impl Into<String>, style: TextStyle) -> Self {
+        Self {
+            key: key.into(),
+            args: Vec::new(),
+            style,
+        }
+    }
+
+    /// Sets the argument `name`, replacing any previous value for it.
+    pub fn set_arg(&mut self, name: impl Into<String>, value: impl ToString) {
+        let name = name.into();
+        let value = value.to_string();
+        match self.args.iter_mut().find(|(arg, _)| *arg == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.args.push((name, value)),
+        }
+    }
+}
+
+/// Resolves the sections of [`LocalizedText`] into their [`Text`] when either changes.
+pub fn update_localized_text(
+    localization: Res<Localization>,
+    mut query: Query<(ChangeTrackers<LocalizedText>, &LocalizedText, &mut Text)>,
+) {
+    let localization_changed = localization.is_changed();
+    for (tracker, localized_text, mut text) in query.iter_mut() {
+        if !localization_changed && !tracker.is_changed() {
+            continue;
+        }
+        text.sections = localized_text
+            .sections
+            .iter()
+            .map(|section| {
+                let value = localization
+                    .localize(&section.key, &section.args)
+                    .unwrap_or_else(|| {
+                        warn!(
+                            "No message for {:?} in locale {:?}",
+                            section.key,
+                            localization.locale()
+                        );
+                        section.key.clone()
+                    });
+                TextSection::new(value, section.style.clone())
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[(&str, &str)]) -> Vec<(String, String)> {
+        args.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn placeables_are_replaced_by_their_argument() {
+        let points = args(&[("points", "42"), ("name", "Ferris")]);
+        assert_eq!(format_message("Score: { $points }", &points), "Score: 42");
+        assert_eq!(format_message("Score: {$points}", &points), "Score: 42");
+        assert_eq!(
+            format_message("{ $name } scored { $points }!", &points),
+            "Ferris scored 42!"
+        );
+        assert_eq!(
+            format_message("Gagné\u{a0}: { $name }", &points),
+            "Gagné\u{a0}: Ferris"
+        );
+        assert_eq!(format_message("No placeables", &points), "No placeables");
+    }
+
+    #[test]
+    fn unknown_placeables_are_kept() {
+        let points = args(&[("points", "42")]);
+        assert_eq!(
+            format_message("{ $missing } { points } { $points }", &points),
+            "{ $missing } { points } 42"
+        );
+        // An unclosed placeable is not a placeable
+        assert_eq!(format_message("{ $points } {", &points), "42 {");
+        assert_eq!(format_message("{ $points", &points), "{ $points");
+    }
+
+    #[test]
+    fn missing_messages_use_the_fallback_locale() {
+        let catalog = MessageCatalog::default()
+            .with_message("en-US", "score", "Score: { $points }")
+            .with_message("en-US", "quit", "Quit")
+            .with_message("fr-FR", "score", "Points : { $points }");
+        let points = args(&[("points", "7")]);
+
+        assert_eq!(
+            catalog.localize("fr-FR", "score", &points).as_deref(),
+            Some("Points : 7")
+        );
+        assert_eq!(catalog.localize("fr-FR", "quit", &points), None);
+
+        let catalog = catalog.with_fallback_locale("en-US");
+        assert_eq!(
+            catalog.localize("fr-FR", "quit", &points).as_deref(),
+            Some("Quit")
+        );
+        assert_eq!(catalog.localize("fr-FR", "missing", &points), None);
+    }
+}
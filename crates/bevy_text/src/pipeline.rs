@@ -1,23 +1,48 @@
-use std::hash::Hash;
+use std::{
+    hash::{BuildHasher, Hash, Hasher},
+    ops::Range,
+};
 
-use ab_glyph::{PxScale, ScaleFont};
+use ab_glyph::{point, FontArc, Glyph, PxScale, PxScaleFont, ScaleFont};
 use bevy_asset::{Assets, Handle, HandleId};
+use bevy_ecs::component::Component;
 use bevy_math::Vec2;
+use bevy_reflect::TypeUuid;
 use bevy_render::texture::Image;
-use bevy_sprite::TextureAtlas;
-use bevy_utils::HashMap;
+use bevy_sprite::{Rect, TextureAtlas};
+use bevy_utils::{hashbrown::hash_map::Entry, FixedState, FloatOrd, HashMap, HashSet};
 
-use glyph_brush_layout::{FontId, SectionText};
+use glyph_brush_layout::{FontId, SectionGlyph, SectionText};
 
 use crate::{
-    error::TextError, glyph_brush::GlyphBrush, scale_value, Font, FontAtlasSet, PositionedGlyph,
-    TextAlignment, TextSection,
+    bidi, error::TextError, glyph_brush::GlyphBrush, scale_value, shaping, Font, FontAtlasSet,
+    FontAtlasSettings, FontAxis, HorizontalAlign, PositionedGlyph, Text, TextAlignment,
+    TextDirection, TextReveal, TextSection,
 };
 
 pub struct TextPipeline<ID> {
     brush: GlyphBrush,
     glyph_map: HashMap<ID, TextLayoutInfo>,
-    map_font_id: HashMap<HandleId, FontId>,
+    map_font_id: HashMap<FontInstanceKey, FontId>,
+}
+
+/// Identifies a font along with the values of its variation axes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct FontInstanceKey {
+    font: HandleId,
+    variations: Vec<(FontAxis, u32)>,
+}
+
+impl FontInstanceKey {
+    fn new(font: HandleId, variations: &[(FontAxis, f32)]) -> Self {
+        Self {
+            font,
+            variations: variations
+                .iter()
+                .map(|(axis, value)| (*axis, value.to_bits()))
+                .collect(),
+        }
+    }
 }
 
 impl<ID> Default for TextPipeline<ID> {
@@ -30,48 +55,548 @@ impl<ID> Default for TextPipeline<ID> {
     }
 }
 
+/// The positioned glyphs and overall size of a block of text, as computed by
+/// [`TextPipeline::queue_text`].
+///
+/// When present on an entity, this component is kept up to date by the text layout systems
+/// whenever the text is laid out again.
+#[derive(Component, Clone, Debug, Default)]
 pub struct TextLayoutInfo {
     pub glyphs: Vec<PositionedGlyph>,
     pub size: Vec2,
+    /// The laid-out characters, in visual order, including whitespace that has no glyph.
+    pub characters: Vec<TextCharacterInfo>,
+    /// The laid-out lines, from top to bottom.
+    pub lines: Vec<TextLineInfo>,
+}
+
+/// The position of a single laid-out character, used to place carets and to hit test text.
+///
+/// Positions are in the same space as [`PositionedGlyph::position`]: in physical pixels, with
+/// the origin at the bottom left of the text and y pointing up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextCharacterInfo {
+    /// The byte index of the character in the value of all the sections joined together, as
+    /// returned by [`Text::value`].
+    pub byte_index: usize,
+    /// The length of the character in bytes.
+    pub byte_len: usize,
+    /// The index of the line the character is on.
+    pub line_index: usize,
+    /// The left edge of the character.
+    pub x: f32,
+    /// The horizontal advance of the character.
+    pub advance: f32,
+    /// Whether the character is part of a right-to-left run, in which case its logical start is
+    /// its right edge.
+    pub rtl: bool,
+}
+
+impl TextCharacterInfo {
+    /// The x position of the caret placed before this character.
+    pub fn leading_edge(&self) -> f32 {
+        if self.rtl {
+            self.x + self.advance
+        } else {
+            self.x
+        }
+    }
+
+    /// The x position of the caret placed after this character.
+    pub fn trailing_edge(&self) -> f32 {
+        if self.rtl {
+            self.x
+        } else {
+            self.x + self.advance
+        }
+    }
+}
+
+/// A single laid-out line, e.g. to draw selection rectangles.
+#[derive(Clone, Debug)]
+pub struct TextLineInfo {
+    /// The bounds of the line, from the left edge of its first character to the advance of its
+    /// last, and from its highest ascent to its lowest descent.
+    pub bounds: Rect,
+    /// The y position of the baseline of the line.
+    pub baseline: f32,
+    /// The bytes of the text laid out on this line, as byte indices in [`Text::value`].
+    pub byte_range: Range<usize>,
+}
+
+/// Where to draw a caret, as returned by [`TextLayoutInfo::caret_position`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextCaret {
+    /// The bottom of the caret.
+    pub position: Vec2,
+    /// The height of the caret, which is the height of its line.
+    pub height: f32,
+    /// The index of the line the caret is on.
+    pub line_index: usize,
+}
+
+impl TextLayoutInfo {
+    /// Iterates over the glyphs that are visible with the given [`TextReveal`], with their index
+    /// in [`TextLayoutInfo::glyphs`], e.g. to look up their [`GlyphModifier`](crate::GlyphModifier).
+    pub fn revealed_glyphs<'a>(
+        &'a self,
+        reveal: Option<&'a TextReveal>,
+    ) -> impl Iterator<Item = (usize, &'a PositionedGlyph)> {
+        self.glyphs
+            .iter()
+            .enumerate()
+            .filter(move |(_, glyph)| reveal.map_or(true, |reveal| reveal.is_visible(glyph)))
+    }
+
+    /// Returns where to draw a caret placed before the character at `byte_index` in
+    /// [`Text::value`], or after the last character when `byte_index` is its length.
+    ///
+    /// A byte index that doesn't start a laid-out character, e.g. the index of a line break,
+    /// places the caret after the preceding character. Returns `None` if no characters were laid
+    /// out.
+    pub fn caret_position(&self, byte_index: usize) -> Option<TextCaret> {
+        let (character, x) = match self
+            .characters
+            .iter()
+            .find(|character| character.byte_index == byte_index)
+        {
+            Some(character) => (character, character.leading_edge()),
+            None => {
+                let character = self
+                    .characters
+                    .iter()
+                    .filter(|character| character.byte_index < byte_index)
+                    .max_by_key(|character| character.byte_index)
+                    .or_else(|| self.characters.first())?;
+                let x = if character.byte_index < byte_index {
+                    character.trailing_edge()
+                } else {
+                    character.leading_edge()
+                };
+                (character, x)
+            }
+        };
+        let line = &self.lines[character.line_index];
+        Some(TextCaret {
+            position: Vec2::new(x, line.bounds.min.y),
+            height: line.bounds.height(),
+            line_index: character.line_index,
+        })
+    }
+
+    /// Returns the byte index in [`Text::value`] of the caret position closest to `point`, in
+    /// the same space as [`PositionedGlyph::position`].
+    ///
+    /// Points above or below the text hit the first or last line. Returns `None` if no characters
+    /// were laid out.
+    pub fn hit_test(&self, point: Vec2) -> Option<usize> {
+        let line_index = self
+            .lines
+            .iter()
+            .position(|line| point.y >= line.bounds.min.y)
+            .unwrap_or(self.lines.len().saturating_sub(1));
+        let character = self
+            .characters
+            .iter()
+            .filter(|character| character.line_index == line_index)
+            .min_by_key(|character| {
+                FloatOrd((character.x + character.advance * 0.5 - point.x).abs())
+            })?;
+        let before_middle = point.x < character.x + character.advance * 0.5;
+        if before_middle != character.rtl {
+            Some(character.byte_index)
+        } else {
+            Some(character.byte_index + character.byte_len)
+        }
+    }
+}
+
+/// The size and per-line metrics of a block of text, as computed by
+/// [`TextPipeline::measure_text`].
+///
+/// All values are in physical pixels, i.e. already multiplied by the scale factor that was
+/// passed in.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextMeasureInfo {
+    /// The size of the bounding box of the laid-out text.
+    pub size: Vec2,
+    /// The metrics of each line of text, from top to bottom.
+    pub lines: Vec<TextLineMetrics>,
+}
+
+/// The metrics of a single laid-out line of text.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TextLineMetrics {
+    /// The width of the line, from the left edge of its first glyph to the advance of its last.
+    pub width: f32,
+    /// The distance from the top of the line to its baseline.
+    pub ascent: f32,
+    /// The distance from the baseline to the bottom of the line. This is usually negative.
+    pub descent: f32,
+    /// The vertical offset of the top of the line, relative to the top of the text block.
+    pub top: f32,
+    /// The number of glyphs laid out on this line.
+    pub glyph_count: usize,
+}
+
+impl TextLineMetrics {
+    /// The total height of the line.
+    #[inline]
+    pub fn height(&self) -> f32 {
+        self.ascent - self.descent
+    }
 }
 
 impl<ID: Hash + Eq> TextPipeline<ID> {
     pub fn get_or_insert_font_id(&mut self, handle: &Handle<Font>, font: &Font) -> FontId {
+        self.get_or_insert_font_instance_id(handle, font, &[])
+    }
+
+    /// Returns the id of the instance of `font` with the given variation axes set, registering it
+    /// with the glyph brush if needed.
+    ///
+    /// Each instance caches its glyphs in its own [`FontAtlasSet`], so that different weights of
+    /// the same font don't collide.
+    pub fn get_or_insert_font_instance_id(
+        &mut self,
+        handle: &Handle<Font>,
+        font: &Font,
+        variations: &[(FontAxis, f32)],
+    ) -> FontId {
         let brush = &mut self.brush;
-        *self
-            .map_font_id
-            .entry(handle.id)
-            .or_insert_with(|| brush.add_font(handle.clone(), font.font.clone()))
+        let key = FontInstanceKey::new(handle.id, variations);
+        match self.map_font_id.entry(key) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                let font_id = if variations.is_empty() {
+                    brush.add_font(handle.clone(), font.font.clone())
+                } else {
+                    let mut hasher = FixedState.build_hasher();
+                    entry.key().hash(&mut hasher);
+                    let font_atlas_set =
+                        Handle::weak(HandleId::new(FontAtlasSet::TYPE_UUID, hasher.finish()));
+                    brush.add_font_instance(
+                        handle.clone(),
+                        font.with_variations(variations),
+                        font_atlas_set,
+                    )
+                };
+                *entry.insert(font_id)
+            }
+        }
     }
 
     pub fn get_glyphs(&self, id: &ID) -> Option<&TextLayoutInfo> {
         self.glyph_map.get(id)
     }
 
+    /// Returns whether the layout stored for `id` has glyphs in any of the given texture atlases.
+    ///
+    /// This is used to lay out text again when atlases are evicted from a [`FontAtlasSet`].
+    pub fn uses_texture_atlases(&self, id: &ID, texture_atlases: &HashSet<HandleId>) -> bool {
+        self.glyph_map.get(id).map_or(false, |layout| {
+            layout
+                .glyphs
+                .iter()
+                .any(|glyph| texture_atlases.contains(&glyph.atlas_info.texture_atlas.id))
+        })
+    }
+
+    /// Lays out the given [`Text`] without rasterizing any glyphs or storing the result, and
+    /// returns its size along with the metrics of each line.
+    ///
+    /// This can be used to find out how much space a piece of text will take up before spawning
+    /// an entity for it, e.g. to position a tooltip.
+    pub fn measure_text(
+        &mut self,
+        fonts: &Assets<Font>,
+        text: &Text,
+        scale_factor: f64,
+        bounds: Vec2,
+    ) -> Result<TextMeasureInfo, TextError> {
+        let layout = self.layout(
+            fonts,
+            &text.sections,
+            scale_factor,
+            text.alignment,
+            text.direction,
+            bounds,
+        )?;
+
+        if layout.glyphs.is_empty() {
+            return Ok(TextMeasureInfo::default());
+        }
+
+        let size = layout.size();
+        let min_y = (0..layout.glyphs.len())
+            .map(|index| layout.baseline(index) - layout.scaled_font(index).ascent())
+            .fold(f32::MAX, f32::min);
+
+        let mut lines: Vec<TextLineMetrics> = Vec::new();
+        let mut line_y = None;
+        let mut line_min_x = f32::MAX;
+        let mut line_max_x = f32::MIN;
+        for (index, sg) in layout.glyphs.iter().enumerate() {
+            let scaled_font = layout.scaled_font(index);
+            let baseline = layout.baseline(index);
+            // Every glyph of a line is placed on the same baseline
+            if line_y != Some(baseline) {
+                if let Some(line) = lines.last_mut() {
+                    line.width = line_max_x - line_min_x;
+                }
+                lines.push(TextLineMetrics {
+                    top: baseline,
+                    ..Default::default()
+                });
+                line_y = Some(baseline);
+                line_min_x = f32::MAX;
+                line_max_x = f32::MIN;
+            }
+            let line = lines.last_mut().unwrap();
+            line.ascent = line.ascent.max(scaled_font.ascent());
+            line.descent = line.descent.min(scaled_font.descent());
+            line.glyph_count += 1;
+            line_min_x = line_min_x.min(sg.glyph.position.x);
+            line_max_x = line_max_x.max(sg.glyph.position.x + layout.advance(index));
+        }
+        if let Some(line) = lines.last_mut() {
+            line.width = line_max_x - line_min_x;
+        }
+        for line in &mut lines {
+            // `top` currently holds the baseline of the line
+            line.top = line.top - line.ascent - min_y;
+        }
+
+        Ok(TextMeasureInfo { size, lines })
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn queue_text(
         &mut self,
         id: ID,
         fonts: &Assets<Font>,
-        sections: &[TextSection],
+        text_sections: &[TextSection],
         scale_factor: f64,
         text_alignment: TextAlignment,
+        text_direction: TextDirection,
         bounds: Vec2,
         font_atlas_set_storage: &mut Assets<FontAtlasSet>,
         texture_atlases: &mut Assets<TextureAtlas>,
         textures: &mut Assets<Image>,
+        font_atlas_settings: &FontAtlasSettings,
     ) -> Result<(), TextError> {
+        let layout = self.layout(
+            fonts,
+            text_sections,
+            scale_factor,
+            text_alignment,
+            text_direction,
+            bounds,
+        )?;
+
+        if layout.glyphs.is_empty() {
+            self.glyph_map.insert(id, TextLayoutInfo::default());
+            return Ok(());
+        }
+
+        let size = layout.size();
+        let (characters, lines) = layout.characters(text_sections);
+
+        let glyphs = self.brush.process_glyphs(
+            layout.glyphs,
+            &layout.sections,
+            font_atlas_set_storage,
+            fonts,
+            texture_atlases,
+            textures,
+            font_atlas_settings,
+        )?;
+
+        self.glyph_map.insert(
+            id,
+            TextLayoutInfo {
+                glyphs,
+                size,
+                characters,
+                lines,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Wraps and aligns the sections, then reorders and shapes each line if the text contains
+    /// right-to-left content.
+    fn layout<'a>(
+        &mut self,
+        fonts: &Assets<Font>,
+        text_sections: &'a [TextSection],
+        scale_factor: f64,
+        alignment: TextAlignment,
+        direction: TextDirection,
+        bounds: Vec2,
+    ) -> Result<Layout<'a>, TextError> {
+        let horizontal = alignment
+            .horizontal
+            .resolve(bidi::is_rtl(text_sections, direction));
+        let alignment = TextAlignment {
+            horizontal,
+            ..alignment
+        };
+
         let mut scaled_fonts = Vec::new();
-        let sections = sections
+        let sections = self.section_texts(fonts, text_sections, scale_factor, &mut scaled_fonts)?;
+        let glyphs = self.brush.compute_glyphs(&sections, bounds, alignment)?;
+        let mut layout = Layout {
+            sections,
+            scaled_fonts,
+            glyphs,
+            shaped: None,
+        };
+        if layout.glyphs.is_empty() {
+            return Ok(layout);
+        }
+
+        let mut section_starts = Vec::with_capacity(text_sections.len());
+        let mut start = 0;
+        for section in text_sections {
+            section_starts.push(start);
+            start += section.value.len();
+        }
+
+        // The glyphs of each line, and the bytes they were laid out from in the joined text
+        let mut line_glyphs: Vec<Range<usize>> = Vec::new();
+        let mut line_bytes: Vec<Range<usize>> = Vec::new();
+        for (index, sg) in layout.glyphs.iter().enumerate() {
+            let byte_start = section_starts[sg.section_index] + sg.byte_index;
+            let byte_end = byte_start
+                + text_sections[sg.section_index].value[sg.byte_index..]
+                    .chars()
+                    .next()
+                    .map_or(0, char::len_utf8);
+            // glyph_brush_layout places every glyph of a line on the same baseline
+            let same_line = line_glyphs.last().map_or(false, |glyphs| {
+                layout.glyphs[glyphs.start].glyph.position.y == sg.glyph.position.y
+            });
+            if same_line {
+                line_glyphs.last_mut().unwrap().end = index + 1;
+                let bytes = line_bytes.last_mut().unwrap();
+                bytes.start = bytes.start.min(byte_start);
+                bytes.end = bytes.end.max(byte_end);
+            } else {
+                line_glyphs.push(index..index + 1);
+                line_bytes.push(byte_start..byte_end);
+            }
+        }
+
+        let visual_lines = match bidi::visual_runs(text_sections, direction, &line_bytes) {
+            Some(visual_lines) => visual_lines,
+            None => return Ok(layout),
+        };
+
+        let mut glyphs = Vec::with_capacity(layout.glyphs.len());
+        let mut shaped = Vec::with_capacity(layout.glyphs.len());
+        for (runs, line) in visual_lines.iter().zip(line_glyphs) {
+            let line_glyphs = &layout.glyphs[line.clone()];
+            let baseline = line_glyphs[0].glyph.position.y;
+            let left = line_glyphs
+                .iter()
+                .map(|sg| sg.glyph.position.x)
+                .fold(f32::MAX, f32::min);
+            let right = line
+                .clone()
+                .map(|index| layout.glyphs[index].glyph.position.x + layout.advance(index))
+                .fold(f32::MIN, f32::max);
+
+            let line_start = glyphs.len();
+            let mut pen = 0.0;
+            for run in runs {
+                let section = &text_sections[run.section_index];
+                let font = fonts
+                    .get(&section.style.font)
+                    .ok_or(TextError::NoSuchFont)?;
+                let text = &section.value[run.range.clone()];
+                let shaped_glyphs = shaping::shape(
+                    font,
+                    &section.style.variations,
+                    &layout.scaled_fonts[run.section_index],
+                    text,
+                    run.rtl,
+                );
+                for (index, shaped_glyph) in shaped_glyphs.iter().enumerate() {
+                    // A glyph covers the characters up to the next cluster, e.g. for ligatures
+                    let cluster_end = shaped_glyphs
+                        .iter()
+                        .map(|other| other.cluster)
+                        .filter(|cluster| *cluster > shaped_glyph.cluster)
+                        .min()
+                        .unwrap_or(text.len());
+                    let section_text = &layout.sections[run.section_index];
+                    glyphs.push(SectionGlyph {
+                        section_index: run.section_index,
+                        byte_index: run.range.start + shaped_glyph.cluster,
+                        glyph: Glyph {
+                            id: shaped_glyph.id,
+                            scale: section_text.scale,
+                            position: point(
+                                pen + shaped_glyph.offset.x,
+                                baseline + shaped_glyph.offset.y,
+                            ),
+                        },
+                        font_id: section_text.font_id,
+                    });
+                    shaped.push(ShapedGlyphInfo {
+                        advance: shaped_glyph.advance,
+                        byte_len: cluster_end - shaped_glyph.cluster,
+                        baseline,
+                        rtl: run.rtl,
+                        // Marks are drawn on the glyph of the preceding character
+                        is_continuation: index > 0
+                            && shaped_glyphs[index - 1].cluster == shaped_glyph.cluster,
+                    });
+                    pen += shaped_glyph.advance;
+                }
+            }
+
+            let line_left = match horizontal {
+                HorizontalAlign::Right => right - pen,
+                HorizontalAlign::Center => (left + right - pen) * 0.5,
+                _ => left,
+            };
+            for sg in &mut glyphs[line_start..] {
+                sg.glyph.position.x += line_left;
+            }
+        }
+
+        layout.glyphs = glyphs;
+        layout.shaped = Some(shaped);
+        Ok(layout)
+    }
+
+    fn section_texts<'a>(
+        &mut self,
+        fonts: &Assets<Font>,
+        sections: &'a [TextSection],
+        scale_factor: f64,
+        scaled_fonts: &mut Vec<PxScaleFont<FontArc>>,
+    ) -> Result<Vec<SectionText<'a>>, TextError> {
+        sections
             .iter()
             .map(|section| {
                 let font = fonts
                     .get(&section.style.font)
                     .ok_or(TextError::NoSuchFont)?;
-                let font_id = self.get_or_insert_font_id(&section.style.font, font);
+                let font_id = self.get_or_insert_font_instance_id(
+                    &section.style.font,
+                    font,
+                    &section.style.variations,
+                );
                 let font_size = scale_value(section.style.font_size, scale_factor);
 
-                scaled_fonts.push(ab_glyph::Font::as_scaled(&font.font, font_size));
+                scaled_fonts.push(ab_glyph::Font::into_scaled(
+                    self.brush.font(font_id).clone(),
+                    font_size,
+                ));
 
                 let section = SectionText {
                     font_id,
@@ -81,50 +606,469 @@ impl<ID: Hash + Eq> TextPipeline<ID> {
 
                 Ok(section)
             })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let section_glyphs = self
-            .brush
-            .compute_glyphs(&sections, bounds, text_alignment)?;
-
-        if section_glyphs.is_empty() {
-            self.glyph_map.insert(
-                id,
-                TextLayoutInfo {
-                    glyphs: Vec::new(),
-                    size: Vec2::ZERO,
-                },
-            );
-            return Ok(());
+            .collect()
+    }
+}
+
+/// How a glyph was shaped as part of bidirectional text.
+#[derive(Debug, Clone, Copy)]
+struct ShapedGlyphInfo {
+    advance: f32,
+    /// The length of the characters the glyph was shaped from, in bytes.
+    byte_len: usize,
+    /// The baseline of the line of the glyph, which may be offset from it.
+    baseline: f32,
+    rtl: bool,
+    /// Whether the glyph was shaped from the same characters as the preceding one, e.g. a mark.
+    is_continuation: bool,
+}
+
+/// Glyphs laid out by [`TextPipeline::layout`].
+struct Layout<'a> {
+    sections: Vec<SectionText<'a>>,
+    scaled_fonts: Vec<PxScaleFont<FontArc>>,
+    glyphs: Vec<SectionGlyph>,
+    /// Set for each glyph when the text was reordered and shaped as bidirectional text.
+    shaped: Option<Vec<ShapedGlyphInfo>>,
+}
+
+impl<'a> Layout<'a> {
+    fn scaled_font(&self, index: usize) -> &PxScaleFont<FontArc> {
+        &self.scaled_fonts[self.glyphs[index].section_index]
+    }
+
+    fn advance(&self, index: usize) -> f32 {
+        match &self.shaped {
+            Some(shaped) => shaped[index].advance,
+            None => self
+                .scaled_font(index)
+                .h_advance(self.glyphs[index].glyph.id),
         }
+    }
 
+    fn baseline(&self, index: usize) -> f32 {
+        match &self.shaped {
+            Some(shaped) => shaped[index].baseline,
+            None => self.glyphs[index].glyph.position.y,
+        }
+    }
+
+    fn size(&self) -> Vec2 {
         let mut min_x: f32 = std::f32::MAX;
         let mut min_y: f32 = std::f32::MAX;
         let mut max_x: f32 = std::f32::MIN;
         let mut max_y: f32 = std::f32::MIN;
 
-        for sg in &section_glyphs {
-            let scaled_font = scaled_fonts[sg.section_index];
-            let glyph = &sg.glyph;
-            min_x = min_x.min(glyph.position.x);
-            min_y = min_y.min(glyph.position.y - scaled_font.ascent());
-            max_x = max_x.max(glyph.position.x + scaled_font.h_advance(glyph.id));
-            max_y = max_y.max(glyph.position.y - scaled_font.descent());
+        for (index, sg) in self.glyphs.iter().enumerate() {
+            let scaled_font = self.scaled_font(index);
+            let baseline = self.baseline(index);
+            min_x = min_x.min(sg.glyph.position.x);
+            min_y = min_y.min(baseline - scaled_font.ascent());
+            max_x = max_x.max(sg.glyph.position.x + self.advance(index));
+            max_y = max_y.max(baseline - scaled_font.descent());
         }
 
-        let size = Vec2::new(max_x - min_x, max_y - min_y);
+        Vec2::new(max_x - min_x, max_y - min_y)
+    }
 
-        let glyphs = self.brush.process_glyphs(
-            section_glyphs,
-            &sections,
-            font_atlas_set_storage,
-            fonts,
-            texture_atlases,
-            textures,
-        )?;
+    /// Computes the position of each laid-out character and the bounds of each line, in the
+    /// same space as the positioned glyphs returned by [`GlyphBrush::process_glyphs`].
+    fn characters(
+        &self,
+        text_sections: &[TextSection],
+    ) -> (Vec<TextCharacterInfo>, Vec<TextLineInfo>) {
+        let mut section_starts = Vec::with_capacity(text_sections.len());
+        let mut start = 0;
+        for section in text_sections {
+            section_starts.push(start);
+            start += section.value.len();
+        }
 
-        self.glyph_map.insert(id, TextLayoutInfo { glyphs, size });
+        // Matches the origin of the positioned glyphs
+        let min_x = self
+            .glyphs
+            .iter()
+            .map(|sg| sg.glyph.position.x)
+            .fold(f32::MAX, f32::min)
+            .floor();
+        let max_y = (0..self.glyphs.len())
+            .map(|index| self.baseline(index) - self.scaled_font(index).descent())
+            .fold(f32::MIN, f32::max)
+            .floor();
 
-        Ok(())
+        let mut characters = Vec::with_capacity(self.glyphs.len());
+        let mut lines: Vec<TextLineInfo> = Vec::new();
+        let mut line_y = None;
+        for (index, sg) in self.glyphs.iter().enumerate() {
+            let shaped = self.shaped.as_ref().map(|shaped| shaped[index]);
+            if shaped.map_or(false, |shaped| shaped.is_continuation) {
+                continue;
+            }
+            let scaled_font = self.scaled_font(index);
+            let byte_len = match shaped {
+                Some(shaped) => shaped.byte_len,
+                None => self.sections[sg.section_index].text[sg.byte_index..]
+                    .chars()
+                    .next()
+                    .map_or(0, char::len_utf8),
+            };
+            let rtl = shaped.map_or(false, |shaped| shaped.rtl);
+            let byte_index = section_starts[sg.section_index] + sg.byte_index;
+
+            let x = sg.glyph.position.x - min_x;
+            let advance = self.advance(index);
+            let baseline = max_y - self.baseline(index);
+            let top = baseline + scaled_font.ascent();
+            let bottom = baseline + scaled_font.descent();
+
+            // Every glyph of a line is placed on the same baseline
+            if line_y != Some(baseline) {
+                line_y = Some(baseline);
+                lines.push(TextLineInfo {
+                    bounds: Rect {
+                        min: Vec2::new(x, bottom),
+                        max: Vec2::new(x + advance, top),
+                    },
+                    baseline,
+                    byte_range: byte_index..byte_index + byte_len,
+                });
+            }
+            let line = lines.last_mut().unwrap();
+            line.bounds.min = line.bounds.min.min(Vec2::new(x, bottom));
+            line.bounds.max = line.bounds.max.max(Vec2::new(x + advance, top));
+            line.byte_range.start = line.byte_range.start.min(byte_index);
+            line.byte_range.end = line.byte_range.end.max(byte_index + byte_len);
+
+            characters.push(TextCharacterInfo {
+                byte_index,
+                byte_len,
+                line_index: lines.len() - 1,
+                x,
+                advance,
+                rtl,
+            });
+        }
+
+        (characters, lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TextStyle;
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_tasks::IoTaskPool;
+
+    fn fonts_app() -> (App, Handle<Font>) {
+        IoTaskPool::init(Default::default);
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin).add_asset::<Font>();
+        let font = Font::try_from_bytes(
+            include_bytes!("../../../assets/fonts/FiraSans-Bold.ttf").to_vec(),
+        )
+        .unwrap();
+        let handle = app.world.resource_mut::<Assets<Font>>().add(font);
+        (app, handle)
+    }
+
+    fn text(value: &str, font: &Handle<Font>) -> Vec<TextSection> {
+        vec![TextSection::new(
+            value,
+            TextStyle {
+                font: font.clone(),
+                font_size: 20.0,
+                ..Default::default()
+            },
+        )]
+    }
+
+    #[test]
+    fn glyphs_are_revealed_in_reading_order() {
+        let (mut app, font) = fonts_app();
+        app.add_asset::<Image>()
+            .add_asset::<TextureAtlas>()
+            .add_asset::<FontAtlasSet>();
+        let mut textures = app.world.remove_resource::<Assets<Image>>().unwrap();
+        let mut texture_atlases = app.world.remove_resource::<Assets<TextureAtlas>>().unwrap();
+        let mut font_atlas_sets = app.world.remove_resource::<Assets<FontAtlasSet>>().unwrap();
+        let fonts = app.world.resource::<Assets<Font>>();
+        let mut pipeline = TextPipeline::<u32>::default();
+        pipeline
+            .queue_text(
+                0,
+                fonts,
+                &text("abc אבג", &font),
+                1.0,
+                TextAlignment::default(),
+                TextDirection::Auto,
+                Vec2::new(f32::MAX, f32::MAX),
+                &mut font_atlas_sets,
+                &mut texture_atlases,
+                &mut textures,
+                &FontAtlasSettings::default(),
+            )
+            .unwrap();
+        let layout = pipeline.get_glyphs(&0).unwrap();
+
+        let revealed = |visible_glyphs| {
+            let reveal = TextReveal { visible_glyphs };
+            layout
+                .revealed_glyphs(Some(&reveal))
+                .map(|(index, glyph)| (index, glyph.byte_index))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(revealed(0), vec![]);
+        // The first right to left glyph is the last one on the line
+        assert_eq!(revealed(4), vec![(0, 0), (1, 1), (2, 2), (5, 4)]);
+        assert_eq!(revealed(6).len(), 6);
+        assert_eq!(revealed(100), revealed(6));
+    }
+
+    #[test]
+    fn right_to_left_runs_are_reordered() {
+        let (app, font) = fonts_app();
+        let fonts = app.world.resource::<Assets<Font>>();
+        let mut pipeline = TextPipeline::<u32>::default();
+        let sections = text("abc אבג", &font);
+        let layout = pipeline
+            .layout(
+                fonts,
+                &sections,
+                1.0,
+                TextAlignment::default(),
+                TextDirection::Auto,
+                Vec2::new(f32::MAX, f32::MAX),
+            )
+            .unwrap();
+
+        let (characters, lines) = layout.characters(&sections);
+        let order: Vec<(usize, bool)> = characters
+            .iter()
+            .map(|character| (character.byte_index, character.rtl))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (0, false),
+                (1, false),
+                (2, false),
+                (3, false),
+                (8, true),
+                (6, true),
+                (4, true),
+            ]
+        );
+        assert!(characters
+            .windows(2)
+            .all(|pair| pair[0].x + pair[0].advance <= pair[1].x + 0.01));
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].byte_range, 0..10);
+    }
+
+    #[test]
+    fn wrapped_right_to_left_lines_keep_their_order() {
+        let (app, font) = fonts_app();
+        let fonts = app.world.resource::<Assets<Font>>();
+        let mut pipeline = TextPipeline::<u32>::default();
+        let sections = text("אבג דהו", &font);
+        let layout = pipeline
+            .layout(
+                fonts,
+                &sections,
+                1.0,
+                TextAlignment::default(),
+                TextDirection::Auto,
+                Vec2::new(1.0, f32::MAX),
+            )
+            .unwrap();
+
+        let (characters, lines) = layout.characters(&sections);
+        assert_eq!(lines.len(), 2);
+        // The first line holds the first word, displayed from right to left
+        let first_line: Vec<usize> = characters
+            .iter()
+            .filter(|character| character.line_index == 0 && character.byte_index < 6)
+            .map(|character| character.byte_index)
+            .collect();
+        assert_eq!(first_line, vec![4, 2, 0]);
+        let second_line: Vec<usize> = characters
+            .iter()
+            .filter(|character| character.line_index == 1)
+            .map(|character| character.byte_index)
+            .collect();
+        assert_eq!(second_line, vec![11, 9, 7]);
+    }
+
+    #[test]
+    fn start_alignment_follows_the_base_direction() {
+        let (app, font) = fonts_app();
+        let fonts = app.world.resource::<Assets<Font>>();
+        let mut pipeline = TextPipeline::<u32>::default();
+        let bounds = Vec2::new(f32::MAX, f32::MAX);
+
+        // Right-aligned text ends at the origin, left-aligned text starts from it
+        for (value, direction, rtl) in [
+            ("abc", TextDirection::Auto, false),
+            ("אבג", TextDirection::Auto, true),
+            ("abc", TextDirection::RightToLeft, true),
+        ] {
+            let sections = text(value, &font);
+            let layout = pipeline
+                .layout(
+                    fonts,
+                    &sections,
+                    1.0,
+                    TextAlignment::default(),
+                    direction,
+                    bounds,
+                )
+                .unwrap();
+            let left = layout.glyphs[0].glyph.position.x;
+            let last = layout.glyphs.len() - 1;
+            let right = layout.glyphs[last].glyph.position.x + layout.advance(last);
+            if rtl {
+                assert!(right.abs() < 0.01, "{value} ends at {right}");
+            } else {
+                assert!(left.abs() < 0.01, "{value} starts at {left}");
+            }
+        }
+    }
+
+    #[test]
+    fn measure_text_lines() {
+        let (app, font) = fonts_app();
+        let fonts = app.world.resource::<Assets<Font>>();
+        let mut pipeline = TextPipeline::<u32>::default();
+        let measure = |pipeline: &mut TextPipeline<u32>, value, scale_factor, width| {
+            pipeline
+                .measure_text(
+                    fonts,
+                    &Text {
+                        sections: text(value, &font),
+                        ..Default::default()
+                    },
+                    scale_factor,
+                    Vec2::new(width, f32::MAX),
+                )
+                .unwrap()
+        };
+
+        let single = measure(&mut pipeline, "hello world", 1.0, f32::MAX);
+        assert_eq!(single.lines.len(), 1);
+        let line = single.lines[0];
+        assert_eq!(line.glyph_count, 11);
+        assert_eq!(line.top, 0.0);
+        assert!(line.ascent > 0.0 && line.descent < 0.0);
+        assert!((line.width - single.size.x).abs() < 0.01);
+
+        // Wrapped in two lines, one under the other
+        let wrapped = measure(&mut pipeline, "hello world", 1.0, single.size.x * 0.75);
+        assert_eq!(wrapped.lines.len(), 2);
+        let (first, second) = (wrapped.lines[0], wrapped.lines[1]);
+        assert_eq!(first.glyph_count + second.glyph_count, 11);
+        assert!(first.width < single.size.x && second.width < single.size.x);
+        assert!((second.top - first.height()).abs() < 0.01);
+        assert!(wrapped.size.y > single.size.y);
+
+        // The metrics are in physical pixels
+        let scaled = measure(&mut pipeline, "hello world", 2.0, f32::MAX);
+        assert!((scaled.size.x - single.size.x * 2.0).abs() < 0.5);
+        assert!((scaled.lines[0].ascent - line.ascent * 2.0).abs() < 0.01);
+
+        // Nothing is stored for measured text
+        assert!(pipeline.get_glyphs(&0).is_none());
+        assert_eq!(
+            measure(&mut pipeline, "", 1.0, f32::MAX),
+            TextMeasureInfo::default()
+        );
+    }
+
+    fn layout_info(value: &str, width: f32) -> TextLayoutInfo {
+        let (app, font) = fonts_app();
+        let fonts = app.world.resource::<Assets<Font>>();
+        let mut pipeline = TextPipeline::<u32>::default();
+        let sections = text(value, &font);
+        let layout = pipeline
+            .layout(
+                fonts,
+                &sections,
+                1.0,
+                TextAlignment::default(),
+                TextDirection::Auto,
+                Vec2::new(width, f32::MAX),
+            )
+            .unwrap();
+        let (characters, lines) = layout.characters(&sections);
+        TextLayoutInfo {
+            characters,
+            lines,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn carets_are_placed_between_characters() {
+        // One word on each line
+        let info = layout_info("ab cd", 1.0);
+        assert_eq!(info.lines.len(), 2);
+        let (first_line, second_line) = (&info.lines[0], &info.lines[1]);
+        assert!(first_line.bounds.min.y > second_line.bounds.min.y);
+
+        let start = info.caret_position(0).unwrap();
+        assert_eq!(start.line_index, 0);
+        assert!((start.position.x - first_line.bounds.min.x).abs() < 0.01);
+        assert_eq!(start.position.y, first_line.bounds.min.y);
+        assert_eq!(start.height, first_line.bounds.height());
+
+        let c = info.caret_position(3).unwrap();
+        assert_eq!(c.line_index, 1);
+        assert!((c.position.x - second_line.bounds.min.x).abs() < 0.01);
+        assert_eq!(c.position.y, second_line.bounds.min.y);
+
+        let end = info.caret_position(5).unwrap();
+        assert_eq!(end.line_index, 1);
+        assert!((end.position.x - second_line.bounds.max.x).abs() < 0.01);
+        // Past the end of the text, the caret stays after the last character
+        assert_eq!(info.caret_position(100), Some(end));
+
+        // Right to left text starts on the right
+        let info = layout_info("אבג", f32::MAX);
+        let start = info.caret_position(0).unwrap();
+        let end = info.caret_position(6).unwrap();
+        assert!((start.position.x - info.lines[0].bounds.max.x).abs() < 0.01);
+        assert!((end.position.x - info.lines[0].bounds.min.x).abs() < 0.01);
+
+        assert_eq!(TextLayoutInfo::default().caret_position(0), None);
+    }
+
+    #[test]
+    fn hit_test_finds_the_closest_caret() {
+        let info = layout_info("ab cd", 1.0);
+        let (first_line, second_line) = (&info.lines[0], &info.lines[1]);
+        let first_y = first_line.bounds.min.y + 1.0;
+        let second_y = second_line.bounds.min.y + 1.0;
+
+        assert_eq!(info.hit_test(Vec2::new(-100.0, first_y)), Some(0));
+        assert_eq!(info.hit_test(Vec2::new(-100.0, second_y)), Some(3));
+        assert_eq!(info.hit_test(Vec2::new(1000.0, second_y)), Some(5));
+        // Just before and after the middle of "b"
+        let b = info.characters[1];
+        let middle = b.x + b.advance * 0.5;
+        assert_eq!(info.hit_test(Vec2::new(middle - 0.1, first_y)), Some(1));
+        assert_eq!(info.hit_test(Vec2::new(middle + 0.1, first_y)), Some(2));
+
+        // Points above or below the text hit the first or last line
+        assert_eq!(info.hit_test(Vec2::new(-100.0, 1000.0)), Some(0));
+        assert_eq!(info.hit_test(Vec2::new(1000.0, -1000.0)), Some(5));
+
+        // Right to left text starts on the right
+        let info = layout_info("אבג", f32::MAX);
+        let y = info.lines[0].bounds.min.y + 1.0;
+        assert_eq!(info.hit_test(Vec2::new(1000.0, y)), Some(0));
+        assert_eq!(info.hit_test(Vec2::new(-1000.0, y)), Some(6));
+
+        assert_eq!(TextLayoutInfo::default().hit_test(Vec2::ZERO), None);
     }
 }
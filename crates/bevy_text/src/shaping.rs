@@ -0,0 +1,92 @@
+use ab_glyph::{FontArc, GlyphId, PxScaleFont, ScaleFont};
+use bevy_math::Vec2;
+use rustybuzz::{ttf_parser::Tag, Direction, Face, UnicodeBuffer, Variation};
+
+use crate::{Font, FontAxis};
+
+/// A glyph returned by [`shape`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ShapedGlyph {
+    pub id: GlyphId,
+    /// The byte index in the shaped text of the first character this glyph was shaped from.
+    pub cluster: usize,
+    /// The distance to move the pen after drawing this glyph, in pixels.
+    pub advance: f32,
+    /// The offset of the glyph from the pen position, in pixels, with y pointing down.
+    pub offset: Vec2,
+}
+
+/// Shapes `text` with the font, applying the contextual forms, ligatures and mark positioning
+/// of its script, e.g. to join Arabic letters.
+///
+/// The glyphs are returned in visual order, with `rtl` text reversed. If the shaper cannot read
+/// the font, the glyphs of the characters are returned without shaping.
+pub(crate) fn shape(
+    font: &Font,
+    variations: &[(FontAxis, f32)],
+    scaled_font: &PxScaleFont<FontArc>,
+    text: &str,
+    rtl: bool,
+) -> Vec<ShapedGlyph> {
+    let mut face = match Face::from_slice(ab_glyph::Font::font_data(&font.font), font.face_index) {
+        Some(face) => face,
+        None => return shape_unsupported(scaled_font, text, rtl),
+    };
+    let variations: Vec<Variation> = variations
+        .iter()
+        .map(|(axis, value)| Variation {
+            tag: Tag::from_bytes(&axis.0),
+            value: *value,
+        })
+        .collect();
+    face.set_variations(&variations);
+
+    let mut buffer = UnicodeBuffer::new();
+    buffer.push_str(text);
+    buffer.set_direction(if rtl {
+        Direction::RightToLeft
+    } else {
+        Direction::LeftToRight
+    });
+    let output = rustybuzz::shape(&face, &[], buffer);
+
+    let scale = Vec2::new(scaled_font.h_scale_factor(), scaled_font.v_scale_factor());
+    output
+        .glyph_infos()
+        .iter()
+        .zip(output.glyph_positions())
+        .map(|(info, position)| ShapedGlyph {
+            id: GlyphId(info.glyph_id as u16),
+            cluster: info.cluster as usize,
+            advance: position.x_advance as f32 * scale.x,
+            offset: Vec2::new(
+                position.x_offset as f32 * scale.x,
+                -position.y_offset as f32 * scale.y,
+            ),
+        })
+        .collect()
+}
+
+/// Maps each character of `text` to its glyph, in visual order.
+fn shape_unsupported(
+    scaled_font: &PxScaleFont<FontArc>,
+    text: &str,
+    rtl: bool,
+) -> Vec<ShapedGlyph> {
+    let mut glyphs: Vec<ShapedGlyph> = text
+        .char_indices()
+        .map(|(cluster, c)| {
+            let id = scaled_font.glyph_id(c);
+            ShapedGlyph {
+                id,
+                cluster,
+                advance: scaled_font.h_advance(id),
+                offset: Vec2::ZERO,
+            }
+        })
+        .collect();
+    if rtl {
+        glyphs.reverse();
+    }
+    glyphs
+}
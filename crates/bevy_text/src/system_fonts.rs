@@ -0,0 +1,185 @@
+//! Enumeration and loading of the fonts installed on the operating system.
+//!
+//! Insert the [`SystemFonts`] resource to scan the system for fonts, then look them up by family
+//! name with a [`FontQuery`]:
+//!
+//! ```
+//! # use bevy_asset::Assets;
+//! # use bevy_ecs::system::{Res, ResMut};
+//! # use bevy_text::{Font, system_fonts::{FontQuery, SystemFonts}};
+//! fn load_title_font(system_fonts: Res<SystemFonts>, mut fonts: ResMut<Assets<Font>>) {
+//!     let handle = system_fonts.load(&FontQuery::family("DejaVu Sans").bold(), &mut fonts);
+//! }
+//! ```
+
+use bevy_asset::{Assets, Handle};
+use bevy_utils::tracing::warn;
+use fontdb::{Database, Family, Query};
+
+use crate::Font;
+
+/// The fonts installed on the system.
+///
+/// The system is scanned for fonts when this resource is created, which can take a while if a lot
+/// of fonts are installed.
+pub struct SystemFonts {
+    database: Database,
+}
+
+impl Default for SystemFonts {
+    fn default() -> Self {
+        let mut database = Database::new();
+        database.load_system_fonts();
+        Self { database }
+    }
+}
+
+/// The weight of a font, from `1` to `1000`, as used by CSS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: FontWeight = FontWeight(100);
+    pub const LIGHT: FontWeight = FontWeight(300);
+    pub const NORMAL: FontWeight = FontWeight(400);
+    pub const MEDIUM: FontWeight = FontWeight(500);
+    pub const BOLD: FontWeight = FontWeight(700);
+    pub const BLACK: FontWeight = FontWeight(900);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+/// Whether a font is upright or slanted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FontStyle {
+    #[default]
+    Normal,
+    Italic,
+    Oblique,
+}
+
+impl From<FontStyle> for fontdb::Style {
+    fn from(style: FontStyle) -> Self {
+        match style {
+            FontStyle::Normal => fontdb::Style::Normal,
+            FontStyle::Italic => fontdb::Style::Italic,
+            FontStyle::Oblique => fontdb::Style::Oblique,
+        }
+    }
+}
+
+impl From<fontdb::Style> for FontStyle {
+    fn from(style: fontdb::Style) -> Self {
+        match style {
+            fontdb::Style::Normal => FontStyle::Normal,
+            fontdb::Style::Italic => FontStyle::Italic,
+            fontdb::Style::Oblique => FontStyle::Oblique,
+        }
+    }
+}
+
+/// Describes the system font to look up. The closest matching face of the family is used.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct FontQuery {
+    pub family: String,
+    pub weight: FontWeight,
+    pub style: FontStyle,
+}
+
+impl FontQuery {
+    /// Creates a query for the regular face of the given family.
+    pub fn family(family: impl Into<String>) -> Self {
+        Self {
+            family: family.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns this query with the given weight.
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Returns this query for the bold face of the family.
+    pub fn bold(self) -> Self {
+        self.weight(FontWeight::BOLD)
+    }
+
+    /// Returns this query with the given style.
+    pub fn style(mut self, style: FontStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Returns this query for the italic face of the family.
+    pub fn italic(self) -> Self {
+        self.style(FontStyle::Italic)
+    }
+}
+
+/// A single font face installed on the system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemFontInfo {
+    pub family: String,
+    pub post_script_name: String,
+    pub weight: FontWeight,
+    pub style: FontStyle,
+    pub monospaced: bool,
+}
+
+impl SystemFonts {
+    /// Returns the names of all the font families installed on the system, sorted and without
+    /// duplicates.
+    pub fn families(&self) -> Vec<String> {
+        let mut families: Vec<String> = self
+            .database
+            .faces()
+            .filter_map(|face| face.families.first().map(|(name, _)| name.clone()))
+            .collect();
+        families.sort_unstable();
+        families.dedup();
+        families
+    }
+
+    /// Iterates over all the font faces installed on the system.
+    pub fn faces(&self) -> impl Iterator<Item = SystemFontInfo> + '_ {
+        self.database.faces().map(|face| SystemFontInfo {
+            family: face
+                .families
+                .first()
+                .map(|(name, _)| name.clone())
+                .unwrap_or_default(),
+            post_script_name: face.post_script_name.clone(),
+            weight: FontWeight(face.weight.0),
+            style: face.style.into(),
+            monospaced: face.monospaced,
+        })
+    }
+
+    /// Reads the face that best matches `query` from disk, or returns `None` if no face of the
+    /// requested family is installed.
+    pub fn get(&self, query: &FontQuery) -> Option<Font> {
+        let id = self.database.query(&Query {
+            families: &[Family::Name(&query.family)],
+            weight: fontdb::Weight(query.weight.0),
+            style: query.style.into(),
+            ..Default::default()
+        })?;
+        self.database
+            .with_face_data(id, |data, index| {
+                Font::try_from_bytes_and_index(data.to_vec(), index)
+            })?
+            .map_err(|err| warn!("Failed to load system font {:?}: {}", query, err))
+            .ok()
+    }
+
+    /// Reads the face that best matches `query` from disk and adds it to `fonts`.
+    pub fn load(&self, query: &FontQuery, fonts: &mut Assets<Font>) -> Option<Handle<Font>> {
+        self.get(query).map(|font| fonts.add(font))
+    }
+}
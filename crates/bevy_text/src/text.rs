@@ -1,16 +1,18 @@
 use bevy_asset::Handle;
 use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_math::Vec2;
 use bevy_reflect::{prelude::*, FromReflect};
 use bevy_render::color::Color;
 use serde::{Deserialize, Serialize};
 
-use crate::Font;
+use crate::{Font, PositionedGlyph};
 
 #[derive(Component, Debug, Default, Clone, Reflect)]
 #[reflect(Component, Default)]
 pub struct Text {
     pub sections: Vec<TextSection>,
     pub alignment: TextAlignment,
+    pub direction: TextDirection,
 }
 
 impl Text {
@@ -30,6 +32,7 @@ impl Text {
     ///         font: font_handle.clone(),
     ///         font_size: 60.0,
     ///         color: Color::WHITE,
+    ///         ..Default::default()
     ///     },
     ///     TextAlignment {
     ///         vertical: VerticalAlign::Center,
@@ -44,6 +47,7 @@ impl Text {
     ///         font: font_handle,
     ///         font_size: 60.0,
     ///         color: Color::WHITE,
+    ///         ..Default::default()
     ///     },
     ///     // you can still use Default
     ///     Default::default(),
@@ -60,8 +64,95 @@ impl Text {
                 style,
             }],
             alignment,
+            direction: TextDirection::Auto,
         }
     }
+
+    /// Constructs a [`Text`] from a list of sections.
+    ///
+    /// ```
+    /// # use bevy_asset::Handle;
+    /// # use bevy_render::color::Color;
+    /// # use bevy_text::{Font, Text, TextSection, TextStyle};
+    /// #
+    /// # let font_handle: Handle<Font> = Default::default();
+    /// #
+    /// let style = TextStyle::default().font(font_handle).size(40.0);
+    /// let score = Text::with_sections(
+    ///     [
+    ///         TextSection::new("Score: ", style.clone()),
+    ///         TextSection::new("0", style.color(Color::GOLD)),
+    ///     ],
+    ///     Default::default(),
+    /// );
+    /// ```
+    pub fn with_sections(
+        sections: impl IntoIterator<Item = TextSection>,
+        alignment: TextAlignment,
+    ) -> Self {
+        Self {
+            sections: sections.into_iter().collect(),
+            alignment,
+            direction: TextDirection::Auto,
+        }
+    }
+
+    /// Returns this [`Text`] with the given alignment.
+    #[must_use]
+    pub fn with_alignment(mut self, alignment: TextAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Returns this [`Text`] with the given base direction.
+    #[must_use]
+    pub fn with_direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Returns the horizontal alignment of this [`Text`], with [`HorizontalAlign::Start`] and
+    /// [`HorizontalAlign::End`] resolved against its base direction.
+    pub fn horizontal_alignment(&self) -> HorizontalAlign {
+        self.alignment
+            .horizontal
+            .resolve(crate::bidi::is_rtl(&self.sections, self.direction))
+    }
+
+    /// Appends a section to the end of this [`Text`].
+    pub fn push_section(&mut self, value: impl Into<String>, style: TextStyle) -> &mut Self {
+        self.sections.push(TextSection::new(value, style));
+        self
+    }
+
+    /// Replaces the text of the section at `index`, keeping its style.
+    ///
+    /// This is the usual way to update text that is made of static labels and changing values:
+    ///
+    /// ```
+    /// # use bevy_ecs::system::Query;
+    /// # use bevy_text::Text;
+    /// fn update_score(mut query: Query<&mut Text>) {
+    ///     for mut text in query.iter_mut() {
+    ///         text.set_section_value(1, 42.to_string());
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no section at `index`.
+    pub fn set_section_value(&mut self, index: usize, value: impl Into<String>) {
+        self.sections[index].value = value.into();
+    }
+
+    /// Returns the value of all the sections joined together.
+    pub fn value(&self) -> String {
+        self.sections
+            .iter()
+            .map(|section| section.value.as_str())
+            .collect()
+    }
 }
 
 #[derive(Debug, Default, Clone, FromReflect, Reflect)]
@@ -70,6 +161,15 @@ pub struct TextSection {
     pub style: TextStyle,
 }
 
+impl TextSection {
+    pub fn new(value: impl Into<String>, style: TextStyle) -> Self {
+        Self {
+            value: value.into(),
+            style,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Reflect)]
 pub struct TextAlignment {
     pub vertical: VerticalAlign,
@@ -80,7 +180,7 @@ impl Default for TextAlignment {
     fn default() -> Self {
         TextAlignment {
             vertical: VerticalAlign::Top,
-            horizontal: HorizontalAlign::Left,
+            horizontal: HorizontalAlign::Start,
         }
     }
 }
@@ -98,18 +198,71 @@ pub enum HorizontalAlign {
     /// Rightmost character is immetiately to the left of the render position.<br/>
     /// Bounds start from the render position and advance leftwards.
     Right,
+    /// Aligned to the side lines start from in the base direction of the text: [`Left`] for
+    /// left-to-right text and [`Right`] for right-to-left text.
+    ///
+    /// [`Left`]: HorizontalAlign::Left
+    /// [`Right`]: HorizontalAlign::Right
+    Start,
+    /// Aligned to the side lines end on in the base direction of the text: [`Right`] for
+    /// left-to-right text and [`Left`] for right-to-left text.
+    ///
+    /// [`Left`]: HorizontalAlign::Left
+    /// [`Right`]: HorizontalAlign::Right
+    End,
+}
+
+impl HorizontalAlign {
+    /// Returns the alignment of text with the given base direction, with [`HorizontalAlign::Start`]
+    /// and [`HorizontalAlign::End`] replaced by [`HorizontalAlign::Left`] or
+    /// [`HorizontalAlign::Right`].
+    pub fn resolve(self, rtl: bool) -> HorizontalAlign {
+        match (self, rtl) {
+            (HorizontalAlign::Start, false) | (HorizontalAlign::End, true) => HorizontalAlign::Left,
+            (HorizontalAlign::Start, true) | (HorizontalAlign::End, false) => {
+                HorizontalAlign::Right
+            }
+            (align, _) => align,
+        }
+    }
 }
 
 impl From<HorizontalAlign> for glyph_brush_layout::HorizontalAlign {
     fn from(val: HorizontalAlign) -> Self {
         match val {
-            HorizontalAlign::Left => glyph_brush_layout::HorizontalAlign::Left,
+            HorizontalAlign::Left | HorizontalAlign::Start => {
+                glyph_brush_layout::HorizontalAlign::Left
+            }
             HorizontalAlign::Center => glyph_brush_layout::HorizontalAlign::Center,
-            HorizontalAlign::Right => glyph_brush_layout::HorizontalAlign::Right,
+            HorizontalAlign::Right | HorizontalAlign::End => {
+                glyph_brush_layout::HorizontalAlign::Right
+            }
         }
     }
 }
 
+/// Describes the base direction in which text is laid out.
+///
+/// Right-to-left runs of text, e.g. in Arabic or Hebrew, are reordered for display according to
+/// the Unicode Bidirectional Algorithm, line by line after wrapping. The runs of text containing
+/// right-to-left content are then shaped, which joins Arabic letters. Lines are wrapped before
+/// shaping, using the advances of the unshaped glyphs.
+///
+/// The base direction also decides on which side [`HorizontalAlign::Start`] and
+/// [`HorizontalAlign::End`] align the text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect_value(Serialize, Deserialize)]
+pub enum TextDirection {
+    /// The direction of each paragraph is detected from its first strongly directional
+    /// character. The alignment of the text follows the direction of the first paragraph.
+    #[default]
+    Auto,
+    /// Lines are laid out left-to-right, with right-to-left runs reordered inside them.
+    LeftToRight,
+    /// Lines are laid out right-to-left, with left-to-right runs reordered inside them.
+    RightToLeft,
+}
+
 /// Describes vertical alignment preference for positioning & bounds. Currently a placeholder
 /// for future functionality.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
@@ -138,6 +291,10 @@ pub struct TextStyle {
     pub font: Handle<Font>,
     pub font_size: f32,
     pub color: Color,
+    /// The values of the variation axes to use if `font` is a variable font.
+    ///
+    /// Axes that aren't listed use the default value defined by the font.
+    pub variations: Vec<(FontAxis, f32)>,
 }
 
 impl Default for TextStyle {
@@ -146,6 +303,217 @@ impl Default for TextStyle {
             font: Default::default(),
             font_size: 12.0,
             color: Color::WHITE,
+            variations: Vec::new(),
         }
     }
 }
+
+impl TextStyle {
+    /// Returns this style with the given font.
+    #[must_use]
+    pub fn font(mut self, font: Handle<Font>) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Returns this style with the given font size.
+    #[must_use]
+    pub fn size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Returns this style with the given color.
+    #[must_use]
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Returns this style with the given variation axis set, replacing any previous value for it.
+    #[must_use]
+    pub fn variation(mut self, axis: FontAxis, value: f32) -> Self {
+        self.variations.retain(|(existing, _)| *existing != axis);
+        self.variations.push((axis, value));
+        self
+    }
+}
+
+/// The tag of a variation axis of a variable font, such as its weight or width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, FromReflect)]
+pub struct FontAxis(pub [u8; 4]);
+
+impl FontAxis {
+    /// The `wght` axis, typically ranging from `100` (thin) to `900` (black).
+    pub const WEIGHT: FontAxis = FontAxis(*b"wght");
+    /// The `wdth` axis, as a percentage of the normal width.
+    pub const WIDTH: FontAxis = FontAxis(*b"wdth");
+    /// The `slnt` axis, as an angle in counter-clockwise degrees.
+    pub const SLANT: FontAxis = FontAxis(*b"slnt");
+    /// The `ital` axis, from `0` (upright) to `1` (italic).
+    pub const ITALIC: FontAxis = FontAxis(*b"ital");
+    /// The `opsz` axis, the point size the glyphs are optimized for.
+    pub const OPTICAL_SIZE: FontAxis = FontAxis(*b"opsz");
+}
+
+/// Per-glyph adjustments applied to laid-out text when it is extracted for rendering, without
+/// laying the text out again.
+///
+/// Entries correspond by index to the glyphs of the entity's [`TextLayoutInfo`](crate::TextLayoutInfo).
+/// Glyphs without a corresponding entry are drawn unmodified. This can be updated every frame to
+/// animate text, e.g. to make it wave or shake.
+#[derive(Component, Debug, Default, Clone)]
+pub struct TextGlyphModifiers {
+    pub glyphs: Vec<GlyphModifier>,
+}
+
+impl TextGlyphModifiers {
+    /// Returns the modifier for the glyph at `index`, or an identity modifier if there is none.
+    pub fn get(&self, index: usize) -> GlyphModifier {
+        self.glyphs.get(index).copied().unwrap_or_default()
+    }
+}
+
+/// Limits how many glyphs of the entity's text are drawn, e.g. for a typewriter effect.
+///
+/// Glyphs are revealed in reading order. The text is only clipped when it is extracted for
+/// rendering, so increasing `visible_glyphs` every frame doesn't cause the text to be laid out
+/// again. Whitespace doesn't have a glyph, so it isn't counted.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct TextReveal {
+    pub visible_glyphs: usize,
+}
+
+impl TextReveal {
+    /// Returns whether a glyph is among the first [`TextReveal::visible_glyphs`] in reading
+    /// order.
+    pub fn is_visible(&self, glyph: &PositionedGlyph) -> bool {
+        glyph.reading_index < self.visible_glyphs
+    }
+}
+
+/// An adjustment applied to a single glyph, see [`TextGlyphModifiers`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GlyphModifier {
+    /// Offset added to the position of the glyph, in logical pixels.
+    pub offset: Vec2,
+    /// Overrides the color of the glyph's section if set.
+    pub color: Option<Color>,
+}
+
+impl GlyphModifier {
+    /// Returns the position of `glyph` once moved by this modifier, in physical pixels, and the
+    /// color to draw it with, given the `sections` it was laid out from.
+    pub fn apply(
+        &self,
+        glyph: &PositionedGlyph,
+        sections: &[TextSection],
+        scale_factor: f32,
+    ) -> (Vec2, Color) {
+        let color = self
+            .color
+            .unwrap_or(sections[glyph.section_index].style.color);
+        (
+            glyph.position + self.offset * scale_factor,
+            glyph.tint(color),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GlyphAtlasInfo;
+
+    #[test]
+    fn text_style_builders() {
+        let style = TextStyle::default()
+            .size(40.0)
+            .color(Color::GOLD)
+            .variation(FontAxis::WEIGHT, 400.0)
+            .variation(FontAxis::WIDTH, 75.0)
+            .variation(FontAxis::WEIGHT, 700.0);
+        assert_eq!(style.font_size, 40.0);
+        assert_eq!(style.color, Color::GOLD);
+        // Setting an axis again replaces its value
+        assert_eq!(
+            style.variations,
+            vec![(FontAxis::WIDTH, 75.0), (FontAxis::WEIGHT, 700.0)]
+        );
+    }
+
+    #[test]
+    fn text_sections_can_be_built_and_updated() {
+        let style = TextStyle::default();
+        let mut text = Text::with_sections(
+            [
+                TextSection::new("Score: ", style.clone()),
+                TextSection::new("0", style.clone().color(Color::GOLD)),
+            ],
+            TextAlignment::default(),
+        )
+        .with_direction(TextDirection::RightToLeft);
+        assert_eq!(text.direction, TextDirection::RightToLeft);
+        assert_eq!(text.value(), "Score: 0");
+
+        text.set_section_value(1, 42.to_string());
+        text.push_section(" points", style);
+        assert_eq!(text.value(), "Score: 42 points");
+        assert_eq!(text.sections.len(), 3);
+        assert_eq!(text.sections[1].style.color, Color::GOLD);
+    }
+
+    #[test]
+    #[should_panic]
+    fn setting_a_missing_section_panics() {
+        Text::default().set_section_value(0, "value");
+    }
+
+    fn glyph(section_index: usize, is_color: bool) -> PositionedGlyph {
+        PositionedGlyph {
+            position: Vec2::new(10.0, 20.0),
+            size: Vec2::splat(8.0),
+            atlas_info: GlyphAtlasInfo {
+                texture_atlas: Default::default(),
+                glyph_index: 0,
+            },
+            section_index,
+            byte_index: 0,
+            reading_index: 0,
+            is_color,
+        }
+    }
+
+    #[test]
+    fn glyph_modifiers_move_and_recolor_glyphs() {
+        let sections = [
+            TextSection::new("a", TextStyle::default().color(Color::BLUE)),
+            TextSection::new("b", TextStyle::default().color(Color::GREEN)),
+        ];
+        let modifiers = TextGlyphModifiers {
+            glyphs: vec![GlyphModifier {
+                offset: Vec2::new(1.0, -2.0),
+                color: Some(Color::RED),
+            }],
+        };
+
+        // The offset is in logical pixels
+        let (position, color) = modifiers.get(0).apply(&glyph(0, false), &sections, 2.0);
+        assert_eq!(position, Vec2::new(12.0, 16.0));
+        assert_eq!(color, Color::RED);
+
+        // Glyphs without a modifier are drawn as they are laid out
+        let (position, color) = modifiers.get(1).apply(&glyph(1, false), &sections, 2.0);
+        assert_eq!(position, Vec2::new(10.0, 20.0));
+        assert_eq!(color, Color::GREEN);
+
+        // Color glyphs only take the alpha of the modifier
+        let modifier = GlyphModifier {
+            color: Some(Color::rgba(1.0, 0.0, 0.0, 0.5)),
+            ..Default::default()
+        };
+        let (_, color) = modifier.apply(&glyph(0, true), &sections, 1.0);
+        assert_eq!(color, Color::rgba(1.0, 1.0, 1.0, 0.5));
+    }
+}
@@ -1,23 +1,29 @@
-use bevy_asset::Assets;
+use bevy_asset::{AssetEvent, Assets, HandleId};
 use bevy_ecs::{
     bundle::Bundle,
     component::Component,
     entity::Entity,
     event::EventReader,
-    query::Changed,
+    query::{Changed, Or, Without},
     reflect::ReflectComponent,
-    system::{Local, Query, Res, ResMut},
+    system::{Commands, Local, Query, Res, ResMut},
 };
 use bevy_math::{Vec2, Vec3};
 use bevy_reflect::Reflect;
-use bevy_render::{texture::Image, view::Visibility, Extract};
+use bevy_render::{
+    primitives::Aabb,
+    texture::Image,
+    view::{ComputedVisibility, NoFrustumCulling, Visibility},
+    Extract,
+};
 use bevy_sprite::{Anchor, ExtractedSprite, ExtractedSprites, TextureAtlas};
 use bevy_transform::prelude::{GlobalTransform, Transform};
 use bevy_utils::HashSet;
 use bevy_window::{WindowId, WindowScaleFactorChanged, Windows};
 
 use crate::{
-    DefaultTextPipeline, Font, FontAtlasSet, HorizontalAlign, Text, TextError, VerticalAlign,
+    DefaultTextPipeline, Font, FontAtlasSet, FontAtlasSettings, HorizontalAlign, Text, TextError,
+    TextGlyphModifiers, TextLayoutInfo, TextReveal, VerticalAlign,
 };
 
 /// The calculated size of text drawn in 2D scene.
@@ -57,7 +63,9 @@ pub struct Text2dBundle {
     pub global_transform: GlobalTransform,
     pub text_2d_size: Text2dSize,
     pub text_2d_bounds: Text2dBounds,
+    pub text_layout_info: TextLayoutInfo,
     pub visibility: Visibility,
+    pub computed_visibility: ComputedVisibility,
 }
 
 pub fn extract_text2d_sprite(
@@ -65,51 +73,55 @@ pub fn extract_text2d_sprite(
     texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
     text_pipeline: Extract<Res<DefaultTextPipeline>>,
     windows: Extract<Res<Windows>>,
-    text2d_query: Extract<Query<(Entity, &Visibility, &Text, &GlobalTransform, &Text2dSize)>>,
+    text2d_query: Extract<
+        Query<(
+            Entity,
+            &ComputedVisibility,
+            &Text,
+            &GlobalTransform,
+            &Text2dSize,
+            Option<&TextGlyphModifiers>,
+            Option<&TextReveal>,
+        )>,
+    >,
 ) {
     let scale_factor = windows.scale_factor(WindowId::primary()) as f32;
-    for (entity, visibility, text, transform, calculated_size) in text2d_query.iter() {
+    for (entity, visibility, text, transform, calculated_size, modifiers, reveal) in
+        text2d_query.iter()
+    {
         if !visibility.is_visible {
             continue;
         }
-        let (width, height) = (calculated_size.size.x, calculated_size.size.y);
-
         if let Some(text_layout) = text_pipeline.get_glyphs(&entity) {
-            let text_glyphs = &text_layout.glyphs;
-            let alignment_offset = match text.alignment.vertical {
-                VerticalAlign::Top => Vec3::new(0.0, -height, 0.0),
-                VerticalAlign::Center => Vec3::new(0.0, -height * 0.5, 0.0),
-                VerticalAlign::Bottom => Vec3::ZERO,
-            } + match text.alignment.horizontal {
-                HorizontalAlign::Left => Vec3::ZERO,
-                HorizontalAlign::Center => Vec3::new(-width * 0.5, 0.0, 0.0),
-                HorizontalAlign::Right => Vec3::new(-width, 0.0, 0.0),
-            };
+            let alignment_offset = alignment_offset(text, calculated_size.size);
 
             let mut text_transform = *transform;
             text_transform.scale /= scale_factor;
 
-            for text_glyph in text_glyphs {
-                let color = text.sections[text_glyph.section_index]
-                    .style
-                    .color
-                    .as_rgba_linear();
-                let atlas = texture_atlases
-                    .get(&text_glyph.atlas_info.texture_atlas)
-                    .unwrap();
+            for (glyph_index, text_glyph) in text_layout.revealed_glyphs(reveal) {
+                let modifier = modifiers
+                    .map(|modifiers| modifiers.get(glyph_index))
+                    .unwrap_or_default();
+                let (position, color) = modifier.apply(text_glyph, &text.sections, scale_factor);
+                // The atlas may have been evicted, the text is laid out again on the next update
+                let atlas = match texture_atlases.get(&text_glyph.atlas_info.texture_atlas) {
+                    Some(atlas) => atlas,
+                    None => continue,
+                };
                 let handle = atlas.texture.clone_weak();
                 let index = text_glyph.atlas_info.glyph_index as usize;
                 let rect = Some(atlas.textures[index]);
 
                 let glyph_transform = Transform::from_translation(
-                    alignment_offset * scale_factor + text_glyph.position.extend(0.),
+                    alignment_offset * scale_factor + position.extend(0.),
                 );
 
                 let transform = text_transform.mul_transform(glyph_transform);
 
                 extracted_sprites.sprites.push(ExtractedSprite {
+                    entity: Some(entity),
                     transform,
-                    color,
+                    color: color.as_rgba_linear(),
                     rect,
                     custom_size: None,
                     image_handle_id: handle.id,
@@ -122,6 +134,45 @@ pub fn extract_text2d_sprite(
     }
 }
 
+/// The offset of the bottom left corner of text of `size` from its origin, according to its
+/// alignment
+fn alignment_offset(text: &Text, size: Vec2) -> Vec3 {
+    let (width, height) = (size.x, size.y);
+    (match text.alignment.vertical {
+        VerticalAlign::Top => Vec3::new(0.0, -height, 0.0),
+        VerticalAlign::Center => Vec3::new(0.0, -height * 0.5, 0.0),
+        VerticalAlign::Bottom => Vec3::ZERO,
+    }) + match text.horizontal_alignment() {
+        HorizontalAlign::Left | HorizontalAlign::Start => Vec3::ZERO,
+        HorizontalAlign::Center => Vec3::new(-width * 0.5, 0.0, 0.0),
+        HorizontalAlign::Right | HorizontalAlign::End => Vec3::new(-width, 0.0, 0.0),
+    }
+}
+
+/// System computing the [`Aabb`] of the 2d texts which don't have one yet or which size or
+/// alignment changed, for them to be frustum culled.
+pub fn calculate_text2d_bounds(
+    mut commands: Commands,
+    mut text_query: Query<
+        (Entity, &Text, &Text2dSize, Option<&mut Aabb>),
+        (
+            Or<(Without<Aabb>, Changed<Text>, Changed<Text2dSize>)>,
+            Without<NoFrustumCulling>,
+        ),
+    >,
+) {
+    for (entity, text, calculated_size, aabb) in text_query.iter_mut() {
+        let min = alignment_offset(text, calculated_size.size);
+        let bounds = Aabb::from_min_max(min, min + calculated_size.size.extend(0.0));
+        match aabb {
+            Some(mut aabb) => *aabb = bounds,
+            None => {
+                commands.entity(entity).insert(bounds);
+            }
+        }
+    }
+}
+
 /// Updates the layout and size information whenever the text or style is changed.
 /// This information is computed by the `TextPipeline` on insertion, then stored.
 #[allow(clippy::too_many_arguments)]
@@ -134,6 +185,8 @@ pub fn update_text2d_layout(
     mut scale_factor_changed: EventReader<WindowScaleFactorChanged>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
     mut font_atlas_set_storage: ResMut<Assets<FontAtlasSet>>,
+    font_atlas_settings: Res<FontAtlasSettings>,
+    mut texture_atlas_events: EventReader<AssetEvent<TextureAtlas>>,
     mut text_pipeline: ResMut<DefaultTextPipeline>,
     mut text_query: Query<(
         Entity,
@@ -141,14 +194,22 @@ pub fn update_text2d_layout(
         &Text,
         Option<&Text2dBounds>,
         &mut Text2dSize,
+        Option<&mut TextLayoutInfo>,
     )>,
 ) {
     // We need to consume the entire iterator, hence `last`
     let factor_changed = scale_factor_changed.iter().last().is_some();
     let scale_factor = windows.scale_factor(WindowId::primary());
+    let removed_atlases = removed_texture_atlases(&mut texture_atlas_events);
 
-    for (entity, text_changed, text, maybe_bounds, mut calculated_size) in text_query.iter_mut() {
-        if factor_changed || text_changed || queue.remove(&entity) {
+    for (entity, text_changed, text, maybe_bounds, mut calculated_size, layout_info) in
+        text_query.iter_mut()
+    {
+        if factor_changed
+            || text_changed
+            || queue.remove(&entity)
+            || text_pipeline.uses_texture_atlases(&entity, &removed_atlases)
+        {
             let text_bounds = match maybe_bounds {
                 Some(bounds) => Vec2::new(
                     scale_value(bounds.size.x, scale_factor),
@@ -162,10 +223,12 @@ pub fn update_text2d_layout(
                 &text.sections,
                 scale_factor,
                 text.alignment,
+                text.direction,
                 text_bounds,
                 &mut *font_atlas_set_storage,
                 &mut *texture_atlases,
                 &mut *textures,
+                &font_atlas_settings,
             ) {
                 Err(TextError::NoSuchFont) => {
                     // There was an error processing the text layout, let's add this entity to the
@@ -183,12 +246,99 @@ pub fn update_text2d_layout(
                         scale_value(text_layout_info.size.x, 1. / scale_factor),
                         scale_value(text_layout_info.size.y, 1. / scale_factor),
                     );
+                    if let Some(mut layout_info) = layout_info {
+                        *layout_info = text_layout_info.clone();
+                    }
                 }
             }
         }
     }
 }
 
+/// Collects the texture atlases that were removed, e.g. because they were evicted from a
+/// [`FontAtlasSet`], so that text with glyphs in them can be laid out again.
+pub fn removed_texture_atlases(
+    events: &mut EventReader<AssetEvent<TextureAtlas>>,
+) -> HashSet<HandleId> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Removed { handle } => Some(handle.id),
+            _ => None,
+        })
+        .collect()
+}
+
 pub fn scale_value(value: f32, factor: f64) -> f32 {
     (value as f64 * factor) as f32
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TextSection, TextStyle};
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Handle};
+    use bevy_tasks::IoTaskPool;
+
+    fn layout_app() -> (App, Handle<Font>) {
+        IoTaskPool::init(Default::default);
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin)
+            .add_asset::<Font>()
+            .add_asset::<FontAtlasSet>()
+            .add_asset::<Image>()
+            .add_asset::<TextureAtlas>()
+            .add_event::<WindowScaleFactorChanged>()
+            .init_resource::<Windows>()
+            .init_resource::<FontAtlasSettings>()
+            .init_resource::<DefaultTextPipeline>()
+            .add_system(update_text2d_layout);
+        let font = Font::try_from_bytes(
+            include_bytes!("../../../assets/fonts/FiraSans-Bold.ttf").to_vec(),
+        )
+        .unwrap();
+        let font = app.world.resource_mut::<Assets<Font>>().add(font);
+        (app, font)
+    }
+
+    #[test]
+    fn layout_info_is_kept_up_to_date() {
+        let (mut app, font) = layout_app();
+        let style = TextStyle {
+            font,
+            font_size: 20.0,
+            ..Default::default()
+        };
+        let entity = app
+            .world
+            .spawn()
+            .insert_bundle((
+                Text::with_section("ab c", style.clone(), Default::default()),
+                Text2dSize::default(),
+                TextLayoutInfo::default(),
+            ))
+            .id();
+        app.update();
+
+        // Whitespace has no glyph
+        let layout_info = app.world.get::<TextLayoutInfo>(entity).unwrap();
+        assert_eq!(layout_info.glyphs.len(), 3);
+        assert_eq!(
+            layout_info.size,
+            app.world.get::<Text2dSize>(entity).unwrap().size
+        );
+        let first_line = layout_info.size;
+
+        app.world
+            .get_mut::<Text>(entity)
+            .unwrap()
+            .sections
+            .push(TextSection::new("\nde", style));
+        app.update();
+        let layout_info = app.world.get::<TextLayoutInfo>(entity).unwrap();
+        assert_eq!(layout_info.glyphs.len(), 5);
+        assert!(layout_info.size.y > first_line.y);
+        assert_eq!(layout_info.glyphs[4].section_index, 1);
+    }
+}
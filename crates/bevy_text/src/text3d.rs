@@ -0,0 +1,465 @@
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_core_pipeline::core_3d::Camera3d;
+use bevy_ecs::{
+    bundle::Bundle,
+    component::Component,
+    entity::Entity,
+    event::EventReader,
+    query::{Changed, Or, With, Without},
+    reflect::ReflectComponent,
+    system::{Commands, Local, Query, RemovedComponents, Res, ResMut},
+};
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt, Parent};
+use bevy_math::{Vec2, Vec3};
+use bevy_pbr::{AlphaMode, NotShadowCaster, PbrBundle, StandardMaterial};
+use bevy_reflect::Reflect;
+use bevy_render::{
+    camera::Camera,
+    color::Color,
+    mesh::{Indices, Mesh},
+    render_resource::PrimitiveTopology,
+    texture::Image,
+    view::{ComputedVisibility, Visibility},
+};
+use bevy_sprite::TextureAtlas;
+use bevy_transform::prelude::{GlobalTransform, Transform};
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{
+    removed_texture_atlases, DefaultTextPipeline, Font, FontAtlasSet, FontAtlasSettings,
+    HorizontalAlign, Text, TextError, TextGlyphModifiers, TextLayoutInfo, TextReveal,
+    VerticalAlign,
+};
+
+/// Marks [`Text`] to be drawn in world space, as a mesh visible to 3D cameras.
+#[derive(Component, Copy, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Text3d {
+    /// How many pixels of the laid out text make up one world unit.
+    ///
+    /// The text is rasterized at its font size, so this also controls how sharp it looks up
+    /// close.
+    pub pixels_per_unit: f32,
+    /// The maximum width and height of the text in pixels. The text wraps according to the
+    /// specified size.
+    pub bounds: Vec2,
+    /// If `true`, the text always faces the active 3D camera, e.g. for name tags.
+    pub billboard: bool,
+}
+
+impl Default for Text3d {
+    fn default() -> Self {
+        Self {
+            pixels_per_unit: 100.0,
+            bounds: Vec2::new(f32::MAX, f32::MAX),
+            billboard: false,
+        }
+    }
+}
+
+/// The bundle of components needed to draw text in a 3D scene via a `Camera3dBundle`.
+///
+/// The text is centered on its transform according to its [`TextAlignment`](crate::TextAlignment).
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct Text3dBundle {
+    pub text: Text,
+    pub text_3d: Text3d,
+    pub text_layout_info: TextLayoutInfo,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    pub visibility: Visibility,
+}
+
+/// The child entities drawing the glyphs of a [`Text3d`], one for each glyph atlas texture used.
+#[derive(Component, Default)]
+pub struct Text3dPages {
+    pages: Vec<(Entity, Handle<Mesh>)>,
+}
+
+/// Marks a child entity drawing the glyphs of a [`Text3d`], listed in its [`Text3dPages`].
+#[derive(Component, Default)]
+pub struct Text3dPage;
+
+/// The unlit materials used to draw [`Text3d`] glyphs, one for each glyph atlas texture.
+#[derive(Default)]
+pub struct Text3dMaterials {
+    materials: HashMap<Handle<Image>, Handle<StandardMaterial>>,
+}
+
+impl Text3dMaterials {
+    fn get_or_insert(
+        &mut self,
+        texture: &Handle<Image>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        self.materials
+            .entry(texture.clone_weak())
+            .or_insert_with(|| {
+                materials.add(StandardMaterial {
+                    base_color: Color::WHITE,
+                    base_color_texture: Some(texture.clone_weak()),
+                    unlit: true,
+                    alpha_mode: AlphaMode::Blend,
+                    double_sided: true,
+                    cull_mode: None,
+                    ..Default::default()
+                })
+            })
+            .clone()
+    }
+}
+
+/// Updates the layout of [`Text3d`] whenever the text or its settings are changed.
+#[allow(clippy::too_many_arguments)]
+pub fn update_text3d_layout(
+    // Text items which should be reprocessed again, generally when the font hasn't loaded yet.
+    mut queue: Local<HashSet<Entity>>,
+    mut textures: ResMut<Assets<Image>>,
+    fonts: Res<Assets<Font>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut font_atlas_set_storage: ResMut<Assets<FontAtlasSet>>,
+    font_atlas_settings: Res<FontAtlasSettings>,
+    mut texture_atlas_events: EventReader<AssetEvent<TextureAtlas>>,
+    mut text_pipeline: ResMut<DefaultTextPipeline>,
+    mut text_query: Query<(
+        Entity,
+        Or<(Changed<Text>, Changed<Text3d>)>,
+        &Text,
+        &Text3d,
+        &mut TextLayoutInfo,
+    )>,
+) {
+    let removed_atlases = removed_texture_atlases(&mut texture_atlas_events);
+
+    for (entity, changed, text, text_3d, mut layout_info) in text_query.iter_mut() {
+        if changed
+            || queue.remove(&entity)
+            || text_pipeline.uses_texture_atlases(&entity, &removed_atlases)
+        {
+            // World space text isn't affected by the window's scale factor
+            match text_pipeline.queue_text(
+                entity,
+                &fonts,
+                &text.sections,
+                1.0,
+                text.alignment,
+                text.direction,
+                text_3d.bounds,
+                &mut *font_atlas_set_storage,
+                &mut *texture_atlases,
+                &mut *textures,
+                &font_atlas_settings,
+            ) {
+                Err(TextError::NoSuchFont) => {
+                    queue.insert(entity);
+                }
+                Err(e @ TextError::FailedToAddGlyph(_)) => {
+                    panic!("Fatal error when processing text: {}.", e);
+                }
+                Ok(()) => {
+                    let text_layout_info = text_pipeline.get_glyphs(&entity).expect(
+                        "Failed to get glyphs from the pipeline that have just been computed",
+                    );
+                    *layout_info = text_layout_info.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Builds a mesh for each glyph atlas texture used by a [`Text3d`] when its layout changes, and
+/// spawns or despawns the child entities drawing them.
+///
+/// The pages of a text whose [`Text3d`] is removed, or that is despawned without its children,
+/// are despawned too.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_text3d_meshes(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut text_materials: ResMut<Text3dMaterials>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    texture_atlases: Res<Assets<TextureAtlas>>,
+    mut text_query: Query<
+        (
+            Entity,
+            &Text,
+            &Text3d,
+            &TextLayoutInfo,
+            Option<&TextGlyphModifiers>,
+            Option<&TextReveal>,
+            &Visibility,
+            &GlobalTransform,
+            Option<&mut Text3dPages>,
+        ),
+        Or<(
+            Changed<TextLayoutInfo>,
+            Changed<TextGlyphModifiers>,
+            Changed<TextReveal>,
+            Changed<Text3d>,
+            Changed<Visibility>,
+        )>,
+    >,
+    mut page_query: Query<&mut Visibility, Without<Text3d>>,
+    removed_texts: RemovedComponents<Text3d>,
+    stale_page_query: Query<(Entity, &Parent, &Handle<Mesh>), With<Text3dPage>>,
+    stale_text_query: Query<Entity, (With<Text3dPages>, Without<Text3d>)>,
+) {
+    for event in image_events.iter() {
+        if let AssetEvent::Removed { handle } = event {
+            if let Some(material) = text_materials.materials.remove(handle) {
+                materials.remove(material);
+            }
+        }
+    }
+
+    let removed_texts: HashSet<Entity> = removed_texts.iter().collect();
+    if !removed_texts.is_empty() {
+        for (page, parent, mesh) in stale_page_query.iter() {
+            if removed_texts.contains(&parent.0) {
+                meshes.remove(mesh);
+                commands.entity(page).despawn_recursive();
+            }
+        }
+    }
+    for entity in stale_text_query.iter() {
+        commands.entity(entity).remove::<Text3dPages>();
+    }
+
+    for (entity, text, text_3d, layout, modifiers, reveal, visibility, transform, pages) in
+        text_query.iter_mut()
+    {
+        let size = layout.size;
+        let alignment_offset = match text.alignment.vertical {
+            VerticalAlign::Top => Vec2::new(0.0, -size.y),
+            VerticalAlign::Center => Vec2::new(0.0, -size.y * 0.5),
+            VerticalAlign::Bottom => Vec2::ZERO,
+        } + match text.horizontal_alignment() {
+            HorizontalAlign::Left | HorizontalAlign::Start => Vec2::ZERO,
+            HorizontalAlign::Center => Vec2::new(-size.x * 0.5, 0.0),
+            HorizontalAlign::Right | HorizontalAlign::End => Vec2::new(-size.x, 0.0),
+        };
+        let units_per_pixel = 1.0 / text_3d.pixels_per_unit;
+
+        // Group the glyph quads by the texture they are drawn from
+        let mut page_meshes: Vec<(Handle<Image>, GlyphMeshBuilder)> = Vec::new();
+        for (glyph_index, glyph) in layout.revealed_glyphs(reveal) {
+            let atlas = match texture_atlases.get(&glyph.atlas_info.texture_atlas) {
+                Some(atlas) => atlas,
+                None => continue,
+            };
+            let modifier = modifiers
+                .map(|modifiers| modifiers.get(glyph_index))
+                .unwrap_or_default();
+            let (position, color) = modifier.apply(glyph, &text.sections, 1.0);
+            let rect = atlas.textures[glyph.atlas_info.glyph_index as usize];
+            let center = (position + alignment_offset) * units_per_pixel;
+            let half_size = glyph.size * 0.5 * units_per_pixel;
+
+            let builder = match page_meshes
+                .iter_mut()
+                .position(|(texture, _)| *texture == atlas.texture)
+            {
+                Some(index) => &mut page_meshes[index].1,
+                None => {
+                    page_meshes.push((atlas.texture.clone_weak(), GlyphMeshBuilder::default()));
+                    &mut page_meshes.last_mut().unwrap().1
+                }
+            };
+            builder.push_quad(
+                center - half_size,
+                center + half_size,
+                rect.min / atlas.size,
+                rect.max / atlas.size,
+                color,
+            );
+        }
+
+        let mut pages = match pages {
+            Some(pages) => std::mem::take(&mut pages.into_inner().pages),
+            None => Vec::new(),
+        };
+        for page in pages.drain(page_meshes.len().min(pages.len())..) {
+            meshes.remove(&page.1);
+            commands.entity(page.0).despawn_recursive();
+        }
+
+        for (index, (texture, builder)) in page_meshes.into_iter().enumerate() {
+            let mesh = builder.build();
+            let aabb = mesh.compute_aabb();
+            let material = text_materials.get_or_insert(&texture, &mut materials);
+            if let Some((page, mesh_handle)) = pages.get(index) {
+                meshes.set_untracked(mesh_handle, mesh);
+                let mut page_commands = commands.entity(*page);
+                page_commands.insert(material);
+                if let Some(aabb) = aabb {
+                    page_commands.insert(aabb);
+                }
+                if let Ok(mut page_visibility) = page_query.get_mut(*page) {
+                    *page_visibility = visibility.clone();
+                }
+            } else {
+                let mesh_handle = meshes.add(mesh);
+                let mut page_commands = commands.spawn_bundle(PbrBundle {
+                    mesh: mesh_handle.clone(),
+                    material,
+                    // Avoids a frame at the origin before the transform is propagated
+                    global_transform: *transform,
+                    visibility: visibility.clone(),
+                    computed_visibility: ComputedVisibility::default(),
+                    ..Default::default()
+                });
+                page_commands.insert_bundle((NotShadowCaster, Text3dPage));
+                if let Some(aabb) = aabb {
+                    page_commands.insert(aabb);
+                }
+                let page = page_commands.id();
+                commands.entity(entity).add_child(page);
+                pages.push((page, mesh_handle));
+            }
+        }
+
+        commands.entity(entity).insert(Text3dPages { pages });
+    }
+}
+
+/// Rotates billboarded [`Text3d`] to face the active 3D camera.
+///
+/// This runs after transform propagation, and only changes the rotation of the
+/// [`GlobalTransform`] of the entities drawing the text, so that the [`Transform`] of the text
+/// itself is left untouched.
+pub fn billboard_text3d(
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    text_query: Query<(&Text3d, &Text3dPages)>,
+    mut page_query: Query<&mut GlobalTransform, (Without<Camera3d>, Without<Text3d>)>,
+) {
+    let camera_rotation = match cameras.iter().find(|(camera, _)| camera.is_active) {
+        Some((_, camera_transform)) => camera_transform.rotation,
+        None => return,
+    };
+    for (text_3d, pages) in text_query.iter() {
+        if !text_3d.billboard {
+            continue;
+        }
+        for (page, _) in &pages.pages {
+            if let Ok(mut page_transform) = page_query.get_mut(*page) {
+                page_transform.rotation = camera_rotation;
+            }
+        }
+    }
+}
+
+/// Builds a mesh made of textured, vertex colored quads facing +Z.
+#[derive(Default)]
+struct GlyphMeshBuilder {
+    positions: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    colors: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+}
+
+impl GlyphMeshBuilder {
+    fn push_quad(&mut self, min: Vec2, max: Vec2, uv_min: Vec2, uv_max: Vec2, color: Color) {
+        let start = self.positions.len() as u32;
+        // Texture coordinates grow downwards, while positions grow upwards
+        self.positions.extend([
+            [min.x, min.y, 0.0],
+            [max.x, min.y, 0.0],
+            [max.x, max.y, 0.0],
+            [min.x, max.y, 0.0],
+        ]);
+        self.uvs.extend([
+            [uv_min.x, uv_max.y],
+            [uv_max.x, uv_max.y],
+            [uv_max.x, uv_min.y],
+            [uv_min.x, uv_min.y],
+        ]);
+        self.colors.extend([color.as_linear_rgba_f32(); 4]);
+        self.indices
+            .extend([start, start + 1, start + 2, start, start + 2, start + 3]);
+    }
+
+    fn build(self) -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        let normals = vec![Vec3::Z.to_array(); self.positions.len()];
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, self.positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, self.colors);
+        mesh.set_indices(Some(Indices::U32(self.indices)));
+        mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_tasks::IoTaskPool;
+
+    fn text3d_app() -> App {
+        IoTaskPool::init(Default::default);
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin)
+            .add_asset::<Image>()
+            .add_asset::<Mesh>()
+            .add_asset::<StandardMaterial>()
+            .add_asset::<TextureAtlas>()
+            .init_resource::<Text3dMaterials>()
+            .add_system(update_text3d_meshes);
+        app
+    }
+
+    /// Spawns a text with a single page, as if it had been drawn.
+    fn spawn_text(app: &mut App) -> (Entity, Entity, Handle<Mesh>) {
+        let mesh = app
+            .world
+            .resource_mut::<Assets<Mesh>>()
+            .add(GlyphMeshBuilder::default().build());
+        let page = app
+            .world
+            .spawn()
+            .insert_bundle((mesh.clone(), Visibility::default(), Text3dPage))
+            .id();
+        let text = app
+            .world
+            .spawn()
+            .insert_bundle(Text3dBundle::default())
+            .insert(Text3dPages {
+                pages: vec![(page, mesh.clone())],
+            })
+            .push_children(&[page])
+            .id();
+        (text, page, mesh)
+    }
+
+    #[test]
+    fn pages_without_glyphs_are_despawned() {
+        let mut app = text3d_app();
+        let (text, page, mesh) = spawn_text(&mut app);
+        app.update();
+
+        // The layout has no glyphs left
+        assert!(app.world.get_entity(page).is_none());
+        assert!(app.world.resource::<Assets<Mesh>>().get(&mesh).is_none());
+        assert!(app.world.get::<Text3dPages>(text).unwrap().pages.is_empty());
+    }
+
+    #[test]
+    fn pages_of_removed_texts_are_despawned() {
+        let mut app = text3d_app();
+        let (text, page, _) = spawn_text(&mut app);
+        // Only the text is despawned, not its children
+        app.world.despawn(text);
+        app.update();
+        assert!(app.world.get_entity(page).is_none());
+
+        let (text, page, _) = spawn_text(&mut app);
+        app.world.entity_mut(text).remove::<Text3d>();
+        app.update();
+        assert!(app.world.get_entity(page).is_none());
+        assert!(app.world.get::<Text3dPages>(text).is_none());
+    }
+}
@@ -0,0 +1,162 @@
+use crate::Time;
+use bevy_ecs::{schedule::StageLabel, world::World};
+use bevy_utils::Duration;
+
+/// The label of the schedule run at the fixed timestep of the [`FixedTime`] resource, see
+/// [`Schedules`](bevy_ecs::schedule::Schedules).
+///
+/// It is run by [`run_fixed_update_schedule`] in `CoreStage::FixedUpdate`, as many times as there
+/// are periods of [`FixedTime`] in the time elapsed since the last frame: none with a high
+/// frame rate, several with a low frame rate. This keeps the simulation of a game deterministic
+/// and independent of the frame rate.
+///
+/// The systems of this schedule should use [`FixedTime::period`] as their time step, instead of
+/// [`Time::delta`].
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, schedule::Schedules};
+/// # use bevy_time::{FixedTime, FixedUpdate};
+/// # #[derive(Component)]
+/// # struct Velocity(f32);
+/// # #[derive(Component)]
+/// # struct Position(f32);
+/// fn integrate(fixed_time: Res<FixedTime>, mut query: Query<(&Velocity, &mut Position)>) {
+///     for (velocity, mut position) in query.iter_mut() {
+///         position.0 += velocity.0 * fixed_time.period.as_secs_f32();
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(FixedTime::new_from_secs(0.02));
+/// world
+///     .get_resource_or_insert_with(Schedules::default)
+///     .add_system(FixedUpdate, integrate);
+/// world.run_schedule(FixedUpdate);
+/// ```
+#[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
+pub struct FixedUpdate;
+
+/// The timestep of the [`FixedUpdate`] schedule, and the time accumulated since its last step.
+#[derive(Debug, Clone)]
+pub struct FixedTime {
+    accumulated: Duration,
+    /// The time between two steps of the [`FixedUpdate`] schedule
+    pub period: Duration,
+}
+
+impl Default for FixedTime {
+    /// Steps 60 times per second.
+    fn default() -> Self {
+        Self::new(Duration::from_secs_f64(1.0 / 60.0))
+    }
+}
+
+impl FixedTime {
+    /// Creates a timestep stepping once every `period`.
+    pub fn new(period: Duration) -> Self {
+        Self {
+            accumulated: Duration::ZERO,
+            period,
+        }
+    }
+
+    /// Creates a timestep stepping once every `period` seconds.
+    pub fn new_from_secs(period: f32) -> Self {
+        Self::new(Duration::from_secs_f32(period))
+    }
+
+    /// Adds `delta_time` to the accumulated time.
+    pub fn tick(&mut self, delta_time: Duration) {
+        self.accumulated += delta_time;
+    }
+
+    /// The time accumulated since the last step, less than a [`FixedTime::period`] after the
+    /// steps of a frame.
+    pub fn accumulated(&self) -> Duration {
+        self.accumulated
+    }
+
+    /// The fraction of a period accumulated since the last step, between 0 and 1 after the steps of
+    /// a frame. It is the progress towards the next step, to interpolate between the last two
+    /// steps.
+    pub fn overstep_percentage(&self) -> f32 {
+        self.accumulated.as_secs_f32() / self.period.as_secs_f32()
+    }
+
+    /// Removes a period from the accumulated time, returning `true` if a period was accumulated,
+    /// meaning a step should run.
+    pub fn expend(&mut self) -> bool {
+        match self.accumulated.checked_sub(self.period) {
+            Some(accumulated) => {
+                self.accumulated = accumulated;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// System accumulating the time elapsed since the last frame in the [`FixedTime`] resource, and
+/// running the [`FixedUpdate`] schedule once for each period accumulated.
+pub fn run_fixed_update_schedule(world: &mut World) {
+    let delta = world.resource::<Time>().delta();
+    world.resource_mut::<FixedTime>().tick(delta);
+    while world.resource_mut::<FixedTime>().expend() {
+        world.run_schedule(FixedUpdate);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bevy_ecs::{prelude::*, schedule::Schedules};
+    use bevy_utils::Instant;
+
+    type Count = usize;
+
+    #[test]
+    fn fixed_update() {
+        let mut world = World::default();
+        let instant = Instant::now();
+        let mut time = Time::default();
+        time.update_with_instant(instant);
+        world.insert_resource(time);
+        world.insert_resource(FixedTime::new_from_secs(0.5));
+        world.insert_resource::<Count>(0);
+        world
+            .get_resource_or_insert_with(Schedules::default)
+            .add_system(FixedUpdate, |mut count: ResMut<Count>| *count += 1);
+
+        // if time does not progress, the step does not run
+        run_fixed_update_schedule(&mut world);
+        assert_eq!(0, *world.resource::<Count>());
+
+        // let's progress less than one step
+        advance_time(&mut world, instant, 400);
+        assert_eq!(0, *world.resource::<Count>());
+        assert_eq!(0.8, world.resource::<FixedTime>().overstep_percentage());
+
+        // finish the first step with 0.1s above the step length
+        advance_time(&mut world, instant, 600);
+        assert_eq!(1, *world.resource::<Count>());
+        assert_eq!(
+            Duration::from_millis(100),
+            world.resource::<FixedTime>().accumulated()
+        );
+
+        // runs multiple times if the delta is multiple step lengths
+        advance_time(&mut world, instant, 1700);
+        assert_eq!(3, *world.resource::<Count>());
+        assert_eq!(
+            Duration::from_millis(200),
+            world.resource::<FixedTime>().accumulated()
+        );
+    }
+
+    fn advance_time(world: &mut World, instant: Instant, millis: u64) {
+        world
+            .resource_mut::<Time>()
+            .update_with_instant(instant + Duration::from_millis(millis));
+        run_fixed_update_schedule(world);
+    }
+}
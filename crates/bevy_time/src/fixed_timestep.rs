@@ -70,6 +70,9 @@ impl FixedTimesteps {
 ///
 /// For more fine tuned information about the execution status of a given fixed timestep,
 /// use the [`FixedTimesteps`] resource.
+///
+/// Systems running at the fixed timestep of the whole app are better added to the
+/// [`FixedUpdate`](crate::FixedUpdate) schedule.
 pub struct FixedTimestep {
     state: LocalFixedTimestepState,
     internal_system: Box<dyn System<In = (), Out = ShouldRun>>,
@@ -1,9 +1,11 @@
+mod fixed_time;
 mod fixed_timestep;
 mod stopwatch;
 #[allow(clippy::module_inception)]
 mod time;
 mod timer;
 
+pub use fixed_time::*;
 pub use fixed_timestep::*;
 pub use stopwatch::*;
 pub use time::*;
@@ -12,11 +14,11 @@ pub use timer::*;
 pub mod prelude {
     //! The Bevy Time Prelude.
     #[doc(hidden)]
-    pub use crate::{Time, Timer};
+    pub use crate::{FixedTime, FixedUpdate, Time, Timer};
 }
 
 use bevy_app::prelude::*;
-use bevy_ecs::prelude::*;
+use bevy_ecs::{prelude::*, schedule::Schedules};
 
 /// Adds time functionality to Apps.
 #[derive(Default)]
@@ -31,13 +33,22 @@ impl Plugin for TimePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Time>()
             .init_resource::<FixedTimesteps>()
+            .init_resource::<FixedTime>()
             .register_type::<Timer>()
             // time system is added as an "exclusive system" to ensure it runs before other systems
             // in CoreStage::First
             .add_system_to_stage(
                 CoreStage::First,
                 time_system.exclusive_system().at_start().label(TimeSystem),
+            )
+            .add_system_to_stage(
+                CoreStage::FixedUpdate,
+                run_fixed_update_schedule.exclusive_system(),
             );
+        // The schedule is run even without systems
+        app.world
+            .get_resource_or_insert_with(Schedules::default)
+            .get_or_insert(FixedUpdate);
     }
 }
 
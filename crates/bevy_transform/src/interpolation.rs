@@ -0,0 +1,166 @@
+use crate::components::Transform;
+use bevy_ecs::prelude::*;
+use bevy_reflect::Reflect;
+use bevy_time::FixedTime;
+
+/// Smooths the movement of an entity whose [`Transform`] is only changed in the
+/// [`FixedUpdate`](bevy_time::FixedUpdate) schedule, by rendering it between its last two fixed
+/// steps.
+///
+/// Without it, an entity moved at a fixed timestep stutters whenever the frame rate isn't a
+/// multiple of the fixed timestep: some frames run two steps, some run none. With it, the
+/// [`Transform`] of the entity is interpolated between its value before and after the last step,
+/// according to the time accumulated since this step by [`FixedTime`]. The entity is rendered
+/// up to one step late.
+///
+/// The [`Transform`] is set back to its value after the last step in `CoreStage::PreUpdate`, and
+/// interpolated again in `CoreStage::PostUpdate`: systems see the interpolated value after
+/// `PostUpdate`. To move the entity without interpolation, like a teleport, insert a new
+/// [`TransformInterpolation`].
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct TransformInterpolation {
+    previous: Transform,
+    current: Transform,
+}
+
+impl TransformInterpolation {
+    /// The [`Transform`] of the entity before the last fixed step
+    #[inline]
+    pub fn previous(&self) -> &Transform {
+        &self.previous
+    }
+
+    /// The [`Transform`] of the entity after the last fixed step
+    #[inline]
+    pub fn current(&self) -> &Transform {
+        &self.current
+    }
+}
+
+/// Returns the transform between `from` and `to`, at `s` between 0 and 1.
+fn interpolate(from: &Transform, to: &Transform, s: f32) -> Transform {
+    Transform {
+        translation: from.translation.lerp(to.translation, s),
+        rotation: from.rotation.slerp(to.rotation, s),
+        scale: from.scale.lerp(to.scale, s),
+    }
+}
+
+/// System setting the [`Transform`] of the entities with a [`TransformInterpolation`] back to
+/// their value after the last fixed step, before it is stepped again
+pub fn restore_interpolated_transforms(
+    mut query: Query<(&mut Transform, &mut TransformInterpolation)>,
+) {
+    for (mut transform, mut interpolation) in query.iter_mut() {
+        if interpolation.is_added() {
+            interpolation.previous = *transform;
+            interpolation.current = *transform;
+        } else if *transform != interpolation.current {
+            *transform = interpolation.current;
+        }
+    }
+}
+
+/// System storing the [`Transform`] of the entities with a [`TransformInterpolation`] before each
+/// fixed step
+pub fn store_previous_transforms(mut query: Query<(&Transform, &mut TransformInterpolation)>) {
+    for (transform, mut interpolation) in query.iter_mut() {
+        interpolation.previous = *transform;
+    }
+}
+
+/// System interpolating the [`Transform`] of the entities with a [`TransformInterpolation`]
+/// between their last two fixed steps
+pub fn interpolate_transforms(
+    fixed_time: Option<Res<FixedTime>>,
+    mut query: Query<(&mut Transform, &mut TransformInterpolation)>,
+) {
+    let overstep = fixed_time.map_or(1.0, |fixed_time| {
+        fixed_time.overstep_percentage().clamp(0.0, 1.0)
+    });
+    for (mut transform, mut interpolation) in query.iter_mut() {
+        if interpolation.is_added() {
+            interpolation.previous = *transform;
+        }
+        interpolation.current = *transform;
+        let interpolated = interpolate(&interpolation.previous, &interpolation.current, overstep);
+        if *transform != interpolated {
+            *transform = interpolated;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bevy_ecs::schedule::Schedules;
+    use bevy_math::Vec3;
+    use bevy_time::{run_fixed_update_schedule, FixedUpdate, Time};
+    use std::time::{Duration, Instant};
+
+    fn step(mut query: Query<&mut Transform>) {
+        for mut transform in query.iter_mut() {
+            transform.translation.x += 1.0;
+        }
+    }
+
+    #[test]
+    fn interpolate_between_fixed_steps() {
+        let mut world = World::default();
+        let instant = Instant::now();
+        let mut time = Time::default();
+        time.update_with_instant(instant);
+        world.insert_resource(time);
+        world.insert_resource(FixedTime::new_from_secs(1.0));
+        world
+            .get_resource_or_insert_with(Schedules::default)
+            .add_system(
+                FixedUpdate,
+                store_previous_transforms.exclusive_system().at_start(),
+            )
+            .add_system(FixedUpdate, step);
+
+        let mut schedule = Schedule::default();
+        schedule
+            .add_stage(
+                "pre_update",
+                SystemStage::parallel().with_system(restore_interpolated_transforms),
+            )
+            .add_stage(
+                "fixed_update",
+                SystemStage::parallel().with_system(run_fixed_update_schedule.exclusive_system()),
+            )
+            .add_stage(
+                "post_update",
+                SystemStage::parallel().with_system(interpolate_transforms),
+            );
+
+        let entity = world
+            .spawn()
+            .insert_bundle((Transform::default(), TransformInterpolation::default()))
+            .id();
+        let mut run_at = |world: &mut World, millis: u64| {
+            world
+                .resource_mut::<Time>()
+                .update_with_instant(instant + Duration::from_millis(millis));
+            schedule.run(world);
+            world.get::<Transform>(entity).unwrap().translation
+        };
+
+        assert_eq!(run_at(&mut world, 0), Vec3::ZERO);
+        // halfway between the first step and the next one
+        assert_eq!(run_at(&mut world, 1500), Vec3::new(0.5, 0.0, 0.0));
+        // right on the second step, the entity is rendered at the first step
+        assert_eq!(run_at(&mut world, 2000), Vec3::new(1.0, 0.0, 0.0));
+        // without step, the entity still moves
+        assert_eq!(run_at(&mut world, 2250), Vec3::new(1.25, 0.0, 0.0));
+        assert_eq!(
+            world
+                .get::<TransformInterpolation>(entity)
+                .unwrap()
+                .current(),
+            &Transform::from_xyz(2.0, 0.0, 0.0)
+        );
+    }
+}
@@ -3,18 +3,21 @@
 
 /// The basic components of the transform crate
 pub mod components;
+mod interpolation;
 mod systems;
+pub use crate::interpolation::*;
 pub use crate::systems::transform_propagate_system;
 
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{components::*, TransformBundle, TransformPlugin};
+    pub use crate::{components::*, TransformBundle, TransformInterpolation, TransformPlugin};
 }
 
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_hierarchy::HierarchySystem;
+use bevy_time::FixedUpdate;
 use prelude::{GlobalTransform, Transform};
 
 /// A [`Bundle`] of the [`Transform`] and [`GlobalTransform`]
@@ -92,6 +95,7 @@ impl Plugin for TransformPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Transform>()
             .register_type::<GlobalTransform>()
+            .register_type::<TransformInterpolation>()
             // Adding these to startup ensures the first update is "correct"
             .add_startup_system_to_stage(
                 StartupStage::PostStartup,
@@ -104,6 +108,15 @@ impl Plugin for TransformPlugin {
                 systems::transform_propagate_system
                     .label(TransformSystem::TransformPropagate)
                     .after(HierarchySystem::ParentUpdate),
+            )
+            .add_system_to_stage(CoreStage::PreUpdate, restore_interpolated_transforms)
+            .add_system_to_schedule(
+                FixedUpdate,
+                store_previous_transforms.exclusive_system().at_start(),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                interpolate_transforms.before(TransformSystem::TransformPropagate),
             );
     }
 }
@@ -1,9 +1,17 @@
 use crate::components::{GlobalTransform, Transform};
-use bevy_ecs::prelude::{Changed, Entity, Query, With, Without};
+use bevy_ecs::prelude::{Changed, Entity, Local, Or, Query, RemovedComponents, With, Without};
 use bevy_hierarchy::{Children, Parent};
+use bevy_utils::HashSet;
+
+/// The number of hierarchy roots propagated by each task of the `ComputeTaskPool`.
+const ROOTS_PER_TASK: usize = 32;
 
 /// Update [`GlobalTransform`] component of entities based on entity hierarchy and
 /// [`Transform`] component.
+///
+/// Only the hierarchies with a changed [`Transform`], [`Parent`] or [`Children`] are walked, and
+/// only the subtrees of these hierarchies leading to a change. Independent hierarchies are
+/// propagated in parallel on the `ComputeTaskPool`.
 pub fn transform_propagate_system(
     mut root_query: Query<
         (
@@ -15,76 +23,134 @@ pub fn transform_propagate_system(
         ),
         Without<Parent>,
     >,
-    mut transform_query: Query<(
-        &Transform,
-        Changed<Transform>,
-        &mut GlobalTransform,
-        &Parent,
-    )>,
-    children_query: Query<(&Children, Changed<Children>), (With<Parent>, With<GlobalTransform>)>,
+    transform_query: Query<
+        (
+            Option<(&Children, Changed<Children>)>,
+            &Transform,
+            Changed<Transform>,
+            &mut GlobalTransform,
+        ),
+        With<Parent>,
+    >,
+    parent_query: Query<(&Parent, Changed<Parent>)>,
+    changed_query: Query<Entity, Or<(Changed<Transform>, Changed<Children>, Changed<Parent>)>>,
+    removed_parents: RemovedComponents<Parent>,
+    mut dirty: Local<HashSet<Entity>>,
+    mut orphans: Local<HashSet<Entity>>,
 ) {
-    for (children, transform, transform_changed, mut global_transform, entity) in
-        root_query.iter_mut()
-    {
-        let mut changed = transform_changed;
-        if transform_changed {
-            *global_transform = GlobalTransform::from(*transform);
-        }
-
-        if let Some((children, changed_children)) = children {
-            // If our `Children` has changed, we need to recalculate everything below us
-            changed |= changed_children;
-            for child in children.iter() {
-                let _ = propagate_recursive(
-                    &global_transform,
-                    &mut transform_query,
-                    &children_query,
-                    *child,
-                    entity,
-                    changed,
-                );
+    // Entities that lost their parent are now roots, relative to the reference frame
+    orphans.clear();
+    orphans.extend(removed_parents.iter());
+
+    // Mark the changed entities and their ancestors, the hierarchies and subtrees to walk
+    dirty.clear();
+    for changed in changed_query.iter().chain(orphans.iter().copied()) {
+        let mut entity = changed;
+        // Stop at the first ancestor already marked, its own ancestors are marked too
+        while dirty.insert(entity) {
+            match parent_query.get(entity) {
+                Ok((parent, _)) => entity = parent.0,
+                Err(_) => break,
             }
         }
     }
+    if dirty.is_empty() {
+        return;
+    }
+
+    let dirty = &*dirty;
+    let orphans = &*orphans;
+    root_query.par_for_each_mut(
+        ROOTS_PER_TASK,
+        |(children, transform, transform_changed, mut global_transform, entity)| {
+            if !dirty.contains(&entity) {
+                return;
+            }
+            let mut changed = transform_changed || orphans.contains(&entity);
+            if changed {
+                *global_transform = GlobalTransform::from(*transform);
+            }
+
+            if let Some((children, changed_children)) = children {
+                // If our `Children` has changed, we need to recalculate everything below us
+                changed |= changed_children;
+                for child in children.iter() {
+                    // SAFETY: each hierarchy is walked by a single task, and `propagate_recursive`
+                    // checks that `child` has `entity` as its only parent before accessing it
+                    let _ = unsafe {
+                        propagate_recursive(
+                            &global_transform,
+                            &transform_query,
+                            &parent_query,
+                            dirty,
+                            *child,
+                            entity,
+                            changed,
+                        )
+                    };
+                }
+            }
+        },
+    );
 }
 
-fn propagate_recursive(
+/// Propagates the [`GlobalTransform`] of `expected_parent` to `entity` and its descendants.
+///
+/// # Safety
+///
+/// No other task may access the [`GlobalTransform`] of `entity` or its descendants through
+/// `transform_query` during this call. Walking each hierarchy from its root in a single task
+/// ensures this, as an entity is only propagated to from its [`Parent`].
+unsafe fn propagate_recursive(
     parent: &GlobalTransform,
-    transform_query: &mut Query<(
-        &Transform,
-        Changed<Transform>,
-        &mut GlobalTransform,
-        &Parent,
-    )>,
-    children_query: &Query<(&Children, Changed<Children>), (With<Parent>, With<GlobalTransform>)>,
+    transform_query: &Query<
+        (
+            Option<(&Children, Changed<Children>)>,
+            &Transform,
+            Changed<Transform>,
+            &mut GlobalTransform,
+        ),
+        With<Parent>,
+    >,
+    parent_query: &Query<(&Parent, Changed<Parent>)>,
+    dirty: &HashSet<Entity>,
     entity: Entity,
     expected_parent: Entity,
     mut changed: bool,
     // We use a result here to use the `?` operator. Ideally we'd use a try block instead
 ) -> Result<(), ()> {
-    let global_matrix = {
-        let (transform, transform_changed, mut global_transform, child_parent) =
-            transform_query.get_mut(entity).map_err(drop)?;
-        // Note that for parallelising, this check cannot occur here, since there is an `&mut GlobalTransform` (in global_transform)
-        assert_eq!(
-            child_parent.0, expected_parent,
-            "Malformed hierarchy. This probably means that your hierarchy has been improperly maintained, or contains a cycle"
-        );
-        changed |= transform_changed;
+    // The unchanged subtrees without a change below them are skipped
+    if !changed && !dirty.contains(&entity) {
+        return Ok(());
+    }
+
+    let (child_parent, parent_changed) = parent_query.get(entity).map_err(drop)?;
+    // This check must occur before accessing the `GlobalTransform` mutably: another task could
+    // be accessing it if `entity` was a child of several entities
+    assert_eq!(
+        child_parent.0, expected_parent,
+        "Malformed hierarchy. This probably means that your hierarchy has been improperly maintained, or contains a cycle"
+    );
+
+    let (children, global_matrix) = {
+        let (children, transform, transform_changed, mut global_transform) =
+            transform_query.get_unchecked(entity).map_err(drop)?;
+        changed |= parent_changed || transform_changed;
         if changed {
             *global_transform = parent.mul_transform(*transform);
         }
-        *global_transform
+        (children, *global_transform)
     };
 
-    let (children, changed_children) = children_query.get(entity).map_err(drop)?;
+    let (children, changed_children) = children.ok_or(())?;
     // If our `Children` has changed, we need to recalculate everything below us
     changed |= changed_children;
     for child in children.iter() {
         let _ = propagate_recursive(
             &global_matrix,
             transform_query,
-            children_query,
+            parent_query,
+            dirty,
             *child,
             entity,
             changed,
@@ -99,6 +165,7 @@ mod test {
     use bevy_ecs::prelude::*;
     use bevy_ecs::system::CommandQueue;
     use bevy_math::vec3;
+    use bevy_tasks::{ComputeTaskPool, TaskPool};
 
     use crate::components::{GlobalTransform, Transform};
     use crate::systems::transform_propagate_system;
@@ -336,6 +403,122 @@ mod test {
             );
         }
     }
+    #[test]
+    fn skip_unchanged_subtrees() {
+        let mut world = World::default();
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(parent_update_system);
+        update_stage.add_system(transform_propagate_system.after(parent_update_system));
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", update_stage);
+
+        let mut children = Vec::new();
+        let changed_root = world
+            .spawn()
+            .insert_bundle(TransformBundle::from(Transform::from_xyz(1.0, 0.0, 0.0)))
+            .with_children(|parent| {
+                children.push(
+                    parent
+                        .spawn_bundle(TransformBundle::from(Transform::from_xyz(0.0, 2.0, 0.0)))
+                        .id(),
+                );
+                children.push(
+                    parent
+                        .spawn_bundle(TransformBundle::from(Transform::from_xyz(0.0, 0.0, 3.0)))
+                        .id(),
+                );
+            })
+            .id();
+        let mut grandchild = None;
+        world
+            .spawn()
+            .insert_bundle(TransformBundle::from(Transform::from_xyz(1.0, 0.0, 0.0)))
+            .with_children(|parent| {
+                children.push(
+                    parent
+                        .spawn_bundle(TransformBundle::identity())
+                        .with_children(|parent| {
+                            grandchild =
+                                Some(parent.spawn_bundle(TransformBundle::identity()).id());
+                        })
+                        .id(),
+                );
+            });
+        children.push(grandchild.unwrap());
+        schedule.run(&mut world);
+
+        // The `GlobalTransform`s are only overwritten when propagation reaches them
+        let marker = GlobalTransform::from_xyz(-1.0, -1.0, -1.0);
+        for child in &children {
+            *world.get_mut::<GlobalTransform>(*child).unwrap() = marker;
+        }
+        world
+            .get_mut::<Transform>(changed_root)
+            .unwrap()
+            .translation
+            .x = 2.0;
+        world
+            .get_mut::<Transform>(children[3])
+            .unwrap()
+            .translation
+            .x = 1.0;
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *world.get::<GlobalTransform>(children[0]).unwrap(),
+            GlobalTransform::from_xyz(2.0, 2.0, 0.0)
+        );
+        assert_eq!(
+            *world.get::<GlobalTransform>(children[1]).unwrap(),
+            GlobalTransform::from_xyz(2.0, 0.0, 3.0)
+        );
+        // The unchanged ancestor of a changed entity isn't updated
+        assert_eq!(*world.get::<GlobalTransform>(children[2]).unwrap(), marker);
+        assert_eq!(
+            *world.get::<GlobalTransform>(children[3]).unwrap(),
+            marker * Transform::from_xyz(1.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn orphan_is_relative_to_reference_frame() {
+        let mut world = World::default();
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(parent_update_system);
+        update_stage.add_system(transform_propagate_system.after(parent_update_system));
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", update_stage);
+
+        let mut child = None;
+        world
+            .spawn()
+            .insert_bundle(TransformBundle::from(Transform::from_xyz(1.0, 0.0, 0.0)))
+            .with_children(|parent| {
+                child = Some(
+                    parent
+                        .spawn_bundle(TransformBundle::from(Transform::from_xyz(0.0, 2.0, 0.0)))
+                        .id(),
+                );
+            });
+        let child = child.unwrap();
+        schedule.run(&mut world);
+        assert_eq!(
+            *world.get::<GlobalTransform>(child).unwrap(),
+            GlobalTransform::from_xyz(1.0, 2.0, 0.0)
+        );
+
+        world.entity_mut(child).remove::<Parent>();
+        schedule.run(&mut world);
+        assert_eq!(
+            *world.get::<GlobalTransform>(child).unwrap(),
+            GlobalTransform::from_xyz(0.0, 2.0, 0.0)
+        );
+    }
+
     #[test]
     #[should_panic]
     fn panic_when_hierarchy_cycle() {
@@ -343,6 +526,8 @@ mod test {
         // This test is run on a single thread in order to avoid breaking the global task pool by panicking
         // This fixes the flaky tests reported in https://github.com/bevyengine/bevy/issues/4996
         let mut update_stage = SystemStage::single_threaded();
+        // The hierarchies are propagated on the task pool even from a single threaded stage
+        ComputeTaskPool::init(TaskPool::default);
 
         update_stage.add_system(parent_update_system);
         update_stage.add_system(transform_propagate_system.after(parent_update_system));
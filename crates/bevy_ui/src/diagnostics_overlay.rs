@@ -0,0 +1,391 @@
+use crate::{
+    entity::{NodeBundle, TextBundle},
+    AlignItems, Display, FlexDirection, PositionType, Size, Style, UiColor, UiRect, Val, ZIndex,
+};
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics, FrameTimeDiagnosticsPlugin};
+use bevy_ecs::{
+    entity::Entity,
+    prelude::Component,
+    query::{With, Without},
+    schedule::ParallelSystemDescriptorCoercion,
+    system::{Commands, Query, Res, ResMut},
+};
+use bevy_hierarchy::BuildChildren;
+use bevy_input::{keyboard::KeyCode, Input};
+use bevy_render::color::Color;
+use bevy_text::{Text, TextSection, TextStyle};
+use bevy_time::{Time, Timer};
+use bevy_utils::{default, Duration};
+use std::fmt::Write;
+
+/// The height of the bars of the frame time graph, in logical pixels per millisecond
+const GRAPH_PIXELS_PER_MILLISECOND: f32 = 2.0;
+/// The height of the frame time graph, in logical pixels
+const GRAPH_HEIGHT: f32 = 100.0;
+/// The width of the bars of the frame time graph, in logical pixels
+const GRAPH_BAR_WIDTH: f32 = 4.0;
+
+/// An App Plugin that displays the diagnostics as a text overlay in the top left corner of the
+/// window, and optionally a graph of the recent frame times.
+///
+/// Only the diagnostics added by other plugins are displayed, like the FPS and frame time of the
+/// [`FrameTimeDiagnosticsPlugin`] and the entity count of the
+/// [`EntityCountDiagnosticsPlugin`](bevy_diagnostic::EntityCountDiagnosticsPlugin).
+///
+/// The text has no font by default, set one in the [`DiagnosticsOverlayConfig`] resource:
+///
+/// ```
+/// # use bevy_asset::AssetServer;
+/// # use bevy_ecs::system::{Res, ResMut};
+/// # use bevy_ui::DiagnosticsOverlayConfig;
+/// fn setup(mut config: ResMut<DiagnosticsOverlayConfig>, asset_server: Res<AssetServer>) {
+///     config.text_style.font = asset_server.load("fonts/FiraMono-Medium.ttf");
+/// }
+/// # bevy_ecs::system::assert_is_system(setup);
+/// ```
+pub struct DiagnosticsOverlayPlugin {
+    pub text_style: TextStyle,
+    pub refresh_interval: Duration,
+    pub filter: Option<Vec<DiagnosticId>>,
+    pub frame_time_graph: bool,
+    pub toggle_key: Option<KeyCode>,
+}
+
+impl Default for DiagnosticsOverlayPlugin {
+    fn default() -> Self {
+        DiagnosticsOverlayPlugin {
+            text_style: TextStyle {
+                font_size: 16.0,
+                ..default()
+            },
+            refresh_interval: Duration::from_millis(250),
+            filter: None,
+            frame_time_graph: true,
+            toggle_key: Some(KeyCode::F12),
+        }
+    }
+}
+
+impl Plugin for DiagnosticsOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DiagnosticsOverlayConfig {
+            text_style: self.text_style.clone(),
+            refresh_interval: self.refresh_interval,
+            filter: self.filter.clone(),
+            frame_time_graph: self.frame_time_graph,
+            toggle_key: self.toggle_key,
+            visible: true,
+        })
+        .insert_resource(DiagnosticsOverlayState {
+            timer: Timer::new(self.refresh_interval, true),
+            root: None,
+        })
+        .add_system(toggle_diagnostics_overlay)
+        .add_system(update_diagnostics_overlay.after(toggle_diagnostics_overlay));
+    }
+}
+
+impl DiagnosticsOverlayPlugin {
+    pub fn filtered(filter: Vec<DiagnosticId>) -> Self {
+        DiagnosticsOverlayPlugin {
+            filter: Some(filter),
+            ..Default::default()
+        }
+    }
+}
+
+/// The configuration of the [`DiagnosticsOverlayPlugin`], which can be changed at runtime
+pub struct DiagnosticsOverlayConfig {
+    /// The style of the text of the diagnostics
+    pub text_style: TextStyle,
+    /// How often the displayed values are refreshed
+    pub refresh_interval: Duration,
+    /// The diagnostics to display, or all the enabled diagnostics if `None`
+    pub filter: Option<Vec<DiagnosticId>>,
+    /// Whether to display a graph of the recent
+    /// [frame times](FrameTimeDiagnosticsPlugin::FRAME_TIME) under the text
+    pub frame_time_graph: bool,
+    /// The key toggling the overlay
+    pub toggle_key: Option<KeyCode>,
+    /// Whether the overlay is displayed
+    pub visible: bool,
+}
+
+/// State used by the [`DiagnosticsOverlayPlugin`]
+struct DiagnosticsOverlayState {
+    timer: Timer,
+    root: Option<Entity>,
+}
+
+/// Marks the root node of the overlay of the [`DiagnosticsOverlayPlugin`]
+#[derive(Component)]
+pub struct DiagnosticsOverlay;
+
+/// Marks the text of the overlay of the [`DiagnosticsOverlayPlugin`]
+#[derive(Component)]
+pub struct DiagnosticsOverlayText;
+
+/// Marks the node containing the bars of the frame time graph of the
+/// [`DiagnosticsOverlayPlugin`]
+#[derive(Component)]
+pub struct FrameTimeGraph;
+
+/// A bar of the frame time graph of the [`DiagnosticsOverlayPlugin`], displaying the frame time
+/// at this index in the history of the diagnostic
+#[derive(Component)]
+pub struct FrameTimeGraphBar(pub usize);
+
+/// Toggles the overlay of the [`DiagnosticsOverlayPlugin`] when its key is pressed.
+fn toggle_diagnostics_overlay(
+    mut config: ResMut<DiagnosticsOverlayConfig>,
+    keyboard_input: Res<Input<KeyCode>>,
+) {
+    if let Some(toggle_key) = config.toggle_key {
+        if keyboard_input.just_pressed(toggle_key) {
+            config.visible = !config.visible;
+        }
+    }
+}
+
+/// Spawns the overlay of the [`DiagnosticsOverlayPlugin`], and refreshes the displayed
+/// diagnostics.
+#[allow(clippy::too_many_arguments)]
+fn update_diagnostics_overlay(
+    mut commands: Commands,
+    mut state: ResMut<DiagnosticsOverlayState>,
+    config: Res<DiagnosticsOverlayConfig>,
+    time: Res<Time>,
+    diagnostics: Res<Diagnostics>,
+    mut root_query: Query<&mut Style, With<DiagnosticsOverlay>>,
+    mut text_query: Query<&mut Text, With<DiagnosticsOverlayText>>,
+    mut graph_query: Query<
+        (&mut Style, &mut UiColor, &FrameTimeGraphBar),
+        Without<DiagnosticsOverlay>,
+    >,
+    mut graph_root_query: Query<
+        &mut Style,
+        (
+            With<FrameTimeGraph>,
+            Without<DiagnosticsOverlay>,
+            Without<FrameTimeGraphBar>,
+        ),
+    >,
+) {
+    let root = match state.root.filter(|root| root_query.get(*root).is_ok()) {
+        Some(root) => root,
+        None => {
+            state.root = Some(spawn_overlay(&mut commands, &config, &diagnostics));
+            return;
+        }
+    };
+
+    let refresh = state.timer.tick(time.delta()).just_finished() || config.is_changed();
+    if config.is_changed() {
+        state.timer.set_duration(config.refresh_interval);
+        if let Ok(mut style) = root_query.get_mut(root) {
+            style.display = if config.visible {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+        for mut style in graph_root_query.iter_mut() {
+            style.display = if config.frame_time_graph {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+    }
+    if !refresh || !config.visible {
+        return;
+    }
+
+    let mut value = String::new();
+    for diagnostic in displayed_diagnostics(&config, &diagnostics) {
+        write_diagnostic(&mut value, diagnostic);
+    }
+    for mut text in text_query.iter_mut() {
+        text.sections = vec![TextSection {
+            value: value.clone(),
+            style: config.text_style.clone(),
+        }];
+    }
+
+    if config.frame_time_graph {
+        let frame_times = diagnostics
+            .get(FrameTimeDiagnosticsPlugin::FRAME_TIME)
+            .map(|diagnostic| diagnostic.values().copied().collect::<Vec<_>>())
+            .unwrap_or_default();
+        for (mut style, mut color, bar) in graph_query.iter_mut() {
+            let frame_time = frame_times.get(bar.0).copied().unwrap_or(0.0);
+            let height = frame_time as f32 * 1000.0 * GRAPH_PIXELS_PER_MILLISECOND;
+            style.size.height = Val::Px(height.min(GRAPH_HEIGHT));
+            *color = frame_time_color(frame_time).into();
+        }
+    }
+}
+
+fn spawn_overlay(
+    commands: &mut Commands,
+    config: &DiagnosticsOverlayConfig,
+    diagnostics: &Diagnostics,
+) -> Entity {
+    let bar_count = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .map_or(0, |diagnostic| diagnostic.get_max_history_length());
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                display: if config.visible {
+                    Display::Flex
+                } else {
+                    Display::None
+                },
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    left: Val::Px(4.0),
+                    top: Val::Px(4.0),
+                    ..default()
+                },
+                padding: UiRect::all(Val::Px(4.0)),
+                // The children are laid out from the top
+                flex_direction: FlexDirection::ColumnReverse,
+                ..default()
+            },
+            color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+            ..default()
+        })
+        .insert(ZIndex::Global(i32::MAX))
+        .insert(DiagnosticsOverlay)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section("", config.text_style.clone(), default()),
+                    ..default()
+                })
+                .insert(DiagnosticsOverlayText);
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        display: if config.frame_time_graph {
+                            Display::Flex
+                        } else {
+                            Display::None
+                        },
+                        size: Size::new(Val::Auto, Val::Px(GRAPH_HEIGHT)),
+                        margin: UiRect {
+                            top: Val::Px(4.0),
+                            ..default()
+                        },
+                        // The bars grow from the bottom
+                        align_items: AlignItems::FlexStart,
+                        ..default()
+                    },
+                    color: Color::NONE.into(),
+                    ..default()
+                })
+                .insert(FrameTimeGraph)
+                .with_children(|parent| {
+                    for index in 0..bar_count {
+                        parent
+                            .spawn_bundle(NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(GRAPH_BAR_WIDTH), Val::Px(0.0)),
+                                    margin: UiRect {
+                                        right: Val::Px(1.0),
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .insert(FrameTimeGraphBar(index));
+                    }
+                });
+        })
+        .id()
+}
+
+/// The enabled diagnostics passing the filter of the configuration
+fn displayed_diagnostics<'a>(
+    config: &'a DiagnosticsOverlayConfig,
+    diagnostics: &'a Diagnostics,
+) -> Box<dyn Iterator<Item = &'a Diagnostic> + 'a> {
+    match &config.filter {
+        Some(filter) => Box::new(
+            filter
+                .iter()
+                .filter_map(|id| diagnostics.get(*id))
+                .filter(|diagnostic| diagnostic.is_enabled),
+        ),
+        None => Box::new(
+            diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.is_enabled),
+        ),
+    }
+}
+
+/// Writes a line with the current value of the diagnostic, and its average when it has a history
+fn write_diagnostic(text: &mut String, diagnostic: &Diagnostic) {
+    let value = match diagnostic.value() {
+        Some(value) => value,
+        None => return,
+    };
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    let _ = write!(
+        text,
+        "{name}: {value:.2}{suffix}",
+        name = diagnostic.name,
+        suffix = diagnostic.suffix,
+    );
+    if diagnostic.get_max_history_length() > 1 {
+        if let Some(average) = diagnostic.average() {
+            let _ = write!(
+                text,
+                " (avg {average:.2}{suffix})",
+                suffix = diagnostic.suffix,
+            );
+        }
+    }
+}
+
+/// The color of a bar of the frame time graph: green at 60 FPS or more, yellow down to 30 FPS
+/// and red below
+fn frame_time_color(frame_time: f64) -> Color {
+    if frame_time <= 1.0 / 59.0 {
+        Color::GREEN
+    } else if frame_time <= 1.0 / 29.0 {
+        Color::YELLOW
+    } else {
+        Color::RED
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_diagnostic::{Diagnostic, DiagnosticId};
+
+    use super::write_diagnostic;
+
+    #[test]
+    fn diagnostics_are_written_with_their_average() {
+        let mut fps = Diagnostic::new(DiagnosticId::from_u128(1), "fps", 20);
+        fps.add_measurement(50.0);
+        fps.add_measurement(70.0);
+        let mut frame_time =
+            Diagnostic::new(DiagnosticId::from_u128(2), "frame_time", 1).with_suffix("s");
+        frame_time.add_measurement(0.5);
+        let empty = Diagnostic::new(DiagnosticId::from_u128(3), "empty", 1);
+
+        let mut text = String::new();
+        write_diagnostic(&mut text, &fps);
+        write_diagnostic(&mut text, &empty);
+        write_diagnostic(&mut text, &frame_time);
+        assert_eq!(text, "fps: 70.00 (avg 60.00)\nframe_time: 0.50s");
+    }
+}
@@ -1,8 +1,9 @@
 //! This module contains the bundles used in Bevy's UI
 
 use crate::{
-    widget::{Button, ImageMode},
-    CalculatedSize, FocusPolicy, Interaction, Node, Style, UiColor, UiImage,
+    widget::{Button, ImageMode, TextInput, TextOverflow},
+    BorderColor, CalculatedSize, FocusPolicy, Focusable, Interaction, Node, ScrollPosition, Size,
+    Style, UiColor, UiImage, Val,
 };
 use bevy_ecs::{
     bundle::Bundle,
@@ -10,7 +11,7 @@ use bevy_ecs::{
     query::QueryItem,
 };
 use bevy_render::{camera::Camera, extract_component::ExtractComponent, view::Visibility};
-use bevy_text::Text;
+use bevy_text::{Text, TextLayoutInfo};
 use bevy_transform::prelude::{GlobalTransform, Transform};
 
 /// The basic UI node
@@ -20,8 +21,12 @@ pub struct NodeBundle {
     pub node: Node,
     /// Describes the style including flexbox settings
     pub style: Style,
+    /// The scroll offset of the content, used if the node has [`Overflow::Scroll`](crate::Overflow::Scroll)
+    pub scroll_position: ScrollPosition,
     /// Describes the color of the node
     pub color: UiColor,
+    /// Describes the color of the border of the node
+    pub border_color: BorderColor,
     /// Describes the image of the node
     pub image: UiImage,
     /// Whether this node should block interaction with lower nodes
@@ -68,6 +73,10 @@ pub struct TextBundle {
     pub style: Style,
     /// Contains the text of the node
     pub text: Text,
+    /// Describes what happens to text that doesn't fit inside the node
+    pub text_overflow: TextOverflow,
+    /// The positioned glyphs of the text, updated whenever it is laid out
+    pub text_layout_info: TextLayoutInfo,
     /// The calculated size based on the given image
     pub calculated_size: CalculatedSize,
     /// Whether this node should block interaction with lower nodes
@@ -85,6 +94,8 @@ impl Default for TextBundle {
         TextBundle {
             focus_policy: FocusPolicy::Pass,
             text: Default::default(),
+            text_overflow: Default::default(),
+            text_layout_info: Default::default(),
             node: Default::default(),
             calculated_size: Default::default(),
             style: Default::default(),
@@ -95,6 +106,61 @@ impl Default for TextBundle {
     }
 }
 
+/// A UI node that is an editable text field
+#[derive(Bundle, Clone, Debug)]
+pub struct TextInputBundle {
+    /// Describes the size of the node
+    pub node: Node,
+    /// Describes the style including flexbox settings
+    pub style: Style,
+    /// Contains the edited text
+    pub text: Text,
+    /// Holds the caret and selection of the field
+    pub text_input: TextInput,
+    /// Describes what happens to text that doesn't fit inside the node
+    pub text_overflow: TextOverflow,
+    /// The positioned glyphs of the text, used to place the caret
+    pub text_layout_info: TextLayoutInfo,
+    /// The calculated size based on the given text
+    pub calculated_size: CalculatedSize,
+    /// Describes whether and how the field has been interacted with by the input
+    pub interaction: Interaction,
+    /// Lets the field receive the focus by navigating to it with the keyboard or a gamepad
+    pub focusable: Focusable,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The transform of the node
+    pub transform: Transform,
+    /// The global transform of the node
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+}
+
+impl Default for TextInputBundle {
+    fn default() -> Self {
+        TextInputBundle {
+            // Keep empty fields large enough to be clicked
+            style: Style {
+                min_size: Size::new(Val::Px(100.0), Val::Px(20.0)),
+                ..Default::default()
+            },
+            text: Text::with_section("", Default::default(), Default::default()),
+            text_input: Default::default(),
+            focusable: Default::default(),
+            text_overflow: Default::default(),
+            text_layout_info: Default::default(),
+            node: Default::default(),
+            calculated_size: Default::default(),
+            interaction: Default::default(),
+            focus_policy: FocusPolicy::Block,
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+        }
+    }
+}
+
 /// A UI node that is a button
 #[derive(Bundle, Clone, Debug)]
 pub struct ButtonBundle {
@@ -106,10 +172,14 @@ pub struct ButtonBundle {
     pub style: Style,
     /// Describes whether and how the button has been interacted with by the input
     pub interaction: Interaction,
+    /// Lets the button receive the focus by navigating to it with the keyboard or a gamepad
+    pub focusable: Focusable,
     /// Whether this node should block interaction with lower nodes
     pub focus_policy: FocusPolicy,
     /// The color of the node
     pub color: UiColor,
+    /// The color of the border of the node
+    pub border_color: BorderColor,
     /// The image of the node
     pub image: UiImage,
     /// The transform of the node
@@ -125,10 +195,12 @@ impl Default for ButtonBundle {
         ButtonBundle {
             button: Button,
             interaction: Default::default(),
+            focusable: Default::default(),
             focus_policy: Default::default(),
             node: Default::default(),
             style: Default::default(),
             color: Default::default(),
+            border_color: Default::default(),
             image: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
@@ -112,6 +112,8 @@ impl From<Display> for taffy::style::Display {
         match value {
             Display::Flex => taffy::style::Display::Flex,
             Display::None => taffy::style::Display::None,
+            // The children of grid nodes are positioned in their cells, see `FlexSurface`
+            Display::Grid => taffy::style::Display::Flex,
         }
     }
 }
@@ -0,0 +1,206 @@
+//! Placement of the children of [`Display::Grid`](crate::Display::Grid) nodes, and sizing of
+//! their rows and columns
+
+use crate::{GridPlacement, GridTrack};
+use std::ops::Range;
+
+/// The tracks occupied by a child of a grid
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct GridArea {
+    pub columns: Range<usize>,
+    pub rows: Range<usize>,
+}
+
+/// Places the children of a grid with `column_count` columns, given the placement of each child
+/// along the columns and the rows.
+///
+/// Children without a start are placed in the next cell that is free, row by row. Returns the
+/// area of each child and the number of rows they need.
+pub(crate) fn place_items(
+    column_count: usize,
+    placements: &[(GridPlacement, GridPlacement)],
+) -> (Vec<GridArea>, usize) {
+    let column_count = column_count.max(1);
+    let mut occupied: Vec<Vec<bool>> = Vec::new();
+    let is_free = |occupied: &Vec<Vec<bool>>, area: &GridArea| {
+        area.rows.clone().all(|row| {
+            occupied.get(row).map_or(true, |cells| {
+                area.columns.clone().all(|column| !cells[column])
+            })
+        })
+    };
+
+    // The position after the last child placed automatically, as (row, column)
+    let mut cursor = (0, 0);
+    let mut areas = Vec::with_capacity(placements.len());
+    for (column, row) in placements {
+        let column_span = column.span.clamp(1, column_count);
+        let row_span = row.span.max(1);
+        let column_start = column
+            .start
+            .map(|start| start.min(column_count - column_span));
+        let area_at = |row: usize, column: usize| GridArea {
+            columns: column..column + column_span,
+            rows: row..row + row_span,
+        };
+
+        let area = match (row.start, column_start) {
+            (Some(row), Some(column)) => area_at(row, column),
+            _ => {
+                let (mut row, mut column) = match row.start {
+                    Some(row) => (row, 0),
+                    None => cursor,
+                };
+                loop {
+                    let area = area_at(row, column_start.unwrap_or(column));
+                    if area.columns.end <= column_count && is_free(&occupied, &area) {
+                        break area;
+                    }
+                    if column_start.is_some() || column + column_span >= column_count {
+                        row += 1;
+                        column = 0;
+                    } else {
+                        column += 1;
+                    }
+                }
+            }
+        };
+
+        if occupied.len() < area.rows.end {
+            occupied.resize(area.rows.end, vec![false; column_count]);
+        }
+        for row in area.rows.clone() {
+            for column in area.columns.clone() {
+                occupied[row][column] = true;
+            }
+        }
+        if row.start.is_none() {
+            cursor = (area.rows.start, area.columns.end);
+        }
+        areas.push(area);
+    }
+    (areas, occupied.len())
+}
+
+/// Computes the size of `count` tracks along an axis of a grid, in physical pixels.
+///
+/// `items` are the tracks spanned by each child, along with the size of the child along the
+/// axis. `available` is the size of the content of the grid, or `None` if the grid is sized by
+/// its content. Tracks past the end of `tracks` are sized as [`GridTrack::Auto`].
+pub(crate) fn size_tracks(
+    tracks: &[GridTrack],
+    count: usize,
+    items: &[(Range<usize>, f32)],
+    available: Option<f32>,
+    gap: f32,
+    scale_factor: f32,
+) -> Vec<f32> {
+    let track = |index: usize| tracks.get(index).copied().unwrap_or(GridTrack::Auto);
+    let content_size = |index: usize| {
+        items
+            .iter()
+            .filter(|(range, _)| *range == (index..index + 1))
+            .map(|(_, size)| *size)
+            .fold(0.0, f32::max)
+    };
+
+    let mut sizes: Vec<f32> = (0..count)
+        .map(|index| match (track(index), available) {
+            (GridTrack::Px(size), _) => size * scale_factor,
+            (GridTrack::Percent(percent), Some(available)) => percent / 100.0 * available,
+            (GridTrack::Fr(_), Some(_)) => 0.0,
+            (GridTrack::Auto | GridTrack::Percent(_) | GridTrack::Fr(_), _) => content_size(index),
+        })
+        .collect();
+
+    // Share the space left between the flexible tracks
+    if let Some(available) = available {
+        let total_factor: f32 = (0..count)
+            .filter_map(|index| match track(index) {
+                GridTrack::Fr(factor) => Some(factor.max(0.0)),
+                _ => None,
+            })
+            .sum();
+        if total_factor > 0.0 {
+            let used = sizes.iter().sum::<f32>() + gap * count.saturating_sub(1) as f32;
+            let free = (available - used).max(0.0);
+            for (index, size) in sizes.iter_mut().enumerate() {
+                if let GridTrack::Fr(factor) = track(index) {
+                    *size = free * factor.max(0.0) / total_factor;
+                }
+            }
+        }
+    }
+    sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{place_items, size_tracks, GridArea};
+    use crate::{GridPlacement, GridTrack};
+
+    fn area(columns: std::ops::Range<usize>, rows: std::ops::Range<usize>) -> GridArea {
+        GridArea { columns, rows }
+    }
+
+    #[test]
+    fn items_are_placed_row_by_row() {
+        let placements = [
+            (GridPlacement::AUTO, GridPlacement::AUTO),
+            (GridPlacement::AUTO.with_span(2), GridPlacement::AUTO),
+            (GridPlacement::AUTO, GridPlacement::AUTO.with_span(2)),
+            (GridPlacement::AUTO, GridPlacement::AUTO),
+        ];
+        let (areas, row_count) = place_items(3, &placements);
+        assert_eq!(
+            areas,
+            vec![
+                area(0..1, 0..1),
+                area(1..3, 0..1),
+                area(0..1, 1..3),
+                area(1..2, 1..2),
+            ]
+        );
+        assert_eq!(row_count, 3);
+    }
+
+    #[test]
+    fn explicit_placements_are_respected() {
+        let placements = [
+            (GridPlacement::start(1), GridPlacement::start(0)),
+            (GridPlacement::AUTO, GridPlacement::AUTO),
+            (GridPlacement::AUTO, GridPlacement::AUTO),
+            (GridPlacement::start(0), GridPlacement::AUTO),
+        ];
+        let (areas, row_count) = place_items(2, &placements);
+        assert_eq!(
+            areas,
+            vec![
+                area(1..2, 0..1),
+                area(0..1, 0..1),
+                area(0..1, 1..2),
+                area(0..1, 2..3),
+            ]
+        );
+        assert_eq!(row_count, 3);
+    }
+
+    #[test]
+    fn tracks_are_sized() {
+        let tracks = [
+            GridTrack::Px(50.0),
+            GridTrack::Auto,
+            GridTrack::Fr(1.0),
+            GridTrack::Fr(3.0),
+        ];
+        let items = [(1..2, 30.0), (1..2, 20.0), (2..3, 100.0), (0..4, 1000.0)];
+
+        let sizes = size_tracks(&tracks, 4, &items, Some(500.0), 10.0, 2.0);
+        // 500 - (100 + 30 + 3 * 10) = 340 left for the flexible tracks
+        assert_eq!(sizes, vec![100.0, 30.0, 85.0, 255.0]);
+
+        // Sized by its content, flexible tracks fit their content
+        let sizes = size_tracks(&tracks, 5, &items, None, 10.0, 2.0);
+        assert_eq!(sizes, vec![100.0, 30.0, 100.0, 0.0, 0.0]);
+    }
+}
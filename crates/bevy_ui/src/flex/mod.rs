@@ -1,15 +1,20 @@
 mod convert;
+mod grid;
 
-use crate::{CalculatedSize, Node, Style};
+use crate::{
+    scroll::clamp_scroll_offset, CalculatedSize, Display, GridPlacement, Node, Overflow,
+    ScrollPosition, Style, UiRect, UiWindowNodes, Val, WorldUi,
+};
 use bevy_ecs::{
     entity::Entity,
     event::EventReader,
-    query::{Changed, With, Without, WorldQuery},
+    query::{Changed, Or, With, Without, WorldQuery},
     system::{Query, Res, ResMut},
 };
 use bevy_hierarchy::{Children, Parent};
 use bevy_log::warn;
 use bevy_math::Vec2;
+use bevy_render::camera::Camera;
 use bevy_transform::components::Transform;
 use bevy_utils::HashMap;
 use bevy_window::{Window, WindowId, WindowScaleFactorChanged, Windows};
@@ -19,9 +24,32 @@ use taffy::{number::Number, Taffy};
 pub struct FlexSurface {
     entity_to_taffy: HashMap<Entity, taffy::node::Node>,
     window_nodes: HashMap<WindowId, taffy::node::Node>,
+    /// The nodes containing the [`WorldUi`] roots, with the size of the image they're rendered to
+    world_ui_nodes: HashMap<Entity, taffy::node::Node>,
+    /// The nodes with [`Display::Grid`]
+    grids: HashMap<Entity, GridContainer>,
+    /// The placement of the nodes that aren't placed automatically in a grid
+    grid_placements: HashMap<Entity, (GridPlacement, GridPlacement)>,
+    /// The taffy nodes wrapping each child of a grid, positioned and sized as its cell
+    grid_cells: HashMap<Entity, taffy::node::Node>,
     taffy: Taffy,
 }
 
+/// A node with [`Display::Grid`], whose children are laid out in cells
+#[derive(Debug)]
+struct GridContainer {
+    style: Style,
+    scale_factor: f64,
+    children: Vec<Entity>,
+    /// The taffy style of the node, with a minimum size fitting its content
+    taffy_style: Option<taffy::style::Style>,
+    /// The area of each child
+    areas: Vec<grid::GridArea>,
+    row_count: usize,
+    /// The size of each child when sized by its content, in physical pixels
+    content_sizes: Vec<Vec2>,
+}
+
 // SAFETY: as long as MeasureFunc is Send + Sync. https://github.com/DioxusLabs/taffy/issues/146
 // TODO: remove allow on lint - https://github.com/bevyengine/bevy/issues/3666
 #[allow(clippy::non_send_fields_in_send_ty)]
@@ -41,6 +69,9 @@ impl fmt::Debug for FlexSurface {
         f.debug_struct("FlexSurface")
             .field("entity_to_taffy", &self.entity_to_taffy)
             .field("window_nodes", &self.window_nodes)
+            .field("world_ui_nodes", &self.world_ui_nodes)
+            .field("grids", &self.grids)
+            .field("grid_cells", &self.grid_cells)
             .finish()
     }
 }
@@ -50,6 +81,10 @@ impl Default for FlexSurface {
         Self {
             entity_to_taffy: Default::default(),
             window_nodes: Default::default(),
+            world_ui_nodes: Default::default(),
+            grids: Default::default(),
+            grid_placements: Default::default(),
+            grid_cells: Default::default(),
             taffy: Taffy::new(),
         }
     }
@@ -68,6 +103,7 @@ impl FlexSurface {
         if !added {
             self.taffy.set_style(*taffy_node, taffy_style).unwrap();
         }
+        self.update_grid_style(entity, style, scale_factor);
     }
 
     pub fn upsert_leaf(
@@ -108,6 +144,34 @@ impl FlexSurface {
             let taffy_node = taffy.new_leaf(taffy_style, measure).unwrap();
             self.entity_to_taffy.insert(entity, taffy_node);
         }
+        self.update_grid_style(entity, style, scale_factor);
+    }
+
+    fn update_grid_style(&mut self, entity: Entity, style: &Style, scale_factor: f64) {
+        if style.grid_column == GridPlacement::AUTO && style.grid_row == GridPlacement::AUTO {
+            self.grid_placements.remove(&entity);
+        } else {
+            self.grid_placements
+                .insert(entity, (style.grid_column, style.grid_row));
+        }
+
+        if style.display == Display::Grid {
+            let grid = self.grids.entry(entity).or_insert_with(|| GridContainer {
+                style: style.clone(),
+                scale_factor,
+                children: Vec::new(),
+                taffy_style: None,
+                areas: Vec::new(),
+                row_count: 0,
+                content_sizes: Vec::new(),
+            });
+            grid.style = style.clone();
+            grid.scale_factor = scale_factor;
+            // The taffy style was reset, the minimum size is applied again
+            grid.taffy_style = None;
+        } else {
+            self.grids.remove(&entity);
+        }
     }
 
     pub fn update_children(&mut self, entity: Entity, children: &Children) {
@@ -123,10 +187,37 @@ without UI components as a child of an entity with UI components, results may be
             }
         }
 
-        let taffy_node = self.entity_to_taffy.get(&entity).unwrap();
-        self.taffy
-            .set_children(*taffy_node, &taffy_children)
-            .unwrap();
+        let taffy_node = *self.entity_to_taffy.get(&entity).unwrap();
+        if let Some(grid) = self.grids.get_mut(&entity) {
+            // Wrap each child in a node positioned as its cell
+            grid.children = children
+                .iter()
+                .filter(|child| self.entity_to_taffy.contains_key(child))
+                .copied()
+                .collect();
+            let mut cells = Vec::with_capacity(taffy_children.len());
+            for (child, taffy_child) in grid.children.iter().zip(taffy_children) {
+                let taffy = &mut self.taffy;
+                let cell = *self.grid_cells.entry(*child).or_insert_with(|| {
+                    taffy
+                        .new_node(taffy::style::Style::default(), &Vec::new())
+                        .unwrap()
+                });
+                self.taffy.set_children(cell, &[taffy_child]).unwrap();
+                cells.push(cell);
+            }
+            self.taffy.set_children(taffy_node, &cells).unwrap();
+        } else {
+            // Detach the children from the cells of a grid they were in
+            for child in children.iter() {
+                if let Some(cell) = self.grid_cells.get(child) {
+                    self.taffy.set_children(*cell, &[]).unwrap();
+                }
+            }
+            self.taffy
+                .set_children(taffy_node, &taffy_children)
+                .unwrap();
+        }
     }
 
     pub fn update_window(&mut self, window: &Window) {
@@ -163,14 +254,223 @@ without UI components as a child of an entity with UI components, results may be
         self.taffy.set_children(*taffy_node, &child_nodes).unwrap();
     }
 
+    /// Lays out each [`WorldUi`] root node in a node of the size of the image it is rendered to,
+    /// given in physical pixels.
+    pub fn set_world_ui_roots(&mut self, roots: impl Iterator<Item = (Entity, Vec2)>) {
+        let roots: HashMap<Entity, Vec2> = roots.collect();
+        let taffy = &mut self.taffy;
+        self.world_ui_nodes.retain(|root, node| {
+            let keep = roots.contains_key(root);
+            if !keep {
+                taffy.remove(*node);
+            }
+            keep
+        });
+
+        for (root, size) in roots {
+            let taffy_root = match self.entity_to_taffy.get(&root) {
+                Some(taffy_root) => *taffy_root,
+                None => continue,
+            };
+            let style = taffy::style::Style {
+                size: taffy::geometry::Size {
+                    width: taffy::style::Dimension::Points(size.x),
+                    height: taffy::style::Dimension::Points(size.y),
+                },
+                ..Default::default()
+            };
+            let taffy = &mut self.taffy;
+            let node = *self
+                .world_ui_nodes
+                .entry(root)
+                .or_insert_with(|| taffy.new_node(style, &[taffy_root]).unwrap());
+            if *self.taffy.style(node).unwrap() != style {
+                self.taffy.set_style(node, style).unwrap();
+            }
+            if self.taffy.children(node).unwrap() != [taffy_root] {
+                self.taffy.set_children(node, &[taffy_root]).unwrap();
+            }
+        }
+    }
+
     pub fn compute_window_layouts(&mut self) {
-        for window_node in self.window_nodes.values() {
+        if self.grids.is_empty() {
+            self.compute_layouts();
+            return;
+        }
+        // Grids are first sized to fit their content, then their children are placed in cells
+        // fitting the size of the grids
+        self.measure_grids();
+        self.compute_layouts();
+        if self.place_grid_cells() {
+            self.compute_layouts();
+        }
+    }
+
+    fn compute_layouts(&mut self) {
+        for window_node in self
+            .window_nodes
+            .values()
+            .chain(self.world_ui_nodes.values())
+        {
             self.taffy
                 .compute_layout(*window_node, taffy::geometry::Size::undefined())
                 .unwrap();
         }
     }
 
+    /// Places the children of each grid and sets the minimum size of the grid to fit them.
+    fn measure_grids(&mut self) {
+        for (entity, grid) in self.grids.iter_mut() {
+            let column_count = grid.style.grid_template_columns.len();
+            let placements: Vec<_> = grid
+                .children
+                .iter()
+                .map(|child| {
+                    self.grid_placements
+                        .get(child)
+                        .copied()
+                        .unwrap_or((GridPlacement::AUTO, GridPlacement::AUTO))
+                })
+                .collect();
+            let (areas, row_count) = grid::place_items(column_count, &placements);
+            grid.areas = areas;
+            grid.row_count = row_count;
+
+            // Lay out each child on its own to find the size of its content
+            grid.content_sizes.clear();
+            for child in &grid.children {
+                let taffy_child = self.entity_to_taffy[child];
+                self.taffy
+                    .compute_layout(taffy_child, taffy::geometry::Size::undefined())
+                    .unwrap();
+                let size = self.taffy.layout(taffy_child).unwrap().size;
+                grid.content_sizes.push(Vec2::new(size.width, size.height));
+                // The layout of the child is computed again in its cell
+                self.taffy.mark_dirty(self.grid_cells[child]).unwrap();
+            }
+
+            let scale_factor = grid.scale_factor as f32;
+            let gap = resolve_gap(&grid.style, scale_factor, None);
+            let (columns, rows) = grid.track_sizes(None, None, gap);
+            let spacing = Vec2::new(
+                resolve_length(grid.style.padding.left, scale_factor, None)
+                    + resolve_length(grid.style.padding.right, scale_factor, None)
+                    + resolve_length(grid.style.border.left, scale_factor, None)
+                    + resolve_length(grid.style.border.right, scale_factor, None),
+                resolve_length(grid.style.padding.top, scale_factor, None)
+                    + resolve_length(grid.style.padding.bottom, scale_factor, None)
+                    + resolve_length(grid.style.border.top, scale_factor, None)
+                    + resolve_length(grid.style.border.bottom, scale_factor, None),
+            );
+            let content_size = Vec2::new(
+                columns.iter().sum::<f32>() + gap.x * columns.len().saturating_sub(1) as f32,
+                rows.iter().sum::<f32>() + gap.y * rows.len().saturating_sub(1) as f32,
+            ) + spacing;
+
+            // Only grow the grid to fit its content along axes it's sized by its content
+            let mut taffy_style = convert::from_style(grid.scale_factor, &grid.style);
+            let fit_content = |size: taffy::style::Dimension,
+                               min_size: &mut taffy::style::Dimension,
+                               content_size: f32| {
+                if matches!(
+                    size,
+                    taffy::style::Dimension::Auto | taffy::style::Dimension::Undefined
+                ) {
+                    *min_size = match *min_size {
+                        taffy::style::Dimension::Points(min) => {
+                            taffy::style::Dimension::Points(min.max(content_size))
+                        }
+                        taffy::style::Dimension::Percent(percent) => {
+                            taffy::style::Dimension::Percent(percent)
+                        }
+                        _ => taffy::style::Dimension::Points(content_size),
+                    };
+                }
+            };
+            fit_content(
+                taffy_style.size.width,
+                &mut taffy_style.min_size.width,
+                content_size.x,
+            );
+            fit_content(
+                taffy_style.size.height,
+                &mut taffy_style.min_size.height,
+                content_size.y,
+            );
+            if grid.taffy_style != Some(taffy_style) {
+                self.taffy
+                    .set_style(self.entity_to_taffy[entity], taffy_style)
+                    .unwrap();
+                grid.taffy_style = Some(taffy_style);
+            }
+        }
+    }
+
+    /// Positions and sizes the cells of each grid to fit the size of the grid. Returns `true`
+    /// if any cell changed.
+    fn place_grid_cells(&mut self) -> bool {
+        let mut changed = false;
+        for (entity, grid) in self.grids.iter() {
+            let size = self
+                .taffy
+                .layout(self.entity_to_taffy[entity])
+                .unwrap()
+                .size;
+            let scale_factor = grid.scale_factor as f32;
+            let style = &grid.style;
+            let (content_offset, content_size) =
+                grid_content_box(style, scale_factor, Vec2::new(size.width, size.height));
+
+            let gap = resolve_gap(style, scale_factor, Some(content_size));
+            let (columns, rows) = grid.track_sizes(Some(content_size.x), Some(content_size.y), gap);
+            let offsets = |sizes: &[f32], gap: f32| {
+                sizes
+                    .iter()
+                    .scan(0.0, |offset, size| {
+                        let start = *offset;
+                        *offset += size + gap;
+                        Some(start)
+                    })
+                    .collect::<Vec<f32>>()
+            };
+            let column_offsets = offsets(&columns, gap.x);
+            let row_offsets = offsets(&rows, gap.y);
+            let span_size = |sizes: &[f32], range: &std::ops::Range<usize>, gap: f32| {
+                sizes[range.clone()].iter().sum::<f32>() + gap * (range.len() - 1) as f32
+            };
+
+            for (child, area) in grid.children.iter().zip(&grid.areas) {
+                let width = span_size(&columns, &area.columns, gap.x);
+                let height = span_size(&rows, &area.rows, gap.y);
+                // Rows go from the top down, while the y axis of taffy points up in bevy
+                let x = content_offset.x + column_offsets[area.columns.start];
+                let y = content_offset.y + content_size.y - row_offsets[area.rows.start] - height;
+                let cell_style = taffy::style::Style {
+                    position_type: taffy::style::PositionType::Absolute,
+                    position: taffy::geometry::Rect {
+                        start: taffy::style::Dimension::Points(x),
+                        top: taffy::style::Dimension::Points(y),
+                        ..Default::default()
+                    },
+                    size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Points(width),
+                        height: taffy::style::Dimension::Points(height),
+                    },
+                    justify_content: style.justify_content.into(),
+                    align_items: style.align_items.into(),
+                    ..Default::default()
+                };
+                let cell = self.grid_cells[child];
+                if *self.taffy.style(cell).unwrap() != cell_style {
+                    self.taffy.set_style(cell, cell_style).unwrap();
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
     pub fn get_layout(&self, entity: Entity) -> Result<&taffy::layout::Layout, FlexError> {
         if let Some(taffy_node) = self.entity_to_taffy.get(&entity) {
             self.taffy
@@ -184,6 +484,115 @@ with UI components as a child of an entity without UI components, results may be
             Err(FlexError::InvalidHierarchy)
         }
     }
+
+    /// Returns the position of the bottom left corner of a node relative to its parent, in
+    /// physical pixels.
+    pub fn get_location(&self, entity: Entity) -> Result<Vec2, FlexError> {
+        let layout = self.get_layout(entity)?;
+        let mut location = Vec2::new(layout.location.x, layout.location.y);
+        // The children of grids are positioned relative to their cell
+        if let Some(cell) = self.grid_cells.get(&entity) {
+            if self
+                .taffy
+                .child_count(*cell)
+                .map_err(FlexError::TaffyError)?
+                > 0
+            {
+                let cell_layout = self.taffy.layout(*cell).map_err(FlexError::TaffyError)?;
+                location += Vec2::new(cell_layout.location.x, cell_layout.location.y);
+            }
+        }
+        Ok(location)
+    }
+}
+
+impl GridContainer {
+    /// Returns the size of the columns and the rows, in physical pixels.
+    fn track_sizes(
+        &self,
+        width: Option<f32>,
+        height: Option<f32>,
+        gap: Vec2,
+    ) -> (Vec<f32>, Vec<f32>) {
+        let scale_factor = self.scale_factor as f32;
+        let column_count = self.style.grid_template_columns.len().max(1);
+        let row_count = self.style.grid_template_rows.len().max(self.row_count);
+        let items = |axis: fn(&grid::GridArea) -> &std::ops::Range<usize>,
+                     size: fn(Vec2) -> f32| {
+            self.areas
+                .iter()
+                .zip(&self.content_sizes)
+                .map(|(area, content_size)| (axis(area).clone(), size(*content_size)))
+                .collect::<Vec<_>>()
+        };
+        let columns = grid::size_tracks(
+            &self.style.grid_template_columns,
+            column_count,
+            &items(|area| &area.columns, |size| size.x),
+            width,
+            gap.x,
+            scale_factor,
+        );
+        let rows = grid::size_tracks(
+            &self.style.grid_template_rows,
+            row_count,
+            &items(|area| &area.rows, |size| size.y),
+            height,
+            gap.y,
+            scale_factor,
+        );
+        (columns, rows)
+    }
+}
+
+/// Resolves a length in physical pixels, with percentages relative to `base`.
+fn resolve_length(value: Val, scale_factor: f32, base: Option<f32>) -> f32 {
+    match value {
+        Val::Px(value) => value * scale_factor,
+        Val::Percent(percent) => base.map_or(0.0, |base| percent / 100.0 * base),
+        Val::Auto | Val::Undefined => 0.0,
+    }
+}
+
+/// Returns the offset of the content of a grid from the bottom left of its border, where taffy
+/// places absolutely positioned children, and the size of its content, in physical pixels.
+fn grid_content_box(style: &Style, scale_factor: f32, size: Vec2) -> (Vec2, Vec2) {
+    // Padding and borders in percent are relative to the width of the grid
+    let resolve = |value| resolve_length(value, scale_factor, Some(size.x));
+    let padding = UiRect {
+        left: resolve(style.padding.left),
+        right: resolve(style.padding.right),
+        top: resolve(style.padding.top),
+        bottom: resolve(style.padding.bottom),
+    };
+    let border = UiRect {
+        left: resolve(style.border.left),
+        right: resolve(style.border.right),
+        top: resolve(style.border.top),
+        bottom: resolve(style.border.bottom),
+    };
+    let content_size = Vec2::new(
+        size.x - padding.left - padding.right - border.left - border.right,
+        size.y - padding.top - padding.bottom - border.top - border.bottom,
+    );
+    (Vec2::new(padding.left, padding.bottom), content_size)
+}
+
+/// Resolves the gaps between the columns and the rows of a grid, with percentages relative to
+/// the size of its content.
+fn resolve_gap(style: &Style, scale_factor: f32, content_size: Option<Vec2>) -> Vec2 {
+    Vec2::new(
+        resolve_length(
+            style.gap.width,
+            scale_factor,
+            content_size.map(|size| size.x),
+        ),
+        resolve_length(
+            style.gap.height,
+            scale_factor,
+            content_size.map(|size| size.y),
+        ),
+    )
 }
 
 #[derive(Debug)]
@@ -195,27 +604,35 @@ pub enum FlexError {
 #[allow(clippy::too_many_arguments)]
 pub fn flex_node_system(
     windows: Res<Windows>,
+    ui_window_nodes: Res<UiWindowNodes>,
     mut scale_factor_events: EventReader<WindowScaleFactorChanged>,
     mut flex_surface: ResMut<FlexSurface>,
-    root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
+    root_node_query: Query<Entity, (With<Node>, Without<Parent>, Without<WorldUi>)>,
+    world_ui_query: Query<(Entity, &WorldUi), With<Node>>,
+    camera_query: Query<&Camera>,
     node_query: Query<(Entity, &Style, Option<&CalculatedSize>), (With<Node>, Changed<Style>)>,
     full_node_query: Query<(Entity, &Style, Option<&CalculatedSize>), With<Node>>,
     changed_size_query: Query<
         (Entity, &Style, &CalculatedSize),
         (With<Node>, Changed<CalculatedSize>),
     >,
-    children_query: Query<(Entity, &Children), (With<Node>, Changed<Children>)>,
+    children_query: Query<
+        (Entity, &Children),
+        (With<Node>, Or<(Changed<Children>, Changed<Style>)>),
+    >,
     mut node_transform_query: Query<(Entity, &mut Node, &mut Transform, Option<&Parent>)>,
+    mut scroll_query: Query<(Entity, &Style, &mut ScrollPosition, Option<&Children>), With<Node>>,
 ) {
     // update window root nodes
     for window in windows.iter() {
         flex_surface.update_window(window);
     }
 
-    // assume one window for time being...
-    let logical_to_physical_factor = windows.scale_factor(WindowId::primary());
+    // each node is laid out in the scale factor of the window displaying it
+    let logical_to_physical_factor = |entity| ui_window_nodes.scale_factor(&windows, entity);
 
-    if scale_factor_events.iter().next_back().is_some() {
+    // nodes moved to another window are laid out again in its scale factor
+    if scale_factor_events.iter().next_back().is_some() || ui_window_nodes.is_changed() {
         update_changed(
             &mut *flex_surface,
             logical_to_physical_factor,
@@ -227,31 +644,54 @@ pub fn flex_node_system(
 
     fn update_changed<F: WorldQuery>(
         flex_surface: &mut FlexSurface,
-        scaling_factor: f64,
+        scaling_factor: impl Fn(Entity) -> f64,
         query: Query<(Entity, &Style, Option<&CalculatedSize>), F>,
     ) {
         // update changed nodes
         for (entity, style, calculated_size) in query.iter() {
             // TODO: remove node from old hierarchy if its root has changed
             if let Some(calculated_size) = calculated_size {
-                flex_surface.upsert_leaf(entity, style, *calculated_size, scaling_factor);
+                flex_surface.upsert_leaf(entity, style, *calculated_size, scaling_factor(entity));
             } else {
-                flex_surface.upsert_node(entity, style, scaling_factor);
+                flex_surface.upsert_node(entity, style, scaling_factor(entity));
             }
         }
     }
 
     for (entity, style, calculated_size) in changed_size_query.iter() {
-        flex_surface.upsert_leaf(entity, style, *calculated_size, logical_to_physical_factor);
+        flex_surface.upsert_leaf(
+            entity,
+            style,
+            *calculated_size,
+            logical_to_physical_factor(entity),
+        );
     }
 
     // TODO: handle removed nodes
 
-    // update window children (for now assuming all Nodes live in the primary window)
-    if let Some(primary_window) = windows.get_primary() {
-        flex_surface.set_window_children(primary_window.id(), root_node_query.iter());
+    // update the children of each window with the root nodes it displays
+    let mut window_roots: HashMap<WindowId, Vec<Entity>> = windows
+        .iter()
+        .map(|window| (window.id(), Vec::new()))
+        .collect();
+    for root in root_node_query.iter() {
+        if let Some(roots) = window_roots.get_mut(&ui_window_nodes.window(root)) {
+            roots.push(root);
+        }
+    }
+    for (window_id, roots) in window_roots {
+        flex_surface.set_window_children(window_id, roots.into_iter());
     }
 
+    // update the roots rendered to images, laid out in the size of the images
+    flex_surface.set_world_ui_roots(world_ui_query.iter().filter_map(|(entity, world_ui)| {
+        let size = camera_query
+            .get(world_ui.camera)
+            .ok()?
+            .logical_viewport_size()?;
+        Some((entity, size * logical_to_physical_factor(entity) as f32))
+    }));
+
     // update children
     for (entity, children) in children_query.iter() {
         flex_surface.update_children(entity, children);
@@ -260,13 +700,53 @@ pub fn flex_node_system(
     // compute layouts
     flex_surface.compute_window_layouts();
 
-    let physical_to_logical_factor = 1. / logical_to_physical_factor;
+    let physical_to_logical = |entity| {
+        let physical_to_logical_factor = 1. / logical_to_physical_factor(entity);
+        move |v: f32| (physical_to_logical_factor * v as f64) as f32
+    };
 
-    let to_logical = |v| (physical_to_logical_factor * v as f64) as f32;
+    // clamp the scroll positions to the laid out content
+    let mut scroll_offsets = HashMap::default();
+    for (entity, style, mut scroll_position, children) in scroll_query.iter_mut() {
+        if style.overflow != Overflow::Scroll {
+            continue;
+        }
+        let to_logical = physical_to_logical(entity);
+        let layout = flex_surface.get_layout(entity).unwrap();
+        let node_size = Vec2::new(
+            to_logical(layout.size.width),
+            to_logical(layout.size.height),
+        );
+        let mut content_min = Vec2::ZERO;
+        let mut content_max = Vec2::ZERO;
+        for child in children.iter().flat_map(|children| children.iter()) {
+            if let (Ok(child_layout), Ok(location)) = (
+                flex_surface.get_layout(*child),
+                flex_surface.get_location(*child),
+            ) {
+                let min = Vec2::new(to_logical(location.x), to_logical(location.y));
+                let size = Vec2::new(
+                    to_logical(child_layout.size.width),
+                    to_logical(child_layout.size.height),
+                );
+                content_min = content_min.min(min);
+                content_max = content_max.max(min + size);
+            }
+        }
+        let offset =
+            clamp_scroll_offset(scroll_position.offset, node_size, content_min, content_max);
+        // only trigger change detection when the new value is different
+        if scroll_position.offset != offset {
+            scroll_position.offset = offset;
+        }
+        scroll_offsets.insert(entity, offset);
+    }
 
     // PERF: try doing this incrementally
     for (entity, mut node, mut transform, parent) in node_transform_query.iter_mut() {
+        let to_logical = physical_to_logical(entity);
         let layout = flex_surface.get_layout(entity).unwrap();
+        let location = flex_surface.get_location(entity).unwrap();
         let new_size = Vec2::new(
             to_logical(layout.size.width),
             to_logical(layout.size.height),
@@ -276,13 +756,17 @@ pub fn flex_node_system(
             node.size = new_size;
         }
         let mut new_position = transform.translation;
-        new_position.x = to_logical(layout.location.x + layout.size.width / 2.0);
-        new_position.y = to_logical(layout.location.y + layout.size.height / 2.0);
+        new_position.x = to_logical(location.x + layout.size.width / 2.0);
+        new_position.y = to_logical(location.y + layout.size.height / 2.0);
         if let Some(parent) = parent {
             if let Ok(parent_layout) = flex_surface.get_layout(parent.0) {
                 new_position.x -= to_logical(parent_layout.size.width / 2.0);
                 new_position.y -= to_logical(parent_layout.size.height / 2.0);
             }
+            if let Some(offset) = scroll_offsets.get(&parent.0) {
+                new_position.x -= offset.x;
+                new_position.y += offset.y;
+            }
         }
         // only trigger change detection when the new value is different
         if transform.translation != new_position {
@@ -290,3 +774,37 @@ pub fn flex_node_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_content_is_inset_by_padding_and_border() {
+        let style = Style {
+            padding: UiRect {
+                left: Val::Px(1.0),
+                right: Val::Px(2.0),
+                top: Val::Px(3.0),
+                bottom: Val::Percent(2.0),
+            },
+            border: UiRect {
+                left: Val::Px(10.0),
+                right: Val::Px(20.0),
+                top: Val::Percent(10.0),
+                bottom: Val::Px(40.0),
+            },
+            ..Default::default()
+        };
+        let (offset, size) = grid_content_box(&style, 2.0, Vec2::new(200.0, 300.0));
+        // Taffy already offsets the cells by the left and bottom borders
+        assert_eq!(offset, Vec2::new(2.0, 4.0));
+        assert_eq!(
+            size,
+            Vec2::new(
+                200.0 - 2.0 - 4.0 - 20.0 - 40.0,
+                300.0 - 6.0 - 4.0 - 20.0 - 80.0
+            )
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{CalculatedClip, Node};
+use crate::{CalculatedClip, Node, UiWindowNodes, WorldUiNodes};
 use bevy_ecs::{
     entity::Entity,
     prelude::Component,
@@ -10,7 +10,7 @@ use bevy_math::Vec2;
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::FloatOrd;
-use bevy_window::Windows;
+use bevy_window::{WindowId, Windows};
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 
@@ -55,6 +55,8 @@ pub fn ui_focus_system(
     windows: Res<Windows>,
     mouse_button_input: Res<Input<MouseButton>>,
     touches_input: Res<Touches>,
+    world_ui_nodes: Res<WorldUiNodes>,
+    ui_window_nodes: Res<UiWindowNodes>,
     mut node_query: Query<(
         Entity,
         &Node,
@@ -88,7 +90,8 @@ pub fn ui_focus_system(
     let mouse_clicked =
         mouse_button_input.just_pressed(MouseButton::Left) || touches_input.any_just_pressed();
 
-    let cursor_position = windows
+    // Touches only interact with the UI of the primary window
+    let primary_cursor_position = windows
         .get_primary()
         .and_then(|window| window.cursor_position())
         .or_else(|| touches_input.first_pressed_position());
@@ -97,6 +100,16 @@ pub fn ui_focus_system(
         .iter_mut()
         .filter_map(
             |(entity, node, global_transform, interaction, focus_policy, clip)| {
+                // The nodes rendered to a surface in the world are pointed at through the surface
+                let world_ui_root = world_ui_nodes.root(entity);
+                let window_id = ui_window_nodes.window(entity);
+                let cursor_position = match world_ui_root {
+                    Some(_) => world_ui_nodes.cursor_position(entity),
+                    None if window_id == WindowId::primary() => primary_cursor_position,
+                    None => windows
+                        .get(window_id)
+                        .and_then(|window| window.cursor_position()),
+                };
                 let position = global_transform.translation;
                 let ui_position = position.truncate();
                 let extents = node.size / 2.0;
@@ -116,7 +129,13 @@ pub fn ui_focus_system(
                 };
 
                 if contains_cursor {
-                    Some((entity, focus_policy, interaction, FloatOrd(position.z)))
+                    Some((
+                        entity,
+                        focus_policy,
+                        interaction,
+                        FloatOrd(position.z),
+                        (window_id, world_ui_root),
+                    ))
                 } else {
                     if let Some(mut interaction) = interaction {
                         if *interaction == Interaction::Hovered
@@ -131,11 +150,23 @@ pub fn ui_focus_system(
         )
         .collect::<Vec<_>>();
 
-    moused_over_z_sorted_nodes.sort_by_key(|(_, _, _, z)| -*z);
+    moused_over_z_sorted_nodes.sort_by_key(|(_, _, _, z, _)| -*z);
 
-    let mut moused_over_z_sorted_nodes = moused_over_z_sorted_nodes.into_iter();
-    // set Clicked or Hovered on top nodes
-    for (entity, focus_policy, interaction, _) in moused_over_z_sorted_nodes.by_ref() {
+    // The UI of each window and of each world UI block the interactions independently
+    let mut blocked_uis: SmallVec<[(WindowId, Option<Entity>); 1]> = SmallVec::new();
+    for (entity, focus_policy, interaction, _, ui) in moused_over_z_sorted_nodes {
+        if blocked_uis.contains(&ui) {
+            // reset lower nodes to None
+            if let Some(mut interaction) = interaction {
+                // don't reset clicked nodes because they're handled separately
+                if *interaction != Interaction::Clicked && *interaction != Interaction::None {
+                    *interaction = Interaction::None;
+                }
+            }
+            continue;
+        }
+
+        // set Clicked or Hovered on top nodes
         if let Some(mut interaction) = interaction {
             if mouse_clicked {
                 // only consider nodes with Interaction "clickable"
@@ -154,18 +185,9 @@ pub fn ui_focus_system(
 
         match focus_policy.cloned().unwrap_or(FocusPolicy::Block) {
             FocusPolicy::Block => {
-                break;
+                blocked_uis.push(ui);
             }
             FocusPolicy::Pass => { /* allow the next node to be hovered/clicked */ }
         }
     }
-    // reset lower nodes to None
-    for (_entity, _focus_policy, interaction, _) in moused_over_z_sorted_nodes {
-        if let Some(mut interaction) = interaction {
-            // don't reset clicked nodes because they're handled separately
-            if *interaction != Interaction::Clicked && *interaction != Interaction::None {
-                *interaction = Interaction::None;
-            }
-        }
-    }
 }
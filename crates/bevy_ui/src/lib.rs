@@ -2,33 +2,50 @@
 //! # Basic usage
 //! Spawn UI elements with [`entity::ButtonBundle`], [`entity::ImageBundle`], [`entity::TextBundle`] and [`entity::NodeBundle`]
 //! This UI is laid out with the Flexbox paradigm (see <https://cssreference.io/flexbox/> ) except the vertical axis is inverted
+mod diagnostics_overlay;
 mod flex;
 mod focus;
 mod geometry;
+mod navigation;
 mod render;
+mod scroll;
 mod ui_node;
+mod ui_window;
+mod world_ui;
 
 pub mod entity;
 pub mod update;
 pub mod widget;
 
 use bevy_render::extract_component::ExtractComponentPlugin;
+pub use diagnostics_overlay::*;
 pub use flex::*;
 pub use focus::*;
 pub use geometry::*;
+pub use navigation::*;
 pub use render::*;
+pub use scroll::*;
 pub use ui_node::*;
+pub use ui_window::*;
+pub use world_ui::*;
 
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{entity::*, geometry::*, ui_node::*, widget::Button, Interaction};
+    pub use crate::{
+        entity::*,
+        geometry::*,
+        ui_node::*,
+        widget::{Button, TextInput},
+        Focus, Focusable, Interaction, UiWindow, WorldUi,
+    };
 }
 
 use crate::Size;
 use bevy_app::prelude::*;
 use bevy_ecs::schedule::{ParallelSystemDescriptorCoercion, SystemLabel};
 use bevy_input::InputSystem;
+use bevy_text::TextLayoutSystem;
 use bevy_transform::TransformSystem;
 use bevy_window::ModifiesWindows;
 use update::{ui_z_system, update_clipping_system};
@@ -42,10 +59,17 @@ pub struct UiPlugin;
 /// The label enum labeling the types of systems in the Bevy UI
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum UiSystem {
+    /// After this label, the window displaying each UI node has been found, see [`UiWindow`]
+    Windows,
     /// After this label, the ui flex state has been updated
     Flex,
     /// After this label, input interactions with UI entities have been updated for this frame
     Focus,
+    /// After this label, the [`Focus`] has been moved by keyboard and gamepad input for this frame
+    Navigation,
+    /// After this label, the cursor has been cast onto the surfaces of the [`WorldUi`] nodes for
+    /// this frame
+    WorldUiCursor,
 }
 
 impl Plugin for UiPlugin {
@@ -55,12 +79,18 @@ impl Plugin for UiPlugin {
             .register_type::<AlignContent>()
             .register_type::<AlignItems>()
             .register_type::<AlignSelf>()
+            .register_type::<BorderColor>()
+            .register_type::<BorderRadius>()
             .register_type::<CalculatedSize>()
             .register_type::<Direction>()
             .register_type::<Display>()
             .register_type::<FlexDirection>()
             .register_type::<FlexWrap>()
+            .register_type::<Focusable>()
             .register_type::<FocusPolicy>()
+            .register_type::<GridPlacement>()
+            .register_type::<GridTrack>()
+            .register_type::<Vec<GridTrack>>()
             .register_type::<Interaction>()
             .register_type::<JustifyContent>()
             .register_type::<Node>()
@@ -68,6 +98,7 @@ impl Plugin for UiPlugin {
             .register_type::<Option<f32>>()
             .register_type::<Overflow>()
             .register_type::<PositionType>()
+            .register_type::<ScrollPosition>()
             .register_type::<Size<f32>>()
             .register_type::<Size<Val>>()
             .register_type::<UiRect<Val>>()
@@ -75,17 +106,59 @@ impl Plugin for UiPlugin {
             .register_type::<UiColor>()
             .register_type::<UiImage>()
             .register_type::<Val>()
+            .register_type::<ZIndex>()
+            .register_type::<WorldUi>()
             .register_type::<widget::Button>()
+            .register_type::<widget::BorderRect>()
             .register_type::<widget::ImageMode>()
+            .register_type::<widget::TextOverflow>()
+            .register_type::<widget::TextInput>()
+            // NOTE: used by TextInput::selection_anchor
+            .register_type::<Option<usize>>()
+            .init_resource::<Focus>()
+            .init_resource::<WorldUiNodes>()
+            .init_resource::<UiWindowNodes>()
+            .add_event::<FocusEnter>()
+            .add_event::<FocusExit>()
+            .init_resource::<widget::Clipboard>()
+            .add_event::<widget::TextInputSubmitted>()
             .add_system_to_stage(
                 CoreStage::PreUpdate,
-                ui_focus_system.label(UiSystem::Focus).after(InputSystem),
+                world_ui_cursor_system
+                    .label(UiSystem::WorldUiCursor)
+                    .after(InputSystem),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                ui_focus_system
+                    .label(UiSystem::Focus)
+                    .after(UiSystem::WorldUiCursor),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                ui_navigation_system
+                    .label(UiSystem::Navigation)
+                    .after(UiSystem::Focus),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                widget::text_input_system.after(UiSystem::Navigation),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                ui_scroll_system.after(UiSystem::Focus),
             )
             // add these stages to front because these must run before transform update systems
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_ui_window_nodes.label(UiSystem::Windows),
+            )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 widget::text_system
+                    .label(TextLayoutSystem)
                     .before(UiSystem::Flex)
+                    .after(UiSystem::Windows)
                     .after(ModifiesWindows),
             )
             .add_system_to_stage(
@@ -96,9 +169,14 @@ impl Plugin for UiPlugin {
                 CoreStage::PostUpdate,
                 flex_node_system
                     .label(UiSystem::Flex)
+                    .after(UiSystem::Windows)
                     .before(TransformSystem::TransformPropagate)
                     .after(ModifiesWindows),
             )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_world_ui_nodes.after(UiSystem::Flex),
+            )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 ui_z_system
@@ -0,0 +1,421 @@
+use crate::{widget::TextInput, Interaction, Node};
+use bevy_ecs::{
+    entity::Entity,
+    event::EventWriter,
+    prelude::Component,
+    query::{Changed, With},
+    reflect::ReflectComponent,
+    system::{Local, ParamSet, Query, Res, ResMut},
+};
+use bevy_input::{
+    gamepad::{GamepadButton, GamepadButtonType, Gamepads},
+    keyboard::KeyCode,
+    Input,
+};
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::view::Visibility;
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::FloatOrd;
+
+/// Marks a UI node that can receive the [`Focus`], by clicking on it or by navigating to it with
+/// the keyboard or a gamepad.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component, Default)]
+pub struct Focusable;
+
+/// The [`Focusable`] node that has the focus, if any.
+///
+/// The focus is moved between nodes with the arrow keys or the D-pad of a gamepad, in the
+/// direction of the key, and with tab and shift+tab, from the top left to the bottom right.
+/// Enter, space and the south button of a gamepad click the focused node, setting its
+/// [`Interaction`] to [`Interaction::Clicked`] until they are released.
+///
+/// A focused [`TextInput`] receives the keyboard input instead, including the arrow keys.
+///
+/// [`FocusExit`] and [`FocusEnter`] events are sent whenever the focus changes, including when
+/// it is changed through this resource.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Focus {
+    entity: Option<Entity>,
+}
+
+impl Focus {
+    /// Returns the focused entity, if any.
+    pub fn get(&self) -> Option<Entity> {
+        self.entity
+    }
+
+    /// Moves the focus to `entity`.
+    pub fn set(&mut self, entity: Entity) {
+        self.entity = Some(entity);
+    }
+
+    /// Removes the focus from the focused entity.
+    pub fn clear(&mut self) {
+        self.entity = None;
+    }
+}
+
+/// Sent when a node receives the [`Focus`].
+#[derive(Debug, Clone, Copy)]
+pub struct FocusEnter {
+    pub entity: Entity,
+}
+
+/// Sent when a node loses the [`Focus`].
+#[derive(Debug, Clone, Copy)]
+pub struct FocusExit {
+    pub entity: Entity,
+}
+
+/// The entity that had the focus last frame, and the entity clicked through the focus.
+#[derive(Default)]
+pub struct NavigationState {
+    focused: Option<Entity>,
+    clicked: Option<Entity>,
+}
+
+/// Moves the [`Focus`] with keyboard and gamepad input, clicks the focused node, and sends the
+/// focus events.
+#[allow(clippy::too_many_arguments)]
+pub fn ui_navigation_system(
+    mut state: Local<NavigationState>,
+    mut focus: ResMut<Focus>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    mut focus_enter_events: EventWriter<FocusEnter>,
+    mut focus_exit_events: EventWriter<FocusExit>,
+    focusable_query: Query<(Entity, &Node, &GlobalTransform, &Visibility), With<Focusable>>,
+    mut interaction_queries: ParamSet<(
+        Query<(Entity, &Interaction), (Changed<Interaction>, With<Focusable>)>,
+        Query<&mut Interaction, With<Focusable>>,
+    )>,
+    text_input_query: Query<(), With<TextInput>>,
+) {
+    let gamepad_pressed = |button_type| {
+        gamepads
+            .iter()
+            .any(|gamepad| gamepad_input.just_pressed(GamepadButton::new(*gamepad, button_type)))
+    };
+    let gamepad_released = |button_type| {
+        gamepads
+            .iter()
+            .any(|gamepad| gamepad_input.just_released(GamepadButton::new(*gamepad, button_type)))
+    };
+
+    // Clicking on a node with the mouse focuses it
+    for (entity, interaction) in interaction_queries.p0().iter() {
+        if *interaction == Interaction::Clicked && state.clicked != Some(entity) {
+            focus.set(entity);
+        }
+    }
+
+    // The focused node may have been despawned or made unfocusable
+    if let Some(entity) = focus.get() {
+        if !focusable_query.contains(entity) {
+            focus.clear();
+        }
+    }
+
+    // Arrow keys move the caret of a text input rather than the focus
+    let editing_text = focus
+        .get()
+        .map_or(false, |entity| text_input_query.contains(entity));
+
+    let direction = if !editing_text && keyboard_input.just_pressed(KeyCode::Up)
+        || gamepad_pressed(GamepadButtonType::DPadUp)
+    {
+        Some(Vec2::Y)
+    } else if !editing_text && keyboard_input.just_pressed(KeyCode::Down)
+        || gamepad_pressed(GamepadButtonType::DPadDown)
+    {
+        Some(-Vec2::Y)
+    } else if !editing_text && keyboard_input.just_pressed(KeyCode::Left)
+        || gamepad_pressed(GamepadButtonType::DPadLeft)
+    {
+        Some(-Vec2::X)
+    } else if !editing_text && keyboard_input.just_pressed(KeyCode::Right)
+        || gamepad_pressed(GamepadButtonType::DPadRight)
+    {
+        Some(Vec2::X)
+    } else {
+        None
+    };
+    let tab = keyboard_input.just_pressed(KeyCode::Tab);
+
+    if direction.is_some() || tab {
+        // The visible focusable nodes, from the top left to the bottom right
+        let mut nodes: Vec<(Entity, Vec2)> = focusable_query
+            .iter()
+            .filter(|(_, node, _, visibility)| visibility.is_visible && node.size != Vec2::ZERO)
+            .map(|(entity, _, transform, _)| (entity, transform.translation.truncate()))
+            .collect();
+        nodes.sort_by_key(|(_, position)| (FloatOrd(-position.y), FloatOrd(position.x)));
+
+        let current = focus
+            .get()
+            .and_then(|entity| nodes.iter().position(|(node, _)| *node == entity));
+        let next = match (current, direction) {
+            // Start from the first node when nothing is focused
+            (None, _) => nodes.first().map(|(entity, _)| *entity),
+            (Some(current), Some(direction)) => {
+                let origin = nodes[current].1;
+                nodes
+                    .iter()
+                    .filter_map(|(entity, position)| {
+                        let offset = *position - origin;
+                        let distance = offset.dot(direction);
+                        if distance <= 0.0 {
+                            return None;
+                        }
+                        // Prefer the nodes that are the most aligned with the direction
+                        let misalignment = (offset - direction * distance).length();
+                        Some((*entity, distance + 2.0 * misalignment))
+                    })
+                    .min_by_key(|(_, score)| FloatOrd(*score))
+                    .map(|(entity, _)| entity)
+            }
+            (Some(current), None) => {
+                let shift = keyboard_input.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+                let next = if shift {
+                    (current + nodes.len() - 1) % nodes.len()
+                } else {
+                    (current + 1) % nodes.len()
+                };
+                Some(nodes[next].0)
+            }
+        };
+        if let Some(next) = next {
+            focus.set(next);
+        }
+    }
+
+    // Clicking the focused node, except for text inputs which handle enter themselves
+    let click_pressed = keyboard_input.any_just_pressed([KeyCode::Return, KeyCode::Space])
+        || gamepad_pressed(GamepadButtonType::South);
+    let click_released = keyboard_input.any_just_released([KeyCode::Return, KeyCode::Space])
+        || gamepad_released(GamepadButtonType::South);
+    if let Some(entity) = state.clicked {
+        if click_released || focus.get() != Some(entity) {
+            if let Ok(mut interaction) = interaction_queries.p1().get_mut(entity) {
+                if *interaction == Interaction::Clicked {
+                    *interaction = Interaction::None;
+                }
+            }
+            state.clicked = None;
+        }
+    }
+    if click_pressed && !editing_text {
+        if let Some(entity) = focus.get() {
+            if let Ok(mut interaction) = interaction_queries.p1().get_mut(entity) {
+                *interaction = Interaction::Clicked;
+                state.clicked = Some(entity);
+            }
+        }
+    }
+
+    if state.focused != focus.get() {
+        if let Some(entity) = state.focused {
+            focus_exit_events.send(FocusExit { entity });
+        }
+        if let Some(entity) = focus.get() {
+            focus_enter_events.send(FocusEnter { entity });
+        }
+        state.focused = focus.get();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+    use bevy_ecs::event::Events;
+
+    fn navigation_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Focus>()
+            .init_resource::<Input<KeyCode>>()
+            .init_resource::<Input<GamepadButton>>()
+            .init_resource::<Gamepads>()
+            .add_event::<FocusEnter>()
+            .add_event::<FocusExit>()
+            .add_system(ui_navigation_system);
+        app
+    }
+
+    fn spawn_node(app: &mut App, x: f32, y: f32) -> Entity {
+        app.world
+            .spawn()
+            .insert_bundle((
+                Focusable,
+                Node {
+                    size: Vec2::splat(10.0),
+                },
+                GlobalTransform::from_xyz(x, y, 0.0),
+                Visibility::default(),
+                Interaction::default(),
+            ))
+            .id()
+    }
+
+    /// Presses `keys` for a single update, releasing the keys pressed before.
+    fn press(app: &mut App, keys: &[KeyCode]) {
+        let mut input = app.world.resource_mut::<Input<KeyCode>>();
+        input.release_all();
+        input.clear();
+        for key in keys {
+            input.press(*key);
+        }
+        app.update();
+    }
+
+    fn release_all(app: &mut App) {
+        let mut input = app.world.resource_mut::<Input<KeyCode>>();
+        input.clear();
+        input.release_all();
+        app.update();
+    }
+
+    fn focused(app: &App) -> Option<Entity> {
+        app.world.resource::<Focus>().get()
+    }
+
+    /// The entities that lost and received the focus during the last update.
+    fn focus_events(app: &App) -> (Vec<Entity>, Vec<Entity>) {
+        let exits = app.world.resource::<Events<FocusExit>>();
+        let enters = app.world.resource::<Events<FocusEnter>>();
+        (
+            exits
+                .iter_current_update_events()
+                .map(|e| e.entity)
+                .collect(),
+            enters
+                .iter_current_update_events()
+                .map(|e| e.entity)
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn tab_moves_the_focus_in_reading_order() {
+        let mut app = navigation_app();
+        let bottom_left = spawn_node(&mut app, 0.0, 0.0);
+        let top_right = spawn_node(&mut app, 100.0, 100.0);
+        let top_left = spawn_node(&mut app, 0.0, 100.0);
+        app.update();
+        assert_eq!(focused(&app), None);
+
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(focused(&app), Some(top_left));
+        assert_eq!(focus_events(&app), (vec![], vec![top_left]));
+
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(focused(&app), Some(top_right));
+        assert_eq!(focus_events(&app), (vec![top_left], vec![top_right]));
+
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(focused(&app), Some(bottom_left));
+        // The focus wraps around, and shift goes backwards
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(focused(&app), Some(top_left));
+        press(&mut app, &[KeyCode::LShift, KeyCode::Tab]);
+        assert_eq!(focused(&app), Some(bottom_left));
+
+        // Nothing happens without a change
+        release_all(&mut app);
+        assert_eq!(focus_events(&app), (vec![], vec![]));
+    }
+
+    #[test]
+    fn arrow_keys_move_the_focus_in_their_direction() {
+        let mut app = navigation_app();
+        let top_left = spawn_node(&mut app, 0.0, 100.0);
+        let top_right = spawn_node(&mut app, 100.0, 100.0);
+        let bottom_left = spawn_node(&mut app, 0.0, 0.0);
+        let bottom_right = spawn_node(&mut app, 100.0, 10.0);
+        app.world.resource_mut::<Focus>().set(top_left);
+
+        press(&mut app, &[KeyCode::Right]);
+        assert_eq!(focused(&app), Some(top_right));
+        press(&mut app, &[KeyCode::Down]);
+        assert_eq!(focused(&app), Some(bottom_right));
+        press(&mut app, &[KeyCode::Left]);
+        assert_eq!(focused(&app), Some(bottom_left));
+        press(&mut app, &[KeyCode::Up]);
+        assert_eq!(focused(&app), Some(top_left));
+        // There is no node further up
+        press(&mut app, &[KeyCode::Up]);
+        assert_eq!(focused(&app), Some(top_left));
+
+        // Hidden nodes are skipped
+        app.world
+            .get_mut::<Visibility>(top_right)
+            .unwrap()
+            .is_visible = false;
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(focused(&app), Some(bottom_right));
+    }
+
+    #[test]
+    fn enter_clicks_the_focused_node() {
+        let mut app = navigation_app();
+        let node = spawn_node(&mut app, 0.0, 0.0);
+        app.world.resource_mut::<Focus>().set(node);
+
+        press(&mut app, &[KeyCode::Return]);
+        assert_eq!(
+            app.world.get::<Interaction>(node),
+            Some(&Interaction::Clicked)
+        );
+        // Holding the key keeps the node clicked
+        app.world.resource_mut::<Input<KeyCode>>().clear();
+        app.update();
+        assert_eq!(
+            app.world.get::<Interaction>(node),
+            Some(&Interaction::Clicked)
+        );
+
+        release_all(&mut app);
+        assert_eq!(app.world.get::<Interaction>(node), Some(&Interaction::None));
+    }
+
+    #[test]
+    fn focused_text_inputs_keep_the_keyboard_input() {
+        let mut app = navigation_app();
+        let input = spawn_node(&mut app, 0.0, 100.0);
+        let other = spawn_node(&mut app, 100.0, 100.0);
+        app.world.entity_mut(input).insert(TextInput::default());
+        app.world.resource_mut::<Focus>().set(input);
+
+        press(&mut app, &[KeyCode::Right]);
+        assert_eq!(focused(&app), Some(input));
+        press(&mut app, &[KeyCode::Return]);
+        assert_eq!(
+            app.world.get::<Interaction>(input),
+            Some(&Interaction::None)
+        );
+
+        // Tab still moves the focus out of the field
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(focused(&app), Some(other));
+    }
+
+    #[test]
+    fn clicked_nodes_receive_the_focus_until_removed() {
+        let mut app = navigation_app();
+        let node = spawn_node(&mut app, 0.0, 0.0);
+        app.update();
+
+        *app.world.get_mut::<Interaction>(node).unwrap() = Interaction::Clicked;
+        app.update();
+        assert_eq!(focused(&app), Some(node));
+        assert_eq!(focus_events(&app), (vec![], vec![node]));
+
+        app.world.despawn(node);
+        app.update();
+        assert_eq!(focused(&app), None);
+        assert_eq!(focus_events(&app), (vec![node], vec![]));
+    }
+}
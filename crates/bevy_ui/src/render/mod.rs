@@ -5,29 +5,38 @@ use bevy_core_pipeline::{core_2d::Camera2d, core_3d::Camera3d};
 pub use pipeline::*;
 pub use render_pass::*;
 
-use crate::{prelude::UiCameraConfig, CalculatedClip, Node, UiColor, UiImage};
+use crate::{
+    prelude::UiCameraConfig,
+    widget::{BorderRect, ImageMode, TextInput},
+    BorderColor, BorderRadius, CalculatedClip, Focus, Node, Style, UiColor, UiImage, UiRect,
+    UiWindowNodes, Val, WorldUiNodes,
+};
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, AssetEvent, Assets, Handle, HandleUntyped};
 use bevy_ecs::prelude::*;
+use bevy_hierarchy::Parent;
 use bevy_math::{Mat4, Vec2, Vec3, Vec4Swizzles};
 use bevy_reflect::TypeUuid;
 use bevy_render::{
-    camera::{Camera, CameraProjection, DepthCalculation, OrthographicProjection, WindowOrigin},
+    camera::{
+        Camera, CameraProjection, DepthCalculation, ExtractedCamera, OrthographicProjection,
+        RenderTarget, WindowOrigin,
+    },
     color::Color,
     render_asset::RenderAssets,
     render_graph::{RenderGraph, RunGraphOnViewNode, SlotInfo, SlotType},
     render_phase::{sort_phase_system, AddRenderCommand, DrawFunctions, RenderPhase},
     render_resource::*,
     renderer::{RenderDevice, RenderQueue},
-    texture::Image,
+    texture::{Image, DEFAULT_IMAGE_HANDLE},
     view::{ExtractedView, ViewUniforms, Visibility},
     Extract, RenderApp, RenderStage,
 };
 use bevy_sprite::{Rect, SpriteAssetEvents, TextureAtlas};
-use bevy_text::{DefaultTextPipeline, Text};
+use bevy_text::{DefaultTextPipeline, Text, TextGlyphModifiers, TextLayoutInfo, TextReveal};
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::FloatOrd;
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, HashSet};
 use bevy_window::{WindowId, Windows};
 use bytemuck::{Pod, Zeroable};
 use std::ops::Range;
@@ -86,6 +95,10 @@ pub fn build_ui_render(app: &mut App) {
             RenderStage::Extract,
             extract_text_uinodes.after(RenderUiSystem::ExtractNode),
         )
+        .add_system_to_stage(
+            RenderStage::Extract,
+            extract_text_input_carets.after(extract_text_uinodes),
+        )
         .add_system_to_stage(RenderStage::Prepare, prepare_uinodes)
         .add_system_to_stage(RenderStage::Queue, queue_uinodes)
         .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<TransparentUi>);
@@ -123,9 +136,10 @@ pub fn build_ui_render(app: &mut App) {
             draw_ui_graph::node::UI_PASS,
             RunGraphOnViewNode::new(draw_ui_graph::NAME),
         );
+        // NOTE: The ui is drawn over the tonemapped image of hdr cameras
         graph_3d
             .add_node_edge(
-                bevy_core_pipeline::core_3d::graph::node::MAIN_PASS,
+                bevy_core_pipeline::core_3d::graph::node::TONEMAPPING,
                 draw_ui_graph::node::UI_PASS,
             )
             .unwrap();
@@ -166,51 +180,221 @@ pub struct ExtractedUiNode {
     pub image: Handle<Image>,
     pub atlas_size: Option<Vec2>,
     pub clip: Option<Rect>,
+    /// The rounded corners of `clip`
+    pub clip_radius: BorderRadius,
+    /// The rounded corners of the node, in the space of `rect`
+    pub border_radius: BorderRadius,
+    /// If set, only a border with these widths is drawn, in the space of `rect`, with the color
+    /// of the node and ignoring its image
+    pub border: Option<UiRect<f32>>,
+    /// The camera rendering the node if it is in a [`WorldUi`](crate::WorldUi), otherwise the
+    /// node is rendered by the cameras of its window
+    pub camera: Option<Entity>,
+    /// The window displaying the node, see [`UiWindow`](crate::UiWindow)
+    pub window: WindowId,
 }
 
 #[derive(Default)]
 pub struct ExtractedUiNodes {
     pub uinodes: Vec<ExtractedUiNode>,
+    /// The cameras rendering a [`WorldUi`](crate::WorldUi) rather than the UI of the windows
+    pub world_ui_cameras: HashSet<Entity>,
 }
 
 pub fn extract_uinodes(
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
     images: Extract<Res<Assets<Image>>>,
+    windows: Extract<Res<Windows>>,
+    world_ui_nodes: Extract<Res<WorldUiNodes>>,
+    ui_window_nodes: Extract<Res<UiWindowNodes>>,
     uinode_query: Extract<
         Query<(
+            Entity,
             &Node,
             &GlobalTransform,
             &UiColor,
             &UiImage,
             &Visibility,
+            &Style,
             Option<&CalculatedClip>,
+            Option<&ImageMode>,
+            Option<&BorderColor>,
+            Option<&BorderRadius>,
+            Option<&Parent>,
         )>,
     >,
+    parent_query: Extract<Query<&Node>>,
 ) {
     extracted_uinodes.uinodes.clear();
-    for (uinode, transform, color, image, visibility, clip) in uinode_query.iter() {
+    extracted_uinodes.world_ui_cameras.clear();
+    extracted_uinodes
+        .world_ui_cameras
+        .extend(world_ui_nodes.world_ui_cameras());
+    for (
+        entity,
+        uinode,
+        transform,
+        color,
+        image,
+        visibility,
+        style,
+        clip,
+        image_mode,
+        border_color,
+        border_radius,
+        parent,
+    ) in uinode_query.iter()
+    {
         if !visibility.is_visible {
             continue;
         }
+        let camera = world_ui_nodes.camera(entity);
+        let window = ui_window_nodes.window(entity);
         let image = image.0.clone_weak();
         // Skip loading images
-        if !images.contains(&image) {
+        let image_size = match images.get(&image) {
+            Some(image) => image.size(),
+            None => continue,
+        };
+        if let Some(ImageMode::Sliced(border)) = image_mode {
+            let transform = transform.compute_matrix();
+            for (rect, slice) in compute_slices(uinode.size, image_size, border) {
+                // Each slice is drawn with the size of its part of the image, scaled to fill its
+                // part of the node
+                let scale = slice.size() / rect.size();
+                let center = slice.min + slice.size() / 2.0;
+                extracted_uinodes.uinodes.push(ExtractedUiNode {
+                    transform: transform
+                        * Mat4::from_translation(center.extend(0.0))
+                        * Mat4::from_scale(scale.extend(1.0)),
+                    color: color.0,
+                    rect,
+                    image: image.clone_weak(),
+                    atlas_size: Some(image_size),
+                    clip: clip.map(|clip| clip.clip),
+                    clip_radius: clip.map_or(BorderRadius::ZERO, |clip| clip.radius),
+                    border_radius: BorderRadius::ZERO,
+                    border: None,
+                    camera,
+                    window,
+                });
+            }
             continue;
         }
+        let transform = transform.compute_matrix();
+        let rect = bevy_sprite::Rect {
+            min: Vec2::ZERO,
+            max: uinode.size,
+        };
+        let border_radius =
+            border_radius.map_or(BorderRadius::ZERO, |radius| radius.clamped(uinode.size));
+        let clip_radius = clip.map_or(BorderRadius::ZERO, |clip| clip.radius);
+        let clip = clip.map(|clip| clip.clip);
         extracted_uinodes.uinodes.push(ExtractedUiNode {
-            transform: transform.compute_matrix(),
+            transform,
             color: color.0,
-            rect: bevy_sprite::Rect {
-                min: Vec2::ZERO,
-                max: uinode.size,
-            },
-            image,
+            rect,
+            image: image.clone_weak(),
             atlas_size: None,
-            clip: clip.map(|clip| clip.clip),
+            clip,
+            clip_radius,
+            border_radius,
+            border: None,
+            camera,
+            window,
         });
+
+        // The border is drawn over the node, in the same batch
+        if let Some(border_color) = border_color {
+            // Borders in percent are relative to the width of the parent, as in the layout
+            let parent_width = match parent {
+                Some(parent) => parent_query.get(parent.0).map_or(0.0, |node| node.size.x),
+                None => windows.get(window).map_or(0.0, |window| window.width()),
+            };
+            let border = UiRect {
+                left: resolve_border_width(style.border.left, parent_width),
+                right: resolve_border_width(style.border.right, parent_width),
+                top: resolve_border_width(style.border.top, parent_width),
+                bottom: resolve_border_width(style.border.bottom, parent_width),
+            };
+            let has_border = [border.left, border.right, border.top, border.bottom]
+                .iter()
+                .any(|width| *width > 0.0);
+            if has_border && border_color.0.a() > 0.0 {
+                extracted_uinodes.uinodes.push(ExtractedUiNode {
+                    transform,
+                    color: border_color.0,
+                    rect,
+                    image,
+                    atlas_size: None,
+                    clip,
+                    clip_radius,
+                    border_radius,
+                    border: Some(border),
+                    camera,
+                    window,
+                });
+            }
+        }
     }
 }
 
+/// Returns the width in logical pixels of a side of a border, with [`Val::Percent`] widths
+/// relative to `parent_width`.
+fn resolve_border_width(width: Val, parent_width: f32) -> f32 {
+    match width {
+        Val::Px(width) => width.max(0.0),
+        Val::Percent(percent) => (percent / 100.0 * parent_width).max(0.0),
+        Val::Auto | Val::Undefined => 0.0,
+    }
+}
+
+/// Splits an image in the nine slices of a [`ImageMode::Sliced`] node.
+///
+/// Returns the rect of each slice in the image, and the rect it fills in the node, relative to
+/// the center of the node. Empty slices are skipped.
+fn compute_slices(node_size: Vec2, image_size: Vec2, border: &BorderRect) -> Vec<(Rect, Rect)> {
+    // Scale the borders down if they don't fit in the node
+    let border_size = Vec2::new(border.left + border.right, border.top + border.bottom);
+    let fit = (node_size / border_size).min_element().min(1.0);
+
+    // The edges of the slices in the image, from its top left
+    let image_x = [0.0, border.left, image_size.x - border.right, image_size.x];
+    let image_y = [0.0, border.top, image_size.y - border.bottom, image_size.y];
+    // The edges of the slices in the node, from its top left
+    let node_x = [
+        0.0,
+        border.left * fit,
+        node_size.x - border.right * fit,
+        node_size.x,
+    ];
+    let node_y = [
+        0.0,
+        border.top * fit,
+        node_size.y - border.bottom * fit,
+        node_size.y,
+    ];
+
+    let mut slices = Vec::with_capacity(9);
+    for row in 0..3 {
+        for column in 0..3 {
+            let rect = Rect {
+                min: Vec2::new(image_x[column], image_y[row]),
+                max: Vec2::new(image_x[column + 1], image_y[row + 1]),
+            };
+            // The node is y-up from its center
+            let slice = Rect {
+                min: Vec2::new(node_x[column], -node_y[row + 1]) + node_size * Vec2::new(-0.5, 0.5),
+                max: Vec2::new(node_x[column + 1], -node_y[row]) + node_size * Vec2::new(-0.5, 0.5),
+            };
+            if rect.size().cmpgt(Vec2::ZERO).all() && slice.size().cmpgt(Vec2::ZERO).all() {
+                slices.push((rect, slice));
+            }
+        }
+    }
+    slices
+}
+
 /// The UI camera is "moved back" by this many units (plus the [`UI_CAMERA_TRANSFORM_OFFSET`]) and also has a view
 /// distance of this many units. This ensures that with a left-handed projection,
 /// as ui elements are "stacked on top of each other", they are within the camera's view
@@ -271,6 +455,8 @@ pub fn extract_text_uinodes(
     texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
     text_pipeline: Extract<Res<DefaultTextPipeline>>,
     windows: Extract<Res<Windows>>,
+    world_ui_nodes: Extract<Res<WorldUiNodes>>,
+    ui_window_nodes: Extract<Res<UiWindowNodes>>,
     uinode_query: Extract<
         Query<(
             Entity,
@@ -279,11 +465,14 @@ pub fn extract_text_uinodes(
             &Text,
             &Visibility,
             Option<&CalculatedClip>,
+            Option<&TextGlyphModifiers>,
+            Option<&TextReveal>,
         )>,
     >,
 ) {
-    let scale_factor = windows.scale_factor(WindowId::primary()) as f32;
-    for (entity, uinode, transform, text, visibility, clip) in uinode_query.iter() {
+    for (entity, uinode, transform, text, visibility, clip, modifiers, reveal) in
+        uinode_query.iter()
+    {
         if !visibility.is_visible {
             continue;
         }
@@ -291,15 +480,22 @@ pub fn extract_text_uinodes(
         if uinode.size == Vec2::ZERO {
             continue;
         }
+        let camera = world_ui_nodes.camera(entity);
+        let window = ui_window_nodes.window(entity);
+        let scale_factor = windows.scale_factor(window) as f32;
         if let Some(text_layout) = text_pipeline.get_glyphs(&entity) {
-            let text_glyphs = &text_layout.glyphs;
             let alignment_offset = (uinode.size / -2.0).extend(0.0);
 
-            for text_glyph in text_glyphs {
-                let color = text.sections[text_glyph.section_index].style.color;
-                let atlas = texture_atlases
-                    .get(&text_glyph.atlas_info.texture_atlas)
-                    .unwrap();
+            for (glyph_index, text_glyph) in text_layout.revealed_glyphs(reveal) {
+                let modifier = modifiers
+                    .map(|modifiers| modifiers.get(glyph_index))
+                    .unwrap_or_default();
+                let (position, color) = modifier.apply(text_glyph, &text.sections, scale_factor);
+                // The atlas may have been evicted, the text is laid out again on the next update
+                let atlas = match texture_atlases.get(&text_glyph.atlas_info.texture_atlas) {
+                    Some(atlas) => atlas,
+                    None => continue,
+                };
                 let texture = atlas.texture.clone_weak();
                 let index = text_glyph.atlas_info.glyph_index as usize;
                 let rect = atlas.textures[index];
@@ -309,7 +505,7 @@ pub fn extract_text_uinodes(
                     Mat4::from_rotation_translation(transform.rotation, transform.translation)
                         * Mat4::from_scale(transform.scale / scale_factor)
                         * Mat4::from_translation(
-                            alignment_offset * scale_factor + text_glyph.position.extend(0.),
+                            alignment_offset * scale_factor + position.extend(0.),
                         );
 
                 extracted_uinodes.uinodes.push(ExtractedUiNode {
@@ -319,12 +515,141 @@ pub fn extract_text_uinodes(
                     image: texture,
                     atlas_size,
                     clip: clip.map(|clip| clip.clip),
+                    clip_radius: clip.map_or(BorderRadius::ZERO, |clip| clip.radius),
+                    border_radius: BorderRadius::ZERO,
+                    border: None,
+                    camera,
+                    window,
                 });
             }
         }
     }
 }
 
+/// Extracts the caret and selection of focused [`TextInput`]s, drawn over their text.
+pub fn extract_text_input_carets(
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    windows: Extract<Res<Windows>>,
+    focus: Extract<Res<Focus>>,
+    world_ui_nodes: Extract<Res<WorldUiNodes>>,
+    ui_window_nodes: Extract<Res<UiWindowNodes>>,
+    input_query: Extract<
+        Query<(
+            Entity,
+            &Node,
+            &GlobalTransform,
+            &Text,
+            &TextInput,
+            &TextLayoutInfo,
+            &Visibility,
+            Option<&CalculatedClip>,
+        )>,
+    >,
+) {
+    for (entity, uinode, transform, text, input, layout, visibility, clip) in input_query.iter() {
+        if !visibility.is_visible || focus.get() != Some(entity) || uinode.size == Vec2::ZERO {
+            continue;
+        }
+        let camera = world_ui_nodes.camera(entity);
+        let window = ui_window_nodes.window(entity);
+        let scale_factor = windows.scale_factor(window) as f32;
+        // The rects below are in the space of the text layout, in physical pixels
+        let mut rects = Vec::new();
+        // The horizontal extent of the text between two byte indices on each line it spans
+        let spans = |start: usize, end: usize| {
+            layout.lines.iter().filter_map(move |line| {
+                if line.byte_range.end <= start || line.byte_range.start >= end {
+                    return None;
+                }
+                let caret_x = |byte_index| {
+                    layout
+                        .caret_position(byte_index)
+                        .map(|caret| caret.position.x)
+                };
+                let min_x = if start > line.byte_range.start {
+                    caret_x(start).unwrap_or(line.bounds.min.x)
+                } else {
+                    line.bounds.min.x
+                };
+                let max_x = if end < line.byte_range.end {
+                    caret_x(end).unwrap_or(line.bounds.max.x)
+                } else {
+                    line.bounds.max.x
+                };
+                Some((min_x.min(max_x), min_x.max(max_x), line))
+            })
+        };
+        if let Some((start, end)) = input.selection() {
+            let (start, end) = (input.displayed_index(start), input.displayed_index(end));
+            for (min_x, max_x, line) in spans(start, end) {
+                rects.push((
+                    Rect {
+                        min: Vec2::new(min_x, line.bounds.min.y),
+                        max: Vec2::new(max_x, line.bounds.max.y),
+                    },
+                    input.selection_color,
+                ));
+            }
+        }
+        let caret_width = input.caret_width * scale_factor;
+        // The text being composed with an IME is underlined
+        if let Some((start, end)) = input.preedit_range() {
+            for (min_x, max_x, line) in spans(start, end) {
+                rects.push((
+                    Rect {
+                        min: Vec2::new(min_x, line.bounds.min.y),
+                        max: Vec2::new(max_x, line.bounds.min.y + caret_width / 2.0),
+                    },
+                    input.caret_color,
+                ));
+            }
+        }
+        let (caret_position, caret_height) =
+            match layout.caret_position(input.displayed_index(input.cursor)) {
+                Some(caret) => (caret.position, caret.height),
+                None => {
+                    let font_size = text
+                        .sections
+                        .first()
+                        .map_or(0.0, |section| section.style.font_size);
+                    (Vec2::ZERO, font_size * scale_factor)
+                }
+            };
+        rects.push((
+            Rect {
+                min: caret_position - Vec2::new(caret_width / 2.0, 0.0),
+                max: caret_position + Vec2::new(caret_width / 2.0, caret_height),
+            },
+            input.caret_color,
+        ));
+
+        let alignment_offset = (uinode.size / -2.0).extend(0.0);
+        for (rect, color) in rects {
+            let center = (rect.min + rect.max) / 2.0;
+            let transform =
+                Mat4::from_rotation_translation(transform.rotation, transform.translation)
+                    * Mat4::from_scale(transform.scale / scale_factor)
+                    * Mat4::from_translation(alignment_offset * scale_factor + center.extend(0.));
+            extracted_uinodes.uinodes.push(ExtractedUiNode {
+                transform,
+                color,
+                rect: Rect {
+                    min: Vec2::ZERO,
+                    max: rect.max - rect.min,
+                },
+                image: DEFAULT_IMAGE_HANDLE.typed(),
+                atlas_size: None,
+                clip: clip.map(|clip| clip.clip),
+                clip_radius: clip.map_or(BorderRadius::ZERO, |clip| clip.radius),
+                border_radius: BorderRadius::ZERO,
+                border: None,
+                camera,
+                window,
+            });
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Pod, Zeroable)]
 struct UiVertex {
@@ -333,8 +658,51 @@ struct UiVertex {
     pub color: [f32; 4],
 }
 
+/// The vertex of a node with rounded corners, a border or a rounded clip, drawn by the
+/// [`UiPipelineKey::shaped`] pipeline
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct UiShapeVertex {
+    pub position: [f32; 3],
+    pub uv: [f32; 2],
+    pub color: [f32; 4],
+    /// The position of the vertex relative to the center of the node, in the space of its rect
+    pub point: [f32; 2],
+    /// The size of the node, in the space of its rect
+    pub size: [f32; 2],
+    /// The radius of the corners: top left, top right, bottom right and bottom left
+    pub radius: [f32; 4],
+    /// The width of the border: left, right, top and bottom
+    pub border: [f32; 4],
+    /// The clip rect: min x, min y, max x and max y
+    pub clip: [f32; 4],
+    /// The radius of the corners of the clip rect
+    pub clip_radius: [f32; 4],
+    /// How the node is drawn, see `ui.wgsl`
+    pub mode: f32,
+}
+
+/// Draws the image of the node as it is
+const MODE_IMAGE: f32 = 0.0;
+/// Draws the image of the node with rounded corners
+const MODE_ROUNDED: f32 = 1.0;
+/// Draws the border of the node
+const MODE_BORDER: f32 = 2.0;
+
+impl BorderRadius {
+    fn to_array(self) -> [f32; 4] {
+        [
+            self.top_left,
+            self.top_right,
+            self.bottom_right,
+            self.bottom_left,
+        ]
+    }
+}
+
 pub struct UiMeta {
     vertices: BufferVec<UiVertex>,
+    shape_vertices: BufferVec<UiShapeVertex>,
     view_bind_group: Option<BindGroup>,
 }
 
@@ -342,6 +710,7 @@ impl Default for UiMeta {
     fn default() -> Self {
         Self {
             vertices: BufferVec::new(BufferUsages::VERTEX),
+            shape_vertices: BufferVec::new(BufferUsages::VERTEX),
             view_bind_group: None,
         }
     }
@@ -358,9 +727,16 @@ const QUAD_INDICES: [usize; 6] = [0, 2, 3, 0, 1, 2];
 
 #[derive(Component)]
 pub struct UiBatch {
+    /// The vertices of the batch, in the buffer of its pipeline
     pub range: Range<u32>,
     pub image: Handle<Image>,
+    /// Whether the batch is drawn with the [`UiPipelineKey::shaped`] pipeline
+    pub shaped: bool,
     pub z: f32,
+    /// The camera rendering the batch, see [`ExtractedUiNode::camera`]
+    pub camera: Option<Entity>,
+    /// The window displaying the batch, see [`ExtractedUiNode::window`]
+    pub window: WindowId,
 }
 
 pub fn prepare_uinodes(
@@ -371,6 +747,7 @@ pub fn prepare_uinodes(
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
 ) {
     ui_meta.vertices.clear();
+    ui_meta.shape_vertices.clear();
 
     // sort by increasing z for correct transparency
     extracted_uinodes
@@ -380,18 +757,44 @@ pub fn prepare_uinodes(
     let mut start = 0;
     let mut end = 0;
     let mut current_batch_handle = Default::default();
+    let mut current_batch_shaped = false;
+    let mut current_batch_camera = None;
+    let mut current_batch_window = WindowId::primary();
     let mut last_z = 0.0;
     for extracted_uinode in &extracted_uinodes.uinodes {
-        if current_batch_handle != extracted_uinode.image {
+        let mode = match extracted_uinode.border {
+            Some(_) => MODE_BORDER,
+            None if extracted_uinode.border_radius.is_zero() => MODE_IMAGE,
+            None => MODE_ROUNDED,
+        };
+        // Only the nodes that need it are drawn with the larger vertices of the shaped pipeline
+        let shaped = mode != MODE_IMAGE || !extracted_uinode.clip_radius.is_zero();
+        if current_batch_handle != extracted_uinode.image
+            || current_batch_shaped != shaped
+            || current_batch_camera != extracted_uinode.camera
+            || current_batch_window != extracted_uinode.window
+        {
             if start != end {
                 commands.spawn_bundle((UiBatch {
                     range: start..end,
                     image: current_batch_handle,
+                    shaped: current_batch_shaped,
                     z: last_z,
+                    camera: current_batch_camera,
+                    window: current_batch_window,
                 },));
-                start = end;
             }
+            // Each pipeline has its own vertex buffer
+            start = if shaped {
+                ui_meta.shape_vertices.len()
+            } else {
+                ui_meta.vertices.len()
+            } as u32;
+            end = start;
             current_batch_handle = extracted_uinode.image.clone_weak();
+            current_batch_shaped = shaped;
+            current_batch_camera = extracted_uinode.camera;
+            current_batch_window = extracted_uinode.window;
         }
 
         let uinode_rect = extracted_uinode.rect;
@@ -443,6 +846,9 @@ pub fn prepare_uinodes(
         }
 
         // Clip UVs (Note: y is reversed in UV space)
+        // The clipped distances are scaled to the space of the rect, which may be drawn scaled
+        let rect_scale = rect_size.truncate() / transformed_rect_size.truncate();
+        let positions_diff = positions_diff.map(|diff| diff * rect_scale);
         let atlas_extent = extracted_uinode.atlas_size.unwrap_or(uinode_rect.max);
         let uvs = [
             Vec2::new(
@@ -464,12 +870,38 @@ pub fn prepare_uinodes(
         ]
         .map(|pos| pos / atlas_extent);
 
-        for i in QUAD_INDICES {
-            ui_meta.vertices.push(UiVertex {
-                position: positions_clipped[i].into(),
-                uv: uvs[i].into(),
-                color: extracted_uinode.color.as_linear_rgba_f32(),
+        let color = extracted_uinode.color.as_linear_rgba_f32();
+        if !shaped {
+            for i in QUAD_INDICES {
+                ui_meta.vertices.push(UiVertex {
+                    position: positions_clipped[i].into(),
+                    uv: uvs[i].into(),
+                    color,
+                });
+            }
+        } else {
+            let border = extracted_uinode.border.map_or([0.0; 4], |border| {
+                [border.left, border.right, border.top, border.bottom]
             });
+            let clip = extracted_uinode.clip.map_or([0.0; 4], |clip| {
+                [clip.min.x, clip.min.y, clip.max.x, clip.max.y]
+            });
+            for i in QUAD_INDICES {
+                let point =
+                    QUAD_VERTEX_POSITIONS[i].truncate() * rect_size.truncate() + positions_diff[i];
+                ui_meta.shape_vertices.push(UiShapeVertex {
+                    position: positions_clipped[i].into(),
+                    uv: uvs[i].into(),
+                    color,
+                    point: point.into(),
+                    size: rect_size.truncate().into(),
+                    radius: extracted_uinode.border_radius.to_array(),
+                    border,
+                    clip,
+                    clip_radius: extracted_uinode.clip_radius.to_array(),
+                    mode,
+                });
+            }
         }
 
         last_z = extracted_uinode.transform.w_axis[2];
@@ -481,11 +913,17 @@ pub fn prepare_uinodes(
         commands.spawn_bundle((UiBatch {
             range: start..end,
             image: current_batch_handle,
+            shaped: current_batch_shaped,
             z: last_z,
+            camera: current_batch_camera,
+            window: current_batch_window,
         },));
     }
 
     ui_meta.vertices.write_buffer(&render_device, &render_queue);
+    ui_meta
+        .shape_vertices
+        .write_buffer(&render_device, &render_queue);
 }
 
 #[derive(Default)]
@@ -505,7 +943,12 @@ pub fn queue_uinodes(
     mut image_bind_groups: ResMut<UiImageBindGroups>,
     gpu_images: Res<RenderAssets<Image>>,
     ui_batches: Query<(Entity, &UiBatch)>,
-    mut views: Query<&mut RenderPhase<TransparentUi>>,
+    mut views: Query<(
+        Entity,
+        Option<&ExtractedCamera>,
+        &mut RenderPhase<TransparentUi>,
+    )>,
+    extracted_uinodes: Res<ExtractedUiNodes>,
     events: Res<SpriteAssetEvents>,
 ) {
     // If an image has changed, the GpuImage has (probably) changed
@@ -528,9 +971,35 @@ pub fn queue_uinodes(
             layout: &ui_pipeline.view_layout,
         }));
         let draw_ui_function = draw_functions.read().get_id::<DrawUi>().unwrap();
-        let pipeline = pipelines.specialize(&mut pipeline_cache, &ui_pipeline, UiPipelineKey {});
-        for mut transparent_phase in views.iter_mut() {
+        let pipeline = pipelines.specialize(
+            &mut pipeline_cache,
+            &ui_pipeline,
+            UiPipelineKey { shaped: false },
+        );
+        let shaped_pipeline = pipelines.specialize(
+            &mut pipeline_cache,
+            &ui_pipeline,
+            UiPipelineKey { shaped: true },
+        );
+        for (view, extracted_camera, mut transparent_phase) in views.iter_mut() {
+            // The cameras rendering a world UI only render its nodes
+            let view_camera = if extracted_uinodes.world_ui_cameras.contains(&view) {
+                Some(view)
+            } else {
+                None
+            };
+            // The cameras rendering to a window only render the nodes it displays, the other
+            // cameras render the nodes of the primary window
+            let view_window = match extracted_camera.map(|camera| &camera.target) {
+                Some(RenderTarget::Window(window_id)) => *window_id,
+                _ => WindowId::primary(),
+            };
             for (entity, batch) in ui_batches.iter() {
+                if batch.camera != view_camera
+                    || (view_camera.is_none() && batch.window != view_window)
+                {
+                    continue;
+                }
                 image_bind_groups
                     .values
                     .entry(batch.image.clone_weak())
@@ -553,7 +1022,11 @@ pub fn queue_uinodes(
                     });
                 transparent_phase.add(TransparentUi {
                     draw_function: draw_ui_function,
-                    pipeline,
+                    pipeline: if batch.shaped {
+                        shaped_pipeline
+                    } else {
+                        pipeline
+                    },
                     entity,
                     sort_key: FloatOrd(batch.z),
                 });
@@ -561,3 +1034,92 @@ pub fn queue_uinodes(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn border_widths_in_percent_are_relative_to_the_parent_width() {
+        assert_eq!(resolve_border_width(Val::Px(4.0), 200.0), 4.0);
+        assert_eq!(resolve_border_width(Val::Percent(5.0), 200.0), 10.0);
+        assert_eq!(resolve_border_width(Val::Percent(-5.0), 200.0), 0.0);
+        assert_eq!(resolve_border_width(Val::Auto, 200.0), 0.0);
+        assert_eq!(resolve_border_width(Val::Undefined, 200.0), 0.0);
+    }
+
+    fn rect(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Rect {
+        Rect {
+            min: Vec2::new(min_x, min_y),
+            max: Vec2::new(max_x, max_y),
+        }
+    }
+
+    #[test]
+    fn sliced_images_stretch_their_center() {
+        let slices = compute_slices(
+            Vec2::new(100.0, 50.0),
+            Vec2::new(30.0, 30.0),
+            &BorderRect::all(10.0),
+        );
+        assert_eq!(slices.len(), 9);
+        // Corners keep their size, from the top left
+        assert_eq!(
+            slices[0],
+            (rect(0.0, 0.0, 10.0, 10.0), rect(-50.0, 15.0, -40.0, 25.0))
+        );
+        assert_eq!(
+            slices[2],
+            (rect(20.0, 0.0, 30.0, 10.0), rect(40.0, 15.0, 50.0, 25.0))
+        );
+        assert_eq!(
+            slices[8],
+            (rect(20.0, 20.0, 30.0, 30.0), rect(40.0, -25.0, 50.0, -15.0))
+        );
+        // Edges are stretched along their side, the center in both directions
+        assert_eq!(
+            slices[1],
+            (rect(10.0, 0.0, 20.0, 10.0), rect(-40.0, 15.0, 40.0, 25.0))
+        );
+        assert_eq!(
+            slices[4],
+            (rect(10.0, 10.0, 20.0, 20.0), rect(-40.0, -15.0, 40.0, 15.0))
+        );
+    }
+
+    #[test]
+    fn borders_that_dont_fit_are_scaled_down() {
+        let slices = compute_slices(
+            Vec2::new(10.0, 40.0),
+            Vec2::new(30.0, 30.0),
+            &BorderRect::all(10.0),
+        );
+        // The middle column is empty
+        assert_eq!(slices.len(), 6);
+        assert_eq!(
+            slices[0],
+            (rect(0.0, 0.0, 10.0, 10.0), rect(-5.0, 15.0, 0.0, 20.0))
+        );
+        assert_eq!(
+            slices[2],
+            (rect(0.0, 10.0, 10.0, 20.0), rect(-5.0, -15.0, 0.0, 15.0))
+        );
+        assert_eq!(
+            slices[5],
+            (rect(20.0, 20.0, 30.0, 30.0), rect(0.0, -20.0, 5.0, -15.0))
+        );
+    }
+
+    #[test]
+    fn images_without_borders_are_a_single_slice() {
+        let slices = compute_slices(
+            Vec2::new(100.0, 50.0),
+            Vec2::new(30.0, 30.0),
+            &BorderRect::default(),
+        );
+        assert_eq!(
+            slices,
+            vec![(rect(0.0, 0.0, 30.0, 30.0), rect(-50.0, -25.0, 50.0, 25.0))]
+        );
+    }
+}
@@ -56,24 +56,45 @@ impl FromWorld for UiPipeline {
 }
 
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
-pub struct UiPipelineKey {}
+pub struct UiPipelineKey {
+    /// Draws nodes with rounded corners, borders or rounded clips, at the cost of larger vertices
+    pub shaped: bool,
+}
 
 impl SpecializedRenderPipeline for UiPipeline {
     type Key = UiPipelineKey;
-    /// FIXME: there are no specialization for now, should this be removed?
-    fn specialize(&self, _key: Self::Key) -> RenderPipelineDescriptor {
-        let vertex_layout = VertexBufferLayout::from_vertex_formats(
-            VertexStepMode::Vertex,
-            vec![
-                // position
-                VertexFormat::Float32x3,
-                // uv
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut formats = vec![
+            // position
+            VertexFormat::Float32x3,
+            // uv
+            VertexFormat::Float32x2,
+            // color
+            VertexFormat::Float32x4,
+        ];
+        let mut shader_defs = Vec::new();
+        if key.shaped {
+            shader_defs.push("SHAPED".to_string());
+            formats.extend([
+                // point
+                VertexFormat::Float32x2,
+                // size
                 VertexFormat::Float32x2,
-                // color
+                // radius
                 VertexFormat::Float32x4,
-            ],
-        );
-        let shader_defs = Vec::new();
+                // border
+                VertexFormat::Float32x4,
+                // clip
+                VertexFormat::Float32x4,
+                // clip_radius
+                VertexFormat::Float32x4,
+                // mode
+                VertexFormat::Float32,
+            ]);
+        }
+        let vertex_layout =
+            VertexBufferLayout::from_vertex_formats(VertexStepMode::Vertex, formats);
 
         RenderPipelineDescriptor {
             vertex: VertexState {
@@ -108,7 +129,11 @@ impl SpecializedRenderPipeline for UiPipeline {
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
-            label: Some("ui_pipeline".into()),
+            label: Some(if key.shaped {
+                "ui_shaped_pipeline".into()
+            } else {
+                "ui_pipeline".into()
+            }),
         }
     }
 }
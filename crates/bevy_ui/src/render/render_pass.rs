@@ -197,8 +197,14 @@ impl EntityRenderCommand for DrawUiNode {
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
         let batch = query_batch.get(item).unwrap();
+        let ui_meta = ui_meta.into_inner();
+        let vertices = if batch.shaped {
+            ui_meta.shape_vertices.buffer()
+        } else {
+            ui_meta.vertices.buffer()
+        };
 
-        pass.set_vertex_buffer(0, ui_meta.into_inner().vertices.buffer().unwrap().slice(..));
+        pass.set_vertex_buffer(0, vertices.unwrap().slice(..));
         pass.draw(batch.range.clone(), 0..1);
         RenderCommandResult::Success
     }
@@ -8,6 +8,17 @@ var<uniform> view: View;
 struct VertexOutput {
     [[location(0)]] uv: vec2<f32>;
     [[location(1)]] color: vec4<f32>;
+#ifdef SHAPED
+    // The position relative to the center of the node
+    [[location(2)]] point: vec2<f32>;
+    [[location(3)]] size: vec2<f32>;
+    [[location(4)]] radius: vec4<f32>;
+    [[location(5)]] border: vec4<f32>;
+    [[location(6)]] clip: vec4<f32>;
+    [[location(7)]] clip_radius: vec4<f32>;
+    [[location(8)]] mode: f32;
+    [[location(9)]] world_position: vec2<f32>;
+#endif
     [[builtin(position)]] position: vec4<f32>;
 };
 
@@ -16,22 +27,95 @@ fn vertex(
     [[location(0)]] vertex_position: vec3<f32>,
     [[location(1)]] vertex_uv: vec2<f32>,
     [[location(2)]] vertex_color: vec4<f32>,
+#ifdef SHAPED
+    [[location(3)]] vertex_point: vec2<f32>,
+    [[location(4)]] vertex_size: vec2<f32>,
+    [[location(5)]] vertex_radius: vec4<f32>,
+    [[location(6)]] vertex_border: vec4<f32>,
+    [[location(7)]] vertex_clip: vec4<f32>,
+    [[location(8)]] vertex_clip_radius: vec4<f32>,
+    [[location(9)]] vertex_mode: f32,
+#endif
 ) -> VertexOutput {
     var out: VertexOutput;
     out.uv = vertex_uv;
     out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
     out.color = vertex_color;
+#ifdef SHAPED
+    out.point = vertex_point;
+    out.size = vertex_size;
+    out.radius = vertex_radius;
+    out.border = vertex_border;
+    out.clip = vertex_clip;
+    out.clip_radius = vertex_clip_radius;
+    out.mode = vertex_mode;
+    out.world_position = vertex_position.xy;
+#endif
     return out;
-} 
+}
 
 [[group(1), binding(0)]]
 var sprite_texture: texture_2d<f32>;
 [[group(1), binding(1)]]
 var sprite_sampler: sampler;
 
+#ifdef SHAPED
+// The signed distance from a point to a box centered on the origin, with its corners rounded by
+// the radii (top left, top right, bottom right, bottom left). The y axis points up.
+fn sd_rounded_box(point: vec2<f32>, size: vec2<f32>, radius: vec4<f32>) -> f32 {
+    // Pick the radius of the corner in the quadrant of the point
+    let radii = select(radius.wz, radius.xy, 0.0 < point.y);
+    let r = select(radii.x, radii.y, 0.0 < point.x);
+    let q = abs(point) - 0.5 * size + r;
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - r;
+}
+
+// The signed distance from a point to the inside of a border with the widths (left, right, top,
+// bottom) of a rounded box.
+fn sd_inset_rounded_box(point: vec2<f32>, size: vec2<f32>, radius: vec4<f32>, inset: vec4<f32>) -> f32 {
+    let inner_size = size - vec2<f32>(inset.x + inset.y, inset.z + inset.w);
+    let inner_center = 0.5 * vec2<f32>(inset.x - inset.y, inset.w - inset.z);
+    let inner_radius = max(
+        radius - vec4<f32>(
+            max(inset.x, inset.z),
+            max(inset.y, inset.z),
+            max(inset.y, inset.w),
+            max(inset.x, inset.w),
+        ),
+        vec4<f32>(0.0),
+    );
+    return sd_rounded_box(point - inner_center, inner_size, inner_radius);
+}
+
+// The coverage of a pixel at the given signed distance from an edge, smoothed over one pixel.
+fn antialias(distance: f32) -> f32 {
+    return clamp(0.5 - distance / max(fwidth(distance), 0.0001), 0.0, 1.0);
+}
+#endif
+
 [[stage(fragment)]]
 fn fragment(in: VertexOutput) -> [[location(0)]] vec4<f32> {
-    var color = textureSample(sprite_texture, sprite_sampler, in.uv); 
+    var color = textureSample(sprite_texture, sprite_sampler, in.uv);
     color = in.color * color;
+
+#ifdef SHAPED
+    // The coverages are computed in uniform control flow for the derivatives to be valid
+    let outer = antialias(sd_rounded_box(in.point, in.size, in.radius));
+    let inner = antialias(sd_inset_rounded_box(in.point, in.size, in.radius, in.border));
+    let clip_center = 0.5 * (in.clip.xy + in.clip.zw);
+    let clip = antialias(sd_rounded_box(in.world_position - clip_center, in.clip.zw - in.clip.xy, in.clip_radius));
+
+    // Mode 0 draws the image as it is, mode 1 rounds its corners and mode 2 draws the border
+    var coverage = select(1.0, outer, 0.5 < in.mode);
+    if (1.5 < in.mode) {
+        color = in.color;
+        coverage = outer * (1.0 - inner);
+    }
+    // The rect of the clip is already applied to the vertices, only its corners are left
+    if (any(vec4<f32>(0.0) < in.clip_radius)) {
+        coverage = coverage * clip;
+    }
+    color = vec4<f32>(color.rgb, color.a * coverage);
+#endif
     return color;
-}
\ No newline at end of file
+}
@@ -0,0 +1,169 @@
+use crate::{CalculatedClip, Node, Overflow, ScrollPosition, Style, UiWindowNodes};
+use bevy_ecs::{
+    entity::Entity,
+    event::EventReader,
+    system::{Local, Query, Res},
+};
+use bevy_input::{
+    mouse::{MouseScrollUnit, MouseWheel},
+    touch::Touches,
+};
+use bevy_math::Vec2;
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::{FloatOrd, HashMap};
+use bevy_window::{WindowId, Windows};
+
+/// The distance in logical pixels scrolled by one line of mouse wheel movement.
+pub const SCROLL_LINE_HEIGHT: f32 = 20.0;
+
+/// Clamps a scroll offset so that the content of a node can't be scrolled past its edges.
+///
+/// `content_min` and `content_max` are the bounds of the node's children, relative to the bottom
+/// left of the node, before scrolling.
+pub(crate) fn clamp_scroll_offset(
+    offset: Vec2,
+    node_size: Vec2,
+    content_min: Vec2,
+    content_max: Vec2,
+) -> Vec2 {
+    // A positive x offset moves the content left, a positive y offset moves it up
+    Vec2::new(
+        offset.x.clamp(
+            content_min.x.min(0.0),
+            (content_max.x - node_size.x).max(0.0),
+        ),
+        offset.y.clamp(
+            -(content_max.y - node_size.y).max(0.0),
+            (-content_min.y).max(0.0),
+        ),
+    )
+}
+
+/// Scrolls nodes with [`Overflow::Scroll`] using the mouse wheel over them, or by dragging them
+/// with a touch.
+pub fn ui_scroll_system(
+    windows: Res<Windows>,
+    ui_window_nodes: Res<UiWindowNodes>,
+    touches: Res<Touches>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    // The last position of each touch, to scroll by how much it moved since the last frame
+    mut touch_positions: Local<HashMap<u64, Vec2>>,
+    mut node_query: Query<(
+        Entity,
+        &Node,
+        &Style,
+        &GlobalTransform,
+        &mut ScrollPosition,
+        Option<&CalculatedClip>,
+    )>,
+) {
+    let mut wheel_delta = Vec2::ZERO;
+    for event in mouse_wheel_events.iter() {
+        let delta = Vec2::new(event.x, event.y);
+        wheel_delta += match event.unit {
+            MouseScrollUnit::Line => delta * SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => delta,
+        };
+    }
+
+    let mut touch_delta = Vec2::ZERO;
+    let mut touch_start = None;
+    touch_positions.retain(|id, _| touches.get_pressed(*id).is_some());
+    for touch in touches.iter() {
+        let previous = *touch_positions
+            .entry(touch.id())
+            .or_insert_with(|| touch.start_position());
+        touch_delta += touch.position() - previous;
+        touch_positions.insert(touch.id(), touch.position());
+        touch_start.get_or_insert(touch.start_position());
+    }
+
+    let mut scroll = |window_id: WindowId, position: Vec2, delta: Vec2| {
+        // Only scroll the topmost node of the window under the position
+        let target = node_query
+            .iter_mut()
+            .filter(|(entity, node, style, transform, _, clip)| {
+                if style.overflow != Overflow::Scroll
+                    || ui_window_nodes.window(*entity) != window_id
+                {
+                    return false;
+                }
+                let center = transform.translation.truncate();
+                let mut min = center - node.size / 2.0;
+                let mut max = center + node.size / 2.0;
+                if let Some(clip) = clip {
+                    min = min.max(clip.clip.min);
+                    max = max.min(clip.clip.max);
+                }
+                (min.x..max.x).contains(&position.x) && (min.y..max.y).contains(&position.y)
+            })
+            .max_by_key(|(_, _, _, transform, _, _)| FloatOrd(transform.translation.z));
+        if let Some((_, _, _, _, mut scroll_position, _)) = target {
+            // The offset is clamped to the content once the layout is updated
+            scroll_position.offset += delta;
+        }
+    };
+
+    if wheel_delta != Vec2::ZERO {
+        // The wheel scrolls the UI of the window under the cursor
+        for window in windows.iter() {
+            if let Some(cursor_position) = window.cursor_position() {
+                // Scrolling the wheel up moves the content down
+                scroll(
+                    window.id(),
+                    cursor_position,
+                    Vec2::new(-wheel_delta.x, -wheel_delta.y),
+                );
+            }
+        }
+    }
+    if let Some(touch_start) = touch_start {
+        if touch_delta != Vec2::ZERO {
+            // The content follows the touch, in the primary window
+            scroll(
+                WindowId::primary(),
+                touch_start,
+                Vec2::new(-touch_delta.x, touch_delta.y),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::clamp_scroll_offset;
+    use bevy_math::Vec2;
+
+    #[test]
+    fn scroll_offset_is_clamped_to_content() {
+        let node_size = Vec2::new(100.0, 100.0);
+        // Content overflowing below and to the right of the node
+        let content_min = Vec2::new(0.0, -200.0);
+        let content_max = Vec2::new(150.0, 100.0);
+
+        assert_eq!(
+            clamp_scroll_offset(Vec2::new(20.0, 50.0), node_size, content_min, content_max),
+            Vec2::new(20.0, 50.0)
+        );
+        assert_eq!(
+            clamp_scroll_offset(Vec2::new(80.0, 500.0), node_size, content_min, content_max),
+            Vec2::new(50.0, 200.0)
+        );
+        assert_eq!(
+            clamp_scroll_offset(Vec2::new(-10.0, -10.0), node_size, content_min, content_max),
+            Vec2::ZERO
+        );
+    }
+
+    #[test]
+    fn content_fitting_in_node_does_not_scroll() {
+        let node_size = Vec2::new(100.0, 100.0);
+        let offset = clamp_scroll_offset(
+            Vec2::new(30.0, -30.0),
+            node_size,
+            Vec2::new(10.0, 10.0),
+            Vec2::new(90.0, 90.0),
+        );
+        assert_eq!(offset, Vec2::ZERO);
+    }
+}
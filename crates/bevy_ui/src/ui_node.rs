@@ -3,7 +3,7 @@ use bevy_asset::Handle;
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
 use bevy_math::Vec2;
-use bevy_reflect::prelude::*;
+use bevy_reflect::{prelude::*, FromReflect};
 use bevy_render::{
     color::Color,
     texture::{Image, DEFAULT_IMAGE_HANDLE},
@@ -20,8 +20,8 @@ pub struct Node {
 }
 
 /// An enum that describes possible types of value in flexbox layout options
-#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize, Reflect)]
-#[reflect_value(PartialEq, Serialize, Deserialize)]
+#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize, Reflect, FromReflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
 pub enum Val {
     /// No value defined
     #[default]
@@ -108,6 +108,18 @@ pub struct Style {
     pub aspect_ratio: Option<f32>,
     /// How to handle overflow
     pub overflow: Overflow,
+    /// The columns of a [`Display::Grid`] node, from left to right
+    pub grid_template_columns: Vec<GridTrack>,
+    /// The rows of a [`Display::Grid`] node, from top to bottom
+    ///
+    /// Rows are added with a size of [`GridTrack::Auto`] when the children don't fit in them.
+    pub grid_template_rows: Vec<GridTrack>,
+    /// The space between the columns (width) and the rows (height) of a [`Display::Grid`] node
+    pub gap: Size<Val>,
+    /// Where to place this node along the columns of a [`Display::Grid`] parent
+    pub grid_column: GridPlacement,
+    /// Where to place this node along the rows of a [`Display::Grid`] parent
+    pub grid_row: GridPlacement,
 }
 
 impl Default for Style {
@@ -134,6 +146,11 @@ impl Default for Style {
             max_size: Size::new(Val::Auto, Val::Auto),
             aspect_ratio: Default::default(),
             overflow: Default::default(),
+            grid_template_columns: Vec::new(),
+            grid_template_rows: Vec::new(),
+            gap: Size::new(Val::Px(0.0), Val::Px(0.0)),
+            grid_column: Default::default(),
+            grid_row: Default::default(),
         }
     }
 }
@@ -221,6 +238,77 @@ pub enum Display {
     Flex,
     /// Use no layout, don't render this node and its children
     None,
+    /// Arrange the children of this node in the rows and columns of a grid, see
+    /// [`Style::grid_template_columns`]
+    ///
+    /// Each child is laid out in its cell as in a flexbox row with the `justify_content` and
+    /// `align_items` of this node.
+    Grid,
+}
+
+/// The size of a row or a column of a [`Display::Grid`] node
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect, FromReflect)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum GridTrack {
+    /// A size in logical pixels
+    Px(f32),
+    /// A percentage of the size of the content of the grid node
+    Percent(f32),
+    /// The size of the largest item spanning only this track
+    Auto,
+    /// A share of the space left by the other tracks, proportional to the given factor
+    ///
+    /// If the grid node is sized by its content, the track is sized as [`GridTrack::Auto`].
+    Fr(f32),
+}
+
+impl GridTrack {
+    /// Returns `count` copies of `track`
+    pub fn repeat(count: usize, track: GridTrack) -> Vec<GridTrack> {
+        vec![track; count]
+    }
+}
+
+/// Where a child of a [`Display::Grid`] node is placed along a row or a column of the grid
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, Reflect)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub struct GridPlacement {
+    /// The index of the first track occupied by the child, starting from 0, or `None` to place
+    /// it in the next free cell
+    pub start: Option<usize>,
+    /// The number of tracks occupied by the child
+    pub span: usize,
+}
+
+impl GridPlacement {
+    /// Places the child in the next free cell
+    pub const AUTO: Self = Self {
+        start: None,
+        span: 1,
+    };
+
+    /// Places the child starting at the given track
+    pub const fn start(start: usize) -> Self {
+        Self {
+            start: Some(start),
+            span: 1,
+        }
+    }
+
+    /// Makes the child occupy the given number of tracks
+    #[must_use]
+    pub const fn with_span(self, span: usize) -> Self {
+        Self {
+            start: self.start,
+            span,
+        }
+    }
+}
+
+impl Default for GridPlacement {
+    fn default() -> Self {
+        Self::AUTO
+    }
 }
 
 /// Defines how flexbox items are ordered within a flexbox
@@ -266,6 +354,40 @@ pub enum Overflow {
     Visible,
     /// Hide overflowing items
     Hidden,
+    /// Hide overflowing items, which can be scrolled into view by changing the
+    /// [`ScrollPosition`] of the node
+    Scroll,
+}
+
+/// The offset of the content of a node with [`Overflow::Scroll`], in logical pixels
+///
+/// A positive `x` moves the content to the left and a positive `y` moves it up, revealing what
+/// overflows below the node. The offset is clamped so that the content can't be scrolled past
+/// its edges when the layout is updated.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct ScrollPosition {
+    pub offset: Vec2,
+}
+
+/// The stacking order of a node, relative to the other nodes
+///
+/// By default, nodes are drawn above their parent and above their previous siblings.
+#[derive(Component, Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, Reflect)]
+#[reflect_value(Component, PartialEq, Serialize, Deserialize)]
+pub enum ZIndex {
+    /// Orders the node among its siblings, nodes with a higher index are drawn above. Siblings
+    /// with the same index keep the order of the hierarchy.
+    Local(i32),
+    /// Takes the node and its descendants out of the order of its hierarchy and orders it among
+    /// the root nodes, which have an index of 0. Nodes with a higher index are drawn above.
+    Global(i32),
+}
+
+impl Default for ZIndex {
+    fn default() -> Self {
+        Self::Local(0)
+    }
 }
 
 /// The strategy used to position this node
@@ -313,6 +435,70 @@ impl From<Color> for UiColor {
     }
 }
 
+/// The color of the border of the node
+///
+/// The border is drawn with the widths given by [`Style::border`]. Only widths in
+/// [`Val::Px`] are drawn. The default color is transparent, drawing no border.
+#[derive(Component, Copy, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct BorderColor(pub Color);
+
+impl Default for BorderColor {
+    fn default() -> Self {
+        Self(Color::NONE)
+    }
+}
+
+impl From<Color> for BorderColor {
+    fn from(color: Color) -> Self {
+        Self(color)
+    }
+}
+
+/// The radius of each corner of the node, in logical pixels
+///
+/// The node, its border and, unless it has [`Overflow::Visible`], its children are clipped to
+/// the rounded corners.
+#[derive(Component, Default, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct BorderRadius {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl BorderRadius {
+    pub const ZERO: Self = Self::all(0.0);
+
+    /// Creates a new border radius with the same radius for every corner
+    pub const fn all(radius: f32) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+
+    /// Returns the radii clamped to fit in a node of the given size
+    #[must_use]
+    pub fn clamped(self, size: Vec2) -> Self {
+        let max = size.min_element().max(0.0) / 2.0;
+        Self {
+            top_left: self.top_left.clamp(0.0, max),
+            top_right: self.top_right.clamp(0.0, max),
+            bottom_right: self.bottom_right.clamp(0.0, max),
+            bottom_left: self.bottom_left.clamp(0.0, max),
+        }
+    }
+
+    /// Returns `true` if no corner is rounded
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+}
+
 /// The image of the node
 #[derive(Component, Clone, Debug, Reflect, Deref, DerefMut)]
 #[reflect(Component, Default)]
@@ -336,4 +522,6 @@ impl From<Handle<Image>> for UiImage {
 pub struct CalculatedClip {
     /// The rect of the clip
     pub clip: bevy_sprite::Rect,
+    /// The rounded corners of the clip, in logical pixels
+    pub radius: BorderRadius,
 }
@@ -0,0 +1,129 @@
+use crate::{Node, WorldUi};
+use bevy_ecs::{
+    entity::Entity,
+    prelude::Component,
+    query::{With, Without},
+    system::{Query, ResMut},
+};
+use bevy_hierarchy::{Children, Parent};
+use bevy_utils::HashMap;
+use bevy_window::{WindowId, Windows};
+
+/// Displays the UI hierarchy of a root node in the given window rather than in the primary
+/// window.
+///
+/// The hierarchy is laid out in the size of the window, its text is rendered with the scale
+/// factor of the window, it is drawn by the cameras rendering to the window and it interacts
+/// with the cursor of the window. This has no effect on nodes that have a parent or a
+/// [`WorldUi`](crate::WorldUi).
+#[derive(Component, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct UiWindow(pub WindowId);
+
+/// The window displaying each UI node, see [`UiWindow`].
+#[derive(Debug, Default)]
+pub struct UiWindowNodes {
+    /// The window of each node that isn't displayed in the primary window
+    windows: HashMap<Entity, WindowId>,
+}
+
+impl UiWindowNodes {
+    /// Returns the window displaying the node, which is the primary window unless the root of
+    /// its hierarchy has a [`UiWindow`].
+    pub fn window(&self, node: Entity) -> WindowId {
+        self.windows
+            .get(&node)
+            .copied()
+            .unwrap_or_else(WindowId::primary)
+    }
+
+    /// Returns the scale factor of the window displaying the node, or `1.0` if it is not open.
+    pub fn scale_factor(&self, windows: &Windows, node: Entity) -> f64 {
+        windows.scale_factor(self.window(node))
+    }
+}
+
+/// Finds the nodes in the hierarchies of the root nodes with a [`UiWindow`].
+pub fn update_ui_window_nodes(
+    mut ui_window_nodes: ResMut<UiWindowNodes>,
+    root_query: Query<(Entity, &UiWindow), (With<Node>, Without<Parent>, Without<WorldUi>)>,
+    children_query: Query<&Children, With<Node>>,
+) {
+    if root_query.is_empty() && ui_window_nodes.windows.is_empty() {
+        return;
+    }
+    let mut windows = HashMap::default();
+    for (root, ui_window) in root_query.iter() {
+        if ui_window.0 == WindowId::primary() {
+            continue;
+        }
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            windows.insert(node, ui_window.0);
+            if let Ok(children) = children_query.get(node) {
+                stack.extend(children.iter());
+            }
+        }
+    }
+    // only trigger change detection when a node moved to another window, so that the layout is
+    // only recomputed in the new scale factor when needed
+    if ui_window_nodes.windows != windows {
+        ui_window_nodes.windows = windows;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        schedule::{Schedule, Stage, SystemStage},
+        system::{CommandQueue, Commands},
+        world::World,
+    };
+    use bevy_hierarchy::BuildChildren;
+    use bevy_window::WindowId;
+
+    use crate::Node;
+
+    use super::{update_ui_window_nodes, UiWindow, UiWindowNodes};
+
+    #[test]
+    fn ui_window_is_inherited_from_the_root() {
+        let mut world = World::default();
+        world.init_resource::<UiWindowNodes>();
+        let secondary_window = WindowId::new();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let primary_root = commands.spawn_bundle((Node::default(),)).id();
+        let mut secondary_child = None;
+        let secondary_root = commands
+            .spawn_bundle((Node::default(), UiWindow(secondary_window)))
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle((Node::default(),))
+                    .with_children(|parent| {
+                        secondary_child = Some(
+                            parent
+                                // Only the window of the root is used
+                                .spawn_bundle((Node::default(), UiWindow(WindowId::primary())))
+                                .id(),
+                        );
+                    });
+            })
+            .id();
+        queue.apply(&mut world);
+
+        let mut schedule = Schedule::default();
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(update_ui_window_nodes);
+        schedule.add_stage("update", update_stage);
+        schedule.run(&mut world);
+
+        let ui_window_nodes = world.resource::<UiWindowNodes>();
+        assert_eq!(ui_window_nodes.window(primary_root), WindowId::primary());
+        assert_eq!(ui_window_nodes.window(secondary_root), secondary_window);
+        assert_eq!(
+            ui_window_nodes.window(secondary_child.unwrap()),
+            secondary_window
+        );
+    }
+}
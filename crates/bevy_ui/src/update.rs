@@ -1,6 +1,6 @@
 //! This module contains systems that update the UI when something changes
 
-use crate::{CalculatedClip, Overflow, Style};
+use crate::{BorderRadius, CalculatedClip, Overflow, Style, ZIndex};
 
 use super::Node;
 use bevy_ecs::{
@@ -12,63 +12,113 @@ use bevy_hierarchy::{Children, Parent};
 use bevy_math::Vec2;
 use bevy_sprite::Rect;
 use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::HashMap;
 
 /// The resolution of Z values for UI
 pub const UI_Z_STEP: f32 = 0.001;
 
 /// Updates transforms of nodes to fit with the z system
+///
+/// Nodes are stacked in the order of their hierarchy, reordered by their [`ZIndex`].
 pub fn ui_z_system(
-    root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
+    root_node_query: Query<(Entity, Option<&ZIndex>), (With<Node>, Without<Parent>)>,
+    global_node_query: Query<(Entity, &ZIndex), (With<Node>, With<Parent>)>,
     mut node_query: Query<&mut Transform, With<Node>>,
     children_query: Query<&Children>,
+    z_index_query: Query<&ZIndex>,
+    parent_query: Query<&Parent>,
 ) {
+    // Root nodes and nodes with a global z-index each start a stack
+    let mut stacks: Vec<(i32, Entity)> = root_node_query
+        .iter()
+        .map(|(entity, z_index)| match z_index {
+            Some(ZIndex::Global(index)) => (*index, entity),
+            _ => (0, entity),
+        })
+        .chain(
+            global_node_query
+                .iter()
+                .filter_map(|(entity, z_index)| match z_index {
+                    ZIndex::Global(index) => Some((*index, entity)),
+                    ZIndex::Local(_) => None,
+                }),
+        )
+        .collect();
+    // The sort is stable, keeping the order of root nodes with the same index
+    stacks.sort_by_key(|(index, _)| *index);
+
+    let mut global_z = HashMap::default();
     let mut current_global_z = 0.0;
-    for entity in root_node_query.iter() {
-        current_global_z = update_hierarchy(
+    for (_, entity) in stacks {
+        stack_hierarchy(
             &children_query,
-            &mut node_query,
+            &z_index_query,
             entity,
-            current_global_z,
-            current_global_z,
+            &mut global_z,
+            &mut current_global_z,
         );
     }
+
+    for (entity, z) in global_z.iter() {
+        if let Ok(mut transform) = node_query.get_mut(*entity) {
+            let parent_z = parent_query
+                .get(*entity)
+                .ok()
+                .and_then(|parent| global_z.get(&parent.0))
+                .copied()
+                .unwrap_or(0.0);
+            let new_z = z - parent_z;
+            // only trigger change detection when the new value is different
+            if transform.translation.z != new_z {
+                transform.translation.z = new_z;
+            }
+        }
+    }
 }
 
-fn update_hierarchy(
+fn stack_hierarchy(
     children_query: &Query<&Children>,
-    node_query: &mut Query<&mut Transform, With<Node>>,
+    z_index_query: &Query<&ZIndex>,
     entity: Entity,
-    parent_global_z: f32,
-    mut current_global_z: f32,
-) -> f32 {
-    current_global_z += UI_Z_STEP;
-    if let Ok(mut transform) = node_query.get_mut(entity) {
-        let new_z = current_global_z - parent_global_z;
-        // only trigger change detection when the new value is different
-        if transform.translation.z != new_z {
-            transform.translation.z = new_z;
-        }
-    }
+    global_z: &mut HashMap<Entity, f32>,
+    current_global_z: &mut f32,
+) {
+    *current_global_z += UI_Z_STEP;
+    global_z.insert(entity, *current_global_z);
     if let Ok(children) = children_query.get(entity) {
-        let current_parent_global_z = current_global_z;
-        for child in children.iter().cloned() {
-            current_global_z = update_hierarchy(
+        // Children with a global z-index are stacked on their own
+        let mut children: Vec<(i32, Entity)> = children
+            .iter()
+            .filter_map(|child| match z_index_query.get(*child) {
+                Ok(ZIndex::Global(_)) => None,
+                Ok(ZIndex::Local(index)) => Some((*index, *child)),
+                Err(_) => Some((0, *child)),
+            })
+            .collect();
+        children.sort_by_key(|(index, _)| *index);
+        for (_, child) in children {
+            stack_hierarchy(
                 children_query,
-                node_query,
+                z_index_query,
                 child,
-                current_parent_global_z,
+                global_z,
                 current_global_z,
             );
         }
     }
-    current_global_z
 }
 
 /// Updates clipping for all nodes
 pub fn update_clipping_system(
     mut commands: Commands,
     root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
-    mut node_query: Query<(&Node, &GlobalTransform, &Style, Option<&mut CalculatedClip>)>,
+    mut node_query: Query<(
+        &Node,
+        &GlobalTransform,
+        &Style,
+        Option<&BorderRadius>,
+        Option<&mut CalculatedClip>,
+    )>,
     children_query: Query<&Children>,
 ) {
     for root_node in root_node_query.iter() {
@@ -85,11 +135,18 @@ pub fn update_clipping_system(
 fn update_clipping(
     commands: &mut Commands,
     children_query: &Query<&Children>,
-    node_query: &mut Query<(&Node, &GlobalTransform, &Style, Option<&mut CalculatedClip>)>,
+    node_query: &mut Query<(
+        &Node,
+        &GlobalTransform,
+        &Style,
+        Option<&BorderRadius>,
+        Option<&mut CalculatedClip>,
+    )>,
     entity: Entity,
-    clip: Option<Rect>,
+    clip: Option<CalculatedClip>,
 ) {
-    let (node, global_transform, style, calculated_clip) = node_query.get_mut(entity).unwrap();
+    let (node, global_transform, style, border_radius, calculated_clip) =
+        node_query.get_mut(entity).unwrap();
     // Update this node's CalculatedClip component
     match (clip, calculated_clip) {
         (None, None) => {}
@@ -97,29 +154,44 @@ fn update_clipping(
             commands.entity(entity).remove::<CalculatedClip>();
         }
         (Some(clip), None) => {
-            commands.entity(entity).insert(CalculatedClip { clip });
+            commands.entity(entity).insert(clip);
         }
         (Some(clip), Some(mut old_clip)) => {
-            *old_clip = CalculatedClip { clip };
+            *old_clip = clip;
         }
     }
 
     // Calculate new clip for its children
     let children_clip = match style.overflow {
         Overflow::Visible => clip,
-        Overflow::Hidden => {
+        Overflow::Hidden | Overflow::Scroll => {
             let node_center = global_transform.translation.truncate();
             let node_rect = Rect {
                 min: node_center - node.size / 2.,
                 max: node_center + node.size / 2.,
             };
+            let node_radius =
+                border_radius.map_or(BorderRadius::ZERO, |radius| radius.clamped(node.size));
             if let Some(clip) = clip {
-                Some(Rect {
-                    min: Vec2::max(clip.min, node_rect.min),
-                    max: Vec2::min(clip.max, node_rect.max),
-                })
+                let rect = Rect {
+                    min: Vec2::max(clip.clip.min, node_rect.min),
+                    max: Vec2::min(clip.clip.max, node_rect.max),
+                };
+                // Keep the corners of whichever rect the clip matches, an intersection of rounded
+                // rects can't be represented exactly
+                let radius = if rect == node_rect {
+                    node_radius
+                } else if rect == clip.clip {
+                    clip.radius
+                } else {
+                    BorderRadius::ZERO
+                };
+                Some(CalculatedClip { clip: rect, radius })
             } else {
-                Some(node_rect)
+                Some(CalculatedClip {
+                    clip: node_rect,
+                    radius: node_radius,
+                })
             }
         }
     };
@@ -142,7 +214,7 @@ mod tests {
     use bevy_hierarchy::BuildChildren;
     use bevy_transform::components::Transform;
 
-    use crate::Node;
+    use crate::{Node, ZIndex};
 
     use super::{ui_z_system, UI_Z_STEP};
 
@@ -220,7 +292,7 @@ mod tests {
         actual_result.sort_unstable_by_key(|(name, _)| name.0);
         let expected_result = vec![
             (Label("0"), 1),
-            (Label("1"), 1),
+            (Label("1"), 2),
             (Label("1-0"), 1),
             (Label("1-0-0"), 1),
             // 1-0-1 has no transform
@@ -239,4 +311,56 @@ mod tests {
         ];
         assert_eq!(actual_result, expected_result);
     }
+
+    #[test]
+    fn test_ui_z_system_with_z_index() {
+        let mut world = World::default();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands
+            .spawn_bundle(node_with_transform("a"))
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(node_with_transform("a-0"))
+                    .insert(ZIndex::Local(1));
+                parent.spawn_bundle(node_with_transform("a-1"));
+                parent
+                    .spawn_bundle(node_with_transform("a-2"))
+                    .insert(ZIndex::Global(1))
+                    .with_children(|parent| {
+                        parent.spawn_bundle(node_with_transform("a-2-0"));
+                    });
+            });
+        commands
+            .spawn_bundle(node_with_transform("b"))
+            .with_children(|parent| {
+                parent.spawn_bundle(node_with_transform("b-0"));
+            });
+        queue.apply(&mut world);
+
+        let mut schedule = Schedule::default();
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(ui_z_system);
+        schedule.add_stage("update", update_stage);
+        schedule.run(&mut world);
+
+        let mut actual_result = world
+            .query::<(&Label, &Transform)>()
+            .iter(&world)
+            .map(|(name, transform)| (name.clone(), get_steps(transform)))
+            .collect::<Vec<(Label, u32)>>();
+        actual_result.sort_unstable_by_key(|(name, _)| name.0);
+        let expected_result = vec![
+            (Label("a"), 1),
+            // a-0 is stacked above its later sibling a-1
+            (Label("a-0"), 2),
+            (Label("a-1"), 1),
+            // a-2 is stacked above the hierarchy of b
+            (Label("a-2"), 5),
+            (Label("a-2-0"), 1),
+            (Label("b"), 4),
+            (Label("b-0"), 1),
+        ];
+        assert_eq!(actual_result, expected_result);
+    }
 }
@@ -18,6 +18,48 @@ pub enum ImageMode {
     /// Keep the aspect ratio of the image
     #[default]
     KeepAspect,
+    /// Split the image in nine slices along the given borders, and stretch them to fill the node
+    ///
+    /// The corners keep their size, the edges are stretched along their length and the center is
+    /// stretched in both directions. If the node is smaller than its borders, they are scaled
+    /// down to fit in it.
+    ///
+    /// This mode can also be inserted on nodes that aren't [`ImageBundle`](crate::entity::ImageBundle)s,
+    /// such as buttons, which are sized by their [`Style`](crate::Style).
+    Sliced(BorderRect),
+}
+
+/// The size of the borders of a [`ImageMode::Sliced`] image, in pixels of the image
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct BorderRect {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl BorderRect {
+    /// Creates a new border with the same size on every side
+    pub const fn all(size: f32) -> Self {
+        Self {
+            left: size,
+            right: size,
+            top: size,
+            bottom: size,
+        }
+    }
+
+    /// Creates a new border with the given horizontal size on the left and right sides and the
+    /// given vertical size on the top and bottom sides
+    pub const fn axes(horizontal: f32, vertical: f32) -> Self {
+        Self {
+            left: horizontal,
+            right: horizontal,
+            top: vertical,
+            bottom: vertical,
+        }
+    }
 }
 
 /// Updates calculated size of the node based on the image provided
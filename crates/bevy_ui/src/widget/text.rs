@@ -1,15 +1,41 @@
-use crate::{CalculatedSize, Size, Style, Val};
-use bevy_asset::Assets;
+use crate::{CalculatedSize, Size, Style, UiWindowNodes, Val};
+use bevy_asset::{AssetEvent, Assets};
 use bevy_ecs::{
+    component::Component,
     entity::Entity,
+    event::EventReader,
     query::{Changed, Or, With},
+    reflect::ReflectComponent,
     system::{Local, ParamSet, Query, Res, ResMut},
 };
 use bevy_math::Vec2;
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 use bevy_render::texture::Image;
 use bevy_sprite::TextureAtlas;
-use bevy_text::{DefaultTextPipeline, Font, FontAtlasSet, Text, TextError};
+use bevy_text::{
+    removed_texture_atlases, DefaultTextPipeline, Font, FontAtlasSet, FontAtlasSettings, Text,
+    TextError, TextLayoutInfo, TextSection,
+};
+use bevy_utils::HashMap;
 use bevy_window::{WindowId, Windows};
+use serde::{Deserialize, Serialize};
+
+/// Describes what happens to text that doesn't fit inside the bounds of its node
+#[derive(
+    Component, Copy, Clone, PartialEq, Eq, Debug, Default, Reflect, Serialize, Deserialize,
+)]
+#[reflect_value(Component, PartialEq, Serialize, Deserialize)]
+pub enum TextOverflow {
+    /// Lines that don't fit inside the node are not drawn
+    #[default]
+    Clip,
+    /// The text is truncated so that it fits inside the node, and the final line ends with "…"
+    Ellipsis,
+    /// All of the text is drawn, even if it extends past the node
+    Visible,
+}
+
+const ELLIPSIS: char = '…';
 
 #[derive(Debug, Default)]
 pub struct QueuedText {
@@ -39,35 +65,57 @@ pub fn text_constraint(min_size: Val, size: Val, max_size: Val, scale_factor: f6
 #[allow(clippy::too_many_arguments)]
 pub fn text_system(
     mut queued_text: Local<QueuedText>,
-    mut last_scale_factor: Local<f64>,
+    mut last_scale_factors: Local<HashMap<WindowId, f64>>,
     mut textures: ResMut<Assets<Image>>,
     fonts: Res<Assets<Font>>,
     windows: Res<Windows>,
+    ui_window_nodes: Res<UiWindowNodes>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
     mut font_atlas_set_storage: ResMut<Assets<FontAtlasSet>>,
+    font_atlas_settings: Res<FontAtlasSettings>,
+    mut texture_atlas_events: EventReader<AssetEvent<TextureAtlas>>,
     mut text_pipeline: ResMut<DefaultTextPipeline>,
     mut text_queries: ParamSet<(
-        Query<Entity, Or<(Changed<Text>, Changed<Style>)>>,
+        Query<Entity, Or<(Changed<Text>, Changed<Style>, Changed<TextOverflow>)>>,
         Query<Entity, (With<Text>, With<Style>)>,
-        Query<(&Text, &Style, &mut CalculatedSize)>,
+        Query<(
+            &Text,
+            &Style,
+            Option<&TextOverflow>,
+            &mut CalculatedSize,
+            Option<&mut TextLayoutInfo>,
+        )>,
     )>,
 ) {
-    let scale_factor = windows.scale_factor(WindowId::primary());
-
-    let inv_scale_factor = 1. / scale_factor;
+    // Each text is laid out in the scale factor of the window displaying it
+    let scale_factors_changed = windows
+        .iter()
+        .any(|window| last_scale_factors.get(&window.id()) != Some(&window.scale_factor()));
 
-    #[allow(clippy::float_cmp)]
-    if *last_scale_factor == scale_factor {
+    if !scale_factors_changed && !ui_window_nodes.is_changed() {
         // Adds all entities where the text or the style has changed to the local queue
         for entity in text_queries.p0().iter() {
             queued_text.entities.push(entity);
         }
     } else {
-        // If the scale factor has changed, queue all text
+        // If a scale factor has changed, or text moved to another window, queue all text
         for entity in text_queries.p1().iter() {
             queued_text.entities.push(entity);
         }
-        *last_scale_factor = scale_factor;
+        *last_scale_factors = windows
+            .iter()
+            .map(|window| (window.id(), window.scale_factor()))
+            .collect();
+    }
+
+    // Queue text with glyphs in atlases that were evicted from their font atlas set
+    let removed_atlases = removed_texture_atlases(&mut texture_atlas_events);
+    if !removed_atlases.is_empty() {
+        for entity in text_queries.p1().iter() {
+            if text_pipeline.uses_texture_atlases(&entity, &removed_atlases) {
+                queued_text.entities.push(entity);
+            }
+        }
     }
 
     if queued_text.entities.is_empty() {
@@ -78,8 +126,11 @@ pub fn text_system(
     let mut new_queue = Vec::new();
     let mut query = text_queries.p2();
     for entity in queued_text.entities.drain(..) {
-        if let Ok((text, style, mut calculated_size)) = query.get_mut(entity) {
-            let node_size = Vec2::new(
+        if let Ok((text, style, overflow, mut calculated_size, layout_info)) = query.get_mut(entity)
+        {
+            let scale_factor = ui_window_nodes.scale_factor(&windows, entity);
+            let inv_scale_factor = 1. / scale_factor;
+            let mut node_size = Vec2::new(
                 text_constraint(
                     style.min_size.width,
                     style.size.width,
@@ -94,16 +145,37 @@ pub fn text_system(
                 ),
             );
 
+            let truncated_sections = match overflow.copied().unwrap_or_default() {
+                TextOverflow::Clip => Ok(None),
+                TextOverflow::Ellipsis => {
+                    ellipsize_sections(&mut text_pipeline, &fonts, text, scale_factor, node_size)
+                }
+                TextOverflow::Visible => {
+                    node_size.y = f32::MAX;
+                    Ok(None)
+                }
+            };
+            let sections = match &truncated_sections {
+                Ok(Some(sections)) => sections,
+                Ok(None) => &text.sections,
+                Err(_) => {
+                    new_queue.push(entity);
+                    continue;
+                }
+            };
+
             match text_pipeline.queue_text(
                 entity,
                 &fonts,
-                &text.sections,
+                sections,
                 scale_factor,
                 text.alignment,
+                text.direction,
                 node_size,
                 &mut *font_atlas_set_storage,
                 &mut *texture_atlases,
                 &mut *textures,
+                &font_atlas_settings,
             ) {
                 Err(TextError::NoSuchFont) => {
                     // There was an error processing the text layout, let's add this entity to the
@@ -121,6 +193,9 @@ pub fn text_system(
                         width: scale_value(text_layout_info.size.x, inv_scale_factor),
                         height: scale_value(text_layout_info.size.y, inv_scale_factor),
                     };
+                    if let Some(mut layout_info) = layout_info {
+                        *layout_info = text_layout_info.clone();
+                    }
                 }
             }
         }
@@ -128,3 +203,132 @@ pub fn text_system(
 
     queued_text.entities = new_queue;
 }
+
+/// If the given text doesn't fit inside `bounds`, returns its sections truncated to the longest
+/// prefix that fits when followed by an ellipsis.
+fn ellipsize_sections(
+    text_pipeline: &mut DefaultTextPipeline,
+    fonts: &Assets<Font>,
+    text: &Text,
+    scale_factor: f64,
+    bounds: Vec2,
+) -> Result<Option<Vec<TextSection>>, TextError> {
+    let mut fits = |sections: Vec<TextSection>| -> Result<bool, TextError> {
+        let candidate = Text {
+            sections,
+            alignment: text.alignment,
+            direction: text.direction,
+        };
+        let info = text_pipeline.measure_text(
+            fonts,
+            &candidate,
+            scale_factor,
+            Vec2::new(bounds.x, f32::MAX),
+        )?;
+        Ok(info.size.x <= bounds.x && info.size.y <= bounds.y)
+    };
+
+    if fits(text.sections.clone())? {
+        return Ok(None);
+    }
+
+    // Binary search for the largest number of characters that still fits with the ellipsis
+    let char_count: usize = text.sections.iter().map(|s| s.value.chars().count()).sum();
+    let (mut low, mut high) = (0, char_count);
+    while low < high {
+        let mid = (low + high + 1) / 2;
+        if fits(truncate_sections(&text.sections, mid))? {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    Ok(Some(truncate_sections(&text.sections, low)))
+}
+
+/// Keeps the first `char_count` characters of `sections` and appends an ellipsis to the last
+/// section that is kept.
+fn truncate_sections(sections: &[TextSection], char_count: usize) -> Vec<TextSection> {
+    let mut remaining = char_count;
+    let mut truncated: Vec<TextSection> = Vec::new();
+    for section in sections {
+        if remaining == 0 {
+            break;
+        }
+        let value: String = section.value.chars().take(remaining).collect();
+        remaining -= value.chars().count();
+        truncated.push(TextSection {
+            value,
+            style: section.style.clone(),
+        });
+    }
+
+    match truncated.last_mut() {
+        Some(last) => {
+            last.value.truncate(last.value.trim_end().len());
+            last.value.push(ELLIPSIS);
+        }
+        None => {
+            if let Some(first) = sections.first() {
+                truncated.push(TextSection {
+                    value: ELLIPSIS.to_string(),
+                    style: first.style.clone(),
+                });
+            }
+        }
+    }
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_render::color::Color;
+    use bevy_text::TextStyle;
+
+    fn section(value: &str, color: Color) -> TextSection {
+        TextSection {
+            value: value.to_string(),
+            style: TextStyle {
+                color,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn values(sections: &[TextSection]) -> Vec<&str> {
+        sections
+            .iter()
+            .map(|section| section.value.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn truncated_sections_end_with_an_ellipsis() {
+        let sections = [section("hello ", Color::RED), section("wörld", Color::BLUE)];
+
+        assert_eq!(values(&truncate_sections(&sections, 3)), ["hel…"]);
+        // Trailing whitespace is replaced by the ellipsis
+        assert_eq!(values(&truncate_sections(&sections, 6)), ["hello…"]);
+        // Characters are counted, not bytes
+        let truncated = truncate_sections(&sections, 8);
+        assert_eq!(values(&truncated), ["hello ", "wö…"]);
+        assert_eq!(truncated[0].style.color, Color::RED);
+        assert_eq!(truncated[1].style.color, Color::BLUE);
+        assert_eq!(
+            values(&truncate_sections(&sections, 11)),
+            ["hello ", "wörld…"]
+        );
+    }
+
+    #[test]
+    fn nothing_kept_leaves_only_the_ellipsis() {
+        let sections = [section("hello", Color::RED), section("world", Color::BLUE)];
+        let truncated = truncate_sections(&sections, 0);
+        assert_eq!(values(&truncated), ["…"]);
+        assert_eq!(truncated[0].style.color, Color::RED);
+
+        assert!(truncate_sections(&[], 0).is_empty());
+    }
+}
@@ -0,0 +1,963 @@
+use crate::{Focus, Interaction, Node, UiWindowNodes};
+use bevy_ecs::{
+    entity::Entity,
+    event::{EventReader, EventWriter},
+    prelude::Component,
+    reflect::ReflectComponent,
+    system::{Query, Res, ResMut},
+};
+use bevy_input::{
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::MouseButton,
+    ButtonState, Input,
+};
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::color::Color;
+use bevy_text::{Text, TextLayoutInfo, TextSection};
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{Ime, ReceivedCharacter, Windows};
+
+/// An editable text field, see [`TextInputBundle`](crate::entity::TextInputBundle).
+///
+/// The edited value is the [`Text`] of the entity. Edits replace its sections with a single
+/// section that has the style of the first one.
+///
+/// Text entered through an input method editor (IME) is inserted once it is committed. The text
+/// being composed is stored in [`TextInput::ime_preedit`] and displayed underlined at the caret,
+/// as part of the [`Text`], until then. Use [`TextInput::value`] to get the value without it.
+/// The IME candidate box is placed at the caret of the focused field.
+///
+/// The field receives keyboard input while it has the [`Focus`]. Clicking on a field focuses it,
+/// clicking anywhere else unfocuses it.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct TextInput {
+    /// The byte index of the caret in the value of the text.
+    pub cursor: usize,
+    /// The byte index of the other end of the selection. Text is selected if this is set and
+    /// differs from `cursor`.
+    pub selection_anchor: Option<usize>,
+    /// If `true`, pressing enter inserts a line break instead of sending a
+    /// [`TextInputSubmitted`] event.
+    pub multiline: bool,
+    /// The color of the caret, drawn while the field is focused.
+    pub caret_color: Color,
+    /// The width of the caret in logical pixels.
+    pub caret_width: f32,
+    /// The color drawn over selected text.
+    pub selection_color: Color,
+    /// The text being composed with an input method editor while this field is focused, which
+    /// is inserted at the caret when it is committed.
+    pub ime_preedit: String,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self {
+            cursor: 0,
+            selection_anchor: None,
+            multiline: false,
+            caret_color: Color::WHITE,
+            caret_width: 2.0,
+            selection_color: Color::rgba(0.3, 0.5, 1.0, 0.4),
+            ime_preedit: String::new(),
+        }
+    }
+}
+
+impl TextInput {
+    /// Returns the edited value, which is the value of `text` without the displayed
+    /// [`TextInput::ime_preedit`].
+    pub fn value(&self, text: &Text) -> String {
+        let mut value = text.value();
+        self.remove_preedit(&mut value);
+        value
+    }
+
+    /// Returns the byte range of the [`TextInput::ime_preedit`] displayed in the [`Text`], if
+    /// any text is being composed.
+    pub fn preedit_range(&self) -> Option<(usize, usize)> {
+        Some((self.cursor, self.cursor + self.ime_preedit.len()))
+            .filter(|_| !self.ime_preedit.is_empty())
+    }
+
+    /// Converts a byte index in the value to a byte index in the displayed text, which also
+    /// contains the preedit.
+    pub fn displayed_index(&self, index: usize) -> usize {
+        match self.preedit_range() {
+            Some((start, end)) if index >= start => index + (end - start),
+            _ => index,
+        }
+    }
+
+    /// Converts a byte index in the displayed text to a byte index in the value, moving the
+    /// indices inside of the preedit to its start.
+    pub fn value_index(&self, displayed_index: usize) -> usize {
+        let index = displayed_index;
+        match self.preedit_range() {
+            Some((start, end)) if index >= end => index - (end - start),
+            Some((start, _)) if index > start => start,
+            _ => index,
+        }
+    }
+
+    /// Removes the displayed preedit from `value`, if it is still there.
+    fn remove_preedit(&self, value: &mut String) {
+        if let Some((start, end)) = self.preedit_range() {
+            if value.get(start..end) == Some(self.ime_preedit.as_str()) {
+                value.replace_range(start..end, "");
+            }
+        }
+    }
+
+    /// Returns `value` with the preedit inserted at the caret.
+    fn display(&self, value: &str) -> String {
+        let mut displayed = value.to_string();
+        if !self.ime_preedit.is_empty() {
+            displayed.insert_str(self.cursor, &self.ime_preedit);
+        }
+        displayed
+    }
+
+    /// Returns the byte range of the selected text, if any text is selected.
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        self.selection_anchor
+            .filter(|anchor| *anchor != self.cursor)
+            .map(|anchor| (anchor.min(self.cursor), anchor.max(self.cursor)))
+    }
+
+    /// Moves the caret to `cursor`, extending the selection if `select` is `true` and clearing
+    /// it otherwise.
+    fn move_cursor(&mut self, cursor: usize, select: bool) {
+        if select {
+            self.selection_anchor.get_or_insert(self.cursor);
+        } else {
+            self.selection_anchor = None;
+        }
+        self.cursor = cursor;
+    }
+
+    /// Replaces the selected text, or inserts at the caret if nothing is selected.
+    fn replace_selection(&mut self, value: &mut String, replacement: &str) {
+        let (start, end) = self.selection().unwrap_or((self.cursor, self.cursor));
+        value.replace_range(start..end, replacement);
+        self.cursor = start + replacement.len();
+        self.selection_anchor = None;
+    }
+}
+
+/// Sent when enter is pressed in a focused single line [`TextInput`].
+#[derive(Debug, Clone)]
+pub struct TextInputSubmitted {
+    pub entity: Entity,
+    pub value: String,
+}
+
+/// The clipboard that text is copied to, cut to and pasted from in [`TextInput`]s.
+///
+/// This is the clipboard of the system on Windows, macOS and Linux with X11. Where it isn't
+/// available, e.g. on the web or without a display server, the text is only shared between the
+/// text fields of the app.
+pub struct Clipboard {
+    #[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+    system: Option<arboard::Clipboard>,
+    contents: String,
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self {
+            #[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+            system: arboard::Clipboard::new()
+                .map_err(|error| bevy_log::warn!("The system clipboard is unavailable: {}", error))
+                .ok(),
+            contents: String::new(),
+        }
+    }
+}
+
+impl Clipboard {
+    /// Creates a clipboard that is only shared between the text fields of the app.
+    pub fn local() -> Self {
+        Self {
+            #[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+            system: None,
+            contents: String::new(),
+        }
+    }
+
+    /// Returns the text in the clipboard, which is empty if it doesn't contain text.
+    pub fn get(&mut self) -> String {
+        #[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+        if let Some(system) = &mut self.system {
+            return match system.get_text() {
+                Ok(contents) => contents,
+                Err(arboard::Error::ContentNotAvailable) => String::new(),
+                Err(error) => {
+                    bevy_log::warn!("Failed to paste from the system clipboard: {}", error);
+                    self.contents.clone()
+                }
+            };
+        }
+        self.contents.clone()
+    }
+
+    /// Puts `contents` in the clipboard.
+    pub fn set(&mut self, contents: impl Into<String>) {
+        self.contents = contents.into();
+        #[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+        if let Some(system) = &mut self.system {
+            if let Err(error) = system.set_text(self.contents.as_str()) {
+                bevy_log::warn!("Failed to copy to the system clipboard: {}", error);
+            }
+        }
+    }
+}
+
+fn previous_boundary(value: &str, index: usize) -> usize {
+    value[..index]
+        .char_indices()
+        .next_back()
+        .map_or(0, |(index, _)| index)
+}
+
+fn next_boundary(value: &str, index: usize) -> usize {
+    value[index..]
+        .chars()
+        .next()
+        .map_or(index, |c| index + c.len_utf8())
+}
+
+/// Returns `true` for the keys that type a character, which is received as a
+/// [`ReceivedCharacter`] event after the key press. Keys without a [`KeyCode`] are assumed to
+/// type one, as they are usually the keys of non-English layouts.
+fn types_character(key: Option<KeyCode>) -> bool {
+    use KeyCode::*;
+    matches!(
+        key,
+        None | Some(
+            Key1 | Key2
+                | Key3
+                | Key4
+                | Key5
+                | Key6
+                | Key7
+                | Key8
+                | Key9
+                | Key0
+                | A
+                | B
+                | C
+                | D
+                | E
+                | F
+                | G
+                | H
+                | I
+                | J
+                | K
+                | L
+                | M
+                | N
+                | O
+                | P
+                | Q
+                | R
+                | S
+                | T
+                | U
+                | V
+                | W
+                | X
+                | Y
+                | Z
+                | Space
+                | Caret
+                | Numpad0
+                | Numpad1
+                | Numpad2
+                | Numpad3
+                | Numpad4
+                | Numpad5
+                | Numpad6
+                | Numpad7
+                | Numpad8
+                | Numpad9
+                | AbntC1
+                | AbntC2
+                | NumpadAdd
+                | NumpadComma
+                | NumpadDecimal
+                | NumpadDivide
+                | NumpadEquals
+                | NumpadMultiply
+                | NumpadSubtract
+                | Apostrophe
+                | Asterisk
+                | At
+                | Backslash
+                | Colon
+                | Comma
+                | Equals
+                | Grave
+                | LBracket
+                | Minus
+                | Oem102
+                | Period
+                | Plus
+                | RBracket
+                | Semicolon
+                | Slash
+                | Underline
+                | Yen
+        )
+    )
+}
+
+/// An edit of a [`TextInput`].
+#[derive(Debug, Clone, PartialEq)]
+enum Edit {
+    Key(KeyCode),
+    Insert(String),
+}
+
+/// Merges the pressed keys and the typed characters into the edits they make, in the order they
+/// were typed.
+///
+/// The characters are received separately from the keys, so each key that types a character is
+/// assumed to have typed the next one, unless it is pressed as a `shortcut`. Characters left
+/// over, e.g. by dead keys, are inserted after the keys.
+fn typed_edits(
+    keys: impl IntoIterator<Item = Option<KeyCode>>,
+    characters: impl IntoIterator<Item = char>,
+    shortcut: bool,
+) -> Vec<Edit> {
+    fn insert(edits: &mut Vec<Edit>, c: char) {
+        match edits.last_mut() {
+            Some(Edit::Insert(text)) => text.push(c),
+            _ => edits.push(Edit::Insert(c.to_string())),
+        }
+    }
+
+    let mut characters = characters.into_iter();
+    let mut edits = Vec::new();
+    for key in keys {
+        if !shortcut && types_character(key) {
+            if let Some(c) = characters.next() {
+                insert(&mut edits, c);
+            }
+        } else if let Some(key) = key {
+            edits.push(Edit::Key(key));
+        }
+    }
+    for c in characters {
+        insert(&mut edits, c);
+    }
+    edits
+}
+
+impl TextInput {
+    /// Applies `edit` to `value`, returning `true` if it submits the value.
+    fn edit(
+        &mut self,
+        value: &mut String,
+        edit: &Edit,
+        shift: bool,
+        control: bool,
+        layout: &TextLayoutInfo,
+        clipboard: &mut Clipboard,
+    ) -> bool {
+        let key = match edit {
+            Edit::Insert(text) => {
+                self.replace_selection(value, text);
+                return false;
+            }
+            Edit::Key(key) => *key,
+        };
+        match key {
+            KeyCode::Left => {
+                let cursor = match self.selection() {
+                    Some((start, _)) if !shift => start,
+                    _ => previous_boundary(value, self.cursor),
+                };
+                self.move_cursor(cursor, shift);
+            }
+            KeyCode::Right => {
+                let cursor = match self.selection() {
+                    Some((_, end)) if !shift => end,
+                    _ => next_boundary(value, self.cursor),
+                };
+                self.move_cursor(cursor, shift);
+            }
+            KeyCode::Home | KeyCode::End => {
+                let line = layout
+                    .caret_position(self.cursor)
+                    .and_then(|caret| layout.lines.get(caret.line_index));
+                let cursor = match (key, line) {
+                    (KeyCode::Home, Some(line)) => line.byte_range.start,
+                    (KeyCode::Home, None) => 0,
+                    (_, Some(line)) => line.byte_range.end.min(value.len()),
+                    (_, None) => value.len(),
+                };
+                // Don't place the caret after a trailing line break
+                let cursor = if key == KeyCode::End && value[..cursor].ends_with('\n') {
+                    cursor - 1
+                } else {
+                    cursor
+                };
+                self.move_cursor(cursor, shift);
+            }
+            KeyCode::Up | KeyCode::Down if self.multiline => {
+                if let Some(caret) = layout.caret_position(self.cursor) {
+                    let y = if key == KeyCode::Up {
+                        caret.position.y + caret.height * 1.5
+                    } else {
+                        caret.position.y - caret.height * 0.5
+                    };
+                    if let Some(cursor) = layout.hit_test(Vec2::new(caret.position.x, y)) {
+                        self.move_cursor(cursor.min(value.len()), shift);
+                    }
+                }
+            }
+            KeyCode::Back => {
+                if self.selection().is_none() {
+                    self.selection_anchor = Some(previous_boundary(value, self.cursor));
+                }
+                self.replace_selection(value, "");
+            }
+            KeyCode::Delete => {
+                if self.selection().is_none() {
+                    self.selection_anchor = Some(next_boundary(value, self.cursor));
+                }
+                self.replace_selection(value, "");
+            }
+            KeyCode::Return | KeyCode::NumpadEnter => {
+                if !self.multiline {
+                    return true;
+                }
+                self.replace_selection(value, "\n");
+            }
+            KeyCode::A if control => {
+                self.selection_anchor = Some(0);
+                self.cursor = value.len();
+            }
+            KeyCode::C | KeyCode::Copy if control || key == KeyCode::Copy => {
+                if let Some((start, end)) = self.selection() {
+                    clipboard.set(&value[start..end]);
+                }
+            }
+            KeyCode::X | KeyCode::Cut if control || key == KeyCode::Cut => {
+                if let Some((start, end)) = self.selection() {
+                    clipboard.set(&value[start..end]);
+                    self.replace_selection(value, "");
+                }
+            }
+            KeyCode::V | KeyCode::Paste if control || key == KeyCode::Paste => {
+                let mut pasted = clipboard.get();
+                if !self.multiline {
+                    pasted = pasted.replace(['\r', '\n'], " ");
+                }
+                self.replace_selection(value, &pasted);
+            }
+            _ => {}
+        }
+        false
+    }
+}
+
+/// Handles focus, caret placement and editing of [`TextInput`]s.
+#[allow(clippy::too_many_arguments)]
+pub fn text_input_system(
+    mut windows: ResMut<Windows>,
+    mut focus: ResMut<Focus>,
+    ui_window_nodes: Res<UiWindowNodes>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut received_characters: EventReader<ReceivedCharacter>,
+    mut ime_events: EventReader<Ime>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut clipboard: ResMut<Clipboard>,
+    mut submitted_events: EventWriter<TextInputSubmitted>,
+    mut input_query: Query<(
+        Entity,
+        &mut TextInput,
+        &mut Text,
+        &Interaction,
+        &Node,
+        &GlobalTransform,
+        &TextLayoutInfo,
+    )>,
+) {
+    let shift = keyboard_input.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+    let control = keyboard_input.any_pressed([
+        KeyCode::LControl,
+        KeyCode::RControl,
+        KeyCode::LWin,
+        KeyCode::RWin,
+    ]);
+    let alt = keyboard_input.any_pressed([KeyCode::LAlt, KeyCode::RAlt]);
+    // Keys pressed while control is held are shortcuts that type no text, except with AltGr,
+    // which some platforms report as Ctrl+Alt
+    let shortcut = control && !alt;
+
+    let pressed_keys = keyboard_events
+        .iter()
+        .filter(|event| event.state == ButtonState::Pressed)
+        .map(|event| event.key_code);
+    let characters = received_characters
+        .iter()
+        .map(|event| event.char)
+        .filter(|c| !c.is_control() && !shortcut);
+    let mut edits = typed_edits(pressed_keys, characters, shortcut);
+    let mut preedit = None;
+    for event in ime_events.iter() {
+        match event {
+            Ime::Preedit { value, .. } => preedit = Some(value.clone()),
+            Ime::Commit { value, .. } => {
+                edits.push(Edit::Insert(value.clone()));
+                preedit = Some(String::new());
+            }
+            Ime::Enabled { .. } => {}
+            Ime::Disabled { .. } => preedit = Some(String::new()),
+        }
+    }
+
+    let mut ime_position = None;
+
+    for (entity, mut input, mut text, interaction, node, transform, layout) in
+        input_query.iter_mut()
+    {
+        let mut focused = focus.get() == Some(entity);
+        let clicked =
+            mouse_button_input.pressed(MouseButton::Left) && *interaction == Interaction::Clicked;
+        if !focused && !clicked && input.ime_preedit.is_empty() {
+            continue;
+        }
+
+        let window_id = ui_window_nodes.window(entity);
+        let window = windows.get(window_id);
+        let scale_factor = window.map_or(1.0, |window| window.scale_factor()) as f32;
+        let cursor_position = window.and_then(|window| window.cursor_position());
+
+        // Hit test in the space of the text layout, which starts at the bottom left of the node
+        let hit = cursor_position.and_then(|position| {
+            let bottom_left = transform.translation.truncate() - node.size / 2.0;
+            layout
+                .hit_test((position - bottom_left) * scale_factor)
+                .map(|index| input.value_index(index))
+        });
+
+        let displayed = text.value();
+        let mut value = displayed.clone();
+        input.remove_preedit(&mut value);
+        // Keep the caret valid if the text was changed from elsewhere
+        if input.cursor > value.len() || !value.is_char_boundary(input.cursor) {
+            input.cursor = value.len();
+            input.selection_anchor = None;
+        }
+
+        if mouse_button_input.just_pressed(MouseButton::Left) {
+            let clicked = *interaction == Interaction::Clicked;
+            if clicked {
+                let cursor = hit.unwrap_or(value.len());
+                let select = shift && focused;
+                input.move_cursor(cursor, select);
+                if !focused {
+                    focus.set(entity);
+                    focused = true;
+                }
+            } else if focused {
+                input.selection_anchor = None;
+                focus.clear();
+                focused = false;
+            }
+        } else if mouse_button_input.pressed(MouseButton::Left)
+            && *interaction == Interaction::Clicked
+            && focused
+        {
+            // Dragging selects text
+            if let Some(cursor) = hit {
+                if cursor != input.cursor {
+                    input.move_cursor(cursor, true);
+                }
+            }
+        }
+
+        if focused {
+            if let Some(preedit) = &preedit {
+                if input.ime_preedit != *preedit {
+                    input.ime_preedit = preedit.clone();
+                }
+            }
+            for edit in &edits {
+                if input.edit(&mut value, edit, shift, control, layout, &mut clipboard) {
+                    submitted_events.send(TextInputSubmitted {
+                        entity,
+                        value: value.clone(),
+                    });
+                }
+            }
+            // Place the IME candidate box at the bottom left of the caret, in window coordinates
+            let cursor = input.displayed_index(input.cursor);
+            if let Some(caret) = layout.caret_position(cursor) {
+                let bottom_left = transform.translation.truncate() - node.size / 2.0;
+                ime_position = Some((window_id, bottom_left + caret.position / scale_factor));
+            }
+        } else if !input.ime_preedit.is_empty() {
+            input.ime_preedit.clear();
+        }
+
+        let new_displayed = input.display(&value);
+        if new_displayed != displayed {
+            match text.sections.len() {
+                1 => text.set_section_value(0, new_displayed),
+                _ => {
+                    let style = text
+                        .sections
+                        .first()
+                        .map(|section| section.style.clone())
+                        .unwrap_or_default();
+                    text.sections = vec![TextSection::new(new_displayed, style)];
+                }
+            }
+        }
+    }
+
+    if let Some((window_id, position)) = ime_position {
+        if let Some(window) = windows.get_mut(window_id) {
+            window.set_ime_position(position);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+    use bevy_ecs::event::Events;
+    use bevy_utils::default;
+    use bevy_window::WindowId;
+
+    /// Applies `edits` to `value`, returning the edited value and whether it was submitted.
+    fn apply(
+        input: &mut TextInput,
+        value: &str,
+        edits: &[Edit],
+        shift: bool,
+        control: bool,
+        clipboard: &mut Clipboard,
+    ) -> (String, bool) {
+        let mut value = value.to_string();
+        let mut submitted = false;
+        for edit in edits {
+            submitted |= input.edit(
+                &mut value,
+                edit,
+                shift,
+                control,
+                &TextLayoutInfo::default(),
+                clipboard,
+            );
+        }
+        (value, submitted)
+    }
+
+    fn insert(text: &str) -> Edit {
+        Edit::Insert(text.to_string())
+    }
+
+    #[test]
+    fn edits_keep_the_typing_order() {
+        let keys = [
+            KeyCode::A,
+            KeyCode::Back,
+            KeyCode::B,
+            KeyCode::Left,
+            KeyCode::C,
+        ];
+        let edits = typed_edits(keys.into_iter().map(Some), "abc".chars(), false);
+        assert_eq!(
+            edits,
+            [
+                insert("a"),
+                Edit::Key(KeyCode::Back),
+                insert("b"),
+                Edit::Key(KeyCode::Left),
+                insert("c"),
+            ]
+        );
+
+        let mut input = TextInput::default();
+        let (value, _) = apply(
+            &mut input,
+            "",
+            &edits,
+            false,
+            false,
+            &mut Clipboard::local(),
+        );
+        assert_eq!(value, "cb");
+        assert_eq!(input.cursor, 1);
+    }
+
+    #[test]
+    fn keys_that_type_no_character_are_not_text() {
+        let keys = [Some(KeyCode::LShift), Some(KeyCode::Left), Some(KeyCode::A)];
+        let edits = typed_edits(keys, "A".chars(), false);
+        assert_eq!(
+            edits,
+            [
+                Edit::Key(KeyCode::LShift),
+                Edit::Key(KeyCode::Left),
+                insert("A"),
+            ]
+        );
+
+        // Consecutive characters are inserted together, and the characters of keys without a
+        // key code or of dead keys are inserted in order
+        let keys = [Some(KeyCode::A), None, Some(KeyCode::Grave)];
+        assert_eq!(typed_edits(keys, "aßé!".chars(), false), [insert("aßé!")]);
+
+        // Shortcuts type nothing
+        let edits = typed_edits([Some(KeyCode::V)], "".chars(), true);
+        assert_eq!(edits, [Edit::Key(KeyCode::V)]);
+    }
+
+    #[test]
+    fn delete_characters() {
+        let mut input = TextInput {
+            cursor: 3,
+            ..Default::default()
+        };
+        let clipboard = &mut Clipboard::local();
+        let back = [Edit::Key(KeyCode::Back)];
+        let (value, _) = apply(&mut input, "héllo", &back, false, false, clipboard);
+        assert_eq!((value.as_str(), input.cursor), ("hllo", 1));
+
+        let delete = [Edit::Key(KeyCode::Delete)];
+        let (value, _) = apply(&mut input, &value, &delete, false, false, clipboard);
+        assert_eq!((value.as_str(), input.cursor), ("hlo", 1));
+
+        // At the ends of the value, nothing is deleted
+        input.cursor = 0;
+        let (value, _) = apply(&mut input, &value, &back, false, false, clipboard);
+        assert_eq!(value, "hlo");
+        input.cursor = 3;
+        let (value, _) = apply(&mut input, &value, &delete, false, false, clipboard);
+        assert_eq!(value, "hlo");
+    }
+
+    #[test]
+    fn select_and_replace() {
+        let mut input = TextInput {
+            cursor: 5,
+            ..Default::default()
+        };
+        let clipboard = &mut Clipboard::local();
+        let left = [Edit::Key(KeyCode::Left), Edit::Key(KeyCode::Left)];
+        apply(&mut input, "hello", &left, true, false, clipboard);
+        assert_eq!(input.selection(), Some((3, 5)));
+
+        let (value, _) = apply(
+            &mut input,
+            "hello",
+            &[insert("p!")],
+            false,
+            false,
+            clipboard,
+        );
+        assert_eq!(value, "help!");
+        assert_eq!(input.selection(), None);
+
+        // Moving without shift collapses the selection to its edge
+        input.selection_anchor = Some(1);
+        apply(
+            &mut input,
+            &value,
+            &[Edit::Key(KeyCode::Right)],
+            false,
+            false,
+            clipboard,
+        );
+        assert_eq!((input.cursor, input.selection()), (5, None));
+
+        let home = [Edit::Key(KeyCode::Home)];
+        apply(&mut input, &value, &home, true, false, clipboard);
+        assert_eq!(input.selection(), Some((0, 5)));
+        let end = [Edit::Key(KeyCode::End)];
+        apply(&mut input, &value, &end, false, false, clipboard);
+        assert_eq!((input.cursor, input.selection()), (5, None));
+    }
+
+    #[test]
+    fn copy_cut_and_paste() {
+        let mut input = TextInput::default();
+        let clipboard = &mut Clipboard::local();
+        let select_all = [Edit::Key(KeyCode::A)];
+        apply(&mut input, "hello", &select_all, false, true, clipboard);
+        assert_eq!(input.selection(), Some((0, 5)));
+
+        let copy = [Edit::Key(KeyCode::C)];
+        let (value, _) = apply(&mut input, "hello", &copy, false, true, clipboard);
+        assert_eq!(
+            (value.as_str(), clipboard.get().as_str()),
+            ("hello", "hello")
+        );
+
+        input.selection_anchor = Some(1);
+        let cut = [Edit::Key(KeyCode::X)];
+        let (value, _) = apply(&mut input, &value, &cut, false, true, clipboard);
+        assert_eq!((value.as_str(), clipboard.get().as_str()), ("h", "ello"));
+
+        let paste = [Edit::Key(KeyCode::V), Edit::Key(KeyCode::Paste)];
+        let (value, _) = apply(&mut input, &value, &paste, false, true, clipboard);
+        assert_eq!((value.as_str(), input.cursor), ("helloello", 9));
+
+        // Without control, the keys of the shortcuts do nothing
+        let (value, _) = apply(&mut input, &value, &paste[..1], false, false, clipboard);
+        assert_eq!(value, "helloello");
+
+        // Line breaks can only be pasted in multiline fields
+        clipboard.set("a\nb");
+        input.cursor = 0;
+        let (value, _) = apply(&mut input, "", &paste[..1], false, true, clipboard);
+        assert_eq!(value, "a b");
+        input.cursor = 0;
+        input.multiline = true;
+        let (value, _) = apply(&mut input, "", &paste[..1], false, true, clipboard);
+        assert_eq!(value, "a\nb");
+    }
+
+    #[test]
+    fn enter_submits_single_line_fields() {
+        let mut input = TextInput {
+            cursor: 2,
+            ..Default::default()
+        };
+        let clipboard = &mut Clipboard::local();
+        let enter = [Edit::Key(KeyCode::Return)];
+        let (value, submitted) = apply(&mut input, "ab", &enter, false, false, clipboard);
+        assert_eq!((value.as_str(), submitted), ("ab", true));
+
+        input.multiline = true;
+        let (value, submitted) = apply(&mut input, "ab", &enter, false, false, clipboard);
+        assert_eq!((value.as_str(), submitted), ("ab\n", false));
+    }
+
+    #[test]
+    fn preedit_is_displayed_at_the_caret() {
+        let input = TextInput {
+            cursor: 1,
+            ime_preedit: String::from("かな"),
+            ..Default::default()
+        };
+        let displayed = input.display("ab");
+        assert_eq!(displayed, "aかなb");
+        assert_eq!(input.preedit_range(), Some((1, 7)));
+        assert_eq!(
+            input.value(&Text::with_section(displayed, default(), default())),
+            "ab"
+        );
+
+        assert_eq!(input.displayed_index(0), 0);
+        assert_eq!(input.displayed_index(1), 7);
+        assert_eq!(input.displayed_index(2), 8);
+        assert_eq!(input.value_index(1), 1);
+        assert_eq!(input.value_index(4), 1);
+        assert_eq!(input.value_index(8), 2);
+
+        // The preedit is only removed if it is still displayed
+        let text = Text::with_section("abc", default(), default());
+        assert_eq!(input.value(&text), "abc");
+    }
+
+    fn input_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Focus>()
+            .init_resource::<Windows>()
+            .init_resource::<UiWindowNodes>()
+            .init_resource::<Input<MouseButton>>()
+            .init_resource::<Input<KeyCode>>()
+            .insert_resource(Clipboard::local())
+            .add_event::<ReceivedCharacter>()
+            .add_event::<Ime>()
+            .add_event::<KeyboardInput>()
+            .add_event::<TextInputSubmitted>()
+            .add_system(text_input_system);
+        app
+    }
+
+    fn spawn_input(app: &mut App) -> Entity {
+        app.world
+            .spawn()
+            .insert_bundle((
+                TextInput::default(),
+                Text::with_section("", Default::default(), Default::default()),
+                Interaction::default(),
+                Node::default(),
+                GlobalTransform::default(),
+                TextLayoutInfo::default(),
+            ))
+            .id()
+    }
+
+    /// Clicks with the mouse while `clicked` is hovered, if any.
+    fn click(app: &mut App, inputs: &[Entity], clicked: Option<Entity>) {
+        for input in inputs {
+            *app.world.get_mut::<Interaction>(*input).unwrap() = if Some(*input) == clicked {
+                Interaction::Clicked
+            } else {
+                Interaction::None
+            };
+        }
+        let mut mouse_button_input = app.world.resource_mut::<Input<MouseButton>>();
+        mouse_button_input.release_all();
+        mouse_button_input.clear();
+        mouse_button_input.press(MouseButton::Left);
+        app.update();
+    }
+
+    fn type_char(app: &mut App, char: char) {
+        let mut mouse_button_input = app.world.resource_mut::<Input<MouseButton>>();
+        mouse_button_input.release_all();
+        mouse_button_input.clear();
+        app.world
+            .resource_mut::<Events<ReceivedCharacter>>()
+            .send(ReceivedCharacter {
+                id: WindowId::primary(),
+                char,
+            });
+        app.update();
+    }
+
+    fn value(app: &App, input: Entity) -> String {
+        app.world.get::<Text>(input).unwrap().value()
+    }
+
+    #[test]
+    fn only_the_focused_field_is_edited() {
+        let mut app = input_app();
+        let first = spawn_input(&mut app);
+        let second = spawn_input(&mut app);
+        let inputs = [first, second];
+
+        click(&mut app, &inputs, Some(first));
+        assert_eq!(app.world.resource::<Focus>().get(), Some(first));
+        type_char(&mut app, 'a');
+        assert_eq!(value(&app, first), "a");
+        assert_eq!(value(&app, second), "");
+
+        // Clicking anywhere else unfocuses the field
+        click(&mut app, &inputs, None);
+        assert_eq!(app.world.resource::<Focus>().get(), None);
+        type_char(&mut app, 'b');
+        assert_eq!(value(&app, first), "a");
+
+        // The focus can be moved through the resource, e.g. by navigating with the keyboard
+        app.world.resource_mut::<Focus>().set(second);
+        type_char(&mut app, 'c');
+        assert_eq!(value(&app, first), "a");
+        assert_eq!(value(&app, second), "c");
+    }
+}
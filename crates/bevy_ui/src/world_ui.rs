@@ -0,0 +1,252 @@
+use crate::Node;
+use bevy_ecs::{
+    entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+    prelude::Component,
+    query::With,
+    reflect::{ReflectComponent, ReflectMapEntities},
+    system::{Query, Res, ResMut},
+    world::{FromWorld, World},
+};
+use bevy_hierarchy::Children;
+use bevy_input::touch::Touches;
+use bevy_math::{Vec2, Vec3};
+use bevy_reflect::Reflect;
+use bevy_render::{
+    camera::{Camera, RenderTarget},
+    primitives::Aabb,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+use bevy_window::{WindowId, Windows};
+
+/// Renders the UI hierarchy of a root node to the image of a camera rather than to the window,
+/// to display it on a surface in the world, like the screen of a cockpit or of a terminal.
+///
+/// The camera should render to a [`RenderTarget::Image`], usually with a lower
+/// [`Camera::priority`] than the cameras of the window so that the image is up to date when it
+/// is displayed. The root node is laid out in the size of the image, and only this camera renders
+/// its hierarchy.
+///
+/// The surface is an entity with a mesh, like a `shape::Quad`, displaying the image in the XY
+/// plane of its transform, with the top of the image towards its Y axis. Pointing at the surface
+/// with the cursor of the primary window interacts with the UI as if pointing at the image.
+#[derive(Component, Debug, Copy, Clone, Eq, PartialEq, Reflect)]
+#[reflect(Component, MapEntities, PartialEq)]
+pub struct WorldUi {
+    /// The camera rendering the UI to its image
+    pub camera: Entity,
+    /// The entity displaying the image in the world
+    pub surface: Entity,
+}
+
+impl FromWorld for WorldUi {
+    fn from_world(_world: &mut World) -> Self {
+        WorldUi {
+            camera: Entity::from_raw(u32::MAX),
+            surface: Entity::from_raw(u32::MAX),
+        }
+    }
+}
+
+impl MapEntities for WorldUi {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        self.camera = entity_map.get(self.camera)?;
+        self.surface = entity_map.get(self.surface)?;
+        Ok(())
+    }
+}
+
+/// The nodes in the hierarchies of the [`WorldUi`] root nodes, and where the cursor points at on
+/// their surfaces.
+#[derive(Debug, Default)]
+pub struct WorldUiNodes {
+    /// The root of the hierarchy of each node
+    roots: HashMap<Entity, Entity>,
+    /// The camera rendering each root
+    cameras: HashMap<Entity, Entity>,
+    /// The position of the cursor in the UI of each root whose surface it points at
+    cursor_positions: HashMap<Entity, Vec2>,
+}
+
+impl WorldUiNodes {
+    /// Returns the root node of the [`WorldUi`] hierarchy of the node, if it is in one.
+    pub fn root(&self, node: Entity) -> Option<Entity> {
+        self.roots.get(&node).copied()
+    }
+
+    /// Returns the camera rendering the node, if it is in the hierarchy of a [`WorldUi`].
+    pub fn camera(&self, node: Entity) -> Option<Entity> {
+        self.roots
+            .get(&node)
+            .and_then(|root| self.cameras.get(root))
+            .copied()
+    }
+
+    /// Returns the cameras rendering a [`WorldUi`], which don't render the UI of the windows.
+    pub fn world_ui_cameras(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.cameras.values().copied()
+    }
+
+    /// Returns the position of the cursor in the UI of the [`WorldUi`] of the node, in logical
+    /// pixels from the bottom left of the image, if the cursor points at its surface.
+    pub fn cursor_position(&self, node: Entity) -> Option<Vec2> {
+        self.roots
+            .get(&node)
+            .and_then(|root| self.cursor_positions.get(root))
+            .copied()
+    }
+}
+
+/// Finds the nodes in the hierarchies of the [`WorldUi`] root nodes.
+pub fn update_world_ui_nodes(
+    mut world_ui_nodes: ResMut<WorldUiNodes>,
+    root_query: Query<(Entity, &WorldUi), With<Node>>,
+    children_query: Query<&Children, With<Node>>,
+) {
+    if root_query.is_empty() && world_ui_nodes.roots.is_empty() {
+        return;
+    }
+    let world_ui_nodes = &mut *world_ui_nodes;
+    world_ui_nodes.roots.clear();
+    world_ui_nodes.cameras.clear();
+    for (root, world_ui) in root_query.iter() {
+        world_ui_nodes.cameras.insert(root, world_ui.camera);
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            world_ui_nodes.roots.insert(node, root);
+            if let Ok(children) = children_query.get(node) {
+                stack.extend(children.iter());
+            }
+        }
+    }
+}
+
+/// Casts a ray from the cursor of the primary window through the camera rendering it, to find
+/// where it points at on the surfaces of the [`WorldUi`] root nodes.
+pub fn world_ui_cursor_system(
+    windows: Res<Windows>,
+    touches_input: Res<Touches>,
+    mut world_ui_nodes: ResMut<WorldUiNodes>,
+    root_query: Query<&WorldUi, With<Node>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    surface_query: Query<(&GlobalTransform, &Aabb)>,
+) {
+    world_ui_nodes.cursor_positions.clear();
+    if world_ui_nodes.cameras.is_empty() {
+        return;
+    }
+
+    let cursor_position = windows
+        .get_primary()
+        .and_then(|window| window.cursor_position())
+        .or_else(|| touches_input.first_pressed_position());
+    // The cursor looks through the camera rendered last to the window
+    let window_camera = camera_query
+        .iter()
+        .filter(|(camera, _)| {
+            camera.is_active && camera.target == RenderTarget::Window(WindowId::primary())
+        })
+        .max_by_key(|(camera, _)| camera.priority);
+    let ray = match (cursor_position, window_camera) {
+        (Some(cursor_position), Some((camera, camera_transform))) => {
+            match camera.viewport_to_world(camera_transform, cursor_position) {
+                Some(ray) => ray,
+                None => return,
+            }
+        }
+        _ => return,
+    };
+
+    let world_ui_nodes = &mut *world_ui_nodes;
+    for (root, camera) in world_ui_nodes.cameras.iter() {
+        let (world_ui, image_size) = match (
+            root_query.get(*root),
+            camera_query
+                .get(*camera)
+                .ok()
+                .and_then(|(camera, _)| camera.logical_viewport_size()),
+        ) {
+            (Ok(world_ui), Some(image_size)) => (world_ui, image_size),
+            _ => continue,
+        };
+        let (surface_transform, aabb) = match surface_query.get(world_ui.surface) {
+            Ok(surface) => surface,
+            Err(_) => continue,
+        };
+
+        let surface_matrix = surface_transform.compute_matrix();
+        let plane_origin = surface_matrix.transform_point3(aabb.center.into());
+        let plane_normal = surface_matrix.transform_vector3(Vec3::Z);
+        let distance = match ray.intersect_plane(plane_origin, plane_normal) {
+            Some(distance) => distance,
+            None => continue,
+        };
+        let point = surface_matrix
+            .inverse()
+            .transform_point3(ray.get_point(distance));
+        let min = aabb.center.truncate() - aabb.half_extents.truncate();
+        let size = aabb.half_extents.truncate() * 2.0;
+        let uv = (point.truncate() - min) / size;
+        if (0.0..=1.0).contains(&uv.x) && (0.0..=1.0).contains(&uv.y) {
+            world_ui_nodes
+                .cursor_positions
+                .insert(*root, uv * image_size);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        schedule::{Schedule, Stage, SystemStage},
+        system::{CommandQueue, Commands},
+        world::World,
+    };
+    use bevy_hierarchy::BuildChildren;
+
+    use crate::Node;
+
+    use super::{update_world_ui_nodes, WorldUi, WorldUiNodes};
+
+    #[test]
+    fn world_ui_nodes_are_rendered_by_their_camera() {
+        let mut world = World::default();
+        world.init_resource::<WorldUiNodes>();
+        let camera = world.spawn().id();
+        let surface = world.spawn().id();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let window_root = commands.spawn_bundle((Node::default(),)).id();
+        let mut world_ui_child = None;
+        let world_ui_root = commands
+            .spawn_bundle((Node::default(), WorldUi { camera, surface }))
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle((Node::default(),))
+                    .with_children(|parent| {
+                        world_ui_child = Some(parent.spawn_bundle((Node::default(),)).id());
+                    });
+            })
+            .id();
+        queue.apply(&mut world);
+
+        let mut schedule = Schedule::default();
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(update_world_ui_nodes);
+        schedule.add_stage("update", update_stage);
+        schedule.run(&mut world);
+
+        let world_ui_nodes = world.resource::<WorldUiNodes>();
+        assert_eq!(world_ui_nodes.camera(window_root), None);
+        assert_eq!(world_ui_nodes.camera(world_ui_root), Some(camera));
+        assert_eq!(
+            world_ui_nodes.root(world_ui_child.unwrap()),
+            Some(world_ui_root)
+        );
+        assert_eq!(
+            world_ui_nodes.world_ui_cameras().collect::<Vec<_>>(),
+            vec![camera]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::{WindowDescriptor, WindowId};
 use bevy_math::{IVec2, Vec2};
@@ -93,6 +93,44 @@ pub struct ReceivedCharacter {
     pub char: char,
 }
 
+/// An input method editor (IME) event, sent while the user composes text with an IME, like
+/// when typing Chinese, Japanese or Korean text.
+///
+/// The committed text is not sent as [`ReceivedCharacter`] events, text fields should insert
+/// the value of [`Ime::Commit`] events. Use [`Window::set_ime_position`](crate::Window::set_ime_position)
+/// to place the IME candidate box next to the text being edited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ime {
+    /// The text being composed changed.
+    Preedit {
+        id: WindowId,
+        /// The text being composed, which is not part of the edited text yet.
+        ///
+        /// This is empty when the composition was cancelled or committed.
+        value: String,
+        /// The byte range of the caret in `value`, or `None` if the caret should be hidden.
+        cursor: Option<(usize, usize)>,
+    },
+    /// The composed text was committed.
+    Commit { id: WindowId, value: String },
+    /// The IME was enabled, [`Ime::Preedit`] and [`Ime::Commit`] events may be sent.
+    Enabled { id: WindowId },
+    /// The IME was disabled.
+    Disabled { id: WindowId },
+}
+
+impl Ime {
+    /// Returns the id of the window receiving this event.
+    pub fn window_id(&self) -> WindowId {
+        match self {
+            Ime::Preedit { id, .. }
+            | Ime::Commit { id, .. }
+            | Ime::Enabled { id }
+            | Ime::Disabled { id } => *id,
+        }
+    }
+}
+
 /// An event that indicates a window has received or lost focus.
 #[derive(Debug, Clone)]
 pub struct WindowFocused {
@@ -114,15 +152,40 @@ pub struct WindowBackendScaleFactorChanged {
 }
 
 /// Events related to files being dragged and dropped on a window.
+///
+/// When several files are dragged at once, an event is sent for each of them.
 #[derive(Debug, Clone)]
 pub enum FileDragAndDrop {
+    /// A file was dropped on the window.
     DroppedFile { id: WindowId, path_buf: PathBuf },
 
+    /// A file is being dragged over the window.
     HoveredFile { id: WindowId, path_buf: PathBuf },
 
+    /// The files dragged over the window left it without being dropped.
     HoveredFileCancelled { id: WindowId },
 }
 
+impl FileDragAndDrop {
+    /// Returns the id of the window receiving this event.
+    pub fn window_id(&self) -> WindowId {
+        match self {
+            FileDragAndDrop::DroppedFile { id, .. }
+            | FileDragAndDrop::HoveredFile { id, .. }
+            | FileDragAndDrop::HoveredFileCancelled { id } => *id,
+        }
+    }
+
+    /// Returns the path of the dropped or hovered file, if any.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            FileDragAndDrop::DroppedFile { path_buf, .. }
+            | FileDragAndDrop::HoveredFile { path_buf, .. } => Some(path_buf.as_path()),
+            FileDragAndDrop::HoveredFileCancelled { .. } => None,
+        }
+    }
+}
+
 /// An event that is sent when a window is repositioned in physical pixels.
 #[derive(Debug, Clone)]
 pub struct WindowMoved {
@@ -0,0 +1,64 @@
+use thiserror::Error;
+
+/// The icon of a window, shown in its title bar and in the taskbar of the system.
+///
+/// It is stored as 8 bit RGBA pixels, row by row from the top left. Images can be converted with
+/// `Image::to_window_icon` from `bevy_render`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowIcon {
+    rgba: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+/// An error creating a [`WindowIcon`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum WindowIconError {
+    #[error(
+        "the icon has {len} bytes of RGBA data, {width}x{height} pixels need {expected} bytes"
+    )]
+    InvalidSize {
+        len: usize,
+        width: u32,
+        height: u32,
+        expected: usize,
+    },
+}
+
+impl WindowIcon {
+    /// Creates an icon from 8 bit RGBA pixels, row by row from the top left.
+    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, WindowIconError> {
+        let expected = width as usize * height as usize * 4;
+        if rgba.len() != expected {
+            return Err(WindowIconError::InvalidSize {
+                len: rgba.len(),
+                width,
+                height,
+                expected,
+            });
+        }
+        Ok(WindowIcon {
+            rgba,
+            width,
+            height,
+        })
+    }
+
+    /// The RGBA pixels of the icon, row by row from the top left.
+    #[inline]
+    pub fn rgba(&self) -> &[u8] {
+        &self.rgba
+    }
+
+    /// The width of the icon, in pixels.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the icon, in pixels.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
@@ -1,6 +1,7 @@
 #[warn(missing_docs)]
 mod cursor;
 mod event;
+mod icon;
 mod raw_window_handle;
 mod system;
 mod window;
@@ -9,6 +10,7 @@ mod windows;
 pub use crate::raw_window_handle::*;
 pub use cursor::*;
 pub use event::*;
+pub use icon::*;
 pub use system::*;
 pub use window::*;
 pub use windows::*;
@@ -16,8 +18,9 @@ pub use windows::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        CursorEntered, CursorIcon, CursorLeft, CursorMoved, FileDragAndDrop, MonitorSelection,
-        ReceivedCharacter, Window, WindowDescriptor, WindowMoved, WindowPosition, Windows,
+        CursorEntered, CursorIcon, CursorLeft, CursorMoved, FileDragAndDrop, Ime, MonitorSelection,
+        ReceivedCharacter, Window, WindowDescriptor, WindowIcon, WindowMoved, WindowPosition,
+        Windows,
     };
 }
 
@@ -79,6 +82,7 @@ impl Plugin for WindowPlugin {
             .add_event::<CursorEntered>()
             .add_event::<CursorLeft>()
             .add_event::<ReceivedCharacter>()
+            .add_event::<Ime>()
             .add_event::<WindowFocused>()
             .add_event::<WindowScaleFactorChanged>()
             .add_event::<WindowBackendScaleFactorChanged>()
@@ -54,7 +54,7 @@ impl WindowId {
     }
 }
 
-use crate::CursorIcon;
+use crate::{CursorIcon, WindowIcon};
 use std::fmt;
 
 use crate::raw_window_handle::RawWindowHandleWrapper;
@@ -179,6 +179,7 @@ pub struct Window {
     scale_factor_override: Option<f64>,
     backend_scale_factor: f64,
     title: String,
+    icon: Option<WindowIcon>,
     present_mode: PresentMode,
     resizable: bool,
     decorations: bool,
@@ -186,6 +187,7 @@ pub struct Window {
     cursor_visible: bool,
     cursor_locked: bool,
     physical_cursor_position: Option<DVec2>,
+    ime_position: Option<Vec2>,
     raw_window_handle: RawWindowHandleWrapper,
     focused: bool,
     mode: WindowMode,
@@ -208,6 +210,10 @@ pub enum WindowCommand {
     SetTitle {
         title: String,
     },
+    /// Set the window's [`WindowIcon`], or remove it.
+    SetIcon {
+        icon: Option<WindowIcon>,
+    },
     /// Set the window's scale factor.
     SetScaleFactor {
         scale_factor: f64,
@@ -247,6 +253,10 @@ pub enum WindowCommand {
     SetCursorPosition {
         position: Vec2,
     },
+    /// Set the position of the input method editor (IME) candidate box.
+    SetImePosition {
+        position: Vec2,
+    },
     /// Set whether or not the window is maxizimed.
     SetMaximized {
         maximized: bool,
@@ -305,6 +315,7 @@ impl Window {
             scale_factor_override: window_descriptor.scale_factor_override,
             backend_scale_factor: scale_factor,
             title: window_descriptor.title.clone(),
+            icon: window_descriptor.icon.clone(),
             present_mode: window_descriptor.present_mode,
             resizable: window_descriptor.resizable,
             decorations: window_descriptor.decorations,
@@ -312,6 +323,7 @@ impl Window {
             cursor_locked: window_descriptor.cursor_locked,
             cursor_icon: CursorIcon::Default,
             physical_cursor_position: None,
+            ime_position: None,
             raw_window_handle: RawWindowHandleWrapper::new(raw_window_handle),
             focused: true,
             mode: window_descriptor.mode,
@@ -520,6 +532,21 @@ impl Window {
         self.title = title.to_string();
         self.command_queue.push(WindowCommand::SetTitle { title });
     }
+    /// Get the window's [`WindowIcon`].
+    #[inline]
+    pub fn icon(&self) -> Option<&WindowIcon> {
+        self.icon.as_ref()
+    }
+    /// Set the window's [`WindowIcon`], or remove it with `None`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **`Windows`** and **`X11`**: Supported.
+    /// - Other platforms: Unsupported, the icon of the application bundle is used.
+    pub fn set_icon(&mut self, icon: Option<WindowIcon>) {
+        self.icon = icon.clone();
+        self.command_queue.push(WindowCommand::SetIcon { icon });
+    }
 
     #[inline]
     #[doc(alias = "vsync")]
@@ -647,6 +674,29 @@ impl Window {
             .push(WindowCommand::SetCursorPosition { position });
     }
 
+    /// The position of the input method editor (IME) candidate box, in logical pixels, if it
+    /// was set with [`Window::set_ime_position`].
+    #[inline]
+    pub fn ime_position(&self) -> Option<Vec2> {
+        self.ime_position
+    }
+    /// Set the position of the input method editor (IME) candidate box, in logical pixels from
+    /// the bottom left of the window like [`Window::cursor_position`].
+    ///
+    /// Text fields should set it to the bottom left of their caret, so the candidate box shows up
+    /// below the text being composed, see [`Ime`](crate::Ime).
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **`iOS`**, **`Android`** and **`Web`**: Unsupported.
+    pub fn set_ime_position(&mut self, position: Vec2) {
+        if self.ime_position != Some(position) {
+            self.ime_position = Some(position);
+            self.command_queue
+                .push(WindowCommand::SetImePosition { position });
+        }
+    }
+
     #[allow(missing_docs)]
     #[inline]
     pub fn update_focused_status_from_backend(&mut self, focused: bool) {
@@ -784,6 +834,12 @@ pub struct WindowDescriptor {
     /// ## Platform-specific
     /// - Web: Unsupported.
     pub title: String,
+    /// Sets the icon that displays on the window top bar and on the system task bar.
+    ///
+    /// ## Platform-specific
+    /// - Windows and X11: Supported.
+    /// - Other platforms: Unsupported, the icon of the application bundle is used.
+    pub icon: Option<WindowIcon>,
     /// Controls when a frame is presented to the screen.
     #[doc(alias = "vsync")]
     /// The window's [`PresentMode`].
@@ -834,6 +890,7 @@ impl Default for WindowDescriptor {
     fn default() -> Self {
         WindowDescriptor {
             title: "app".to_string(),
+            icon: None,
             width: 1280.,
             height: 720.,
             position: WindowPosition::Automatic,
@@ -5,7 +5,7 @@ use bevy_input::{
     ButtonState,
 };
 use bevy_math::Vec2;
-use bevy_window::CursorIcon;
+use bevy_window::{CursorIcon, Ime, WindowIcon, WindowId};
 
 pub fn convert_keyboard_input(keyboard_input: &winit::event::KeyboardInput) -> KeyboardInput {
     KeyboardInput {
@@ -266,3 +266,16 @@ pub fn convert_cursor_icon(cursor_icon: CursorIcon) -> winit::window::CursorIcon
         CursorIcon::RowResize => winit::window::CursorIcon::RowResize,
     }
 }
+
+pub fn convert_window_icon(icon: &WindowIcon) -> Option<winit::window::Icon> {
+    winit::window::Icon::from_rgba(icon.rgba().to_vec(), icon.width(), icon.height()).ok()
+}
+
+pub fn convert_ime(ime: winit::event::Ime, id: WindowId) -> Ime {
+    match ime {
+        winit::event::Ime::Preedit(value, cursor) => Ime::Preedit { id, value, cursor },
+        winit::event::Ime::Commit(value) => Ime::Commit { id, value },
+        winit::event::Ime::Enabled => Ime::Enabled { id },
+        winit::event::Ime::Disabled => Ime::Disabled { id },
+    }
+}
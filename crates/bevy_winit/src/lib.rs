@@ -24,7 +24,7 @@ use bevy_utils::{
     Instant,
 };
 use bevy_window::{
-    CreateWindow, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, ModifiesWindows,
+    CreateWindow, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, Ime, ModifiesWindows,
     ReceivedCharacter, RequestRedraw, WindowBackendScaleFactorChanged, WindowCloseRequested,
     WindowClosed, WindowCreated, WindowFocused, WindowMoved, WindowResized,
     WindowScaleFactorChanged, Windows,
@@ -101,6 +101,10 @@ fn change_window(
                     let window = winit_windows.get_window(id).unwrap();
                     window.set_title(&title);
                 }
+                bevy_window::WindowCommand::SetIcon { icon } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    window.set_window_icon(icon.as_ref().and_then(converters::convert_window_icon));
+                }
                 bevy_window::WindowCommand::SetScaleFactor { scale_factor } => {
                     window_dpi_changed_events.send(WindowScaleFactorChanged { id, scale_factor });
                 }
@@ -129,8 +133,7 @@ fn change_window(
                 }
                 bevy_window::WindowCommand::SetCursorLockMode { locked } => {
                     let window = winit_windows.get_window(id).unwrap();
-                    window
-                        .set_cursor_grab(locked)
+                    winit_windows::set_cursor_locked(window, locked)
                         .unwrap_or_else(|e| error!("Unable to un/grab cursor: {}", e));
                 }
                 bevy_window::WindowCommand::SetCursorVisibility { visible } => {
@@ -147,6 +150,14 @@ fn change_window(
                         ))
                         .unwrap_or_else(|e| error!("Unable to set cursor position: {}", e));
                 }
+                bevy_window::WindowCommand::SetImePosition { position } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    let inner_size = window.inner_size().to_logical::<f32>(window.scale_factor());
+                    window.set_ime_position(winit::dpi::LogicalPosition::new(
+                        position.x,
+                        inner_size.height - position.y,
+                    ));
+                }
                 bevy_window::WindowCommand::SetMaximized { maximized } => {
                     let window = winit_windows.get_window(id).unwrap();
                     window.set_maximized(maximized);
@@ -481,6 +492,10 @@ pub fn winit_runner_with(mut app: App) {
                             char: c,
                         });
                     }
+                    WindowEvent::Ime(event) => {
+                        let mut ime_events = world.resource_mut::<Events<Ime>>();
+                        ime_events.send(converters::convert_ime(event, window_id));
+                    }
                     WindowEvent::ScaleFactorChanged {
                         scale_factor,
                         new_inner_size,
@@ -131,7 +131,14 @@ impl WinitWindows {
             };
 
         #[allow(unused_mut)]
-        let mut winit_window_builder = winit_window_builder.with_title(&window_descriptor.title);
+        let mut winit_window_builder = winit_window_builder
+            .with_title(&window_descriptor.title)
+            .with_window_icon(
+                window_descriptor
+                    .icon
+                    .as_ref()
+                    .and_then(crate::converters::convert_window_icon),
+            );
 
         #[cfg(target_arch = "wasm32")]
         {
@@ -155,8 +162,11 @@ impl WinitWindows {
 
         let winit_window = winit_window_builder.build(event_loop).unwrap();
 
+        // Text input is composed with the IME of the platform, see `bevy_window::Ime`
+        winit_window.set_ime_allowed(true);
+
         if window_descriptor.cursor_locked {
-            match winit_window.set_cursor_grab(true) {
+            match set_cursor_locked(&winit_window, true) {
                 Ok(_) | Err(winit::error::ExternalError::NotSupported(_)) => {}
                 Err(err) => Err(err).unwrap(),
             }
@@ -238,7 +248,9 @@ pub fn get_fitting_videomode(
         match abs_diff(a.size().width, width).cmp(&abs_diff(b.size().width, width)) {
             Equal => {
                 match abs_diff(a.size().height, height).cmp(&abs_diff(b.size().height, height)) {
-                    Equal => b.refresh_rate().cmp(&a.refresh_rate()),
+                    Equal => b
+                        .refresh_rate_millihertz()
+                        .cmp(&a.refresh_rate_millihertz()),
                     default => default,
                 }
             }
@@ -249,13 +261,31 @@ pub fn get_fitting_videomode(
     modes.first().unwrap().clone()
 }
 
+/// Confines the cursor to the window if `locked`, or locks it in place on the platforms that
+/// can't confine it, like macOS.
+pub(crate) fn set_cursor_locked(
+    winit_window: &winit::window::Window,
+    locked: bool,
+) -> Result<(), winit::error::ExternalError> {
+    use winit::window::CursorGrabMode;
+
+    if !locked {
+        return winit_window.set_cursor_grab(CursorGrabMode::None);
+    }
+    winit_window
+        .set_cursor_grab(CursorGrabMode::Confined)
+        .or_else(|_| winit_window.set_cursor_grab(CursorGrabMode::Locked))
+}
+
 pub fn get_best_videomode(monitor: &winit::monitor::MonitorHandle) -> winit::monitor::VideoMode {
     let mut modes = monitor.video_modes().collect::<Vec<_>>();
     modes.sort_by(|a, b| {
         use std::cmp::Ordering::*;
         match b.size().width.cmp(&a.size().width) {
             Equal => match b.size().height.cmp(&a.size().height) {
-                Equal => b.refresh_rate().cmp(&a.refresh_rate()),
+                Equal => b
+                    .refresh_rate_millihertz()
+                    .cmp(&a.refresh_rate_millihertz()),
                 default => default,
             },
             default => default,
@@ -0,0 +1,36 @@
+//! Draws debug shapes in 2D with the `Gizmos` system parameter.
+
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(draw_gizmos)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn_bundle(Camera2dBundle::default());
+}
+
+fn draw_gizmos(mut gizmos: Gizmos, time: Res<Time>) {
+    let sin = time.seconds_since_startup().sin() as f32 * 50.0;
+    gizmos.line_2d(Vec2::Y * -sin, Vec2::splat(-80.0), Color::RED);
+    gizmos.ray_2d(Vec2::Y * sin, Vec2::splat(80.0), Color::GREEN);
+
+    // The gizmos are only drawn for the current frame, so shapes can move from frame to frame
+    gizmos.rect_2d(
+        Vec2::ZERO,
+        time.seconds_since_startup() as f32 / 3.0,
+        Vec2::splat(300.0),
+        Color::BLACK,
+    );
+    gizmos.circle_2d(Vec2::ZERO, 120.0, Color::NAVY);
+    gizmos.linestrip_2d(
+        (0..=5).map(|i| Vec2::from_angle(i as f32 * 4.0 * PI / 5.0 + PI / 2.0) * 200.0),
+        Color::YELLOW,
+    );
+}
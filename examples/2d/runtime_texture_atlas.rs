@@ -0,0 +1,106 @@
+//! Packs individually loaded images into texture atlases at runtime, so that their sprites are
+//! drawn in a single batch.
+
+use bevy::{prelude::*, render::texture::ImageSettings};
+
+const PROPS: [&str; 8] = [
+    "textures/rpg/props/generic-rpg-barrel01.png",
+    "textures/rpg/props/generic-rpg-barrel02.png",
+    "textures/rpg/props/generic-rpg-board01.png",
+    "textures/rpg/props/generic-rpg-bridge.png",
+    "textures/rpg/props/generic-rpg-crate01.png",
+    "textures/rpg/props/generic-rpg-crate02.png",
+    "textures/rpg/props/generic-rpg-board02.png",
+    "textures/rpg/props/generic-rpg-barrel03.png",
+];
+
+fn main() {
+    println!(
+        "Controls:
+    Space - load another image and add its sprite to the atlases"
+    );
+    App::new()
+        .insert_resource(ImageSettings::default_nearest()) // prevents blurry sprites
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(spawn_prop)
+        .add_system(show_atlas_page)
+        .run();
+}
+
+#[derive(Component)]
+struct AtlasPage;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn_bundle(Camera2dBundle::default());
+
+    let mobs = [
+        "textures/rpg/mobs/boss_bee.png",
+        "textures/rpg/mobs/slime-blue.png",
+        "textures/rpg/mobs/slime-green.png",
+        "textures/rpg/mobs/slime-orange.png",
+    ];
+    for (i, path) in mobs.iter().enumerate() {
+        // the sprite is only drawn once its image is loaded and packed
+        commands
+            .spawn_bundle(SpriteSheetBundle {
+                transform: Transform {
+                    translation: Vec3::new(-300.0 + 200.0 * i as f32, 200.0, 0.0),
+                    scale: Vec3::splat(2.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .insert(RuntimeAtlasSprite(asset_server.load(*path)));
+    }
+}
+
+/// load a new image and spawn its sprite when space is pressed
+fn spawn_prop(
+    mut commands: Commands,
+    mut spawned: Local<usize>,
+    input: Res<Input<KeyCode>>,
+    asset_server: Res<AssetServer>,
+) {
+    if !input.just_pressed(KeyCode::Space) || *spawned >= PROPS.len() {
+        return;
+    }
+    commands
+        .spawn_bundle(SpriteSheetBundle {
+            transform: Transform {
+                translation: Vec3::new(-350.0 + 100.0 * *spawned as f32, 0.0, 0.0),
+                scale: Vec3::splat(2.0),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(RuntimeAtlasSprite(asset_server.load(PROPS[*spawned])));
+    *spawned += 1;
+}
+
+/// draw the first page of the atlases, to see the images being packed in it
+fn show_atlas_page(
+    mut commands: Commands,
+    runtime_atlases: Res<RuntimeTextureAtlases>,
+    texture_atlases: Res<Assets<TextureAtlas>>,
+    pages: Query<(), With<AtlasPage>>,
+) {
+    if !pages.is_empty() {
+        return;
+    }
+    let atlas = match runtime_atlases
+        .atlases()
+        .next()
+        .and_then(|atlas| texture_atlases.get(atlas))
+    {
+        Some(atlas) => atlas,
+        None => return,
+    };
+    commands
+        .spawn_bundle(SpriteBundle {
+            texture: atlas.texture.clone(),
+            transform: Transform::from_xyz(0.0, -200.0, 0.0).with_scale(Vec3::splat(0.25)),
+            ..default()
+        })
+        .insert(AtlasPage);
+}
@@ -0,0 +1,117 @@
+//! Renders a large tilemap with two layers, and changes some of its tiles every frame.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+const MAP_SIZE: u32 = 256;
+const TILE_SIZE: f32 = 16.0;
+
+fn main() {
+    println!(
+        "Controls:
+    Arrows - move the camera"
+    );
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(move_camera)
+        .add_system(change_tiles)
+        .run();
+}
+
+#[derive(Component)]
+struct Ground;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    commands.spawn_bundle(Camera2dBundle::default());
+
+    // the four images of the texture are stacked vertically
+    let texture_atlas = texture_atlases.add(TextureAtlas::from_grid(
+        asset_server.load("textures/array_texture.png"),
+        Vec2::splat(250.0),
+        1,
+        4,
+    ));
+    let mut rng = rand::thread_rng();
+    // center the tilemaps on the camera
+    let origin = Vec2::splat(-(MAP_SIZE as f32) * TILE_SIZE / 2.0);
+
+    // the ground layer has a tile everywhere
+    let mut ground = Tilemap::new(UVec2::splat(MAP_SIZE), Vec2::splat(TILE_SIZE));
+    ground.fill(Tile::new(0));
+    commands
+        .spawn_bundle(TilemapBundle {
+            tilemap: ground,
+            texture_atlas: texture_atlas.clone(),
+            transform: Transform::from_translation(origin.extend(0.0)),
+            ..default()
+        })
+        .insert(Ground);
+
+    // the decoration layer is drawn over the ground, with a few rotated or flipped tiles
+    let mut decorations = Tilemap::new(UVec2::splat(MAP_SIZE), Vec2::splat(TILE_SIZE));
+    for _ in 0..4096 {
+        let position = UVec2::new(rng.gen_range(0..MAP_SIZE), rng.gen_range(0..MAP_SIZE));
+        decorations.set(
+            position,
+            Tile {
+                index: rng.gen_range(1..4),
+                color: Color::rgba(1.0, 1.0, 1.0, 0.8),
+                flip_x: rng.gen(),
+                flip_y: rng.gen(),
+                rotation: match rng.gen_range(0..4) {
+                    0 => TileRotation::None,
+                    1 => TileRotation::Rotate90,
+                    2 => TileRotation::Rotate180,
+                    _ => TileRotation::Rotate270,
+                },
+            },
+        );
+    }
+    commands.spawn_bundle(TilemapBundle {
+        tilemap: decorations,
+        texture_atlas,
+        transform: Transform::from_translation(origin.extend(1.0)),
+        ..default()
+    });
+}
+
+/// change a few random tiles of the ground every frame, only their chunks are updated on the GPU
+fn change_tiles(mut ground: Query<&mut Tilemap, With<Ground>>) {
+    let mut rng = rand::thread_rng();
+    for mut tilemap in ground.iter_mut() {
+        for _ in 0..16 {
+            let position = UVec2::new(rng.gen_range(0..MAP_SIZE), rng.gen_range(0..MAP_SIZE));
+            if let Some(tile) = tilemap.get_mut(position) {
+                tile.color = Color::hsl(rng.gen_range(0.0..360.0), 0.5, 0.7);
+            }
+        }
+    }
+}
+
+fn move_camera(
+    time: Res<Time>,
+    input: Res<Input<KeyCode>>,
+    mut cameras: Query<&mut Transform, With<Camera>>,
+) {
+    let mut direction = Vec3::ZERO;
+    if input.pressed(KeyCode::Left) {
+        direction.x -= 1.0;
+    }
+    if input.pressed(KeyCode::Right) {
+        direction.x += 1.0;
+    }
+    if input.pressed(KeyCode::Down) {
+        direction.y -= 1.0;
+    }
+    if input.pressed(KeyCode::Up) {
+        direction.y += 1.0;
+    }
+    for mut transform in cameras.iter_mut() {
+        transform.translation += direction * 500.0 * time.delta_seconds();
+    }
+}
@@ -0,0 +1,106 @@
+//! Draws debug shapes in 3D with the `Gizmos` system parameter, like the bounds of a volume and
+//! the path of an agent.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(draw_gizmos)
+        .add_system(update_config)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    // plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+    // cube
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..default()
+    });
+    // light
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            intensity: 1500.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+    // instructions
+    commands.spawn_bundle(TextBundle {
+        text: Text::with_section(
+            "Press 'T' to toggle drawing the gizmos on top of the meshes\n\
+            Press 'G' to toggle the gizmos",
+            TextStyle {
+                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 20.0,
+                color: Color::WHITE,
+                ..default()
+            },
+            default(),
+        ),
+        style: Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(12.0),
+                left: Val::Px(12.0),
+                ..default()
+            },
+            ..default()
+        },
+        ..default()
+    });
+}
+
+fn draw_gizmos(mut gizmos: Gizmos, time: Res<Time>) {
+    // The bounds of the cube
+    gizmos.cuboid(
+        Transform::from_xyz(0.0, 0.5, 0.0).with_scale(Vec3::splat(1.25)),
+        Color::BLACK,
+    );
+    // The path of an agent circling the cube, drawn again every frame as it moves
+    let t = time.seconds_since_startup() as f32;
+    let path = (0..=16).map(|i| {
+        let angle = t + i as f32 * 0.1;
+        Vec3::new(angle.cos() * 1.8, 0.1, angle.sin() * 1.8)
+    });
+    gizmos.linestrip(path, Color::YELLOW);
+    let agent = Vec3::new(t.cos() * 1.8, 0.1, t.sin() * 1.8);
+    gizmos.sphere(agent, Quat::IDENTITY, 0.1, Color::RED);
+    gizmos.ray(agent, Vec3::Y * 0.5, Color::GREEN);
+    gizmos.rect(
+        Vec3::new(0.0, 0.01, 0.0),
+        Quat::from_rotation_x(std::f32::consts::FRAC_PI_2),
+        Vec2::splat(4.0),
+        Color::NAVY,
+    );
+}
+
+fn update_config(mut config: ResMut<GizmoConfig>, keyboard: Res<Input<KeyCode>>) {
+    if keyboard.just_pressed(KeyCode::T) {
+        config.on_top = !config.on_top;
+    }
+    if keyboard.just_pressed(KeyCode::G) {
+        config.enabled = !config.enabled;
+    }
+}
@@ -0,0 +1,108 @@
+//! Illustrates bloom, which makes the emissive materials of a camera rendering in HDR glow.
+
+use bevy::prelude::*;
+
+fn main() {
+    println!(
+        "Controls:
+    Up/Down    - increase/decrease the intensity of the bloom
+    Left/Right - decrease/increase the threshold of the bloom
+    T          - cycle through the tonemapping operators"
+    );
+    App::new()
+        .insert_resource(ClearColor(Color::BLACK))
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(adjust_bloom)
+        .run();
+}
+
+/// set up a row of glowing spheres of increasing brightness
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let sphere = meshes.add(Mesh::from(shape::UVSphere {
+        radius: 0.4,
+        ..default()
+    }));
+    for i in 0..5 {
+        let brightness = 1.0 + 4.0 * i as f32;
+        commands.spawn_bundle(PbrBundle {
+            mesh: sphere.clone(),
+            material: materials.add(StandardMaterial {
+                base_color: Color::BLACK,
+                emissive: Color::rgb_linear(brightness, 0.4 * brightness, 0.1 * brightness),
+                ..default()
+            }),
+            transform: Transform::from_xyz(2.0 * i as f32 - 4.0, 0.5, 0.0),
+            ..default()
+        });
+    }
+
+    // ground plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 20.0 })),
+        material: materials.add(StandardMaterial {
+            base_color: Color::rgb(0.3, 0.3, 0.3),
+            perceptual_roughness: 1.0,
+            ..default()
+        }),
+        ..default()
+    });
+
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            intensity: 400.0,
+            ..default()
+        },
+        transform: Transform::from_xyz(0.0, 4.0, 2.0),
+        ..default()
+    });
+
+    // the camera renders in HDR, which bloom needs
+    commands
+        .spawn_bundle(Camera3dBundle {
+            camera_3d: Camera3d {
+                hdr: true,
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 3.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(BloomSettings::default());
+}
+
+fn adjust_bloom(
+    input: Res<Input<KeyCode>>,
+    mut cameras: Query<(&mut Camera3d, &mut BloomSettings)>,
+) {
+    for (mut camera_3d, mut bloom) in cameras.iter_mut() {
+        if input.just_pressed(KeyCode::Up) {
+            bloom.intensity += 0.1;
+            println!("Intensity: {:.1}", bloom.intensity);
+        }
+        if input.just_pressed(KeyCode::Down) {
+            bloom.intensity = (bloom.intensity - 0.1).max(0.0);
+            println!("Intensity: {:.1}", bloom.intensity);
+        }
+        if input.just_pressed(KeyCode::Left) {
+            bloom.threshold = (bloom.threshold - 0.5).max(0.0);
+            println!("Threshold: {:.1}", bloom.threshold);
+        }
+        if input.just_pressed(KeyCode::Right) {
+            bloom.threshold += 0.5;
+            println!("Threshold: {:.1}", bloom.threshold);
+        }
+        if input.just_pressed(KeyCode::T) {
+            camera_3d.tonemapping = match camera_3d.tonemapping {
+                Tonemapping::None => Tonemapping::Reinhard,
+                Tonemapping::Reinhard => Tonemapping::ReinhardLuminance,
+                Tonemapping::ReinhardLuminance => Tonemapping::Aces,
+                Tonemapping::Aces => Tonemapping::None,
+            };
+            println!("Tonemapping: {:?}", camera_3d.tonemapping);
+        }
+    }
+}
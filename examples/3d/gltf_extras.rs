@@ -0,0 +1,68 @@
+//! Converts the extras of the nodes of a glTF file, like the custom properties of objects in
+//! Blender, into components when its scene is spawned.
+
+use bevy::{gltf::AddGltfExtrasMapping, prelude::*};
+use serde::Deserialize;
+
+fn main() {
+    App::new()
+        .insert_resource(AmbientLight {
+            color: Color::WHITE,
+            brightness: 1.0 / 5.0f32,
+        })
+        .add_plugins(DefaultPlugins)
+        // Extras with the name of a component registered with `#[reflect(Component, Deserialize)]`,
+        // like `"Spin": { "speed": 1.0 }`, are deserialized as that component
+        .register_type::<Spin>()
+        // Other extras can be converted with a mapping, like `"bounce_height": 0.5`
+        .add_gltf_extras_mapping("bounce_height", |value, entity| {
+            if let Some(height) = value.as_f64() {
+                entity.insert(Bounce {
+                    height: height as f32,
+                });
+            }
+        })
+        .add_startup_system(setup)
+        .add_system(spin)
+        .add_system(bounce)
+        .run();
+}
+
+#[derive(Component, Reflect, Deserialize, Default)]
+#[reflect(Component, Deserialize)]
+struct Spin {
+    speed: f32,
+}
+
+#[derive(Component)]
+struct Bounce {
+    height: f32,
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 4.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    commands.spawn_bundle(SceneBundle {
+        scene: asset_server.load("models/cube/cube_extras.gltf#Scene0"),
+        ..default()
+    });
+}
+
+fn spin(time: Res<Time>, mut query: Query<(&Spin, &mut Transform)>) {
+    for (spin, mut transform) in query.iter_mut() {
+        transform.rotate_y(spin.speed * time.delta_seconds());
+    }
+}
+
+fn bounce(time: Res<Time>, mut query: Query<(&Bounce, &mut Transform)>) {
+    for (bounce, mut transform) in query.iter_mut() {
+        transform.translation.y =
+            bounce.height * (time.seconds_since_startup() as f32 * 3.0).sin().abs();
+    }
+}
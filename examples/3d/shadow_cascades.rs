@@ -0,0 +1,103 @@
+//! Demonstrates how the cascades of a directional light keep shadows sharp close to the camera
+//! while still casting them far away.
+
+use bevy::prelude::*;
+
+fn main() {
+    println!(
+        "Controls:
+    Up/Down    - increase/decrease the number of cascades
+    Left/Right - decrease/increase the lambda of the cascade splits
+    C          - toggle the debug visualization of the cascades"
+    );
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(move_camera)
+        .add_system(adjust_cascades)
+        .run();
+}
+
+/// set up a long row of pillars lit by the sun
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let pillar_mesh = meshes.add(Mesh::from(shape::Box::new(0.5, 3.0, 0.5)));
+    let pillar_material = materials.add(StandardMaterial {
+        base_color: Color::rgb(0.8, 0.7, 0.6),
+        perceptual_roughness: 1.0,
+        ..default()
+    });
+    for i in 0..100 {
+        for x in [-3.0, 3.0] {
+            commands.spawn_bundle(PbrBundle {
+                mesh: pillar_mesh.clone(),
+                material: pillar_material.clone(),
+                transform: Transform::from_xyz(x, 1.5, -4.0 * i as f32),
+                ..default()
+            });
+        }
+    }
+
+    // ground plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 1000.0 })),
+        material: materials.add(StandardMaterial {
+            base_color: Color::rgb(0.3, 0.5, 0.3),
+            perceptual_roughness: 1.0,
+            ..default()
+        }),
+        ..default()
+    });
+
+    commands.spawn_bundle(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            shadows_enabled: true,
+            shadow_cascade_count: 4,
+            shadow_cascade_max_distance: 150.0,
+            ..default()
+        },
+        transform: Transform::from_rotation(Quat::from_euler(EulerRot::ZYX, 0.0, 1.0, -0.6)),
+        ..default()
+    });
+
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 2.0, 10.0)
+            .looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+        ..default()
+    });
+}
+
+/// walk the camera along the pillars, and back
+fn move_camera(time: Res<Time>, mut cameras: Query<&mut Transform, With<Camera>>) {
+    let walked = 1.0 - (time.seconds_since_startup() as f32 * 0.1).cos();
+    for mut transform in cameras.iter_mut() {
+        transform.translation.z = 10.0 - 150.0 * walked;
+    }
+}
+
+fn adjust_cascades(input: Res<Input<KeyCode>>, mut lights: Query<&mut DirectionalLight>) {
+    for mut light in lights.iter_mut() {
+        if input.just_pressed(KeyCode::Up) {
+            light.shadow_cascade_count = (light.shadow_cascade_count + 1).min(4);
+            println!("Cascades: {}", light.shadow_cascade_count);
+        }
+        if input.just_pressed(KeyCode::Down) {
+            light.shadow_cascade_count = (light.shadow_cascade_count - 1).max(1);
+            println!("Cascades: {}", light.shadow_cascade_count);
+        }
+        if input.just_pressed(KeyCode::Left) {
+            light.shadow_cascade_lambda = (light.shadow_cascade_lambda - 0.1).clamp(0.0, 1.0);
+            println!("Lambda: {:.1}", light.shadow_cascade_lambda);
+        }
+        if input.just_pressed(KeyCode::Right) {
+            light.shadow_cascade_lambda = (light.shadow_cascade_lambda + 0.1).clamp(0.0, 1.0);
+            println!("Lambda: {:.1}", light.shadow_cascade_lambda);
+        }
+        if input.just_pressed(KeyCode::C) {
+            light.debug_shadow_cascades = !light.debug_shadow_cascades;
+        }
+    }
+}
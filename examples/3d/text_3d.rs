@@ -0,0 +1,96 @@
+//! Draws text in world space, with name tags that always face the camera.
+//!
+//! Requires the `text3d` feature.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(rotate_camera)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let centered = TextAlignment {
+        vertical: VerticalAlign::Center,
+        horizontal: HorizontalAlign::Center,
+    };
+
+    // plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+    // a sign lying on the ground, keeping its orientation
+    commands.spawn_bundle(Text3dBundle {
+        text: Text::with_section(
+            "Welcome!",
+            TextStyle::default().font(font.clone()).size(60.0),
+            centered,
+        ),
+        transform: Transform::from_xyz(0.0, 0.01, 1.5)
+            .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+        ..default()
+    });
+    // cubes with name tags
+    for (name, x, color) in [("Alice", -1.0, Color::CYAN), ("Bob", 1.0, Color::ORANGE)] {
+        commands
+            .spawn_bundle(PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Cube { size: 0.5 })),
+                material: materials.add(color.into()),
+                transform: Transform::from_xyz(x, 0.25, 0.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn_bundle(Text3dBundle {
+                    text: Text::with_section(
+                        name,
+                        TextStyle::default()
+                            .font(font.clone())
+                            .size(40.0)
+                            .color(color),
+                        centered,
+                    ),
+                    text_3d: Text3d {
+                        billboard: true,
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.6, 0.0),
+                    ..default()
+                });
+            });
+    }
+    // light
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            intensity: 1500.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+fn rotate_camera(time: Res<Time>, mut query: Query<&mut Transform, With<Camera3d>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotate_around(
+            Vec3::ZERO,
+            Quat::from_rotation_y(time.delta_seconds() * 0.3),
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Create and play an animation defined by code that operates on fields of UI components,
+//! accessed by reflection.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Components other than `Transform` must be registered to be animated
+        .add_animated_component::<Style>()
+        .add_animated_component::<UiColor>()
+        .add_animated_component::<Text>()
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut animations: ResMut<Assets<AnimationClip>>,
+) {
+    // Camera
+    commands.spawn_bundle(Camera2dBundle::default());
+
+    // The animation API uses the `Name` component to target entities
+    let panel = Name::new("panel");
+    let label = Name::new("label");
+
+    // Creating the animation
+    let mut animation = AnimationClip::default();
+    // A curve can modify a field of a component, found with a path. Here the width of the panel
+    animation.add_curve_to_path(
+        EntityPath {
+            parts: vec![panel.clone()],
+        },
+        VariableCurve {
+            keyframe_timestamps: vec![0.0, 1.0, 2.0],
+            keyframes: Keyframes::Property(
+                PropertyKeyframes::new::<Style, Val>(
+                    "size.width",
+                    vec![Val::Px(300.0), Val::Px(500.0), Val::Px(300.0)],
+                )
+                .unwrap(),
+            ),
+        },
+    );
+    // Or its color, the first field of `UiColor`
+    animation.add_curve_to_path(
+        EntityPath {
+            parts: vec![panel.clone()],
+        },
+        VariableCurve {
+            keyframe_timestamps: vec![0.0, 1.0, 2.0],
+            keyframes: Keyframes::Property(
+                PropertyKeyframes::new::<UiColor, Color>(
+                    "0",
+                    vec![
+                        Color::rgb(0.15, 0.15, 0.35),
+                        Color::rgb(0.35, 0.15, 0.15),
+                        Color::rgb(0.15, 0.15, 0.35),
+                    ],
+                )
+                .unwrap(),
+            ),
+        },
+    );
+    // Fields of nested values are separated by dots, and items of lists are accessed by index
+    animation.add_curve_to_path(
+        EntityPath {
+            parts: vec![panel.clone(), label.clone()],
+        },
+        VariableCurve {
+            keyframe_timestamps: vec![0.0, 0.5, 1.0, 1.5, 2.0],
+            keyframes: Keyframes::Property(
+                PropertyKeyframes::new::<Text, Color>(
+                    "sections[0].style.color",
+                    vec![
+                        Color::WHITE,
+                        Color::YELLOW,
+                        Color::WHITE,
+                        Color::CYAN,
+                        Color::WHITE,
+                    ],
+                )
+                .unwrap(),
+            ),
+        },
+    );
+
+    // Create the animation player, and set it to repeat
+    let mut player = AnimationPlayer::default();
+    player.play(animations.add(animation)).repeat();
+
+    // Create the UI that will be animated
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            // The root of the animation is the panel
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(300.0), Val::Px(100.0)),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                // Add the Name component, and the animation player
+                .insert_bundle((panel, player))
+                .with_children(|parent| {
+                    parent
+                        .spawn_bundle(TextBundle {
+                            text: Text::with_section(
+                                "Animated",
+                                TextStyle {
+                                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                                    font_size: 40.0,
+                                    color: Color::WHITE,
+                                    ..default()
+                                },
+                                Default::default(),
+                            ),
+                            ..default()
+                        })
+                        // Add the Name component
+                        .insert(label);
+                });
+        });
+}
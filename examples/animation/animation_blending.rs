@@ -0,0 +1,138 @@
+//! Blends the walk and run animations of a skinned glTF depending on the speed of the character,
+//! and cross-fades to the idle animation when it stops.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .insert_resource(AmbientLight {
+            color: Color::WHITE,
+            brightness: 1.0,
+        })
+        .init_resource::<CharacterSpeed>()
+        .add_startup_system(setup)
+        .add_system(keyboard_speed_control)
+        .add_system(blend_animations)
+        .run();
+}
+
+struct Animations {
+    idle: Handle<AnimationClip>,
+    walk: Handle<AnimationClip>,
+    run: Handle<AnimationClip>,
+}
+
+/// The speed of the character, from `0.0` when idle to `2.0` when running
+#[derive(Default)]
+struct CharacterSpeed(f32);
+
+const TRANSITION_DURATION: Duration = Duration::from_millis(300);
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(Animations {
+        idle: asset_server.load("models/animated/Fox.glb#Animation0"),
+        walk: asset_server.load("models/animated/Fox.glb#Animation1"),
+        run: asset_server.load("models/animated/Fox.glb#Animation2"),
+    });
+
+    // Camera
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(100.0, 100.0, 150.0)
+            .looking_at(Vec3::new(0.0, 20.0, 0.0), Vec3::Y),
+        ..default()
+    });
+
+    // Plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 500000.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+
+    // Light
+    commands.spawn_bundle(DirectionalLightBundle {
+        transform: Transform::from_rotation(Quat::from_euler(
+            EulerRot::ZYX,
+            0.0,
+            1.0,
+            -std::f32::consts::FRAC_PI_4,
+        )),
+        directional_light: DirectionalLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        ..default()
+    });
+
+    // Fox
+    commands.spawn_bundle(SceneBundle {
+        scene: asset_server.load("models/animated/Fox.glb#Scene0"),
+        ..default()
+    });
+
+    println!("Animation controls:");
+    println!("  - arrow up / down: speed up / slow down the fox");
+}
+
+fn keyboard_speed_control(
+    keyboard_input: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut speed: ResMut<CharacterSpeed>,
+) {
+    let mut change = 0.0;
+    if keyboard_input.pressed(KeyCode::Up) {
+        change += 1.0;
+    }
+    if keyboard_input.pressed(KeyCode::Down) {
+        change -= 1.0;
+    }
+    if change != 0.0 {
+        speed.0 = (speed.0 + change * time.delta_seconds()).clamp(0.0, 2.0);
+    }
+}
+
+fn blend_animations(
+    animations: Res<Animations>,
+    speed: Res<CharacterSpeed>,
+    mut players: Query<&mut AnimationPlayer>,
+    mut moving: Local<Option<bool>>,
+) {
+    let mut player = match players.get_single_mut() {
+        Ok(player) => player,
+        Err(_) => return,
+    };
+
+    let is_moving = speed.0 > 0.0;
+    if *moving != Some(is_moving) {
+        if is_moving {
+            // Fade in the walk animation, the run animation is blended with it below
+            player
+                .cross_fade(animations.walk.clone_weak(), TRANSITION_DURATION)
+                .repeat()
+                .play_blended(animations.run.clone_weak(), 0.0)
+                .repeat();
+        } else {
+            player
+                .cross_fade(animations.idle.clone_weak(), TRANSITION_DURATION)
+                .repeat();
+        }
+        *moving = Some(is_moving);
+    }
+
+    // Once the transition is done, blend walking and running depending on the speed
+    if is_moving && !player.is_fading() {
+        let run_weight = (speed.0 - 1.0).clamp(0.0, 1.0);
+        player
+            .set_weight(&animations.walk, 1.0 - run_weight)
+            .set_weight(&animations.run, run_weight)
+            .set_speed(speed.0.clamp(1.0, 1.5));
+    }
+}
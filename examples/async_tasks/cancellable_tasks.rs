@@ -0,0 +1,108 @@
+//! This example shows how to spawn long-running tasks with a [`TaskHandle`], to follow their
+//! progress and cancel them from systems.
+
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, TaskHandle},
+};
+
+fn main() {
+    println!(
+        "Controls:
+    Space - start a job
+    Escape - cancel the running jobs"
+    );
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(start_jobs)
+        .add_system(cancel_jobs)
+        .add_system(handle_jobs)
+        .run();
+}
+
+/// The primes counted by a job
+const PRIMES_BELOW: u64 = 2_000_000;
+
+/// A job counting the primes below [`PRIMES_BELOW`], [`None`] if it was cancelled
+#[derive(Component)]
+struct Job(TaskHandle<Option<usize>>);
+
+#[derive(Component)]
+struct JobsText;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn_bundle(Camera2dBundle::default());
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                margin: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 30.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+                default(),
+            ),
+            ..default()
+        })
+        .insert(JobsText);
+}
+
+fn start_jobs(mut commands: Commands, input: Res<Input<KeyCode>>) {
+    if !input.just_pressed(KeyCode::Space) {
+        return;
+    }
+    let task = AsyncComputeTaskPool::get().spawn_cancellable(|context| async move {
+        let mut count = 0;
+        for n in 2..PRIMES_BELOW {
+            // Cancellation is cooperative: the job checks regularly if it should stop
+            if n % 1024 == 0 {
+                if context.is_cancelled() {
+                    return None;
+                }
+                context.set_progress(n as f32 / PRIMES_BELOW as f32);
+            }
+            if (2..).take_while(|d| d * d <= n).all(|d| n % d != 0) {
+                count += 1;
+            }
+        }
+        Some(count)
+    });
+    commands.spawn().insert(Job(task));
+}
+
+fn cancel_jobs(input: Res<Input<KeyCode>>, jobs: Query<&Job>) {
+    if input.just_pressed(KeyCode::Escape) {
+        for job in jobs.iter() {
+            job.0.cancel();
+        }
+    }
+}
+
+/// Polls the jobs without blocking, and shows the progress of the running ones
+fn handle_jobs(
+    mut commands: Commands,
+    mut jobs: Query<(Entity, &mut Job)>,
+    mut text: Query<&mut Text, With<JobsText>>,
+) {
+    let mut progress = String::new();
+    for (entity, mut job) in jobs.iter_mut() {
+        match job.0.take_output() {
+            Some(Some(count)) => info!("{:?} found {} primes", entity, count),
+            Some(None) => info!("{:?} was cancelled", entity),
+            None => {
+                progress += &format!("{:?}: {:.0}%\n", entity, job.0.progress() * 100.0);
+                continue;
+            }
+        }
+        // Despawning the entity drops the handle, which would cancel the job if it was running
+        commands.entity(entity).despawn();
+    }
+    text.single_mut().sections[0].value = progress;
+}
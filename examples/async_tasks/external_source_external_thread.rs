@@ -61,6 +61,7 @@ fn spawn_text(
         font: loaded_font.clone(),
         font_size: 20.0,
         color: Color::WHITE,
+        ..default()
     };
     let text_alignment = TextAlignment {
         vertical: VerticalAlign::Center,
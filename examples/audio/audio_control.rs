@@ -1,6 +1,6 @@
 //! This example illustrates how to load and play an audio file, and control how it's played.
 
-use bevy::{audio::AudioSink, prelude::*};
+use bevy::{audio::AudioSink, prelude::*, utils::Duration};
 
 fn main() {
     App::new()
@@ -9,17 +9,14 @@ fn main() {
         .add_system(update_speed)
         .add_system(pause)
         .add_system(volume)
+        .add_system(seek)
         .run();
 }
 
-fn setup(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
-    audio_sinks: Res<Assets<AudioSink>>,
-) {
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, audio: Res<Audio>) {
     let music = asset_server.load("sounds/Windless Slopes.ogg");
-    let handle = audio_sinks.get_handle(audio.play(music));
+    // the returned handle keeps the sink alive to control the playback
+    let handle = audio.play_with_settings(music, PlaybackSettings::LOOP);
     commands.insert_resource(MusicController(handle));
 }
 
@@ -43,7 +40,7 @@ fn pause(
     if keyboard_input.just_pressed(KeyCode::Space) {
         if let Some(sink) = audio_sinks.get(&music_controller.0) {
             if sink.is_paused() {
-                sink.play();
+                sink.resume();
             } else {
                 sink.pause();
             }
@@ -64,3 +61,15 @@ fn volume(
         }
     }
 }
+
+fn seek(
+    keyboard_input: Res<Input<KeyCode>>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    music_controller: Res<MusicController>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Return) {
+        if let Some(sink) = audio_sinks.get(&music_controller.0) {
+            sink.seek(Duration::ZERO);
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! This example illustrates how to play a sound from a position in the world, panned and
+//! attenuated relative to the listener.
+
+use bevy::{
+    audio::{Attenuation, SpatialAudioEmitter},
+    prelude::*,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(orbit)
+        .run();
+}
+
+#[derive(Component)]
+struct Orbit;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // the sound must be played as a spatial sound to follow its emitter
+    let sink = audio.play_with_settings(
+        asset_server.load("sounds/Windless Slopes.ogg"),
+        PlaybackSettings::LOOP.with_spatial(true),
+    );
+
+    // the emitter orbits around the listener, and is louder when it is closer
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Icosphere {
+                radius: 0.3,
+                subdivisions: 4,
+            })),
+            material: materials.add(Color::rgb(0.9, 0.4, 0.3).into()),
+            ..default()
+        })
+        .insert(
+            SpatialAudioEmitter::new(sink)
+                .with_attenuation(Attenuation::Inverse {
+                    reference_distance: 2.0,
+                    rolloff: 1.0,
+                })
+                .with_doppler(true),
+        )
+        .insert(Orbit);
+
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(0.0, 8.0, 0.0),
+        ..default()
+    });
+    // the camera is the listener, with its right ear towards the right of the screen
+    commands
+        .spawn_bundle(Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 10.0, 0.1).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(AudioListener::default());
+}
+
+fn orbit(time: Res<Time>, mut query: Query<&mut Transform, With<Orbit>>) {
+    let angle = time.seconds_since_startup() as f32 * 0.8;
+    for mut transform in query.iter_mut() {
+        transform.translation = Vec3::new(angle.cos() * 6.0, 0.0, angle.sin() * 3.0);
+    }
+}
@@ -0,0 +1,36 @@
+//! Displays the diagnostics, like frames per second (FPS), as a text overlay in the window.
+//!
+//! Press F12 to toggle the overlay.
+
+use bevy::{
+    diagnostic::{EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+    ui::{DiagnosticsOverlayConfig, DiagnosticsOverlayPlugin},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Adds frame time and entity count diagnostics
+        .add_plugin(FrameTimeDiagnosticsPlugin::default())
+        .add_plugin(EntityCountDiagnosticsPlugin::default())
+        // Adds an overlay displaying the diagnostics, and a graph of the frame times
+        .add_plugin(DiagnosticsOverlayPlugin::default())
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut config: ResMut<DiagnosticsOverlayConfig>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn_bundle(Camera2dBundle::default());
+    // The overlay has no font by default
+    config.text_style = TextStyle {
+        font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+        font_size: 18.0,
+        color: Color::WHITE,
+        ..default()
+    };
+}
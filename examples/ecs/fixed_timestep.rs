@@ -1,33 +1,16 @@
 //! Shows how to create systems that run every fixed timestep, rather than every tick.
 
-use bevy::{
-    prelude::*,
-    time::{FixedTimestep, FixedTimesteps},
-};
-
-const LABEL: &str = "my_fixed_timestep";
-
-#[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
-struct FixedUpdateStage;
+use bevy::prelude::*;
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
+        // the `FixedUpdate` schedule runs twice a second
+        .insert_resource(FixedTime::new_from_secs(0.5))
         // this system will run once every update (it should match your screen's refresh rate)
         .add_system(frame_update)
-        // add a new stage that runs twice a second
-        .add_stage_after(
-            CoreStage::Update,
-            FixedUpdateStage,
-            SystemStage::parallel()
-                .with_run_criteria(
-                    FixedTimestep::step(0.5)
-                        // labels are optional. they provide a way to access the current
-                        // FixedTimestep state from within a system
-                        .with_label(LABEL),
-                )
-                .with_system(fixed_update),
-        )
+        // this system will run once every fixed timestep
+        .add_system_to_schedule(FixedUpdate, fixed_update)
         .run();
 }
 
@@ -36,16 +19,16 @@ fn frame_update(mut last_time: Local<f64>, time: Res<Time>) {
     *last_time = time.seconds_since_startup();
 }
 
-fn fixed_update(mut last_time: Local<f64>, time: Res<Time>, fixed_timesteps: Res<FixedTimesteps>) {
+fn fixed_update(mut last_time: Local<f64>, time: Res<Time>, fixed_time: Res<FixedTime>) {
     info!(
         "fixed_update: {}",
         time.seconds_since_startup() - *last_time,
     );
 
-    let fixed_timestep = fixed_timesteps.get(LABEL).unwrap();
+    // the time accumulated towards the next step, as a fraction of the period
     info!(
         "  overstep_percentage: {}",
-        fixed_timestep.overstep_percentage()
+        fixed_time.overstep_percentage()
     );
 
     *last_time = time.seconds_since_startup();
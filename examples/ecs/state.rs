@@ -6,22 +6,23 @@ use bevy::prelude::*;
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_state(AppState::Menu)
+        .add_state::<AppState>()
         .add_startup_system(setup)
-        .add_system_set(SystemSet::on_enter(AppState::Menu).with_system(setup_menu))
-        .add_system_set(SystemSet::on_update(AppState::Menu).with_system(menu))
-        .add_system_set(SystemSet::on_exit(AppState::Menu).with_system(cleanup_menu))
-        .add_system_set(SystemSet::on_enter(AppState::InGame).with_system(setup_game))
-        .add_system_set(
-            SystemSet::on_update(AppState::InGame)
-                .with_system(movement)
-                .with_system(change_color),
-        )
+        // This system runs when we enter `AppState::Menu`
+        .add_system_to_schedule(OnEnter(AppState::Menu), setup_menu)
+        // This system runs every frame while we are in `AppState::Menu`
+        .add_system(menu.run_if(in_state(AppState::Menu)))
+        // This system runs when we exit `AppState::Menu`
+        .add_system_to_schedule(OnExit(AppState::Menu), cleanup_menu)
+        .add_system_to_schedule(OnEnter(AppState::InGame), setup_game)
+        .add_system(movement.run_if(in_state(AppState::InGame)))
+        .add_system(change_color.run_if(in_state(AppState::InGame)))
         .run();
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(States, Debug, Clone, Eq, PartialEq, Hash, Default)]
 enum AppState {
+    #[default]
     Menu,
     InGame,
 }
@@ -62,6 +63,7 @@ fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: 40.0,
                         color: Color::rgb(0.9, 0.9, 0.9),
+                        ..default()
                     },
                     Default::default(),
                 ),
@@ -73,7 +75,7 @@ fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
 }
 
 fn menu(
-    mut state: ResMut<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
     mut interaction_query: Query<
         (&Interaction, &mut UiColor),
         (Changed<Interaction>, With<Button>),
@@ -83,7 +85,7 @@ fn menu(
         match *interaction {
             Interaction::Clicked => {
                 *color = PRESSED_BUTTON.into();
-                state.set(AppState::InGame).unwrap();
+                next_state.set(AppState::InGame);
             }
             Interaction::Hovered => {
                 *color = HOVERED_BUTTON.into();
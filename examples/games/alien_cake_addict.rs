@@ -3,8 +3,9 @@
 use bevy::{ecs::schedule::SystemSet, prelude::*, time::FixedTimestep};
 use rand::Rng;
 
-#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+#[derive(States, Clone, Eq, PartialEq, Debug, Hash, Default)]
 enum GameState {
+    #[default]
     Playing,
     GameOver,
 }
@@ -13,24 +14,21 @@ fn main() {
     App::new()
         .init_resource::<Game>()
         .add_plugins(DefaultPlugins)
-        .add_state(GameState::Playing)
+        .add_state::<GameState>()
         .add_startup_system(setup_cameras)
-        .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(setup))
-        .add_system_set(
-            SystemSet::on_update(GameState::Playing)
-                .with_system(move_player)
-                .with_system(focus_camera)
-                .with_system(rotate_bonus)
-                .with_system(scoreboard_system),
-        )
-        .add_system_set(SystemSet::on_exit(GameState::Playing).with_system(teardown))
-        .add_system_set(SystemSet::on_enter(GameState::GameOver).with_system(display_score))
-        .add_system_set(SystemSet::on_update(GameState::GameOver).with_system(gameover_keyboard))
-        .add_system_set(SystemSet::on_exit(GameState::GameOver).with_system(teardown))
+        .add_system_to_schedule(OnEnter(GameState::Playing), setup)
+        .add_system(move_player.run_if(in_state(GameState::Playing)))
+        .add_system(focus_camera.run_if(in_state(GameState::Playing)))
+        .add_system(rotate_bonus.run_if(in_state(GameState::Playing)))
+        .add_system(scoreboard_system.run_if(in_state(GameState::Playing)))
+        .add_system_to_schedule(OnExit(GameState::Playing), teardown)
+        .add_system_to_schedule(OnEnter(GameState::GameOver), display_score)
+        .add_system(gameover_keyboard.run_if(in_state(GameState::GameOver)))
+        .add_system_to_schedule(OnExit(GameState::GameOver), teardown)
         .add_system_set(
             SystemSet::new()
                 .with_run_criteria(FixedTimestep::step(5.0))
-                .with_system(spawn_bonus),
+                .with_system(spawn_bonus.run_if(in_state(GameState::Playing))),
         )
         .add_system(bevy::window::close_on_esc)
         .run();
@@ -157,6 +155,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>, mut game: ResMu
                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                 font_size: 40.0,
                 color: Color::rgb(0.5, 0.5, 1.0),
+                ..default()
             },
             Default::default(),
         ),
@@ -291,20 +290,16 @@ fn focus_camera(
 
 // despawn the bonus if there is one, then spawn a new one at a random location
 fn spawn_bonus(
-    mut state: ResMut<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
     mut commands: Commands,
     mut game: ResMut<Game>,
 ) {
-    if *state.current() != GameState::Playing {
-        return;
-    }
     if let Some(entity) = game.bonus.entity {
         game.score -= 3;
         commands.entity(entity).despawn_recursive();
         game.bonus.entity = None;
         if game.score <= -5 {
-            // We don't particularly care if this operation fails
-            let _ = state.overwrite_set(GameState::GameOver);
+            next_state.set(GameState::GameOver);
             return;
         }
     }
@@ -363,9 +358,12 @@ fn scoreboard_system(game: Res<Game>, mut query: Query<&mut Text>) {
 }
 
 // restart the game when pressing spacebar
-fn gameover_keyboard(mut state: ResMut<State<GameState>>, keyboard_input: Res<Input<KeyCode>>) {
+fn gameover_keyboard(
+    mut next_state: ResMut<NextState<GameState>>,
+    keyboard_input: Res<Input<KeyCode>>,
+) {
     if keyboard_input.just_pressed(KeyCode::Space) {
-        state.set(GameState::Playing).unwrap();
+        next_state.set(GameState::Playing);
     }
 }
 
@@ -390,6 +388,7 @@ fn display_score(mut commands: Commands, asset_server: Res<AssetServer>, game: R
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: 80.0,
                         color: Color::rgb(0.5, 0.5, 1.0),
+                        ..default()
                     },
                     Default::default(),
                 ),
@@ -225,6 +225,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: SCOREBOARD_FONT_SIZE,
                         color: TEXT_COLOR,
+                        ..default()
                     },
                 },
                 TextSection {
@@ -233,6 +234,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                         font_size: SCOREBOARD_FONT_SIZE,
                         color: SCORE_COLOR,
+                        ..default()
                     },
                 },
             ],
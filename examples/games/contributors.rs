@@ -150,6 +150,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                             font_size: 60.0,
                             color: Color::WHITE,
+                            ..default()
                         },
                     },
                     TextSection {
@@ -158,6 +159,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                             font_size: 60.0,
                             color: Color::WHITE,
+                            ..default()
                         },
                     },
                 ],
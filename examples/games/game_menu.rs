@@ -7,8 +7,9 @@ use bevy::prelude::*;
 const TEXT_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
 
 // Enum that will be used as a global state for the game
-#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+#[derive(States, Clone, Eq, PartialEq, Debug, Hash, Default)]
 enum GameState {
+    #[default]
     Splash,
     Menu,
     Game,
@@ -33,8 +34,8 @@ fn main() {
         .insert_resource(DisplayQuality::Medium)
         .insert_resource(Volume(7))
         .add_startup_system(setup)
-        // Declare the game state, and set its startup value
-        .add_state(GameState::Splash)
+        // Declare the game state, whose starting value is determined by the `Default` trait
+        .add_state::<GameState>()
         // Adds the plugins for each state
         .add_plugin(splash::SplashPlugin)
         .add_plugin(menu::MenuPlugin)
@@ -59,13 +60,13 @@ mod splash {
             // As this plugin is managing the splash screen, it will focus on the state `GameState::Splash`
             app
                 // When entering the state, spawn everything needed for this screen
-                .add_system_set(SystemSet::on_enter(GameState::Splash).with_system(splash_setup))
+                .add_system_to_schedule(OnEnter(GameState::Splash), splash_setup)
                 // While in this state, run the `countdown` system
-                .add_system_set(SystemSet::on_update(GameState::Splash).with_system(countdown))
+                .add_system(countdown.run_if(in_state(GameState::Splash)))
                 // When exiting the state, despawn everything that was spawned for this screen
-                .add_system_set(
-                    SystemSet::on_exit(GameState::Splash)
-                        .with_system(despawn_screen::<OnSplashScreen>),
+                .add_system_to_schedule(
+                    OnExit(GameState::Splash),
+                    despawn_screen::<OnSplashScreen>,
                 );
         }
     }
@@ -100,12 +101,12 @@ mod splash {
 
     // Tick the timer, and change state when finished
     fn countdown(
-        mut game_state: ResMut<State<GameState>>,
+        mut game_state: ResMut<NextState<GameState>>,
         time: Res<Time>,
         mut timer: ResMut<SplashTimer>,
     ) {
         if timer.tick(time.delta()).finished() {
-            game_state.set(GameState::Menu).unwrap();
+            game_state.set(GameState::Menu);
         }
     }
 }
@@ -121,11 +122,9 @@ mod game {
 
     impl Plugin for GamePlugin {
         fn build(&self, app: &mut App) {
-            app.add_system_set(SystemSet::on_enter(GameState::Game).with_system(game_setup))
-                .add_system_set(SystemSet::on_update(GameState::Game).with_system(game))
-                .add_system_set(
-                    SystemSet::on_exit(GameState::Game).with_system(despawn_screen::<OnGameScreen>),
-                );
+            app.add_system_to_schedule(OnEnter(GameState::Game), game_setup)
+                .add_system(game.run_if(in_state(GameState::Game)))
+                .add_system_to_schedule(OnExit(GameState::Game), despawn_screen::<OnGameScreen>);
         }
     }
 
@@ -176,6 +175,7 @@ mod game {
                             font: font.clone(),
                             font_size: 80.0,
                             color: TEXT_COLOR,
+                            ..default()
                         },
                         Default::default(),
                     ),
@@ -194,6 +194,7 @@ mod game {
                                     font: font.clone(),
                                     font_size: 60.0,
                                     color: Color::BLUE,
+                                    ..default()
                                 },
                             },
                             TextSection {
@@ -202,6 +203,7 @@ mod game {
                                     font: font.clone(),
                                     font_size: 60.0,
                                     color: TEXT_COLOR,
+                                    ..default()
                                 },
                             },
                             TextSection {
@@ -210,6 +212,7 @@ mod game {
                                     font: font.clone(),
                                     font_size: 60.0,
                                     color: Color::GREEN,
+                                    ..default()
                                 },
                             },
                         ],
@@ -225,11 +228,11 @@ mod game {
     // Tick the timer, and change state when finished
     fn game(
         time: Res<Time>,
-        mut game_state: ResMut<State<GameState>>,
+        mut game_state: ResMut<NextState<GameState>>,
         mut timer: ResMut<GameTimer>,
     ) {
         if timer.tick(time.delta()).finished() {
-            game_state.set(GameState::Menu).unwrap();
+            game_state.set(GameState::Menu);
         }
     }
 }
@@ -251,64 +254,53 @@ mod menu {
                 // At start, the menu is not enabled. This will be changed in `menu_setup` when
                 // entering the `GameState::Menu` state.
                 // Current screen in the menu is handled by an independent state from `GameState`
-                .add_state(MenuState::Disabled)
-                .add_system_set(SystemSet::on_enter(GameState::Menu).with_system(menu_setup))
+                .add_state::<MenuState>()
+                .add_system_to_schedule(OnEnter(GameState::Menu), menu_setup)
                 // Systems to handle the main menu screen
-                .add_system_set(SystemSet::on_enter(MenuState::Main).with_system(main_menu_setup))
-                .add_system_set(
-                    SystemSet::on_exit(MenuState::Main)
-                        .with_system(despawn_screen::<OnMainMenuScreen>),
-                )
+                .add_system_to_schedule(OnEnter(MenuState::Main), main_menu_setup)
+                .add_system_to_schedule(OnExit(MenuState::Main), despawn_screen::<OnMainMenuScreen>)
                 // Systems to handle the settings menu screen
-                .add_system_set(
-                    SystemSet::on_enter(MenuState::Settings).with_system(settings_menu_setup),
-                )
-                .add_system_set(
-                    SystemSet::on_exit(MenuState::Settings)
-                        .with_system(despawn_screen::<OnSettingsMenuScreen>),
+                .add_system_to_schedule(OnEnter(MenuState::Settings), settings_menu_setup)
+                .add_system_to_schedule(
+                    OnExit(MenuState::Settings),
+                    despawn_screen::<OnSettingsMenuScreen>,
                 )
                 // Systems to handle the display settings screen
-                .add_system_set(
-                    SystemSet::on_enter(MenuState::SettingsDisplay)
-                        .with_system(display_settings_menu_setup),
+                .add_system_to_schedule(
+                    OnEnter(MenuState::SettingsDisplay),
+                    display_settings_menu_setup,
                 )
-                .add_system_set(
-                    SystemSet::on_update(MenuState::SettingsDisplay)
-                        .with_system(setting_button::<DisplayQuality>),
+                .add_system(
+                    setting_button::<DisplayQuality>.run_if(in_state(MenuState::SettingsDisplay)),
                 )
-                .add_system_set(
-                    SystemSet::on_exit(MenuState::SettingsDisplay)
-                        .with_system(despawn_screen::<OnDisplaySettingsMenuScreen>),
+                .add_system_to_schedule(
+                    OnExit(MenuState::SettingsDisplay),
+                    despawn_screen::<OnDisplaySettingsMenuScreen>,
                 )
                 // Systems to handle the sound settings screen
-                .add_system_set(
-                    SystemSet::on_enter(MenuState::SettingsSound)
-                        .with_system(sound_settings_menu_setup),
+                .add_system_to_schedule(
+                    OnEnter(MenuState::SettingsSound),
+                    sound_settings_menu_setup,
                 )
-                .add_system_set(
-                    SystemSet::on_update(MenuState::SettingsSound)
-                        .with_system(setting_button::<Volume>),
-                )
-                .add_system_set(
-                    SystemSet::on_exit(MenuState::SettingsSound)
-                        .with_system(despawn_screen::<OnSoundSettingsMenuScreen>),
+                .add_system(setting_button::<Volume>.run_if(in_state(MenuState::SettingsSound)))
+                .add_system_to_schedule(
+                    OnExit(MenuState::SettingsSound),
+                    despawn_screen::<OnSoundSettingsMenuScreen>,
                 )
                 // Common systems to all screens that handles buttons behaviour
-                .add_system_set(
-                    SystemSet::on_update(GameState::Menu)
-                        .with_system(menu_action)
-                        .with_system(button_system),
-                );
+                .add_system(menu_action.run_if(in_state(GameState::Menu)))
+                .add_system(button_system.run_if(in_state(GameState::Menu)));
         }
     }
 
     // State used for the current menu screen
-    #[derive(Clone, Eq, PartialEq, Debug, Hash)]
+    #[derive(States, Clone, Eq, PartialEq, Debug, Hash, Default)]
     enum MenuState {
         Main,
         Settings,
         SettingsDisplay,
         SettingsSound,
+        #[default]
         Disabled,
     }
 
@@ -385,8 +377,8 @@ mod menu {
         }
     }
 
-    fn menu_setup(mut menu_state: ResMut<State<MenuState>>) {
-        let _ = menu_state.set(MenuState::Main);
+    fn menu_setup(mut menu_state: ResMut<NextState<MenuState>>) {
+        menu_state.set(MenuState::Main);
     }
 
     fn main_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
@@ -416,6 +408,7 @@ mod menu {
             font: font.clone(),
             font_size: 40.0,
             color: TEXT_COLOR,
+            ..default()
         };
 
         commands
@@ -443,6 +436,7 @@ mod menu {
                             font: font.clone(),
                             font_size: 80.0,
                             color: TEXT_COLOR,
+                            ..default()
                         },
                         Default::default(),
                     ),
@@ -533,6 +527,7 @@ mod menu {
             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
             font_size: 40.0,
             color: TEXT_COLOR,
+            ..default()
         };
 
         commands
@@ -616,6 +611,7 @@ mod menu {
             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
             font_size: 40.0,
             color: TEXT_COLOR,
+            ..default()
         };
 
         commands
@@ -714,6 +710,7 @@ mod menu {
             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
             font_size: 40.0,
             color: TEXT_COLOR,
+            ..default()
         };
 
         commands
@@ -784,27 +781,27 @@ mod menu {
             (Changed<Interaction>, With<Button>),
         >,
         mut app_exit_events: EventWriter<AppExit>,
-        mut menu_state: ResMut<State<MenuState>>,
-        mut game_state: ResMut<State<GameState>>,
+        mut menu_state: ResMut<NextState<MenuState>>,
+        mut game_state: ResMut<NextState<GameState>>,
     ) {
         for (interaction, menu_button_action) in interaction_query.iter() {
             if *interaction == Interaction::Clicked {
                 match menu_button_action {
                     MenuButtonAction::Quit => app_exit_events.send(AppExit),
                     MenuButtonAction::Play => {
-                        game_state.set(GameState::Game).unwrap();
-                        menu_state.set(MenuState::Disabled).unwrap();
+                        game_state.set(GameState::Game);
+                        menu_state.set(MenuState::Disabled);
                     }
-                    MenuButtonAction::Settings => menu_state.set(MenuState::Settings).unwrap(),
+                    MenuButtonAction::Settings => menu_state.set(MenuState::Settings),
                     MenuButtonAction::SettingsDisplay => {
-                        menu_state.set(MenuState::SettingsDisplay).unwrap();
+                        menu_state.set(MenuState::SettingsDisplay);
                     }
                     MenuButtonAction::SettingsSound => {
-                        menu_state.set(MenuState::SettingsSound).unwrap();
+                        menu_state.set(MenuState::SettingsSound);
                     }
-                    MenuButtonAction::BackToMainMenu => menu_state.set(MenuState::Main).unwrap(),
+                    MenuButtonAction::BackToMainMenu => menu_state.set(MenuState::Main),
                     MenuButtonAction::BackToSettings => {
-                        menu_state.set(MenuState::Settings).unwrap();
+                        menu_state.set(MenuState::Settings);
                     }
                 }
             }
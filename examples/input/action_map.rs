@@ -0,0 +1,71 @@
+//! Binds logical actions to keys, buttons and gamepad axes, and rebinds them at runtime.
+
+use bevy::{
+    input::action::{ActionMap, ActionPlugin, ActionState, InputBinding},
+    prelude::*,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum PlayerAction {
+    Jump,
+    Move,
+    Rebind,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(ActionPlugin::<PlayerAction>::default())
+        .add_startup_system(setup_bindings)
+        .add_system(player_actions)
+        .add_system(rebind_jump)
+        .run();
+}
+
+fn setup_bindings(mut action_map: ResMut<ActionMap<PlayerAction>>) {
+    action_map
+        .bind(PlayerAction::Jump, KeyCode::Space)
+        .bind(PlayerAction::Jump, GamepadButtonType::South)
+        .bind(PlayerAction::Move, KeyCode::Right)
+        .bind(
+            PlayerAction::Move,
+            InputBinding::gamepad_axis(GamepadAxisType::LeftStickX, 0.1),
+        )
+        .bind(PlayerAction::Rebind, KeyCode::R);
+}
+
+/// This system prints the state of the actions, whatever input they are bound to
+fn player_actions(action_state: Res<ActionState<PlayerAction>>) {
+    if action_state.just_pressed(PlayerAction::Jump) {
+        info!("Jump!");
+    }
+
+    if action_state.pressed(PlayerAction::Move) {
+        info!("Moving at {}", action_state.value(PlayerAction::Move));
+    }
+}
+
+/// This system binds `Jump` to the next key pressed after `Rebind`
+fn rebind_jump(
+    mut waiting_for_key: Local<bool>,
+    action_state: Res<ActionState<PlayerAction>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut action_map: ResMut<ActionMap<PlayerAction>>,
+) {
+    if action_state.just_pressed(PlayerAction::Rebind) {
+        info!("Press a key to bind it to Jump");
+        *waiting_for_key = true;
+    } else if *waiting_for_key {
+        if let Some(key_code) = keyboard_input.get_just_pressed().next() {
+            info!("Jump is now bound to {:?}", key_code);
+            action_map.set_bindings(
+                PlayerAction::Jump,
+                [
+                    InputBinding::Key(*key_code),
+                    InputBinding::GamepadButton(GamepadButtonType::South),
+                ],
+            );
+            *waiting_for_key = false;
+        }
+    }
+}
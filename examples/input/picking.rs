@@ -0,0 +1,158 @@
+//! Picks the meshes and UI nodes under the cursor, highlighting them when hovered and printing
+//! when they are clicked.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(highlight_meshes)
+        .add_system(highlight_buttons)
+        .add_system(print_clicks)
+        .run();
+}
+
+/// The material of a mesh when it isn't hovered
+#[derive(Component)]
+struct BaseMaterial(Handle<StandardMaterial>);
+
+/// The material of the meshes when they are hovered
+struct HoveredMaterial(Handle<StandardMaterial>);
+
+const BUTTON_COLOR: Color = Color::rgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON_COLOR: Color = Color::rgb(0.25, 0.25, 0.25);
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(HoveredMaterial(
+        materials.add(Color::rgb(1.0, 0.8, 0.2).into()),
+    ));
+
+    // plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+    // pickable meshes
+    let shapes = [
+        meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        meshes.add(Mesh::from(shape::UVSphere {
+            radius: 0.5,
+            ..default()
+        })),
+        meshes.add(Mesh::from(shape::Torus {
+            radius: 0.4,
+            ring_radius: 0.15,
+            ..default()
+        })),
+    ];
+    for (i, mesh) in shapes.into_iter().enumerate() {
+        let material = materials.add(Color::rgb(0.8, 0.7, 0.6).into());
+        commands
+            .spawn_bundle(PbrBundle {
+                mesh,
+                material: material.clone(),
+                transform: Transform::from_xyz(i as f32 * 1.5 - 1.5, 0.5, 0.0),
+                ..default()
+            })
+            .insert(BaseMaterial(material))
+            .insert(Pickable);
+    }
+    // light
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            intensity: 1500.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 3.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    // a pickable button, in front of the meshes
+    commands
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(150.0), Val::Px(65.0)),
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    left: Val::Px(20.0),
+                    bottom: Val::Px(20.0),
+                    ..default()
+                },
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: BUTTON_COLOR.into(),
+            ..default()
+        })
+        .insert(Pickable)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    "Button",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        font_size: 40.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                        ..default()
+                    },
+                    default(),
+                ),
+                ..default()
+            });
+        });
+}
+
+fn highlight_meshes(
+    mut over_events: EventReader<PointerOver>,
+    mut out_events: EventReader<PointerOut>,
+    hovered_material: Res<HoveredMaterial>,
+    mut mesh_query: Query<(&mut Handle<StandardMaterial>, &BaseMaterial)>,
+) {
+    for event in out_events.iter() {
+        if let Ok((mut material, base_material)) = mesh_query.get_mut(event.entity) {
+            *material = base_material.0.clone();
+        }
+    }
+    for event in over_events.iter() {
+        if let Ok((mut material, _)) = mesh_query.get_mut(event.entity) {
+            *material = hovered_material.0.clone();
+        }
+    }
+}
+
+fn highlight_buttons(
+    mut over_events: EventReader<PointerOver>,
+    mut out_events: EventReader<PointerOut>,
+    mut button_query: Query<&mut UiColor, With<Button>>,
+) {
+    for event in out_events.iter() {
+        if let Ok(mut color) = button_query.get_mut(event.entity) {
+            *color = BUTTON_COLOR.into();
+        }
+    }
+    for event in over_events.iter() {
+        if let Ok(mut color) = button_query.get_mut(event.entity) {
+            *color = HOVERED_BUTTON_COLOR.into();
+        }
+    }
+}
+
+fn print_clicks(mut click_events: EventReader<PointerClick>) {
+    for event in click_events.iter() {
+        info!("Clicked {:?} at {}", event.entity, event.position);
+    }
+}
@@ -116,6 +116,7 @@ fn setup_scene(
                             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                             font_size: 30.0,
                             color: Color::BLACK,
+                            ..default()
                         },
                     }],
                     alignment: TextAlignment {
@@ -116,6 +116,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                 font_size: 50.0,
                 color: Color::WHITE,
+                ..default()
             },
             Default::default(),
         ),
@@ -106,18 +106,25 @@ fn queue_custom(
     mut pipeline_cache: ResMut<PipelineCache>,
     render_meshes: Res<RenderAssets<Mesh>>,
     material_meshes: Query<(Entity, &MeshUniform, &Handle<Mesh>), With<CustomMaterial>>,
-    mut views: Query<(&ExtractedView, &mut RenderPhase<Transparent3d>)>,
+    mut views: Query<(
+        &ExtractedView,
+        Option<&Camera3d>,
+        &mut RenderPhase<Transparent3d>,
+    )>,
 ) {
     let draw_custom = transparent_3d_draw_functions
         .read()
         .get_id::<DrawCustom>()
         .unwrap();
 
-    let key = MeshPipelineKey::from_msaa_samples(msaa.samples)
+    let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples)
         | MeshPipelineKey::from_primitive_topology(PrimitiveTopology::TriangleList);
 
-    for (view, mut transparent_phase) in views.iter_mut() {
+    for (view, camera_3d, mut transparent_phase) in views.iter_mut() {
         let rangefinder = view.rangefinder3d();
+        // The pipeline must target the hdr texture of hdr cameras
+        let key = msaa_key
+            | MeshPipelineKey::from_hdr(camera_3d.map_or(false, |camera_3d| camera_3d.hdr));
         for (entity, mesh_uniform, mesh_handle) in material_meshes.iter() {
             if let Some(mesh) = render_meshes.get(mesh_handle) {
                 let pipeline = pipelines
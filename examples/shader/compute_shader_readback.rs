@@ -0,0 +1,95 @@
+//! A compute shader writing a storage buffer, whose values are read back on the CPU.
+//!
+//! The shader is dispatched by the renderer every frame while the `CollatzSteps` resource exists,
+//! with a bind group created from its fields with `AsBindGroup`. Its buffer is read back once,
+//! after a `ComputeReadbackRequest` is sent.
+
+use bevy::{
+    prelude::*,
+    render::{
+        compute::{ComputeReadback, ComputeReadbackRequest, ComputeShader, ComputeShaderPlugin},
+        render_resource::*,
+        renderer::RenderDevice,
+    },
+};
+
+const COUNT: u32 = 1024;
+const WORKGROUP_SIZE: u32 = 64;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(ComputeShaderPlugin::<CollatzSteps>::default())
+        .add_startup_system(setup)
+        .add_system(print_steps)
+        .run();
+}
+
+/// Counts the steps of the Collatz sequences starting at each number from 1 to `COUNT`
+#[derive(AsBindGroup, Clone)]
+struct CollatzSteps {
+    #[storage(0)]
+    steps: Buffer,
+}
+
+impl ComputeShader for CollatzSteps {
+    fn shader() -> ShaderRef {
+        "shaders/collatz.wgsl".into()
+    }
+
+    fn workgroups(&self) -> Option<UVec3> {
+        Some(UVec3::new(
+            (COUNT + WORKGROUP_SIZE - 1) / WORKGROUP_SIZE,
+            1,
+            1,
+        ))
+    }
+
+    fn readback_buffers(&self) -> Vec<(Buffer, BufferAddress)> {
+        let size = COUNT as BufferAddress * std::mem::size_of::<u32>() as BufferAddress;
+        vec![(self.steps.clone(), size)]
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut readback_requests: EventWriter<ComputeReadbackRequest<CollatzSteps>>,
+) {
+    let steps = render_device.create_buffer(&BufferDescriptor {
+        label: Some("collatz_steps"),
+        size: COUNT as BufferAddress * std::mem::size_of::<u32>() as BufferAddress,
+        // The buffer is copied to be read back
+        usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    commands.insert_resource(CollatzSteps { steps });
+    // Read the steps back after the first dispatch
+    readback_requests.send_default();
+    commands.spawn_bundle(Camera2dBundle::default());
+}
+
+fn print_steps(mut commands: Commands, mut readbacks: EventReader<ComputeReadback<CollatzSteps>>) {
+    for readback in readbacks.iter() {
+        let steps = readback
+            .data
+            .chunks_exact(4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect::<Vec<_>>();
+        let (longest, longest_steps) = steps
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, steps)| **steps)
+            .unwrap();
+        info!(
+            "Steps of the first numbers: {:?}. The longest sequence below {} starts at {}, with {} steps.",
+            &steps[..10],
+            COUNT,
+            longest + 1,
+            longest_steps
+        );
+
+        // The values won't change, stop dispatching the shader
+        commands.remove_resource::<CollatzSteps>();
+    }
+}
@@ -38,14 +38,23 @@ fn setup(
         // The cube mesh has 24 vertices (6 faces, 4 vertices per face), so we insert one BlendColor for each
         vec![[1.0, 0.0, 0.0, 1.0]; 24],
     );
+    let material = materials.add(CustomMaterial {
+        color: Color::WHITE,
+    });
 
     // cube
     commands.spawn().insert_bundle(MaterialMeshBundle {
         mesh: meshes.add(mesh),
         transform: Transform::from_xyz(0.0, 0.5, 0.0),
-        material: materials.add(CustomMaterial {
-            color: Color::WHITE,
-        }),
+        material: material.clone(),
+        ..default()
+    });
+
+    // a cube without the custom attribute, using another specialization of the material's pipeline
+    commands.spawn().insert_bundle(MaterialMeshBundle {
+        mesh: meshes.add(Mesh::from(shape::Cube { size: 0.5 })),
+        transform: Transform::from_xyz(1.5, 0.25, 0.0),
+        material,
         ..default()
     });
 
@@ -78,11 +87,22 @@ impl Material for CustomMaterial {
         layout: &MeshVertexBufferLayout,
         _key: MaterialPipelineKey<Self>,
     ) -> Result<(), SpecializedMeshPipelineError> {
-        let vertex_layout = layout.get_layout(&[
-            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
-            ATTRIBUTE_BLEND_COLOR.at_shader_location(1),
-        ])?;
-        descriptor.vertex.buffers = vec![vertex_layout];
+        // The mesh pipeline already laid out the standard attributes at locations 0 to 6, the custom
+        // attribute is added after them when the mesh has it
+        if layout.contains(ATTRIBUTE_BLEND_COLOR) {
+            descriptor.vertex.buffers[0]
+                .attributes
+                .push(layout.get_attribute(&ATTRIBUTE_BLEND_COLOR.at_shader_location(7))?);
+            descriptor
+                .vertex
+                .shader_defs
+                .push(String::from("VERTEX_BLEND_COLOR"));
+            if let Some(fragment) = &mut descriptor.fragment {
+                fragment
+                    .shader_defs
+                    .push(String::from("VERTEX_BLEND_COLOR"));
+            }
+        }
         Ok(())
     }
 }
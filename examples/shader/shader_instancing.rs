@@ -107,7 +107,11 @@ fn queue_custom(
     mut pipeline_cache: ResMut<PipelineCache>,
     meshes: Res<RenderAssets<Mesh>>,
     material_meshes: Query<(Entity, &MeshUniform, &Handle<Mesh>), With<InstanceMaterialData>>,
-    mut views: Query<(&ExtractedView, &mut RenderPhase<Transparent3d>)>,
+    mut views: Query<(
+        &ExtractedView,
+        Option<&Camera3d>,
+        &mut RenderPhase<Transparent3d>,
+    )>,
 ) {
     let draw_custom = transparent_3d_draw_functions
         .read()
@@ -116,12 +120,15 @@ fn queue_custom(
 
     let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples);
 
-    for (view, mut transparent_phase) in views.iter_mut() {
+    for (view, camera_3d, mut transparent_phase) in views.iter_mut() {
         let rangefinder = view.rangefinder3d();
+        // The pipeline must target the hdr texture of hdr cameras
+        let view_key = msaa_key
+            | MeshPipelineKey::from_hdr(camera_3d.map_or(false, |camera_3d| camera_3d.hdr));
         for (entity, mesh_uniform, mesh_handle) in material_meshes.iter() {
             if let Some(mesh) = meshes.get(mesh_handle) {
                 let key =
-                    msaa_key | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology);
+                    view_key | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology);
                 let pipeline = pipelines
                     .specialize(&mut pipeline_cache, &custom_pipeline, key, &mesh.layout)
                     .unwrap();
@@ -103,6 +103,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                             font_size: 40.0,
                             color: Color::rgb(0.0, 1.0, 0.0),
+                            ..default()
                         },
                     },
                     TextSection {
@@ -111,6 +112,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                             font_size: 40.0,
                             color: Color::rgb(0.0, 1.0, 1.0),
+                            ..default()
                         },
                     },
                     TextSection {
@@ -119,6 +121,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                             font_size: 40.0,
                             color: Color::rgb(0.0, 1.0, 0.0),
+                            ..default()
                         },
                     },
                     TextSection {
@@ -127,6 +130,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                             font_size: 40.0,
                             color: Color::rgb(0.0, 1.0, 1.0),
+                            ..default()
                         },
                     },
                 ],
@@ -9,6 +9,7 @@ use bevy::{
     math::Quat,
     prelude::*,
     render::camera::Camera,
+    sprite::SpriteDiagnosticsPlugin,
     window::PresentMode,
 };
 
@@ -26,6 +27,7 @@ fn main() {
         .add_plugin(LogDiagnosticsPlugin::default())
         .add_plugin(FrameTimeDiagnosticsPlugin::default())
         .add_plugins(DefaultPlugins)
+        .add_plugin(SpriteDiagnosticsPlugin)
         .add_startup_system(setup)
         .add_system(print_sprite_count)
         .add_system(move_camera.after(print_sprite_count))
@@ -112,6 +112,7 @@ The red cube is moved through its GlobalTransform and thus is unaffected by the
                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                 font_size: 22.0,
                 color: Color::WHITE,
+                ..default()
             },
             TextAlignment {
                 horizontal: HorizontalAlign::Left,
@@ -0,0 +1,80 @@
+//! Illustrates borders and rounded corners of UI nodes.
+
+use bevy::{prelude::*, winit::WinitSettings};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Only run the app when there is user input. This will significantly reduce CPU/GPU use.
+        .insert_resource(WinitSettings::desktop_app())
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn_bundle(Camera2dBundle::default());
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_wrap: FlexWrap::Wrap,
+                align_items: AlignItems::Center,
+                align_content: AlignContent::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            let radii = [
+                BorderRadius::ZERO,
+                BorderRadius::all(10.0),
+                BorderRadius::all(50.0),
+                BorderRadius {
+                    top_left: 40.0,
+                    bottom_right: 40.0,
+                    ..default()
+                },
+            ];
+            let borders = [
+                UiRect::all(Val::Px(0.0)),
+                UiRect::all(Val::Px(5.0)),
+                UiRect {
+                    left: Val::Px(15.0),
+                    right: Val::Px(5.0),
+                    top: Val::Px(5.0),
+                    bottom: Val::Px(15.0),
+                },
+            ];
+            for border in borders {
+                for radius in radii {
+                    parent
+                        .spawn_bundle(NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(100.0), Val::Px(100.0)),
+                                margin: UiRect::all(Val::Px(20.0)),
+                                border,
+                                // The children are clipped to the rounded corners
+                                overflow: Overflow::Hidden,
+                                ..default()
+                            },
+                            color: Color::rgb(0.3, 0.3, 0.6).into(),
+                            border_color: Color::GOLD.into(),
+                            ..default()
+                        })
+                        .insert(radius)
+                        .with_children(|parent| {
+                            parent.spawn_bundle(NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(60.0), Val::Px(60.0)),
+                                    ..default()
+                                },
+                                color: Color::rgb(0.8, 0.3, 0.3).into(),
+                                ..default()
+                            });
+                        });
+                }
+            }
+        });
+}
@@ -28,15 +28,15 @@ fn button_system(
         let mut text = text_query.get_mut(children[0]).unwrap();
         match *interaction {
             Interaction::Clicked => {
-                text.sections[0].value = "Press".to_string();
+                text.set_section_value(0, "Press".to_string());
                 *color = PRESSED_BUTTON.into();
             }
             Interaction::Hovered => {
-                text.sections[0].value = "Hover".to_string();
+                text.set_section_value(0, "Hover".to_string());
                 *color = HOVERED_BUTTON.into();
             }
             Interaction::None => {
-                text.sections[0].value = "Button".to_string();
+                text.set_section_value(0, "Button".to_string());
                 *color = NORMAL_BUTTON.into();
             }
         }
@@ -69,6 +69,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: 40.0,
                         color: Color::rgb(0.9, 0.9, 0.9),
+                        ..default()
                     },
                     Default::default(),
                 ),
@@ -87,6 +87,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>, mut state: ResM
                 font: font_handle,
                 font_size: 60.0,
                 color: Color::YELLOW,
+                ..default()
             },
             Default::default(),
         ),
@@ -0,0 +1,96 @@
+//! Illustrates laying out UI nodes in the rows and columns of a grid.
+
+use bevy::{prelude::*, winit::WinitSettings};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Only run the app when there is user input. This will significantly reduce CPU/GPU use.
+        .insert_resource(WinitSettings::desktop_app())
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands.spawn_bundle(Camera2dBundle::default());
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                display: Display::Grid,
+                // A sidebar sized by its text, and two columns sharing the rest of the width
+                grid_template_columns: vec![
+                    GridTrack::Auto,
+                    GridTrack::Fr(1.0),
+                    GridTrack::Fr(2.0),
+                ],
+                // A header, two rows sharing the rest of the height and a footer of fixed height
+                grid_template_rows: vec![
+                    GridTrack::Auto,
+                    GridTrack::Fr(1.0),
+                    GridTrack::Fr(1.0),
+                    GridTrack::Px(40.0),
+                ],
+                gap: Size::new(Val::Px(10.0), Val::Px(10.0)),
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            let mut cell = |grid_column: GridPlacement, grid_row: GridPlacement, label: &str| {
+                parent
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            grid_column,
+                            grid_row,
+                            // Fill the cell
+                            size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                            padding: UiRect::all(Val::Px(10.0)),
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        color: Color::rgb(0.3, 0.3, 0.6).into(),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn_bundle(TextBundle {
+                            text: Text::with_section(
+                                label,
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 24.0,
+                                    color: Color::WHITE,
+                                    ..default()
+                                },
+                                default(),
+                            ),
+                            ..default()
+                        });
+                    });
+            };
+
+            cell(
+                GridPlacement::start(0).with_span(3),
+                GridPlacement::AUTO,
+                "Header",
+            );
+            cell(
+                GridPlacement::AUTO,
+                GridPlacement::AUTO.with_span(2),
+                "Sidebar with a long label",
+            );
+            // The remaining cells are placed in the next free cells, row by row
+            for label in ["One", "Two", "Three", "Four"] {
+                cell(GridPlacement::AUTO, GridPlacement::AUTO, label);
+            }
+            cell(
+                GridPlacement::AUTO.with_span(3),
+                GridPlacement::AUTO,
+                "Footer",
+            );
+        });
+}
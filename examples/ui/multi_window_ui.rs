@@ -0,0 +1,186 @@
+//! Displays a UI in a second window, like the tool panels of an editor, to control a scene
+//! rendered in the primary window.
+
+use bevy::{
+    prelude::*,
+    render::camera::RenderTarget,
+    window::{CreateWindow, WindowId},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .init_resource::<Rotating>()
+        .add_startup_system(setup)
+        .add_system(button_system)
+        .add_system(rotate)
+        .run();
+}
+
+#[derive(Default)]
+struct Rotating(bool);
+
+#[derive(Component)]
+struct Shape;
+
+const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut create_window_events: EventWriter<CreateWindow>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    // the scene, rendered in the primary window
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            ..default()
+        })
+        .insert(Shape);
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+    // the UI of the primary window
+    commands.spawn_bundle(TextBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            ..default()
+        },
+        text: Text::with_section(
+            "Viewport",
+            TextStyle {
+                font: font.clone(),
+                font_size: 30.0,
+                color: Color::WHITE,
+                ..default()
+            },
+            Default::default(),
+        ),
+        ..default()
+    });
+
+    let window_id = WindowId::new();
+    create_window_events.send(CreateWindow {
+        id: window_id,
+        descriptor: WindowDescriptor {
+            width: 300.,
+            height: 400.,
+            title: "Tools".to_string(),
+            ..default()
+        },
+    });
+
+    // the camera of the second window, which only renders its UI
+    commands.spawn_bundle(Camera2dBundle {
+        camera: Camera {
+            target: RenderTarget::Window(window_id),
+            ..default()
+        },
+        ..default()
+    });
+    // the UI of the second window, laid out in its size
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..default()
+        })
+        .insert(UiWindow(window_id))
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    "Tools",
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 30.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                    Default::default(),
+                ),
+                ..default()
+            });
+            parent
+                .spawn_bundle(ButtonBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(150.0), Val::Px(65.0)),
+                        margin: UiRect::all(Val::Px(20.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    color: NORMAL_BUTTON.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn_bundle(TextBundle {
+                        text: Text::with_section(
+                            "Rotate",
+                            TextStyle {
+                                font,
+                                font_size: 30.0,
+                                color: Color::rgb(0.9, 0.9, 0.9),
+                                ..default()
+                            },
+                            Default::default(),
+                        ),
+                        ..default()
+                    });
+                });
+        });
+}
+
+fn button_system(
+    mut rotating: ResMut<Rotating>,
+    mut interaction_query: Query<
+        (&Interaction, &mut UiColor, &Children),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut text_query: Query<&mut Text>,
+) {
+    for (interaction, mut color, children) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                rotating.0 = !rotating.0;
+                let label = if rotating.0 { "Stop" } else { "Rotate" };
+                let mut text = text_query.get_mut(children[0]).unwrap();
+                text.set_section_value(0, label.to_string());
+                *color = PRESSED_BUTTON.into();
+            }
+            Interaction::Hovered => *color = HOVERED_BUTTON.into(),
+            Interaction::None => *color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+fn rotate(rotating: Res<Rotating>, time: Res<Time>, mut query: Query<&mut Transform, With<Shape>>) {
+    if !rotating.0 {
+        return;
+    }
+    for mut transform in query.iter_mut() {
+        transform.rotate_y(time.delta_seconds());
+    }
+}
@@ -0,0 +1,72 @@
+//! Illustrates a scrollable list, clipped to its container.
+//!
+//! Scroll the list with the mouse wheel, or by dragging it with a touch.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let style = TextStyle::default()
+        .font(asset_server.load("fonts/FiraSans-Bold.ttf"))
+        .size(25.0);
+
+    commands.spawn_bundle(Camera2dBundle::default());
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            // The list, which only shows the items that fit in it
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(300.0), Val::Px(400.0)),
+                        flex_direction: FlexDirection::ColumnReverse,
+                        padding: UiRect::all(Val::Px(10.0)),
+                        overflow: Overflow::Scroll,
+                        ..default()
+                    },
+                    color: Color::rgb(0.15, 0.15, 0.15).into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for i in 0..30 {
+                        parent
+                            .spawn_bundle(NodeBundle {
+                                style: Style {
+                                    // Keep the items from shrinking to fit in the list
+                                    flex_shrink: 0.0,
+                                    margin: UiRect::all(Val::Px(5.0)),
+                                    padding: UiRect::all(Val::Px(5.0)),
+                                    ..default()
+                                },
+                                color: Color::rgb(0.25, 0.25, 0.35).into(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn_bundle(TextBundle {
+                                    text: Text::with_section(
+                                        format!("Item {}", i + 1),
+                                        style.clone(),
+                                        default(),
+                                    ),
+                                    ..default()
+                                });
+                            });
+                    }
+                });
+        });
+}
@@ -50,6 +50,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                     font_size: 100.0,
                     color: Color::WHITE,
+                    ..default()
                 },
                 // Note: You can use `Default::default()` in place of the `TextAlignment`
                 TextAlignment {
@@ -77,6 +78,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                             font_size: 60.0,
                             color: Color::WHITE,
+                            ..default()
                         },
                     },
                     TextSection {
@@ -85,6 +87,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                             font_size: 60.0,
                             color: Color::GOLD,
+                            ..default()
                         },
                     },
                 ],
@@ -100,7 +103,7 @@ fn text_update_system(diagnostics: Res<Diagnostics>, mut query: Query<&mut Text,
         if let Some(fps) = diagnostics.get(FrameTimeDiagnosticsPlugin::FPS) {
             if let Some(average) = fps.average() {
                 // Update the value of the second section
-                text.sections[1].value = format!("{:.2}", average);
+                text.set_section_value(1, format!("{:.2}", average));
             }
         }
     }
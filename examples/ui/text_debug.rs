@@ -42,6 +42,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 font: font.clone(),
                 font_size: 50.0,
                 color: Color::WHITE,
+                ..default()
             },
             Default::default(),
         ),
@@ -68,6 +69,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 50.0,
                     color: Color::rgb(0.8, 0.2, 0.7),
+                    ..default()
                 },
             TextAlignment {
                 horizontal: HorizontalAlign::Center,
@@ -96,6 +98,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: font.clone(),
                             font_size: 30.0,
                             color: Color::WHITE,
+                            ..default()
                         },
                     },
                     TextSection {
@@ -104,6 +107,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: font.clone(),
                             font_size: 30.0,
                             color: Color::RED,
+                            ..default()
                         },
                     },
                     TextSection {
@@ -112,6 +116,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: font.clone(),
                             font_size: 30.0,
                             color: Color::ORANGE_RED,
+                            ..default()
                         },
                     },
                     TextSection {
@@ -120,6 +125,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: font.clone(),
                             font_size: 30.0,
                             color: Color::YELLOW,
+                            ..default()
                         },
                     },
                     TextSection {
@@ -128,6 +134,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: font.clone(),
                             font_size: 30.0,
                             color: Color::GREEN,
+                            ..default()
                         },
                     },
                     TextSection {
@@ -136,10 +143,11 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: font.clone(),
                             font_size: 30.0,
                             color: Color::BLUE,
+                            ..default()
                         },
                     },
                 ],
-                alignment: Default::default(),
+                ..default()
             },
             ..default()
         })
@@ -165,6 +173,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 font,
                 font_size: 50.0,
                 color: Color::WHITE,
+                ..default()
             },
             Default::default(),
         ),
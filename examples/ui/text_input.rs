@@ -0,0 +1,75 @@
+//! Illustrates editable text fields and reacting to submitted text.
+//!
+//! Click on a field to focus it. Text can be selected with the mouse or with shift and the arrow
+//! keys, and copied or pasted with ctrl+C and ctrl+V.
+
+use bevy::{prelude::*, ui::widget::TextInputSubmitted};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(show_submitted_text)
+        .run();
+}
+
+#[derive(Component)]
+struct SubmittedText;
+
+fn setup(mut commands: Commands, mut focus: ResMut<Focus>, asset_server: Res<AssetServer>) {
+    let style = TextStyle::default()
+        .font(asset_server.load("fonts/FiraSans-Bold.ttf"))
+        .size(30.0);
+
+    commands.spawn_bundle(Camera2dBundle::default());
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::FlexStart,
+                padding: UiRect::all(Val::Px(20.0)),
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    "Type something and press enter:",
+                    style.clone(),
+                    default(),
+                ),
+                ..default()
+            });
+            // The field starts with the focus, so that typing goes to it right away
+            let input = parent.spawn_bundle(TextInputBundle {
+                style: Style {
+                    min_size: Size::new(Val::Px(400.0), Val::Px(30.0)),
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                text: Text::with_section("", style.clone().color(Color::GOLD), default()),
+                ..default()
+            });
+            focus.set(input.id());
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section("", style, default()),
+                    ..default()
+                })
+                .insert(SubmittedText);
+        });
+}
+
+fn show_submitted_text(
+    mut submitted_events: EventReader<TextInputSubmitted>,
+    mut query: Query<&mut Text, With<SubmittedText>>,
+) {
+    for event in submitted_events.iter() {
+        for mut text in query.iter_mut() {
+            text.set_section_value(0, format!("You wrote: {}", event.value));
+        }
+    }
+}
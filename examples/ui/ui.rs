@@ -1,10 +1,6 @@
 //! This example illustrates the various features of Bevy UI.
 
-use bevy::{
-    input::mouse::{MouseScrollUnit, MouseWheel},
-    prelude::*,
-    winit::WinitSettings,
-};
+use bevy::{prelude::*, winit::WinitSettings};
 
 fn main() {
     App::new()
@@ -12,7 +8,6 @@ fn main() {
         // Only run the app when there is user input. This will significantly reduce CPU/GPU use.
         .insert_resource(WinitSettings::desktop_app())
         .add_startup_system(setup)
-        .add_system(mouse_scroll)
         .run();
 }
 
@@ -68,6 +63,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                         font_size: 30.0,
                                         color: Color::WHITE,
+                                        ..default()
                                     },
                                     Default::default(),
                                 ),
@@ -105,66 +101,52 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                 font_size: 25.,
                                 color: Color::WHITE,
+                                ..default()
                             },
                             Default::default(),
                         ),
                         ..default()
                     });
-                    // List with hidden overflow
+                    // List scrolled with the mouse wheel
                     parent
                         .spawn_bundle(NodeBundle {
                             style: Style {
                                 flex_direction: FlexDirection::ColumnReverse,
                                 align_self: AlignSelf::Center,
                                 size: Size::new(Val::Percent(100.0), Val::Percent(50.0)),
-                                overflow: Overflow::Hidden,
+                                overflow: Overflow::Scroll,
                                 ..default()
                             },
                             color: Color::rgb(0.10, 0.10, 0.10).into(),
                             ..default()
                         })
                         .with_children(|parent| {
-                            // Moving panel
-                            parent
-                                .spawn_bundle(NodeBundle {
+                            // List items
+                            for i in 0..30 {
+                                parent.spawn_bundle(TextBundle {
                                     style: Style {
-                                        flex_direction: FlexDirection::ColumnReverse,
-                                        flex_grow: 1.0,
-                                        max_size: Size::new(Val::Undefined, Val::Undefined),
+                                        flex_shrink: 0.,
+                                        size: Size::new(Val::Undefined, Val::Px(20.)),
+                                        margin: UiRect {
+                                            left: Val::Auto,
+                                            right: Val::Auto,
+                                            ..default()
+                                        },
                                         ..default()
                                     },
-                                    color: Color::NONE.into(),
-                                    ..default()
-                                })
-                                .insert(ScrollingList::default())
-                                .with_children(|parent| {
-                                    // List items
-                                    for i in 0..30 {
-                                        parent.spawn_bundle(TextBundle {
-                                            style: Style {
-                                                flex_shrink: 0.,
-                                                size: Size::new(Val::Undefined, Val::Px(20.)),
-                                                margin: UiRect {
-                                                    left: Val::Auto,
-                                                    right: Val::Auto,
-                                                    ..default()
-                                                },
-                                                ..default()
-                                            },
-                                            text: Text::with_section(
-                                                format!("Item {}", i),
-                                                TextStyle {
-                                                    font: asset_server
-                                                        .load("fonts/FiraSans-Bold.ttf"),
-                                                    font_size: 20.,
-                                                    color: Color::WHITE,
-                                                },
-                                                Default::default(),
-                                            ),
+                                    text: Text::with_section(
+                                        format!("Item {}", i),
+                                        TextStyle {
+                                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                                            font_size: 20.,
+                                            color: Color::WHITE,
                                             ..default()
-                                        });
-                                    }
+                                        },
+                                        Default::default(),
+                                    ),
+                                    ..default()
                                 });
+                            }
                         });
                 });
             // absolute positioning
@@ -303,32 +285,3 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 });
         });
 }
-
-#[derive(Component, Default)]
-struct ScrollingList {
-    position: f32,
-}
-
-fn mouse_scroll(
-    mut mouse_wheel_events: EventReader<MouseWheel>,
-    mut query_list: Query<(&mut ScrollingList, &mut Style, &Children, &Node)>,
-    query_item: Query<&Node>,
-) {
-    for mouse_wheel_event in mouse_wheel_events.iter() {
-        for (mut scrolling_list, mut style, children, uinode) in query_list.iter_mut() {
-            let items_height: f32 = children
-                .iter()
-                .map(|entity| query_item.get(*entity).unwrap().size.y)
-                .sum();
-            let panel_height = uinode.size.y;
-            let max_scroll = (items_height - panel_height).max(0.);
-            let dy = match mouse_wheel_event.unit {
-                MouseScrollUnit::Line => mouse_wheel_event.y * 20.,
-                MouseScrollUnit::Pixel => mouse_wheel_event.y,
-            };
-            scrolling_list.position += dy;
-            scrolling_list.position = scrolling_list.position.clamp(-max_scroll, 0.);
-            style.position.top = Val::Px(scrolling_list.position);
-        }
-    }
-}
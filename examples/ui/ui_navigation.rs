@@ -0,0 +1,106 @@
+//! Illustrates moving the focus between buttons with the keyboard or a gamepad.
+//!
+//! Use the arrow keys, tab or the D-pad to move the focus, and enter, space or the south button
+//! of the gamepad to click the focused button.
+
+use bevy::{
+    prelude::*,
+    ui::{FocusEnter, FocusExit},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(highlight_focus)
+        .add_system(show_clicks)
+        .run();
+}
+
+const FOCUSED_BORDER: Color = Color::GOLD;
+
+fn setup(mut commands: Commands, mut focus: ResMut<Focus>, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    commands.spawn_bundle(Camera2dBundle::default());
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(540.0), Val::Percent(100.0)),
+                margin: UiRect::all(Val::Auto),
+                flex_wrap: FlexWrap::WrapReverse,
+                align_content: AlignContent::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            for i in 0..9 {
+                let button = parent
+                    .spawn_bundle(ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(150.0), Val::Px(80.0)),
+                            margin: UiRect::all(Val::Px(15.0)),
+                            border: UiRect::all(Val::Px(4.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        color: Color::rgb(0.15, 0.15, 0.15).into(),
+                        ..default()
+                    })
+                    .insert(BorderRadius::all(10.0))
+                    .with_children(|parent| {
+                        parent.spawn_bundle(TextBundle {
+                            text: Text::with_section(
+                                format!("Button {}", i + 1),
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 25.0,
+                                    color: Color::WHITE,
+                                    ..default()
+                                },
+                                default(),
+                            ),
+                            ..default()
+                        });
+                    })
+                    .id();
+                // Start with the first button focused
+                if i == 0 {
+                    focus.set(button);
+                }
+            }
+        });
+}
+
+fn highlight_focus(
+    mut focus_enter_events: EventReader<FocusEnter>,
+    mut focus_exit_events: EventReader<FocusExit>,
+    mut border_query: Query<&mut BorderColor>,
+) {
+    for event in focus_exit_events.iter() {
+        if let Ok(mut border_color) = border_query.get_mut(event.entity) {
+            border_color.0 = Color::NONE;
+        }
+    }
+    for event in focus_enter_events.iter() {
+        if let Ok(mut border_color) = border_query.get_mut(event.entity) {
+            border_color.0 = FOCUSED_BORDER;
+        }
+    }
+}
+
+fn show_clicks(
+    interaction_query: Query<(&Interaction, &Children), (Changed<Interaction>, With<Button>)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (interaction, children) in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            let mut text = text_query.get_mut(children[0]).unwrap();
+            text.set_section_value(0, "Clicked!");
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Illustrates how a sliced image keeps its borders intact when stretched to fill a node.
+
+use bevy::{
+    prelude::*,
+    ui::widget::{BorderRect, ImageMode},
+    winit::WinitSettings,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Only run the app when there is user input. This will significantly reduce CPU/GPU use.
+        .insert_resource(WinitSettings::desktop_app())
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let image = asset_server.load("textures/rpg/ui/generic-rpg-ui-text-box.png");
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    commands.spawn_bundle(Camera2dBundle::default());
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::SpaceEvenly,
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            for (width, height) in [(150.0, 150.0), (300.0, 150.0), (150.0, 300.0)] {
+                parent
+                    .spawn_bundle(ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(width), Val::Px(height)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        image: image.clone().into(),
+                        ..default()
+                    })
+                    // The corners of the image are 8 pixels wide
+                    .insert(ImageMode::Sliced(BorderRect::all(8.0)))
+                    .with_children(|parent| {
+                        parent.spawn_bundle(TextBundle {
+                            text: Text::with_section(
+                                "Button",
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 30.0,
+                                    color: Color::WHITE,
+                                    ..default()
+                                },
+                                default(),
+                            ),
+                            ..default()
+                        });
+                    });
+            }
+        });
+}
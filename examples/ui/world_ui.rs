@@ -0,0 +1,171 @@
+//! Renders a UI to a texture displayed on a screen in the world, and interacts with it by
+//! pointing at the screen.
+
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig,
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+    },
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(button_system)
+        .add_system(rotate_screen)
+        .run();
+}
+
+#[derive(Component)]
+struct Screen;
+
+#[derive(Component)]
+struct Counter(u32);
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+) {
+    let size = Extent3d {
+        width: 512,
+        height: 384,
+        ..default()
+    };
+
+    // The image the UI is rendered to
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: None,
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+        },
+        ..default()
+    };
+    image.resize(size);
+    let image_handle = images.add(image);
+
+    // The camera rendering the UI to the image, before the main camera displays it
+    let ui_camera = commands
+        .spawn_bundle(Camera2dBundle {
+            camera_2d: Camera2d {
+                clear_color: ClearColorConfig::Custom(Color::rgb(0.05, 0.1, 0.05)),
+            },
+            camera: Camera {
+                priority: -1,
+                target: RenderTarget::Image(image_handle.clone()),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+
+    // The screen displaying the image
+    let screen = commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2::new(4.0, 3.0)))),
+            material: materials.add(StandardMaterial {
+                base_color_texture: Some(image_handle),
+                unlit: true,
+                ..default()
+            }),
+            ..default()
+        })
+        .insert(Screen)
+        .id();
+
+    // The UI of the screen
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+        font_size: 40.0,
+        color: Color::rgb(0.4, 1.0, 0.4),
+        ..default()
+    };
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .insert(WorldUi {
+            camera: ui_camera,
+            surface: screen,
+        })
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section("Clicked 0 times", text_style.clone(), default()),
+                    ..default()
+                })
+                .insert(Counter(0));
+            parent
+                .spawn_bundle(ButtonBundle {
+                    style: Style {
+                        margin: UiRect::all(Val::Px(20.0)),
+                        padding: UiRect::all(Val::Px(10.0)),
+                        border: UiRect::all(Val::Px(4.0)),
+                        ..default()
+                    },
+                    color: Color::NONE.into(),
+                    border_color: text_style.color.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn_bundle(TextBundle {
+                        text: Text::with_section("Click me", text_style, default()),
+                        ..default()
+                    });
+                });
+        });
+
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 0.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+fn button_system(
+    interaction_query: Query<(&Interaction, &Children), (Changed<Interaction>, With<Button>)>,
+    mut text_query: Query<&mut Text, Without<Counter>>,
+    mut counter_query: Query<(&mut Text, &mut Counter)>,
+) {
+    for (interaction, children) in interaction_query.iter() {
+        let mut text = text_query.get_mut(children[0]).unwrap();
+        match *interaction {
+            Interaction::Clicked => {
+                for (mut counter_text, mut counter) in counter_query.iter_mut() {
+                    counter.0 += 1;
+                    counter_text.sections[0].value = format!("Clicked {} times", counter.0);
+                }
+            }
+            Interaction::Hovered => text.sections[0].value = "> Click me <".to_string(),
+            Interaction::None => text.sections[0].value = "Click me".to_string(),
+        }
+    }
+}
+
+/// Swings the screen around, the cursor still points at the UI on the screen
+fn rotate_screen(time: Res<Time>, mut query: Query<&mut Transform, With<Screen>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotation = Quat::from_rotation_y(0.6 * time.seconds_since_startup().sin() as f32);
+    }
+}
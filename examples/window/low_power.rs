@@ -184,6 +184,7 @@ pub(crate) mod test_setup {
                                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                 font_size: 50.0,
                                 color: Color::WHITE,
+                                ..default()
                             },
                         },
                         TextSection {
@@ -192,6 +193,7 @@ pub(crate) mod test_setup {
                                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                 font_size: 50.0,
                                 color: Color::GREEN,
+                                ..default()
                             },
                         },
                         TextSection {
@@ -200,6 +202,7 @@ pub(crate) mod test_setup {
                                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                 font_size: 50.0,
                                 color: Color::YELLOW,
+                                ..default()
                             },
                         },
                         TextSection {
@@ -208,10 +211,11 @@ pub(crate) mod test_setup {
                                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                 font_size: 50.0,
                                 color: Color::YELLOW,
+                                ..default()
                             },
                         },
                     ],
-                    alignment: TextAlignment::default(),
+                    ..default()
                 },
                 ..default()
             })
@@ -55,6 +55,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                 font_size: 30.0,
                                 color: Color::WHITE,
+                                ..default()
                             },
                             Default::default(),
                         ),
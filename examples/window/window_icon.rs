@@ -0,0 +1,62 @@
+//! Sets the icon of the window from an image, and replaces it with the images dropped on the
+//! window.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(file_drag_and_drop_system)
+        .add_system(set_icon)
+        .run();
+}
+
+/// The image to use as the icon of the window once it is loaded.
+struct PendingIcon(Option<Handle<Image>>);
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(PendingIcon(Some(asset_server.load("branding/icon.png"))));
+}
+
+fn file_drag_and_drop_system(
+    mut events: EventReader<FileDragAndDrop>,
+    mut windows: ResMut<Windows>,
+    mut pending_icon: ResMut<PendingIcon>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in events.iter() {
+        let window = match windows.get_mut(event.window_id()) {
+            Some(window) => window,
+            None => continue,
+        };
+        match event {
+            FileDragAndDrop::HoveredFile { path_buf, .. } => {
+                window.set_title(format!("Drop {} to use it as icon", path_buf.display()));
+            }
+            FileDragAndDrop::HoveredFileCancelled { .. } => {
+                window.set_title("Drop an image to use it as icon".to_string());
+            }
+            FileDragAndDrop::DroppedFile { path_buf, .. } => {
+                window.set_title("Drop an image to use it as icon".to_string());
+                pending_icon.0 = Some(asset_server.load(path_buf.clone()));
+            }
+        }
+    }
+}
+
+fn set_icon(
+    mut pending_icon: ResMut<PendingIcon>,
+    images: Res<Assets<Image>>,
+    mut windows: ResMut<Windows>,
+) {
+    let image = match pending_icon.0.as_ref().and_then(|handle| images.get(handle)) {
+        Some(image) => image,
+        None => return,
+    };
+    match image.to_window_icon() {
+        Some(icon) => windows.primary_mut().set_icon(Some(icon)),
+        None => warn!("The image can't be used as an icon"),
+    }
+    pending_icon.0 = None;
+}
@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use bevy_asset::create_pak_archive;
+use clap::Parser;
+
+/// Packs all the files of an assets folder in an archive, to be loaded with
+/// `AssetServerSettings::asset_archive`.
+#[derive(Parser, Debug)]
+struct Args {
+    /// The assets folder to pack
+    #[clap(value_parser, default_value = "assets")]
+    asset_folder: PathBuf,
+
+    #[clap(short, long, value_parser, default_value = "assets.pak")]
+    /// The archive to write, which must not be inside the assets folder
+    output: PathBuf,
+}
+
+fn main() {
+    let cli = Args::parse();
+
+    if let (Ok(asset_folder), Some(output_folder)) = (
+        cli.asset_folder.canonicalize(),
+        cli.output
+            .parent()
+            .and_then(|parent| parent.canonicalize().ok()),
+    ) {
+        assert!(
+            !output_folder.starts_with(asset_folder),
+            "the archive must not be written inside the assets folder"
+        );
+    }
+
+    if let Err(err) = create_pak_archive(&cli.asset_folder, &cli.output) {
+        eprintln!(
+            "failed to pack {:?} in {:?}: {}",
+            cli.asset_folder, cli.output, err
+        );
+        std::process::exit(1);
+    }
+    println!("packed {:?} in {:?}", cli.asset_folder, cli.output);
+}